@@ -0,0 +1,636 @@
+use crate::matrix::Matrix;
+use crate::vector::Vector;
+use crate::{mat_vec_mul, vec_mat_mul, StrError};
+use std::cell::RefCell;
+
+/// Defines the callback `(v, u)` computing `v := A·u` (or `v := Aᵀ·u`)
+type OperatorCallback<'a> = Box<dyn Fn(&mut Vector, &Vector) -> Result<(), StrError> + 'a>;
+
+/// Defines a linear operator, i.e., a (possibly matrix-free) representation of a matrix `A`
+///
+/// A linear operator only needs to compute the products with a vector:
+///
+/// ```text
+///  v  :=  A  ⋅  u          v  :=  Aᵀ  ⋅  u
+/// (m)   (m,n)  (n)        (n)   (n,m)   (m)
+/// ```
+///
+/// Thus, stencils, Kronecker products, or Jacobian-vector products can be used by iterative solvers
+/// without assembling the matrix. The trait is implemented for [Matrix] and for the sparse matrices
+/// of `russell_sparse`; see also [OperatorFn], [OperatorScaled], [OperatorSum], [OperatorProduct],
+/// and [OperatorBlock].
+///
+/// # Examples
+///
+/// ```
+/// use russell_lab::{vec_approx_eq, LinearOperator, Matrix, Vector, StrError};
+///
+/// fn main() -> Result<(), StrError> {
+///     let a = Matrix::from(&[
+///         [1.0, 2.0, 3.0],
+///         [4.0, 5.0, 6.0],
+///     ]);
+///     assert_eq!(a.dims(), (2, 3));
+///     let u = Vector::from(&[1.0, 1.0, 1.0]);
+///     let mut v = Vector::new(2);
+///     a.apply(&mut v, &u)?;
+///     vec_approx_eq(&v, &[6.0, 15.0], 1e-15);
+///     let w = Vector::from(&[1.0, 1.0]);
+///     let mut z = Vector::new(3);
+///     a.apply_transpose(&mut z, &w)?;
+///     vec_approx_eq(&z, &[5.0, 7.0, 9.0], 1e-15);
+///     Ok(())
+/// }
+/// ```
+pub trait LinearOperator {
+    /// Returns the dimensions `(nrow, ncol)` of the operator
+    fn dims(&self) -> (usize, usize);
+
+    /// Computes `v := A·u`
+    ///
+    /// `u` has dimension equal to `ncol` and `v` has dimension equal to `nrow`
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError>;
+
+    /// Computes `v := Aᵀ·u`
+    ///
+    /// `u` has dimension equal to `nrow` and `v` has dimension equal to `ncol`
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError>;
+}
+
+impl LinearOperator for Matrix {
+    fn dims(&self) -> (usize, usize) {
+        (self.nrow(), self.ncol())
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        mat_vec_mul(v, 1.0, self, u)
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        vec_mat_mul(v, 1.0, u, self)
+    }
+}
+
+impl<T: LinearOperator + ?Sized> LinearOperator for &T {
+    fn dims(&self) -> (usize, usize) {
+        (**self).dims()
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        (**self).apply(v, u)
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        (**self).apply_transpose(v, u)
+    }
+}
+
+impl<T: LinearOperator + ?Sized> LinearOperator for Box<T> {
+    fn dims(&self) -> (usize, usize) {
+        (**self).dims()
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        (**self).apply(v, u)
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        (**self).apply_transpose(v, u)
+    }
+}
+
+/// Checks the dimensions of the vectors in `v := A·u` (or `v := Aᵀ·u` if `transpose`)
+fn check_dims(op: &dyn LinearOperator, v: &Vector, u: &Vector, transpose: bool) -> Result<(), StrError> {
+    let (nrow, ncol) = op.dims();
+    let (m, n) = if transpose { (ncol, nrow) } else { (nrow, ncol) };
+    if v.dim() != m || u.dim() != n {
+        return Err("operator and vectors are incompatible");
+    }
+    Ok(())
+}
+
+/// Implements a linear operator given by functions (matrix-free operator)
+///
+/// # Examples
+///
+/// ```
+/// use russell_lab::{vec_approx_eq, LinearOperator, OperatorFn, Vector, StrError};
+///
+/// fn main() -> Result<(), StrError> {
+///     // tridiagonal (symmetric) matrix with 2 on the diagonal and -1 off the diagonal
+///     let n = 4;
+///     let stencil = |v: &mut Vector, u: &Vector| {
+///         for i in 0..n {
+///             v[i] = 2.0 * u[i];
+///             if i > 0 {
+///                 v[i] -= u[i - 1];
+///             }
+///             if i < n - 1 {
+///                 v[i] -= u[i + 1];
+///             }
+///         }
+///         Ok(())
+///     };
+///     let mut op = OperatorFn::new(n, n, stencil);
+///     op.set_transpose(stencil);
+///     let u = Vector::from(&[1.0, 2.0, 3.0, 4.0]);
+///     let mut v = Vector::new(n);
+///     op.apply(&mut v, &u)?;
+///     vec_approx_eq(&v, &[0.0, 0.0, 0.0, 5.0], 1e-15);
+///     Ok(())
+/// }
+/// ```
+pub struct OperatorFn<'a> {
+    /// Holds the number of rows
+    nrow: usize,
+
+    /// Holds the number of columns
+    ncol: usize,
+
+    /// Computes v := A·u
+    apply: OperatorCallback<'a>,
+
+    /// Computes v := Aᵀ·u
+    apply_transpose: Option<OperatorCallback<'a>>,
+}
+
+impl<'a> OperatorFn<'a> {
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `nrow` -- number of rows
+    /// * `ncol` -- number of columns
+    /// * `apply` -- a function `(v, u)` computing `v := A·u`
+    pub fn new(nrow: usize, ncol: usize, apply: impl Fn(&mut Vector, &Vector) -> Result<(), StrError> + 'a) -> Self {
+        OperatorFn {
+            nrow,
+            ncol,
+            apply: Box::new(apply),
+            apply_transpose: None,
+        }
+    }
+
+    /// Sets the function `(v, u)` computing `v := Aᵀ·u`
+    ///
+    /// **Note:** Otherwise, `apply_transpose` returns an error.
+    pub fn set_transpose(&mut self, apply_transpose: impl Fn(&mut Vector, &Vector) -> Result<(), StrError> + 'a) {
+        self.apply_transpose = Some(Box::new(apply_transpose));
+    }
+}
+
+impl<'a> LinearOperator for OperatorFn<'a> {
+    fn dims(&self) -> (usize, usize) {
+        (self.nrow, self.ncol)
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        check_dims(self, v, u, false)?;
+        (self.apply)(v, u)
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        check_dims(self, v, u, true)?;
+        match &self.apply_transpose {
+            Some(f) => f(v, u),
+            None => Err("the transpose of the operator is not available"),
+        }
+    }
+}
+
+/// Implements the scaled operator `α·A`
+pub struct OperatorScaled<A: LinearOperator> {
+    alpha: f64,
+    a: A,
+}
+
+impl<A: LinearOperator> OperatorScaled<A> {
+    /// Allocates a new instance representing `α·A`
+    pub fn new(alpha: f64, a: A) -> Self {
+        OperatorScaled { alpha, a }
+    }
+}
+
+impl<A: LinearOperator> LinearOperator for OperatorScaled<A> {
+    fn dims(&self) -> (usize, usize) {
+        self.a.dims()
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.a.apply(v, u)?;
+        v.map(|x| self.alpha * x);
+        Ok(())
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.a.apply_transpose(v, u)?;
+        v.map(|x| self.alpha * x);
+        Ok(())
+    }
+}
+
+/// Implements the linear combination `α·A + β·B`
+///
+/// # Examples
+///
+/// ```
+/// use russell_lab::{vec_approx_eq, LinearOperator, Matrix, OperatorSum, Vector, StrError};
+///
+/// fn main() -> Result<(), StrError> {
+///     let a = Matrix::from(&[[1.0, 2.0], [3.0, 4.0]]);
+///     let b = Matrix::from(&[[1.0, 0.0], [0.0, 1.0]]);
+///     let op = OperatorSum::new(2.0, &a, -1.0, &b)?;
+///     let u = Vector::from(&[1.0, 1.0]);
+///     let mut v = Vector::new(2);
+///     op.apply(&mut v, &u)?;
+///     vec_approx_eq(&v, &[5.0, 13.0], 1e-15);
+///     Ok(())
+/// }
+/// ```
+pub struct OperatorSum<A: LinearOperator, B: LinearOperator> {
+    alpha: f64,
+    a: A,
+    beta: f64,
+    b: B,
+    work_apply: RefCell<Vector>,
+    work_transpose: RefCell<Vector>,
+}
+
+impl<A: LinearOperator, B: LinearOperator> OperatorSum<A, B> {
+    /// Allocates a new instance representing `α·A + β·B`
+    ///
+    /// **Note:** `A` and `B` must have the same dimensions.
+    pub fn new(alpha: f64, a: A, beta: f64, b: B) -> Result<Self, StrError> {
+        if a.dims() != b.dims() {
+            return Err("the operators must have the same dimensions");
+        }
+        let (nrow, ncol) = a.dims();
+        Ok(OperatorSum {
+            alpha,
+            a,
+            beta,
+            b,
+            work_apply: RefCell::new(Vector::new(nrow)),
+            work_transpose: RefCell::new(Vector::new(ncol)),
+        })
+    }
+}
+
+impl<A: LinearOperator, B: LinearOperator> LinearOperator for OperatorSum<A, B> {
+    fn dims(&self) -> (usize, usize) {
+        self.a.dims()
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        check_dims(self, v, u, false)?;
+        let mut w = self.work_apply.borrow_mut();
+        self.a.apply(v, u)?;
+        self.b.apply(&mut w, u)?;
+        for i in 0..v.dim() {
+            v[i] = self.alpha * v[i] + self.beta * w[i];
+        }
+        Ok(())
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        check_dims(self, v, u, true)?;
+        let mut w = self.work_transpose.borrow_mut();
+        self.a.apply_transpose(v, u)?;
+        self.b.apply_transpose(&mut w, u)?;
+        for i in 0..v.dim() {
+            v[i] = self.alpha * v[i] + self.beta * w[i];
+        }
+        Ok(())
+    }
+}
+
+/// Implements the product `A·B`
+///
+/// ```text
+/// v := A·(B·u)     and     v := Bᵀ·(Aᵀ·u)
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use russell_lab::{vec_approx_eq, LinearOperator, Matrix, OperatorProduct, Vector, StrError};
+///
+/// fn main() -> Result<(), StrError> {
+///     let a = Matrix::from(&[[1.0, 2.0, 3.0]]);
+///     let b = Matrix::from(&[[1.0], [1.0], [1.0]]);
+///     let op = OperatorProduct::new(&a, &b)?;
+///     assert_eq!(op.dims(), (1, 1));
+///     let u = Vector::from(&[2.0]);
+///     let mut v = Vector::new(1);
+///     op.apply(&mut v, &u)?;
+///     vec_approx_eq(&v, &[12.0], 1e-15);
+///     Ok(())
+/// }
+/// ```
+pub struct OperatorProduct<A: LinearOperator, B: LinearOperator> {
+    a: A,
+    b: B,
+    workspace: RefCell<Vector>,
+}
+
+impl<A: LinearOperator, B: LinearOperator> OperatorProduct<A, B> {
+    /// Allocates a new instance representing `A·B`
+    ///
+    /// **Note:** The number of columns of `A` must equal the number of rows of `B`.
+    pub fn new(a: A, b: B) -> Result<Self, StrError> {
+        if a.dims().1 != b.dims().0 {
+            return Err("the number of columns of A must equal the number of rows of B");
+        }
+        let dim = a.dims().1;
+        Ok(OperatorProduct {
+            a,
+            b,
+            workspace: RefCell::new(Vector::new(dim)),
+        })
+    }
+}
+
+impl<A: LinearOperator, B: LinearOperator> LinearOperator for OperatorProduct<A, B> {
+    fn dims(&self) -> (usize, usize) {
+        (self.a.dims().0, self.b.dims().1)
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        check_dims(self, v, u, false)?;
+        let mut w = self.workspace.borrow_mut();
+        self.b.apply(&mut w, u)?;
+        self.a.apply(v, &w)
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        check_dims(self, v, u, true)?;
+        let mut w = self.workspace.borrow_mut();
+        self.a.apply_transpose(&mut w, u)?;
+        self.b.apply_transpose(v, &w)
+    }
+}
+
+/// Implements a block operator
+///
+/// ```text
+///     ┌                 ┐
+///     │ A₀₀  A₀₁  ⋯     │
+/// A = │ A₁₀  A₁₁  ⋯     │
+///     │  ⋮    ⋮    ⋱    │
+///     └                 ┘
+/// ```
+///
+/// The blocks that are not set are zero.
+///
+/// # Examples
+///
+/// ```
+/// use russell_lab::{vec_approx_eq, LinearOperator, Matrix, OperatorBlock, Vector, StrError};
+///
+/// fn main() -> Result<(), StrError> {
+///     // saddle-point operator ┌ K  Bᵀ ┐
+///     //                       └ B  0  ┘
+///     let kk = Matrix::from(&[[2.0, 0.0], [0.0, 3.0]]);
+///     let bb = Matrix::from(&[[1.0, 1.0]]);
+///     let bbt = Matrix::from(&[[1.0], [1.0]]);
+///     let mut op = OperatorBlock::new(&[2, 1], &[2, 1]);
+///     op.set(0, 0, &kk)?;
+///     op.set(0, 1, &bbt)?;
+///     op.set(1, 0, &bb)?;
+///     assert_eq!(op.dims(), (3, 3));
+///     let u = Vector::from(&[1.0, 2.0, 3.0]);
+///     let mut v = Vector::new(3);
+///     op.apply(&mut v, &u)?;
+///     vec_approx_eq(&v, &[5.0, 9.0, 3.0], 1e-15);
+///     Ok(())
+/// }
+/// ```
+pub struct OperatorBlock<'a> {
+    /// Holds the offsets of the block rows (len = number of block rows + 1)
+    row_offsets: Vec<usize>,
+
+    /// Holds the offsets of the block columns (len = number of block columns + 1)
+    col_offsets: Vec<usize>,
+
+    /// Holds the blocks (row-major; None means a zero block)
+    blocks: Vec<Option<Box<dyn LinearOperator + 'a>>>,
+}
+
+impl<'a> OperatorBlock<'a> {
+    /// Allocates a new instance with zero blocks
+    ///
+    /// # Input
+    ///
+    /// * `row_sizes` -- the number of rows of each block row
+    /// * `col_sizes` -- the number of columns of each block column
+    pub fn new(row_sizes: &[usize], col_sizes: &[usize]) -> Self {
+        let offsets = |sizes: &[usize]| {
+            let mut res = vec![0; sizes.len() + 1];
+            for k in 0..sizes.len() {
+                res[k + 1] = res[k] + sizes[k];
+            }
+            res
+        };
+        OperatorBlock {
+            row_offsets: offsets(row_sizes),
+            col_offsets: offsets(col_sizes),
+            blocks: (0..row_sizes.len() * col_sizes.len()).map(|_| None).collect(),
+        }
+    }
+
+    /// Sets the (i,j) block
+    ///
+    /// **Note:** The dimensions of the operator must match the sizes of the i-th block row and j-th block column.
+    pub fn set(&mut self, i: usize, j: usize, op: impl LinearOperator + 'a) -> Result<(), StrError> {
+        let nbr = self.row_offsets.len() - 1;
+        let nbc = self.col_offsets.len() - 1;
+        if i >= nbr || j >= nbc {
+            return Err("the block index is out of bounds");
+        }
+        let m = self.row_offsets[i + 1] - self.row_offsets[i];
+        let n = self.col_offsets[j + 1] - self.col_offsets[j];
+        if op.dims() != (m, n) {
+            return Err("the dimensions of the operator are incompatible with the block");
+        }
+        self.blocks[i * nbc + j] = Some(Box::new(op));
+        Ok(())
+    }
+
+    /// Computes v := A·u or v := Aᵀ·u
+    fn calc(&self, v: &mut Vector, u: &Vector, transpose: bool) -> Result<(), StrError> {
+        check_dims(self, v, u, transpose)?;
+        let nbc = self.col_offsets.len() - 1;
+        let (out_offsets, in_offsets) = if transpose {
+            (&self.col_offsets, &self.row_offsets)
+        } else {
+            (&self.row_offsets, &self.col_offsets)
+        };
+        v.fill(0.0);
+        for (k, block) in self.blocks.iter().enumerate() {
+            if let Some(op) = block {
+                let (bi, bj) = (k / nbc, k % nbc);
+                let (bo, bin) = if transpose { (bj, bi) } else { (bi, bj) };
+                let (o0, o1) = (out_offsets[bo], out_offsets[bo + 1]);
+                let (i0, i1) = (in_offsets[bin], in_offsets[bin + 1]);
+                let uu = Vector::from(&&u.as_data()[i0..i1]);
+                let mut vv = Vector::new(o1 - o0);
+                if transpose {
+                    op.apply_transpose(&mut vv, &uu)?;
+                } else {
+                    op.apply(&mut vv, &uu)?;
+                }
+                for r in 0..(o1 - o0) {
+                    v[o0 + r] += vv[r];
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> LinearOperator for OperatorBlock<'a> {
+    fn dims(&self) -> (usize, usize) {
+        (
+            self.row_offsets[self.row_offsets.len() - 1],
+            self.col_offsets[self.col_offsets.len() - 1],
+        )
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.calc(v, u, false)
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.calc(v, u, true)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{LinearOperator, OperatorBlock, OperatorFn, OperatorProduct, OperatorScaled, OperatorSum};
+    use crate::{vec_approx_eq, Matrix, Vector};
+
+    #[test]
+    fn matrix_operator_works() {
+        let a = Matrix::from(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let op: &dyn LinearOperator = &a;
+        assert_eq!(op.dims(), (2, 3));
+        let u = Vector::from(&[1.0, 0.0, -1.0]);
+        let mut v = Vector::new(2);
+        op.apply(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[-2.0, -2.0], 1e-15);
+        let w = Vector::from(&[1.0, -1.0]);
+        let mut z = Vector::new(3);
+        op.apply_transpose(&mut z, &w).unwrap();
+        vec_approx_eq(&z, &[-3.0, -3.0, -3.0], 1e-15);
+        let boxed: Box<dyn LinearOperator> = Box::new(a);
+        assert_eq!(boxed.dims(), (2, 3));
+    }
+
+    #[test]
+    fn operator_fn_works() {
+        let mut op = OperatorFn::new(2, 1, |v, u| {
+            v[0] = u[0];
+            v[1] = 2.0 * u[0];
+            Ok(())
+        });
+        let u = Vector::from(&[3.0]);
+        let mut v = Vector::new(2);
+        assert_eq!(
+            op.apply(&mut v, &Vector::new(2)).err(),
+            Some("operator and vectors are incompatible")
+        );
+        op.apply(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[3.0, 6.0], 1e-15);
+        let mut z = Vector::new(1);
+        assert_eq!(
+            op.apply_transpose(&mut z, &v).err(),
+            Some("the transpose of the operator is not available")
+        );
+        op.set_transpose(|v, u| {
+            v[0] = u[0] + 2.0 * u[1];
+            Ok(())
+        });
+        op.apply_transpose(&mut z, &v).unwrap();
+        vec_approx_eq(&z, &[15.0], 1e-15);
+    }
+
+    #[test]
+    fn scaled_sum_and_product_work() {
+        let a = Matrix::from(&[[1.0, 2.0], [3.0, 4.0]]);
+        let b = Matrix::from(&[[0.0, 1.0], [1.0, 0.0]]);
+        let c = Matrix::from(&[[1.0, 2.0, 3.0]]);
+        let u = Vector::from(&[1.0, -1.0]);
+        let mut v = Vector::new(2);
+
+        // scaled
+        let op = OperatorScaled::new(-2.0, &a);
+        op.apply(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[2.0, 2.0], 1e-15);
+        op.apply_transpose(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[4.0, 4.0], 1e-15);
+
+        // sum
+        assert_eq!(
+            OperatorSum::new(1.0, &a, 1.0, &c).err(),
+            Some("the operators must have the same dimensions")
+        );
+        let op = OperatorSum::new(2.0, &a, 3.0, &b).unwrap();
+        op.apply(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[-5.0, 1.0], 1e-15);
+        op.apply_transpose(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[-7.0, -1.0], 1e-15);
+
+        // product
+        assert_eq!(
+            OperatorProduct::new(&c, &c).err(),
+            Some("the number of columns of A must equal the number of rows of B")
+        );
+        let op = OperatorProduct::new(&a, &b).unwrap();
+        op.apply(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[1.0, 1.0], 1e-15);
+        op.apply_transpose(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[-2.0, -2.0], 1e-15);
+
+        // nested
+        let ab = OperatorProduct::new(&a, &b).unwrap();
+        let op = OperatorSum::new(1.0, ab, 1.0, OperatorScaled::new(2.0, &b)).unwrap();
+        op.apply(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[-1.0, 3.0], 1e-15);
+    }
+
+    #[test]
+    fn block_operator_works() {
+        let kk = Matrix::from(&[[2.0, 0.0], [0.0, 3.0]]);
+        let bb = Matrix::from(&[[1.0, 1.0]]);
+        let bbt = Matrix::from(&[[1.0], [1.0]]);
+        let mut op = OperatorBlock::new(&[2, 1], &[2, 1]);
+        assert_eq!(op.set(2, 0, &kk).err(), Some("the block index is out of bounds"));
+        assert_eq!(
+            op.set(0, 1, &kk).err(),
+            Some("the dimensions of the operator are incompatible with the block")
+        );
+        op.set(0, 0, &kk).unwrap();
+        op.set(0, 1, &bbt).unwrap();
+        op.set(1, 0, &bb).unwrap();
+        let u = Vector::from(&[1.0, 2.0, 3.0]);
+        let mut v = Vector::new(3);
+        op.apply(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[5.0, 9.0, 3.0], 1e-15);
+        op.apply_transpose(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[5.0, 9.0, 3.0], 1e-15);
+        // rectangular
+        let mut op = OperatorBlock::new(&[1], &[2, 1]);
+        op.set(0, 1, Matrix::from(&[[4.0]])).unwrap();
+        assert_eq!(op.dims(), (1, 3));
+        let mut v = Vector::new(1);
+        op.apply(&mut v, &u).unwrap();
+        vec_approx_eq(&v, &[12.0], 1e-15);
+        let w = Vector::from(&[2.0]);
+        let mut z = Vector::new(3);
+        op.apply_transpose(&mut z, &w).unwrap();
+        vec_approx_eq(&z, &[0.0, 0.0, 8.0], 1e-15);
+    }
+}
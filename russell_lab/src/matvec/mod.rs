@@ -3,6 +3,7 @@
 mod complex_mat_vec_mul;
 mod complex_solve_lin_sys;
 mod complex_vec_mat_mul;
+mod linear_operator;
 mod mat_sum_cols;
 mod mat_sum_rows;
 mod mat_vec_mul;
@@ -15,6 +16,7 @@ mod vec_outer_update;
 pub use complex_mat_vec_mul::*;
 pub use complex_solve_lin_sys::*;
 pub use complex_vec_mat_mul::*;
+pub use linear_operator::*;
 pub use mat_sum_cols::*;
 pub use mat_sum_rows::*;
 pub use mat_vec_mul::*;
@@ -202,6 +202,8 @@ mod ode_solver_trait;
 mod output;
 mod params;
 mod pde_discrete_laplacian_2d;
+mod pde_finite_volume;
+mod pde_spectral_collocation;
 pub mod prelude;
 mod radau5;
 mod samples;
@@ -221,6 +223,8 @@ use ode_solver_trait::*;
 pub use output::*;
 pub use params::*;
 pub use pde_discrete_laplacian_2d::*;
+pub use pde_finite_volume::*;
+pub use pde_spectral_collocation::*;
 use radau5::*;
 pub use samples::*;
 pub use stats::*;
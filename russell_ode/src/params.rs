@@ -28,6 +28,8 @@ pub struct ParamsNewton {
     pub use_numerical_jacobian: bool,
 
     /// Linear solver kind
+    ///
+    /// **Note:** The default is [Genie::Umfpack] if the `with_suitesparse` feature is enabled; otherwise, [Genie::Native]
     pub genie: Genie,
 
     /// Configurations for sparse linear solver
@@ -39,12 +41,14 @@ pub struct ParamsNewton {
     ///
     /// Will write the following files:
     ///
-    /// * `/tmp/russell_ode/jacobian.{mtx,smat}` -- Jacobian matrix
-    /// * `/tmp/russell_ode/kk_real.{mtx,smat}` -- Radau5 coefficient matrix of the real system
-    /// * `/tmp/russell_ode/kk_comp.{mtx,smat}` -- Radau5 coefficient matrix of the complex system
+    /// * `/tmp/russell_ode/jacobian.{mtx,smat,svg}` -- Jacobian matrix
+    /// * `/tmp/russell_ode/kk_real.{mtx,smat,svg}` -- Radau5 coefficient matrix of the real system
+    /// * `/tmp/russell_ode/kk_comp.{mtx,smat,svg}` -- Radau5 coefficient matrix of the complex system
     ///
-    /// where `mtx` is the extension for the MatrixMarket files and `smat` is the extension
-    /// for the vismatrix files (for visualization).
+    /// where `mtx` is the extension for the MatrixMarket files, `smat` is the extension
+    /// for the vismatrix files (for visualization), and `svg` is the extension for the
+    /// sparsity pattern plots (spy plots; see `russell_sparse::SpyParams`), which can be
+    /// viewed in a web browser (e.g., as CI artifacts).
     ///
     /// # References
     ///
@@ -247,7 +251,11 @@ impl ParamsNewton {
         ParamsNewton {
             n_iteration_max: 7, // line 436 of radau5.f
             use_numerical_jacobian: false,
-            genie: Genie::Umfpack,
+            genie: if cfg!(feature = "with_suitesparse") {
+                Genie::Umfpack
+            } else {
+                Genie::Native
+            },
             lin_sol_params: None,
             write_matrix_after_nstep_and_stop: None,
         }
@@ -0,0 +1,705 @@
+use crate::{Side, StrError, System};
+use russell_lab::Vector;
+use std::sync::Arc;
+
+/// Specifies the slope limiter for the MUSCL reconstruction
+///
+/// The limited slope is a function of the backward and forward differences `a = uᵢ - uᵢ₋₁` and `b = uᵢ₊₁ - uᵢ`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SlopeLimiter {
+    /// No reconstruction (piecewise constant; first-order accurate)
+    FirstOrder,
+
+    /// Minmod limiter (the most diffusive)
+    Minmod,
+
+    /// Van Leer (harmonic) limiter
+    VanLeer,
+
+    /// Superbee limiter (the most compressive)
+    Superbee,
+}
+
+impl SlopeLimiter {
+    /// Calculates the limited slope given the backward (a) and forward (b) differences
+    ///
+    /// ```text
+    /// FirstOrder: 0
+    /// Minmod:     sign(a) min(|a|, |b|)                         if a b > 0 else 0
+    /// VanLeer:    2 a b / (a + b)                               if a b > 0 else 0
+    /// Superbee:   sign(a) max(min(2|a|, |b|), min(|a|, 2|b|))   if a b > 0 else 0
+    /// ```
+    pub fn slope(&self, a: f64, b: f64) -> f64 {
+        if a * b <= 0.0 {
+            return 0.0;
+        }
+        match self {
+            SlopeLimiter::FirstOrder => 0.0,
+            SlopeLimiter::Minmod => f64::signum(a) * f64::min(f64::abs(a), f64::abs(b)),
+            SlopeLimiter::VanLeer => 2.0 * a * b / (a + b),
+            SlopeLimiter::Superbee => {
+                let (aa, bb) = (f64::abs(a), f64::abs(b));
+                f64::signum(a) * f64::max(f64::min(2.0 * aa, bb), f64::min(aa, 2.0 * bb))
+            }
+        }
+    }
+}
+
+/// Specifies the approximate Riemann solver to compute the numerical fluxes
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RiemannSolver {
+    /// Rusanov (local Lax-Friedrichs) flux
+    ///
+    /// ```text
+    /// F = ½ (f(uL) + f(uR)) - ½ s (uR - uL)    with    s = max(|sL|, |sR|)
+    /// ```
+    Rusanov,
+
+    /// Harten-Lax-van Leer (HLL) flux
+    ///
+    /// ```text
+    ///     ⎧ f(uL)                                        if sL ≥ 0
+    /// F = ⎨ f(uR)                                        if sR ≤ 0
+    ///     ⎩ (sR f(uL) - sL f(uR) + sL sR (uR - uL)) / (sR - sL)   otherwise
+    /// ```
+    Hll,
+}
+
+/// Implements the finite volume method (FVM) semi-discretization of conservation laws in 1D and 2D
+///
+/// The system of conservation laws is:
+///
+/// ```text
+/// ∂u   ∂f(u)   ∂g(u)
+/// —— + ————— + ————— = s(t, x, y, u)
+/// ∂t    ∂x      ∂y
+/// ```
+///
+/// where `u` is the vector of conserved variables with `ncomp` components, `f` and `g` are the
+/// fluxes along x and y, respectively, and `s` is an optional source term.
+///
+/// The domain is a rectangle divided into `nx × ny` cells (`ny = 1` in 1D). The cell averages are
+/// "sequentially" mapped onto the vector `U` with the components of each cell stored contiguously:
+///
+/// ```text
+/// uᵢⱼ[c] → Uₖ   with   k = c + m ncomp   and   m = i + j nx
+/// ```
+///
+/// The method of lines yields the following ODE system:
+///
+/// ```text
+/// dUᵢⱼ     Fᵢ₊½ⱼ - Fᵢ₋½ⱼ     Gᵢⱼ₊½ - Gᵢⱼ₋½
+/// ———— = - ————————————— - ————————————— + Sᵢⱼ
+///  dt           Δx              Δy
+/// ```
+///
+/// where `F` and `G` are the numerical fluxes computed by a [RiemannSolver] with the left and
+/// right states obtained from the MUSCL reconstruction with a [SlopeLimiter].
+///
+/// The resulting [System] should be solved by the explicit methods (e.g., [crate::Method::Rk3],
+/// [crate::Method::Rk4], or [crate::Method::DoPri5]). See [PdeFiniteVolume::max_stable_step]
+/// to estimate the stepsize satisfying the CFL condition.
+///
+/// # Remarks
+///
+/// * The boundaries are transmissive (zero-gradient) by default; they may be made periodic instead
+/// * Two ghost cells are implicitly considered on each side by mirroring (transmissive) or wrapping (periodic) the indices
+///
+/// # References
+///
+/// 1. LeVeque RJ (2002) Finite Volume Methods for Hyperbolic Problems, Cambridge University Press, 558p
+/// 2. Toro EF (2009) Riemann Solvers and Numerical Methods for Fluid Dynamics, Third Edition, Springer, 724p
+#[derive(Clone)]
+pub struct PdeFiniteVolume<'a> {
+    ncomp: usize,           // number of components (conserved variables)
+    two_dim: bool,          // indicates a 2D grid
+    xmin: f64,              // min x coordinate
+    ymin: f64,              // min y coordinate
+    nx: usize,              // number of cells along x
+    ny: usize,              // number of cells along y (= 1 in 1D)
+    dx: f64,                // cell size along x
+    dy: f64,                // cell size along y
+    periodic_along_x: bool, // the left/right boundaries are periodic
+    periodic_along_y: bool, // the bottom/top boundaries are periodic
+
+    /// Holds the slope limiter
+    limiter: SlopeLimiter,
+
+    /// Holds the Riemann solver
+    riemann: RiemannSolver,
+
+    /// Calculates the physical flux `function(f, u, direction)` with direction = 0 (x) or 1 (y)
+    flux: Arc<dyn Fn(&mut [f64], &[f64], usize) -> Result<(), StrError> + Send + Sync + 'a>,
+
+    /// Calculates the `(min, max)` wave speeds `function(u, direction)` with direction = 0 (x) or 1 (y)
+    wave_speeds: Arc<dyn Fn(&[f64], usize) -> (f64, f64) + Send + Sync + 'a>,
+
+    /// Calculates the source term `function(s, t, x, y, u)`
+    source: Option<Arc<dyn Fn(&mut [f64], f64, f64, f64, &[f64]) -> Result<(), StrError> + Send + Sync + 'a>>,
+}
+
+impl<'a> PdeFiniteVolume<'a> {
+    /// Allocates a new instance for a 1D domain
+    ///
+    /// # Input
+    ///
+    /// * `ncomp` -- number of components (conserved variables) (≥ 1)
+    /// * `xmin` -- min x coordinate
+    /// * `xmax` -- max x coordinate
+    /// * `nx` -- number of cells along x (≥ 2)
+    /// * `flux` -- a `function(f, u, direction)` to calculate the physical flux `f(u)`;
+    ///   `direction` is always 0 in 1D
+    /// * `wave_speeds` -- a `function(u, direction)` returning the `(min, max)` eigenvalues of the flux Jacobian
+    pub fn new_1d(
+        ncomp: usize,
+        xmin: f64,
+        xmax: f64,
+        nx: usize,
+        flux: impl Fn(&mut [f64], &[f64], usize) -> Result<(), StrError> + Send + Sync + 'a,
+        wave_speeds: impl Fn(&[f64], usize) -> (f64, f64) + Send + Sync + 'a,
+    ) -> Result<Self, StrError> {
+        let mut fvm = PdeFiniteVolume::new_2d(ncomp, xmin, xmax, 0.0, 1.0, nx, 1, flux, wave_speeds)?;
+        fvm.two_dim = false;
+        fvm.dy = 0.0;
+        Ok(fvm)
+    }
+
+    /// Allocates a new instance for a 2D (rectangular) domain
+    ///
+    /// # Input
+    ///
+    /// * `ncomp` -- number of components (conserved variables) (≥ 1)
+    /// * `xmin` -- min x coordinate
+    /// * `xmax` -- max x coordinate
+    /// * `ymin` -- min y coordinate
+    /// * `ymax` -- max y coordinate
+    /// * `nx` -- number of cells along x (≥ 2)
+    /// * `ny` -- number of cells along y (≥ 1)
+    /// * `flux` -- a `function(f, u, direction)` to calculate the physical flux `f(u)` if `direction = 0`
+    ///   or `g(u)` if `direction = 1`
+    /// * `wave_speeds` -- a `function(u, direction)` returning the `(min, max)` eigenvalues of the flux Jacobian
+    ///   along the given direction
+    pub fn new_2d(
+        ncomp: usize,
+        xmin: f64,
+        xmax: f64,
+        ymin: f64,
+        ymax: f64,
+        nx: usize,
+        ny: usize,
+        flux: impl Fn(&mut [f64], &[f64], usize) -> Result<(), StrError> + Send + Sync + 'a,
+        wave_speeds: impl Fn(&[f64], usize) -> (f64, f64) + Send + Sync + 'a,
+    ) -> Result<Self, StrError> {
+        if ncomp < 1 {
+            return Err("ncomp must be ≥ 1");
+        }
+        if nx < 2 {
+            return Err("nx must be ≥ 2");
+        }
+        if ny < 1 {
+            return Err("ny must be ≥ 1");
+        }
+        if xmax <= xmin {
+            return Err("xmax must be greater than xmin");
+        }
+        if ymax <= ymin {
+            return Err("ymax must be greater than ymin");
+        }
+        Ok(PdeFiniteVolume {
+            ncomp,
+            two_dim: true,
+            xmin,
+            ymin,
+            nx,
+            ny,
+            dx: (xmax - xmin) / (nx as f64),
+            dy: (ymax - ymin) / (ny as f64),
+            periodic_along_x: false,
+            periodic_along_y: false,
+            limiter: SlopeLimiter::Minmod,
+            riemann: RiemannSolver::Rusanov,
+            flux: Arc::new(flux),
+            wave_speeds: Arc::new(wave_speeds),
+            source: None,
+        })
+    }
+
+    /// Sets the slope limiter (default is [SlopeLimiter::Minmod])
+    pub fn set_limiter(&mut self, limiter: SlopeLimiter) -> &mut Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Sets the Riemann solver (default is [RiemannSolver::Rusanov])
+    pub fn set_riemann_solver(&mut self, riemann: RiemannSolver) -> &mut Self {
+        self.riemann = riemann;
+        self
+    }
+
+    /// Sets periodic boundary condition
+    ///
+    /// **Note:** It is only necessary to specify one of (Left, Right) or (Bottom, Top).
+    /// In 1D, only Left or Right are available.
+    pub fn set_periodic_boundary_condition(&mut self, side: Side) -> Result<(), StrError> {
+        match side {
+            Side::Left | Side::Right => self.periodic_along_x = true,
+            Side::Bottom | Side::Top => {
+                if !self.two_dim {
+                    return Err("the Bottom and Top sides are not available in 1D");
+                }
+                self.periodic_along_y = true
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the source term
+    ///
+    /// Use `|s, t, x, y, u|` where `s` is the output (with `ncomp` components), `t` is the time,
+    /// `(x, y)` are the coordinates of the cell center, and `u` are the conserved variables of the cell.
+    pub fn set_source(
+        &mut self,
+        callback: impl Fn(&mut [f64], f64, f64, f64, &[f64]) -> Result<(), StrError> + Send + Sync + 'a,
+    ) -> &mut Self {
+        self.source = Some(Arc::new(callback));
+        self
+    }
+
+    /// Calculates the right-hand side of the semi-discrete system
+    ///
+    /// ```text
+    /// dU
+    /// —— = -div(F) + S
+    /// dt
+    /// ```
+    ///
+    /// # Input
+    ///
+    /// * `dudt` -- (output) the rate of the cell averages (with `dim` components)
+    /// * `t` -- the time
+    /// * `u` -- the cell averages (with `dim` components)
+    pub fn calc_rate(&self, dudt: &mut Vector, t: f64, u: &Vector) -> Result<(), StrError> {
+        let dim = self.dim();
+        if dudt.dim() != dim || u.dim() != dim {
+            return Err("the vectors must have dim components");
+        }
+        let nc = self.ncomp;
+        let mut ul = vec![0.0; nc];
+        let mut ur = vec![0.0; nc];
+        let mut fl = vec![0.0; nc];
+        let mut fr = vec![0.0; nc];
+        let mut ff = vec![0.0; nc];
+        dudt.fill(0.0);
+
+        // fluxes along x
+        for j in 0..self.ny {
+            for p in 0..(self.nx + 1) {
+                let k = p as isize;
+                self.reconstruct(&mut ul, u, k - 1, j, 0, 0.5);
+                self.reconstruct(&mut ur, u, k, j, 0, -0.5);
+                self.numerical_flux(&mut ff, &mut fl, &mut fr, &ul, &ur, 0)?;
+                if p > 0 {
+                    let m = (p - 1) + j * self.nx;
+                    for c in 0..nc {
+                        dudt[c + m * nc] -= ff[c] / self.dx;
+                    }
+                }
+                if p < self.nx {
+                    let m = p + j * self.nx;
+                    for c in 0..nc {
+                        dudt[c + m * nc] += ff[c] / self.dx;
+                    }
+                }
+            }
+        }
+
+        // fluxes along y
+        if self.two_dim {
+            for i in 0..self.nx {
+                for q in 0..(self.ny + 1) {
+                    let k = q as isize;
+                    self.reconstruct(&mut ul, u, k - 1, i, 1, 0.5);
+                    self.reconstruct(&mut ur, u, k, i, 1, -0.5);
+                    self.numerical_flux(&mut ff, &mut fl, &mut fr, &ul, &ur, 1)?;
+                    if q > 0 {
+                        let m = i + (q - 1) * self.nx;
+                        for c in 0..nc {
+                            dudt[c + m * nc] -= ff[c] / self.dy;
+                        }
+                    }
+                    if q < self.ny {
+                        let m = i + q * self.nx;
+                        for c in 0..nc {
+                            dudt[c + m * nc] += ff[c] / self.dy;
+                        }
+                    }
+                }
+            }
+        }
+
+        // source term
+        if let Some(source) = self.source.as_ref() {
+            let mut ss = vec![0.0; nc];
+            for m in 0..self.ncell() {
+                let (x, y) = self.center(m);
+                let start = m * nc;
+                (source)(&mut ss, t, x, y, &u.as_data()[start..(start + nc)])?;
+                for c in 0..nc {
+                    dudt[c + start] += ss[c];
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the ODE system corresponding to the method of lines
+    ///
+    /// **Note:** The Jacobian function is not set; thus, this system is meant for the explicit methods.
+    ///
+    /// # Generics
+    ///
+    /// * `A` -- generic argument to assist in the f(x,y) and Jacobian functions.
+    ///   It may be simply [crate::NoArgs] indicating that no arguments are needed.
+    pub fn get_system<A>(&self) -> System<'a, A> {
+        let fvm = self.clone();
+        System::new(
+            self.dim(),
+            move |dudt: &mut Vector, t: f64, u: &Vector, _args: &mut A| fvm.calc_rate(dudt, t, u),
+        )
+    }
+
+    /// Estimates the maximum stepsize satisfying the CFL condition
+    ///
+    /// ```text
+    ///                      CFL
+    /// Δt = ——————————————————————————————
+    ///      max(|s|ₓ) / Δx + max(|s|ᵧ) / Δy
+    /// ```
+    ///
+    /// # Input
+    ///
+    /// * `u` -- the cell averages (with `dim` components)
+    /// * `cfl` -- the Courant number (e.g., 0.4)
+    pub fn max_stable_step(&self, u: &Vector, cfl: f64) -> Result<f64, StrError> {
+        if u.dim() != self.dim() {
+            return Err("the vector must have dim components");
+        }
+        let nc = self.ncomp;
+        let mut sx: f64 = 0.0;
+        let mut sy: f64 = 0.0;
+        for m in 0..self.ncell() {
+            let um = &u.as_data()[(m * nc)..((m + 1) * nc)];
+            let (smin, smax) = (self.wave_speeds)(um, 0);
+            sx = f64::max(sx, f64::max(f64::abs(smin), f64::abs(smax)));
+            if self.two_dim {
+                let (smin, smax) = (self.wave_speeds)(um, 1);
+                sy = f64::max(sy, f64::max(f64::abs(smin), f64::abs(smax)));
+            }
+        }
+        let den = if self.two_dim {
+            sx / self.dx + sy / self.dy
+        } else {
+            sx / self.dx
+        };
+        if den == 0.0 {
+            return Err("the wave speeds must not be all zero");
+        }
+        Ok(cfl / den)
+    }
+
+    /// Executes a loop over the cells
+    ///
+    /// # Input
+    ///
+    /// * `callback` -- a function of `(m, x, y)` where `m` is the sequential cell number,
+    ///   and `(x, y)` are the Cartesian coordinates of the cell center. In 1D, `y = 0`.
+    ///
+    /// Note that the components of the cell `m` are stored in `U[c + m ncomp]`.
+    pub fn loop_over_cells<F>(&self, mut callback: F)
+    where
+        F: FnMut(usize, f64, f64),
+    {
+        for m in 0..self.ncell() {
+            let (x, y) = self.center(m);
+            callback(m, x, y)
+        }
+    }
+
+    /// Returns the dimension of the ODE system
+    ///
+    /// ```text
+    /// dim = ncomp × nx × ny
+    /// ```
+    pub fn dim(&self) -> usize {
+        self.ncomp * self.nx * self.ny
+    }
+
+    /// Returns the number of cells
+    pub fn ncell(&self) -> usize {
+        self.nx * self.ny
+    }
+
+    /// Returns the coordinates of the center of cell m
+    fn center(&self, m: usize) -> (f64, f64) {
+        let i = m % self.nx;
+        let j = m / self.nx;
+        let x = self.xmin + (i as f64 + 0.5) * self.dx;
+        let y = if self.two_dim {
+            self.ymin + (j as f64 + 0.5) * self.dy
+        } else {
+            0.0
+        };
+        (x, y)
+    }
+
+    /// Returns the cell number given the (possibly ghost) index k along a direction and the fixed index along the other
+    fn cell(&self, k: isize, fixed: usize, direction: usize) -> usize {
+        let (n, periodic) = if direction == 0 {
+            (self.nx as isize, self.periodic_along_x)
+        } else {
+            (self.ny as isize, self.periodic_along_y)
+        };
+        let kk = if periodic {
+            k.rem_euclid(n) as usize
+        } else {
+            k.clamp(0, n - 1) as usize
+        };
+        if direction == 0 {
+            kk + fixed * self.nx
+        } else {
+            fixed + kk * self.nx
+        }
+    }
+
+    /// Calculates the reconstructed state at the face of cell k
+    ///
+    /// `side` is 0.5 for the right face and -0.5 for the left face
+    fn reconstruct(&self, res: &mut [f64], u: &Vector, k: isize, fixed: usize, direction: usize, side: f64) {
+        let nc = self.ncomp;
+        let m = self.cell(k, fixed, direction);
+        if self.limiter == SlopeLimiter::FirstOrder {
+            res.copy_from_slice(&u.as_data()[(m * nc)..((m + 1) * nc)]);
+            return;
+        }
+        let prev = self.cell(k - 1, fixed, direction);
+        let next = self.cell(k + 1, fixed, direction);
+        for c in 0..nc {
+            let uc = u[c + m * nc];
+            let a = uc - u[c + prev * nc];
+            let b = u[c + next * nc] - uc;
+            res[c] = uc + side * self.limiter.slope(a, b);
+        }
+    }
+
+    /// Calculates the numerical flux given the left and right states
+    fn numerical_flux(
+        &self,
+        ff: &mut [f64],
+        fl: &mut [f64],
+        fr: &mut [f64],
+        ul: &[f64],
+        ur: &[f64],
+        direction: usize,
+    ) -> Result<(), StrError> {
+        (self.flux)(fl, ul, direction)?;
+        (self.flux)(fr, ur, direction)?;
+        let (sl_min, sl_max) = (self.wave_speeds)(ul, direction);
+        let (sr_min, sr_max) = (self.wave_speeds)(ur, direction);
+        match self.riemann {
+            RiemannSolver::Rusanov => {
+                let s = f64::max(
+                    f64::max(f64::abs(sl_min), f64::abs(sl_max)),
+                    f64::max(f64::abs(sr_min), f64::abs(sr_max)),
+                );
+                for c in 0..self.ncomp {
+                    ff[c] = 0.5 * (fl[c] + fr[c]) - 0.5 * s * (ur[c] - ul[c]);
+                }
+            }
+            RiemannSolver::Hll => {
+                let sl = f64::min(sl_min, sr_min);
+                let sr = f64::max(sl_max, sr_max);
+                if sl >= 0.0 {
+                    ff.copy_from_slice(fl);
+                } else if sr <= 0.0 {
+                    ff.copy_from_slice(fr);
+                } else {
+                    for c in 0..self.ncomp {
+                        ff[c] = (sr * fl[c] - sl * fr[c] + sl * sr * (ur[c] - ul[c])) / (sr - sl);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{PdeFiniteVolume, RiemannSolver, SlopeLimiter};
+    use crate::{NoArgs, Side};
+    use russell_lab::{approx_eq, vec_approx_eq, Vector};
+
+    // linear advection with speed a
+    fn advection<'a>(a: f64, nx: usize) -> PdeFiniteVolume<'a> {
+        PdeFiniteVolume::new_1d(
+            1,
+            0.0,
+            1.0,
+            nx,
+            move |f, u, _| {
+                f[0] = a * u[0];
+                Ok(())
+            },
+            move |_, _| (a, a),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn slope_limiters_work() {
+        for limiter in [
+            SlopeLimiter::FirstOrder,
+            SlopeLimiter::Minmod,
+            SlopeLimiter::VanLeer,
+            SlopeLimiter::Superbee,
+        ] {
+            assert_eq!(limiter.slope(1.0, -1.0), 0.0);
+            assert_eq!(limiter.slope(0.0, 2.0), 0.0);
+        }
+        assert_eq!(SlopeLimiter::FirstOrder.slope(1.0, 3.0), 0.0);
+        assert_eq!(SlopeLimiter::Minmod.slope(1.0, 3.0), 1.0);
+        assert_eq!(SlopeLimiter::Minmod.slope(-1.0, -3.0), -1.0);
+        assert_eq!(SlopeLimiter::VanLeer.slope(1.0, 3.0), 1.5);
+        assert_eq!(SlopeLimiter::VanLeer.slope(-1.0, -3.0), -1.5);
+        assert_eq!(SlopeLimiter::Superbee.slope(1.0, 3.0), 2.0);
+        assert_eq!(SlopeLimiter::Superbee.slope(1.0, 1.5), 1.5);
+        assert_eq!(SlopeLimiter::Superbee.slope(-1.0, -1.5), -1.5);
+    }
+
+    #[test]
+    fn new_captures_errors() {
+        let flux = |_: &mut [f64], _: &[f64], _: usize| Ok(());
+        let speeds = |_: &[f64], _: usize| (0.0, 0.0);
+        assert_eq!(
+            PdeFiniteVolume::new_1d(0, 0.0, 1.0, 2, flux, speeds).err(),
+            Some("ncomp must be ≥ 1")
+        );
+        assert_eq!(
+            PdeFiniteVolume::new_1d(1, 0.0, 1.0, 1, flux, speeds).err(),
+            Some("nx must be ≥ 2")
+        );
+        assert_eq!(
+            PdeFiniteVolume::new_2d(1, 0.0, 1.0, 0.0, 1.0, 2, 0, flux, speeds).err(),
+            Some("ny must be ≥ 1")
+        );
+        assert_eq!(
+            PdeFiniteVolume::new_1d(1, 1.0, 1.0, 2, flux, speeds).err(),
+            Some("xmax must be greater than xmin")
+        );
+        assert_eq!(
+            PdeFiniteVolume::new_2d(1, 0.0, 1.0, 1.0, 0.0, 2, 2, flux, speeds).err(),
+            Some("ymax must be greater than ymin")
+        );
+        let mut fvm = PdeFiniteVolume::new_1d(1, 0.0, 1.0, 2, flux, speeds).unwrap();
+        assert_eq!(
+            fvm.set_periodic_boundary_condition(Side::Top).err(),
+            Some("the Bottom and Top sides are not available in 1D")
+        );
+        let u = Vector::new(2);
+        assert_eq!(
+            fvm.max_stable_step(&u, 0.5).err(),
+            Some("the wave speeds must not be all zero")
+        );
+        let mut dudt = Vector::new(3);
+        assert_eq!(
+            fvm.calc_rate(&mut dudt, 0.0, &u).err(),
+            Some("the vectors must have dim components")
+        );
+    }
+
+    #[test]
+    fn first_order_upwind_works() {
+        // with a > 0, both Rusanov and HLL reduce to the upwind scheme
+        for riemann in [RiemannSolver::Rusanov, RiemannSolver::Hll] {
+            let mut fvm = advection(2.0, 4);
+            fvm.set_limiter(SlopeLimiter::FirstOrder).set_riemann_solver(riemann);
+            fvm.set_periodic_boundary_condition(Side::Left).unwrap();
+            let u = Vector::from(&[1.0, 2.0, 4.0, 8.0]);
+            let mut dudt = Vector::new(4);
+            fvm.calc_rate(&mut dudt, 0.0, &u).unwrap();
+            // dudt[i] = -a (u[i] - u[i-1]) / dx with dx = 0.25
+            vec_approx_eq(&dudt, &[-8.0 * (1.0 - 8.0), -8.0 * 1.0, -8.0 * 2.0, -8.0 * 4.0], 1e-14);
+        }
+    }
+
+    #[test]
+    fn transmissive_and_muscl_work() {
+        let mut fvm = advection(1.0, 4);
+        fvm.set_limiter(SlopeLimiter::Minmod);
+        let u = Vector::from(&[1.0, 2.0, 3.0, 4.0]);
+        let mut dudt = Vector::new(4);
+        fvm.calc_rate(&mut dudt, 0.0, &u).unwrap();
+        // face values (upwind = left state) with zero slopes at the boundary cells:
+        // F₀ = 1, F₁ = 1, F₂ = 2.5, F₃ = 3.5, F₄ = 4
+        vec_approx_eq(&dudt, &[0.0, -6.0, -4.0, -2.0], 1e-14);
+
+        // uniform state yields zero rate
+        let u = Vector::filled(4, 3.0);
+        fvm.calc_rate(&mut dudt, 0.0, &u).unwrap();
+        vec_approx_eq(&dudt, &[0.0, 0.0, 0.0, 0.0], 1e-15);
+    }
+
+    #[test]
+    fn two_dim_and_source_work() {
+        // u_t + u_x + 2 u_y = 1 on a 2 × 3 grid with periodic boundaries
+        let mut fvm = PdeFiniteVolume::new_2d(
+            1,
+            0.0,
+            2.0,
+            0.0,
+            3.0,
+            2,
+            3,
+            |f, u, dir| {
+                f[0] = if dir == 0 { u[0] } else { 2.0 * u[0] };
+                Ok(())
+            },
+            |_, dir| if dir == 0 { (1.0, 1.0) } else { (2.0, 2.0) },
+        )
+        .unwrap();
+        fvm.set_periodic_boundary_condition(Side::Left).unwrap();
+        fvm.set_periodic_boundary_condition(Side::Bottom).unwrap();
+        fvm.set_source(|s, _, _, _, _| {
+            s[0] = 1.0;
+            Ok(())
+        });
+        assert_eq!(fvm.dim(), 6);
+        assert_eq!(fvm.ncell(), 6);
+        let u = Vector::filled(6, 5.0);
+        let mut dudt = Vector::new(6);
+        fvm.calc_rate(&mut dudt, 0.0, &u).unwrap();
+        vec_approx_eq(&dudt, &[1.0, 1.0, 1.0, 1.0, 1.0, 1.0], 1e-15);
+        let mut centers = Vec::new();
+        fvm.loop_over_cells(|m, x, y| centers.push((m, x, y)));
+        assert_eq!(centers[0], (0, 0.5, 0.5));
+        assert_eq!(centers[5], (5, 1.5, 2.5));
+        // CFL: 0.5 / (1/1 + 2/1)
+        approx_eq(fvm.max_stable_step(&u, 0.5).unwrap(), 0.5 / 3.0, 1e-15);
+    }
+
+    #[test]
+    fn get_system_works() {
+        let mut fvm = advection(1.0, 4);
+        fvm.set_periodic_boundary_condition(Side::Right).unwrap();
+        let system = fvm.get_system::<NoArgs>();
+        assert_eq!(system.get_ndim(), 4);
+        let u = Vector::from(&[1.0, 2.0, 3.0, 4.0]);
+        let mut f = Vector::new(4);
+        let mut dudt = Vector::new(4);
+        (system.function)(&mut f, 0.0, &u, &mut 0).unwrap();
+        fvm.calc_rate(&mut dudt, 0.0, &u).unwrap();
+        vec_approx_eq(&f, dudt.as_data(), 1e-15);
+    }
+}
@@ -0,0 +1,745 @@
+use crate::{FnSpace, Side, StrError, System};
+use russell_lab::algo::{InterpGrid, InterpLagrange, InterpParams};
+use russell_lab::{mat_vec_mul, Matrix, Vector};
+use russell_sparse::{CooMatrix, Sym};
+use std::collections::HashMap;
+
+/// Specifies a boundary condition for the spectral collocation method
+///
+/// The outward normal derivative `∂u/∂n` is computed with the first-order differentiation
+/// matrices. The normal vectors are: Left = (-1, 0), Right = (1, 0), Bottom = (0, -1), and Top = (0, 1).
+#[derive(Clone, Copy, Debug)]
+pub enum SpectralBc {
+    /// Dirichlet condition: `u = g(x, y)`
+    Dirichlet(FnSpace),
+
+    /// Neumann condition: `∂u/∂n = g(x, y)`
+    Neumann(FnSpace),
+
+    /// Robin condition: `a u + b ∂u/∂n = g(x, y)` with the coefficients `(a, b, g)`
+    Robin(f64, f64, FnSpace),
+}
+
+/// Implements the spectral collocation method over Chebyshev-Gauss-Lobatto grids in 1D and 2D
+///
+/// The differentiation matrices are computed by [InterpLagrange] on the reference interval `[-1, 1]`
+/// and then mapped onto the physical domain `[xmin, xmax] × [ymin, ymax]`. In 2D, the grid is the
+/// tensor product of two 1D grids and the (dense) operators are assembled using Kronecker products.
+///
+/// Given the scalar field u(x, y), the linear differential operator is:
+///
+/// ```text
+///          ∂²u        ∂u         ∂²u        ∂u
+/// L{u} = axx ——— + ax —— +  ayy ——— + ay —— + a u
+///          ∂x²        ∂x         ∂y²        ∂y
+/// ```
+///
+/// The discrete counterpart of u(x, y) at the grid point (i, j) is "sequentially"
+/// mapped onto the vector `U` using the following formula (as in [crate::PdeDiscreteLaplacian2d]):
+///
+/// ```text
+/// uᵢⱼ → Uₘ   with   m = i + j nx
+/// ```
+///
+/// where `nx = Nx + 1` is the number of points along x. In 1D, `ny = 1` and thus `m = i`.
+///
+/// The boundary conditions are imposed by replacing the rows of the coefficient matrix
+/// corresponding to the boundary nodes (row replacement). For instance, considering the
+/// Dirichlet condition at node `m`, the row `m` of the coefficient matrix becomes the
+/// m-th row of the identity matrix and the m-th component of the right-hand side becomes `g(xₘ, yₘ)`.
+///
+/// # Remarks
+///
+/// * The coordinates of the grid points are sorted in ascending order
+/// * The corner nodes in 2D take the boundary condition of the side specified last
+/// * The operators are dense matrices because spectral methods are global
+///
+/// # References
+///
+/// 1. Kopriva DA (2009) Implementing Spectral Methods for Partial Differential Equations
+///    Springer, 404p
+/// 2. Canuto C, Hussaini MY, Quarteroni A, Zang TA (2006) Spectral Methods: Fundamentals in
+///    Single Domains. Springer. 563p
+pub struct PdeSpectralCollocation {
+    two_dim: bool,      // indicates a 2D grid
+    nx: usize,          // number of points along x (≥ 2)
+    ny: usize,          // number of points along y (≥ 2 in 2D; = 1 in 1D)
+    xx: Vector,         // (nx) coordinates of grid points along x
+    yy: Vector,         // (ny) coordinates of grid points along y
+    ddx1: Matrix,       // (nx, nx) first-order differentiation matrix along x
+    ddx2: Matrix,       // (nx, nx) second-order differentiation matrix along x
+    ddy1: Matrix,       // (ny, ny) first-order differentiation matrix along y
+    ddy2: Matrix,       // (ny, ny) second-order differentiation matrix along y
+    left: Vec<usize>,   // indices of nodes on the left edge
+    right: Vec<usize>,  // indices of nodes on the right edge
+    bottom: Vec<usize>, // indices of nodes on the bottom edge (empty in 1D)
+    top: Vec<usize>,    // indices of nodes on the top edge (empty in 1D)
+
+    /// Collects the boundary conditions
+    /// Maps node => (condition, outward normal vector)
+    conditions: HashMap<usize, (SpectralBc, [f64; 2])>,
+}
+
+impl PdeSpectralCollocation {
+    /// Allocates a new instance for a 1D domain
+    ///
+    /// # Input
+    ///
+    /// * `xmin` -- min x coordinate
+    /// * `xmax` -- max x coordinate
+    /// * `nn` -- polynomial degree `N` in `[1, 2048]`; thus the number of points is `N + 1`
+    pub fn new_1d(xmin: f64, xmax: f64, nn: usize) -> Result<Self, StrError> {
+        if xmax <= xmin {
+            return Err("xmax must be greater than xmin");
+        }
+        let (xx, ddx1, ddx2) = calc_mapped_matrices(xmin, xmax, nn)?;
+        let nx = nn + 1;
+        Ok(PdeSpectralCollocation {
+            two_dim: false,
+            nx,
+            ny: 1,
+            xx,
+            yy: Vector::new(1),
+            ddx1,
+            ddx2,
+            ddy1: Matrix::new(1, 1),
+            ddy2: Matrix::new(1, 1),
+            left: vec![0],
+            right: vec![nx - 1],
+            bottom: Vec::new(),
+            top: Vec::new(),
+            conditions: HashMap::new(),
+        })
+    }
+
+    /// Allocates a new instance for a 2D (rectangular) domain
+    ///
+    /// # Input
+    ///
+    /// * `xmin` -- min x coordinate
+    /// * `xmax` -- max x coordinate
+    /// * `ymin` -- min y coordinate
+    /// * `ymax` -- max y coordinate
+    /// * `nnx` -- polynomial degree along x in `[1, 2048]`; thus `nx = nnx + 1`
+    /// * `nny` -- polynomial degree along y in `[1, 2048]`; thus `ny = nny + 1`
+    pub fn new_2d(xmin: f64, xmax: f64, ymin: f64, ymax: f64, nnx: usize, nny: usize) -> Result<Self, StrError> {
+        if xmax <= xmin {
+            return Err("xmax must be greater than xmin");
+        }
+        if ymax <= ymin {
+            return Err("ymax must be greater than ymin");
+        }
+        let (xx, ddx1, ddx2) = calc_mapped_matrices(xmin, xmax, nnx)?;
+        let (yy, ddy1, ddy2) = calc_mapped_matrices(ymin, ymax, nny)?;
+        let nx = nnx + 1;
+        let ny = nny + 1;
+        let dim = nx * ny;
+        Ok(PdeSpectralCollocation {
+            two_dim: true,
+            nx,
+            ny,
+            xx,
+            yy,
+            ddx1,
+            ddx2,
+            ddy1,
+            ddy2,
+            left: (0..dim).step_by(nx).collect(),
+            right: ((nx - 1)..dim).step_by(nx).collect(),
+            bottom: (0..nx).collect(),
+            top: ((dim - nx)..dim).collect(),
+            conditions: HashMap::new(),
+        })
+    }
+
+    /// Sets a boundary condition on a side of the domain
+    ///
+    /// **Note:** In 1D, only the Left and Right sides are available.
+    pub fn set_boundary_condition(&mut self, side: Side, bc: SpectralBc) -> Result<(), StrError> {
+        let (nodes, normal) = match side {
+            Side::Left => (&self.left, [-1.0, 0.0]),
+            Side::Right => (&self.right, [1.0, 0.0]),
+            Side::Bottom => (&self.bottom, [0.0, -1.0]),
+            Side::Top => (&self.top, [0.0, 1.0]),
+        };
+        if nodes.is_empty() {
+            return Err("the Bottom and Top sides are not available in 1D");
+        }
+        for m in nodes {
+            self.conditions.insert(*m, (bc, normal));
+        }
+        Ok(())
+    }
+
+    /// Sets homogeneous boundary conditions (i.e., zero Dirichlet values on all sides)
+    pub fn set_homogeneous_boundary_conditions(&mut self) {
+        self.conditions.clear();
+        let sides = if self.two_dim {
+            vec![Side::Left, Side::Right, Side::Bottom, Side::Top]
+        } else {
+            vec![Side::Left, Side::Right]
+        };
+        for side in sides {
+            self.set_boundary_condition(side, SpectralBc::Dirichlet(|_, _| 0.0))
+                .unwrap();
+        }
+    }
+
+    /// Computes the (dense) matrix of the linear differential operator
+    ///
+    /// ```text
+    ///          ∂²u        ∂u         ∂²u        ∂u
+    /// L{u} = axx ——— + ax —— +  ayy ——— + ay —— + a u
+    ///          ∂x²        ∂x         ∂y²        ∂y
+    /// ```
+    ///
+    /// **Note:** The boundary conditions are **not** applied by this function;
+    /// see [PdeSpectralCollocation::apply_boundary_conditions].
+    ///
+    /// # Output
+    ///
+    /// Returns the `(dim × dim)` matrix `A` such that `L{u} ≈ A ⋅ U`
+    pub fn differential_operator(&self, axx: f64, ax: f64, ayy: f64, ay: f64, a: f64) -> Matrix {
+        let dim = self.dim();
+        let mut aa = Matrix::new(dim, dim);
+        for j in 0..self.ny {
+            for i in 0..self.nx {
+                let m = i + j * self.nx;
+                for k in 0..self.nx {
+                    let n = k + j * self.nx;
+                    aa.add(m, n, axx * self.ddx2.get(i, k) + ax * self.ddx1.get(i, k));
+                }
+                if self.two_dim {
+                    for l in 0..self.ny {
+                        let n = i + l * self.nx;
+                        aa.add(m, n, ayy * self.ddy2.get(j, l) + ay * self.ddy1.get(j, l));
+                    }
+                }
+                aa.add(m, m, a);
+            }
+        }
+        aa
+    }
+
+    /// Computes the (dense) matrix of the Laplacian operator
+    ///
+    /// ```text
+    ///          ∂²u        ∂²u
+    /// L{u} = kx ——— +  ky ———
+    ///          ∂x²        ∂y²
+    /// ```
+    ///
+    /// **Note:** The boundary conditions are **not** applied by this function;
+    /// see [PdeSpectralCollocation::apply_boundary_conditions].
+    pub fn laplacian(&self, kx: f64, ky: f64) -> Matrix {
+        self.differential_operator(kx, 0.0, ky, 0.0, 0.0)
+    }
+
+    /// Applies the boundary conditions to the coefficient matrix and the right-hand side by row replacement
+    ///
+    /// For a boundary node `m` with outward normal `n`, the row `m` is replaced as follows:
+    ///
+    /// ```text
+    /// Dirichlet:  Aₘₖ = δₘₖ                  bₘ = g(xₘ, yₘ)
+    /// Neumann:    Aₘₖ = (n ⋅ ∇)ₘₖ            bₘ = g(xₘ, yₘ)
+    /// Robin:      Aₘₖ = a δₘₖ + b (n ⋅ ∇)ₘₖ   bₘ = g(xₘ, yₘ)
+    /// ```
+    ///
+    /// where `(n ⋅ ∇)` is the discrete normal derivative operator.
+    ///
+    /// # Input
+    ///
+    /// * `aa` -- the `(dim × dim)` coefficient matrix (e.g., from [PdeSpectralCollocation::differential_operator])
+    /// * `b` -- (optional) the right-hand side vector with `dim` components
+    pub fn apply_boundary_conditions(&self, aa: &mut Matrix, b: Option<&mut Vector>) -> Result<(), StrError> {
+        let dim = self.dim();
+        if aa.nrow() != dim || aa.ncol() != dim {
+            return Err("the coefficient matrix must be dim × dim");
+        }
+        for (m, (bc, normal)) in &self.conditions {
+            for k in 0..dim {
+                aa.set(*m, k, 0.0);
+            }
+            match bc {
+                SpectralBc::Dirichlet(_) => aa.set(*m, *m, 1.0),
+                SpectralBc::Neumann(_) => self.add_normal_derivative_row(aa, *m, normal, 1.0),
+                SpectralBc::Robin(ca, cb, _) => {
+                    aa.add(*m, *m, *ca);
+                    self.add_normal_derivative_row(aa, *m, normal, *cb);
+                }
+            }
+        }
+        if let Some(bb) = b {
+            if bb.dim() != dim {
+                return Err("the right-hand side vector must have dim components");
+            }
+            self.loop_over_boundary_values(|m, value| bb[m] = value);
+        }
+        Ok(())
+    }
+
+    /// Sets the Dirichlet values into a vector (e.g., the initial values)
+    ///
+    /// **Note:** The nodes with Neumann or Robin conditions are not modified.
+    pub fn set_dirichlet_values(&self, u: &mut Vector) -> Result<(), StrError> {
+        if u.dim() != self.dim() {
+            return Err("the vector must have dim components");
+        }
+        for (m, (bc, _)) in &self.conditions {
+            if let SpectralBc::Dirichlet(g) = bc {
+                let (x, y) = self.coordinates(*m);
+                u[*m] = g(x, y);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the ODE system corresponding to the method of lines
+    ///
+    /// The semi-discrete system is:
+    ///
+    /// ```text
+    /// dU
+    /// —— = A ⋅ U + S
+    /// dt
+    /// ```
+    ///
+    /// where `A` is the operator matrix and `S` holds the source term evaluated at the grid points.
+    ///
+    /// If all boundary conditions are of Dirichlet type, the equations corresponding to the
+    /// boundary nodes are replaced by `dUₘ/dt = 0`. In this case, any method can be used for
+    /// time stepping (including the explicit ones); however, the initial values must satisfy the
+    /// boundary conditions (see [PdeSpectralCollocation::set_dirichlet_values]).
+    ///
+    /// Otherwise, the boundary rows become algebraic equations and the system is a DAE with a
+    /// (diagonal) mass matrix having zeros on the boundary rows. In this case, the
+    /// [crate::Method::Radau5] must be used.
+    ///
+    /// # Input
+    ///
+    /// * `aa` -- the operator matrix (without boundary conditions), e.g., from [PdeSpectralCollocation::laplacian]
+    /// * `source` -- (optional) the source term `s(x, y)`
+    ///
+    /// # Generics
+    ///
+    /// * `A` -- generic argument to assist in the f(x,y) and Jacobian functions.
+    ///   It may be simply [crate::NoArgs] indicating that no arguments are needed.
+    pub fn get_system<'a, A>(&self, aa: &Matrix, source: Option<FnSpace>) -> Result<System<'a, A>, StrError> {
+        // apply boundary conditions
+        let dim = self.dim();
+        let mut aa_bc = aa.clone();
+        let mut cc = Vector::new(dim);
+        self.apply_boundary_conditions(&mut aa_bc, Some(&mut cc))?;
+        let only_dirichlet = self
+            .conditions
+            .values()
+            .all(|(bc, _)| matches!(bc, SpectralBc::Dirichlet(_)));
+
+        // constant vector: source term on interior rows and boundary values on boundary rows
+        self.loop_over_grid_points(|m, x, y| {
+            if self.conditions.contains_key(&m) {
+                if only_dirichlet {
+                    for k in 0..dim {
+                        aa_bc.set(m, k, 0.0);
+                    }
+                    cc[m] = 0.0;
+                } else {
+                    cc[m] = -cc[m];
+                }
+            } else {
+                cc[m] = match source {
+                    Some(s) => s(x, y),
+                    None => 0.0,
+                };
+            }
+        });
+
+        // non-zero entries of the Jacobian matrix
+        let mut triplets = Vec::new();
+        for m in 0..dim {
+            for n in 0..dim {
+                let amn = aa_bc.get(m, n);
+                if amn != 0.0 {
+                    triplets.push((m, n, amn));
+                }
+            }
+        }
+        let jac_nnz = usize::max(1, triplets.len());
+
+        // system
+        let mut system = System::new(dim, move |f: &mut Vector, _t: f64, u: &Vector, _args: &mut A| {
+            mat_vec_mul(f, 1.0, &aa_bc, u)?;
+            for m in 0..f.dim() {
+                f[m] += cc[m];
+            }
+            Ok(())
+        });
+        system.set_jacobian(
+            Some(jac_nnz),
+            Sym::No,
+            move |jj: &mut CooMatrix, alpha: f64, _t: f64, _u: &Vector, _args: &mut A| {
+                jj.reset();
+                for (m, n, amn) in &triplets {
+                    jj.put(*m, *n, alpha * amn)?;
+                }
+                Ok(())
+            },
+        )?;
+
+        // mass matrix
+        if !only_dirichlet {
+            let interior: Vec<usize> = (0..dim).filter(|m| !self.conditions.contains_key(m)).collect();
+            let mass_nnz = usize::max(1, interior.len());
+            system.set_mass(Some(mass_nnz), Sym::No, move |mm: &mut CooMatrix| {
+                for m in &interior {
+                    mm.put(*m, *m, 1.0).unwrap();
+                }
+            })?;
+        }
+        Ok(system)
+    }
+
+    /// Executes a loop over the boundary values
+    ///
+    /// # Input
+    ///
+    /// * `callback` -- a `function(m, value)` where `m` is the row index and
+    ///   `value` is the value `g(xₘ, yₘ)` of the boundary condition.
+    pub fn loop_over_boundary_values<F>(&self, mut callback: F)
+    where
+        F: FnMut(usize, f64),
+    {
+        self.conditions.iter().for_each(|(m, (bc, _))| {
+            let (x, y) = self.coordinates(*m);
+            let value = match bc {
+                SpectralBc::Dirichlet(g) => g(x, y),
+                SpectralBc::Neumann(g) => g(x, y),
+                SpectralBc::Robin(_, _, g) => g(x, y),
+            };
+            callback(*m, value);
+        });
+    }
+
+    /// Executes a loop over the grid points
+    ///
+    /// # Input
+    ///
+    /// * `callback` -- a function of `(m, x, y)` where `m` is the sequential point number,
+    ///   and `(x, y)` are the Cartesian coordinates of the grid point. In 1D, `y = 0`.
+    ///
+    /// Note that:
+    ///
+    /// ```text
+    /// m = i + j nx
+    /// i = m % nx
+    /// j = m / nx
+    /// ```
+    pub fn loop_over_grid_points<F>(&self, mut callback: F)
+    where
+        F: FnMut(usize, f64, f64),
+    {
+        for m in 0..self.dim() {
+            let (x, y) = self.coordinates(m);
+            callback(m, x, y)
+        }
+    }
+
+    /// Returns the dimension of the discrete problem
+    ///
+    /// ```text
+    /// dim = nx × ny
+    /// ```
+    pub fn dim(&self) -> usize {
+        self.nx * self.ny
+    }
+
+    /// Returns the number of nodes with boundary conditions
+    pub fn num_prescribed(&self) -> usize {
+        self.conditions.len()
+    }
+
+    /// Returns the coordinates of the grid points along x
+    pub fn get_points_x(&self) -> &Vector {
+        &self.xx
+    }
+
+    /// Returns the coordinates of the grid points along y (a single zero in 1D)
+    pub fn get_points_y(&self) -> &Vector {
+        &self.yy
+    }
+
+    /// Returns the (mapped) first-order differentiation matrix along x
+    pub fn get_ddx1(&self) -> &Matrix {
+        &self.ddx1
+    }
+
+    /// Returns the (mapped) second-order differentiation matrix along x
+    pub fn get_ddx2(&self) -> &Matrix {
+        &self.ddx2
+    }
+
+    /// Returns the (mapped) first-order differentiation matrix along y (a single zero in 1D)
+    pub fn get_ddy1(&self) -> &Matrix {
+        &self.ddy1
+    }
+
+    /// Returns the (mapped) second-order differentiation matrix along y (a single zero in 1D)
+    pub fn get_ddy2(&self) -> &Matrix {
+        &self.ddy2
+    }
+
+    /// Returns the coordinates of the grid point m
+    fn coordinates(&self, m: usize) -> (f64, f64) {
+        let i = m % self.nx;
+        let j = m / self.nx;
+        (self.xx[i], self.yy[j])
+    }
+
+    /// Adds the scaled normal derivative operator to the row m of the coefficient matrix
+    fn add_normal_derivative_row(&self, aa: &mut Matrix, m: usize, normal: &[f64; 2], scale: f64) {
+        let i = m % self.nx;
+        let j = m / self.nx;
+        if normal[0] != 0.0 {
+            for k in 0..self.nx {
+                aa.add(m, k + j * self.nx, scale * normal[0] * self.ddx1.get(i, k));
+            }
+        }
+        if normal[1] != 0.0 {
+            for l in 0..self.ny {
+                aa.add(m, i + l * self.nx, scale * normal[1] * self.ddy1.get(j, l));
+            }
+        }
+    }
+}
+
+/// Computes the grid coordinates and the differentiation matrices mapped onto [xmin, xmax]
+///
+/// Returns `(xx, dd1, dd2)`
+fn calc_mapped_matrices(xmin: f64, xmax: f64, nn: usize) -> Result<(Vector, Matrix, Matrix), StrError> {
+    let mut params = InterpParams::new();
+    params.grid_type = InterpGrid::ChebyshevGaussLobatto;
+    let mut interp = InterpLagrange::new(nn, Some(params))?;
+    interp.calc_dd2_matrix();
+    let npoint = nn + 1;
+    let scale = 2.0 / (xmax - xmin);
+    let xx = interp
+        .get_points()
+        .get_mapped(|xi| xmin + (xi + 1.0) * (xmax - xmin) / 2.0);
+    let mut dd1 = interp.get_dd1()?.clone();
+    let mut dd2 = interp.get_dd2()?.clone();
+    for i in 0..npoint {
+        for j in 0..npoint {
+            dd1.mul(i, j, scale);
+            dd2.mul(i, j, scale * scale);
+        }
+    }
+    Ok((xx, dd1, dd2))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{PdeSpectralCollocation, SpectralBc};
+    use crate::{NoArgs, Side};
+    use russell_lab::math::NAPIER;
+    use russell_lab::{approx_eq, array_approx_eq, mat_approx_eq, solve_lin_sys, vec_approx_eq, Matrix, Vector};
+    use russell_sparse::CooMatrix;
+
+    #[test]
+    fn new_captures_errors() {
+        assert_eq!(
+            PdeSpectralCollocation::new_1d(1.0, 1.0, 2).err(),
+            Some("xmax must be greater than xmin")
+        );
+        assert_eq!(
+            PdeSpectralCollocation::new_1d(0.0, 1.0, 0).err(),
+            Some("the polynomial degree must be in [1, 2048]")
+        );
+        assert_eq!(
+            PdeSpectralCollocation::new_2d(0.0, 1.0, 1.0, 1.0, 2, 2).err(),
+            Some("ymax must be greater than ymin")
+        );
+        let mut spc = PdeSpectralCollocation::new_1d(0.0, 1.0, 2).unwrap();
+        assert_eq!(
+            spc.set_boundary_condition(Side::Top, SpectralBc::Dirichlet(|_, _| 0.0))
+                .err(),
+            Some("the Bottom and Top sides are not available in 1D")
+        );
+    }
+
+    #[test]
+    fn new_1d_works() {
+        let spc = PdeSpectralCollocation::new_1d(0.0, 4.0, 2).unwrap();
+        assert_eq!(spc.dim(), 3);
+        assert_eq!(spc.get_points_x().as_data(), &[0.0, 2.0, 4.0]);
+        assert_eq!(spc.left, &[0]);
+        assert_eq!(spc.right, &[2]);
+        // D1 on [-1, 1] scaled by 2/(4-0) = 0.5
+        #[rustfmt::skip]
+        let correct = Matrix::from(&[
+            [-0.75,  1.0, -0.25],
+            [-0.25,  0.0,  0.25],
+            [ 0.25, -1.0,  0.75],
+        ]);
+        mat_approx_eq(spc.get_ddx1(), &correct, 1e-15);
+    }
+
+    #[test]
+    fn new_2d_works() {
+        let spc = PdeSpectralCollocation::new_2d(-1.0, 1.0, -3.0, 3.0, 1, 2).unwrap();
+        assert_eq!(spc.dim(), 6);
+        assert_eq!(spc.get_points_x().as_data(), &[-1.0, 1.0]);
+        assert_eq!(spc.get_points_y().as_data(), &[-3.0, 0.0, 3.0]);
+        assert_eq!(spc.left, &[0, 2, 4]);
+        assert_eq!(spc.right, &[1, 3, 5]);
+        assert_eq!(spc.bottom, &[0, 1]);
+        assert_eq!(spc.top, &[4, 5]);
+        let mut coords = Vec::new();
+        spc.loop_over_grid_points(|m, x, y| coords.push((m, x, y)));
+        assert_eq!(
+            coords,
+            &[
+                (0, -1.0, -3.0),
+                (1, 1.0, -3.0),
+                (2, -1.0, 0.0),
+                (3, 1.0, 0.0),
+                (4, -1.0, 3.0),
+                (5, 1.0, 3.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn differential_operator_works() {
+        // 2D operator with a single element along x and y (bilinear)
+        let spc = PdeSpectralCollocation::new_2d(-1.0, 1.0, -1.0, 1.0, 1, 1).unwrap();
+        let aa = spc.differential_operator(0.0, 1.0, 0.0, 2.0, 3.0);
+        // D1 = [[-0.5, 0.5], [-0.5, 0.5]]
+        #[rustfmt::skip]
+        let correct = Matrix::from(&[
+            [ 3.0 - 0.5 - 1.0,  0.5,             1.0,             0.0            ],
+            [-0.5,              3.0 + 0.5 - 1.0, 0.0,             1.0            ],
+            [-1.0,              0.0,             3.0 - 0.5 + 1.0, 0.5            ],
+            [ 0.0,             -1.0,            -0.5,             3.0 + 0.5 + 1.0],
+        ]);
+        mat_approx_eq(&aa, &correct, 1e-15);
+
+        // the Laplacian of a quadratic function is exact
+        let spc = PdeSpectralCollocation::new_2d(0.0, 2.0, 0.0, 3.0, 4, 4).unwrap();
+        let aa = spc.laplacian(2.0, 3.0);
+        let mut u = Vector::new(spc.dim());
+        spc.loop_over_grid_points(|m, x, y| u[m] = x * x + x * y + 0.5 * y * y);
+        let mut v = Vector::new(spc.dim());
+        russell_lab::mat_vec_mul(&mut v, 1.0, &aa, &u).unwrap();
+        vec_approx_eq(&v, &Vector::filled(spc.dim(), 2.0 * 2.0 + 3.0 * 1.0), 1e-12);
+    }
+
+    #[test]
+    fn apply_boundary_conditions_works() {
+        let mut spc = PdeSpectralCollocation::new_1d(-1.0, 1.0, 2).unwrap();
+        spc.set_boundary_condition(Side::Left, SpectralBc::Dirichlet(|_, _| 3.0))
+            .unwrap();
+        spc.set_boundary_condition(Side::Right, SpectralBc::Robin(2.0, 4.0, |_, _| 5.0))
+            .unwrap();
+        assert_eq!(spc.num_prescribed(), 2);
+        let mut aa = spc.laplacian(1.0, 0.0);
+        let mut b = Vector::new(3);
+        spc.apply_boundary_conditions(&mut aa, Some(&mut b)).unwrap();
+        // D1 = [[-1.5, 2.0, -0.5], [-0.5, 0.0, 0.5], [0.5, -2.0, 1.5]]
+        // D2 = [[1, -2, 1], [1, -2, 1], [1, -2, 1]]
+        #[rustfmt::skip]
+        let correct = Matrix::from(&[
+            [1.0,        0.0,        0.0            ],
+            [1.0,       -2.0,        1.0            ],
+            [4.0 * 0.5, -4.0 * 2.0,  2.0 + 4.0 * 1.5],
+        ]);
+        mat_approx_eq(&aa, &correct, 1e-14);
+        vec_approx_eq(&b, &[3.0, 0.0, 5.0], 1e-15);
+
+        // Neumann
+        spc.set_boundary_condition(Side::Left, SpectralBc::Neumann(|_, _| 7.0))
+            .unwrap();
+        let mut aa = spc.laplacian(1.0, 0.0);
+        spc.apply_boundary_conditions(&mut aa, None).unwrap();
+        array_approx_eq(&aa.extract_row(0), &[1.5, -2.0, 0.5], 1e-15); // outward normal = -1
+
+        // errors
+        let mut wrong = Matrix::new(2, 2);
+        assert_eq!(
+            spc.apply_boundary_conditions(&mut wrong, None).err(),
+            Some("the coefficient matrix must be dim × dim")
+        );
+        let mut wrong = Vector::new(2);
+        assert_eq!(
+            spc.apply_boundary_conditions(&mut aa, Some(&mut wrong)).err(),
+            Some("the right-hand side vector must have dim components")
+        );
+    }
+
+    #[test]
+    fn solve_lorene_problem_works() {
+        // d²u/dx² - 4 du/dx + 4 u = exp(x) + C with u(-1) = u(1) = 0
+        const C: f64 = -4.0 * NAPIER / (1.0 + NAPIER * NAPIER);
+        let mut spc = PdeSpectralCollocation::new_1d(-1.0, 1.0, 16).unwrap();
+        spc.set_homogeneous_boundary_conditions();
+        let mut aa = spc.differential_operator(1.0, -4.0, 0.0, 0.0, 4.0);
+        let mut b = Vector::new(spc.dim());
+        spc.loop_over_grid_points(|m, x, _| b[m] = f64::exp(x) + C);
+        spc.apply_boundary_conditions(&mut aa, Some(&mut b)).unwrap();
+        solve_lin_sys(&mut b, &mut aa).unwrap();
+        let sh1 = f64::sinh(1.0);
+        let sh2 = f64::sinh(2.0);
+        spc.loop_over_grid_points(|m, x, _| {
+            approx_eq(b[m], f64::exp(x) - f64::exp(2.0 * x) * sh1 / sh2 + C / 4.0, 1e-12);
+        });
+    }
+
+    #[test]
+    fn get_system_works() {
+        // Dirichlet only: no mass matrix and zero rows at the boundary
+        let mut spc = PdeSpectralCollocation::new_1d(-1.0, 1.0, 2).unwrap();
+        spc.set_homogeneous_boundary_conditions();
+        let aa = spc.laplacian(1.0, 0.0);
+        let system = spc.get_system::<NoArgs>(&aa, Some(|_, _| 10.0)).unwrap();
+        assert_eq!(system.get_ndim(), 3);
+        assert_eq!(system.get_jac_nnz(), 3);
+        assert_eq!(system.get_mass_nnz(), 0);
+        let u = Vector::from(&[0.0, 1.0, 0.0]);
+        let mut f = Vector::new(3);
+        (system.function)(&mut f, 0.0, &u, &mut 0).unwrap();
+        vec_approx_eq(&f, &[0.0, -2.0 + 10.0, 0.0], 1e-15);
+        let mut jj = CooMatrix::new(3, 3, system.get_jac_nnz(), system.symmetric).unwrap();
+        (system.jacobian.as_ref().unwrap())(&mut jj, 2.0, 0.0, &u, &mut 0).unwrap();
+        #[rustfmt::skip]
+        let correct = Matrix::from(&[
+            [0.0,  0.0, 0.0],
+            [2.0, -4.0, 2.0],
+            [0.0,  0.0, 0.0],
+        ]);
+        mat_approx_eq(&jj.as_dense(), &correct, 1e-15);
+
+        // Neumann: DAE with mass matrix
+        spc.set_boundary_condition(Side::Right, SpectralBc::Neumann(|_, _| 1.0))
+            .unwrap();
+        let system = spc.get_system::<NoArgs>(&aa, None).unwrap();
+        assert_eq!(system.get_mass_nnz(), 1);
+        let mut mm = CooMatrix::new(3, 3, system.get_mass_nnz(), system.symmetric).unwrap();
+        (system.calc_mass.as_ref().unwrap())(&mut mm);
+        assert_eq!(mm.get_row_indices(), &[1]);
+        let u = Vector::from(&[0.0, 1.0, 2.0]);
+        (system.function)(&mut f, 0.0, &u, &mut 0).unwrap();
+        // f[2] = (D1 u)[2] - 1 = (0.5 * 0 - 2 * 1 + 1.5 * 2) - 1 = 0
+        vec_approx_eq(&f, &[0.0, 0.0, 0.0], 1e-15);
+
+        // set Dirichlet values
+        let mut u = Vector::filled(3, 8.0);
+        spc.set_dirichlet_values(&mut u).unwrap();
+        assert_eq!(u.as_data(), &[0.0, 8.0, 8.0]);
+        let mut wrong = Vector::new(2);
+        assert_eq!(
+            spc.set_dirichlet_values(&mut wrong).err(),
+            Some("the vector must have dim components")
+        );
+    }
+}
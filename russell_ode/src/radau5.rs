@@ -248,6 +248,9 @@ impl<'a, A> Radau5<'a, A> {
                 csc_kk_real.write_matrix_market("/tmp/russell_ode/kk_real.mtx", false)?;
                 csc_kk_comp.write_matrix_market("/tmp/russell_ode/kk_comp.smat", true)?;
                 csc_kk_comp.write_matrix_market("/tmp/russell_ode/kk_comp.mtx", false)?;
+                csc_jacobian.write_spy_svg("/tmp/russell_ode/jacobian.svg", None)?;
+                csc_kk_real.write_spy_svg("/tmp/russell_ode/kk_real.svg", None)?;
+                csc_kk_comp.write_spy_svg("/tmp/russell_ode/kk_comp.svg", None)?;
                 return Err("MATRIX FILES GENERATED in /tmp/russell_ode/");
             }
         }
@@ -930,7 +933,11 @@ mod tests {
 
     #[test]
     fn radau5_works_mass_matrix() {
-        let genie = Genie::Umfpack;
+        let genie = if cfg!(feature = "with_suitesparse") {
+            Genie::Umfpack
+        } else {
+            Genie::Native
+        };
         for symmetric in [true, false] {
             for numerical in [true, false] {
                 // problem
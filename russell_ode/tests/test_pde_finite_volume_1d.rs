@@ -0,0 +1,112 @@
+use russell_lab::math::PI;
+use russell_lab::{approx_eq, Vector};
+use russell_ode::{Method, NoArgs, OdeSolver, Params, PdeFiniteVolume, RiemannSolver, Side, SlopeLimiter};
+
+#[test]
+fn test_pde_finite_volume_advection_1d() {
+    // Approximate (with the finite volume method, FVM) the solution of
+    //
+    // ∂u   ∂u
+    // —— + —— = 0     with x ∈ [0, 1] and periodic boundaries
+    // ∂t   ∂x
+    //
+    // with u(x, 0) = sin(2 π x)
+    //
+    // The analytical solution at t = 1 (one period) is equal to the initial condition
+
+    // allocate the FVM discretization
+    let nx = 100;
+    let mut fvm = PdeFiniteVolume::new_1d(
+        1,
+        0.0,
+        1.0,
+        nx,
+        |f, u, _| {
+            f[0] = u[0];
+            Ok(())
+        },
+        |_, _| (1.0, 1.0),
+    )
+    .unwrap();
+    fvm.set_limiter(SlopeLimiter::VanLeer);
+    fvm.set_periodic_boundary_condition(Side::Left).unwrap();
+
+    // initial values (cell averages)
+    let dx = 1.0 / (nx as f64);
+    let mut uu = Vector::new(fvm.dim());
+    fvm.loop_over_cells(|m, x, _| {
+        let (xa, xb) = (x - dx / 2.0, x + dx / 2.0);
+        uu[m] = (f64::cos(2.0 * PI * xa) - f64::cos(2.0 * PI * xb)) / (2.0 * PI * dx);
+    });
+    let uu0 = uu.clone();
+    let total0: f64 = uu0.as_data().iter().sum();
+
+    // solve the problem
+    let h = fvm.max_stable_step(&uu, 0.4).unwrap();
+    let system = fvm.get_system::<NoArgs>();
+    let params = Params::new(Method::Rk3);
+    let mut ode = OdeSolver::new(params, system).unwrap();
+    ode.solve(&mut uu, 0.0, 1.0, Some(h), &mut 0).unwrap();
+    println!("{}", ode.stats());
+
+    // check conservation
+    let total1: f64 = uu.as_data().iter().sum();
+    approx_eq(total1, total0, 1e-10);
+
+    // check the solution
+    for m in 0..nx {
+        approx_eq(uu[m], uu0[m], 2e-2);
+    }
+}
+
+#[test]
+fn test_pde_finite_volume_burgers_1d() {
+    // Inviscid Burgers' equation
+    //
+    // ∂u   ∂(u²/2)
+    // —— + ——————— = 0     with x ∈ [-1, 1] and transmissive boundaries
+    // ∂t     ∂x
+    //
+    // with a Riemann problem: u(x, 0) = 1 if x < 0, otherwise 0
+    //
+    // The exact solution is a shock moving with speed s = (uL + uR) / 2 = 0.5
+
+    // allocate the FVM discretization
+    let nx = 200;
+    let mut fvm = PdeFiniteVolume::new_1d(
+        1,
+        -1.0,
+        1.0,
+        nx,
+        |f, u, _| {
+            f[0] = 0.5 * u[0] * u[0];
+            Ok(())
+        },
+        |u, _| (u[0], u[0]),
+    )
+    .unwrap();
+    fvm.set_limiter(SlopeLimiter::Minmod)
+        .set_riemann_solver(RiemannSolver::Hll);
+
+    // initial values
+    let mut uu = Vector::new(fvm.dim());
+    fvm.loop_over_cells(|m, x, _| uu[m] = if x < 0.0 { 1.0 } else { 0.0 });
+
+    // solve the problem
+    let t1 = 0.5;
+    let h = fvm.max_stable_step(&uu, 0.4).unwrap();
+    let system = fvm.get_system::<NoArgs>();
+    let params = Params::new(Method::Rk3);
+    let mut ode = OdeSolver::new(params, system).unwrap();
+    ode.solve(&mut uu, 0.0, t1, Some(h), &mut 0).unwrap();
+
+    // check the solution away from the shock located at x = 0.25
+    fvm.loop_over_cells(|m, x, _| {
+        if x < 0.15 {
+            approx_eq(uu[m], 1.0, 1e-10);
+        }
+        if x > 0.35 {
+            approx_eq(uu[m], 0.0, 1e-8);
+        }
+    });
+}
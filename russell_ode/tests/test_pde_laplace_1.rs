@@ -67,7 +67,12 @@ fn test_pde_laplace_1() {
 
     // solve the linear system
     let mut mat = SparseMatrix::from_coo(aa);
-    let mut solver = LinSolver::new(Genie::Umfpack).unwrap();
+    let genie = if cfg!(feature = "with_suitesparse") {
+        Genie::Umfpack
+    } else {
+        Genie::Native
+    };
+    let mut solver = LinSolver::new(genie).unwrap();
     solver.actual.factorize(&mut mat, None).unwrap();
     solver.actual.solve(&mut x, &mut mat, &b, false).unwrap();
 
@@ -60,7 +60,12 @@ fn test_pde_poisson_3() {
 
     // solve the linear system
     let mut mat = SparseMatrix::from_coo(aa);
-    let mut solver = LinSolver::new(Genie::Umfpack).unwrap();
+    let genie = if cfg!(feature = "with_suitesparse") {
+        Genie::Umfpack
+    } else {
+        Genie::Native
+    };
+    let mut solver = LinSolver::new(genie).unwrap();
     solver.actual.factorize(&mut mat, None).unwrap();
     solver.actual.solve(&mut phi, &mut mat, &rhs, false).unwrap();
 
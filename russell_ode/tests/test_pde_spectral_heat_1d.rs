@@ -0,0 +1,45 @@
+use russell_lab::math::PI;
+use russell_lab::{approx_eq, Vector};
+use russell_ode::{Method, NoArgs, OdeSolver, Params, PdeSpectralCollocation};
+
+#[test]
+fn test_pde_spectral_heat_1d() {
+    // Approximate (with the spectral collocation method) the solution of
+    //
+    // ∂u   ∂²u
+    // —— = ———     with x ∈ [-1, 1]
+    // ∂t   ∂x²
+    //
+    // with u(x, 0) = sin((x + 1) π) and u(-1, t) = u(1, t) = 0
+    //
+    // The analytical solution is:
+    //
+    // u(x, t) = -exp(-π² t) sin(π x)
+
+    // allocate the spectral collocation operators
+    let nn = 16;
+    let mut spc = PdeSpectralCollocation::new_1d(-1.0, 1.0, nn).unwrap();
+    spc.set_homogeneous_boundary_conditions();
+
+    // ODE system
+    let aa = spc.laplacian(1.0, 0.0);
+    let system = spc.get_system::<NoArgs>(&aa, None).unwrap();
+
+    // initial values
+    let mut uu = Vector::new(spc.dim());
+    spc.loop_over_grid_points(|m, x, _| uu[m] = f64::sin((x + 1.0) * PI));
+    spc.set_dirichlet_values(&mut uu).unwrap();
+
+    // solve the problem
+    let mut params = Params::new(Method::DoPri8);
+    params.set_tolerances(1e-10, 1e-10, None).unwrap();
+    let mut ode = OdeSolver::new(params, system).unwrap();
+    let (t0, t1) = (0.0, 0.1);
+    ode.solve(&mut uu, t0, t1, None, &mut 0).unwrap();
+    println!("{}", ode.stats());
+
+    // check
+    spc.loop_over_grid_points(|m, x, _| {
+        approx_eq(uu[m], -f64::exp(-PI * PI * t1) * f64::sin(PI * x), 1e-8);
+    });
+}
@@ -27,6 +27,21 @@
 #define KLU_ERROR_ANALYZE -9  // defined here
 #define KLU_ERROR_FACTOR -8   // defined here
 #define KLU_ERROR_COND_EST -7 // defined here
+#define KLU_ERROR_REFACTOR -6 // defined here
+
+// CHOLMOD -------------------------------------------------------------------------------------------
+
+#define CHOLMOD_ORDERING_BEST -2            // (code defined here) try all methods and select the best
+#define CHOLMOD_ERROR_ANALYZE -19           // defined here
+#define CHOLMOD_ERROR_FACTORIZE -18         // defined here
+#define CHOLMOD_ERROR_NOT_POSDEF -17        // defined here
+#define CHOLMOD_ERROR_SOLVE -16             // defined here
+#define CHOLMOD_ERROR_UPDATE_DOWNDATE -15   // defined here
+
+// SPQR ----------------------------------------------------------------------------------------------
+
+#define SPQR_ERROR_FACTORIZE -29 // defined here
+#define SPQR_ERROR_SOLVE -28     // defined here
 
 // MUMPS ---------------------------------------------------------------------------------------------
 
@@ -43,3 +58,12 @@
 #define MUMPS_ICNTL18_CENTRALIZED 0     // section 5.2.2, page 27
 #define MUMPS_ICNTL6_PERMUT_AUTO 7      // section 5.3, page 32
 #define MUMPS_ICNTL28_SEQUENTIAL 1      // section 5.4, page 33
+#define MUMPS_ICNTL19_NO_SCHUR 0        // Schur complement not requested
+#define MUMPS_ICNTL19_SCHUR_BY_ROWS 1   // centralized Schur complement by rows (lower triangle if symmetric)
+#define MUMPS_ICNTL26_NO_REDUCTION 0    // standard solution phase
+#define MUMPS_ICNTL26_CONDENSATION 1    // computes the reduced right-hand side on the Schur variables
+#define MUMPS_ICNTL26_EXPANSION 2       // expands the solution on the Schur variables to the full solution
+#define MUMPS_ICNTL20_DENSE_RHS 0       // the right-hand side is dense
+#define MUMPS_ICNTL20_SPARSE_RHS 1      // the right-hand side is sparse (the sparsity is exploited automatically)
+#define MUMPS_ICNTL25_NORMAL_SOLUTION 0 // standard solution phase (no null space)
+#define MUMPS_ICNTL25_NULL_SPACE -1     // computes all the null space basis vectors (needs ICNTL(24) = 1)
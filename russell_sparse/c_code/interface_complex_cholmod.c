@@ -0,0 +1,240 @@
+#include <inttypes.h>
+#include <math.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "cholmod.h"
+
+#include "constants.h"
+
+/// @brief Holds the data for CHOLMOD
+struct InterfaceComplexCHOLMOD {
+    /// @brief Holds control parameters and statistics
+    cholmod_common common;
+
+    /// @brief Holds the symbolic and numeric factors
+    cholmod_factor *factor;
+
+    /// @brief Holds the symmetric type (stype) of the matrix
+    int32_t stype;
+
+    /// @brief indicates that the initialization has been completed
+    C_BOOL initialization_completed;
+
+    /// @brief Indicates that the factorization (at least once) has been completed
+    C_BOOL factorization_completed;
+};
+
+/// @brief Sets a CHOLMOD sparse matrix (header only) pointing to the CSC arrays
+static void set_cholmod_sparse(cholmod_sparse *a,
+                               int32_t nrow,
+                               int32_t ncol,
+                               int32_t stype,
+                               const int32_t *col_pointers,
+                               const int32_t *row_indices,
+                               const COMPLEX64 *values) {
+    memset(a, 0, sizeof(cholmod_sparse));
+    a->nrow = nrow;
+    a->ncol = ncol;
+    a->nzmax = col_pointers[ncol];
+    a->p = (void *)col_pointers;
+    a->i = (void *)row_indices;
+    a->x = (void *)values;
+    a->stype = stype;
+    a->itype = CHOLMOD_INT;
+    a->xtype = values == NULL ? CHOLMOD_PATTERN : CHOLMOD_COMPLEX;
+    a->dtype = CHOLMOD_DOUBLE;
+    a->sorted = C_TRUE;
+    a->packed = C_TRUE;
+}
+
+/// @brief Sets a CHOLMOD dense matrix (header only) pointing to a vector
+static void set_cholmod_dense(cholmod_dense *b, int32_t ndim, COMPLEX64 *values) {
+    memset(b, 0, sizeof(cholmod_dense));
+    b->nrow = ndim;
+    b->ncol = 1;
+    b->nzmax = ndim;
+    b->d = ndim;
+    b->x = (void *)values;
+    b->xtype = CHOLMOD_COMPLEX;
+    b->dtype = CHOLMOD_DOUBLE;
+}
+
+/// @brief Allocates a new CHOLMOD interface
+struct InterfaceComplexCHOLMOD *complex_solver_cholmod_new() {
+    struct InterfaceComplexCHOLMOD *solver = (struct InterfaceComplexCHOLMOD *)malloc(sizeof(struct InterfaceComplexCHOLMOD));
+
+    if (solver == NULL) {
+        return NULL;
+    }
+
+    solver->factor = NULL;
+    solver->stype = 0;
+    solver->initialization_completed = C_FALSE;
+    solver->factorization_completed = C_FALSE;
+
+    return solver;
+}
+
+/// @brief Deallocates the CHOLMOD interface
+void complex_solver_cholmod_drop(struct InterfaceComplexCHOLMOD *solver) {
+    if (solver == NULL) {
+        return;
+    }
+
+    if (solver->initialization_completed == C_TRUE) {
+        if (solver->factor != NULL) {
+            cholmod_free_factor(&solver->factor, &solver->common);
+        }
+        cholmod_finish(&solver->common);
+    }
+
+    free(solver);
+}
+
+/// @brief Performs the symbolic factorization
+int32_t complex_solver_cholmod_initialize(struct InterfaceComplexCHOLMOD *solver,
+                                          int32_t ordering,
+                                          int32_t supernodal,
+                                          int32_t stype,
+                                          int32_t ndim,
+                                          const int32_t *col_pointers,
+                                          const int32_t *row_indices) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->initialization_completed == C_TRUE) {
+        return ERROR_ALREADY_INITIALIZED;
+    }
+
+    cholmod_start(&solver->common);
+    solver->initialization_completed = C_TRUE;
+
+    solver->common.print = 0;
+    solver->common.final_ll = C_TRUE; // always keep L·Lᴴ (needed by the partial solves)
+
+    if (supernodal >= 0) {
+        solver->common.supernodal = supernodal;
+    }
+
+    if (ordering == CHOLMOD_ORDERING_BEST) {
+        solver->common.nmethods = CHOLMOD_MAXMETHODS;
+    } else if (ordering >= 0) {
+        solver->common.nmethods = 1;
+        solver->common.method[0].ordering = ordering;
+        solver->common.postorder = C_TRUE;
+    }
+
+    cholmod_sparse a;
+    set_cholmod_sparse(&a, ndim, ndim, stype, col_pointers, row_indices, NULL);
+    solver->stype = stype;
+
+    solver->factor = cholmod_analyze(&a, &solver->common);
+    if (solver->factor == NULL) {
+        return CHOLMOD_ERROR_ANALYZE;
+    }
+
+    return SUCCESSFUL_EXIT;
+}
+
+/// @brief Performs the numeric factorization
+int32_t complex_solver_cholmod_factorize(struct InterfaceComplexCHOLMOD *solver,
+                                         int32_t *effective_ordering,
+                                         C_BOOL *effective_supernodal,
+                                         int32_t ndim,
+                                         const int32_t *col_pointers,
+                                         const int32_t *row_indices,
+                                         const COMPLEX64 *values) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->initialization_completed == C_FALSE) {
+        return ERROR_NEED_INITIALIZATION;
+    }
+
+    cholmod_sparse a;
+    set_cholmod_sparse(&a, ndim, ndim, solver->stype, col_pointers, row_indices, values);
+
+    // remove "const" here assuming that cholmod will not change those variables
+    int status = cholmod_factorize(&a, solver->factor, &solver->common);
+    if (status == C_FALSE) {
+        return CHOLMOD_ERROR_FACTORIZE;
+    }
+    if (solver->common.status == CHOLMOD_NOT_POSDEF) {
+        return CHOLMOD_ERROR_NOT_POSDEF;
+    }
+
+    // save ordering and factorization type
+    *effective_ordering = solver->factor->ordering;
+    *effective_supernodal = solver->factor->is_super ? C_TRUE : C_FALSE;
+
+    solver->factorization_completed = C_TRUE;
+
+    return SUCCESSFUL_EXIT;
+}
+
+/// @brief Computes the solution of a linear system (A, L, Lᴴ, P, or Pᵀ)
+int32_t complex_solver_cholmod_solve(struct InterfaceComplexCHOLMOD *solver,
+                                     int32_t system,
+                                     int32_t ndim,
+                                     COMPLEX64 *x,
+                                     const COMPLEX64 *rhs) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->factorization_completed == C_FALSE) {
+        return ERROR_NEED_FACTORIZATION;
+    }
+
+    cholmod_dense b;
+    set_cholmod_dense(&b, ndim, (COMPLEX64 *)rhs);
+
+    cholmod_dense *sol = cholmod_solve(system, solver->factor, &b, &solver->common);
+    if (sol == NULL) {
+        return CHOLMOD_ERROR_SOLVE;
+    }
+
+    memcpy(x, sol->x, 2 * ndim * sizeof(double));
+    cholmod_free_dense(&sol, &solver->common);
+
+    return SUCCESSFUL_EXIT;
+}
+
+/// @brief Computes the natural logarithm of the determinant of A = L·Lᴴ
+int32_t complex_solver_cholmod_log_det(struct InterfaceComplexCHOLMOD *solver,
+                                       double *log_det) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->factorization_completed == C_FALSE) {
+        return ERROR_NEED_FACTORIZATION;
+    }
+
+    cholmod_factor *l = solver->factor;
+    double *lx = (double *)l->x;
+    double sum = 0.0;
+    if (l->is_super) {
+        int32_t *super = (int32_t *)l->super;
+        int32_t *pi = (int32_t *)l->pi;
+        int32_t *px = (int32_t *)l->px;
+        for (size_t s = 0; s < l->nsuper; s++) {
+            int32_t ncols = super[s + 1] - super[s];
+            int32_t nrows = pi[s + 1] - pi[s];
+            for (int32_t j = 0; j < ncols; j++) {
+                sum += log(lx[2 * (px[s] + j + j * nrows)]); // the diagonal of L is real
+            }
+        }
+    } else {
+        int32_t *lp = (int32_t *)l->p;
+        for (size_t j = 0; j < l->n; j++) {
+            sum += log(lx[2 * lp[j]]); // the diagonal of L is real
+        }
+    }
+    *log_det = 2.0 * sum; // det(A) = |det(L)|²
+
+    return SUCCESSFUL_EXIT;
+}
@@ -151,9 +151,55 @@ int32_t complex_solver_klu_factorize(struct InterfaceComplexKLU *solver,
     return SUCCESSFUL_EXIT;
 }
 
-/// @brief Computes the solution of the linear system
+/// @brief Performs the numeric refactorization (reusing the pivot sequence of the previous factorization)
+int32_t complex_solver_klu_refactorize(struct InterfaceComplexKLU *solver,
+                                       double *cond_estimate,
+                                       C_BOOL compute_cond,
+                                       const int32_t *col_pointers,
+                                       const int32_t *row_indices,
+                                       const COMPLEX64 *values) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->factorization_completed == C_FALSE) {
+        return ERROR_NEED_FACTORIZATION;
+    }
+
+    // remove "const" here assuming that klu will not change those variables
+    int status = klu_z_refactor((int32_t *)col_pointers,
+                                (int32_t *)row_indices,
+                                (COMPLEX64 *)values,
+                                solver->symbolic,
+                                solver->numeric,
+                                &solver->common);
+    if (status == C_FALSE) {
+        return KLU_ERROR_REFACTOR;
+    }
+
+    // reciprocal condition number estimate
+    if (compute_cond == C_TRUE) {
+        status = klu_z_condest((int32_t *)col_pointers,
+                               (COMPLEX64 *)values,
+                               solver->symbolic,
+                               solver->numeric,
+                               &solver->common);
+        if (status == C_FALSE) {
+            return KLU_ERROR_COND_EST;
+        }
+        *cond_estimate = solver->common.condest;
+    }
+
+    return SUCCESSFUL_EXIT;
+}
+
+/// @brief Computes the solution of the linear system A·X = RHS (or Aᵀ·X = RHS or Aᴴ·X = RHS if transpose)
+/// @param nrhs number of right-hand sides (columns of the col-major ndim × nrhs array)
 int32_t complex_solver_klu_solve(struct InterfaceComplexKLU *solver,
                                  int32_t ndim,
+                                 int32_t nrhs,
+                                 C_BOOL transpose,
+                                 C_BOOL conjugate,
                                  COMPLEX64 *in_rhs_out_x) {
     if (solver == NULL) {
         return ERROR_NULL_POINTER;
@@ -163,12 +209,22 @@ int32_t complex_solver_klu_solve(struct InterfaceComplexKLU *solver,
         return ERROR_NEED_FACTORIZATION;
     }
 
-    klu_z_solve(solver->symbolic,
-                solver->numeric,
-                ndim,
-                1,
-                in_rhs_out_x,
-                &solver->common);
+    if (transpose == C_TRUE) {
+        klu_z_tsolve(solver->symbolic,
+                     solver->numeric,
+                     ndim,
+                     nrhs,
+                     in_rhs_out_x,
+                     conjugate,
+                     &solver->common);
+    } else {
+        klu_z_solve(solver->symbolic,
+                    solver->numeric,
+                    ndim,
+                    nrhs,
+                    in_rhs_out_x,
+                    &solver->common);
+    }
 
     return SUCCESSFUL_EXIT;
 }
@@ -162,7 +162,9 @@ int32_t complex_solver_mumps_initialize(struct InterfaceComplexMUMPS *solver,
 }
 
 /// @brief Performs the factorization
+/// @param values_aij the values of the matrix (with the same structure as the one given to initialize)
 int32_t complex_solver_mumps_factorize(struct InterfaceComplexMUMPS *solver,
+                                       ZMUMPS_COMPLEX const *values_aij,
                                        int32_t *effective_ordering,
                                        int32_t *effective_scaling,
                                        double *determinant_coefficient_real,
@@ -178,6 +180,10 @@ int32_t complex_solver_mumps_factorize(struct InterfaceComplexMUMPS *solver,
         return ERROR_NEED_INITIALIZATION;
     }
 
+    // update the values (the structure is the same as the one given to initialize)
+
+    solver->data.a = (ZMUMPS_COMPLEX *)values_aij;
+
     // handle requests
 
     if (compute_determinant == C_TRUE) {
@@ -215,11 +221,16 @@ int32_t complex_solver_mumps_factorize(struct InterfaceComplexMUMPS *solver,
     return solver->data.INFOG(1);
 }
 
-/// @brief Computes the solution of the linear system
+/// @brief Computes the solution of the linear system A·X = RHS (or Aᵀ·X = RHS if transpose)
+/// @param rhs col-major (ndim × nrhs) array with the right-hand sides (input) and the solution (output)
+/// @param nrhs number of right-hand sides
+/// @param transpose solves Aᵀ·X = RHS (ICNTL(9) ≠ 1)
 /// @param error_analysis_array_len_8 array of size 8 to hold the results from the error analysis
 /// @param error_analysis_option ICNTL(11): 0 (nothing), 1 (all; slow), 2 (just errors)
 int32_t complex_solver_mumps_solve(struct InterfaceComplexMUMPS *solver,
                                    ZMUMPS_COMPLEX *rhs,
+                                   int32_t nrhs,
+                                   C_BOOL transpose,
                                    double *error_analysis_array_len_8,
                                    int32_t error_analysis_option,
                                    C_BOOL verbose) {
@@ -231,9 +242,16 @@ int32_t complex_solver_mumps_solve(struct InterfaceComplexMUMPS *solver,
         return ERROR_NEED_FACTORIZATION;
     }
 
+    // the error analysis is only available with a single right-hand side
+    if (nrhs > 1) {
+        error_analysis_option = 0;
+    }
     solver->data.ICNTL(11) = error_analysis_option;
+    solver->data.ICNTL(9) = transpose == C_TRUE ? 0 : 1;
 
     solver->data.rhs = rhs;
+    solver->data.nrhs = nrhs;
+    solver->data.lrhs = solver->data.n;
 
     set_mumps_verbose(&solver->data, verbose);
     solver->data.job = MUMPS_JOB_SOLVE;
@@ -254,3 +272,52 @@ int32_t complex_solver_mumps_solve(struct InterfaceComplexMUMPS *solver,
 
     return solver->data.INFOG(1);
 }
+
+/// @brief Computes the solution of the linear system A·X = RHS (or Aᵀ·X = RHS if transpose) with a sparse RHS (ICNTL(20) = 1)
+/// @param x col-major (ndim × nrhs) array to hold the solution (output)
+/// @param nrhs number of right-hand sides (number of columns of RHS)
+/// @param nz_rhs number of non-zeros of RHS
+/// @param irhs_ptr (one-based) column pointers of RHS (size = nrhs + 1)
+/// @param irhs_sparse (one-based) row indices of RHS (size = nz_rhs)
+/// @param rhs_sparse values of RHS (size = nz_rhs)
+/// @param transpose solves Aᵀ·X = RHS (ICNTL(9) ≠ 1)
+int32_t complex_solver_mumps_solve_sparse_rhs(struct InterfaceComplexMUMPS *solver,
+                                        ZMUMPS_COMPLEX *x,
+                                        int32_t nrhs,
+                                        int32_t nz_rhs,
+                                        int32_t const *irhs_ptr,
+                                        int32_t const *irhs_sparse,
+                                        ZMUMPS_COMPLEX const *rhs_sparse,
+                                        C_BOOL transpose,
+                                        C_BOOL verbose) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->factorization_completed == C_FALSE) {
+        return ERROR_NEED_FACTORIZATION;
+    }
+
+    solver->data.ICNTL(11) = 0;
+    solver->data.ICNTL(9) = transpose == C_TRUE ? 0 : 1;
+    solver->data.ICNTL(20) = MUMPS_ICNTL20_SPARSE_RHS;
+
+    solver->data.rhs = x;
+    solver->data.nrhs = nrhs;
+    solver->data.lrhs = solver->data.n;
+    solver->data.nz_rhs = nz_rhs;
+    solver->data.irhs_ptr = (int *)irhs_ptr;
+    solver->data.irhs_sparse = (int *)irhs_sparse;
+    solver->data.rhs_sparse = (ZMUMPS_COMPLEX *)rhs_sparse;
+
+    set_mumps_verbose(&solver->data, verbose);
+    solver->data.job = MUMPS_JOB_SOLVE;
+    zmumps_c(&solver->data);
+
+    solver->data.ICNTL(20) = MUMPS_ICNTL20_DENSE_RHS;
+    solver->data.irhs_ptr = NULL;
+    solver->data.irhs_sparse = NULL;
+    solver->data.rhs_sparse = NULL;
+
+    return solver->data.INFOG(1);
+}
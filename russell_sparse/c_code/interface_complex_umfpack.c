@@ -183,13 +183,15 @@ int32_t complex_solver_umfpack_factorize(struct InterfaceComplexUMFPACK *solver,
     return code;
 }
 
-/// @brief Computes the solution of the linear system
+/// @brief Computes the solution of the linear system A·x = rhs (or Aᵀ·x = rhs or Aᴴ·x = rhs if transpose)
 int32_t complex_solver_umfpack_solve(struct InterfaceComplexUMFPACK *solver,
                                      COMPLEX64 *x,
                                      const COMPLEX64 *rhs,
                                      const int32_t *col_pointers,
                                      const int32_t *row_indices,
                                      const COMPLEX64 *values,
+                                     C_BOOL transpose,
+                                     C_BOOL conjugate,
                                      C_BOOL verbose) {
     if (solver == NULL) {
         return ERROR_NULL_POINTER;
@@ -201,7 +203,13 @@ int32_t complex_solver_umfpack_solve(struct InterfaceComplexUMFPACK *solver,
 
     set_complex_umfpack_verbose(solver, verbose);
 
-    int code = umfpack_zi_solve(UMFPACK_A,
+    // note: UMFPACK_At is the conjugate transpose and UMFPACK_Aat is the array transpose
+    int32_t sys = UMFPACK_A;
+    if (transpose == C_TRUE) {
+        sys = conjugate == C_TRUE ? UMFPACK_At : UMFPACK_Aat;
+    }
+
+    int code = umfpack_zi_solve(sys,
                                 col_pointers,
                                 row_indices,
                                 values,
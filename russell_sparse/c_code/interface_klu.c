@@ -151,9 +151,54 @@ int32_t solver_klu_factorize(struct InterfaceKLU *solver,
     return SUCCESSFUL_EXIT;
 }
 
-/// @brief Computes the solution of the linear system
+/// @brief Performs the numeric refactorization (reusing the pivot sequence of the previous factorization)
+int32_t solver_klu_refactorize(struct InterfaceKLU *solver,
+                               double *cond_estimate,
+                               C_BOOL compute_cond,
+                               const int32_t *col_pointers,
+                               const int32_t *row_indices,
+                               const double *values) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->factorization_completed == C_FALSE) {
+        return ERROR_NEED_FACTORIZATION;
+    }
+
+    // remove "const" here assuming that klu will not change those variables
+    int status = klu_refactor((int32_t *)col_pointers,
+                              (int32_t *)row_indices,
+                              (double *)values,
+                              solver->symbolic,
+                              solver->numeric,
+                              &solver->common);
+    if (status == C_FALSE) {
+        return KLU_ERROR_REFACTOR;
+    }
+
+    // reciprocal condition number estimate
+    if (compute_cond == C_TRUE) {
+        status = klu_condest((int32_t *)col_pointers,
+                             (double *)values,
+                             solver->symbolic,
+                             solver->numeric,
+                             &solver->common);
+        if (status == C_FALSE) {
+            return KLU_ERROR_COND_EST;
+        }
+        *cond_estimate = solver->common.condest;
+    }
+
+    return SUCCESSFUL_EXIT;
+}
+
+/// @brief Computes the solution of the linear system A·X = RHS (or Aᵀ·X = RHS if transpose)
+/// @param nrhs number of right-hand sides (columns of the col-major ndim × nrhs array)
 int32_t solver_klu_solve(struct InterfaceKLU *solver,
                          int32_t ndim,
+                         int32_t nrhs,
+                         C_BOOL transpose,
                          double *in_rhs_out_x) {
     if (solver == NULL) {
         return ERROR_NULL_POINTER;
@@ -163,12 +208,21 @@ int32_t solver_klu_solve(struct InterfaceKLU *solver,
         return ERROR_NEED_FACTORIZATION;
     }
 
-    klu_solve(solver->symbolic,
-              solver->numeric,
-              ndim,
-              1,
-              in_rhs_out_x,
-              &solver->common);
+    if (transpose == C_TRUE) {
+        klu_tsolve(solver->symbolic,
+                   solver->numeric,
+                   ndim,
+                   nrhs,
+                   in_rhs_out_x,
+                   &solver->common);
+    } else {
+        klu_solve(solver->symbolic,
+                  solver->numeric,
+                  ndim,
+                  nrhs,
+                  in_rhs_out_x,
+                  &solver->common);
+    }
 
     return SUCCESSFUL_EXIT;
 }
@@ -8,6 +8,7 @@
 #include "constants.h"
 
 #define ICNTL(i) icntl[(i)-1]   // macro to make indices match documentation
+#define CNTL(i) cntl[(i)-1]     // macro to make indices match documentation
 #define RINFOG(i) rinfog[(i)-1] // macro to make indices match documentation
 #define INFOG(i) infog[(i)-1]   // macro to make indices match documentation
 #define INFO(i) info[(i)-1]     // macro to make indices match documentation
@@ -53,6 +54,9 @@ struct InterfaceMUMPS *solver_mumps_new() {
     solver->data.irn = NULL;
     solver->data.jcn = NULL;
     solver->data.a = NULL;
+    solver->data.listvar_schur = NULL;
+    solver->data.schur = NULL;
+    solver->data.redrhs = NULL;
     solver->done_job_init = C_FALSE;
     solver->initialization_completed = C_FALSE;
     solver->factorization_completed = C_FALSE;
@@ -70,6 +74,9 @@ void solver_mumps_drop(struct InterfaceMUMPS *solver) {
     solver->data.irn = NULL;
     solver->data.jcn = NULL;
     solver->data.a = NULL;
+    solver->data.listvar_schur = NULL;
+    solver->data.schur = NULL;
+    solver->data.redrhs = NULL;
 
     if (solver->done_job_init == C_TRUE) {
         set_mumps_verbose(&solver->data, C_FALSE);
@@ -81,6 +88,9 @@ void solver_mumps_drop(struct InterfaceMUMPS *solver) {
 }
 
 /// @brief Perform analysis just once (considering that the matrix structure remains constant)
+/// @param size_schur number of Schur variables (zero disables the Schur complement)
+/// @param listvar_schur (one-based) indices of the Schur variables
+/// @param schur (size_schur × size_schur) array to hold the Schur complement (by rows) after the factorization
 int32_t solver_mumps_initialize(struct InterfaceMUMPS *solver,
                                 int32_t ordering,
                                 int32_t scaling,
@@ -94,7 +104,10 @@ int32_t solver_mumps_initialize(struct InterfaceMUMPS *solver,
                                 int32_t nnz,
                                 int32_t const *indices_i,
                                 int32_t const *indices_j,
-                                double const *values_aij) {
+                                double const *values_aij,
+                                int32_t size_schur,
+                                int32_t const *listvar_schur,
+                                double *schur) {
     if (solver == NULL) {
         return ERROR_NULL_POINTER;
     }
@@ -148,6 +161,15 @@ int32_t solver_mumps_initialize(struct InterfaceMUMPS *solver,
     solver->data.jcn = (int *)indices_j;
     solver->data.a = (double *)values_aij;
 
+    if (size_schur > 0) {
+        solver->data.ICNTL(19) = MUMPS_ICNTL19_SCHUR_BY_ROWS;
+        solver->data.size_schur = size_schur;
+        solver->data.listvar_schur = (int *)listvar_schur;
+        solver->data.schur = schur;
+    } else {
+        solver->data.ICNTL(19) = MUMPS_ICNTL19_NO_SCHUR;
+    }
+
     set_mumps_verbose(&solver->data, verbose);
     solver->data.job = MUMPS_JOB_ANALYZE;
     dmumps_c(&solver->data);
@@ -162,12 +184,20 @@ int32_t solver_mumps_initialize(struct InterfaceMUMPS *solver,
 }
 
 /// @brief Performs the factorization
+/// @param values_aij the values of the matrix (with the same structure as the one given to initialize)
+/// @param null_pivot_detection enables the null pivot detection (ICNTL(24))
+/// @param null_pivot_threshold threshold relative to the largest absolute value of the matrix (CNTL(3))
+/// @param number_of_null_pivots holds the number of null pivots found (INFOG(28))
 int32_t solver_mumps_factorize(struct InterfaceMUMPS *solver,
+                               double const *values_aij,
                                int32_t *effective_ordering,
                                int32_t *effective_scaling,
                                double *determinant_coefficient,
                                double *determinant_exponent,
                                C_BOOL compute_determinant,
+                               C_BOOL null_pivot_detection,
+                               double null_pivot_threshold,
+                               int32_t *number_of_null_pivots,
                                C_BOOL verbose) {
     if (solver == NULL) {
         return ERROR_NULL_POINTER;
@@ -177,6 +207,10 @@ int32_t solver_mumps_factorize(struct InterfaceMUMPS *solver,
         return ERROR_NEED_INITIALIZATION;
     }
 
+    // update the values (the structure is the same as the one given to initialize)
+
+    solver->data.a = (double *)values_aij;
+
     // handle requests
 
     if (compute_determinant == C_TRUE) {
@@ -186,6 +220,13 @@ int32_t solver_mumps_factorize(struct InterfaceMUMPS *solver,
         solver->data.ICNTL(33) = 0;
     }
 
+    if (null_pivot_detection == C_TRUE) {
+        solver->data.ICNTL(24) = 1;
+        solver->data.CNTL(3) = null_pivot_threshold; // positive => relative to the largest absolute value
+    } else {
+        solver->data.ICNTL(24) = 0;
+    }
+
     // perform factorization
 
     set_mumps_verbose(&solver->data, verbose);
@@ -196,6 +237,7 @@ int32_t solver_mumps_factorize(struct InterfaceMUMPS *solver,
 
     *effective_ordering = solver->data.INFOG(7);
     *effective_scaling = solver->data.INFOG(33);
+    *number_of_null_pivots = null_pivot_detection == C_TRUE ? solver->data.INFOG(28) : 0;
 
     // read the determinant
 
@@ -212,11 +254,16 @@ int32_t solver_mumps_factorize(struct InterfaceMUMPS *solver,
     return solver->data.INFOG(1);
 }
 
-/// @brief Computes the solution of the linear system
+/// @brief Computes the solution of the linear system A·X = RHS (or Aᵀ·X = RHS if transpose)
+/// @param rhs col-major (ndim × nrhs) array with the right-hand sides (input) and the solution (output)
+/// @param nrhs number of right-hand sides
+/// @param transpose solves Aᵀ·X = RHS (ICNTL(9) ≠ 1)
 /// @param error_analysis_array_len_8 array of size 8 to hold the results from the error analysis
 /// @param error_analysis_option ICNTL(11): 0 (nothing), 1 (all; slow), 2 (just errors)
 int32_t solver_mumps_solve(struct InterfaceMUMPS *solver,
                            double *rhs,
+                           int32_t nrhs,
+                           C_BOOL transpose,
                            double *error_analysis_array_len_8,
                            int32_t error_analysis_option,
                            C_BOOL verbose) {
@@ -228,9 +275,18 @@ int32_t solver_mumps_solve(struct InterfaceMUMPS *solver,
         return ERROR_NEED_FACTORIZATION;
     }
 
+    // the error analysis is only available with a single right-hand side
+    if (nrhs > 1) {
+        error_analysis_option = 0;
+    }
     solver->data.ICNTL(11) = error_analysis_option;
+    solver->data.ICNTL(9) = transpose == C_TRUE ? 0 : 1;
+    solver->data.ICNTL(26) = MUMPS_ICNTL26_NO_REDUCTION;
+    solver->data.ICNTL(25) = MUMPS_ICNTL25_NORMAL_SOLUTION;
 
     solver->data.rhs = rhs;
+    solver->data.nrhs = nrhs;
+    solver->data.lrhs = solver->data.n;
 
     set_mumps_verbose(&solver->data, verbose);
     solver->data.job = MUMPS_JOB_SOLVE;
@@ -251,3 +307,121 @@ int32_t solver_mumps_solve(struct InterfaceMUMPS *solver,
 
     return solver->data.INFOG(1);
 }
+
+/// @brief Performs the condensation (reduction) or expansion step of the solution with the Schur complement
+/// @param rhs (ndim) right-hand side (condensation) or solution (expansion); must not be modified between the steps
+/// @param reduced_rhs (size_schur) reduced right-hand side (output of condensation) or the solution on the Schur variables (input of expansion)
+/// @param expansion performs the expansion step (ICNTL(26) = 2) instead of the condensation step (ICNTL(26) = 1)
+int32_t solver_mumps_solve_schur(struct InterfaceMUMPS *solver,
+                                 double *rhs,
+                                 double *reduced_rhs,
+                                 C_BOOL expansion,
+                                 C_BOOL verbose) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->factorization_completed == C_FALSE) {
+        return ERROR_NEED_FACTORIZATION;
+    }
+
+    solver->data.ICNTL(11) = 0;
+    solver->data.ICNTL(9) = 1;
+    solver->data.ICNTL(26) = expansion == C_TRUE ? MUMPS_ICNTL26_EXPANSION : MUMPS_ICNTL26_CONDENSATION;
+
+    solver->data.rhs = rhs;
+    solver->data.nrhs = 1;
+    solver->data.lrhs = solver->data.n;
+    solver->data.redrhs = reduced_rhs;
+    solver->data.lredrhs = solver->data.size_schur;
+
+    set_mumps_verbose(&solver->data, verbose);
+    solver->data.job = MUMPS_JOB_SOLVE;
+    dmumps_c(&solver->data);
+
+    solver->data.ICNTL(26) = MUMPS_ICNTL26_NO_REDUCTION;
+    solver->data.redrhs = NULL;
+
+    return solver->data.INFOG(1);
+}
+
+/// @brief Computes a basis of the (right) null space of A after the factorization with null pivot detection
+/// @param basis col-major (ndim × num_null) array to hold the basis vectors (output); they are not orthonormal
+/// @param num_null number of null pivots found by the factorization (INFOG(28))
+int32_t solver_mumps_null_space(struct InterfaceMUMPS *solver, double *basis, int32_t num_null, C_BOOL verbose) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->factorization_completed == C_FALSE) {
+        return ERROR_NEED_FACTORIZATION;
+    }
+
+    solver->data.ICNTL(11) = 0;
+    solver->data.ICNTL(9) = 1;
+    solver->data.ICNTL(26) = MUMPS_ICNTL26_NO_REDUCTION;
+    solver->data.ICNTL(25) = MUMPS_ICNTL25_NULL_SPACE;
+
+    solver->data.rhs = basis;
+    solver->data.nrhs = num_null;
+    solver->data.lrhs = solver->data.n;
+
+    set_mumps_verbose(&solver->data, verbose);
+    solver->data.job = MUMPS_JOB_SOLVE;
+    dmumps_c(&solver->data);
+
+    solver->data.ICNTL(25) = MUMPS_ICNTL25_NORMAL_SOLUTION;
+
+    return solver->data.INFOG(1);
+}
+
+/// @brief Computes the solution of the linear system A·X = RHS (or Aᵀ·X = RHS if transpose) with a sparse RHS (ICNTL(20) = 1)
+/// @param x col-major (ndim × nrhs) array to hold the solution (output)
+/// @param nrhs number of right-hand sides (number of columns of RHS)
+/// @param nz_rhs number of non-zeros of RHS
+/// @param irhs_ptr (one-based) column pointers of RHS (size = nrhs + 1)
+/// @param irhs_sparse (one-based) row indices of RHS (size = nz_rhs)
+/// @param rhs_sparse values of RHS (size = nz_rhs)
+/// @param transpose solves Aᵀ·X = RHS (ICNTL(9) ≠ 1)
+int32_t solver_mumps_solve_sparse_rhs(struct InterfaceMUMPS *solver,
+                                double *x,
+                                int32_t nrhs,
+                                int32_t nz_rhs,
+                                int32_t const *irhs_ptr,
+                                int32_t const *irhs_sparse,
+                                double const *rhs_sparse,
+                                C_BOOL transpose,
+                                C_BOOL verbose) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->factorization_completed == C_FALSE) {
+        return ERROR_NEED_FACTORIZATION;
+    }
+
+    solver->data.ICNTL(11) = 0;
+    solver->data.ICNTL(9) = transpose == C_TRUE ? 0 : 1;
+    solver->data.ICNTL(20) = MUMPS_ICNTL20_SPARSE_RHS;
+    solver->data.ICNTL(26) = MUMPS_ICNTL26_NO_REDUCTION;
+    solver->data.ICNTL(25) = MUMPS_ICNTL25_NORMAL_SOLUTION;
+
+    solver->data.rhs = x;
+    solver->data.nrhs = nrhs;
+    solver->data.lrhs = solver->data.n;
+    solver->data.nz_rhs = nz_rhs;
+    solver->data.irhs_ptr = (int *)irhs_ptr;
+    solver->data.irhs_sparse = (int *)irhs_sparse;
+    solver->data.rhs_sparse = (double *)rhs_sparse;
+
+    set_mumps_verbose(&solver->data, verbose);
+    solver->data.job = MUMPS_JOB_SOLVE;
+    dmumps_c(&solver->data);
+
+    solver->data.ICNTL(20) = MUMPS_ICNTL20_DENSE_RHS;
+    solver->data.irhs_ptr = NULL;
+    solver->data.irhs_sparse = NULL;
+    solver->data.rhs_sparse = NULL;
+
+    return solver->data.INFOG(1);
+}
@@ -0,0 +1,190 @@
+#include <inttypes.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "SuiteSparseQR_C.h"
+
+#include "constants.h"
+
+/// @brief Holds the data for SPQR
+struct InterfaceSPQR {
+    /// @brief Holds control parameters and statistics
+    cholmod_common common;
+
+    /// @brief Holds the Q·R factorization (Q is stored as Householder vectors)
+    SuiteSparseQR_C_factorization *factors;
+
+    /// @brief Holds the number of rows of the factorized matrix
+    int32_t nrow;
+
+    /// @brief Holds the number of columns of the factorized matrix
+    int32_t ncol;
+
+    /// @brief indicates that the initialization has been completed
+    C_BOOL initialization_completed;
+
+    /// @brief Indicates that the factorization (at least once) has been completed
+    C_BOOL factorization_completed;
+};
+
+/// @brief Sets a CHOLMOD sparse matrix (header only) pointing to the CSC arrays
+/// @note The SuiteSparseQR C API only accepts int64 (SuiteSparse_long) indices
+static void set_spqr_sparse(cholmod_sparse *a,
+                            int32_t nrow,
+                            int32_t ncol,
+                            const int64_t *col_pointers,
+                            const int64_t *row_indices,
+                            const double *values) {
+    memset(a, 0, sizeof(cholmod_sparse));
+    a->nrow = nrow;
+    a->ncol = ncol;
+    a->nzmax = col_pointers[ncol];
+    a->p = (void *)col_pointers;
+    a->i = (void *)row_indices;
+    a->x = (void *)values;
+    a->stype = 0;
+    a->itype = CHOLMOD_LONG;
+    a->xtype = CHOLMOD_REAL;
+    a->dtype = CHOLMOD_DOUBLE;
+    a->sorted = C_TRUE;
+    a->packed = C_TRUE;
+}
+
+/// @brief Sets a CHOLMOD dense matrix (header only) pointing to a vector
+static void set_spqr_dense(cholmod_dense *b, int32_t dim, double *values) {
+    memset(b, 0, sizeof(cholmod_dense));
+    b->nrow = dim;
+    b->ncol = 1;
+    b->nzmax = dim;
+    b->d = dim;
+    b->x = (void *)values;
+    b->xtype = CHOLMOD_REAL;
+    b->dtype = CHOLMOD_DOUBLE;
+}
+
+/// @brief Allocates a new SPQR interface
+struct InterfaceSPQR *solver_spqr_new() {
+    struct InterfaceSPQR *solver = (struct InterfaceSPQR *)malloc(sizeof(struct InterfaceSPQR));
+
+    if (solver == NULL) {
+        return NULL;
+    }
+
+    solver->factors = NULL;
+    solver->nrow = 0;
+    solver->ncol = 0;
+    solver->initialization_completed = C_FALSE;
+    solver->factorization_completed = C_FALSE;
+
+    return solver;
+}
+
+/// @brief Deallocates the SPQR interface
+void solver_spqr_drop(struct InterfaceSPQR *solver) {
+    if (solver == NULL) {
+        return;
+    }
+
+    if (solver->initialization_completed == C_TRUE) {
+        if (solver->factors != NULL) {
+            SuiteSparseQR_C_free(&solver->factors, &solver->common);
+        }
+        cholmod_l_finish(&solver->common);
+    }
+
+    free(solver);
+}
+
+/// @brief Computes the sparse Q·R factorization (A·E = Q·R, where E is the column permutation)
+/// @param ordering SPQR ordering code
+/// @param tolerance columns of R with norm smaller than or equal to tolerance are considered null (negative => default)
+/// @param rank holds the rank estimate (output)
+/// @param col_pointers the int64 (SuiteSparse_long) column pointers of the CSC matrix
+/// @param row_indices the int64 (SuiteSparse_long) row indices of the CSC matrix
+/// @param effective_ordering holds the ordering used by SPQR (output)
+int32_t solver_spqr_factorize(struct InterfaceSPQR *solver,
+                              int32_t *rank,
+                              int32_t *effective_ordering,
+                              int32_t ordering,
+                              double tolerance,
+                              int32_t nrow,
+                              int32_t ncol,
+                              const int64_t *col_pointers,
+                              const int64_t *row_indices,
+                              const double *values) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->initialization_completed == C_FALSE) {
+        cholmod_l_start(&solver->common);
+        solver->common.print = 0;
+        solver->initialization_completed = C_TRUE;
+    }
+
+    solver->factorization_completed = C_FALSE;
+    if (solver->factors != NULL) {
+        SuiteSparseQR_C_free(&solver->factors, &solver->common);
+    }
+
+    cholmod_sparse a;
+    set_spqr_sparse(&a, nrow, ncol, col_pointers, row_indices, values);
+
+    double tol = tolerance < 0.0 ? SPQR_DEFAULT_TOL : tolerance;
+    solver->factors = SuiteSparseQR_C_factorize(ordering, tol, &a, &solver->common);
+    if (solver->factors == NULL) {
+        return SPQR_ERROR_FACTORIZE;
+    }
+
+    solver->nrow = nrow;
+    solver->ncol = ncol;
+    *rank = (int32_t)solver->common.SPQR_istat[4];
+    *effective_ordering = (int32_t)solver->common.SPQR_istat[7];
+
+    solver->factorization_completed = C_TRUE;
+
+    return SUCCESSFUL_EXIT;
+}
+
+/// @brief Computes the least-squares solution (or the minimum-norm solution) using the Q·R factorization
+/// @param min_norm if false, F = A is m×n with m ≥ n and x = E·R⁻¹·Qᵀ·b (x has dim ncol; rhs has dim nrow);
+/// if true, F = Aᵀ is n×m with n ≥ m and x = Q·R⁻ᵀ·Eᵀ·b (x has dim nrow; rhs has dim ncol)
+int32_t solver_spqr_solve(struct InterfaceSPQR *solver, C_BOOL min_norm, double *x, double *rhs) {
+    if (solver == NULL) {
+        return ERROR_NULL_POINTER;
+    }
+
+    if (solver->factorization_completed == C_FALSE) {
+        return ERROR_NEED_FACTORIZATION;
+    }
+
+    cholmod_dense b;
+    cholmod_dense *y = NULL;
+    cholmod_dense *z = NULL;
+
+    if (min_norm == C_TRUE) {
+        set_spqr_dense(&b, solver->ncol, rhs);
+        y = SuiteSparseQR_C_solve(SPQR_RTX_EQUALS_ETB, solver->factors, &b, &solver->common);
+        if (y != NULL) {
+            z = SuiteSparseQR_C_qmult(SPQR_QX, solver->factors, y, &solver->common);
+        }
+    } else {
+        set_spqr_dense(&b, solver->nrow, rhs);
+        y = SuiteSparseQR_C_qmult(SPQR_QTX, solver->factors, &b, &solver->common);
+        if (y != NULL) {
+            z = SuiteSparseQR_C_solve(SPQR_RETX_EQUALS_B, solver->factors, y, &solver->common);
+        }
+    }
+
+    if (y != NULL) {
+        cholmod_l_free_dense(&y, &solver->common);
+    }
+    if (z == NULL) {
+        return SPQR_ERROR_SOLVE;
+    }
+
+    memcpy(x, z->x, z->nrow * sizeof(double));
+    cholmod_l_free_dense(&z, &solver->common);
+
+    return SUCCESSFUL_EXIT;
+}
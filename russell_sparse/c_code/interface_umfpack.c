@@ -178,13 +178,14 @@ int32_t solver_umfpack_factorize(struct InterfaceUMFPACK *solver,
     return code;
 }
 
-/// @brief Computes the solution of the linear system
+/// @brief Computes the solution of the linear system A·x = rhs (or Aᵀ·x = rhs if transpose)
 int32_t solver_umfpack_solve(struct InterfaceUMFPACK *solver,
                              double *x,
                              const double *rhs,
                              const int32_t *col_pointers,
                              const int32_t *row_indices,
                              const double *values,
+                             C_BOOL transpose,
                              C_BOOL verbose) {
     if (solver == NULL) {
         return ERROR_NULL_POINTER;
@@ -196,7 +197,9 @@ int32_t solver_umfpack_solve(struct InterfaceUMFPACK *solver,
 
     set_umfpack_verbose(solver, verbose);
 
-    int code = umfpack_di_solve(UMFPACK_A,
+    int32_t sys = transpose == C_TRUE ? UMFPACK_At : UMFPACK_A;
+
+    int code = umfpack_di_solve(sys,
                                 col_pointers,
                                 row_indices,
                                 values,
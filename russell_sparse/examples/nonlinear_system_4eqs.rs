@@ -1,4 +1,4 @@
-use russell_lab::{vec_norm, vec_update, Norm, Vector};
+use russell_lab::{NoArgs, Vector};
 use russell_sparse::prelude::*;
 use russell_sparse::StrError;
 use structopt::StructOpt;
@@ -22,66 +22,33 @@ fn main() -> Result<(), StrError> {
     let genie = match opt.genie.to_lowercase().as_str() {
         "klu" => Genie::Klu,
         "mumps" => Genie::Mumps,
+        "native" => Genie::Native,
         "umfpack" => Genie::Umfpack,
         _ => return Err("genie is incorrect"),
     };
     println!("... solving problem with {:?} ...", genie);
-    let mut solver = LinSolver::new(genie)?;
 
-    // allocate Jacobian matrix (J) as SparseMatrix
+    // define the nonlinear system: residual (rr) as a function of the vector of unknowns (uu)
     let (neq, nnz) = (4, 16);
-    let mut jj = SparseMatrix::new_coo(neq, neq, nnz, Sym::No).unwrap();
-
-    // allocate residual (rr), vector of unknowns (uu), and minus-uu (mdu)
-    let mut rr = Vector::new(neq);
+    let mut system = NonlinSystem::new(neq, |rr, uu, _args: &mut NoArgs| {
+        calc_residual(rr, uu);
+        Ok(())
+    });
+    system.set_jacobian(Some(nnz), Sym::No, |jj, uu, _args: &mut NoArgs| calc_jacobian(jj, uu))?;
+
+    // configure the nonlinear solver
+    let mut params = NonlinParams::new();
+    params.genie = genie;
+    params.tol_abs = 1e-13;
+    params.max_iterations = 10;
+    params.verbose = true;
+    let mut solver = NonlinSolver::new(Some(params))?;
+
+    // solve the nonlinear system
     let mut uu = Vector::from(&[0.0, 0.0, 0.0, 0.0]);
-    let mut mdu = Vector::new(neq);
-
-    // message
-    println!(
-        "{:>4}{:>13}{:>13}{:>13}{:>13}{:>15}",
-        "it", "d1", "d2", "d3", "d4", "err"
-    );
-
-    // loop until the residual is smaller than tolerance
-    const TOLERANCE: f64 = 1e-13;
-    let mut it = 0;
-    let mut norm_rr0 = 1.0;
-    while it < 10 {
-        // calculate residual
-        calc_residual(&mut rr, &uu);
-
-        // first error or use the norm of residual as error,
-        // normalized by the norm of the first residual
-        let err = if it == 0 {
-            norm_rr0 = vec_norm(&rr, Norm::Euc);
-            1.0
-        } else {
-            vec_norm(&rr, Norm::Euc) / norm_rr0
-        };
-
-        // message
-        println!(
-            "{:>4}{:>13.6}{:>13.6}{:>13.6}{:>13.6}{:>15.6e}",
-            it, uu[0], uu[1], uu[2], uu[3], err
-        );
-
-        // exit point
-        if err < TOLERANCE {
-            break;
-        }
-
-        // calculate Jacobian matrix
-        calc_jacobian(jj.get_coo_mut()?, &uu)?;
-
-        // factorize and solve linear system: J * mdu = rr
-        solver.actual.factorize(&mut jj, None)?;
-        solver.actual.solve(&mut mdu, &jj, &rr, false)?;
-
-        // update the vector of unknowns: uu -= mdu
-        vec_update(&mut uu, -1.0, &mdu)?;
-        it += 1;
-    }
+    solver.solve(&mut uu, &system, &mut 0)?;
+    println!("\nuu = {:?}\n", uu.as_data());
+    println!("{}", solver.get_stats());
     Ok(())
 }
 
@@ -1,4 +1,6 @@
-use crate::{NumCooMatrix, NumCscMatrix, NumCsrMatrix, NumSparseMatrix};
+use crate::{
+    NumAssembler, NumCooMatrix, NumCscMatrix, NumCsrMatrix, NumPreconditioner, NumSolverKrylov, NumSparseMatrix,
+};
 use russell_lab::Complex64;
 
 /// Defines an alias to NumCooMatrix with f64
@@ -24,3 +26,21 @@ pub type ComplexCsrMatrix = NumCsrMatrix<Complex64>;
 
 /// Defines an alias to NumSparseMatrix with Complex64
 pub type ComplexSparseMatrix = NumSparseMatrix<Complex64>;
+
+/// Defines an alias to NumAssembler with f64
+pub type Assembler = NumAssembler<f64>;
+
+/// Defines an alias to NumAssembler with Complex64
+pub type ComplexAssembler = NumAssembler<Complex64>;
+
+/// Defines an alias to NumPreconditioner with f64
+pub type Preconditioner<'a> = NumPreconditioner<'a, f64>;
+
+/// Defines an alias to NumPreconditioner with Complex64
+pub type ComplexPreconditioner<'a> = NumPreconditioner<'a, Complex64>;
+
+/// Defines an alias to NumSolverKrylov with f64
+pub type SolverKrylov = NumSolverKrylov<f64>;
+
+/// Defines an alias to NumSolverKrylov with Complex64
+pub type ComplexSolverKrylov = NumSolverKrylov<Complex64>;
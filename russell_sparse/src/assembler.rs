@@ -0,0 +1,704 @@
+use super::{NumCscMatrix, NumCsrMatrix, NumSparseMatrix, Sym};
+use crate::StrError;
+use num_traits::{Num, NumCast};
+use russell_lab::NumMatrix;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ops::{AddAssign, MulAssign};
+
+/// Marks an entry of the element matrix that is not stored (e.g., outside the stored triangle)
+const NONE: usize = usize::MAX;
+
+/// Assembles global sparse matrices from element matrices (finite-element style)
+///
+/// The sparsity pattern of the global matrix is computed once from the element DOFs (degrees of freedom),
+/// i.e., the entry `(I, J)` is non-zero if the DOFs `I` and `J` belong to the same element. In addition,
+/// the position of each entry of each element matrix in the array of values of the global matrix is stored.
+/// Thus, the element matrices are scattered directly into the values (without duplicates and without
+/// searching), and the assembly can be repeated (e.g., at every Newton iteration) with minimum cost.
+///
+/// The global matrix is stored as a [NumSparseMatrix] (see [NumAssembler::get_matrix_mut()]) with either the
+/// CSR layout ([NumAssembler::new_csr()]) or the CSC layout ([NumAssembler::new_csc()]). The CSC layout can be
+/// given directly to the linear solvers, whereas the CSR layout is convenient for the matrix-vector products
+/// (e.g., [NumCsrMatrix::mat_vec_mul_parallel()]). Since the element pattern is structurally symmetric,
+/// both layouts share the same precomputed pattern.
+///
+/// If the matrix is symmetric with one triangle stored ([Sym::YesLower] or [Sym::YesUpper]), the entries
+/// of the element matrices outside the stored triangle are ignored.
+///
+/// The assembly may be performed in parallel ([NumAssembler::assemble()]). In this case, the elements are
+/// grouped by colors such that the elements with the same color do not share DOFs (greedy coloring).
+/// Then, the elements of each color are processed simultaneously by the threads because they
+/// write to distinct entries of the global matrix.
+///
+/// # Examples
+///
+/// ```
+/// use russell_lab::{Matrix, Vector, vec_approx_eq};
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // three bars (springs) in series connecting four nodes with one DOF each
+///     //
+///     //  0 ----- 1 ----- 2 ----- 3
+///     //     (0)     (1)     (2)
+///     let connectivity = vec![vec![0, 1], vec![1, 2], vec![2, 3]];
+///     let node_dofs = vec![vec![0], vec![1], vec![2], vec![3]];
+///     let element_dofs = element_dofs_from_connectivity(&connectivity, &node_dofs)?;
+///
+///     // allocate the assembler (computes the pattern once)
+///     let mut assembler = Assembler::new_csc(4, &element_dofs, Sym::No)?;
+///
+///     // assemble the stiffness matrix (may be repeated as many times as needed)
+///     let stiffness = [1.0, 2.0, 3.0];
+///     assembler.assemble(1, |kk, e| {
+///         let k = stiffness[e];
+///         kk.set(0, 0, k);
+///         kk.set(0, 1, -k);
+///         kk.set(1, 0, -k);
+///         kk.set(1, 1, k);
+///         Ok(())
+///     })?;
+///
+///     // check
+///     let kk = assembler.get_matrix().as_dense();
+///     assert_eq!(
+///         format!("{}", kk),
+///         "┌             ┐\n\
+///          │  1 -1  0  0 │\n\
+///          │ -1  3 -2  0 │\n\
+///          │  0 -2  5 -3 │\n\
+///          │  0  0 -3  3 │\n\
+///          └             ┘"
+///     );
+///     Ok(())
+/// }
+/// ```
+pub struct NumAssembler<T>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    /// Holds the global matrix (CSR or CSC)
+    mat: NumSparseMatrix<T>,
+
+    /// Indicates that the global matrix is stored as CSC
+    csc: bool,
+
+    /// Holds the DOFs of all elements (concatenated)
+    element_dofs: Vec<usize>,
+
+    /// Holds the start of the DOFs of each element in `element_dofs` (size = nelement + 1)
+    dof_pointers: Vec<usize>,
+
+    /// Holds the position of each entry of each element matrix in the array of values (or NONE)
+    ///
+    /// The entry `(a, b)` of the element `e` is located at `map_pointers[e] + a * ndof_e + b`
+    map: Vec<usize>,
+
+    /// Holds the start of the map of each element in `map` (size = nelement + 1)
+    map_pointers: Vec<usize>,
+
+    /// Holds the groups of elements that do not share DOFs (computed by the first parallel assembly)
+    colors: Vec<Vec<usize>>,
+}
+
+impl<T> NumAssembler<T>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    /// Allocates a new instance that assembles a CSR matrix
+    ///
+    /// # Input
+    ///
+    /// * `ndof` -- the total number of DOFs, i.e., the dimension of the square global matrix
+    /// * `element_dofs` -- the global DOFs of each element; the local numbering of the element matrix
+    ///   follows the order of the DOFs (see also [element_dofs_from_connectivity()])
+    /// * `symmetric` -- the symmetric type of the global matrix
+    pub fn new_csr(ndof: usize, element_dofs: &[Vec<usize>], symmetric: Sym) -> Result<Self, StrError> {
+        NumAssembler::new(ndof, element_dofs, symmetric, false)
+    }
+
+    /// Allocates a new instance that assembles a CSC matrix (e.g., for the linear solvers)
+    ///
+    /// # Input
+    ///
+    /// * `ndof` -- the total number of DOFs, i.e., the dimension of the square global matrix
+    /// * `element_dofs` -- the global DOFs of each element; the local numbering of the element matrix
+    ///   follows the order of the DOFs (see also [element_dofs_from_connectivity()])
+    /// * `symmetric` -- the symmetric type of the global matrix
+    pub fn new_csc(ndof: usize, element_dofs: &[Vec<usize>], symmetric: Sym) -> Result<Self, StrError> {
+        NumAssembler::new(ndof, element_dofs, symmetric, true)
+    }
+
+    /// Allocates a new instance
+    ///
+    /// The pattern is computed in the "major" (row for CSR, column for CSC) and "minor" (column for CSR,
+    /// row for CSC) indices. The stored triangle is thus swapped for CSC.
+    fn new(ndof: usize, element_dofs: &[Vec<usize>], symmetric: Sym, csc: bool) -> Result<Self, StrError> {
+        if ndof < 1 {
+            return Err("ndof must be ≥ 1");
+        }
+        if element_dofs.is_empty() {
+            return Err("there must be at least one element");
+        }
+
+        // collect the DOFs of all elements
+        let nelement = element_dofs.len();
+        let mut dofs = Vec::new();
+        let mut dof_pointers = vec![0; nelement + 1];
+        for (e, edofs) in element_dofs.iter().enumerate() {
+            if edofs.is_empty() {
+                return Err("all elements must have at least one DOF");
+            }
+            for &dof in edofs {
+                if dof >= ndof {
+                    return Err("the element DOFs must be smaller than ndof");
+                }
+                dofs.push(dof);
+            }
+            dof_pointers[e + 1] = dofs.len();
+        }
+
+        // stored triangle in terms of (major, minor)
+        let keep = |major: usize, minor: usize| match (symmetric, csc) {
+            (Sym::YesLower, false) | (Sym::YesUpper, true) => minor <= major,
+            (Sym::YesUpper, false) | (Sym::YesLower, true) => minor >= major,
+            _ => true,
+        };
+
+        // elements of each DOF
+        let mut dof_elements = vec![Vec::new(); ndof];
+        for e in 0..nelement {
+            for &dof in &dofs[dof_pointers[e]..dof_pointers[e + 1]] {
+                if dof_elements[dof].last() != Some(&e) {
+                    dof_elements[dof].push(e);
+                }
+            }
+        }
+
+        // pattern: the minor indices of each major index are the DOFs sharing an element
+        let mut pointers = vec![0_i32; ndof + 1];
+        let mut indices = Vec::new();
+        let mut mark = vec![NONE; ndof];
+        let mut neighbors = Vec::new();
+        for major in 0..ndof {
+            neighbors.clear();
+            for &e in &dof_elements[major] {
+                for &minor in &dofs[dof_pointers[e]..dof_pointers[e + 1]] {
+                    if mark[minor] != major && keep(major, minor) {
+                        mark[minor] = major;
+                        neighbors.push(minor);
+                    }
+                }
+            }
+            if neighbors.is_empty() {
+                // keep the diagonal (e.g., DOF without elements) such that the matrix is structurally valid
+                neighbors.push(major);
+            }
+            neighbors.sort_unstable();
+            indices.extend(neighbors.iter().map(|&k| k as i32));
+            pointers[major + 1] = indices.len() as i32;
+        }
+
+        // map of each element matrix into the array of values
+        let mut map = Vec::new();
+        let mut map_pointers = vec![0; nelement + 1];
+        for e in 0..nelement {
+            let edofs = &dofs[dof_pointers[e]..dof_pointers[e + 1]];
+            for &row in edofs {
+                for &col in edofs {
+                    let (major, minor) = if csc { (col, row) } else { (row, col) };
+                    if keep(major, minor) {
+                        let start = pointers[major] as usize;
+                        let end = pointers[major + 1] as usize;
+                        let offset = indices[start..end].binary_search(&(minor as i32)).unwrap(); // unwrap OK because the pattern contains all element entries
+                        map.push(start + offset);
+                    } else {
+                        map.push(NONE);
+                    }
+                }
+            }
+            map_pointers[e + 1] = map.len();
+        }
+
+        // allocate the global matrix
+        let nnz = indices.len();
+        let values = vec![T::zero(); nnz];
+        let mat = if csc {
+            NumSparseMatrix::from_csc(NumCscMatrix::new(ndof, ndof, pointers, indices, values, symmetric)?)
+        } else {
+            NumSparseMatrix::from_csr(NumCsrMatrix::new(ndof, ndof, pointers, indices, values, symmetric)?)
+        };
+        Ok(NumAssembler {
+            mat,
+            csc,
+            element_dofs: dofs,
+            dof_pointers,
+            map,
+            map_pointers,
+            colors: Vec::new(),
+        })
+    }
+
+    /// Returns the number of elements
+    pub fn nelement(&self) -> usize {
+        self.dof_pointers.len() - 1
+    }
+
+    /// Returns the global DOFs of an element
+    ///
+    /// # Panics
+    ///
+    /// A panic will occur if `e` is out of range
+    pub fn get_element_dofs(&self, e: usize) -> &[usize] {
+        &self.element_dofs[self.dof_pointers[e]..self.dof_pointers[e + 1]]
+    }
+
+    /// Returns the groups of elements (colors) that do not share DOFs
+    ///
+    /// **Note:** The colors are computed by the first call to [NumAssembler::assemble()] with more than one thread.
+    pub fn get_colors(&self) -> &[Vec<usize>] {
+        &self.colors
+    }
+
+    /// Returns access to the global matrix
+    pub fn get_matrix(&self) -> &NumSparseMatrix<T> {
+        &self.mat
+    }
+
+    /// Returns mutable access to the global matrix (e.g., for the linear solvers)
+    ///
+    /// **Warning:** The sparsity pattern must not be modified.
+    pub fn get_matrix_mut(&mut self) -> &mut NumSparseMatrix<T> {
+        &mut self.mat
+    }
+
+    /// Sets all values of the global matrix to zero (the pattern is kept)
+    pub fn reset(&mut self) {
+        values_mut(&mut self.mat, self.csc).fill(T::zero());
+    }
+
+    /// Adds an element matrix to the global matrix
+    ///
+    /// # Input
+    ///
+    /// * `e` -- the index of the element
+    /// * `kk` -- the (ndof_e, ndof_e) element matrix, where ndof_e is the number of DOFs of the element
+    pub fn add_element(&mut self, e: usize, kk: &NumMatrix<T>) -> Result<(), StrError> {
+        if e >= self.nelement() {
+            return Err("the element index is out of range");
+        }
+        let ndof_e = self.dof_pointers[e + 1] - self.dof_pointers[e];
+        if kk.dims() != (ndof_e, ndof_e) {
+            return Err("the dimensions of the element matrix are incorrect");
+        }
+        let start = self.map_pointers[e];
+        let end = self.map_pointers[e + 1];
+        let values = values_mut(&mut self.mat, self.csc);
+        for (k, &pos) in self.map[start..end].iter().enumerate() {
+            if pos != NONE {
+                values[pos] += kk.get(k / ndof_e, k % ndof_e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Assembles the global matrix from the element matrices
+    ///
+    /// The global matrix is reset first. Then, the element matrices are computed by the `calc`
+    /// function and added to the global matrix.
+    ///
+    /// # Input
+    ///
+    /// * `num_threads` -- number of threads; use 0 to select the available parallelism of the machine.
+    ///   If more than one thread is used, the elements are colored (once) and the elements of each color
+    ///   are distributed among the threads (see [NumAssembler::get_colors()]).
+    /// * `calc` -- a function `calc(kk, e)` that computes the element matrix `kk` of the element `e`.
+    ///   The element matrix has dimensions (ndof_e, ndof_e) and is filled with zeros before the call.
+    ///
+    /// **Note:** If `calc` returns an error, the first error is returned and the global matrix is incomplete.
+    pub fn assemble<F>(&mut self, num_threads: usize, calc: F) -> Result<(), StrError>
+    where
+        T: Send + Sync,
+        F: Fn(&mut NumMatrix<T>, usize) -> Result<(), StrError> + Sync,
+    {
+        self.reset();
+        let nt = if num_threads == 0 {
+            std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+        } else {
+            num_threads
+        };
+        let nt = usize::min(nt, self.nelement());
+
+        // serial
+        if nt <= 1 {
+            let mut kk = NumMatrix::new(0, 0);
+            for e in 0..self.nelement() {
+                let ndof_e = self.dof_pointers[e + 1] - self.dof_pointers[e];
+                if kk.dims() != (ndof_e, ndof_e) {
+                    kk = NumMatrix::new(ndof_e, ndof_e);
+                } else {
+                    kk.fill(T::zero());
+                }
+                calc(&mut kk, e)?;
+                self.add_element(e, &kk)?;
+            }
+            return Ok(());
+        }
+
+        // parallel
+        if self.colors.is_empty() {
+            self.colors = self.compute_colors();
+        }
+        let (map, map_pointers, dof_pointers) = (&self.map, &self.map_pointers, &self.dof_pointers);
+        let values = values_mut(&mut self.mat, self.csc);
+        let shared = SharedValues(values.as_mut_ptr());
+        let shared = &shared;
+        let calc = &calc;
+        for elements in &self.colors {
+            let chunk_size = elements.len().div_ceil(nt);
+            let res: Result<(), StrError> = std::thread::scope(|scope| {
+                let handles: Vec<_> = elements
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        scope.spawn(move || {
+                            let mut kk = NumMatrix::new(0, 0);
+                            for &e in chunk {
+                                let ndof_e = dof_pointers[e + 1] - dof_pointers[e];
+                                if kk.dims() != (ndof_e, ndof_e) {
+                                    kk = NumMatrix::new(ndof_e, ndof_e);
+                                } else {
+                                    kk.fill(T::zero());
+                                }
+                                calc(&mut kk, e)?;
+                                for (k, &pos) in map[map_pointers[e]..map_pointers[e + 1]].iter().enumerate() {
+                                    if pos != NONE {
+                                        // SAFETY: the elements of the same color do not share DOFs; thus, their
+                                        // entries are located in distinct rows (CSR) or columns (CSC) and the
+                                        // positions written by the threads are distinct. Moreover, the array of
+                                        // values is not reallocated while the threads are running.
+                                        unsafe { *shared.0.add(pos) += kk.get(k / ndof_e, k % ndof_e) };
+                                    }
+                                }
+                            }
+                            Ok(())
+                        })
+                    })
+                    .collect();
+                handles.into_iter().try_for_each(|h| h.join().unwrap())
+            });
+            res?;
+        }
+        Ok(())
+    }
+
+    /// Computes the groups of elements that do not share DOFs (greedy coloring)
+    fn compute_colors(&self) -> Vec<Vec<usize>> {
+        let (ndof, _, _, _) = self.mat.get_info();
+        let mut colors: Vec<Vec<usize>> = Vec::new();
+        let mut used: Vec<Vec<bool>> = Vec::new(); // used[color][dof]
+        for e in 0..self.nelement() {
+            let edofs = self.get_element_dofs(e);
+            let color = match used.iter().position(|u| edofs.iter().all(|&dof| !u[dof])) {
+                Some(c) => c,
+                None => {
+                    colors.push(Vec::new());
+                    used.push(vec![false; ndof]);
+                    colors.len() - 1
+                }
+            };
+            for &dof in edofs {
+                used[color][dof] = true;
+            }
+            colors[color].push(e);
+        }
+        colors
+    }
+}
+
+/// Returns the values of the global matrix
+fn values_mut<T>(mat: &mut NumSparseMatrix<T>, csc: bool) -> &mut [T]
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if csc {
+        mat.get_csc_mut().unwrap().get_values_mut() // unwrap OK because CSC is allocated in new
+    } else {
+        mat.get_csr_mut().unwrap().get_values_mut() // unwrap OK because CSR is allocated in new
+    }
+}
+
+/// Holds a pointer to the values of the global matrix shared by the threads
+struct SharedValues<T>(*mut T);
+
+// SAFETY: the threads write to distinct positions (see NumAssembler::assemble)
+unsafe impl<T: Send> Send for SharedValues<T> {}
+unsafe impl<T: Send> Sync for SharedValues<T> {}
+
+/// Computes the global DOFs of the elements from the connectivity and the DOFs of the nodes
+///
+/// The DOFs of each element are ordered by local node and then by the DOFs of the node, i.e.,
+/// `[dofs(node_0)..., dofs(node_1)..., ...]`, which is the usual local numbering of the element matrices.
+///
+/// # Input
+///
+/// * `connectivity` -- the nodes of each element
+/// * `node_dofs` -- the global DOFs of each node (e.g., `[2*m, 2*m+1]` for 2D displacements)
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // two triangles with two DOFs per node
+///     let connectivity = vec![vec![0, 1, 2], vec![1, 3, 2]];
+///     let node_dofs: Vec<_> = (0..4).map(|m| vec![2 * m, 2 * m + 1]).collect();
+///     let element_dofs = element_dofs_from_connectivity(&connectivity, &node_dofs)?;
+///     assert_eq!(element_dofs[1], &[2, 3, 6, 7, 4, 5]);
+///     Ok(())
+/// }
+/// ```
+pub fn element_dofs_from_connectivity(
+    connectivity: &[Vec<usize>],
+    node_dofs: &[Vec<usize>],
+) -> Result<Vec<Vec<usize>>, StrError> {
+    let mut element_dofs = Vec::with_capacity(connectivity.len());
+    for nodes in connectivity {
+        let mut dofs = Vec::new();
+        for &m in nodes {
+            if m >= node_dofs.len() {
+                return Err("the node index in the connectivity is out of range");
+            }
+            dofs.extend_from_slice(&node_dofs[m]);
+        }
+        element_dofs.push(dofs);
+    }
+    Ok(element_dofs)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Assembler, ComplexAssembler, CooMatrix, CscMatrix};
+    use russell_lab::{cpx, mat_approx_eq, Complex64, Matrix};
+
+    /// Returns the DOFs of a mesh of (nx × ny) quadrilaterals with two DOFs per node
+    fn quad_mesh(nx: usize, ny: usize) -> (usize, Vec<Vec<usize>>) {
+        let mut connectivity = Vec::new();
+        for j in 0..ny {
+            for i in 0..nx {
+                let a = j * (nx + 1) + i;
+                connectivity.push(vec![a, a + 1, a + nx + 2, a + nx + 1]);
+            }
+        }
+        let nnode = (nx + 1) * (ny + 1);
+        let node_dofs: Vec<_> = (0..nnode).map(|m| vec![2 * m, 2 * m + 1]).collect();
+        (
+            2 * nnode,
+            element_dofs_from_connectivity(&connectivity, &node_dofs).unwrap(),
+        )
+    }
+
+    /// Computes an (unsymmetric) element matrix depending on the element index
+    fn calc_element(kk: &mut Matrix, e: usize) -> Result<(), StrError> {
+        let (n, _) = kk.dims();
+        for a in 0..n {
+            for b in 0..n {
+                let value = if a == b {
+                    10.0
+                } else {
+                    1.0 / (1.0 + a as f64 + 2.0 * b as f64)
+                };
+                kk.set(a, b, value * (1.0 + e as f64));
+            }
+        }
+        Ok(())
+    }
+
+    /// Assembles the global matrix using COO (reference)
+    fn assemble_with_coo(ndof: usize, element_dofs: &[Vec<usize>]) -> Matrix {
+        let max_nnz = element_dofs.iter().map(|d| d.len() * d.len()).sum();
+        let mut coo = CooMatrix::new(ndof, ndof, max_nnz, Sym::No).unwrap();
+        for (e, dofs) in element_dofs.iter().enumerate() {
+            let mut kk = Matrix::new(dofs.len(), dofs.len());
+            calc_element(&mut kk, e).unwrap();
+            for (a, &row) in dofs.iter().enumerate() {
+                for (b, &col) in dofs.iter().enumerate() {
+                    coo.put(row, col, kk.get(a, b)).unwrap();
+                }
+            }
+        }
+        coo.as_dense()
+    }
+
+    #[test]
+    fn new_captures_errors() {
+        assert_eq!(
+            Assembler::new_csr(0, &[vec![0]], Sym::No).err(),
+            Some("ndof must be ≥ 1")
+        );
+        assert_eq!(
+            Assembler::new_csr(1, &[], Sym::No).err(),
+            Some("there must be at least one element")
+        );
+        assert_eq!(
+            Assembler::new_csc(2, &[vec![0, 1], vec![]], Sym::No).err(),
+            Some("all elements must have at least one DOF")
+        );
+        assert_eq!(
+            Assembler::new_csc(2, &[vec![0, 2]], Sym::No).err(),
+            Some("the element DOFs must be smaller than ndof")
+        );
+        assert_eq!(
+            element_dofs_from_connectivity(&[vec![0, 1]], &[vec![0]]).err(),
+            Some("the node index in the connectivity is out of range")
+        );
+    }
+
+    #[test]
+    fn new_computes_the_pattern() {
+        //  0 ----- 1 ----- 2     3 (no elements)
+        let element_dofs = vec![vec![0, 1], vec![1, 2]];
+        let assembler = Assembler::new_csr(4, &element_dofs, Sym::No).unwrap();
+        let csr = assembler.get_matrix().get_csr().unwrap();
+        assert_eq!(csr.get_row_pointers(), &[0, 2, 5, 7, 8]);
+        assert_eq!(csr.get_col_indices(), &[0, 1, 0, 1, 2, 1, 2, 3]);
+        assert_eq!(assembler.nelement(), 2);
+        assert_eq!(assembler.get_element_dofs(1), &[1, 2]);
+        assert_eq!(assembler.map, &[0, 1, 2, 3, 3, 4, 5, 6]);
+
+        let assembler = Assembler::new_csr(4, &element_dofs, Sym::YesLower).unwrap();
+        let csr = assembler.get_matrix().get_csr().unwrap();
+        assert_eq!(csr.get_row_pointers(), &[0, 1, 3, 5, 6]);
+        assert_eq!(csr.get_col_indices(), &[0, 0, 1, 1, 2, 3]);
+        assert_eq!(assembler.map, &[0, NONE, 1, 2, 2, NONE, 3, 4]);
+
+        let assembler = Assembler::new_csc(4, &element_dofs, Sym::YesLower).unwrap();
+        let csc = assembler.get_matrix().get_csc().unwrap();
+        assert_eq!(csc.get_col_pointers(), &[0, 2, 4, 5, 6]);
+        assert_eq!(csc.get_row_indices(), &[0, 1, 1, 2, 2, 3]);
+        assert_eq!(assembler.map, &[0, NONE, 1, 2, 2, NONE, 3, 4]);
+    }
+
+    #[test]
+    fn add_element_captures_errors() {
+        let mut assembler = Assembler::new_csr(3, &[vec![0, 1], vec![1, 2]], Sym::No).unwrap();
+        let kk = Matrix::new(2, 2);
+        assert_eq!(
+            assembler.add_element(2, &kk).err(),
+            Some("the element index is out of range")
+        );
+        let kk = Matrix::new(3, 2);
+        assert_eq!(
+            assembler.add_element(0, &kk).err(),
+            Some("the dimensions of the element matrix are incorrect")
+        );
+        let res = assembler.assemble(1, |_, e| if e == 1 { Err("stop") } else { Ok(()) });
+        assert_eq!(res.err(), Some("stop"));
+        let res = assembler.assemble(2, |_, e| if e == 1 { Err("stop") } else { Ok(()) });
+        assert_eq!(res.err(), Some("stop"));
+    }
+
+    #[test]
+    fn assemble_works_serial_and_parallel() {
+        let (ndof, element_dofs) = quad_mesh(4, 3);
+        let correct = assemble_with_coo(ndof, &element_dofs);
+        for csc in [false, true] {
+            let mut assembler = if csc {
+                Assembler::new_csc(ndof, &element_dofs, Sym::No).unwrap()
+            } else {
+                Assembler::new_csr(ndof, &element_dofs, Sym::No).unwrap()
+            };
+            for num_threads in [1, 3, 0] {
+                // the second call checks that the matrix is reset
+                for _ in 0..2 {
+                    assembler.assemble(num_threads, calc_element).unwrap();
+                    mat_approx_eq(&assembler.get_matrix().as_dense(), &correct, 1e-14);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn coloring_works() {
+        let (ndof, element_dofs) = quad_mesh(4, 3);
+        let mut assembler = Assembler::new_csr(ndof, &element_dofs, Sym::No).unwrap();
+        assert!(assembler.get_colors().is_empty());
+        assembler.assemble(2, calc_element).unwrap();
+        let colors = assembler.get_colors();
+        // structured quadrilateral meshes require four colors
+        assert_eq!(colors.len(), 4);
+        assert_eq!(colors.iter().map(|c| c.len()).sum::<usize>(), 12);
+        for elements in colors {
+            let mut used = vec![false; ndof];
+            for &e in elements {
+                for &dof in assembler.get_element_dofs(e) {
+                    assert!(!used[dof]);
+                    used[dof] = true;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn assemble_works_symmetric() {
+        // symmetric element matrices
+        let (ndof, element_dofs) = quad_mesh(2, 2);
+        let calc = |kk: &mut Matrix, e: usize| {
+            let (n, _) = kk.dims();
+            for a in 0..n {
+                for b in 0..n {
+                    kk.set(a, b, if a == b { 4.0 } else { 1.0 / (1.0 + (a + b) as f64) } + e as f64);
+                }
+            }
+            Ok(())
+        };
+        let mut full = Assembler::new_csc(ndof, &element_dofs, Sym::YesFull).unwrap();
+        full.assemble(1, calc).unwrap();
+        let correct = full.get_matrix().as_dense();
+        for sym in [Sym::YesLower, Sym::YesUpper] {
+            let mut lower_csc = Assembler::new_csc(ndof, &element_dofs, sym).unwrap();
+            lower_csc.assemble(2, calc).unwrap();
+            mat_approx_eq(&lower_csc.get_matrix().as_dense(), &correct, 1e-14);
+            let mut lower_csr = Assembler::new_csr(ndof, &element_dofs, sym).unwrap();
+            lower_csr.assemble(2, calc).unwrap();
+            mat_approx_eq(&lower_csr.get_matrix().as_dense(), &correct, 1e-14);
+            let nnz_full = full.get_matrix().get_csc().unwrap().get_col_pointers()[ndof] as usize;
+            let nnz = lower_csr.get_matrix().get_csr().unwrap().get_row_pointers()[ndof] as usize;
+            assert_eq!(nnz, (nnz_full + ndof) / 2);
+        }
+
+        // the CSC matrix can be given directly to CscMatrix functions
+        let csc: &CscMatrix = full.get_matrix().get_csc().unwrap();
+        assert_eq!(
+            csc.get_info(),
+            (ndof, ndof, csc.get_col_pointers()[ndof] as usize, Sym::YesFull)
+        );
+    }
+
+    #[test]
+    fn assemble_works_complex() {
+        let element_dofs = vec![vec![0, 1], vec![1, 2]];
+        let mut assembler = ComplexAssembler::new_csr(3, &element_dofs, Sym::No).unwrap();
+        assembler
+            .assemble(2, |kk, e| {
+                kk.set(0, 0, cpx!(1.0, e as f64));
+                kk.set(1, 1, cpx!(1.0, e as f64));
+                kk.set(0, 1, cpx!(-1.0, 0.0));
+                kk.set(1, 0, cpx!(-1.0, 0.0));
+                Ok(())
+            })
+            .unwrap();
+        let kk = assembler.get_matrix().as_dense();
+        assert_eq!(kk.get(0, 0), cpx!(1.0, 0.0));
+        assert_eq!(kk.get(1, 1), cpx!(2.0, 1.0));
+        assert_eq!(kk.get(2, 2), cpx!(1.0, 1.0));
+        assert_eq!(kk.get(0, 2), cpx!(0.0, 0.0));
+        assert_eq!(kk.get(2, 1), cpx!(-1.0, 0.0));
+    }
+}
@@ -5,8 +5,13 @@ use russell_sparse::Samples;
 fn test_solver(genie: Genie) {
     println!("----------------------------------------------------------------------\n");
     match genie {
+        Genie::BiCgStab | Genie::Cg | Genie::Cocg | Genie::Gmres | Genie::Minres => {
+            println!("Testing iterative solver\n")
+        }
+        Genie::Cholmod => println!("Testing CHOLMOD solver\n"),
         Genie::Klu => println!("Testing KLU solver\n"),
         Genie::Mumps => println!("Testing MUMPS solver\n"),
+        Genie::Native => println!("Testing Native solver\n"),
         Genie::Umfpack => println!("Testing UMFPACK solver\n"),
     }
 
@@ -56,8 +61,13 @@ fn test_solver(genie: Genie) {
 fn test_complex_solver(genie: Genie) {
     println!("----------------------------------------------------------------------\n");
     match genie {
+        Genie::BiCgStab | Genie::Cg | Genie::Cocg | Genie::Gmres | Genie::Minres => {
+            println!("Testing Complex iterative solver\n")
+        }
+        Genie::Cholmod => println!("Testing Complex CHOLMOD solver\n"),
         Genie::Klu => println!("Testing Complex KLU solver\n"),
         Genie::Mumps => println!("Testing Complex MUMPS solver\n"),
+        Genie::Native => println!("Testing Complex Native solver\n"),
         Genie::Umfpack => println!("Testing Complex UMFPACK solver\n"),
     }
 
@@ -70,8 +80,13 @@ fn test_complex_solver(genie: Genie) {
     };
 
     let coo = match genie {
+        Genie::BiCgStab | Genie::Cg | Genie::Cocg | Genie::Gmres | Genie::Minres => {
+            Samples::complex_symmetric_3x3_full().0
+        }
+        Genie::Cholmod => Samples::complex_symmetric_3x3_lower().0,
         Genie::Klu => Samples::complex_symmetric_3x3_full().0,
         Genie::Mumps => Samples::complex_symmetric_3x3_lower().0,
+        Genie::Native => Samples::complex_symmetric_3x3_lower().0,
         Genie::Umfpack => Samples::complex_symmetric_3x3_full().0,
     };
     let mut mat = ComplexSparseMatrix::from_coo(coo);
@@ -111,8 +126,13 @@ fn test_complex_solver(genie: Genie) {
 fn test_solver_singular(genie: Genie) {
     println!("----------------------------------------------------------------------\n");
     match genie {
+        Genie::BiCgStab | Genie::Cg | Genie::Cocg | Genie::Gmres | Genie::Minres => {
+            println!("Testing iterative solver (singular matrix)\n")
+        }
+        Genie::Cholmod => println!("Testing CHOLMOD solver (singular matrix)\n"),
         Genie::Klu => println!("Testing KLU solver (singular matrix)\n"),
         Genie::Mumps => println!("Testing MUMPS solver (singular matrix)\n"),
+        Genie::Native => println!("Testing Native solver (singular matrix)\n"),
         Genie::Umfpack => println!("Testing UMFPACK solver (singular matrix)\n"),
     }
 
@@ -146,16 +166,19 @@ fn main() {
     // real
     test_solver(Genie::Klu);
     test_solver(Genie::Mumps);
+    test_solver(Genie::Native);
     test_solver(Genie::Umfpack);
 
     // complex
     test_complex_solver(Genie::Klu);
     test_complex_solver(Genie::Mumps);
+    test_complex_solver(Genie::Native);
     test_complex_solver(Genie::Umfpack);
 
     // singular real
     test_solver_singular(Genie::Klu);
     test_solver_singular(Genie::Mumps);
+    test_solver_singular(Genie::Native);
     test_solver_singular(Genie::Umfpack);
 
     println!("----------------------------------------------------------------------\n");
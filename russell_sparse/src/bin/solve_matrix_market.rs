@@ -1,5 +1,8 @@
-use russell_lab::{cpx, set_num_threads, using_intel_mkl, Complex64, ComplexVector, Stopwatch, StrError, Vector};
+use russell_lab::{cpx, format_nanoseconds, set_num_threads, using_intel_mkl, vec_max_abs_diff};
+use russell_lab::{Complex64, ComplexVector, Stopwatch, StrError, Vector};
 use russell_sparse::prelude::*;
+use russell_sparse::StatsLinSolMatrix;
+use serde::Serialize;
 use structopt::StructOpt;
 
 /// Command line options
@@ -56,48 +59,109 @@ struct Options {
     #[structopt(short = "u", long)]
     enforce_unsymmetric_strategy: bool,
 
+    /// Maximum number of iterative refinement steps (0 = no refinement)
+    #[structopt(long, default_value = "0")]
+    refine: usize,
+
+    /// Factorizes in single precision and refines in double precision (Native solver only; use with --refine)
+    #[structopt(long)]
+    mixed_precision: bool,
+
+    /// Detects null pivots and reports the rank (Native and MUMPS only)
+    #[structopt(long)]
+    null_pivots: bool,
+
+    /// Computes the matrix analysis report, including the 1-norm condition number estimate (real matrices only)
+    #[structopt(short = "a", long)]
+    analyze: bool,
+
+    /// Reads the right-hand side vector from a Matrix-Market file (array or coordinate; one column)
+    #[structopt(short = "r", long)]
+    rhs: Option<String>,
+
+    /// Generates the right-hand side from the known solution x★ with x★ᵢ = 1 + (i mod 10) and reports the error
+    #[structopt(short = "k", long)]
+    known_solution: bool,
+
+    /// Runs all available solvers with the relevant ordering and scaling combinations
+    #[structopt(short = "b", long)]
+    benchmark: bool,
+
+    /// Comma-separated list of solvers for the benchmark mode (e.g., "klu,umfpack")
+    #[structopt(long, default_value = "all")]
+    genies: String,
+
+    /// Number of timed repetitions of each configuration in the benchmark mode
+    #[structopt(long, default_value = "3")]
+    repeat: usize,
+
     /// Writes vismatrix file
     #[structopt(long)]
     vismatrix: bool,
 
+    /// Writes the sparsity pattern (spy plot) with the fill-in of the natural and AMD orderings to SVG files
+    #[structopt(long)]
+    spy: bool,
+
     /// Hide JSON output (useful to pipe MUMPS/UMFPACK logs to files)
     #[structopt(long)]
     hide_json: bool,
 }
 
+/// Holds the aggregated results of one solver configuration (benchmark mode)
+#[derive(Serialize)]
+struct BenchmarkCase {
+    genie: String,
+    ordering: String,
+    scaling: String,
+    effective_ordering: String,
+    effective_scaling: String,
+    repeat: usize,                     // number of successful repetitions
+    factorize_min: u128,               // min initialize + factorize time (nanoseconds)
+    factorize_mean: u128,              // mean initialize + factorize time (nanoseconds)
+    solve_min: u128,                   // min solve time (nanoseconds)
+    solve_mean: u128,                  // mean solve time (nanoseconds)
+    total_min: u128,                   // min initialize + factorize + solve time (nanoseconds)
+    relative_error: f64,               // from VerifyLinSys
+    known_solution_error: Option<f64>, // max abs difference between x and the known solution (if given)
+    error: Option<String>,             // message if the solver has failed
+}
+
+/// Holds the results of the benchmark mode
+#[derive(Serialize)]
+struct Benchmark {
+    blas_lib: String,
+    matrix: StatsLinSolMatrix,
+    rhs: String,
+    unavailable: Vec<String>,
+    cases: Vec<BenchmarkCase>,
+}
+
 fn main() -> Result<(), StrError> {
     // parse options
     let opt = Options::from_args();
+    if opt.rhs.is_some() && opt.known_solution {
+        return Err("the --rhs and --known-solution options cannot be used together");
+    }
 
     // set the number of OpenMP threads
     if opt.nt > 0 {
         set_num_threads(opt.nt as usize);
     }
+    if !using_intel_mkl() && opt.override_prevent_issue {
+        println!("... WARNING: overriding the prevention of issue with OpenBLAS ...");
+    }
+
+    // run all solvers
+    if opt.benchmark {
+        return run_benchmark(&opt);
+    }
 
     // select linear solver
     let genie = Genie::from(&opt.genie);
 
-    // select the symmetric handling option
-    let handling = match genie {
-        Genie::Klu => MMsym::MakeItFull,
-        Genie::Mumps => MMsym::LeaveAsLower,
-        Genie::Umfpack => MMsym::MakeItFull,
-    };
-
     // configuration parameters
-    let mut params = LinSolParams::new();
-    params.ordering = Ordering::from(&opt.ordering);
-    params.scaling = Scaling::from(&opt.scaling);
-    params.compute_determinant = opt.determinant;
-    params.mumps_num_threads = opt.mumps_nt as usize;
-    params.umfpack_enforce_unsymmetric_strategy = opt.enforce_unsymmetric_strategy;
-    params.compute_error_estimates = opt.error_estimates;
-    params.compute_condition_numbers = opt.condition_numbers;
-    params.mumps_override_prevent_nt_issue_with_openblas = opt.override_prevent_issue;
-    params.verbose = opt.verbose;
-    if !using_intel_mkl() && opt.override_prevent_issue {
-        println!("... WARNING: overriding the prevention of issue with OpenBLAS ...");
-    }
+    let params = get_params(&opt, Ordering::from(&opt.ordering), Scaling::from(&opt.scaling));
 
     // allocate stats structure
     let mut stats = StatsLinSol::new();
@@ -107,7 +171,7 @@ fn main() -> Result<(), StrError> {
 
     // read the matrix
     let mut sw = Stopwatch::new();
-    let (coo_real, coo_complex) = read_matrix_market(&opt.matrix_market_file, handling)?;
+    let (coo_real, coo_complex) = read_matrix_market(&opt.matrix_market_file, get_handling(genie))?;
     stats.time_nanoseconds.read_matrix = sw.stop();
 
     // --- real ---------------------------------------------------------------------------------
@@ -118,44 +182,59 @@ fn main() -> Result<(), StrError> {
             csc.write_matrix_market("/tmp/russell_sparse/solve_matrix_market_real.smat", true)?;
         }
 
+        // write spy plots
+        if opt.spy {
+            write_spy_plots(&coo)?;
+        }
+
         // save the COO matrix as a generic SparseMatrix
         let mut mat = SparseMatrix::from_coo(coo);
 
         // save information about the matrix
-        let (nrow, ncol, nnz, sym) = mat.get_info();
-        stats.set_matrix_name_from_path(&opt.matrix_market_file);
-        stats.matrix.nrow = nrow;
-        stats.matrix.ncol = ncol;
-        stats.matrix.nnz = nnz;
-        stats.matrix.complex = false;
-        stats.matrix.symmetric = format!("{:?}", sym);
+        set_matrix_info(&mut stats, &opt, mat.get_info(), false);
+        let nrow = stats.matrix.nrow;
 
         // allocate and configure the solver
         let mut solver = LinSolver::new(genie)?;
 
         // call factorize
-        solver.actual.factorize(&mut mat, Some(params))?;
+        solver.factorize(&mut mat, Some(params))?;
 
         // allocate vectors
         let mut x = Vector::new(nrow);
-        let rhs = Vector::filled(nrow, 1.0);
+        let (rhs, x_known) = get_rhs_real(&opt, &mat)?;
 
         // solve linear system
-        solver.actual.solve(&mut x, &mat, &rhs, opt.verbose)?;
+        solver.solve(&mut x, &mat, &rhs, opt.verbose)?;
 
         // verify the solution
         sw.reset();
         stats.verify = VerifyLinSys::from(&mat, &x, &rhs)?;
         stats.time_nanoseconds.verify = sw.stop();
+        if let Some(x_known) = &x_known {
+            stats.known_solution_error = Some(vec_max_abs_diff(&x, x_known)?.1);
+        }
 
         // update stats
-        solver.actual.update_stats(&mut stats);
+        solver.update_stats(&mut stats);
+
+        // analyze the matrix (after updating the stats because the estimator calls solve)
+        if opt.analyze {
+            let mut analysis = MatrixAnalysis::new(&mat)?;
+            if let Err(e) = analysis.estimate_cond_one(&mut solver, &mat) {
+                println!("... WARNING: cannot estimate the condition number: {} ...", e);
+            }
+            stats.analysis = Some(analysis);
+        }
 
         // check (debug)
-        if stats.matrix.name == "bfwb62" {
+        if stats.matrix.name == "bfwb62" && opt.rhs.is_none() && !opt.known_solution {
             let tolerance = match genie {
+                Genie::BiCgStab | Genie::Cg | Genie::Cocg | Genie::Gmres | Genie::Minres => 1e-6,
+                Genie::Cholmod => 1e-10,
                 Genie::Klu => 1e-10,
                 Genie::Mumps => 1e-10,
+                Genie::Native => 1e-10,
                 Genie::Umfpack => 1e-10,
             };
             let correct_x = get_bfwb62_correct_x();
@@ -176,38 +255,44 @@ fn main() -> Result<(), StrError> {
             csc.write_matrix_market("/tmp/russell_sparse/solve_matrix_market_complex.smat", true)?;
         }
 
+        // write spy plots
+        if opt.spy {
+            let csc = ComplexCscMatrix::from_coo(&coo)?;
+            let mut params = SpyParams::new();
+            params.title = "A".to_string();
+            csc.write_spy_svg("/tmp/russell_sparse/solve_matrix_market_complex.svg", Some(&params))?;
+        }
+
         // save the COO matrix as a generic SparseMatrix
         let mut mat = ComplexSparseMatrix::from_coo(coo);
 
         // save information about the matrix
-        let (nrow, ncol, nnz, sym) = mat.get_info();
-        stats.set_matrix_name_from_path(&opt.matrix_market_file);
-        stats.matrix.nrow = nrow;
-        stats.matrix.ncol = ncol;
-        stats.matrix.nnz = nnz;
-        stats.matrix.complex = true;
-        stats.matrix.symmetric = format!("{:?}", sym);
+        set_matrix_info(&mut stats, &opt, mat.get_info(), true);
+        let nrow = stats.matrix.nrow;
 
         // allocate and configure the solver
         let mut solver = ComplexLinSolver::new(genie)?;
 
         // call factorize
-        solver.actual.factorize(&mut mat, Some(params))?;
+        solver.factorize(&mut mat, Some(params))?;
 
         // allocate vectors
         let mut x = ComplexVector::new(nrow);
-        let rhs = ComplexVector::filled(nrow, cpx!(1.0, 1.0));
+        let (rhs, x_known) = get_rhs_complex(&opt, &mat)?;
 
         // solve linear system
-        solver.actual.solve(&mut x, &mat, &rhs, opt.verbose)?;
+        solver.solve(&mut x, &mat, &rhs, opt.verbose)?;
 
         // verify the solution
         sw.reset();
         stats.verify = VerifyLinSys::from_complex(&mat, &x, &rhs)?;
         stats.time_nanoseconds.verify = sw.stop();
+        if let Some(x_known) = &x_known {
+            stats.known_solution_error = Some(complex_max_abs_diff(&x, x_known));
+        }
 
         // update stats
-        solver.actual.update_stats(&mut stats);
+        solver.update_stats(&mut stats);
     }
 
     // print stats
@@ -219,6 +304,444 @@ fn main() -> Result<(), StrError> {
     Ok(())
 }
 
+/// Returns the symmetric handling option required by the solver
+fn get_handling(genie: Genie) -> MMsym {
+    match genie {
+        Genie::BiCgStab | Genie::Cg | Genie::Cocg | Genie::Gmres | Genie::Minres => MMsym::MakeItFull,
+        Genie::Cholmod => MMsym::LeaveAsLower,
+        Genie::Klu => MMsym::MakeItFull,
+        Genie::Mumps => MMsym::LeaveAsLower,
+        Genie::Native => MMsym::LeaveAsLower,
+        Genie::Umfpack => MMsym::MakeItFull,
+    }
+}
+
+/// Writes the spy plots with the fill-in of the natural and AMD orderings (square matrices only)
+fn write_spy_plots(coo: &CooMatrix) -> Result<(), StrError> {
+    let csc = CscMatrix::from_coo(coo)?;
+    let mut params = SpyParams::new();
+    params.title = "A".to_string();
+    let (nrow, ncol, _, _) = coo.get_info();
+    if nrow != ncol {
+        return csc.write_spy_svg("/tmp/russell_sparse/solve_matrix_market_real.svg", Some(&params));
+    }
+    params.fill_in = true;
+    params.title = "A (natural ordering)".to_string();
+    csc.write_spy_svg("/tmp/russell_sparse/solve_matrix_market_real.svg", Some(&params))?;
+    let csr = CsrMatrix::from_coo(coo)?;
+    params.permutation = Some(ordering_amd(&csr)?);
+    params.title = "A (AMD ordering)".to_string();
+    csc.write_spy_svg("/tmp/russell_sparse/solve_matrix_market_real_amd.svg", Some(&params))
+}
+
+/// Returns the configuration parameters
+fn get_params(opt: &Options, ordering: Ordering, scaling: Scaling) -> LinSolParams {
+    let mut params = LinSolParams::new();
+    params.ordering = ordering;
+    params.scaling = scaling;
+    params.compute_determinant = opt.determinant;
+    params.mumps_num_threads = opt.mumps_nt as usize;
+    params.umfpack_enforce_unsymmetric_strategy = opt.enforce_unsymmetric_strategy;
+    params.compute_error_estimates = opt.error_estimates;
+    params.compute_condition_numbers = opt.condition_numbers;
+    params.mumps_override_prevent_nt_issue_with_openblas = opt.override_prevent_issue;
+    params.refinement_max_iterations = opt.refine;
+    params.mixed_precision = opt.mixed_precision;
+    params.null_pivot_detection = opt.null_pivots;
+    params.verbose = opt.verbose;
+    params
+}
+
+/// Saves information about the matrix
+fn set_matrix_info(stats: &mut StatsLinSol, opt: &Options, info: (usize, usize, usize, Sym), complex: bool) {
+    let (nrow, ncol, nnz, sym) = info;
+    stats.set_matrix_name_from_path(&opt.matrix_market_file);
+    stats.matrix.nrow = nrow;
+    stats.matrix.ncol = ncol;
+    stats.matrix.nnz = nnz;
+    stats.matrix.complex = complex;
+    stats.matrix.symmetric = format!("{:?}", sym);
+}
+
+/// Returns a description of the right-hand side vector
+fn get_rhs_description(opt: &Options) -> String {
+    match &opt.rhs {
+        Some(path) => format!("file: {}", path),
+        None if opt.known_solution => "known solution: x★ᵢ = 1 + (i mod 10)".to_string(),
+        None => "ones".to_string(),
+    }
+}
+
+/// Reads the right-hand side vector from a Matrix-Market file
+///
+/// Returns the real or the complex vector, depending on the file.
+fn read_rhs(path: &str, nrow: usize) -> Result<(Option<Vector>, Option<ComplexVector>), StrError> {
+    let error = "the right-hand side file must have one column and the same number of rows as the matrix";
+    let (coo_real, coo_complex) = read_matrix_market(path, MMsym::MakeItFull)?;
+    if let Some(coo) = coo_real {
+        let (m, n, _, _) = coo.get_info();
+        if m != nrow || n != 1 {
+            return Err(error);
+        }
+        let mut rhs = Vector::new(nrow);
+        for (i, value) in coo.get_row_indices().iter().zip(coo.get_values()) {
+            rhs[*i as usize] += value;
+        }
+        Ok((Some(rhs), None))
+    } else {
+        let coo = coo_complex.unwrap();
+        let (m, n, _, _) = coo.get_info();
+        if m != nrow || n != 1 {
+            return Err(error);
+        }
+        let mut rhs = ComplexVector::new(nrow);
+        for (i, value) in coo.get_row_indices().iter().zip(coo.get_values()) {
+            rhs[*i as usize] += value;
+        }
+        Ok((None, Some(rhs)))
+    }
+}
+
+/// Returns the right-hand side vector and the known solution (if requested) of a real system
+fn get_rhs_real(opt: &Options, mat: &SparseMatrix) -> Result<(Vector, Option<Vector>), StrError> {
+    let nrow = mat.get_info().0;
+    if opt.known_solution {
+        let x_known = Vector::initialized(nrow, |i| (1 + i % 10) as f64);
+        let mut rhs = Vector::new(nrow);
+        mat.mat_vec_mul(&mut rhs, 1.0, &x_known)?;
+        return Ok((rhs, Some(x_known)));
+    }
+    match &opt.rhs {
+        Some(path) => match read_rhs(path, nrow)? {
+            (Some(rhs), _) => Ok((rhs, None)),
+            _ => Err("the right-hand side file must be real for a real matrix"),
+        },
+        None => Ok((Vector::filled(nrow, 1.0), None)),
+    }
+}
+
+/// Returns the right-hand side vector and the known solution (if requested) of a complex system
+fn get_rhs_complex(
+    opt: &Options,
+    mat: &ComplexSparseMatrix,
+) -> Result<(ComplexVector, Option<ComplexVector>), StrError> {
+    let nrow = mat.get_info().0;
+    if opt.known_solution {
+        let x_known = ComplexVector::initialized(nrow, |i| cpx!((1 + i % 10) as f64, 1.0));
+        let mut rhs = ComplexVector::new(nrow);
+        mat.mat_vec_mul(&mut rhs, cpx!(1.0, 0.0), &x_known)?;
+        return Ok((rhs, Some(x_known)));
+    }
+    match &opt.rhs {
+        Some(path) => match read_rhs(path, nrow)? {
+            (Some(rhs), _) => Ok((ComplexVector::initialized(nrow, |i| cpx!(rhs[i], 0.0)), None)),
+            (_, Some(rhs)) => Ok((rhs, None)),
+            _ => unreachable!(),
+        },
+        None => Ok((ComplexVector::filled(nrow, cpx!(1.0, 1.0)), None)),
+    }
+}
+
+/// Returns the max abs difference between two complex vectors
+fn complex_max_abs_diff(u: &ComplexVector, v: &ComplexVector) -> f64 {
+    let mut max = 0.0;
+    for i in 0..u.dim() {
+        max = f64::max(max, (u[i] - v[i]).norm());
+    }
+    max
+}
+
+/// Returns the ordering and scaling options that are relevant to each solver (benchmark mode)
+fn get_combinations(genie: Genie) -> (Vec<Ordering>, Vec<Scaling>) {
+    match genie {
+        Genie::BiCgStab | Genie::Cg | Genie::Cocg | Genie::Gmres | Genie::Minres => {
+            (vec![Ordering::Auto], vec![Scaling::Auto])
+        }
+        Genie::Cholmod => (
+            vec![Ordering::Auto, Ordering::Amd, Ordering::Metis, Ordering::No],
+            vec![Scaling::Auto],
+        ),
+        Genie::Klu => (
+            vec![Ordering::Auto, Ordering::Amd, Ordering::Colamd, Ordering::Metis],
+            vec![Scaling::Auto, Scaling::No, Scaling::Sum, Scaling::Max],
+        ),
+        Genie::Mumps => (
+            vec![
+                Ordering::Auto,
+                Ordering::Amd,
+                Ordering::Amf,
+                Ordering::Metis,
+                Ordering::Pord,
+                Ordering::Qamd,
+                Ordering::Scotch,
+            ],
+            vec![
+                Scaling::Auto,
+                Scaling::No,
+                Scaling::Column,
+                Scaling::Diagonal,
+                Scaling::RowCol,
+                Scaling::RowColIter,
+                Scaling::RowColRig,
+            ],
+        ),
+        Genie::Native => (vec![Ordering::Auto, Ordering::Metis, Ordering::No], vec![Scaling::Auto]),
+        Genie::Umfpack => (
+            vec![
+                Ordering::Auto,
+                Ordering::Amd,
+                Ordering::Best,
+                Ordering::Cholmod,
+                Ordering::Metis,
+                Ordering::No,
+            ],
+            vec![Scaling::Auto, Scaling::No, Scaling::Sum, Scaling::Max],
+        ),
+    }
+}
+
+/// Returns the solvers selected for the benchmark mode
+fn get_genies(opt: &Options) -> Result<Vec<Genie>, StrError> {
+    let all = [
+        Genie::Cholmod,
+        Genie::Klu,
+        Genie::Mumps,
+        Genie::Native,
+        Genie::Umfpack,
+        Genie::BiCgStab,
+        Genie::Cg,
+        Genie::Cocg,
+        Genie::Gmres,
+        Genie::Minres,
+    ];
+    if opt.genies.to_lowercase() == "all" {
+        return Ok(all.to_vec());
+    }
+    let mut genies = Vec::new();
+    for name in opt.genies.split(',') {
+        let name = name.trim().to_lowercase();
+        match all.iter().find(|g| g.to_string() == name) {
+            Some(genie) => genies.push(*genie),
+            None => return Err("the --genies option has an unknown solver name"),
+        }
+    }
+    Ok(genies)
+}
+
+/// Runs one configuration repeatedly and aggregates the results
+///
+/// The `run` function performs a complete initialize/factorize/solve/verify cycle with a new solver.
+fn run_case<F>(genie: Genie, params: LinSolParams, repeat: usize, mut run: F) -> BenchmarkCase
+where
+    F: FnMut(Genie, LinSolParams) -> Result<StatsLinSol, StrError>,
+{
+    let mut case = BenchmarkCase {
+        genie: genie.to_string(),
+        ordering: format!("{:?}", params.ordering),
+        scaling: format!("{:?}", params.scaling),
+        effective_ordering: String::new(),
+        effective_scaling: String::new(),
+        repeat: 0,
+        factorize_min: u128::MAX,
+        factorize_mean: 0,
+        solve_min: u128::MAX,
+        solve_mean: 0,
+        total_min: u128::MAX,
+        relative_error: 0.0,
+        known_solution_error: None,
+        error: None,
+    };
+    for _ in 0..repeat {
+        match run(genie, params) {
+            Ok(stats) => {
+                let t = &stats.time_nanoseconds;
+                let factorize = t.initialize + t.factorize;
+                case.repeat += 1;
+                case.factorize_min = u128::min(case.factorize_min, factorize);
+                case.factorize_mean += factorize;
+                case.solve_min = u128::min(case.solve_min, t.solve);
+                case.solve_mean += t.solve;
+                case.total_min = u128::min(case.total_min, factorize + t.solve);
+                case.effective_ordering = stats.output.effective_ordering;
+                case.effective_scaling = stats.output.effective_scaling;
+                case.relative_error = stats.verify.relative_error;
+                case.known_solution_error = stats.known_solution_error;
+            }
+            Err(e) => {
+                case.error = Some(e.to_string());
+                break;
+            }
+        }
+    }
+    if case.repeat > 0 {
+        case.factorize_mean /= case.repeat as u128;
+        case.solve_mean /= case.repeat as u128;
+    }
+    case
+}
+
+/// Runs all available solvers with the relevant ordering and scaling combinations
+fn run_benchmark(opt: &Options) -> Result<(), StrError> {
+    if opt.repeat < 1 {
+        return Err("the number of repetitions must be ≥ 1");
+    }
+    let genies = get_genies(opt)?;
+    let mut stats = StatsLinSol::new();
+    let mut benchmark = Benchmark {
+        blas_lib: stats.main.blas_lib.clone(),
+        matrix: stats.matrix.clone(),
+        rhs: get_rhs_description(opt),
+        unavailable: Vec::new(),
+        cases: Vec::new(),
+    };
+
+    // read the matrix with the two symmetric handling options
+    let (lower_real, lower_complex) = read_matrix_market(&opt.matrix_market_file, MMsym::LeaveAsLower)?;
+    let (full_real, full_complex) = read_matrix_market(&opt.matrix_market_file, MMsym::MakeItFull)?;
+
+    // --- real ---------------------------------------------------------------------------------
+    if let (Some(lower), Some(full)) = (lower_real, full_real) {
+        let mut mat_lower = SparseMatrix::from_coo(lower);
+        let mut mat_full = SparseMatrix::from_coo(full);
+        set_matrix_info(&mut stats, opt, mat_lower.get_info(), false);
+        let (rhs, x_known) = get_rhs_real(opt, &mat_full)?;
+        let mut run = |genie: Genie, params: LinSolParams| -> Result<StatsLinSol, StrError> {
+            let mat = match get_handling(genie) {
+                MMsym::MakeItFull => &mut mat_full,
+                _ => &mut mat_lower,
+            };
+            let mut stats = StatsLinSol::new();
+            let mut solver = LinSolver::new(genie)?;
+            solver.factorize(mat, Some(params))?;
+            let mut x = Vector::new(rhs.dim());
+            solver.solve(&mut x, mat, &rhs, opt.verbose)?;
+            stats.verify = VerifyLinSys::from(mat, &x, &rhs)?;
+            if let Some(x_known) = &x_known {
+                stats.known_solution_error = Some(vec_max_abs_diff(&x, x_known)?.1);
+            }
+            solver.update_stats(&mut stats);
+            Ok(stats)
+        };
+        for genie in genies {
+            if let Err(e) = LinSolver::new(genie) {
+                benchmark.unavailable.push(format!("{}: {}", genie.to_string(), e));
+                continue;
+            }
+            let (orderings, scalings) = get_combinations(genie);
+            for ordering in &orderings {
+                for scaling in &scalings {
+                    let params = get_params(opt, *ordering, *scaling);
+                    benchmark.cases.push(run_case(genie, params, opt.repeat, &mut run));
+                }
+            }
+        }
+
+    // --- complex ------------------------------------------------------------------------------
+    } else {
+        let mut mat_lower = ComplexSparseMatrix::from_coo(lower_complex.unwrap());
+        let mut mat_full = ComplexSparseMatrix::from_coo(full_complex.unwrap());
+        set_matrix_info(&mut stats, opt, mat_lower.get_info(), true);
+        let (rhs, x_known) = get_rhs_complex(opt, &mat_full)?;
+        let mut run = |genie: Genie, params: LinSolParams| -> Result<StatsLinSol, StrError> {
+            let mat = match get_handling(genie) {
+                MMsym::MakeItFull => &mut mat_full,
+                _ => &mut mat_lower,
+            };
+            let mut stats = StatsLinSol::new();
+            let mut solver = ComplexLinSolver::new(genie)?;
+            solver.factorize(mat, Some(params))?;
+            let mut x = ComplexVector::new(rhs.dim());
+            solver.solve(&mut x, mat, &rhs, opt.verbose)?;
+            stats.verify = VerifyLinSys::from_complex(mat, &x, &rhs)?;
+            if let Some(x_known) = &x_known {
+                stats.known_solution_error = Some(complex_max_abs_diff(&x, x_known));
+            }
+            solver.update_stats(&mut stats);
+            Ok(stats)
+        };
+        for genie in genies {
+            if let Err(e) = ComplexLinSolver::new(genie) {
+                benchmark.unavailable.push(format!("{}: {}", genie.to_string(), e));
+                continue;
+            }
+            let (orderings, scalings) = get_combinations(genie);
+            for ordering in &orderings {
+                for scaling in &scalings {
+                    let params = get_params(opt, *ordering, *scaling);
+                    benchmark.cases.push(run_case(genie, params, opt.repeat, &mut run));
+                }
+            }
+        }
+    }
+    benchmark.matrix = stats.matrix;
+
+    // sort by the total time (the failed cases go last)
+    benchmark
+        .cases
+        .sort_by_key(|case| (case.error.is_some(), case.total_min));
+
+    // print table
+    print_benchmark_table(&benchmark);
+
+    // print JSON
+    if !opt.hide_json {
+        println!("{}", serde_json::to_string_pretty(&benchmark).unwrap());
+    }
+    Ok(())
+}
+
+/// Prints the comparison table of the benchmark mode
+fn print_benchmark_table(benchmark: &Benchmark) {
+    let m = &benchmark.matrix;
+    println!(
+        "matrix: {} ({} × {}, nnz = {}, symmetric = {}, complex = {})",
+        m.name, m.nrow, m.ncol, m.nnz, m.symmetric, m.complex
+    );
+    println!("rhs: {}", benchmark.rhs);
+    for message in &benchmark.unavailable {
+        println!("unavailable: {}", message);
+    }
+    println!(
+        "{:>8} {:>8} {:>10} {:>10} {:>10} {:>14} {:>14} {:>14} {:>10} {:>10}",
+        "genie",
+        "ordering",
+        "scaling",
+        "eff.order",
+        "eff.scale",
+        "factorize",
+        "solve",
+        "total",
+        "rel.error",
+        "x★ error"
+    );
+    for case in &benchmark.cases {
+        if let Some(e) = &case.error {
+            println!(
+                "{:>8} {:>8} {:>10} FAILED: {}",
+                case.genie, case.ordering, case.scaling, e
+            );
+            continue;
+        }
+        let known = match case.known_solution_error {
+            Some(error) => format!("{:.2e}", error),
+            None => "-".to_string(),
+        };
+        println!(
+            "{:>8} {:>8} {:>10} {:>10} {:>10} {:>14} {:>14} {:>14} {:>10.2e} {:>10}",
+            case.genie,
+            case.ordering,
+            case.scaling,
+            case.effective_ordering,
+            case.effective_scaling,
+            format_nanoseconds(case.factorize_min),
+            format_nanoseconds(case.solve_min),
+            format_nanoseconds(case.total_min),
+            case.relative_error,
+            known
+        );
+    }
+}
+
 fn get_bfwb62_correct_x() -> Vector {
     Vector::from(&[
         -1.02570048377040759e+05,
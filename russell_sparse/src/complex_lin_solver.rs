@@ -1,10 +1,14 @@
 #[cfg(feature = "with_mumps")]
 use super::ComplexSolverMUMPS;
 
-use super::{ComplexSparseMatrix, Genie, LinSolParams, StatsLinSol};
+#[cfg(feature = "with_suitesparse")]
+use super::{ComplexSolverCHOLMOD, ComplexSolverKLU, ComplexSolverUMFPACK};
+
+use super::{refine_solution, ComplexCscMatrix, ComplexSolverKrylov, ComplexSolverNative, ComplexSparseMatrix};
+use super::{Genie, LinSolParams};
+use super::{StatsLinSol, StatsLinSolRefinement, Sym};
 use crate::StrError;
-use crate::{ComplexSolverKLU, ComplexSolverUMFPACK};
-use russell_lab::ComplexVector;
+use russell_lab::{Complex64, ComplexMatrix, ComplexVector};
 
 /// Defines a unified interface for complex linear system solvers
 pub trait ComplexLinSolTrait: Send {
@@ -26,6 +30,49 @@ pub trait ComplexLinSolTrait: Send {
     ///    be "dropped" and a new solver allocated.
     fn factorize(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError>;
 
+    /// Performs the symbolic analysis (e.g., fill-reducing ordering) only
+    ///
+    /// Calling `analyze` followed by `factorize_numeric` is equivalent to calling `factorize` once.
+    /// The symbolic analysis is performed just once; afterwards, the numeric factorization
+    /// may be repeated with matrices having the same sparsity pattern (e.g., in Newton iterations).
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- The sparse matrix (COO, CSC, or CSR). Only the sparsity pattern is analyzed;
+    ///   however, the values should be present since some solvers may use them (e.g., for scaling).
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The default implementation returns an error because not all solvers
+    /// expose the symbolic analysis as a separate step.
+    fn analyze(&mut self, _mat: &mut ComplexSparseMatrix, _params: Option<LinSolParams>) -> Result<(), StrError> {
+        Err("the analyze step is not available for this solver")
+    }
+
+    /// Performs the numeric factorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- The sparse matrix with the same sparsity pattern as the one given to `analyze`
+    ///   (the values may differ, nonetheless)
+    ///
+    /// **Note:** The parameters given to `analyze` (or `factorize`) are used.
+    fn factorize_numeric(&mut self, _mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        Err("the numeric-only factorization is not available for this solver")
+    }
+
+    /// Performs the numeric refactorization reusing the symbolic analysis and the pivot sequence
+    ///
+    /// This is faster than `factorize_numeric` because no pivot search is performed. However, the
+    /// previous pivot sequence may yield an inaccurate factorization if the values change significantly.
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- The sparse matrix with the same sparsity pattern as the one previously factorized
+    ///   (the values may differ, nonetheless)
+    fn refactorize(&mut self, _mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        Err("the refactorization is not available for this solver")
+    }
+
     /// Computes the solution of the linear system
     ///
     /// Solves the linear system:
@@ -54,6 +101,156 @@ pub trait ComplexLinSolTrait: Send {
         verbose: bool,
     ) -> Result<(), StrError>;
 
+    /// Computes the solution of the transposed (or conjugate-transposed) linear system (reusing the factorization)
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   Aᵀ  · x = rhs    or    Aᴴ  · x = rhs
+    /// (m,m)  (m)  (m)        (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (not transposed).
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `conjugate` -- solves with the conjugate transpose `Aᴴ` instead of `Aᵀ`
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    ///
+    /// **Note:** The default implementation returns an error; i.e., the solver does not support transpose solves.
+    fn solve_transpose(
+        &mut self,
+        _x: &mut ComplexVector,
+        _mat: &ComplexSparseMatrix,
+        _rhs: &ComplexVector,
+        _conjugate: bool,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        Err("the transpose solve is not available for this solver")
+    }
+
+    /// Computes the solution of the linear system with multiple right-hand sides (reusing the factorization)
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   ·  X  = RHS
+    /// (m,m)  (m,k)  (m,k)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the (m,k) matrix of unknown values; each column corresponds to a column of `rhs`
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A.
+    /// * `rhs` -- the (m,k) matrix of right-hand sides
+    /// * `transpose` -- solves `Aᵀ · X = RHS` instead
+    /// * `conjugate` -- (if `transpose`) solves `Aᴴ · X = RHS` instead
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    ///
+    /// **Note:** The default implementation calls `solve` (or `solve_transpose`) for each column.
+    fn solve_multiple(
+        &mut self,
+        x: &mut ComplexMatrix,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexMatrix,
+        transpose: bool,
+        conjugate: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        let (m, k) = rhs.dims();
+        if x.dims() != (m, k) {
+            return Err("the dimensions of the matrix of unknown values X are incorrect");
+        }
+        let mut xj = ComplexVector::new(m);
+        let mut bj = ComplexVector::new(m);
+        for j in 0..k {
+            for i in 0..m {
+                bj[i] = rhs.get(i, j);
+            }
+            if transpose {
+                self.solve_transpose(&mut xj, mat, &bj, conjugate, verbose)?;
+            } else {
+                self.solve(&mut xj, mat, &bj, verbose)?;
+            }
+            for i in 0..m {
+                x.set(i, j, xj[i]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system with a sparse matrix of right-hand sides
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   ·  X  = RHS
+    /// (m,m)  (m,k)  (m,k)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the (m,k) (dense) matrix of unknown values
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A.
+    /// * `rhs` -- the (m,k) sparse matrix of right-hand sides (with [crate::Sym::No])
+    /// * `transpose` -- solves `Aᵀ · X = RHS` instead
+    /// * `conjugate` -- (if `transpose`) solves `Aᴴ · X = RHS` instead
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    ///
+    /// **Note:** The default implementation calls `solve` (or `solve_transpose`) for each column;
+    /// thus, only one dense column of the right-hand sides is allocated.
+    fn solve_sparse_rhs(
+        &mut self,
+        x: &mut ComplexMatrix,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexCscMatrix,
+        transpose: bool,
+        conjugate: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        if rhs.symmetric != Sym::No {
+            return Err("the sparse matrix of right-hand sides must have Sym::No");
+        }
+        let (m, k) = (rhs.nrow, rhs.ncol);
+        if x.dims() != (m, k) {
+            return Err("the dimensions of the matrix of unknown values X are incorrect");
+        }
+        let zero = Complex64::new(0.0, 0.0);
+        let mut xj = ComplexVector::new(m);
+        let mut bj = ComplexVector::new(m);
+        for j in 0..k {
+            bj.fill(zero);
+            for p in rhs.col_pointers[j]..rhs.col_pointers[j + 1] {
+                bj[rhs.row_indices[p as usize] as usize] += rhs.values[p as usize];
+            }
+            if transpose {
+                self.solve_transpose(&mut xj, mat, &bj, conjugate, verbose)?;
+            } else {
+                self.solve(&mut xj, mat, &bj, verbose)?;
+            }
+            for i in 0..m {
+                x.set(i, j, xj[i]);
+            }
+        }
+        Ok(())
+    }
+
     /// Updates the stats structure (should be called after solve)
     fn update_stats(&self, stats: &mut StatsLinSol);
 
@@ -65,12 +262,23 @@ pub trait ComplexLinSolTrait: Send {
 
     /// Returns the nanoseconds spent on solve
     fn get_ns_solve(&self) -> u128;
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        0
+    }
 }
 
 /// Unifies the access to linear system solvers
 pub struct ComplexLinSolver<'a> {
     /// Holds the actual implementation
     pub actual: Box<dyn Send + ComplexLinSolTrait + 'a>,
+
+    /// Holds the parameters given to factorize (used by the iterative refinement)
+    params: LinSolParams,
+
+    /// Holds the statistics of the last iterative refinement
+    refinement: StatsLinSolRefinement,
 }
 
 impl<'a> ComplexLinSolver<'a> {
@@ -80,19 +288,104 @@ impl<'a> ComplexLinSolver<'a> {
     ///
     /// * `genie` -- the actual implementation that does all the magic
     pub fn new(genie: Genie) -> Result<Self, StrError> {
-        #[cfg(feature = "with_mumps")]
         let actual: Box<dyn Send + ComplexLinSolTrait> = match genie {
+            Genie::BiCgStab => Box::new(ComplexSolverKrylov::new(genie)?),
+            Genie::Cg => Box::new(ComplexSolverKrylov::new(genie)?),
+            #[cfg(feature = "with_suitesparse")]
+            Genie::Cholmod => Box::new(ComplexSolverCHOLMOD::new()?),
+            #[cfg(not(feature = "with_suitesparse"))]
+            Genie::Cholmod => return Err("CHOLMOD solver is not available"),
+            Genie::Cocg => Box::new(ComplexSolverKrylov::new(genie)?),
+            Genie::Gmres => Box::new(ComplexSolverKrylov::new(genie)?),
+            #[cfg(feature = "with_suitesparse")]
             Genie::Klu => Box::new(ComplexSolverKLU::new()?),
+            #[cfg(not(feature = "with_suitesparse"))]
+            Genie::Klu => return Err("KLU solver is not available"),
+            Genie::Minres => Box::new(ComplexSolverKrylov::new(genie)?),
+            #[cfg(feature = "with_mumps")]
             Genie::Mumps => Box::new(ComplexSolverMUMPS::new()?),
-            Genie::Umfpack => Box::new(ComplexSolverUMFPACK::new()?),
-        };
-        #[cfg(not(feature = "with_mumps"))]
-        let actual: Box<dyn Send + ComplexLinSolTrait> = match genie {
-            Genie::Klu => Box::new(ComplexSolverKLU::new()?),
+            #[cfg(not(feature = "with_mumps"))]
             Genie::Mumps => return Err("MUMPS solver is not available"),
+            Genie::Native => Box::new(ComplexSolverNative::new()?),
+            #[cfg(feature = "with_suitesparse")]
             Genie::Umfpack => Box::new(ComplexSolverUMFPACK::new()?),
+            #[cfg(not(feature = "with_suitesparse"))]
+            Genie::Umfpack => return Err("UMFPACK solver is not available"),
         };
-        Ok(ComplexLinSolver { actual })
+        Ok(ComplexLinSolver {
+            actual,
+            params: LinSolParams::new(),
+            refinement: StatsLinSolRefinement::default(),
+        })
+    }
+
+    /// Performs the factorization (and analysis/initialization if needed)
+    ///
+    /// This function calls [ComplexLinSolTrait::factorize()] and saves the parameters needed by
+    /// the iterative refinement performed by [ComplexLinSolver::solve()].
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- The sparse matrix (COO, CSC, or CSR).
+    /// * `params` -- configuration parameters; None => use default
+    pub fn factorize(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if par.refinement_tolerance < 0.0 {
+            return Err("the refinement tolerance must be ≥ 0");
+        }
+        self.actual.factorize(mat, params)?;
+        self.params = par;
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system followed by the iterative refinement (if requested)
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A.
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows messages
+    ///
+    /// # Notes
+    ///
+    /// 1. The refinement is performed if [LinSolParams::refinement_max_iterations] > 0 (given to `factorize`)
+    /// 2. Calling `actual.solve` directly skips the refinement
+    /// 3. The refinement history is saved in [StatsLinSol::refinement] by [ComplexLinSolver::update_stats()]
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    pub fn solve(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        self.actual.solve(x, mat, rhs, verbose)?;
+        self.refinement = StatsLinSolRefinement::default();
+        if self.params.refinement_max_iterations > 0 {
+            let actual = &mut self.actual;
+            refine_solution(x, mat, rhs, &self.params, &mut self.refinement, |d, r| {
+                actual.solve(d, mat, r, verbose)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Updates the stats structure (should be called after solve), including the refinement statistics
+    pub fn update_stats(&self, stats: &mut StatsLinSol) {
+        self.actual.update_stats(stats);
+        stats.refinement = self.refinement.clone();
     }
 
     /// Computes the solution of a complex linear system
@@ -119,7 +412,9 @@ impl<'a> ComplexLinSolver<'a> {
     ///
     /// 1. For symmetric matrices, `MUMPS` requires [crate::Sym::YesLower]
     /// 2. For symmetric matrices, `UMFPACK` requires [crate::Sym::YesFull]
-    /// 4. This function calls the actual implementation (genie) via the functions `factorize`, and `solve`.
+    /// 3. The native solver ([Genie::Native]) accepts any [crate::Sym]; `CHOLMOD` accepts any symmetric representation
+    /// 4. This function calls the actual implementation (genie) via the functions `factorize`, and `solve`,
+    ///    followed by the iterative refinement if [LinSolParams::refinement_max_iterations] > 0.
     /// 5. This function is best for a **single-use**, whereas the actual
     ///    solver should be considered for a recurrent use (e.g., inside a loop).
     pub fn compute(
@@ -130,9 +425,9 @@ impl<'a> ComplexLinSolver<'a> {
         params: Option<LinSolParams>,
     ) -> Result<Self, StrError> {
         let mut solver = ComplexLinSolver::new(genie)?;
-        solver.actual.factorize(mat, params)?;
+        solver.factorize(mat, params)?;
         let verbose = if let Some(p) = params { p.verbose } else { false };
-        solver.actual.solve(x, mat, rhs, verbose)?;
+        solver.solve(x, mat, rhs, verbose)?;
         Ok(solver)
     }
 }
@@ -142,13 +437,27 @@ impl<'a> ComplexLinSolver<'a> {
 #[cfg(test)]
 mod tests {
     use super::ComplexLinSolver;
-    use crate::{ComplexSparseMatrix, Genie, Samples};
+    use crate::{ComplexSparseMatrix, Genie, LinSolParams, Samples, StatsLinSol};
     use russell_lab::{complex_vec_approx_eq, cpx, Complex64, ComplexVector};
 
     #[cfg(feature = "with_mumps")]
     use serial_test::serial;
 
     #[test]
+    fn complex_lin_solver_compute_works_iterative() {
+        let (coo, _, _, _) = Samples::complex_symmetric_3x3_full();
+        let rhs = ComplexVector::from(&[cpx!(-3.0, 3.0), cpx!(2.0, -2.0), cpx!(9.0, 7.0)]);
+        let x_correct = &[cpx!(1.0, 1.0), cpx!(2.0, -2.0), cpx!(3.0, 3.0)];
+        for genie in [Genie::BiCgStab, Genie::Cocg, Genie::Gmres] {
+            let mut mat = ComplexSparseMatrix::from_coo(coo.clone());
+            let mut x = ComplexVector::new(3);
+            ComplexLinSolver::compute(genie, &mut x, &mut mat, &rhs, None).unwrap();
+            complex_vec_approx_eq(&x, x_correct, 1e-10);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "with_suitesparse")]
     fn complex_lin_solver_compute_works_klu() {
         let (coo, _, _, _) = Samples::complex_symmetric_3x3_full();
         let mut mat = ComplexSparseMatrix::from_coo(coo);
@@ -173,6 +482,37 @@ mod tests {
     }
 
     #[test]
+    fn complex_lin_solver_compute_works_native() {
+        let (coo, _, _, _) = Samples::complex_symmetric_3x3_lower();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        let mut x = ComplexVector::new(3);
+        let rhs = ComplexVector::from(&[cpx!(-3.0, 3.0), cpx!(2.0, -2.0), cpx!(9.0, 7.0)]);
+        ComplexLinSolver::compute(Genie::Native, &mut x, &mut mat, &rhs, None).unwrap();
+        let x_correct = &[cpx!(1.0, 1.0), cpx!(2.0, -2.0), cpx!(3.0, 3.0)];
+        complex_vec_approx_eq(&x, x_correct, 1e-15);
+    }
+
+    #[test]
+    fn complex_lin_solver_refinement_works() {
+        let (coo, _, _, _) = Samples::complex_symmetric_3x3_lower();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        let mut x = ComplexVector::new(3);
+        let rhs = ComplexVector::from(&[cpx!(-3.0, 3.0), cpx!(2.0, -2.0), cpx!(9.0, 7.0)]);
+        let mut params = LinSolParams::new();
+        params.refinement_max_iterations = 3;
+        let solver = ComplexLinSolver::compute(Genie::Native, &mut x, &mut mat, &rhs, Some(params)).unwrap();
+        let x_correct = &[cpx!(1.0, 1.0), cpx!(2.0, -2.0), cpx!(3.0, 3.0)];
+        complex_vec_approx_eq(&x, x_correct, 1e-15);
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert!(stats.refinement.converged);
+        assert!(!stats.refinement.mixed_precision);
+        assert_eq!(stats.refinement.history.len(), stats.refinement.iterations + 1);
+        assert!(stats.refinement.backward_error <= 1e-15);
+    }
+
+    #[test]
+    #[cfg(feature = "with_suitesparse")]
     fn complex_lin_solver_compute_works_umfpack() {
         let (coo, _, _, _) = Samples::complex_symmetric_3x3_full();
         let mut mat = ComplexSparseMatrix::from_coo(coo);
@@ -0,0 +1,625 @@
+use super::{cholmod_ordering, cholmod_ordering_name, cholmod_stype, cholmod_supernodal, cholmod_system};
+use super::{handle_cholmod_error_code, CholmodSystem};
+use super::{ComplexCscMatrix, ComplexLinSolTrait, ComplexSparseMatrix, LinSolParams, StatsLinSol, Sym};
+use crate::constants::*;
+use crate::StrError;
+use russell_lab::{Complex64, ComplexVector, Stopwatch};
+
+/// Opaque struct holding a C-pointer to InterfaceComplexCHOLMOD
+///
+/// Reference: <https://doc.rust-lang.org/nomicon/ffi.html#representing-opaque-structs>
+#[repr(C)]
+struct InterfaceComplexCHOLMOD {
+    _data: [u8; 0],
+    _marker: core::marker::PhantomData<(*mut u8, core::marker::PhantomPinned)>,
+}
+
+/// Enforce Send on the C structure
+///
+/// <https://stackoverflow.com/questions/50258359/can-a-struct-containing-a-raw-pointer-implement-send-and-be-ffi-safe>
+unsafe impl Send for InterfaceComplexCHOLMOD {}
+
+/// Enforce Send on the Rust structure
+///
+/// <https://stackoverflow.com/questions/50258359/can-a-struct-containing-a-raw-pointer-implement-send-and-be-ffi-safe>
+unsafe impl Send for ComplexSolverCHOLMOD {}
+
+extern "C" {
+    fn complex_solver_cholmod_new() -> *mut InterfaceComplexCHOLMOD;
+    fn complex_solver_cholmod_drop(solver: *mut InterfaceComplexCHOLMOD);
+    fn complex_solver_cholmod_initialize(
+        solver: *mut InterfaceComplexCHOLMOD,
+        ordering: i32,
+        supernodal: i32,
+        stype: i32,
+        ndim: i32,
+        col_pointers: *const i32,
+        row_indices: *const i32,
+    ) -> i32;
+    fn complex_solver_cholmod_factorize(
+        solver: *mut InterfaceComplexCHOLMOD,
+        effective_ordering: *mut i32,
+        effective_supernodal: *mut CcBool,
+        ndim: i32,
+        col_pointers: *const i32,
+        row_indices: *const i32,
+        values: *const Complex64,
+    ) -> i32;
+    fn complex_solver_cholmod_solve(
+        solver: *mut InterfaceComplexCHOLMOD,
+        system: i32,
+        ndim: i32,
+        x: *mut Complex64,
+        rhs: *const Complex64,
+    ) -> i32;
+    fn complex_solver_cholmod_log_det(solver: *mut InterfaceComplexCHOLMOD, log_det: *mut f64) -> i32;
+}
+
+/// Wraps the CHOLMOD solver for sparse Hermitian positive-definite linear systems
+///
+/// The matrix is assumed to be Hermitian; i.e., only the lower (or upper) triangle is
+/// given and the other triangle is the conjugate transpose.
+///
+/// **Warning:** This solver may "run out of memory" for very large matrices.
+pub struct ComplexSolverCHOLMOD {
+    /// Holds a pointer to the C interface to CHOLMOD
+    solver: *mut InterfaceComplexCHOLMOD,
+
+    /// Indicates whether the solver has been initialized or not (just once)
+    initialized: bool,
+
+    /// Indicates whether the sparse matrix has been factorized or not
+    factorized: bool,
+
+    /// Holds the symmetric flag saved in initialize
+    initialized_sym: Sym,
+
+    /// Holds the matrix dimension saved in initialize
+    initialized_ndim: usize,
+
+    /// Holds the number of non-zeros saved in initialize
+    initialized_nnz: usize,
+
+    /// Holds the fingerprint of the sparsity pattern saved in initialize
+    initialized_fingerprint: u64,
+
+    /// Holds the parameters given to analyze (or factorize)
+    params: LinSolParams,
+
+    /// Holds the used ordering (after factorize)
+    effective_ordering: i32,
+
+    /// Indicates whether the supernodal method has been used or not (after factorize)
+    effective_supernodal: CcBool,
+
+    /// Holds the determinant coefficient (if requested)
+    ///
+    /// det = coefficient * pow(10, exponent)
+    determinant_coefficient: f64,
+
+    /// Holds the determinant exponent (if requested)
+    ///
+    /// det = coefficient * pow(10, exponent)
+    determinant_exponent: f64,
+
+    /// Stopwatch to measure computation times
+    stopwatch: Stopwatch,
+
+    /// Time spent on initialize in nanoseconds
+    time_initialize_ns: u128,
+
+    /// Time spent on factorize in nanoseconds
+    time_factorize_ns: u128,
+
+    /// Time spent on solve in nanoseconds
+    time_solve_ns: u128,
+
+    /// Time spent on refactorize in nanoseconds
+    time_refactorize_ns: u128,
+}
+
+impl Drop for ComplexSolverCHOLMOD {
+    /// Tells the c-code to release memory
+    fn drop(&mut self) {
+        unsafe {
+            complex_solver_cholmod_drop(self.solver);
+        }
+    }
+}
+
+impl ComplexSolverCHOLMOD {
+    /// Allocates a new instance
+    pub fn new() -> Result<Self, StrError> {
+        unsafe {
+            let solver = complex_solver_cholmod_new();
+            if solver.is_null() {
+                return Err("c-code failed to allocate the CHOLMOD solver");
+            }
+            Ok(ComplexSolverCHOLMOD {
+                solver,
+                initialized: false,
+                factorized: false,
+                initialized_sym: Sym::No,
+                initialized_ndim: 0,
+                initialized_nnz: 0,
+                initialized_fingerprint: 0,
+                params: LinSolParams::new(),
+                effective_ordering: -1,
+                effective_supernodal: 0,
+                determinant_coefficient: 0.0,
+                determinant_exponent: 0.0,
+                stopwatch: Stopwatch::new(),
+                time_initialize_ns: 0,
+                time_factorize_ns: 0,
+                time_solve_ns: 0,
+                time_refactorize_ns: 0,
+            })
+        }
+    }
+
+    /// Checks whether the matrix has the same structure as the one given to analyze (or the first factorize)
+    fn check_pattern(&self, csc: &ComplexCscMatrix) -> Result<(), StrError> {
+        if csc.symmetric != self.initialized_sym {
+            return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+        }
+        if csc.nrow != self.initialized_ndim {
+            return Err("subsequent factorizations must use the same matrix (ndim differs)");
+        }
+        if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
+            return Err("subsequent factorizations must use the same matrix (nnz differs)");
+        }
+        if csc.get_pattern_fingerprint() != self.initialized_fingerprint {
+            return Err("subsequent factorizations must use the same matrix (pattern differs)");
+        }
+        Ok(())
+    }
+
+    /// Checks the matrix and performs the numeric factorization reusing the symbolic factor
+    ///
+    /// Returns the elapsed time in nanoseconds.
+    fn numeric(&mut self, mat: &mut ComplexSparseMatrix) -> Result<u128, StrError> {
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        self.check_pattern(csc)?;
+
+        // call factorize
+        self.factorized = false;
+        self.stopwatch.reset();
+        unsafe {
+            let status = complex_solver_cholmod_factorize(
+                self.solver,
+                &mut self.effective_ordering,
+                &mut self.effective_supernodal,
+                to_i32(csc.nrow),
+                csc.col_pointers.as_ptr(),
+                csc.row_indices.as_ptr(),
+                csc.values.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_cholmod_error_code(status));
+            }
+        }
+        let elapsed = self.stopwatch.stop();
+        self.factorized = true;
+
+        // determinant
+        if self.params.compute_determinant {
+            let exponent = self.log_determinant()? / f64::ln(10.0);
+            self.determinant_exponent = f64::floor(exponent);
+            self.determinant_coefficient = f64::powf(10.0, exponent - self.determinant_exponent);
+        }
+        Ok(elapsed)
+    }
+
+    /// Solves a (partial) system with the factorization
+    ///
+    /// See [CholmodSystem] for the available systems.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the solution vector with dimension equal to the dimension of A
+    ///
+    /// # Input
+    ///
+    /// * `rhs` -- the right-hand side vector with dimension equal to the dimension of A
+    /// * `system` -- the system to be solved
+    pub fn solve_system(
+        &mut self,
+        x: &mut ComplexVector,
+        rhs: &ComplexVector,
+        system: CholmodSystem,
+    ) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+        if x.dim() != self.initialized_ndim {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.dim() != self.initialized_ndim {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+        let ndim = to_i32(self.initialized_ndim);
+        self.stopwatch.reset();
+        unsafe {
+            let status = complex_solver_cholmod_solve(
+                self.solver,
+                cholmod_system(system),
+                ndim,
+                x.as_mut_data().as_mut_ptr(),
+                rhs.as_data().as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_cholmod_error_code(status));
+            }
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+        Ok(())
+    }
+
+    /// Returns the natural logarithm of the determinant of A
+    ///
+    /// **Note:** The determinant of a Hermitian positive-definite matrix is real and positive.
+    pub fn log_determinant(&self) -> Result<f64, StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before log_determinant");
+        }
+        let mut log_det = 0.0;
+        unsafe {
+            let status = complex_solver_cholmod_log_det(self.solver, &mut log_det);
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_cholmod_error_code(status));
+            }
+        }
+        Ok(log_det)
+    }
+}
+
+impl ComplexLinSolTrait for ComplexSolverCHOLMOD {
+    /// Performs the factorization (and analysis/initialization if needed)
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`), Hermitian, and positive-definite
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// # Notes
+    ///
+    /// 1. The structure of the matrix (nrow, ncol, nnz, sym, and the sparsity pattern) must be
+    ///    exactly the same among multiple calls to `factorize`. The values may differ
+    ///    from call to call, nonetheless.
+    /// 2. The first call to `factorize` will define the structure which must be
+    ///    kept the same for the next calls.
+    /// 3. If the structure of the matrix needs to be changed, the solver must
+    ///    be "dropped" and a new solver allocated.
+    /// 4. The `sym` flag is interpreted as Hermitian; with [Sym::YesFull],
+    ///    only the upper triangle is accessed
+    /// 5. The method (supernodal or simplicial) is selected by [LinSolParams::cholmod_supernodal]
+    fn factorize(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if !self.initialized {
+            self.analyze(mat, Some(par))?;
+        }
+        self.params = par;
+        self.factorize_numeric(mat)
+    }
+
+    /// Performs the symbolic analysis (fill-reducing ordering and elimination tree) only
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square and Hermitian (any [Sym] representation except [Sym::No])
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The analysis is performed just once; i.e., the solver must be
+    /// "dropped" and a new solver allocated if the structure of the matrix changes.
+    fn analyze(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        // check
+        if self.initialized {
+            return Err("the analysis has been performed already");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        if csc.nrow != csc.ncol {
+            return Err("the matrix must be square");
+        }
+        if csc.symmetric == Sym::No {
+            return Err("CHOLMOD requires a symmetric matrix");
+        }
+        self.initialized_sym = csc.symmetric;
+        self.initialized_ndim = csc.nrow;
+        self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
+        self.initialized_fingerprint = csc.get_pattern_fingerprint();
+
+        // parameters
+        self.params = if let Some(p) = params { p } else { LinSolParams::new() };
+
+        // input parameters
+        let ordering = cholmod_ordering(self.params.ordering);
+        let supernodal = cholmod_supernodal(self.params.cholmod_supernodal);
+
+        // call initialize
+        self.stopwatch.reset();
+        unsafe {
+            let status = complex_solver_cholmod_initialize(
+                self.solver,
+                ordering,
+                supernodal,
+                cholmod_stype(csc.symmetric),
+                to_i32(csc.nrow),
+                csc.col_pointers.as_ptr(),
+                csc.row_indices.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_cholmod_error_code(status));
+            }
+        }
+        self.time_initialize_ns = self.stopwatch.stop();
+
+        // done
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric factorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one given to `analyze`
+    fn factorize_numeric(&mut self, mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        if !self.initialized {
+            return Err("the function analyze must be called before factorize_numeric");
+        }
+        self.time_factorize_ns = self.numeric(mat)?;
+        Ok(())
+    }
+
+    /// Performs the numeric refactorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one previously factorized
+    ///
+    /// **Note:** Since the Cholesky factorization does not pivot, this function is equivalent
+    /// to `factorize_numeric`; however, the time is recorded as the refactorization time.
+    fn refactorize(&mut self, mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before refactorize");
+        }
+        self.time_refactorize_ns = self.numeric(mat)?;
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and Hermitian
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        // check
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+
+        // access CSC matrix
+        // (possibly already converted from COO, because factorize was (should have been) called)
+        let csc = mat.get_csc()?;
+
+        // check already factorized data
+        let (nrow, ncol, nnz, sym) = csc.get_info();
+        if sym != self.initialized_sym {
+            return Err("solve must use the same matrix (symmetric differs)");
+        }
+        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
+            return Err("solve must use the same matrix (ndim differs)");
+        }
+        if nnz != self.initialized_nnz {
+            return Err("solve must use the same matrix (nnz differs)");
+        }
+
+        // call CHOLMOD solve
+        self.solve_system(x, rhs, CholmodSystem::A)
+    }
+
+    /// Computes the solution of the transposed (or conjugate-transposed) linear system
+    ///
+    /// Since A is Hermitian, `Aᴴ · x = rhs` is the same as `A · x = rhs`, whereas
+    /// `Aᵀ · x = rhs` is equivalent to `A · conj(x) = conj(rhs)`.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and Hermitian
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `conjugate` -- solves with `Aᴴ` instead of `Aᵀ`
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_transpose(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        conjugate: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        if conjugate {
+            return self.solve(x, mat, rhs, verbose);
+        }
+        let rhs_conj = ComplexVector::from(&rhs.as_data().iter().map(|v| v.conj()).collect::<Vec<_>>());
+        self.solve(x, mat, &rhs_conj, verbose)?;
+        x.as_mut_data().iter_mut().for_each(|v| *v = v.conj());
+        Ok(())
+    }
+
+    /// Updates the stats structure (should be called after solve)
+    fn update_stats(&self, stats: &mut StatsLinSol) {
+        stats.main.solver = if cfg!(feature = "local_suitesparse") {
+            "CHOLMOD-local".to_string()
+        } else {
+            "CHOLMOD".to_string()
+        };
+        stats.output.effective_ordering = cholmod_ordering_name(self.effective_ordering);
+        stats.output.effective_scaling = "No".to_string();
+        stats.output.cholmod_method = if self.effective_supernodal != 0 {
+            "Supernodal".to_string()
+        } else {
+            "Simplicial".to_string()
+        };
+        stats
+            .determinant
+            .set(self.determinant_coefficient, 0.0, 10.0, self.determinant_exponent);
+        stats.time_nanoseconds.initialize = self.time_initialize_ns;
+        stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.refactorize = self.time_refactorize_ns;
+        stats.time_nanoseconds.solve = self.time_solve_ns;
+    }
+
+    /// Returns the nanoseconds spent on initialize
+    fn get_ns_init(&self) -> u128 {
+        self.time_initialize_ns
+    }
+
+    /// Returns the nanoseconds spent on factorize
+    fn get_ns_fact(&self) -> u128 {
+        self.time_factorize_ns
+    }
+
+    /// Returns the nanoseconds spent on solve
+    fn get_ns_solve(&self) -> u128 {
+        self.time_solve_ns
+    }
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        self.time_refactorize_ns
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComplexCooMatrix, Samples};
+    use russell_lab::{approx_eq, complex_vec_approx_eq, cpx};
+
+    #[test]
+    fn new_and_drop_work() {
+        // you may debug into the C-code to see that drop is working
+        let solver = ComplexSolverCHOLMOD::new().unwrap();
+        assert!(!solver.factorized);
+    }
+
+    #[test]
+    fn factorize_handles_errors() {
+        let mut solver = ComplexSolverCHOLMOD::new().unwrap();
+        let (coo, _, _, _) = Samples::complex_rectangular_4x3();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("the matrix must be square")
+        );
+        let mut coo = ComplexCooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(1.0, 0.0)).unwrap();
+        coo.put(1, 1, cpx!(2.0, 0.0)).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("CHOLMOD requires a symmetric matrix")
+        );
+    }
+
+    #[test]
+    fn solve_handles_errors() {
+        let mut coo = ComplexCooMatrix::new(2, 2, 2, Sym::YesLower).unwrap();
+        coo.put(0, 0, cpx!(1.0, 0.0)).unwrap();
+        coo.put(1, 1, cpx!(2.0, 0.0)).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        let mut solver = ComplexSolverCHOLMOD::new().unwrap();
+        let mut x = ComplexVector::new(2);
+        let rhs = ComplexVector::new(2);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false),
+            Err("the function factorize must be called before solve")
+        );
+        assert_eq!(
+            solver.log_determinant().err(),
+            Some("the function factorize must be called before log_determinant")
+        );
+        solver.factorize(&mut mat, None).unwrap();
+        let mut x = ComplexVector::new(1);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false),
+            Err("the dimension of the vector of unknown values x is incorrect")
+        );
+    }
+
+    #[test]
+    fn solve_works() {
+        // A = | 4     1+i |   (Hermitian, det = 12 - 2 = 10)
+        //     | 1-i   3   |
+        let mut coo = ComplexCooMatrix::new(2, 2, 3, Sym::YesLower).unwrap();
+        coo.put(0, 0, cpx!(4.0, 0.0)).unwrap();
+        coo.put(1, 0, cpx!(1.0, -1.0)).unwrap();
+        coo.put(1, 1, cpx!(3.0, 0.0)).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        let mut solver = ComplexSolverCHOLMOD::new().unwrap();
+        let mut params = LinSolParams::new();
+        params.compute_determinant = true;
+        solver.factorize(&mut mat, Some(params)).unwrap();
+
+        // x = [1, i]
+        let rhs = ComplexVector::from(&[cpx!(3.0, 1.0), cpx!(1.0, 2.0)]);
+        let mut x = ComplexVector::new(2);
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        let x_correct = &[cpx!(1.0, 0.0), cpx!(0.0, 1.0)];
+        complex_vec_approx_eq(&x, x_correct, 1e-14);
+
+        // partial solves
+        let mut y = ComplexVector::new(2);
+        let mut z = ComplexVector::new(2);
+        solver.solve_system(&mut y, &rhs, CholmodSystem::P).unwrap();
+        solver.solve_system(&mut z, &y, CholmodSystem::L).unwrap();
+        solver.solve_system(&mut y, &z, CholmodSystem::Lt).unwrap();
+        solver.solve_system(&mut z, &y, CholmodSystem::Pt).unwrap();
+        complex_vec_approx_eq(&z, x_correct, 1e-14);
+
+        // determinant
+        approx_eq(solver.log_determinant().unwrap(), f64::ln(10.0), 1e-14);
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        let d = stats.determinant.mantissa_real * f64::powf(10.0, stats.determinant.exponent);
+        approx_eq(d, 10.0, 1e-13);
+    }
+}
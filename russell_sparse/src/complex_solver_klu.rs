@@ -1,9 +1,9 @@
 use super::{handle_klu_error_code, klu_ordering, klu_scaling};
-use super::{ComplexLinSolTrait, ComplexSparseMatrix, LinSolParams, StatsLinSol, Sym};
+use super::{ComplexCscMatrix, ComplexLinSolTrait, ComplexSparseMatrix, LinSolParams, StatsLinSol, Sym};
 use super::{KLU_ORDERING_AMD, KLU_ORDERING_COLAMD, KLU_SCALE_MAX, KLU_SCALE_NONE, KLU_SCALE_SUM};
 use crate::constants::*;
 use crate::StrError;
-use russell_lab::{complex_vec_copy, Complex64, ComplexVector, Stopwatch};
+use russell_lab::{Complex64, ComplexMatrix, ComplexVector, Stopwatch};
 
 /// Opaque struct holding a C-pointer to InterfaceComplexKLU
 ///
@@ -45,7 +45,22 @@ extern "C" {
         row_indices: *const i32,
         values: *const Complex64,
     ) -> i32;
-    fn complex_solver_klu_solve(solver: *mut InterfaceComplexKLU, ndim: i32, in_rhs_out_x: *mut Complex64) -> i32;
+    fn complex_solver_klu_refactorize(
+        solver: *mut InterfaceComplexKLU,
+        cond_estimate: *mut f64,
+        compute_cond: CcBool,
+        col_pointers: *const i32,
+        row_indices: *const i32,
+        values: *const Complex64,
+    ) -> i32;
+    fn complex_solver_klu_solve(
+        solver: *mut InterfaceComplexKLU,
+        ndim: i32,
+        nrhs: i32,
+        transpose: CcBool,
+        conjugate: CcBool,
+        in_rhs_out_x: *mut Complex64,
+    ) -> i32;
 }
 
 /// Wraps the KLU solver for sparse linear systems
@@ -70,6 +85,12 @@ pub struct ComplexSolverKLU {
     /// Holds the number of non-zeros saved in initialize
     initialized_nnz: usize,
 
+    /// Holds the fingerprint of the sparsity pattern saved in initialize
+    initialized_fingerprint: u64,
+
+    /// Holds the parameters given to analyze (or factorize)
+    params: LinSolParams,
+
     /// Holds the used ordering (after factorize)
     effective_ordering: i32,
 
@@ -90,6 +111,9 @@ pub struct ComplexSolverKLU {
 
     /// Time spent on solve in nanoseconds
     time_solve_ns: u128,
+
+    /// Time spent on refactorize in nanoseconds
+    time_refactorize_ns: u128,
 }
 
 impl Drop for ComplexSolverKLU {
@@ -116,6 +140,8 @@ impl ComplexSolverKLU {
                 initialized_sym: Sym::No,
                 initialized_ndim: 0,
                 initialized_nnz: 0,
+                initialized_fingerprint: 0,
+                params: LinSolParams::new(),
                 effective_ordering: -1,
                 effective_scaling: -1,
                 cond_estimate: 0.0,
@@ -123,9 +149,89 @@ impl ComplexSolverKLU {
                 time_initialize_ns: 0,
                 time_factorize_ns: 0,
                 time_solve_ns: 0,
+                time_refactorize_ns: 0,
             })
         }
     }
+
+    /// Checks whether the matrix has the same structure as the one given to analyze (or the first factorize)
+    fn check_pattern(&self, csc: &ComplexCscMatrix) -> Result<(), StrError> {
+        if csc.symmetric != self.initialized_sym {
+            return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+        }
+        if csc.nrow != self.initialized_ndim {
+            return Err("subsequent factorizations must use the same matrix (ndim differs)");
+        }
+        if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
+            return Err("subsequent factorizations must use the same matrix (nnz differs)");
+        }
+        if csc.get_pattern_fingerprint() != self.initialized_fingerprint {
+            return Err("subsequent factorizations must use the same matrix (pattern differs)");
+        }
+        Ok(())
+    }
+
+    /// Checks the data and solves `A · X = RHS`, `Aᵀ · X = RHS`, or `Aᴴ · X = RHS` (col-major arrays)
+    fn solve_internal(
+        &mut self,
+        x: &mut [Complex64],
+        mat: &ComplexSparseMatrix,
+        rhs: &[Complex64],
+        nrhs: usize,
+        transpose: bool,
+        conjugate: bool,
+    ) -> Result<(), StrError> {
+        // check
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+
+        // access CSC matrix
+        // (possibly already converted from COO, because factorize was (should have been) called)
+        let csc = mat.get_csc()?;
+
+        // check already factorized data
+        let (nrow, ncol, nnz, sym) = csc.get_info();
+        if sym != self.initialized_sym {
+            return Err("solve must use the same matrix (symmetric differs)");
+        }
+        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
+            return Err("solve must use the same matrix (ndim differs)");
+        }
+        if nnz != self.initialized_nnz {
+            return Err("solve must use the same matrix (nnz differs)");
+        }
+
+        // check vectors
+        if x.len() != self.initialized_ndim * nrhs {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.len() != self.initialized_ndim * nrhs {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+
+        // call KLU solve
+        let ndim = to_i32(self.initialized_ndim);
+        x.copy_from_slice(rhs);
+        self.stopwatch.reset();
+        unsafe {
+            let status = complex_solver_klu_solve(
+                self.solver,
+                ndim,
+                to_i32(nrhs),
+                if transpose { 1 } else { 0 },
+                if conjugate { 1 } else { 0 },
+                x.as_mut_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_klu_error_code(status));
+            }
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+
+        // done
+        Ok(())
+    }
 }
 
 impl ComplexLinSolTrait for ComplexSolverKLU {
@@ -140,7 +246,7 @@ impl ComplexLinSolTrait for ComplexSolverKLU {
     ///
     /// # Notes
     ///
-    /// 1. The structure of the matrix (nrow, ncol, nnz, sym) must be
+    /// 1. The structure of the matrix (nrow, ncol, nnz, sym, and the sparsity pattern) must be
     ///    exactly the same among multiple calls to `factorize`. The values may differ
     ///    from call to call, nonetheless.
     /// 2. The first call to `factorize` will define the structure which must be
@@ -149,65 +255,96 @@ impl ComplexLinSolTrait for ComplexSolverKLU {
     ///    be "dropped" and a new solver allocated.
     /// 4. For symmetric matrices, `KLU` requires [Sym::YesFull]
     fn factorize(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
-        // get CSC matrix
-        // (or convert from COO if CSC is not available and COO is available)
-        let csc = mat.get_csc_or_from_coo()?;
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if !self.initialized {
+            self.analyze(mat, Some(par))?;
+        }
+        self.params = par;
+        self.factorize_numeric(mat)
+    }
 
+    /// Performs the symbolic analysis (ordering) only
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`) and, if symmetric,
+    ///   the symmetric flag must be [Sym::YesFull]
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The analysis is performed just once; i.e., the solver must be
+    /// "dropped" and a new solver allocated if the structure of the matrix changes.
+    fn analyze(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
         // check
         if self.initialized {
-            if csc.symmetric != self.initialized_sym {
-                return Err("subsequent factorizations must use the same matrix (symmetric differs)");
-            }
-            if csc.nrow != self.initialized_ndim {
-                return Err("subsequent factorizations must use the same matrix (ndim differs)");
-            }
-            if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
-                return Err("subsequent factorizations must use the same matrix (nnz differs)");
-            }
-        } else {
-            if csc.nrow != csc.ncol {
-                return Err("the matrix must be square");
-            }
-            if csc.symmetric == Sym::YesLower || csc.symmetric == Sym::YesUpper {
-                return Err("KLU requires Sym::YesFull for symmetric matrices");
-            }
-            self.initialized_sym = csc.symmetric;
-            self.initialized_ndim = csc.nrow;
-            self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
+            return Err("the analysis has been performed already");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        if csc.nrow != csc.ncol {
+            return Err("the matrix must be square");
         }
+        if csc.symmetric == Sym::YesLower || csc.symmetric == Sym::YesUpper {
+            return Err("KLU requires Sym::YesFull for symmetric matrices");
+        }
+        self.initialized_sym = csc.symmetric;
+        self.initialized_ndim = csc.nrow;
+        self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
+        self.initialized_fingerprint = csc.get_pattern_fingerprint();
 
         // parameters
-        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        self.params = if let Some(p) = params { p } else { LinSolParams::new() };
 
         // input parameters
-        let ordering = klu_ordering(par.ordering);
-        let scaling = klu_scaling(par.scaling);
-
-        // requests
-        let compute_cond = if par.compute_condition_numbers { 1 } else { 0 };
+        let ordering = klu_ordering(self.params.ordering);
+        let scaling = klu_scaling(self.params.scaling);
 
         // matrix config
         let ndim = to_i32(csc.nrow);
 
-        // call initialize just once
-        if !self.initialized {
-            self.stopwatch.reset();
-            unsafe {
-                let status = complex_solver_klu_initialize(
-                    self.solver,
-                    ordering,
-                    scaling,
-                    ndim,
-                    csc.col_pointers.as_ptr(),
-                    csc.row_indices.as_ptr(),
-                );
-                if status != SUCCESSFUL_EXIT {
-                    return Err(handle_klu_error_code(status));
-                }
+        // call initialize
+        self.stopwatch.reset();
+        unsafe {
+            let status = complex_solver_klu_initialize(
+                self.solver,
+                ordering,
+                scaling,
+                ndim,
+                csc.col_pointers.as_ptr(),
+                csc.row_indices.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_klu_error_code(status));
             }
-            self.time_initialize_ns = self.stopwatch.stop();
-            self.initialized = true;
         }
+        self.time_initialize_ns = self.stopwatch.stop();
+
+        // done
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric factorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one given to `analyze`
+    fn factorize_numeric(&mut self, mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        // check
+        if !self.initialized {
+            return Err("the function analyze must be called before factorize_numeric");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        self.check_pattern(csc)?;
+
+        // requests
+        let compute_cond = if self.params.compute_condition_numbers { 1 } else { 0 };
 
         // call factorize
         self.stopwatch.reset();
@@ -233,6 +370,48 @@ impl ComplexLinSolTrait for ComplexSolverKLU {
         Ok(())
     }
 
+    /// Performs the numeric refactorization reusing the pivot sequence (via `klu_refactor`)
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one previously factorized
+    ///
+    /// **Warning:** No pivoting is performed; thus, the factorization may be inaccurate if the
+    /// values have changed significantly. In this case, call `factorize_numeric` instead.
+    fn refactorize(&mut self, mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        // check
+        if !self.factorized {
+            return Err("the function factorize must be called before refactorize");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        self.check_pattern(csc)?;
+
+        // requests
+        let compute_cond = if self.params.compute_condition_numbers { 1 } else { 0 };
+
+        // call refactorize
+        self.stopwatch.reset();
+        unsafe {
+            let status = complex_solver_klu_refactorize(
+                self.solver,
+                &mut self.cond_estimate,
+                compute_cond,
+                csc.col_pointers.as_ptr(),
+                csc.row_indices.as_ptr(),
+                csc.values.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_klu_error_code(status));
+            }
+        }
+        self.time_refactorize_ns = self.stopwatch.stop();
+        Ok(())
+    }
+
     /// Computes the solution of the linear system
     ///
     /// Solves the linear system:
@@ -260,49 +439,67 @@ impl ComplexLinSolTrait for ComplexSolverKLU {
         rhs: &ComplexVector,
         _verbose: bool,
     ) -> Result<(), StrError> {
-        // check
-        if !self.factorized {
-            return Err("the function factorize must be called before solve");
-        }
-
-        // access CSC matrix
-        // (possibly already converted from COO, because factorize was (should have been) called)
-        let csc = mat.get_csc()?;
-
-        // check already factorized data
-        let (nrow, ncol, nnz, sym) = csc.get_info();
-        if sym != self.initialized_sym {
-            return Err("solve must use the same matrix (symmetric differs)");
-        }
-        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
-            return Err("solve must use the same matrix (ndim differs)");
-        }
-        if nnz != self.initialized_nnz {
-            return Err("solve must use the same matrix (nnz differs)");
-        }
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), 1, false, false)
+    }
 
-        // check vectors
-        if x.dim() != self.initialized_ndim {
-            return Err("the dimension of the vector of unknown values x is incorrect");
-        }
-        if rhs.dim() != self.initialized_ndim {
-            return Err("the dimension of the right-hand side vector is incorrect");
-        }
+    /// Computes the solution of the transposed (or conjugate-transposed) linear system
+    ///
+    /// Solves `Aᵀ · x = rhs` or `Aᴴ · x = rhs` using the same factorization of A.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (not transposed); it must be square and, if symmetric, [Sym::YesFull].
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `conjugate` -- solves with `Aᴴ` instead of `Aᵀ`
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_transpose(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        conjugate: bool,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), 1, true, conjugate)
+    }
 
-        // call KLU solve
-        let ndim = to_i32(self.initialized_ndim);
-        complex_vec_copy(x, rhs).unwrap();
-        self.stopwatch.reset();
-        unsafe {
-            let status = complex_solver_klu_solve(self.solver, ndim, x.as_mut_data().as_mut_ptr());
-            if status != SUCCESSFUL_EXIT {
-                return Err(handle_klu_error_code(status));
-            }
+    /// Computes the solution of the linear system with multiple right-hand sides (reusing the factorization)
+    ///
+    /// All right-hand sides are handled by a single call to KLU.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the (m,k) matrix of unknown values; each column corresponds to a column of `rhs`
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesFull].
+    /// * `rhs` -- the (m,k) matrix of right-hand sides
+    /// * `transpose` -- solves `Aᵀ · X = RHS` instead
+    /// * `conjugate` -- (if `transpose`) solves `Aᴴ · X = RHS` instead
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_multiple(
+        &mut self,
+        x: &mut ComplexMatrix,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexMatrix,
+        transpose: bool,
+        conjugate: bool,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        let (m, k) = rhs.dims();
+        if x.dims() != (m, k) {
+            return Err("the dimensions of the matrix of unknown values X are incorrect");
         }
-        self.time_solve_ns = self.stopwatch.stop();
-
-        // done
-        Ok(())
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), k, transpose, conjugate)
     }
 
     /// Updates the stats structure (should be called after solve)
@@ -326,6 +523,7 @@ impl ComplexLinSolTrait for ComplexSolverKLU {
         };
         stats.time_nanoseconds.initialize = self.time_initialize_ns;
         stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.refactorize = self.time_refactorize_ns;
         stats.time_nanoseconds.solve = self.time_solve_ns;
     }
 
@@ -343,6 +541,11 @@ impl ComplexLinSolTrait for ComplexSolverKLU {
     fn get_ns_solve(&self) -> u128 {
         self.time_solve_ns
     }
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        self.time_refactorize_ns
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -1,5 +1,5 @@
 use super::{handle_mumps_error_code, mumps_ordering, mumps_scaling};
-use super::{ComplexLinSolTrait, ComplexSparseMatrix, LinSolParams, StatsLinSol, Sym};
+use super::{ComplexCscMatrix, ComplexLinSolTrait, ComplexSparseMatrix, LinSolParams, StatsLinSol, Sym};
 use super::{
     MUMPS_ORDERING_AMD, MUMPS_ORDERING_AMF, MUMPS_ORDERING_AUTO, MUMPS_ORDERING_METIS, MUMPS_ORDERING_PORD,
     MUMPS_ORDERING_QAMD, MUMPS_ORDERING_SCOTCH, MUMPS_SCALING_AUTO, MUMPS_SCALING_COLUMN, MUMPS_SCALING_DIAGONAL,
@@ -7,7 +7,7 @@ use super::{
 };
 use crate::constants::*;
 use crate::StrError;
-use russell_lab::{complex_vec_copy, using_intel_mkl, Complex64, ComplexVector, Stopwatch};
+use russell_lab::{using_intel_mkl, Complex64, ComplexMatrix, ComplexVector, Stopwatch};
 
 /// Opaque struct holding a C-pointer to InterfaceComplexMUMPS
 ///
@@ -49,6 +49,7 @@ extern "C" {
     ) -> i32;
     fn complex_solver_mumps_factorize(
         solver: *mut InterfaceComplexMUMPS,
+        values_aij: *const Complex64,
         effective_ordering: *mut i32,
         effective_scaling: *mut i32,
         determinant_coefficient_real: *mut f64,
@@ -60,10 +61,23 @@ extern "C" {
     fn complex_solver_mumps_solve(
         solver: *mut InterfaceComplexMUMPS,
         rhs: *mut Complex64,
+        nrhs: i32,
+        transpose: CcBool,
         error_analysis_array_len_8: *mut f64,
         error_analysis_option: i32,
         verbose: CcBool,
     ) -> i32;
+    fn complex_solver_mumps_solve_sparse_rhs(
+        solver: *mut InterfaceComplexMUMPS,
+        x: *mut Complex64,
+        nrhs: i32,
+        nz_rhs: i32,
+        irhs_ptr: *const i32,
+        irhs_sparse: *const i32,
+        rhs_sparse: *const Complex64,
+        transpose: CcBool,
+        verbose: CcBool,
+    ) -> i32;
 }
 
 /// Wraps the MUMPS solver for (very large) sparse linear systems
@@ -79,6 +93,9 @@ pub struct ComplexSolverMUMPS {
     /// Indicates whether the sparse matrix has been factorized or not
     factorized: bool,
 
+    /// Holds the parameters saved in analyze (or factorize)
+    params: LinSolParams,
+
     /// Holds the symmetric flag saved in initialize
     initialized_sym: Sym,
 
@@ -129,6 +146,9 @@ pub struct ComplexSolverMUMPS {
     /// Time spent on factorize in nanoseconds
     time_factorize_ns: u128,
 
+    /// Time spent on refactorize in nanoseconds
+    time_refactorize_ns: u128,
+
     /// Time spent on solve in nanoseconds
     time_solve_ns: u128,
 
@@ -160,6 +180,7 @@ impl ComplexSolverMUMPS {
                 solver,
                 initialized: false,
                 factorized: false,
+                params: LinSolParams::new(),
                 initialized_sym: Sym::No,
                 initialized_ndim: 0,
                 initialized_nnz: 0,
@@ -174,12 +195,145 @@ impl ComplexSolverMUMPS {
                 stopwatch: Stopwatch::new(),
                 time_initialize_ns: 0,
                 time_factorize_ns: 0,
+                time_refactorize_ns: 0,
                 time_solve_ns: 0,
                 fortran_indices_i: Vec::new(),
                 fortran_indices_j: Vec::new(),
             })
         }
     }
+
+    /// Checks that the matrix has been factorized and is the same one used in factorize
+    fn check_solve(&self, mat: &ComplexSparseMatrix) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+        let coo = mat.get_coo()?;
+        let (nrow, ncol, nnz, sym) = coo.get_info();
+        if sym != self.initialized_sym {
+            return Err("solve must use the same matrix (symmetric differs)");
+        }
+        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
+            return Err("solve must use the same matrix (ndim differs)");
+        }
+        if nnz != self.initialized_nnz {
+            return Err("solve must use the same matrix (nnz differs)");
+        }
+        Ok(())
+    }
+
+    /// Checks the data and solves `A · X = RHS`, `Aᵀ · X = RHS`, or `Aᴴ · X = RHS` (col-major arrays)
+    fn solve_internal(
+        &mut self,
+        x: &mut [Complex64],
+        mat: &ComplexSparseMatrix,
+        rhs: &[Complex64],
+        nrhs: usize,
+        transpose: bool,
+        conjugate: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        // check
+        self.check_solve(mat)?;
+
+        // check vectors
+        if x.len() != self.initialized_ndim * nrhs {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.len() != self.initialized_ndim * nrhs {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+
+        // call MUMPS solve
+        // (Aᴴ·x = rhs is equivalent to Aᵀ·conj(x) = conj(rhs))
+        x.copy_from_slice(rhs);
+        if conjugate {
+            x.iter_mut().for_each(|v| *v = v.conj());
+        }
+        let verb = if verbose { 1 } else { 0 };
+        self.stopwatch.reset();
+        unsafe {
+            let status = complex_solver_mumps_solve(
+                self.solver,
+                x.as_mut_ptr(),
+                to_i32(nrhs),
+                if transpose { 1 } else { 0 },
+                self.error_analysis_array_len_8.as_mut_ptr(),
+                self.error_analysis_option,
+                verb,
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_mumps_error_code(status));
+            }
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+        if conjugate {
+            x.iter_mut().for_each(|v| *v = v.conj());
+        }
+
+        // done
+        Ok(())
+    }
+
+    /// Checks the matrix and performs the numeric factorization (JOB=2) reusing the analysis
+    ///
+    /// Returns the elapsed time in nanoseconds.
+    fn numeric(&mut self, mat: &mut ComplexSparseMatrix) -> Result<u128, StrError> {
+        // get COO matrix
+        let coo = mat.get_coo()?;
+
+        // check
+        if coo.symmetric != self.initialized_sym {
+            return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+        }
+        if coo.nrow != self.initialized_ndim {
+            return Err("subsequent factorizations must use the same matrix (ndim differs)");
+        }
+        if coo.nnz != self.initialized_nnz {
+            return Err("subsequent factorizations must use the same matrix (nnz differs)");
+        }
+        let current = coo.indices_i[..coo.nnz].iter().zip(&coo.indices_j[..coo.nnz]);
+        let saved = self.fortran_indices_i.iter().zip(&self.fortran_indices_j);
+        if current
+            .zip(saved)
+            .any(|((i, j), (fi, fj))| *i + 1 != *fi || *j + 1 != *fj)
+        {
+            return Err("subsequent factorizations must use the same matrix (pattern differs)");
+        }
+
+        // error analysis option
+        self.error_analysis_option = if self.params.compute_condition_numbers {
+            1 // all the statistics (very expensive) (page 40)
+        } else if self.params.compute_error_estimates {
+            2 // main statistics are computed (page 40)
+        } else {
+            0 // nothing
+        };
+
+        // requests
+        let compute_determinant = if self.params.compute_determinant { 1 } else { 0 };
+        let verbose = if self.params.verbose { 1 } else { 0 };
+
+        // call factorize
+        self.stopwatch.reset();
+        unsafe {
+            let status = complex_solver_mumps_factorize(
+                self.solver,
+                coo.values.as_ptr(),
+                &mut self.effective_ordering,
+                &mut self.effective_scaling,
+                &mut self.determinant_coefficient_real,
+                &mut self.determinant_coefficient_imag,
+                &mut self.determinant_exponent,
+                compute_determinant,
+                verbose,
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_mumps_error_code(status));
+            }
+        }
+        Ok(self.stopwatch.stop())
+    }
 }
 
 impl ComplexLinSolTrait for ComplexSolverMUMPS {
@@ -204,127 +358,142 @@ impl ComplexLinSolTrait for ComplexSolverMUMPS {
     /// 4. For symmetric matrices, `MUMPS` requires [Sym::YesLower].
     /// 5. The COO matrix must be one-based.
     fn factorize(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
-        // get COO matrix
-        let coo = mat.get_coo()?;
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if !self.initialized {
+            self.analyze(mat, Some(par))?;
+        }
+        self.params = par;
+        self.factorize_numeric(mat)
+    }
 
+    /// Performs the symbolic analysis (MUMPS JOB=1) only
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (one-base **COO** only, not CSC and not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`) and, if symmetric,
+    ///   the symmetric flag must be [Sym::YesLower]
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The analysis is performed just once; i.e., the solver must be
+    /// "dropped" and a new solver allocated if the structure of the matrix changes.
+    /// The time spent on the analysis is recorded as the initialization time.
+    fn analyze(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
         // check
         if self.initialized {
-            if coo.symmetric != self.initialized_sym {
-                return Err("subsequent factorizations must use the same matrix (symmetric differs)");
-            }
-            if coo.nrow != self.initialized_ndim {
-                return Err("subsequent factorizations must use the same matrix (ndim differs)");
-            }
-            if coo.nnz != self.initialized_nnz {
-                return Err("subsequent factorizations must use the same matrix (nnz differs)");
-            }
-        } else {
-            if coo.nrow != coo.ncol {
-                return Err("the COO matrix must be square");
-            }
-            if coo.nnz < 1 {
-                return Err("the COO matrix must have at least one non-zero value");
-            }
-            if coo.symmetric == Sym::YesFull || coo.symmetric == Sym::YesUpper {
-                return Err("MUMPS requires Sym::YesLower for symmetric matrices");
-            }
-            self.initialized_sym = coo.symmetric;
-            self.initialized_ndim = coo.nrow;
-            self.initialized_nnz = coo.nnz;
-            self.fortran_indices_i = vec![0; coo.nnz];
-            self.fortran_indices_j = vec![0; coo.nnz];
-            for k in 0..coo.nnz {
-                self.fortran_indices_i[k] = coo.indices_i[k] + 1;
-                self.fortran_indices_j[k] = coo.indices_j[k] + 1;
-            }
+            return Err("the analysis has been performed already");
         }
 
-        // configuration parameters
-        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        // get COO matrix
+        let coo = mat.get_coo()?;
+        if coo.nrow != coo.ncol {
+            return Err("the COO matrix must be square");
+        }
+        if coo.nnz < 1 {
+            return Err("the COO matrix must have at least one non-zero value");
+        }
+        if coo.symmetric == Sym::YesFull || coo.symmetric == Sym::YesUpper {
+            return Err("MUMPS requires Sym::YesLower for symmetric matrices");
+        }
+        self.initialized_sym = coo.symmetric;
+        self.initialized_ndim = coo.nrow;
+        self.initialized_nnz = coo.nnz;
+        self.fortran_indices_i = vec![0; coo.nnz];
+        self.fortran_indices_j = vec![0; coo.nnz];
+        for k in 0..coo.nnz {
+            self.fortran_indices_i[k] = coo.indices_i[k] + 1;
+            self.fortran_indices_j[k] = coo.indices_j[k] + 1;
+        }
 
-        // error analysis option
-        self.error_analysis_option = if par.compute_condition_numbers {
-            1 // all the statistics (very expensive) (page 40)
-        } else if par.compute_error_estimates {
-            2 // main statistics are computed (page 40)
-        } else {
-            0 // nothing
-        };
+        // parameters
+        self.params = if let Some(p) = params { p } else { LinSolParams::new() };
 
         // input parameters
-        let ordering = mumps_ordering(par.ordering);
-        let scaling = mumps_scaling(par.scaling);
-        let pct_inc_workspace = to_i32(par.mumps_pct_inc_workspace);
-        let max_work_memory = to_i32(par.mumps_max_work_memory);
-        self.effective_num_threads =
-            if using_intel_mkl() || par.mumps_num_threads != 0 || par.mumps_override_prevent_nt_issue_with_openblas {
-                to_i32(par.mumps_num_threads)
-            } else {
-                1 // avoid bug with OpenBLAS
-            };
+        let ordering = mumps_ordering(self.params.ordering);
+        let scaling = mumps_scaling(self.params.scaling);
+        let pct_inc_workspace = to_i32(self.params.mumps_pct_inc_workspace);
+        let max_work_memory = to_i32(self.params.mumps_max_work_memory);
+        self.effective_num_threads = if using_intel_mkl()
+            || self.params.mumps_num_threads != 0
+            || self.params.mumps_override_prevent_nt_issue_with_openblas
+        {
+            to_i32(self.params.mumps_num_threads)
+        } else {
+            1 // avoid bug with OpenBLAS
+        };
 
         // requests
-        let compute_determinant = if par.compute_determinant { 1 } else { 0 };
-        let verbose = if par.verbose { 1 } else { 0 };
+        let verbose = if self.params.verbose { 1 } else { 0 };
 
         // matrix config
         let general_symmetric = if coo.symmetric == Sym::YesLower { 1 } else { 0 };
-        let positive_definite = if par.positive_definite { 1 } else { 0 };
+        let positive_definite = if self.params.positive_definite { 1 } else { 0 };
         let ndim = to_i32(coo.nrow);
         let nnz = to_i32(coo.nnz);
 
-        // call initialize just once
-        if !self.initialized {
-            self.stopwatch.reset();
-            unsafe {
-                let status = complex_solver_mumps_initialize(
-                    self.solver,
-                    ordering,
-                    scaling,
-                    pct_inc_workspace,
-                    max_work_memory,
-                    self.effective_num_threads,
-                    verbose,
-                    general_symmetric,
-                    positive_definite,
-                    ndim,
-                    nnz,
-                    self.fortran_indices_i.as_ptr(),
-                    self.fortran_indices_j.as_ptr(),
-                    coo.values.as_ptr(),
-                );
-                if status != SUCCESSFUL_EXIT {
-                    return Err(handle_mumps_error_code(status));
-                }
-            }
-            self.time_initialize_ns = self.stopwatch.stop();
-            self.initialized = true;
-        }
-
-        // call factorize
+        // call initialize (JOB=-1) and analyze (JOB=1)
         self.stopwatch.reset();
         unsafe {
-            let status = complex_solver_mumps_factorize(
+            let status = complex_solver_mumps_initialize(
                 self.solver,
-                &mut self.effective_ordering,
-                &mut self.effective_scaling,
-                &mut self.determinant_coefficient_real,
-                &mut self.determinant_coefficient_imag,
-                &mut self.determinant_exponent,
-                compute_determinant,
+                ordering,
+                scaling,
+                pct_inc_workspace,
+                max_work_memory,
+                self.effective_num_threads,
                 verbose,
+                general_symmetric,
+                positive_definite,
+                ndim,
+                nnz,
+                self.fortran_indices_i.as_ptr(),
+                self.fortran_indices_j.as_ptr(),
+                coo.values.as_ptr(),
             );
             if status != SUCCESSFUL_EXIT {
                 return Err(handle_mumps_error_code(status));
             }
         }
-        self.time_factorize_ns = self.stopwatch.stop();
+        self.time_initialize_ns = self.stopwatch.stop();
 
         // done
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric factorization (MUMPS JOB=2) reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (one-base **COO** only) with the same
+    ///   structure as the one given to `analyze`
+    fn factorize_numeric(&mut self, mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        if !self.initialized {
+            return Err("the function analyze must be called before factorize_numeric");
+        }
+        self.time_factorize_ns = self.numeric(mat)?;
         self.factorized = true;
         Ok(())
     }
 
+    /// Performs the numeric refactorization (MUMPS JOB=2) reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (one-base **COO** only) with the same
+    ///   structure as the one previously factorized
+    ///
+    /// **Note:** MUMPS does not offer a refactorization without pivoting; thus, this function
+    /// performs a new numeric factorization (with pivoting) reusing the analysis.
+    /// The time is recorded as the refactorization time.
+    fn refactorize(&mut self, mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before refactorize");
+        }
+        self.time_refactorize_ns = self.numeric(mat)?;
+        Ok(())
+    }
+
     /// Computes the solution of the linear system
     ///
     /// Solves the linear system:
@@ -352,44 +521,151 @@ impl ComplexLinSolTrait for ComplexSolverMUMPS {
         rhs: &ComplexVector,
         verbose: bool,
     ) -> Result<(), StrError> {
-        // check
-        if !self.factorized {
-            return Err("the function factorize must be called before solve");
-        }
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), 1, false, false, verbose)
+    }
 
-        // access COO matrix
-        let coo = mat.get_coo()?;
+    /// Computes the solution of the transposed (or conjugate-transposed) linear system
+    ///
+    /// Solves `Aᵀ · x = rhs` or `Aᴴ · x = rhs` using the same factorization of A.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (not transposed); it must be square and, if symmetric, [Sym::YesLower].
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `conjugate` -- solves with `Aᴴ` instead of `Aᵀ`
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_transpose(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        conjugate: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), 1, true, conjugate, verbose)
+    }
 
-        // check already factorized data
-        let (nrow, ncol, nnz, sym) = coo.get_info();
-        if sym != self.initialized_sym {
-            return Err("solve must use the same matrix (symmetric differs)");
-        }
-        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
-            return Err("solve must use the same matrix (ndim differs)");
-        }
-        if nnz != self.initialized_nnz {
-            return Err("solve must use the same matrix (nnz differs)");
+    /// Computes the solution of the linear system with multiple right-hand sides (reusing the factorization)
+    ///
+    /// All right-hand sides are handled by a single call to MUMPS.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the (m,k) matrix of unknown values; each column corresponds to a column of `rhs`
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesLower].
+    /// * `rhs` -- the (m,k) matrix of right-hand sides
+    /// * `transpose` -- solves `Aᵀ · X = RHS` instead
+    /// * `conjugate` -- (if `transpose`) solves `Aᴴ · X = RHS` instead
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    ///
+    /// **Note:** The error analysis (if requested) is not performed with more than one right-hand side.
+    fn solve_multiple(
+        &mut self,
+        x: &mut ComplexMatrix,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexMatrix,
+        transpose: bool,
+        conjugate: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        let (m, k) = rhs.dims();
+        if x.dims() != (m, k) {
+            return Err("the dimensions of the matrix of unknown values X are incorrect");
         }
+        self.solve_internal(
+            x.as_mut_data(),
+            mat,
+            rhs.as_data(),
+            k,
+            transpose,
+            transpose && conjugate,
+            verbose,
+        )
+    }
 
-        // check vectors
-        if x.dim() != self.initialized_ndim {
-            return Err("the dimension of the vector of unknown values x is incorrect");
+    /// Computes the solution of the linear system with a sparse matrix of right-hand sides
+    ///
+    /// The sparse right-hand sides are given directly to MUMPS (ICNTL(20) = 1), which exploits
+    /// their sparsity; thus, the dense matrix of right-hand sides is not allocated.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the (m,k) (dense) matrix of unknown values
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesLower].
+    /// * `rhs` -- the (m,k) sparse matrix of right-hand sides (with [Sym::No])
+    /// * `transpose` -- solves `Aᵀ · X = RHS` instead
+    /// * `conjugate` -- (if `transpose`) solves `Aᴴ · X = RHS` instead
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    ///
+    /// **Note:** The error analysis (if requested) is not performed with sparse right-hand sides.
+    fn solve_sparse_rhs(
+        &mut self,
+        x: &mut ComplexMatrix,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexCscMatrix,
+        transpose: bool,
+        conjugate: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        // check
+        if rhs.symmetric != Sym::No {
+            return Err("the sparse matrix of right-hand sides must have Sym::No");
         }
-        if rhs.dim() != self.initialized_ndim {
+        self.check_solve(mat)?;
+        let (m, k) = (rhs.nrow, rhs.ncol);
+        if m != self.initialized_ndim {
             return Err("the dimension of the right-hand side vector is incorrect");
         }
+        if x.dims() != (m, k) {
+            return Err("the dimensions of the matrix of unknown values X are incorrect");
+        }
+
+        // the (one-based) arrays of the right-hand sides
+        let nz_rhs = rhs.col_pointers[k] as usize;
+        if nz_rhs == 0 {
+            x.fill(Complex64::new(0.0, 0.0));
+            return Ok(());
+        }
+        let irhs_ptr: Vec<i32> = rhs.col_pointers.iter().map(|p| *p + 1).collect();
+        let irhs_sparse: Vec<i32> = rhs.row_indices[..nz_rhs].iter().map(|i| *i + 1).collect();
 
         // call MUMPS solve
-        complex_vec_copy(x, rhs).unwrap();
+        // (Aᴴ·x = rhs is equivalent to Aᵀ·conj(x) = conj(rhs))
+        let conjugate = transpose && conjugate;
+        let conj_values: Vec<Complex64> = if conjugate {
+            rhs.values[..nz_rhs].iter().map(|v| v.conj()).collect()
+        } else {
+            Vec::new()
+        };
+        let values = if conjugate { &conj_values } else { &rhs.values };
         let verb = if verbose { 1 } else { 0 };
         self.stopwatch.reset();
         unsafe {
-            let status = complex_solver_mumps_solve(
+            let status = complex_solver_mumps_solve_sparse_rhs(
                 self.solver,
                 x.as_mut_data().as_mut_ptr(),
-                self.error_analysis_array_len_8.as_mut_ptr(),
-                self.error_analysis_option,
+                to_i32(k),
+                to_i32(nz_rhs),
+                irhs_ptr.as_ptr(),
+                irhs_sparse.as_ptr(),
+                values.as_ptr(),
+                if transpose { 1 } else { 0 },
                 verb,
             );
             if status != SUCCESSFUL_EXIT {
@@ -397,18 +673,21 @@ impl ComplexLinSolTrait for ComplexSolverMUMPS {
             }
         }
         self.time_solve_ns = self.stopwatch.stop();
-
-        // done
+        if conjugate {
+            x.as_mut_data().iter_mut().for_each(|v| *v = v.conj());
+        }
         Ok(())
     }
 
     /// Updates the stats structure (should be called after solve)
     fn update_stats(&self, stats: &mut StatsLinSol) {
         stats.main.solver = "MUMPS".to_string();
-        stats.determinant.mantissa_real = self.determinant_coefficient_real;
-        stats.determinant.mantissa_imag = self.determinant_coefficient_imag;
-        stats.determinant.base = 2.0;
-        stats.determinant.exponent = self.determinant_exponent;
+        stats.determinant.set(
+            self.determinant_coefficient_real,
+            self.determinant_coefficient_imag,
+            2.0,
+            self.determinant_exponent,
+        );
         stats.output.effective_ordering = match self.effective_ordering {
             MUMPS_ORDERING_AMD => "Amd".to_string(),
             MUMPS_ORDERING_AMF => "Amf".to_string(),
@@ -441,6 +720,7 @@ impl ComplexLinSolTrait for ComplexSolverMUMPS {
         stats.mumps_stats.condition_number2 = self.error_analysis_array_len_8[7];
         stats.time_nanoseconds.initialize = self.time_initialize_ns;
         stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.refactorize = self.time_refactorize_ns;
         stats.time_nanoseconds.solve = self.time_solve_ns;
     }
 
@@ -458,6 +738,11 @@ impl ComplexLinSolTrait for ComplexSolverMUMPS {
     fn get_ns_solve(&self) -> u128 {
         self.time_solve_ns
     }
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        self.time_refactorize_ns
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -465,8 +750,8 @@ impl ComplexLinSolTrait for ComplexSolverMUMPS {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ComplexCooMatrix, Ordering, Samples, Scaling};
-    use russell_lab::{complex_approx_eq, complex_vec_approx_eq, cpx};
+    use crate::{ComplexCooMatrix, ComplexCscMatrix, Ordering, Samples, Scaling};
+    use russell_lab::{complex_approx_eq, complex_mat_approx_eq, complex_vec_approx_eq, cpx};
     use serial_test::serial;
 
     // IMPORTANT:
@@ -698,4 +983,122 @@ mod tests {
         ];
         complex_vec_approx_eq(&x, x_correct, 1e-10);
     }
+
+    #[test]
+    #[serial]
+    fn solve_sparse_rhs_works() {
+        let mut solver = ComplexSolverMUMPS::new().unwrap();
+        let mut coo = ComplexCooMatrix::new(3, 3, 6, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(2.0, 1.0)).unwrap();
+        coo.put(0, 1, cpx!(1.0, 0.0)).unwrap();
+        coo.put(1, 1, cpx!(3.0, -1.0)).unwrap();
+        coo.put(1, 2, cpx!(0.0, 2.0)).unwrap();
+        coo.put(2, 0, cpx!(-1.0, 1.0)).unwrap();
+        coo.put(2, 2, cpx!(4.0, 0.0)).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        let mut coo_rhs = ComplexCooMatrix::new(3, 3, 3, Sym::No).unwrap();
+        coo_rhs.put(0, 0, cpx!(1.0, 2.0)).unwrap();
+        coo_rhs.put(2, 0, cpx!(-3.0, 1.0)).unwrap();
+        coo_rhs.put(1, 2, cpx!(0.0, 1.0)).unwrap(); // the second column is empty
+        let rhs = ComplexCscMatrix::from_coo(&coo_rhs).unwrap();
+        let mut xx = ComplexMatrix::new(3, 3);
+
+        // errors
+        assert_eq!(
+            solver.solve_sparse_rhs(&mut xx, &mat, &rhs, false, false, false).err(),
+            Some("the function factorize must be called before solve")
+        );
+        solver.factorize(&mut mat, None).unwrap();
+        let (coo_sym, _, _, _) = Samples::complex_symmetric_3x3_lower();
+        let rhs_sym = ComplexCscMatrix::from_coo(&coo_sym).unwrap();
+        assert_eq!(
+            solver
+                .solve_sparse_rhs(&mut xx, &mat, &rhs_sym, false, false, false)
+                .err(),
+            Some("the sparse matrix of right-hand sides must have Sym::No")
+        );
+        let mut wrong = ComplexMatrix::new(3, 2);
+        assert_eq!(
+            solver
+                .solve_sparse_rhs(&mut wrong, &mat, &rhs, false, false, false)
+                .err(),
+            Some("the dimensions of the matrix of unknown values X are incorrect")
+        );
+
+        // A · X = RHS, Aᵀ · X = RHS, and Aᴴ · X = RHS (compared with the dense right-hand sides)
+        let rhs_dense = rhs.as_dense();
+        let mut xx_correct = ComplexMatrix::new(3, 3);
+        for (transpose, conjugate) in [(false, false), (true, false), (true, true)] {
+            solver
+                .solve_multiple(&mut xx_correct, &mat, &rhs_dense, transpose, conjugate, false)
+                .unwrap();
+            solver
+                .solve_sparse_rhs(&mut xx, &mat, &rhs, transpose, conjugate, false)
+                .unwrap();
+            complex_mat_approx_eq(&xx, &xx_correct, 1e-14);
+            for i in 0..3 {
+                complex_approx_eq(xx.get(i, 1), cpx!(0.0, 0.0), 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn analyze_factorize_numeric_and_refactorize_work() {
+        let mut solver = ComplexSolverMUMPS::new().unwrap();
+        let (coo, _, _, _) = Samples::complex_symmetric_3x3_lower();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        let mut x = ComplexVector::new(3);
+        let rhs = ComplexVector::from(&[cpx!(-3.0, 3.0), cpx!(2.0, -2.0), cpx!(9.0, 7.0)]);
+        let x_correct = &[cpx!(1.0, 1.0), cpx!(2.0, -2.0), cpx!(3.0, 3.0)];
+
+        // errors
+        assert_eq!(
+            solver.factorize_numeric(&mut mat).err(),
+            Some("the function analyze must be called before factorize_numeric")
+        );
+        assert_eq!(
+            solver.refactorize(&mut mat).err(),
+            Some("the function factorize must be called before refactorize")
+        );
+
+        // analyze and factorize
+        solver.analyze(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.analyze(&mut mat, None).err(),
+            Some("the analysis has been performed already")
+        );
+        solver.factorize_numeric(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        complex_vec_approx_eq(&x, x_correct, 1e-14);
+
+        // refactorize with other values (same structure)
+        mat.get_coo_mut()
+            .unwrap()
+            .get_values_mut()
+            .iter_mut()
+            .for_each(|v| *v *= 2.0);
+        solver.refactorize(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        let x_half = &[cpx!(0.5, 0.5), cpx!(1.0, -1.0), cpx!(1.5, 1.5)];
+        complex_vec_approx_eq(&x, x_half, 1e-14);
+
+        // the pattern must not change
+        let mut other = mat.get_coo().unwrap().clone();
+        other.indices_i.swap(0, 1);
+        let mut other = ComplexSparseMatrix::from_coo(other);
+        assert_eq!(
+            solver.refactorize(&mut other).err(),
+            Some("subsequent factorizations must use the same matrix (pattern differs)")
+        );
+        assert_eq!(
+            solver.factorize_numeric(&mut other).err(),
+            Some("subsequent factorizations must use the same matrix (pattern differs)")
+        );
+
+        // update stats
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.time_nanoseconds.refactorize, solver.get_ns_refact());
+    }
 }
@@ -0,0 +1,638 @@
+use super::{ComplexCscMatrix, ComplexLinSolTrait, ComplexSparseMatrix, FactorKind, LinSolParams, Ordering};
+use super::{SparseFactor, StatsLinSol, Sym};
+use crate::StrError;
+use russell_lab::{Complex64, ComplexVector, Stopwatch};
+
+/// Implements a native (pure Rust) sparse direct solver for complex linear systems
+///
+/// See [crate::SolverNative] for the details of the algorithms.
+pub struct ComplexSolverNative {
+    /// Holds the symbolic analysis and the factors
+    factor: Option<SparseFactor<Complex64>>,
+
+    /// Indicates whether the sparse matrix has been factorized or not
+    factorized: bool,
+
+    /// Holds the symmetric flag saved in initialize
+    initialized_sym: Sym,
+
+    /// Holds the matrix dimension saved in initialize
+    initialized_ndim: usize,
+
+    /// Holds the number of non-zeros saved in initialize
+    initialized_nnz: usize,
+
+    /// Holds the fingerprint of the sparsity pattern saved in initialize
+    initialized_fingerprint: u64,
+
+    /// Holds the parameters given to analyze (or factorize)
+    params: LinSolParams,
+
+    /// Holds the used ordering (after factorize)
+    effective_ordering: Ordering,
+
+    /// Holds the determinant coefficient (if requested)
+    ///
+    /// det = coefficient * pow(10, exponent)
+    determinant_coefficient: Complex64,
+
+    /// Holds the determinant exponent (if requested)
+    ///
+    /// det = coefficient * pow(10, exponent)
+    determinant_exponent: f64,
+
+    /// Stopwatch to measure computation times
+    stopwatch: Stopwatch,
+
+    /// Time spent on initialize in nanoseconds
+    time_initialize_ns: u128,
+
+    /// Time spent on factorize in nanoseconds
+    time_factorize_ns: u128,
+
+    /// Time spent on solve in nanoseconds
+    time_solve_ns: u128,
+
+    /// Time spent on refactorize in nanoseconds
+    time_refactorize_ns: u128,
+}
+
+impl ComplexSolverNative {
+    /// Allocates a new instance
+    pub fn new() -> Result<Self, StrError> {
+        Ok(ComplexSolverNative {
+            factor: None,
+            factorized: false,
+            initialized_sym: Sym::No,
+            initialized_ndim: 0,
+            initialized_nnz: 0,
+            initialized_fingerprint: 0,
+            params: LinSolParams::new(),
+            effective_ordering: Ordering::No,
+            determinant_coefficient: Complex64::new(0.0, 0.0),
+            determinant_exponent: 0.0,
+            stopwatch: Stopwatch::new(),
+            time_initialize_ns: 0,
+            time_factorize_ns: 0,
+            time_solve_ns: 0,
+            time_refactorize_ns: 0,
+        })
+    }
+
+    /// Checks whether the matrix has the same structure as the one given to analyze (or the first factorize)
+    fn check_pattern(&self, csc: &ComplexCscMatrix) -> Result<(), StrError> {
+        if csc.symmetric != self.initialized_sym {
+            return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+        }
+        if csc.nrow != self.initialized_ndim {
+            return Err("subsequent factorizations must use the same matrix (ndim differs)");
+        }
+        if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
+            return Err("subsequent factorizations must use the same matrix (nnz differs)");
+        }
+        if csc.get_pattern_fingerprint() != self.initialized_fingerprint {
+            return Err("subsequent factorizations must use the same matrix (pattern differs)");
+        }
+        Ok(())
+    }
+
+    /// Returns the number of non-zeros in the factors (after factorize)
+    pub fn get_nnz_factors(&self) -> usize {
+        match &self.factor {
+            Some(f) => f.nnz_factors(),
+            None => 0,
+        }
+    }
+
+    /// Checks the data and solves `A · x = rhs`, `Aᵀ · x = rhs`, or `Aᴴ · x = rhs` using the factors
+    fn solve_internal(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        transpose: bool,
+        conjugate: bool,
+    ) -> Result<(), StrError> {
+        // check
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+
+        // access CSC matrix
+        // (possibly already converted from COO, because factorize was (should have been) called)
+        let csc = mat.get_csc()?;
+
+        // check already factorized data
+        let (nrow, ncol, nnz, sym) = csc.get_info();
+        if sym != self.initialized_sym {
+            return Err("solve must use the same matrix (symmetric differs)");
+        }
+        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
+            return Err("solve must use the same matrix (ndim differs)");
+        }
+        if nnz != self.initialized_nnz {
+            return Err("solve must use the same matrix (nnz differs)");
+        }
+
+        // check vectors
+        if x.dim() != self.initialized_ndim {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.dim() != self.initialized_ndim {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+
+        // solve using the factors
+        self.stopwatch.reset();
+        let factor = self.factor.as_mut().unwrap();
+        if transpose {
+            factor.solve_transpose(x.as_mut_data(), rhs.as_data(), conjugate)?;
+        } else {
+            factor.solve(x.as_mut_data(), rhs.as_data())?;
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+
+        // done
+        Ok(())
+    }
+}
+
+impl ComplexLinSolTrait for ComplexSolverNative {
+    /// Performs the factorization (and analysis/initialization if needed)
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`). Symmetric matrices
+    ///   may have any [Sym] representation.
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// # Notes
+    ///
+    /// 1. The structure of the matrix (nrow, ncol, nnz, sym, and the sparsity pattern) must be
+    ///    exactly the same among multiple calls to `factorize`. The values may differ
+    ///    from call to call, nonetheless.
+    /// 2. The first call to `factorize` will define the structure which must be
+    ///    kept the same for the next calls. The first call also selects the
+    ///    factorization method (Cholesky or LU) and computes the ordering.
+    /// 3. If the structure of the matrix needs to be changed, the solver must
+    ///    be "dropped" and a new solver allocated.
+    /// 4. For complex symmetric matrices, the Cholesky factorization (A = L·Lᵀ, without conjugation)
+    ///    is selected if [LinSolParams::positive_definite] is true. This requires non-zero pivots.
+    fn factorize(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        // parameters
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if par.native_pivot_threshold <= 0.0 || par.native_pivot_threshold > 1.0 {
+            return Err("the native pivot threshold must satisfy 0 < τ ≤ 1");
+        }
+
+        // perform the symbolic analysis just once
+        if self.factor.is_none() {
+            self.analyze(mat, Some(par))?;
+        }
+
+        // numeric factorization
+        self.params = par;
+        self.factorize_numeric(mat)
+    }
+
+    /// Performs the symbolic analysis (ordering and selection of the factorization method) only
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`). Symmetric matrices
+    ///   may have any [Sym] representation.
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The analysis is performed just once; i.e., the solver must be
+    /// "dropped" and a new solver allocated if the structure of the matrix changes.
+    fn analyze(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        // check
+        if self.factor.is_some() {
+            return Err("the analysis has been performed already");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        if csc.nrow != csc.ncol {
+            return Err("the matrix must be square");
+        }
+
+        // parameters
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if par.native_pivot_threshold <= 0.0 || par.native_pivot_threshold > 1.0 {
+            return Err("the native pivot threshold must satisfy 0 < τ ≤ 1");
+        }
+        self.params = par;
+
+        // symbolic analysis
+        self.stopwatch.reset();
+        let kind = if csc.symmetric != Sym::No && par.positive_definite {
+            FactorKind::Cholesky
+        } else {
+            FactorKind::Lu
+        };
+        self.factor = Some(SparseFactor::analyze(csc, kind, par.ordering)?);
+        self.effective_ordering = match par.ordering {
+            Ordering::No | Ordering::Metis => par.ordering,
+            _ => Ordering::Amd,
+        };
+        self.time_initialize_ns = self.stopwatch.stop();
+
+        // done
+        self.initialized_sym = csc.symmetric;
+        self.initialized_ndim = csc.nrow;
+        self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
+        self.initialized_fingerprint = csc.get_pattern_fingerprint();
+        Ok(())
+    }
+
+    /// Performs the numeric factorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one given to `analyze`
+    fn factorize_numeric(&mut self, mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        // check
+        if self.factor.is_none() {
+            return Err("the function analyze must be called before factorize_numeric");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        self.check_pattern(csc)?;
+
+        // numeric factorization
+        self.factorized = false;
+        self.stopwatch.reset();
+        let factor = self.factor.as_mut().unwrap();
+        factor.factorize(csc, self.params.native_pivot_threshold)?;
+        if self.params.compute_determinant {
+            (self.determinant_coefficient, self.determinant_exponent) = factor.determinant();
+        }
+        self.time_factorize_ns = self.stopwatch.stop();
+
+        // done
+        self.factorized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric refactorization reusing the pivot sequence and the patterns of the factors
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one previously factorized
+    ///
+    /// **Warning:** No pivoting is performed; thus, the factorization may be inaccurate if the
+    /// values have changed significantly. In this case, call `factorize_numeric` instead.
+    fn refactorize(&mut self, mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        // check
+        if !self.factorized {
+            return Err("the function factorize must be called before refactorize");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        self.check_pattern(csc)?;
+
+        // numeric refactorization
+        self.factorized = false;
+        self.stopwatch.reset();
+        let factor = self.factor.as_mut().unwrap();
+        factor.refactorize(csc)?;
+        if self.params.compute_determinant {
+            (self.determinant_coefficient, self.determinant_exponent) = factor.determinant();
+        }
+        self.time_refactorize_ns = self.stopwatch.stop();
+
+        // done
+        self.factorized = true;
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square.
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_internal(x, mat, rhs, false, false)
+    }
+
+    /// Computes the solution of the transposed (or conjugate-transposed) linear system
+    ///
+    /// Solves `Aᵀ · x = rhs` or `Aᴴ · x = rhs` using the same factorization of A.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (not transposed); it must be square.
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `conjugate` -- solves with `Aᴴ` instead of `Aᵀ`
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_transpose(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        conjugate: bool,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_internal(x, mat, rhs, true, conjugate)
+    }
+
+    /// Updates the stats structure (should be called after solve)
+    fn update_stats(&self, stats: &mut StatsLinSol) {
+        stats.main.solver = match &self.factor {
+            Some(f) if f.kind() == FactorKind::Cholesky => "Native-Cholesky".to_string(),
+            _ => "Native-LU".to_string(),
+        };
+        stats.determinant.set(
+            self.determinant_coefficient.re,
+            self.determinant_coefficient.im,
+            10.0,
+            self.determinant_exponent,
+        );
+        stats.output.effective_ordering = format!("{:?}", self.effective_ordering);
+        stats.output.effective_scaling = "No".to_string();
+        stats.time_nanoseconds.initialize = self.time_initialize_ns;
+        stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.refactorize = self.time_refactorize_ns;
+        stats.time_nanoseconds.solve = self.time_solve_ns;
+    }
+
+    /// Returns the nanoseconds spent on initialize
+    fn get_ns_init(&self) -> u128 {
+        self.time_initialize_ns
+    }
+
+    /// Returns the nanoseconds spent on factorize
+    fn get_ns_fact(&self) -> u128 {
+        self.time_factorize_ns
+    }
+
+    /// Returns the nanoseconds spent on solve
+    fn get_ns_solve(&self) -> u128 {
+        self.time_solve_ns
+    }
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        self.time_refactorize_ns
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComplexCooMatrix, Samples};
+    use russell_lab::{complex_approx_eq, complex_mat_approx_eq, complex_vec_approx_eq, cpx, ComplexMatrix};
+
+    #[test]
+    fn factorize_handles_errors() {
+        let mut solver = ComplexSolverNative::new().unwrap();
+        assert!(!solver.factorized);
+
+        // COO to CSC errors
+        let coo = ComplexCooMatrix::new(1, 1, 1, Sym::No).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("COO to CSC requires nnz > 0")
+        );
+
+        // check CSC matrix
+        let (coo, _, _, _) = Samples::complex_rectangular_4x3();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("the matrix must be square")
+        );
+
+        // check already factorized data
+        let mut coo = ComplexCooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(1.0, 0.0)).unwrap();
+        coo.put(1, 1, cpx!(2.0, 0.0)).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        // ... factorize once => OK
+        solver.factorize(&mut mat, None).unwrap();
+        // ... change matrix (symmetric)
+        let mut coo = ComplexCooMatrix::new(2, 2, 2, Sym::YesFull).unwrap();
+        coo.put(0, 0, cpx!(1.0, 0.0)).unwrap();
+        coo.put(1, 1, cpx!(2.0, 0.0)).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (symmetric differs)")
+        );
+        // ... change matrix (nnz)
+        let mut coo = ComplexCooMatrix::new(2, 2, 1, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(1.0, 0.0)).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (nnz differs)")
+        );
+    }
+
+    #[test]
+    fn factorize_fails_on_singular_matrix() {
+        let mut solver = ComplexSolverNative::new().unwrap();
+        let mut coo = ComplexCooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(1.0, 0.0)).unwrap();
+        coo.put(1, 1, cpx!(0.0, 0.0)).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None),
+            Err("the matrix is singular (LU factorization failed)")
+        );
+    }
+
+    #[test]
+    fn solve_handles_errors() {
+        let mut coo = ComplexCooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(123.0, 1.0)).unwrap();
+        coo.put(1, 1, cpx!(456.0, 2.0)).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        let mut solver = ComplexSolverNative::new().unwrap();
+        let mut x = ComplexVector::new(2);
+        let rhs = ComplexVector::new(2);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false),
+            Err("the function factorize must be called before solve")
+        );
+        solver.factorize(&mut mat, None).unwrap();
+        let mut x_wrong = ComplexVector::new(1);
+        assert_eq!(
+            solver.solve(&mut x_wrong, &mat, &rhs, false),
+            Err("the dimension of the vector of unknown values x is incorrect")
+        );
+        let rhs_wrong = ComplexVector::new(1);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs_wrong, false),
+            Err("the dimension of the right-hand side vector is incorrect")
+        );
+    }
+
+    #[test]
+    fn solve_works() {
+        let mut solver = ComplexSolverNative::new().unwrap();
+        let (coo, _, _, det_correct) = Samples::complex_symmetric_3x3_full();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        let mut x = ComplexVector::new(3);
+        let rhs = ComplexVector::from(&[cpx!(-3.0, 3.0), cpx!(2.0, -2.0), cpx!(9.0, 7.0)]);
+        let x_correct = &[cpx!(1.0, 1.0), cpx!(2.0, -2.0), cpx!(3.0, 3.0)];
+
+        let mut params = LinSolParams::new();
+        params.compute_determinant = true;
+
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        complex_vec_approx_eq(&x, x_correct, 1e-14);
+        let det = solver.determinant_coefficient * f64::powf(10.0, solver.determinant_exponent);
+        complex_approx_eq(det, det_correct, 1e-13);
+
+        // calling solve again works
+        let mut x_again = ComplexVector::new(3);
+        solver.solve(&mut x_again, &mat, &rhs, false).unwrap();
+        complex_vec_approx_eq(&x_again, x_correct, 1e-14);
+
+        // update stats
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.main.solver, "Native-LU");
+        assert_eq!(stats.output.effective_ordering, "Amd");
+        assert_eq!(stats.determinant.base, 10.0);
+    }
+
+    #[test]
+    fn analyze_factorize_numeric_and_refactorize_work() {
+        let mut solver = ComplexSolverNative::new().unwrap();
+        let (_, csc, _, _) = Samples::complex_symmetric_3x3_full();
+        let mut mat = ComplexSparseMatrix::from_csc(csc);
+        let mut x = ComplexVector::new(3);
+        let rhs = ComplexVector::from(&[cpx!(-3.0, 3.0), cpx!(2.0, -2.0), cpx!(9.0, 7.0)]);
+        solver.analyze(&mut mat, None).unwrap();
+        solver.factorize_numeric(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        complex_vec_approx_eq(&x, &[cpx!(1.0, 1.0), cpx!(2.0, -2.0), cpx!(3.0, 3.0)], 1e-14);
+        mat.get_csc_mut()
+            .unwrap()
+            .get_values_mut()
+            .iter_mut()
+            .for_each(|v| *v *= 2.0);
+        solver.refactorize(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        complex_vec_approx_eq(&x, &[cpx!(0.5, 0.5), cpx!(1.0, -1.0), cpx!(1.5, 1.5)], 1e-14);
+    }
+
+    #[test]
+    fn solve_transpose_and_multiple_works() {
+        let mut coo = ComplexCooMatrix::new(3, 3, 7, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(2.0, 1.0)).unwrap();
+        coo.put(0, 2, cpx!(1.0, -1.0)).unwrap();
+        coo.put(1, 0, cpx!(-1.0, 2.0)).unwrap();
+        coo.put(1, 1, cpx!(3.0, 0.0)).unwrap();
+        coo.put(2, 0, cpx!(0.5, 0.0)).unwrap();
+        coo.put(2, 1, cpx!(0.0, -2.0)).unwrap();
+        coo.put(2, 2, cpx!(4.0, 1.0)).unwrap();
+        let a = coo.as_dense();
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        let mut solver = ComplexSolverNative::new().unwrap();
+        solver.factorize(&mut mat, None).unwrap();
+
+        // rhs = op(A) · x where op(A) = A, Aᵀ, or Aᴴ
+        let xx_correct = ComplexMatrix::from(&[
+            [cpx!(1.0, 1.0), cpx!(0.0, 1.0)],
+            [cpx!(2.0, -1.0), cpx!(1.0, 0.0)],
+            [cpx!(0.0, 3.0), cpx!(-2.0, 2.0)],
+        ]);
+        let calc_rhs = |transpose: bool, conjugate: bool| {
+            let mut rhs = ComplexMatrix::new(3, 2);
+            for i in 0..3 {
+                for j in 0..3 {
+                    let aij = match (transpose, conjugate) {
+                        (false, _) => a.get(i, j),
+                        (true, false) => a.get(j, i),
+                        (true, true) => a.get(j, i).conj(),
+                    };
+                    for k in 0..2 {
+                        rhs.add(i, k, aij * xx_correct.get(j, k));
+                    }
+                }
+            }
+            rhs
+        };
+
+        // single right-hand side
+        let x_correct = &[xx_correct.get(0, 0), xx_correct.get(1, 0), xx_correct.get(2, 0)];
+        let mut x = ComplexVector::new(3);
+        for conjugate in [false, true] {
+            let rhs_mat = calc_rhs(true, conjugate);
+            let rhs = ComplexVector::from(&[rhs_mat.get(0, 0), rhs_mat.get(1, 0), rhs_mat.get(2, 0)]);
+            solver.solve_transpose(&mut x, &mat, &rhs, conjugate, false).unwrap();
+            complex_vec_approx_eq(&x, x_correct, 1e-14);
+        }
+
+        // multiple right-hand sides
+        let mut xx = ComplexMatrix::new(3, 2);
+        for (transpose, conjugate) in [(false, false), (true, false), (true, true)] {
+            let rhs = calc_rhs(transpose, conjugate);
+            solver
+                .solve_multiple(&mut xx, &mat, &rhs, transpose, conjugate, false)
+                .unwrap();
+            complex_mat_approx_eq(&xx, &xx_correct, 1e-14);
+        }
+    }
+
+    #[test]
+    fn solve_works_lower_and_upper() {
+        let rhs = ComplexVector::from(&[cpx!(-3.0, 3.0), cpx!(2.0, -2.0), cpx!(9.0, 7.0)]);
+        let x_correct = &[cpx!(1.0, 1.0), cpx!(2.0, -2.0), cpx!(3.0, 3.0)];
+        for coo in [
+            Samples::complex_symmetric_3x3_lower().0,
+            Samples::complex_symmetric_3x3_upper().0,
+        ] {
+            let mut solver = ComplexSolverNative::new().unwrap();
+            let mut mat = ComplexSparseMatrix::from_coo(coo);
+            let mut x = ComplexVector::new(3);
+            solver.factorize(&mut mat, None).unwrap();
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            complex_vec_approx_eq(&x, x_correct, 1e-14);
+        }
+    }
+}
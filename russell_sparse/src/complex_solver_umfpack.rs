@@ -1,5 +1,5 @@
 use super::{handle_umfpack_error_code, umfpack_ordering, umfpack_scaling};
-use super::{ComplexLinSolTrait, ComplexSparseMatrix, LinSolParams, StatsLinSol, Sym};
+use super::{ComplexCscMatrix, ComplexLinSolTrait, ComplexSparseMatrix, LinSolParams, StatsLinSol, Sym};
 use super::{
     UMFPACK_ORDERING_AMD, UMFPACK_ORDERING_BEST, UMFPACK_ORDERING_CHOLMOD, UMFPACK_ORDERING_METIS,
     UMFPACK_ORDERING_NONE, UMFPACK_SCALE_MAX, UMFPACK_SCALE_NONE, UMFPACK_SCALE_SUM, UMFPACK_STRATEGY_AUTO,
@@ -64,6 +64,8 @@ extern "C" {
         col_pointers: *const i32,
         row_indices: *const i32,
         values: *const Complex64,
+        transpose: CcBool,
+        conjugate: CcBool,
         verbose: CcBool,
     ) -> i32;
 }
@@ -90,6 +92,12 @@ pub struct ComplexSolverUMFPACK {
     /// Holds the number of non-zeros saved in initialize
     initialized_nnz: usize,
 
+    /// Holds the fingerprint of the sparsity pattern saved in initialize
+    initialized_fingerprint: u64,
+
+    /// Holds the parameters given to analyze (or factorize)
+    params: LinSolParams,
+
     /// Holds the used strategy (after factorize)
     effective_strategy: i32,
 
@@ -128,6 +136,9 @@ pub struct ComplexSolverUMFPACK {
 
     /// Time spent on solve in nanoseconds
     time_solve_ns: u128,
+
+    /// Time spent on refactorize in nanoseconds
+    time_refactorize_ns: u128,
 }
 
 impl Drop for ComplexSolverUMFPACK {
@@ -154,6 +165,8 @@ impl ComplexSolverUMFPACK {
                 initialized_sym: Sym::No,
                 initialized_ndim: 0,
                 initialized_nnz: 0,
+                initialized_fingerprint: 0,
+                params: LinSolParams::new(),
                 effective_strategy: -1,
                 effective_ordering: -1,
                 effective_scaling: -1,
@@ -165,96 +178,40 @@ impl ComplexSolverUMFPACK {
                 time_initialize_ns: 0,
                 time_factorize_ns: 0,
                 time_solve_ns: 0,
+                time_refactorize_ns: 0,
             })
         }
     }
-}
 
-impl ComplexLinSolTrait for ComplexSolverUMFPACK {
-    /// Performs the factorization (and analysis/initialization if needed)
-    ///
-    /// # Input
-    ///
-    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
-    ///   Also, the matrix must be square (`nrow = ncol`) and, if symmetric,
-    ///   the symmetric flag must be [Sym::YesFull]
-    /// * `params` -- configuration parameters; None => use default
-    ///
-    /// # Notes
+    /// Checks whether the matrix has the same structure as the one given to analyze (or the first factorize)
+    fn check_pattern(&self, csc: &ComplexCscMatrix) -> Result<(), StrError> {
+        if csc.symmetric != self.initialized_sym {
+            return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+        }
+        if csc.nrow != self.initialized_ndim {
+            return Err("subsequent factorizations must use the same matrix (ndim differs)");
+        }
+        if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
+            return Err("subsequent factorizations must use the same matrix (nnz differs)");
+        }
+        if csc.get_pattern_fingerprint() != self.initialized_fingerprint {
+            return Err("subsequent factorizations must use the same matrix (pattern differs)");
+        }
+        Ok(())
+    }
+
+    /// Checks the matrix and performs the numeric factorization reusing the symbolic object
     ///
-    /// 1. The structure of the matrix (nrow, ncol, nnz, sym) must be
-    ///    exactly the same among multiple calls to `factorize`. The values may differ
-    ///    from call to call, nonetheless.
-    /// 2. The first call to `factorize` will define the structure which must be
-    ///    kept the same for the next calls.
-    /// 3. If the structure of the matrix needs to be changed, the solver must
-    ///    be "dropped" and a new solver allocated.
-    /// 4. For symmetric matrices, `UMFPACK` requires [Sym::YesFull].
-    fn factorize(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+    /// Returns the elapsed time in nanoseconds.
+    fn numeric(&mut self, mat: &mut ComplexSparseMatrix) -> Result<u128, StrError> {
         // get CSC matrix
         // (or convert from COO if CSC is not available and COO is available)
         let csc = mat.get_csc_or_from_coo()?;
-
-        // check
-        if self.initialized {
-            if csc.symmetric != self.initialized_sym {
-                return Err("subsequent factorizations must use the same matrix (symmetric differs)");
-            }
-            if csc.nrow != self.initialized_ndim {
-                return Err("subsequent factorizations must use the same matrix (ndim differs)");
-            }
-            if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
-                return Err("subsequent factorizations must use the same matrix (nnz differs)");
-            }
-        } else {
-            if csc.nrow != csc.ncol {
-                return Err("the matrix must be square");
-            }
-            if csc.symmetric == Sym::YesLower || csc.symmetric == Sym::YesUpper {
-                return Err("UMFPACK requires Sym::YesFull for symmetric matrices");
-            }
-            self.initialized_sym = csc.symmetric;
-            self.initialized_ndim = csc.nrow;
-            self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
-        }
-
-        // parameters
-        let par = if let Some(p) = params { p } else { LinSolParams::new() };
-
-        // input parameters
-        let ordering = umfpack_ordering(par.ordering);
-        let scaling = umfpack_scaling(par.scaling);
+        self.check_pattern(csc)?;
 
         // requests
-        let compute_determinant = if par.compute_determinant { 1 } else { 0 };
-        let verbose = if par.verbose { 1 } else { 0 };
-
-        // matrix config
-        let enforce_unsym = if par.umfpack_enforce_unsymmetric_strategy { 1 } else { 0 };
-        let ndim = to_i32(csc.nrow);
-
-        // call initialize just once
-        if !self.initialized {
-            self.stopwatch.reset();
-            unsafe {
-                let status = complex_solver_umfpack_initialize(
-                    self.solver,
-                    ordering,
-                    scaling,
-                    verbose,
-                    enforce_unsym,
-                    ndim,
-                    csc.col_pointers.as_ptr(),
-                    csc.row_indices.as_ptr(),
-                    csc.values.as_ptr(),
-                );
-                if status != SUCCESSFUL_EXIT {
-                    return Err(handle_umfpack_error_code(status));
-                }
-            }
-            self.time_initialize_ns = self.stopwatch.stop();
-            self.initialized = true;
-        }
+        let compute_determinant = if self.params.compute_determinant { 1 } else { 0 };
+        let verbose = if self.params.verbose { 1 } else { 0 };
 
         // call factorize
         self.stopwatch.reset();
@@ -278,38 +235,17 @@ impl ComplexLinSolTrait for ComplexSolverUMFPACK {
                 return Err(handle_umfpack_error_code(status));
             }
         }
-        self.time_factorize_ns = self.stopwatch.stop();
-
-        // done
-        self.factorized = true;
-        Ok(())
+        Ok(self.stopwatch.stop())
     }
 
-    /// Computes the solution of the linear system
-    ///
-    /// Solves the linear system:
-    ///
-    /// ```text
-    ///   A   · x = rhs
-    /// (m,m)  (m)  (m)
-    /// ```
-    ///
-    /// # Output
-    ///
-    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
-    ///
-    /// # Input
-    ///
-    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesFull].
-    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
-    /// * `verbose` -- shows messages
-    ///
-    /// **Warning:** the matrix must be same one used in `factorize`.
-    fn solve(
+    /// Checks the data and solves `A · x = rhs`, `Aᵀ · x = rhs`, or `Aᴴ · x = rhs`
+    fn solve_internal(
         &mut self,
         x: &mut ComplexVector,
         mat: &ComplexSparseMatrix,
         rhs: &ComplexVector,
+        transpose: bool,
+        conjugate: bool,
         verbose: bool,
     ) -> Result<(), StrError> {
         // check
@@ -352,6 +288,8 @@ impl ComplexLinSolTrait for ComplexSolverUMFPACK {
                 csc.col_pointers.as_ptr(),
                 csc.row_indices.as_ptr(),
                 csc.values.as_ptr(),
+                if transpose { 1 } else { 0 },
+                if conjugate { 1 } else { 0 },
                 verb,
             );
             if status != SUCCESSFUL_EXIT {
@@ -363,6 +301,200 @@ impl ComplexLinSolTrait for ComplexSolverUMFPACK {
         // done
         Ok(())
     }
+}
+
+impl ComplexLinSolTrait for ComplexSolverUMFPACK {
+    /// Performs the factorization (and analysis/initialization if needed)
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`) and, if symmetric,
+    ///   the symmetric flag must be [Sym::YesFull]
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// # Notes
+    ///
+    /// 1. The structure of the matrix (nrow, ncol, nnz, sym, and the sparsity pattern) must be
+    ///    exactly the same among multiple calls to `factorize`. The values may differ
+    ///    from call to call, nonetheless.
+    /// 2. The first call to `factorize` will define the structure which must be
+    ///    kept the same for the next calls.
+    /// 3. If the structure of the matrix needs to be changed, the solver must
+    ///    be "dropped" and a new solver allocated.
+    /// 4. For symmetric matrices, `UMFPACK` requires [Sym::YesFull].
+    fn factorize(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if !self.initialized {
+            self.analyze(mat, Some(par))?;
+        }
+        self.params = par;
+        self.factorize_numeric(mat)
+    }
+
+    /// Performs the symbolic analysis (ordering and strategy selection) only
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`) and, if symmetric,
+    ///   the symmetric flag must be [Sym::YesFull]
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The analysis is performed just once; i.e., the solver must be
+    /// "dropped" and a new solver allocated if the structure of the matrix changes.
+    fn analyze(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        // check
+        if self.initialized {
+            return Err("the analysis has been performed already");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        if csc.nrow != csc.ncol {
+            return Err("the matrix must be square");
+        }
+        if csc.symmetric == Sym::YesLower || csc.symmetric == Sym::YesUpper {
+            return Err("UMFPACK requires Sym::YesFull for symmetric matrices");
+        }
+        self.initialized_sym = csc.symmetric;
+        self.initialized_ndim = csc.nrow;
+        self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
+        self.initialized_fingerprint = csc.get_pattern_fingerprint();
+
+        // parameters
+        self.params = if let Some(p) = params { p } else { LinSolParams::new() };
+
+        // input parameters
+        let ordering = umfpack_ordering(self.params.ordering);
+        let scaling = umfpack_scaling(self.params.scaling);
+
+        // requests
+        let verbose = if self.params.verbose { 1 } else { 0 };
+
+        // matrix config
+        let enforce_unsym = if self.params.umfpack_enforce_unsymmetric_strategy {
+            1
+        } else {
+            0
+        };
+        let ndim = to_i32(csc.nrow);
+
+        // call initialize
+        self.stopwatch.reset();
+        unsafe {
+            let status = complex_solver_umfpack_initialize(
+                self.solver,
+                ordering,
+                scaling,
+                verbose,
+                enforce_unsym,
+                ndim,
+                csc.col_pointers.as_ptr(),
+                csc.row_indices.as_ptr(),
+                csc.values.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_umfpack_error_code(status));
+            }
+        }
+        self.time_initialize_ns = self.stopwatch.stop();
+
+        // done
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric factorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one given to `analyze`
+    fn factorize_numeric(&mut self, mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        if !self.initialized {
+            return Err("the function analyze must be called before factorize_numeric");
+        }
+        self.time_factorize_ns = self.numeric(mat)?;
+        self.factorized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric refactorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one previously factorized
+    ///
+    /// **Note:** UMFPACK does not offer a refactorization without pivoting; thus, this function
+    /// performs a new numeric factorization (with pivoting) reusing the symbolic object,
+    /// which is as fast as UMFPACK allows. The time is recorded as the refactorization time.
+    fn refactorize(&mut self, mat: &mut ComplexSparseMatrix) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before refactorize");
+        }
+        self.time_refactorize_ns = self.numeric(mat)?;
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesFull].
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_internal(x, mat, rhs, false, false, verbose)
+    }
+
+    /// Computes the solution of the transposed (or conjugate-transposed) linear system
+    ///
+    /// Solves `Aᵀ · x = rhs` or `Aᴴ · x = rhs` using the same factorization of A.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (not transposed); it must be square and, if symmetric, [Sym::YesFull].
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `conjugate` -- solves with `Aᴴ` instead of `Aᵀ`
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_transpose(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        conjugate: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_internal(x, mat, rhs, true, conjugate, verbose)
+    }
 
     /// Updates the stats structure (should be called after solve)
     fn update_stats(&self, stats: &mut StatsLinSol) {
@@ -371,10 +503,12 @@ impl ComplexLinSolTrait for ComplexSolverUMFPACK {
         } else {
             "UMFPACK".to_string()
         };
-        stats.determinant.mantissa_real = self.determinant_coefficient_real;
-        stats.determinant.mantissa_imag = self.determinant_coefficient_imag;
-        stats.determinant.base = 10.0;
-        stats.determinant.exponent = self.determinant_exponent;
+        stats.determinant.set(
+            self.determinant_coefficient_real,
+            self.determinant_coefficient_imag,
+            10.0,
+            self.determinant_exponent,
+        );
         stats.output.umfpack_rcond_estimate = self.rcond_estimate;
         stats.output.effective_ordering = match self.effective_ordering {
             UMFPACK_ORDERING_CHOLMOD => "Cholmod".to_string(),
@@ -398,6 +532,7 @@ impl ComplexLinSolTrait for ComplexSolverUMFPACK {
         };
         stats.time_nanoseconds.initialize = self.time_initialize_ns;
         stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.refactorize = self.time_refactorize_ns;
         stats.time_nanoseconds.solve = self.time_solve_ns;
     }
 
@@ -415,6 +550,11 @@ impl ComplexLinSolTrait for ComplexSolverUMFPACK {
     fn get_ns_solve(&self) -> u128 {
         self.time_solve_ns
     }
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        self.time_refactorize_ns
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
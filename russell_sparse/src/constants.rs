@@ -1,3 +1,6 @@
+// (the constants are only used by the interfaces to the C-code)
+#![cfg_attr(not(any(feature = "with_suitesparse", feature = "with_mumps")), allow(dead_code))]
+
 // Make sure that these constants match the c-code constants
 pub(crate) const SUCCESSFUL_EXIT: i32 = 0;
 pub(crate) const ERROR_NULL_POINTER: i32 = 100000;
@@ -2,7 +2,7 @@ use super::Sym;
 use crate::to_i32;
 use crate::StrError;
 use num_traits::{Num, NumCast};
-use russell_lab::{NumMatrix, NumVector};
+use russell_lab::{LinearOperator, NumMatrix, NumVector, Vector};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::ops::{AddAssign, MulAssign};
@@ -558,6 +558,41 @@ where
         Ok(())
     }
 
+    /// Performs the transposed matrix-vector multiplication
+    ///
+    /// ```text
+    ///  v  :=  α ⋅  aᵀ  ⋅  u
+    /// (n)        (n,m)   (m)
+    /// ```
+    ///
+    /// # Input
+    ///
+    /// * `u` -- Vector with dimension equal to the number of rows of the matrix
+    ///
+    /// # Output
+    ///
+    /// * `v` -- Vector with dimension equal to the number of columns of the matrix
+    ///
+    /// **Note:** If the matrix is symmetric, `aᵀ = a` and [NumCooMatrix::mat_vec_mul] is called.
+    pub fn mat_t_vec_mul(&self, v: &mut NumVector<T>, alpha: T, u: &NumVector<T>) -> Result<(), StrError> {
+        if u.dim() != self.nrow {
+            return Err("u vector is incompatible");
+        }
+        if v.dim() != self.ncol {
+            return Err("v vector is incompatible");
+        }
+        if self.symmetric != Sym::No {
+            return self.mat_vec_mul(v, alpha, u);
+        }
+        v.fill(T::zero());
+        for p in 0..self.nnz {
+            let i = self.indices_i[p] as usize;
+            let j = self.indices_j[p] as usize;
+            v[j] += alpha * self.values[p] * u[i];
+        }
+        Ok(())
+    }
+
     /// Assigns this matrix to the values of another matrix (scaled)
     ///
     /// Performs:
@@ -676,6 +711,21 @@ where
     }
 }
 
+impl LinearOperator for NumCooMatrix<f64> {
+    fn dims(&self) -> (usize, usize) {
+        let (nrow, ncol, _, _) = self.get_info();
+        (nrow, ncol)
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.mat_vec_mul(v, 1.0, u)
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.mat_t_vec_mul(v, 1.0, u)
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
@@ -1066,6 +1116,35 @@ mod tests {
         vec_approx_eq(&v, correct_v, 1e-15);
     }
 
+    #[test]
+    fn mat_t_vec_mul_works() {
+        //   5  -2  .  1
+        //  10  -4  .  2
+        //  15  -6  .  3
+        let (coo, _, _, _) = Samples::rectangular_3x4();
+        let u = NumVector::<f64>::new(4);
+        let mut v = NumVector::<f64>::new(4);
+        assert_eq!(
+            coo.mat_t_vec_mul(&mut v, 2.0, &u).err(),
+            Some("u vector is incompatible")
+        );
+        let u = NumVector::<f64>::from(&[1.0, 2.0, 3.0]);
+        let mut v = NumVector::<f64>::new(3);
+        assert_eq!(
+            coo.mat_t_vec_mul(&mut v, 2.0, &u).err(),
+            Some("v vector is incompatible")
+        );
+        let mut v = NumVector::<f64>::new(4);
+        coo.mat_t_vec_mul(&mut v, 2.0, &u).unwrap();
+        vec_approx_eq(&v, &[140.0, -56.0, 0.0, 28.0], 1e-15);
+        // symmetric
+        let (coo, _, _, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let u = NumVector::<f64>::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut v = NumVector::<f64>::new(5);
+        coo.mat_t_vec_mul(&mut v, 2.0, &u).unwrap();
+        vec_approx_eq(&v, &[96.0, 5.0, 84.0, 6.5, 166.0], 1e-15);
+    }
+
     #[test]
     fn mat_vec_mul_complex_works() {
         // 4+4i    .     2+2i
@@ -1,7 +1,7 @@
 use super::{to_i32, NumCooMatrix, NumCsrMatrix, Sym};
 use crate::StrError;
 use num_traits::{Num, NumCast};
-use russell_lab::{NumMatrix, NumVector};
+use russell_lab::{LinearOperator, NumMatrix, NumVector, Vector};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::ops::{AddAssign, MulAssign};
@@ -745,6 +745,44 @@ where
         Ok(())
     }
 
+    /// Performs the transposed matrix-vector multiplication
+    ///
+    /// ```text
+    ///  v  :=  α ⋅  aᵀ  ⋅  u
+    /// (n)        (n,m)   (m)
+    /// ```
+    ///
+    /// # Input
+    ///
+    /// * `u` -- Vector with dimension equal to the number of rows of the matrix
+    ///
+    /// # Output
+    ///
+    /// * `v` -- Vector with dimension equal to the number of columns of the matrix
+    ///
+    /// **Note:** The transpose is not computed; each column of `a` is a row of `aᵀ`.
+    /// If the matrix is symmetric, `aᵀ = a` and [NumCscMatrix::mat_vec_mul] is called.
+    pub fn mat_t_vec_mul(&self, v: &mut NumVector<T>, alpha: T, u: &NumVector<T>) -> Result<(), StrError> {
+        if u.dim() != self.nrow {
+            return Err("u vector is incompatible");
+        }
+        if v.dim() != self.ncol {
+            return Err("v vector is incompatible");
+        }
+        if self.symmetric != Sym::No {
+            return self.mat_vec_mul(v, alpha, u);
+        }
+        for j in 0..self.ncol {
+            let mut sum = T::zero();
+            for p in self.col_pointers[j]..self.col_pointers[j + 1] {
+                let i = self.row_indices[p as usize] as usize;
+                sum += self.values[p as usize] * u[i];
+            }
+            v[j] = alpha * sum;
+        }
+        Ok(())
+    }
+
     /// Returns information about the dimensions and symmetric type
     ///
     /// Returns `(nrow, ncol, nnz, sym)`
@@ -829,6 +867,67 @@ where
         let nnz = self.col_pointers[self.ncol] as usize;
         &mut self.values[..nnz]
     }
+
+    /// Returns a fingerprint (64-bit FNV-1a hash) of the sparsity pattern
+    ///
+    /// The fingerprint depends on the dimensions, the symmetric flag, the column pointers,
+    /// and the row indices, but not on the values. Thus, it can be used to quickly check
+    /// whether the structure of the matrix has changed (e.g., before reusing a symbolic factorization).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut coo = CooMatrix::new(2, 2, 2, Sym::No)?;
+    ///     coo.put(0, 0, 1.0)?;
+    ///     coo.put(1, 1, 2.0)?;
+    ///     let mut csc = CscMatrix::from_coo(&coo)?;
+    ///     let fingerprint = csc.get_pattern_fingerprint();
+    ///     csc.get_values_mut()[0] = 123.0;
+    ///     assert_eq!(csc.get_pattern_fingerprint(), fingerprint);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_pattern_fingerprint(&self) -> u64 {
+        const OFFSET: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+        let sym = match self.symmetric {
+            Sym::No => 0,
+            Sym::YesFull => 1,
+            Sym::YesLower => 2,
+            Sym::YesUpper => 3,
+        };
+        let nnz = self.col_pointers[self.ncol] as usize;
+        let header = [self.nrow as u64, self.ncol as u64, sym];
+        let pattern = self
+            .col_pointers
+            .iter()
+            .chain(&self.row_indices[..nnz])
+            .map(|v| *v as u64);
+        header.into_iter().chain(pattern).fold(OFFSET, |hash, v| {
+            v.to_le_bytes()
+                .iter()
+                .fold(hash, |h, byte| (h ^ (*byte as u64)).wrapping_mul(PRIME))
+        })
+    }
+}
+
+impl LinearOperator for NumCscMatrix<f64> {
+    fn dims(&self) -> (usize, usize) {
+        let (nrow, ncol, _, _) = self.get_info();
+        (nrow, ncol)
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.mat_vec_mul(v, 1.0, u)
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.mat_t_vec_mul(v, 1.0, u)
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -1213,6 +1312,35 @@ mod tests {
         vec_approx_eq(&v, &[-4.0, 8.0, 6.0, -10.0, 2.0], 1e-14);
     }
 
+    #[test]
+    fn mat_t_vec_mul_works() {
+        //   5  -2  .  1
+        //  10  -4  .  2
+        //  15  -6  .  3
+        let (_, csc, _, _) = Samples::rectangular_3x4();
+        let u = Vector::new(4);
+        let mut v = Vector::new(4);
+        assert_eq!(
+            csc.mat_t_vec_mul(&mut v, 2.0, &u).err(),
+            Some("u vector is incompatible")
+        );
+        let u = Vector::from(&[1.0, 2.0, 3.0]);
+        let mut v = Vector::new(3);
+        assert_eq!(
+            csc.mat_t_vec_mul(&mut v, 2.0, &u).err(),
+            Some("v vector is incompatible")
+        );
+        let mut v = Vector::new(4);
+        csc.mat_t_vec_mul(&mut v, 2.0, &u).unwrap();
+        vec_approx_eq(&v, &[140.0, -56.0, 0.0, 28.0], 1e-15);
+        // symmetric
+        let (_, csc, _, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let u = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut v = Vector::new(5);
+        csc.mat_t_vec_mul(&mut v, 2.0, &u).unwrap();
+        vec_approx_eq(&v, &[96.0, 5.0, 84.0, 6.5, 166.0], 1e-15);
+    }
+
     #[test]
     fn mat_vec_mul_complex_works() {
         // 4+4i    .     2+2i
@@ -1268,6 +1396,24 @@ mod tests {
         assert_eq!(csc.get_values(), &[20.0, 10.0]);
     }
 
+    #[test]
+    fn get_pattern_fingerprint_works() {
+        let (_, csc, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let fingerprint = csc.get_pattern_fingerprint();
+        // values do not change the fingerprint
+        let mut other = csc.clone();
+        other.get_values_mut().iter_mut().for_each(|v| *v *= 2.0);
+        assert_eq!(other.get_pattern_fingerprint(), fingerprint);
+        // the row indices do
+        let mut other = csc.clone();
+        other.row_indices[0] = 2;
+        assert_ne!(other.get_pattern_fingerprint(), fingerprint);
+        // the symmetric flag does
+        let mut other = csc.clone();
+        other.symmetric = Sym::YesFull;
+        assert_ne!(other.get_pattern_fingerprint(), fingerprint);
+    }
+
     #[test]
     fn derive_methods_work() {
         let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
@@ -1,7 +1,7 @@
-use super::{to_i32, NumCooMatrix, NumCscMatrix, Sym};
+use super::{to_i32, NumCooMatrix, NumCscMatrix, ParallelMatVec, Sym};
 use crate::StrError;
 use num_traits::{Num, NumCast};
-use russell_lab::{NumMatrix, NumVector};
+use russell_lab::{LinearOperator, NumMatrix, NumVector, Vector};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::ops::{AddAssign, MulAssign};
@@ -719,6 +719,119 @@ where
         Ok(())
     }
 
+    /// Performs the matrix-vector multiplication using multiple threads
+    ///
+    /// ```text
+    ///  v  :=  α ⋅  a   ⋅  u
+    /// (m)        (m,n)   (n)
+    /// ```
+    ///
+    /// # Input
+    ///
+    /// * `u` -- Vector with dimension equal to the number of columns of the matrix
+    /// * `num_threads` -- number of threads; use 0 to select the available parallelism of the machine
+    ///
+    /// # Output
+    ///
+    /// * `v` -- Vector with dimension equal to the number of rows of the matrix
+    ///
+    /// # Notes
+    ///
+    /// 1. The rows are partitioned into contiguous blocks with (approximately) the same number of non-zeros,
+    ///    thus each thread performs a similar amount of work even if the rows have very different lengths
+    /// 2. If the matrix is symmetric and triangular (lower or upper), the full matrix is not assembled.
+    ///    Instead, the transposed pattern of the stored triangle is computed and each thread writes
+    ///    only to its own rows (see [ParallelMatVec])
+    /// 3. The serial version ([NumCsrMatrix::mat_vec_mul]) is called if only one thread is required
+    /// 4. This function spawns the threads (and computes the workspace) on every call; thus, for repeated
+    ///    products with the same matrix structure, use [ParallelMatVec] instead
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_lab::{vec_approx_eq, Vector};
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // ┌          ┐
+    ///     // │  2 -1  . │
+    ///     // │ -1  2 -1 │
+    ///     // │  . -1  2 │
+    ///     // └          ┘
+    ///     let mut coo = CooMatrix::new(3, 3, 7, Sym::No)?;
+    ///     coo.put(0, 0, 2.0)?;
+    ///     coo.put(0, 1, -1.0)?;
+    ///     coo.put(1, 0, -1.0)?;
+    ///     coo.put(1, 1, 2.0)?;
+    ///     coo.put(1, 2, -1.0)?;
+    ///     coo.put(2, 1, -1.0)?;
+    ///     coo.put(2, 2, 2.0)?;
+    ///     let csr = CsrMatrix::from_coo(&coo)?;
+    ///     let u = Vector::from(&[1.0, 2.0, 3.0]);
+    ///     let mut v = Vector::new(3);
+    ///     csr.mat_vec_mul_parallel(&mut v, 1.0, &u, 2)?;
+    ///     vec_approx_eq(&v, &[0.0, 0.0, 4.0], 1e-15);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn mat_vec_mul_parallel(
+        &self,
+        v: &mut NumVector<T>,
+        alpha: T,
+        u: &NumVector<T>,
+        num_threads: usize,
+    ) -> Result<(), StrError>
+    where
+        T: Send + Sync,
+    {
+        if u.dim() != self.ncol {
+            return Err("u vector is incompatible");
+        }
+        if v.dim() != self.nrow {
+            return Err("v vector is incompatible");
+        }
+        ParallelMatVec::new(self, num_threads).mat_vec_mul(v, alpha, self, u)
+    }
+
+    /// Performs the transposed matrix-vector multiplication
+    ///
+    /// ```text
+    ///  v  :=  α ⋅  aᵀ  ⋅  u
+    /// (n)        (n,m)   (m)
+    /// ```
+    ///
+    /// # Input
+    ///
+    /// * `u` -- Vector with dimension equal to the number of rows of the matrix
+    ///
+    /// # Output
+    ///
+    /// * `v` -- Vector with dimension equal to the number of columns of the matrix
+    ///
+    /// **Note:** The transpose is not computed; the values are scattered into `v` instead.
+    /// If the matrix is symmetric, `aᵀ = a` and [NumCsrMatrix::mat_vec_mul] is called.
+    pub fn mat_t_vec_mul(&self, v: &mut NumVector<T>, alpha: T, u: &NumVector<T>) -> Result<(), StrError> {
+        if u.dim() != self.nrow {
+            return Err("u vector is incompatible");
+        }
+        if v.dim() != self.ncol {
+            return Err("v vector is incompatible");
+        }
+        if self.symmetric != Sym::No {
+            return self.mat_vec_mul(v, alpha, u);
+        }
+        v.fill(T::zero());
+        for i in 0..self.nrow {
+            let alpha_ui = alpha * u[i];
+            for p in self.row_pointers[i]..self.row_pointers[i + 1] {
+                let j = self.col_indices[p as usize] as usize;
+                v[j] += self.values[p as usize] * alpha_ui;
+            }
+        }
+        Ok(())
+    }
+
     /// Returns information about the dimensions and symmetric type
     ///
     /// Returns `(nrow, ncol, nnz, sym)`
@@ -805,11 +918,51 @@ where
     }
 }
 
+impl LinearOperator for NumCsrMatrix<f64> {
+    fn dims(&self) -> (usize, usize) {
+        let (nrow, ncol, _, _) = self.get_info();
+        (nrow, ncol)
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.mat_vec_mul(v, 1.0, u)
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.mat_t_vec_mul(v, 1.0, u)
+    }
+}
+
+/// Partitions the rows (or columns) into `n_parts` contiguous blocks with approximately the same number of non-zeros
+///
+/// Returns the `n_parts + 1` boundaries, such that the k-th block spans `bounds[k]..bounds[k+1]`
+pub(crate) fn partition_by_nnz(pointers: &[i32], n_parts: usize) -> Vec<usize> {
+    let n = pointers.len() - 1;
+    let nnz = pointers[n] as usize;
+    let mut bounds = vec![0; n_parts + 1];
+    let mut k = 0;
+    for part in 1..n_parts {
+        // find the boundary whose number of non-zeros is the closest to nnz·part/n_parts
+        let target = nnz * part;
+        while k < n && (pointers[k] as usize) * n_parts < target {
+            k += 1;
+        }
+        if k > bounds[part - 1]
+            && target - (pointers[k - 1] as usize) * n_parts < (pointers[k] as usize) * n_parts - target
+        {
+            k -= 1;
+        }
+        bounds[part] = k;
+    }
+    bounds[n_parts] = n;
+    bounds
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
-    use super::NumCsrMatrix;
+    use super::{partition_by_nnz, NumCsrMatrix};
     use crate::{CooMatrix, Samples, Sym};
     use russell_lab::{
         array_approx_eq, complex_vec_approx_eq, cpx, vec_approx_eq, Complex64, ComplexVector, Matrix, Vector,
@@ -1223,6 +1376,88 @@ mod tests {
         complex_vec_approx_eq(&v, correct, 1e-15);
     }
 
+    #[test]
+    fn mat_vec_mul_parallel_works() {
+        //   5  -2  .  1
+        //  10  -4  .  2
+        //  15  -6  .  3
+        let (_, _, csr, _) = Samples::rectangular_3x4();
+        let u = Vector::new(3);
+        let mut v = Vector::new(csr.nrow);
+        assert_eq!(
+            csr.mat_vec_mul_parallel(&mut v, 2.0, &u, 2).err(),
+            Some("u vector is incompatible")
+        );
+        let u = Vector::from(&[1.0, 3.0, 8.0, 5.0]);
+        let mut v = Vector::new(2);
+        assert_eq!(
+            csr.mat_vec_mul_parallel(&mut v, 2.0, &u, 2).err(),
+            Some("v vector is incompatible")
+        );
+        let mut v = Vector::new(csr.nrow);
+        for nt in [0, 1, 2, 3, 8] {
+            csr.mat_vec_mul_parallel(&mut v, 2.0, &u, nt).unwrap();
+            vec_approx_eq(&v, &[8.0, 16.0, 24.0], 1e-15);
+        }
+        // symmetric (lower) without expanding to full storage
+        let (_, _, csr, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let u = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut v = Vector::new(5);
+        for nt in [2, 3, 5] {
+            csr.mat_vec_mul_parallel(&mut v, 2.0, &u, nt).unwrap();
+            vec_approx_eq(&v, &[96.0, 5.0, 84.0, 6.5, 166.0], 1e-15);
+        }
+        // complex
+        let (_, _, csr, _) = Samples::complex_rectangular_4x3();
+        let u = ComplexVector::from(&[cpx!(1.0, 1.0), cpx!(3.0, 1.0), cpx!(5.0, -1.0)]);
+        let mut v = ComplexVector::new(csr.nrow);
+        csr.mat_vec_mul_parallel(&mut v, cpx!(2.0, 4.0), &u, 3).unwrap();
+        let correct = &[
+            cpx!(-40.0, 80.0),
+            cpx!(-10.0, 110.0),
+            cpx!(-64.0, 112.0),
+            cpx!(-2.0, 6.0),
+        ];
+        complex_vec_approx_eq(&v, correct, 1e-15);
+    }
+
+    #[test]
+    fn mat_t_vec_mul_works() {
+        //   5  -2  .  1
+        //  10  -4  .  2
+        //  15  -6  .  3
+        let (_, _, csr, _) = Samples::rectangular_3x4();
+        let u = Vector::new(4);
+        let mut v = Vector::new(4);
+        assert_eq!(
+            csr.mat_t_vec_mul(&mut v, 2.0, &u).err(),
+            Some("u vector is incompatible")
+        );
+        let u = Vector::from(&[1.0, 2.0, 3.0]);
+        let mut v = Vector::new(3);
+        assert_eq!(
+            csr.mat_t_vec_mul(&mut v, 2.0, &u).err(),
+            Some("v vector is incompatible")
+        );
+        let mut v = Vector::new(4);
+        csr.mat_t_vec_mul(&mut v, 2.0, &u).unwrap();
+        vec_approx_eq(&v, &[140.0, -56.0, 0.0, 28.0], 1e-15);
+        // symmetric
+        let (_, _, csr, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let u = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut v = Vector::new(5);
+        csr.mat_t_vec_mul(&mut v, 2.0, &u).unwrap();
+        vec_approx_eq(&v, &[96.0, 5.0, 84.0, 6.5, 166.0], 1e-15);
+    }
+
+    #[test]
+    fn partition_by_nnz_works() {
+        assert_eq!(partition_by_nnz(&[0, 1, 2, 3, 4], 2), &[0, 2, 4]);
+        assert_eq!(partition_by_nnz(&[0, 6, 7, 8, 9], 2), &[0, 1, 4]);
+        assert_eq!(partition_by_nnz(&[0, 1, 2, 3, 10], 3), &[0, 3, 4, 4]);
+        assert_eq!(partition_by_nnz(&[0, 2, 4, 6], 1), &[0, 3]);
+    }
+
     #[test]
     fn getters_are_correct() {
         let (_, _, csr, _) = Samples::rectangular_1x2(false, false);
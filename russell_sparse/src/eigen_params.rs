@@ -0,0 +1,104 @@
+use super::{EigenMethod, EigenWhich, Genie, LinSolParams, Precond};
+
+/// Defines the configuration parameters for the sparse eigenvalue solver
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EigenParams {
+    /// Defines the method
+    pub method: EigenMethod,
+
+    /// Selects which eigenvalues are computed
+    ///
+    /// **Note:** This option is ignored in the shift-invert mode, where the eigenvalues nearest to σ are computed.
+    pub which: EigenWhich,
+
+    /// Defines the number of Lanczos/Arnoldi vectors (the dimension of the Krylov subspace)
+    ///
+    /// **Note:** Set this value to 0 for an automatic configuration: `min(max(2·nev + 1, 20), n)`.
+    /// Lanczos requires `nev < ncv ≤ n` and Arnoldi requires `nev + 2 ≤ ncv ≤ n`. Not used by LOBPCG.
+    pub ncv: usize,
+
+    /// Defines the relative tolerance on the residual norm of the eigenpairs
+    pub tolerance: f64,
+
+    /// Defines the maximum number of restarts (Lanczos and Arnoldi) or iterations (LOBPCG)
+    pub max_iterations: usize,
+
+    /// Enables the shift-invert mode (Lanczos and Arnoldi only)
+    ///
+    /// In this mode, the operator is `(K - σ·M)⁻¹·M` and the eigenvalues nearest to σ are computed.
+    pub shift_invert: bool,
+
+    /// Defines the shift σ of the shift-invert mode
+    pub sigma: f64,
+
+    /// Defines the linear solver used to factorize `K - σ·M` (shift-invert mode) or M (generalized problems)
+    ///
+    /// **Note:** The storage of the matrices must be compatible with the solver (see [Genie::get_sym]).
+    /// The direct solvers are recommended.
+    pub genie: Genie,
+
+    /// Defines the parameters of the linear solver (and the LOBPCG preconditioner)
+    pub lin_sol_params: LinSolParams,
+
+    /// Defines the preconditioner of the LOBPCG method (computed from K)
+    pub lobpcg_precond: Precond,
+
+    /// Defines the seed of the (deterministic) pseudo-random initial vectors
+    pub seed: u64,
+}
+
+impl EigenParams {
+    /// Allocates a new instance with default values
+    pub fn new() -> Self {
+        EigenParams {
+            method: EigenMethod::Lanczos,
+            which: EigenWhich::LargestMagnitude,
+            ncv: 0,
+            tolerance: 1e-10,
+            max_iterations: 300,
+            shift_invert: false,
+            sigma: 0.0,
+            genie: Genie::Native,
+            lin_sol_params: LinSolParams::new(),
+            lobpcg_precond: Precond::No,
+            seed: 1234,
+        }
+    }
+}
+
+impl Default for EigenParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::EigenParams;
+    use crate::{EigenMethod, EigenWhich, Genie, Precond};
+
+    #[test]
+    fn copy_debug_and_default_work() {
+        let params = EigenParams::new();
+        let copy = params;
+        assert!(!format!("{:?}", params).is_empty());
+        assert_eq!(copy, params);
+        assert_eq!(EigenParams::default(), params);
+    }
+
+    #[test]
+    fn eigen_params_new_works() {
+        let params = EigenParams::new();
+        assert_eq!(params.method, EigenMethod::Lanczos);
+        assert_eq!(params.which, EigenWhich::LargestMagnitude);
+        assert_eq!(params.ncv, 0);
+        assert_eq!(params.tolerance, 1e-10);
+        assert_eq!(params.max_iterations, 300);
+        assert!(!params.shift_invert);
+        assert_eq!(params.sigma, 0.0);
+        assert_eq!(params.genie, Genie::Native);
+        assert_eq!(params.lobpcg_precond, Precond::No);
+    }
+}
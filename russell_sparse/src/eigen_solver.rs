@@ -0,0 +1,1280 @@
+use super::{
+    csr_add, CooMatrix, CscMatrix, CsrMatrix, EigenMethod, EigenParams, EigenWhich, LinSolver, Precond, Preconditioner,
+    SparseMatrix,
+};
+use crate::StrError;
+use russell_lab::{
+    mat_eigen, mat_eigen_sym, mat_mat_mul, mat_t_mat_mul, vec_copy, vec_inner, vec_norm, vec_scale, vec_update, Matrix,
+    Norm, Vector,
+};
+
+/// Defines the relative threshold to detect a breakdown (invariant subspace) or a linearly dependent vector
+const BREAKDOWN_TOL: f64 = 1e-12;
+
+/// Implements solvers for a few eigenvalues of large sparse eigenvalue problems
+///
+/// Solves the standard or the generalized eigenvalue problems:
+///
+/// ```text
+/// K · x = λ · x    or    K · x = λ · M · x
+/// ```
+///
+/// The available methods are (see [EigenParams::method]):
+///
+/// * [EigenMethod::Lanczos] -- thick-restart Lanczos; requires a symmetric K and a symmetric positive-definite M
+/// * [EigenMethod::Arnoldi] -- implicitly restarted Arnoldi with exact shifts; works with general matrices
+/// * [EigenMethod::Lobpcg] -- locally optimal block preconditioned conjugate gradient; requires a symmetric K
+///   and a symmetric positive-definite M
+///
+/// The Lanczos and Arnoldi methods work with the following operators:
+///
+/// ```text
+/// standard mode:      OP = K              or  OP = M⁻¹ · K
+/// shift-invert mode:  OP = (K - σ·I)⁻¹    or  OP = (K - σ·M)⁻¹ · M
+/// ```
+///
+/// where the inverses are computed by a [LinSolver] (see [EigenParams::genie]); the matrix is factorized
+/// once and the factorization is reused in all iterations. In the shift-invert mode, the eigenvalues ν of
+/// OP are related to λ by `λ = σ + 1/ν`; thus, the eigenvalues nearest to σ are found quickly.
+///
+/// The Lanczos method employs the M-inner product for generalized problems; thus, the computed
+/// eigenvectors are M-orthonormal. Otherwise, the eigenvectors have unit Euclidean norm.
+///
+/// The LOBPCG method only needs the products with K and M (and, optionally, a preconditioner;
+/// see [EigenParams::lobpcg_precond]) and computes the smallest or the largest eigenvalues.
+///
+/// # Notes
+///
+/// 1. The symmetry of the matrices is not checked.
+/// 2. The eigenvalues are sorted according to [EigenParams::which] (or by the distance to σ in the shift-invert mode).
+/// 3. The computations are deterministic because the initial vectors are generated from [EigenParams::seed].
+/// 4. An error is returned if the method does not converge within the maximum number of iterations;
+///    nonetheless, the (approximate) results remain available.
+pub struct EigenSolver {
+    /// Holds the parameters
+    params: EigenParams,
+
+    /// Holds the eigenvalues (real part)
+    l_real: Vector,
+
+    /// Holds the eigenvalues (imaginary part)
+    l_imag: Vector,
+
+    /// Holds the eigenvectors (as columns; real part)
+    v_real: Matrix,
+
+    /// Holds the eigenvectors (as columns; imaginary part)
+    v_imag: Matrix,
+
+    /// Holds the relative residual norms of the eigenpairs (estimates for Lanczos and Arnoldi)
+    residuals: Vector,
+
+    /// Indicates that the last solve has converged
+    converged: bool,
+
+    /// Holds the number of restarts (Lanczos and Arnoldi) or iterations (LOBPCG) of the last solve
+    iterations: usize,
+
+    /// Holds the number of applications of the operator OP (Lanczos and Arnoldi) or of K (LOBPCG)
+    op_count: usize,
+
+    /// Holds the state of the pseudo-random number generator
+    seed_state: u64,
+}
+
+/// Holds the operator of the Lanczos and Arnoldi methods
+struct Operator<'a> {
+    /// Holds the K matrix
+    k: &'a CsrMatrix,
+
+    /// Holds the M matrix (generalized problems only)
+    m: Option<&'a CsrMatrix>,
+
+    /// Holds the factorization of K - σ·M (shift-invert mode) or M (generalized problems)
+    factorization: Option<(LinSolver<'static>, SparseMatrix)>,
+
+    /// Indicates the shift-invert mode
+    shift_invert: bool,
+
+    /// Holds the shift σ
+    sigma: f64,
+
+    /// Holds the number of applications of the operator
+    count: usize,
+
+    /// Auxiliary vector
+    work: Vector,
+}
+
+impl<'a> Operator<'a> {
+    /// Allocates a new instance and computes the factorization (if needed)
+    fn new(k: &'a CsrMatrix, m: Option<&'a CsrMatrix>, params: &EigenParams) -> Result<Self, StrError> {
+        let n = k.nrow;
+        let csc = if params.shift_invert {
+            let k_minus_sigma_m = match m {
+                Some(mm) => csr_add(1.0, k, -params.sigma, mm)?,
+                None => {
+                    let mut coo = CooMatrix::new(n, n, n, k.symmetric)?;
+                    for i in 0..n {
+                        coo.put(i, i, 1.0)?;
+                    }
+                    csr_add(1.0, k, -params.sigma, &CsrMatrix::from_coo(&coo)?)?
+                }
+            };
+            Some(CscMatrix::from_csr(&k_minus_sigma_m)?)
+        } else {
+            match m {
+                Some(mm) => Some(CscMatrix::from_csr(mm)?),
+                None => None,
+            }
+        };
+        let factorization = match csc {
+            Some(c) => {
+                let mut mat = SparseMatrix::from_csc(c);
+                let mut solver = LinSolver::new(params.genie)?;
+                solver.actual.factorize(&mut mat, Some(params.lin_sol_params))?;
+                Some((solver, mat))
+            }
+            None => None,
+        };
+        Ok(Operator {
+            k,
+            m,
+            factorization,
+            shift_invert: params.shift_invert,
+            sigma: params.sigma,
+            count: 0,
+            work: Vector::new(n),
+        })
+    }
+
+    /// Computes w := OP · v
+    fn apply(&mut self, w: &mut Vector, v: &Vector) -> Result<(), StrError> {
+        self.count += 1;
+        match &mut self.factorization {
+            Some((solver, mat)) => {
+                if self.shift_invert {
+                    match self.m {
+                        Some(mm) => {
+                            mm.mat_vec_mul(&mut self.work, 1.0, v)?;
+                            solver.actual.solve(w, mat, &self.work, false)
+                        }
+                        None => solver.actual.solve(w, mat, v, false),
+                    }
+                } else {
+                    self.k.mat_vec_mul(&mut self.work, 1.0, v)?;
+                    solver.actual.solve(w, mat, &self.work, false)
+                }
+            }
+            None => self.k.mat_vec_mul(w, 1.0, v),
+        }
+    }
+
+    /// Computes bw := B · w where B = M (generalized problems) or B = I
+    fn apply_b(&self, bw: &mut Vector, w: &Vector) -> Result<(), StrError> {
+        match self.m {
+            Some(mm) => mm.mat_vec_mul(bw, 1.0, w),
+            None => vec_copy(bw, w),
+        }
+    }
+
+    /// Returns the eigenvalue λ (real, imag) corresponding to the eigenvalue θ of OP
+    fn lambda(&self, theta_real: f64, theta_imag: f64) -> (f64, f64) {
+        if self.shift_invert {
+            let den = theta_real * theta_real + theta_imag * theta_imag;
+            (self.sigma + theta_real / den, -theta_imag / den)
+        } else {
+            (theta_real, theta_imag)
+        }
+    }
+}
+
+impl EigenSolver {
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `params` -- configuration parameters; None => use default
+    pub fn new(params: Option<EigenParams>) -> Result<Self, StrError> {
+        let par = params.unwrap_or_default();
+        if par.tolerance <= 0.0 {
+            return Err("the tolerance must be positive");
+        }
+        if par.max_iterations < 1 {
+            return Err("the maximum number of iterations must be ≥ 1");
+        }
+        if par.method == EigenMethod::Lobpcg {
+            if par.shift_invert {
+                return Err("the shift-invert mode is not available with LOBPCG");
+            }
+            if par.which != EigenWhich::LargestReal && par.which != EigenWhich::SmallestReal {
+                return Err("LOBPCG requires which = LargestReal or SmallestReal");
+            }
+        }
+        Ok(EigenSolver {
+            params: par,
+            l_real: Vector::new(0),
+            l_imag: Vector::new(0),
+            v_real: Matrix::new(0, 0),
+            v_imag: Matrix::new(0, 0),
+            residuals: Vector::new(0),
+            converged: false,
+            iterations: 0,
+            op_count: 0,
+            seed_state: 0,
+        })
+    }
+
+    /// Computes a few eigenvalues and eigenvectors
+    ///
+    /// # Input
+    ///
+    /// * `nev` -- the number of eigenvalues; must satisfy `1 ≤ nev < n`
+    /// * `k` -- the (n,n) K matrix
+    /// * `m` -- the (n,n) M matrix for the generalized problem `K · x = λ · M · x`; None => standard problem
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_lab::approx_eq;
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // tridiagonal matrix with 2 on the diagonal and -1 off the diagonal
+    ///     let n = 30;
+    ///     let mut coo = CooMatrix::new(n, n, 3 * n, Sym::No)?;
+    ///     for i in 0..n {
+    ///         coo.put(i, i, 2.0)?;
+    ///         if i > 0 {
+    ///             coo.put(i, i - 1, -1.0)?;
+    ///         }
+    ///         if i < n - 1 {
+    ///             coo.put(i, i + 1, -1.0)?;
+    ///         }
+    ///     }
+    ///     let k = CsrMatrix::from_coo(&coo)?;
+    ///
+    ///     // the two eigenvalues nearest to zero (shift-invert mode)
+    ///     let mut params = EigenParams::new();
+    ///     params.shift_invert = true;
+    ///     params.sigma = 0.0;
+    ///     let mut solver = EigenSolver::new(Some(params))?;
+    ///     solver.solve(2, &k, None)?;
+    ///
+    ///     // check: λⱼ = 2 - 2·cos(j·π/(n+1))
+    ///     let l = solver.get_eigenvalues();
+    ///     let h = std::f64::consts::PI / ((n + 1) as f64);
+    ///     approx_eq(l[0], 2.0 - 2.0 * f64::cos(h), 1e-12);
+    ///     approx_eq(l[1], 2.0 - 2.0 * f64::cos(2.0 * h), 1e-12);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn solve(&mut self, nev: usize, k: &CsrMatrix, m: Option<&CsrMatrix>) -> Result<(), StrError> {
+        // check
+        if k.nrow != k.ncol {
+            return Err("the K matrix must be square");
+        }
+        if let Some(mm) = m {
+            if mm.nrow != k.nrow || mm.ncol != k.ncol {
+                return Err("the M matrix must have the same dimensions as the K matrix");
+            }
+        }
+        let n = k.nrow;
+        if nev < 1 || nev >= n {
+            return Err("nev must satisfy 1 ≤ nev < n");
+        }
+        let ncv = if self.params.ncv == 0 {
+            usize::min(usize::max(2 * nev + 1, 20), n)
+        } else {
+            self.params.ncv
+        };
+        match self.params.method {
+            EigenMethod::Arnoldi => {
+                if ncv < nev + 2 || ncv > n {
+                    return Err("ncv must satisfy nev + 2 ≤ ncv ≤ n");
+                }
+            }
+            EigenMethod::Lanczos => {
+                if ncv <= nev || ncv > n {
+                    return Err("ncv must satisfy nev < ncv ≤ n");
+                }
+            }
+            EigenMethod::Lobpcg => (),
+        }
+
+        // reset the results
+        self.l_real = Vector::new(nev);
+        self.l_imag = Vector::new(nev);
+        self.v_real = Matrix::new(n, nev);
+        self.v_imag = Matrix::new(n, nev);
+        self.residuals = Vector::new(nev);
+        self.converged = false;
+        self.iterations = 0;
+        self.op_count = 0;
+        self.seed_state = if self.params.seed == 0 {
+            0x9E3779B97F4A7C15
+        } else {
+            self.params.seed
+        };
+
+        // run
+        match self.params.method {
+            EigenMethod::Arnoldi => {
+                let mut op = Operator::new(k, m, &self.params)?;
+                let res = self.run_arnoldi(&mut op, nev, ncv);
+                self.op_count = op.count;
+                res?;
+            }
+            EigenMethod::Lanczos => {
+                let mut op = Operator::new(k, m, &self.params)?;
+                let res = self.run_lanczos(&mut op, nev, ncv);
+                self.op_count = op.count;
+                res?;
+            }
+            EigenMethod::Lobpcg => self.run_lobpcg(k, m, nev)?,
+        }
+        if !self.converged {
+            return Err("the eigenvalue solver did not converge (max number of iterations reached)");
+        }
+        Ok(())
+    }
+
+    /// Returns the eigenvalues (real part)
+    pub fn get_eigenvalues(&self) -> &Vector {
+        &self.l_real
+    }
+
+    /// Returns the eigenvalues (imaginary part; zero for the symmetric methods)
+    pub fn get_eigenvalues_imag(&self) -> &Vector {
+        &self.l_imag
+    }
+
+    /// Returns the eigenvectors as the columns of a (n,nev) matrix (real part)
+    pub fn get_eigenvectors(&self) -> &Matrix {
+        &self.v_real
+    }
+
+    /// Returns the eigenvectors as the columns of a (n,nev) matrix (imaginary part; zero for the symmetric methods)
+    pub fn get_eigenvectors_imag(&self) -> &Matrix {
+        &self.v_imag
+    }
+
+    /// Returns the relative residual norms of the eigenpairs
+    ///
+    /// **Note:** Lanczos and Arnoldi compute estimates of the residual norms of the eigenpairs of OP.
+    pub fn get_residuals(&self) -> &Vector {
+        &self.residuals
+    }
+
+    /// Returns whether the last solve has converged or not
+    pub fn get_converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Returns the number of restarts (Lanczos and Arnoldi) or iterations (LOBPCG) of the last solve
+    pub fn get_iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the number of applications of the operator OP (Lanczos and Arnoldi) or of K (LOBPCG)
+    pub fn get_op_count(&self) -> usize {
+        self.op_count
+    }
+
+    /// Runs the thick-restart Lanczos method
+    fn run_lanczos(&mut self, op: &mut Operator, nev: usize, ncv: usize) -> Result<(), StrError> {
+        // allocate the basis V (B-orthonormal) and B·V
+        let n = op.k.nrow;
+        let m = ncv;
+        let use_b = op.m.is_some();
+        let mut v: Vec<Vector> = (0..=m).map(|_| Vector::new(n)).collect();
+        let mut bv: Vec<Vector> = (0..=m).map(|_| Vector::new(if use_b { n } else { 0 })).collect();
+        let mut t = Matrix::new(m, m);
+        let mut h = vec![0.0; m + 1];
+        let mut w = Vector::new(n);
+        let mut theta = Vector::new(m);
+        let mut y = Matrix::new(m, m);
+
+        // initial vector
+        self.new_basis_vector(op, &mut v, if use_b { Some(&mut bv) } else { None }, 0)?;
+
+        // iterations
+        let mut k = 0;
+        for iteration in 0..self.params.max_iterations {
+            self.iterations = iteration + 1;
+
+            // extend the Lanczos factorization from k to m vectors
+            let mut beta = 0.0;
+            for j in k..m {
+                op.apply(&mut w, &v[j])?;
+                vec_copy(&mut v[j + 1], &w)?;
+                h.fill(0.0);
+                beta = orthonormalize(op, &mut v, if use_b { Some(&mut bv) } else { None }, j + 1, &mut h)?;
+                if breakdown(beta, &h[..=j]) {
+                    beta = 0.0;
+                    self.new_basis_vector(op, &mut v, if use_b { Some(&mut bv) } else { None }, j + 1)?;
+                }
+                for (i, hi) in h.iter().enumerate().take(j + 1) {
+                    t.set(i, j, *hi);
+                    t.set(j, i, *hi);
+                }
+            }
+
+            // Ritz values and vectors
+            for i in 0..m {
+                for j in 0..m {
+                    y.set(i, j, t.get(i, j));
+                }
+            }
+            mat_eigen_sym(&mut theta, &mut y, false)?;
+            let zero = Vector::new(m);
+            let order: Vec<usize> = sort_ritz(&theta, &zero, self.params.which, op.shift_invert)
+                .into_iter()
+                .flatten()
+                .collect();
+
+            // check convergence
+            let mut nconv = 0;
+            for (i, &p) in order.iter().enumerate().take(nev) {
+                let scale = f64::max(f64::abs(theta[p]), f64::EPSILON.powf(2.0 / 3.0));
+                self.residuals[i] = f64::abs(beta * y.get(m - 1, p)) / scale;
+                if self.residuals[i] <= self.params.tolerance {
+                    nconv += 1;
+                }
+            }
+            self.converged = nconv == nev;
+
+            // results
+            if self.converged || iteration == self.params.max_iterations - 1 {
+                for (i, &p) in order.iter().enumerate().take(nev) {
+                    let (lr, _) = op.lambda(theta[p], 0.0);
+                    self.l_real[i] = lr;
+                    for r in 0..n {
+                        let sum: f64 = v[..m].iter().enumerate().map(|(l, vl)| vl[r] * y.get(l, p)).sum();
+                        self.v_real.set(r, i, sum);
+                    }
+                }
+                return Ok(());
+            }
+
+            // thick restart: keep the wanted Ritz vectors and the residual vector
+            k = usize::min(nev + usize::min(nconv, (m - nev) / 2), m - 1);
+            let new_v = combine(&v[..m], &y, &order[..k]);
+            let new_bv = if use_b {
+                combine(&bv[..m], &y, &order[..k])
+            } else {
+                Vec::new()
+            };
+            v.swap(k, m);
+            bv.swap(k, m);
+            for (i, vi) in new_v.into_iter().enumerate() {
+                v[i] = vi;
+            }
+            for (i, bvi) in new_bv.into_iter().enumerate() {
+                bv[i] = bvi;
+            }
+            t.fill(0.0);
+            for i in 0..k {
+                t.set(i, i, theta[order[i]]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the implicitly restarted Arnoldi method
+    fn run_arnoldi(&mut self, op: &mut Operator, nev: usize, ncv: usize) -> Result<(), StrError> {
+        // allocate the basis V (orthonormal) and the Hessenberg matrix H
+        let n = op.k.nrow;
+        let m = ncv;
+        let mut v: Vec<Vector> = (0..=m).map(|_| Vector::new(n)).collect();
+        let mut hh = Matrix::new(m + 1, m);
+        let mut h = vec![0.0; m + 1];
+        let mut w = Vector::new(n);
+        let mut theta_real = Vector::new(m);
+        let mut theta_imag = Vector::new(m);
+        let mut y_real = Matrix::new(m, m);
+        let mut y_imag = Matrix::new(m, m);
+        let mut a = Matrix::new(m, m);
+
+        // initial vector
+        self.new_basis_vector(op, &mut v, None, 0)?;
+
+        // iterations
+        let mut k = 0;
+        for iteration in 0..self.params.max_iterations {
+            self.iterations = iteration + 1;
+
+            // extend the Arnoldi factorization from k to m vectors
+            for j in k..m {
+                op.apply(&mut w, &v[j])?;
+                vec_copy(&mut v[j + 1], &w)?;
+                h.fill(0.0);
+                let mut beta = orthonormalize(op, &mut v, None, j + 1, &mut h)?;
+                if breakdown(beta, &h[..=j]) {
+                    beta = 0.0;
+                    self.new_basis_vector(op, &mut v, None, j + 1)?;
+                }
+                for (i, hi) in h.iter().enumerate().take(j + 1) {
+                    hh.set(i, j, *hi);
+                }
+                hh.set(j + 1, j, beta);
+            }
+            let beta_m = hh.get(m, m - 1);
+
+            // Ritz values and vectors
+            for i in 0..m {
+                for j in 0..m {
+                    a.set(i, j, hh.get(i, j));
+                }
+            }
+            mat_eigen(&mut theta_real, &mut theta_imag, &mut y_real, &mut y_imag, &mut a)?;
+            let groups = sort_ritz(&theta_real, &theta_imag, self.params.which, op.shift_invert);
+            let order: Vec<usize> = groups.iter().flatten().copied().collect();
+
+            // check convergence
+            let mut nconv = 0;
+            for (i, &p) in order.iter().enumerate().take(nev) {
+                let scale = f64::max(f64::hypot(theta_real[p], theta_imag[p]), f64::EPSILON.powf(2.0 / 3.0));
+                self.residuals[i] = beta_m * f64::hypot(y_real.get(m - 1, p), y_imag.get(m - 1, p)) / scale;
+                if self.residuals[i] <= self.params.tolerance {
+                    nconv += 1;
+                }
+            }
+            self.converged = nconv == nev;
+
+            // results
+            if self.converged || iteration == self.params.max_iterations - 1 {
+                for (i, &p) in order.iter().enumerate().take(nev) {
+                    let (lr, li) = op.lambda(theta_real[p], theta_imag[p]);
+                    self.l_real[i] = lr;
+                    self.l_imag[i] = li;
+                    for r in 0..n {
+                        let (mut sr, mut si) = (0.0, 0.0);
+                        for (l, vl) in v[..m].iter().enumerate() {
+                            sr += vl[r] * y_real.get(l, p);
+                            si += vl[r] * y_imag.get(l, p);
+                        }
+                        self.v_real.set(r, i, sr);
+                        self.v_imag.set(r, i, si);
+                    }
+                }
+                return Ok(());
+            }
+
+            // select the number of kept vectors without splitting complex conjugate pairs
+            let target = nev + usize::min(nconv, (m - nev) / 2);
+            let mut boundaries = Vec::new();
+            let mut count = 0;
+            for group in &groups {
+                count += group.len();
+                if count >= nev && count < m {
+                    boundaries.push(count);
+                }
+            }
+            k = match boundaries.iter().find(|b| **b >= target) {
+                Some(b) => *b,
+                None => *boundaries.last().unwrap(), // not empty because ncv ≥ nev + 2
+            };
+
+            // apply the unwanted Ritz values as (exact) shifts
+            let mut hm = Matrix::new(m, m);
+            for i in 0..m {
+                for j in 0..m {
+                    hm.set(i, j, hh.get(i, j));
+                }
+            }
+            let mut q = Matrix::identity(m);
+            for p in &order[k..] {
+                if theta_imag[*p] >= 0.0 {
+                    apply_shift(&mut hm, &mut q, theta_real[*p], theta_imag[*p])?;
+                }
+            }
+
+            // restart: V := V·Q (first k columns) and f := V·Q[:,k]·H[k,k-1] + v[m]·β·Q[m-1,k-1]
+            let cols: Vec<usize> = (0..=k).collect();
+            let mut new_v = combine(&v[..m], &q, &cols);
+            vec_scale(&mut new_v[k], hm.get(k, k - 1));
+            vec_update(&mut new_v[k], beta_m * q.get(m - 1, k - 1), &v[m])?;
+            for (i, vi) in new_v.into_iter().enumerate() {
+                v[i] = vi;
+            }
+            hh.fill(0.0);
+            for i in 0..k {
+                for j in 0..k {
+                    hh.set(i, j, hm.get(i, j));
+                }
+            }
+            h.fill(0.0);
+            let mut beta = orthonormalize(op, &mut v, None, k, &mut h)?;
+            if breakdown(beta, &h[..k]) {
+                beta = 0.0;
+                self.new_basis_vector(op, &mut v, None, k)?;
+            } else {
+                for (i, hi) in h.iter().enumerate().take(k) {
+                    hh.set(i, k - 1, hh.get(i, k - 1) + hi);
+                }
+            }
+            hh.set(k, k - 1, beta);
+        }
+        Ok(())
+    }
+
+    /// Runs the LOBPCG method
+    fn run_lobpcg(&mut self, k: &CsrMatrix, m: Option<&CsrMatrix>, nev: usize) -> Result<(), StrError> {
+        // preconditioner
+        let n = k.nrow;
+        let mut precond = if self.params.lobpcg_precond == Precond::No {
+            None
+        } else {
+            let mut p = Preconditioner::new(self.params.lobpcg_precond, Some(self.params.lin_sol_params))?;
+            p.actual.setup(k)?;
+            Some(p)
+        };
+
+        // initial block X
+        let mut x: Vec<Vector> = (0..nev).map(|_| Vector::new(n)).collect();
+        for xi in x.iter_mut() {
+            random_vector(xi, &mut self.seed_state);
+        }
+        let mut w: Vec<Vector> = Vec::new();
+        let mut p: Vec<Vector> = Vec::new();
+        let largest = self.params.which == EigenWhich::LargestReal;
+
+        // iterations
+        let mut kx = Vector::new(n);
+        let mut bx = Vector::new(n);
+        let mut r = Vector::new(n);
+        for iteration in 0..self.params.max_iterations {
+            self.iterations = iteration + 1;
+
+            // B-orthonormal basis S of span{X, W, P}
+            let nx = x.len();
+            let candidates: Vec<Vector> = x.drain(..).chain(w.drain(..)).chain(p.drain(..)).collect();
+            let (s, bs) = b_orthonormal_basis(m, candidates)?;
+            let ns = s.len();
+            if ns < nev {
+                return Err("LOBPCG failed to compute a basis with nev linearly independent vectors");
+            }
+
+            // Rayleigh-Ritz: G = Sᵀ·K·S
+            let mut ks: Vec<Vector> = Vec::with_capacity(ns);
+            for sj in &s {
+                let mut ksj = Vector::new(n);
+                k.mat_vec_mul(&mut ksj, 1.0, sj)?;
+                ks.push(ksj);
+                self.op_count += 1;
+            }
+            let mut g = Matrix::new(ns, ns);
+            for i in 0..ns {
+                for j in i..ns {
+                    let gij = 0.5 * (vec_inner(&s[i], &ks[j]) + vec_inner(&s[j], &ks[i]));
+                    g.set(i, j, gij);
+                    g.set(j, i, gij);
+                }
+            }
+            let mut theta = Vector::new(ns);
+            mat_eigen_sym(&mut theta, &mut g, false)?;
+            let sel: Vec<usize> = if largest {
+                (0..ns).rev().take(nev).collect()
+            } else {
+                (0..nev).collect()
+            };
+
+            // new X and P (the components of the new X outside span{X})
+            let new_x = combine(&s, &g, &sel);
+            let new_kx = combine(&ks, &g, &sel);
+            let new_bx = match m {
+                Some(_) => combine(&bs, &g, &sel),
+                None => Vec::new(),
+            };
+            let mut new_p = Vec::new();
+            if ns > nx {
+                let mut c = Matrix::new(ns, ns);
+                for l in nx..ns {
+                    for j in 0..ns {
+                        c.set(l, j, g.get(l, j));
+                    }
+                }
+                new_p = combine(&s, &c, &sel);
+            }
+
+            // residuals
+            let mut active = Vec::new();
+            let mut nconv = 0;
+            for i in 0..nev {
+                vec_copy(&mut kx, &new_kx[i])?;
+                match m {
+                    Some(_) => vec_copy(&mut bx, &new_bx[i])?,
+                    None => vec_copy(&mut bx, &new_x[i])?,
+                }
+                vec_copy(&mut r, &kx)?;
+                vec_update(&mut r, -theta[sel[i]], &bx)?;
+                let scale = vec_norm(&kx, Norm::Euc) + f64::abs(theta[sel[i]]) * vec_norm(&bx, Norm::Euc);
+                let rnorm = vec_norm(&r, Norm::Euc);
+                self.residuals[i] = if scale > 0.0 { rnorm / scale } else { rnorm };
+                if self.residuals[i] <= self.params.tolerance {
+                    nconv += 1;
+                } else {
+                    active.push(i);
+                    let mut wi = Vector::new(n);
+                    match precond.as_mut() {
+                        Some(pc) => pc.apply(&mut wi, &r)?,
+                        None => vec_copy(&mut wi, &r)?,
+                    }
+                    w.push(wi);
+                }
+                self.l_real[i] = theta[sel[i]];
+                for (row, value) in new_x[i].as_data().iter().enumerate().take(n) {
+                    self.v_real.set(row, i, *value);
+                }
+            }
+            x = new_x;
+            p = active
+                .iter()
+                .filter(|i| **i < new_p.len())
+                .map(|i| new_p[*i].clone())
+                .collect();
+            self.converged = nconv == nev;
+            if self.converged {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates a new (pseudo-random) basis vector v[j] orthonormal to v[0..j]
+    ///
+    /// **Note:** v[j] is set to zero if j ≥ n (i.e., the whole space has been spanned already).
+    fn new_basis_vector(
+        &mut self,
+        op: &Operator,
+        v: &mut [Vector],
+        bv: Option<&mut [Vector]>,
+        j: usize,
+    ) -> Result<(), StrError> {
+        if j >= v[j].dim() {
+            v[j].fill(0.0);
+            if let Some(b) = bv {
+                b[j].fill(0.0);
+            }
+            return Ok(());
+        }
+        let mut h = vec![0.0; j];
+        random_vector(&mut v[j], &mut self.seed_state);
+        orthonormalize(op, v, bv, j, &mut h)?;
+        Ok(())
+    }
+}
+
+/// Orthogonalizes v[j] against v[0..j] and normalizes it
+///
+/// Uses the classical Gram-Schmidt method with reorthogonalization. The B-inner product is
+/// used if `bv` (with the B·v[i] vectors) is given; in this case, bv[j] is also computed.
+/// The projection coefficients are added to `h` and the norm of the orthogonalized v[j]
+/// (before the normalization) is returned.
+fn orthonormalize(
+    op: &Operator,
+    v: &mut [Vector],
+    mut bv: Option<&mut [Vector]>,
+    j: usize,
+    h: &mut [f64],
+) -> Result<f64, StrError> {
+    let (prev, rest) = v.split_at_mut(j);
+    let vj = &mut rest[0];
+    let mut c = vec![0.0; j];
+    for _ in 0..2 {
+        for i in 0..j {
+            c[i] = match bv.as_deref() {
+                Some(b) => vec_inner(&b[i], vj),
+                None => vec_inner(&prev[i], vj),
+            };
+        }
+        for i in 0..j {
+            vec_update(vj, -c[i], &prev[i])?;
+            h[i] += c[i];
+        }
+    }
+    let norm = match bv.as_deref_mut() {
+        Some(b) => {
+            op.apply_b(&mut b[j], vj)?;
+            f64::sqrt(f64::max(vec_inner(vj, &b[j]), 0.0))
+        }
+        None => vec_norm(vj, Norm::Euc),
+    };
+    if norm > 0.0 {
+        vec_scale(vj, 1.0 / norm);
+        if let Some(b) = bv {
+            vec_scale(&mut b[j], 1.0 / norm);
+        }
+    }
+    Ok(norm)
+}
+
+/// Returns true if the norm of the new basis vector is negligible (invariant subspace found)
+fn breakdown(beta: f64, h: &[f64]) -> bool {
+    let hnorm = f64::sqrt(h.iter().map(|x| x * x).sum::<f64>());
+    beta <= BREAKDOWN_TOL * hnorm || beta == 0.0
+}
+
+/// Returns the linear combinations `u[i] = Σₗ v[l]·y[l,cols[i]]`
+fn combine(v: &[Vector], y: &Matrix, cols: &[usize]) -> Vec<Vector> {
+    let n = v[0].dim();
+    cols.iter()
+        .map(|c| {
+            let mut u = Vector::new(n);
+            for (l, vl) in v.iter().enumerate() {
+                let coef = y.get(l, *c);
+                if coef != 0.0 {
+                    for r in 0..n {
+                        u[r] += coef * vl[r];
+                    }
+                }
+            }
+            u
+        })
+        .collect()
+}
+
+/// Sorts the Ritz values such that the wanted ones come first
+///
+/// Returns groups of indices with either a real Ritz value or a complex conjugate pair
+/// (with the positive imaginary part first), as given by the dense eigensolver.
+fn sort_ritz(re: &Vector, im: &Vector, which: EigenWhich, shift_invert: bool) -> Vec<Vec<usize>> {
+    let m = re.dim();
+    let mut groups = Vec::new();
+    let mut i = 0;
+    while i < m {
+        if im[i] > 0.0 && i + 1 < m {
+            groups.push(vec![i, i + 1]);
+            i += 2;
+        } else {
+            groups.push(vec![i]);
+            i += 1;
+        }
+    }
+    let key = |i: usize| {
+        let mag = f64::hypot(re[i], im[i]);
+        if shift_invert {
+            return -mag;
+        }
+        match which {
+            EigenWhich::LargestMagnitude => -mag,
+            EigenWhich::LargestReal => -re[i],
+            EigenWhich::SmallestMagnitude => mag,
+            EigenWhich::SmallestReal => re[i],
+        }
+    };
+    groups.sort_by(|a, b| key(a[0]).partial_cmp(&key(b[0])).unwrap_or(std::cmp::Ordering::Equal));
+    groups
+}
+
+/// Applies a shift μ (or the complex conjugate pair μ, μ̄) to the Hessenberg matrix via one QR step
+///
+/// Computes `H - μ·I = Qᵢ·R` (or `H² - 2·Re(μ)·H + |μ|²·I = Qᵢ·R`) and then `H := Qᵢᵀ·H·Qᵢ` and `Q := Q·Qᵢ`.
+fn apply_shift(hm: &mut Matrix, q: &mut Matrix, mu_real: f64, mu_imag: f64) -> Result<(), StrError> {
+    let m = hm.nrow();
+    let mut a = Matrix::new(m, m);
+    if mu_imag == 0.0 {
+        for i in 0..m {
+            for j in 0..m {
+                a.set(i, j, hm.get(i, j));
+            }
+            a.set(i, i, hm.get(i, i) - mu_real);
+        }
+    } else {
+        mat_mat_mul(&mut a, 1.0, hm, hm, 0.0)?;
+        let s = 2.0 * mu_real;
+        let t = mu_real * mu_real + mu_imag * mu_imag;
+        for i in 0..m {
+            for j in 0..m {
+                a.set(i, j, a.get(i, j) - s * hm.get(i, j));
+            }
+            a.set(i, i, a.get(i, i) + t);
+        }
+    }
+    let qi = qr_orthogonal_factor(&mut a);
+    let mut aux = Matrix::new(m, m);
+    mat_t_mat_mul(&mut aux, 1.0, &qi, hm, 0.0)?;
+    mat_mat_mul(hm, 1.0, &aux, &qi, 0.0)?;
+    for i in 2..m {
+        for j in 0..(i - 1) {
+            hm.set(i, j, 0.0);
+        }
+    }
+    mat_mat_mul(&mut aux, 1.0, q, &qi, 0.0)?;
+    for i in 0..m {
+        for j in 0..m {
+            q.set(i, j, aux.get(i, j));
+        }
+    }
+    Ok(())
+}
+
+/// Computes the orthogonal factor Q of the QR decomposition of a square matrix (Householder reflections)
+///
+/// **Note:** The matrix `a` is overwritten by R.
+fn qr_orthogonal_factor(a: &mut Matrix) -> Matrix {
+    let m = a.nrow();
+    let mut q = Matrix::identity(m);
+    let mut u = vec![0.0; m];
+    for k in 0..(m - 1) {
+        let norm = f64::sqrt((k..m).map(|i| a.get(i, k) * a.get(i, k)).sum::<f64>());
+        if norm == 0.0 {
+            continue;
+        }
+        let alpha = if a.get(k, k) > 0.0 { -norm } else { norm };
+        for (i, ui) in u.iter_mut().enumerate().skip(k) {
+            *ui = a.get(i, k);
+        }
+        u[k] -= alpha;
+        let uu: f64 = (k..m).map(|i| u[i] * u[i]).sum();
+        if uu == 0.0 {
+            continue;
+        }
+        for j in k..m {
+            let f = 2.0 * (k..m).map(|i| u[i] * a.get(i, j)).sum::<f64>() / uu;
+            for (i, ui) in u.iter().enumerate().skip(k) {
+                a.set(i, j, a.get(i, j) - f * ui);
+            }
+        }
+        for i in 0..m {
+            let f = 2.0 * (k..m).map(|l| q.get(i, l) * u[l]).sum::<f64>() / uu;
+            for (l, ul) in u.iter().enumerate().skip(k) {
+                q.set(i, l, q.get(i, l) - f * ul);
+            }
+        }
+    }
+    q
+}
+
+/// Computes a B-orthonormal basis of the span of the given vectors (B = M or B = I)
+///
+/// Uses the modified Gram-Schmidt method with reorthogonalization and drops the (nearly)
+/// linearly dependent vectors. Returns the basis vectors and the B·s vectors.
+fn b_orthonormal_basis(m: Option<&CsrMatrix>, vectors: Vec<Vector>) -> Result<(Vec<Vector>, Vec<Vector>), StrError> {
+    let mut s: Vec<Vector> = Vec::new();
+    let mut bs: Vec<Vector> = Vec::new();
+    for mut u in vectors {
+        let mut bu = Vector::new(u.dim());
+        match m {
+            Some(mm) => mm.mat_vec_mul(&mut bu, 1.0, &u)?,
+            None => vec_copy(&mut bu, &u)?,
+        }
+        let norm0 = f64::sqrt(f64::max(vec_inner(&u, &bu), 0.0));
+        if norm0 == 0.0 {
+            continue;
+        }
+        for _ in 0..2 {
+            for i in 0..s.len() {
+                let c = vec_inner(&bs[i], &u);
+                vec_update(&mut u, -c, &s[i])?;
+                vec_update(&mut bu, -c, &bs[i])?;
+            }
+        }
+        let norm = f64::sqrt(f64::max(vec_inner(&u, &bu), 0.0));
+        if norm <= 1e-10 * norm0 {
+            continue;
+        }
+        vec_scale(&mut u, 1.0 / norm);
+        vec_scale(&mut bu, 1.0 / norm);
+        s.push(u);
+        bs.push(bu);
+    }
+    Ok((s, bs))
+}
+
+/// Fills a vector with pseudo-random values in [-1, 1) using the xorshift64* generator
+fn random_vector(v: &mut Vector, state: &mut u64) {
+    for i in 0..v.dim() {
+        *state ^= *state >> 12;
+        *state ^= *state << 25;
+        *state ^= *state >> 27;
+        let r = state.wrapping_mul(0x2545F4914F6CDD1D) >> 11;
+        v[i] = 2.0 * (r as f64) / ((1u64 << 53) as f64) - 1.0;
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::EigenSolver;
+    use crate::{CooMatrix, CsrMatrix, EigenMethod, EigenParams, EigenWhich, Precond, Sym};
+    use russell_lab::{approx_eq, vec_inner, Vector};
+    use std::f64::consts::PI;
+
+    /// Returns the tridiagonal matrix tridiag(a, d, b) (a below the diagonal; b above)
+    fn tridiagonal(n: usize, a: f64, d: f64, b: f64) -> CsrMatrix {
+        let mut coo = CooMatrix::new(n, n, 3 * n, Sym::No).unwrap();
+        for i in 0..n {
+            coo.put(i, i, d).unwrap();
+            if i > 0 {
+                coo.put(i, i - 1, a).unwrap();
+            }
+            if i < n - 1 {
+                coo.put(i, i + 1, b).unwrap();
+            }
+        }
+        CsrMatrix::from_coo(&coo).unwrap()
+    }
+
+    /// Returns the max norm of K·x - λ·M·x (complex eigenpairs are allowed)
+    fn residual(solver: &EigenSolver, i: usize, k: &CsrMatrix, m: Option<&CsrMatrix>) -> f64 {
+        let n = k.nrow;
+        let (lr, li) = (solver.get_eigenvalues()[i], solver.get_eigenvalues_imag()[i]);
+        let xr = Vector::initialized(n, |r| solver.get_eigenvectors().get(r, i));
+        let xi = Vector::initialized(n, |r| solver.get_eigenvectors_imag().get(r, i));
+        let (mut kxr, mut kxi, mut mxr, mut mxi) = (Vector::new(n), Vector::new(n), Vector::new(n), Vector::new(n));
+        k.mat_vec_mul(&mut kxr, 1.0, &xr).unwrap();
+        k.mat_vec_mul(&mut kxi, 1.0, &xi).unwrap();
+        match m {
+            Some(mm) => {
+                mm.mat_vec_mul(&mut mxr, 1.0, &xr).unwrap();
+                mm.mat_vec_mul(&mut mxi, 1.0, &xi).unwrap();
+            }
+            None => {
+                mxr = xr.clone();
+                mxi = xi.clone();
+            }
+        }
+        let mut res: f64 = 0.0;
+        for r in 0..n {
+            let er = kxr[r] - (lr * mxr[r] - li * mxi[r]);
+            let ei = kxi[r] - (lr * mxi[r] + li * mxr[r]);
+            res = f64::max(res, f64::hypot(er, ei));
+        }
+        res
+    }
+
+    #[test]
+    fn new_captures_errors() {
+        let mut params = EigenParams::new();
+        params.tolerance = 0.0;
+        assert_eq!(
+            EigenSolver::new(Some(params)).err(),
+            Some("the tolerance must be positive")
+        );
+        params.tolerance = 1e-10;
+        params.max_iterations = 0;
+        assert_eq!(
+            EigenSolver::new(Some(params)).err(),
+            Some("the maximum number of iterations must be ≥ 1")
+        );
+        params.max_iterations = 10;
+        params.method = EigenMethod::Lobpcg;
+        params.shift_invert = true;
+        assert_eq!(
+            EigenSolver::new(Some(params)).err(),
+            Some("the shift-invert mode is not available with LOBPCG")
+        );
+        params.shift_invert = false;
+        params.which = EigenWhich::LargestMagnitude;
+        assert_eq!(
+            EigenSolver::new(Some(params)).err(),
+            Some("LOBPCG requires which = LargestReal or SmallestReal")
+        );
+    }
+
+    #[test]
+    fn solve_captures_errors() {
+        let mut solver = EigenSolver::new(None).unwrap();
+        let mut coo = CooMatrix::new(2, 3, 1, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        let rect = CsrMatrix::from_coo(&coo).unwrap();
+        assert_eq!(solver.solve(1, &rect, None).err(), Some("the K matrix must be square"));
+        let k = tridiagonal(4, -1.0, 2.0, -1.0);
+        let m = tridiagonal(3, 0.0, 1.0, 0.0);
+        assert_eq!(
+            solver.solve(1, &k, Some(&m)).err(),
+            Some("the M matrix must have the same dimensions as the K matrix")
+        );
+        assert_eq!(solver.solve(0, &k, None).err(), Some("nev must satisfy 1 ≤ nev < n"));
+        assert_eq!(solver.solve(4, &k, None).err(), Some("nev must satisfy 1 ≤ nev < n"));
+        let mut params = EigenParams::new();
+        params.ncv = 2;
+        let mut solver = EigenSolver::new(Some(params)).unwrap();
+        assert_eq!(solver.solve(2, &k, None).err(), Some("ncv must satisfy nev < ncv ≤ n"));
+        params.method = EigenMethod::Arnoldi;
+        params.ncv = 3;
+        let mut solver = EigenSolver::new(Some(params)).unwrap();
+        assert_eq!(
+            solver.solve(2, &k, None).err(),
+            Some("ncv must satisfy nev + 2 ≤ ncv ≤ n")
+        );
+        params.method = EigenMethod::Lanczos;
+        params.ncv = 0;
+        params.max_iterations = 1;
+        params.tolerance = 1e-15;
+        let k = tridiagonal(100, -1.0, 2.0, -1.0);
+        let mut solver = EigenSolver::new(Some(params)).unwrap();
+        assert_eq!(
+            solver.solve(1, &k, None).err(),
+            Some("the eigenvalue solver did not converge (max number of iterations reached)")
+        );
+        assert!(!solver.get_converged());
+        assert_eq!(solver.get_iterations(), 1);
+    }
+
+    #[test]
+    fn lanczos_works() {
+        // λⱼ = 2 - 2·cos(j·π/(n+1))
+        let n = 60;
+        let k = tridiagonal(n, -1.0, 2.0, -1.0);
+        let h = PI / ((n + 1) as f64);
+        let nev = 4;
+        for which in [EigenWhich::LargestMagnitude, EigenWhich::SmallestReal] {
+            let mut params = EigenParams::new();
+            params.which = which;
+            params.max_iterations = 1000;
+            let mut solver = EigenSolver::new(Some(params)).unwrap();
+            solver.solve(nev, &k, None).unwrap();
+            assert!(solver.get_converged());
+            assert!(solver.get_op_count() > 0);
+            for i in 0..nev {
+                let j = if which == EigenWhich::SmallestReal {
+                    i + 1
+                } else {
+                    n - i
+                };
+                approx_eq(solver.get_eigenvalues()[i], 2.0 - 2.0 * f64::cos((j as f64) * h), 1e-9);
+                assert_eq!(solver.get_eigenvalues_imag()[i], 0.0);
+                assert!(residual(&solver, i, &k, None) < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn lanczos_shift_invert_generalized_works() {
+        // K = tridiag(-1, 2, -1) and M = tridiag(1, 4, 1)/6 (linear finite elements)
+        // λⱼ = 6·(1 - cⱼ)/(2 + cⱼ) with cⱼ = cos(j·π/(n+1))
+        let n = 50;
+        let k = tridiagonal(n, -1.0, 2.0, -1.0);
+        let m = tridiagonal(n, 1.0 / 6.0, 4.0 / 6.0, 1.0 / 6.0);
+        let lambda = |j: usize| {
+            let c = f64::cos((j as f64) * PI / ((n + 1) as f64));
+            6.0 * (1.0 - c) / (2.0 + c)
+        };
+        let nev = 3;
+        for shift_invert in [false, true] {
+            let mut params = EigenParams::new();
+            params.which = EigenWhich::SmallestReal;
+            params.shift_invert = shift_invert;
+            params.sigma = 0.0;
+            params.max_iterations = 1000;
+            let mut solver = EigenSolver::new(Some(params)).unwrap();
+            solver.solve(nev, &k, Some(&m)).unwrap();
+            for i in 0..nev {
+                approx_eq(solver.get_eigenvalues()[i], lambda(i + 1), 1e-9);
+                assert!(residual(&solver, i, &k, Some(&m)) < 1e-8);
+            }
+            // M-orthonormal eigenvectors
+            let x0 = Vector::initialized(n, |r| solver.get_eigenvectors().get(r, 0));
+            let x1 = Vector::initialized(n, |r| solver.get_eigenvectors().get(r, 1));
+            let mut mx = Vector::new(n);
+            m.mat_vec_mul(&mut mx, 1.0, &x0).unwrap();
+            approx_eq(vec_inner(&x0, &mx), 1.0, 1e-10);
+            approx_eq(vec_inner(&x1, &mx), 0.0, 1e-10);
+        }
+    }
+
+    #[test]
+    fn arnoldi_works() {
+        // unsymmetric tridiagonal matrix: λⱼ = d + 2·√(a·b)·cos(j·π/(n+1))
+        let n = 40;
+        let k = tridiagonal(n, 0.9, 2.0, 1.0 / 0.9); // a·b = 1
+        let h = PI / ((n + 1) as f64);
+        let nev = 3;
+        let mut params = EigenParams::new();
+        params.method = EigenMethod::Arnoldi;
+        params.which = EigenWhich::LargestReal;
+        params.max_iterations = 1000;
+        let mut solver = EigenSolver::new(Some(params)).unwrap();
+        solver.solve(nev, &k, None).unwrap();
+        for i in 0..nev {
+            approx_eq(
+                solver.get_eigenvalues()[i],
+                2.0 + 2.0 * f64::cos(((i + 1) as f64) * h),
+                1e-8,
+            );
+            approx_eq(solver.get_eigenvalues_imag()[i], 0.0, 1e-10);
+            assert!(residual(&solver, i, &k, None) < 1e-7);
+        }
+
+        // complex eigenvalues: λⱼ = 2 + 2·i·cos(j·π/(n+1))
+        let k = tridiagonal(n, -1.0, 2.0, 1.0);
+        params.which = EigenWhich::LargestMagnitude;
+        let mut solver = EigenSolver::new(Some(params)).unwrap();
+        solver.solve(2, &k, None).unwrap();
+        for i in 0..2 {
+            approx_eq(solver.get_eigenvalues()[i], 2.0, 1e-9);
+            approx_eq(f64::abs(solver.get_eigenvalues_imag()[i]), 2.0 * f64::cos(h), 1e-9);
+            assert!(residual(&solver, i, &k, None) < 1e-8);
+        }
+        approx_eq(
+            solver.get_eigenvalues_imag()[0],
+            -solver.get_eigenvalues_imag()[1],
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn arnoldi_shift_invert_generalized_works() {
+        let n = 30;
+        let k = tridiagonal(n, 0.9, 2.0, 1.0 / 0.9);
+        let m = tridiagonal(n, 0.0, 2.0, 0.0);
+        let h = PI / ((n + 1) as f64);
+        let sigma = 2.0 + 2.0 * f64::cos(10.0 * h) + 0.01;
+        let mut params = EigenParams::new();
+        params.method = EigenMethod::Arnoldi;
+        params.shift_invert = true;
+        params.sigma = sigma / 2.0;
+        let mut solver = EigenSolver::new(Some(params)).unwrap();
+        solver.solve(2, &k, Some(&m)).unwrap();
+        approx_eq(
+            solver.get_eigenvalues()[0],
+            (2.0 + 2.0 * f64::cos(10.0 * h)) / 2.0,
+            1e-10,
+        );
+        for i in 0..2 {
+            assert!(residual(&solver, i, &k, Some(&m)) < 1e-9);
+        }
+    }
+
+    #[test]
+    fn lobpcg_works() {
+        let n = 40;
+        let k = tridiagonal(n, -1.0, 2.0, -1.0);
+        let m = tridiagonal(n, 1.0 / 6.0, 4.0 / 6.0, 1.0 / 6.0);
+        let h = PI / ((n + 1) as f64);
+        let nev = 3;
+
+        // largest eigenvalues of the standard problem
+        let mut params = EigenParams::new();
+        params.method = EigenMethod::Lobpcg;
+        params.which = EigenWhich::LargestReal;
+        params.tolerance = 1e-9;
+        params.max_iterations = 500;
+        let mut solver = EigenSolver::new(Some(params)).unwrap();
+        solver.solve(nev, &k, None).unwrap();
+        for i in 0..nev {
+            approx_eq(
+                solver.get_eigenvalues()[i],
+                2.0 - 2.0 * f64::cos(((n - i) as f64) * h),
+                1e-9,
+            );
+            assert!(residual(&solver, i, &k, None) < 1e-7);
+        }
+
+        // smallest eigenvalues of the generalized problem with preconditioner
+        params.which = EigenWhich::SmallestReal;
+        params.lobpcg_precond = Precond::Ic0;
+        let mut solver = EigenSolver::new(Some(params)).unwrap();
+        solver.solve(nev, &k, Some(&m)).unwrap();
+        for i in 0..nev {
+            let c = f64::cos(((i + 1) as f64) * h);
+            approx_eq(solver.get_eigenvalues()[i], 6.0 * (1.0 - c) / (2.0 + c), 1e-9);
+            assert!(residual(&solver, i, &k, Some(&m)) < 1e-7);
+        }
+    }
+}
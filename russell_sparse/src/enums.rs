@@ -3,6 +3,46 @@ use serde::{Deserialize, Serialize};
 /// Specifies the underlying library that does all the magic
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum Genie {
+    /// Selects the (iterative) biconjugate gradient stabilized method (BiCGStab)
+    ///
+    /// Works with general (unsymmetric) matrices.
+    ///
+    /// Reference: van der Vorst (1992) Bi-CGSTAB: A fast and smoothly converging variant of Bi-CG
+    /// for the solution of nonsymmetric linear systems, SIAM J. Sci. Stat. Comput. 13(2):631-644
+    BiCgStab,
+
+    /// Selects the (iterative) conjugate gradient method (CG)
+    ///
+    /// Requires a symmetric positive-definite matrix.
+    ///
+    /// Reference: Hestenes and Stiefel (1952) Methods of conjugate gradients for solving linear systems,
+    /// Journal of Research of the National Bureau of Standards 49(6):409-436
+    Cg,
+
+    /// Selects CHOLMOD (supernodal sparse Cholesky factorization)
+    ///
+    /// Requires a symmetric (Hermitian) positive-definite matrix.
+    ///
+    /// Reference: <https://github.com/DrTimothyAldenDavis/SuiteSparse>
+    Cholmod,
+
+    /// Selects the (iterative) conjugate orthogonal conjugate gradient method (COCG)
+    ///
+    /// Requires a complex symmetric matrix (`A = Aᵀ`, not Hermitian), such as the ones arising from
+    /// frequency-domain acoustic and electromagnetic problems. For real matrices, COCG is the same as CG.
+    ///
+    /// Reference: van der Vorst and Melissen (1990) A Petrov-Galerkin type method for solving Ax = b,
+    /// where A is symmetric complex, IEEE Transactions on Magnetics 26(2):706-708
+    Cocg,
+
+    /// Selects the (iterative) restarted generalized minimal residual method (GMRES)
+    ///
+    /// Works with general (unsymmetric) matrices.
+    ///
+    /// Reference: Saad and Schultz (1986) GMRES: A generalized minimal residual algorithm for solving
+    /// nonsymmetric linear systems, SIAM J. Sci. Stat. Comput. 7(3):856-869
+    Gmres,
+
     /// Selects KLU (LU factorization)
     ///
     /// "Clark Kent" LU factorization algorithm (what SuperLU was before it became Super)
@@ -10,11 +50,28 @@ pub enum Genie {
     /// Reference: <https://github.com/DrTimothyAldenDavis/SuiteSparse>
     Klu,
 
+    /// Selects the (iterative) minimal residual method (MINRES)
+    ///
+    /// Requires a symmetric matrix, which may be indefinite.
+    ///
+    /// Reference: Paige and Saunders (1975) Solution of sparse indefinite systems of linear equations,
+    /// SIAM J. Numer. Anal. 12(4):617-629
+    Minres,
+
     /// Selects MUMPS (multi-frontal massively parallel sparse direct) solver
     ///
     /// Reference: <https://mumps-solver.org/index.php>
     Mumps,
 
+    /// Selects the native (pure Rust) sparse direct solver
+    ///
+    /// Uses a fill-reducing ordering followed by the left-looking LU factorization (with threshold
+    /// partial pivoting) or, for symmetric positive-definite matrices, the up-looking Cholesky factorization.
+    /// This solver does not require SuiteSparse or MUMPS.
+    ///
+    /// Reference: Davis TA (2006) Direct Methods for Sparse Linear Systems, SIAM, Philadelphia
+    Native,
+
     /// Selects UMFPACK (unsymmetric multi-frontal) solver
     ///
     /// Reference: <https://github.com/DrTimothyAldenDavis/SuiteSparse>
@@ -102,6 +159,96 @@ pub enum Ordering {
     Scotch,
 }
 
+/// Eigenvalue solver option (sparse eigenvalue problems)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum EigenMethod {
+    /// Implicitly restarted Arnoldi method; works with general (unsymmetric) matrices
+    Arnoldi,
+
+    /// Thick-restart Lanczos method; requires symmetric matrices
+    Lanczos,
+
+    /// Locally optimal block preconditioned conjugate gradient method; requires symmetric matrices
+    Lobpcg,
+}
+
+/// Selects which eigenvalues are computed by the sparse eigenvalue solver
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum EigenWhich {
+    /// Eigenvalues with the largest modulus
+    LargestMagnitude,
+
+    /// Eigenvalues with the largest real part (largest algebraic values for symmetric matrices)
+    LargestReal,
+
+    /// Eigenvalues with the smallest modulus
+    SmallestMagnitude,
+
+    /// Eigenvalues with the smallest real part (smallest algebraic values for symmetric matrices)
+    SmallestReal,
+}
+
+/// Least-squares solver option (overdetermined and underdetermined sparse systems)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum LsqMethod {
+    /// Iterative LSMR method (Fong and Saunders); needs only the products with A and Aᵀ
+    Lsmr,
+
+    /// Iterative LSQR method (Paige and Saunders); needs only the products with A and Aᵀ
+    Lsqr,
+
+    /// Sparse QR factorization (SuiteSparseQR); requires the `with_suitesparse` feature
+    Spqr,
+}
+
+/// Nonlinear system solver option
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum NonlinMethod {
+    /// Newton-Raphson method with the (analytical or numerical) sparse Jacobian matrix
+    Newton,
+
+    /// Jacobian-free Newton-Krylov method; the Jacobian-vector products are approximated by finite differences
+    NewtonKrylov,
+}
+
+/// Globalization strategy of the nonlinear system solver
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum NonlinGlobal {
+    /// Backtracking line search with the Armijo (sufficient decrease) condition on `½‖F‖²`
+    LineSearch,
+
+    /// No globalization (full Newton steps)
+    No,
+
+    /// Dogleg trust region (requires [NonlinMethod::Newton] because the dogleg step needs the Jacobian matrix)
+    TrustRegion,
+}
+
+/// Preconditioner option (iterative solvers only)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Precond {
+    /// Smoothed-aggregation algebraic multigrid (V-cycle)
+    Amg,
+
+    /// Incomplete Cholesky factorization with zero fill-in (symmetric positive-definite matrices)
+    Ic0,
+
+    /// Incomplete LU factorization with zero fill-in
+    Ilu0,
+
+    /// Incomplete LU factorization with threshold dropping and limited fill-in
+    Ilut,
+
+    /// Jacobi (diagonal) preconditioner
+    Jacobi,
+
+    /// No preconditioner
+    No,
+
+    /// Symmetric successive over-relaxation
+    Ssor,
+}
+
 /// Scaling option
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum Scaling {
@@ -137,8 +284,15 @@ impl Genie {
     /// Returns the Genie by name (default is umfpack)
     pub fn from(genie: &str) -> Self {
         match genie.to_lowercase().as_str() {
+            "bicgstab" => Genie::BiCgStab,
+            "cg" => Genie::Cg,
+            "cholmod" => Genie::Cholmod,
+            "cocg" => Genie::Cocg,
+            "gmres" => Genie::Gmres,
             "klu" => Genie::Klu,
+            "minres" => Genie::Minres,
             "mumps" => Genie::Mumps,
+            "native" => Genie::Native,
             "umfpack" => Genie::Umfpack,
             _ => Genie::Umfpack,
         }
@@ -147,8 +301,15 @@ impl Genie {
     /// Returns the string representation
     pub fn to_string(&self) -> String {
         match self {
+            Genie::BiCgStab => "bicgstab".to_string(),
+            Genie::Cg => "cg".to_string(),
+            Genie::Cholmod => "cholmod".to_string(),
+            Genie::Cocg => "cocg".to_string(),
+            Genie::Gmres => "gmres".to_string(),
             Genie::Klu => "klu".to_string(),
+            Genie::Minres => "minres".to_string(),
             Genie::Mumps => "mumps".to_string(),
+            Genie::Native => "native".to_string(),
             Genie::Umfpack => "umfpack".to_string(),
         }
     }
@@ -157,14 +318,29 @@ impl Genie {
     pub fn get_sym(&self, symmetric: bool) -> Sym {
         if symmetric {
             match self {
+                Genie::BiCgStab => Sym::YesFull,
+                Genie::Cg => Sym::YesFull,
+                Genie::Cholmod => Sym::YesLower,
+                Genie::Cocg => Sym::YesFull,
+                Genie::Gmres => Sym::YesFull,
                 Genie::Klu => Sym::YesFull,
+                Genie::Minres => Sym::YesFull,
                 Genie::Mumps => Sym::YesLower,
+                Genie::Native => Sym::YesLower,
                 Genie::Umfpack => Sym::YesFull,
             }
         } else {
             Sym::No
         }
     }
+
+    /// Returns true if the Genie is an iterative (Krylov) solver
+    pub fn is_iterative(&self) -> bool {
+        match self {
+            Genie::BiCgStab | Genie::Cg | Genie::Cocg | Genie::Gmres | Genie::Minres => true,
+            Genie::Cholmod | Genie::Klu | Genie::Mumps | Genie::Native | Genie::Umfpack => false,
+        }
+    }
 }
 
 impl Sym {
@@ -198,6 +374,42 @@ impl Ordering {
     }
 }
 
+impl LsqMethod {
+    /// Returns the LsqMethod by name (default is Lsmr)
+    pub fn from(method: &str) -> Self {
+        match method.to_lowercase().as_str() {
+            "lsmr" => LsqMethod::Lsmr,
+            "lsqr" => LsqMethod::Lsqr,
+            "spqr" => LsqMethod::Spqr,
+            _ => LsqMethod::Lsmr,
+        }
+    }
+
+    /// Returns true if the method is iterative (Krylov)
+    pub fn is_iterative(&self) -> bool {
+        match self {
+            LsqMethod::Lsmr | LsqMethod::Lsqr => true,
+            LsqMethod::Spqr => false,
+        }
+    }
+}
+
+impl Precond {
+    /// Returns the Precond by name (default is No)
+    pub fn from(precond: &str) -> Self {
+        match precond.to_lowercase().as_str() {
+            "amg" => Precond::Amg,
+            "ic0" => Precond::Ic0,
+            "ilu0" => Precond::Ilu0,
+            "ilut" => Precond::Ilut,
+            "jacobi" => Precond::Jacobi,
+            "no" => Precond::No,
+            "ssor" => Precond::Ssor,
+            _ => Precond::No,
+        }
+    }
+}
+
 impl Scaling {
     /// Returns the Scaling by name (default is Auto)
     pub fn from(scaling: &str) -> Self {
@@ -264,6 +476,56 @@ mod tests {
         let from_json: Ordering = serde_json::from_str(&json).unwrap();
         assert_eq!(from_json, ordering);
 
+        let method = EigenMethod::Lanczos;
+        let copy = method;
+        let clone = method.clone();
+        assert_eq!(format!("{:?}", method), "Lanczos");
+        assert_eq!(copy, EigenMethod::Lanczos);
+        assert_eq!(clone, EigenMethod::Lanczos);
+        let json = serde_json::to_string(&method).unwrap();
+        let from_json: EigenMethod = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_json, method);
+
+        let which = EigenWhich::SmallestReal;
+        let copy = which;
+        let clone = which.clone();
+        assert_eq!(format!("{:?}", which), "SmallestReal");
+        assert_eq!(copy, EigenWhich::SmallestReal);
+        assert_eq!(clone, EigenWhich::SmallestReal);
+        let json = serde_json::to_string(&which).unwrap();
+        let from_json: EigenWhich = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_json, which);
+
+        let method = NonlinMethod::NewtonKrylov;
+        let copy = method;
+        let clone = method.clone();
+        assert_eq!(format!("{:?}", method), "NewtonKrylov");
+        assert_eq!(copy, NonlinMethod::NewtonKrylov);
+        assert_eq!(clone, NonlinMethod::NewtonKrylov);
+        let json = serde_json::to_string(&method).unwrap();
+        let from_json: NonlinMethod = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_json, method);
+
+        let global = NonlinGlobal::TrustRegion;
+        let copy = global;
+        let clone = global.clone();
+        assert_eq!(format!("{:?}", global), "TrustRegion");
+        assert_eq!(copy, NonlinGlobal::TrustRegion);
+        assert_eq!(clone, NonlinGlobal::TrustRegion);
+        let json = serde_json::to_string(&global).unwrap();
+        let from_json: NonlinGlobal = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_json, global);
+
+        let precond = Precond::Ilu0;
+        let copy = precond;
+        let clone = precond.clone();
+        assert_eq!(format!("{:?}", precond), "Ilu0");
+        assert_eq!(copy, Precond::Ilu0);
+        assert_eq!(clone, Precond::Ilu0);
+        let json = serde_json::to_string(&precond).unwrap();
+        let from_json: Precond = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_json, precond);
+
         let scaling = Scaling::Column;
         let copy = scaling;
         let clone = scaling.clone();
@@ -304,6 +566,38 @@ mod tests {
         assert_eq!(Ordering::from("unknown"), Ordering::Auto);
     }
 
+    #[test]
+    fn lsq_method_functions_work() {
+        assert_eq!(LsqMethod::from("Lsmr"), LsqMethod::Lsmr);
+        assert_eq!(LsqMethod::from("lsqr"), LsqMethod::Lsqr);
+        assert_eq!(LsqMethod::from("SPQR"), LsqMethod::Spqr);
+        assert_eq!(LsqMethod::from("unknown"), LsqMethod::Lsmr);
+        assert!(LsqMethod::Lsmr.is_iterative());
+        assert!(LsqMethod::Lsqr.is_iterative());
+        assert!(!LsqMethod::Spqr.is_iterative());
+    }
+
+    #[test]
+    fn precond_functions_work() {
+        assert_eq!(Precond::from("Amg"), Precond::Amg);
+        assert_eq!(Precond::from("Ic0"), Precond::Ic0);
+        assert_eq!(Precond::from("Ilu0"), Precond::Ilu0);
+        assert_eq!(Precond::from("Ilut"), Precond::Ilut);
+        assert_eq!(Precond::from("Jacobi"), Precond::Jacobi);
+        assert_eq!(Precond::from("No"), Precond::No);
+        assert_eq!(Precond::from("Ssor"), Precond::Ssor);
+        assert_eq!(Precond::from("Unknown"), Precond::No);
+
+        assert_eq!(Precond::from("amg"), Precond::Amg);
+        assert_eq!(Precond::from("ic0"), Precond::Ic0);
+        assert_eq!(Precond::from("ilu0"), Precond::Ilu0);
+        assert_eq!(Precond::from("ilut"), Precond::Ilut);
+        assert_eq!(Precond::from("jacobi"), Precond::Jacobi);
+        assert_eq!(Precond::from("no"), Precond::No);
+        assert_eq!(Precond::from("ssor"), Precond::Ssor);
+        assert_eq!(Precond::from("unknown"), Precond::No);
+    }
+
     #[test]
     fn scaling_functions_work() {
         assert_eq!(Scaling::from("Auto"), Scaling::Auto);
@@ -347,6 +641,38 @@ mod tests {
         assert_eq!(genie.to_string(), "umfpack");
         assert_eq!(genie.get_sym(false,), Sym::No);
         assert_eq!(genie.get_sym(true), Sym::YesFull);
+        assert!(!genie.is_iterative());
+
+        assert_eq!(Genie::from("bicgstab"), Genie::BiCgStab);
+        assert_eq!(Genie::from("BiCgStab"), Genie::BiCgStab);
+        assert_eq!(Genie::from("cg"), Genie::Cg);
+        assert_eq!(Genie::from("cocg"), Genie::Cocg);
+        assert_eq!(Genie::from("gmres"), Genie::Gmres);
+        assert_eq!(Genie::from("klu"), Genie::Klu);
+        assert_eq!(Genie::from("minres"), Genie::Minres);
+
+        let genie = Genie::Cholmod;
+        assert_eq!(Genie::from("cholmod"), genie);
+        assert_eq!(Genie::from("CHOLMOD"), genie);
+        assert_eq!(genie.to_string(), "cholmod");
+        assert_eq!(genie.get_sym(false), Sym::No);
+        assert_eq!(genie.get_sym(true), Sym::YesLower);
+        assert!(!genie.is_iterative());
+
+        let genie = Genie::Native;
+        assert_eq!(Genie::from("native"), genie);
+        assert_eq!(Genie::from("Native"), genie);
+        assert_eq!(genie.to_string(), "native");
+        assert_eq!(genie.get_sym(false), Sym::No);
+        assert_eq!(genie.get_sym(true), Sym::YesLower);
+        assert!(!genie.is_iterative());
+
+        for genie in [Genie::BiCgStab, Genie::Cg, Genie::Cocg, Genie::Gmres, Genie::Minres] {
+            assert_eq!(Genie::from(&genie.to_string()), genie);
+            assert_eq!(genie.get_sym(false), Sym::No);
+            assert_eq!(genie.get_sym(true), Sym::YesFull);
+            assert!(genie.is_iterative());
+        }
     }
 
     #[test]
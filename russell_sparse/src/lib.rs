@@ -34,15 +34,47 @@
 //!
 //! The three individual sparse matrix structures ([CooMatrix], [CscMatrix], and [CsrMatrix]) and the wrapping (unifying) structure SparseMatrix have functions to calculate the (sparse) matrix-vector product, which, albeit not computer optimized, are convenient for checking the solution to the linear problem A * x = b (see also the VerifyLinSys structure).
 //!
+//! Sparse-sparse operations on CSR and CSC matrices are also available: the transpose (e.g., [csr_transpose()]), the sum (e.g., [csr_add()]), the product (e.g., [csr_mat_mat_mul()]), the triple product Pᵀ·A·P (e.g., [csr_triple_product()]), and the scaling of rows and columns (e.g., [csr_scale_rows()]). These functions have separate symbolic and numeric phases (e.g., [csr_add_symbolic()] and [csr_add_numeric()]); thus, a known sparsity pattern can be reused when only the values change. These functions are generic and thus also work with complex matrices (e.g., [ComplexCsrMatrix]), for which the conjugate and the conjugate transpose are also available (e.g., [csr_conjugate()] and [csr_conjugate_transpose()]).
+//!
+//! The fill-reducing and bandwidth-reducing orderings are also implemented (in Rust): the reverse Cuthill-McKee ordering ([ordering_rcm()]), the approximate minimum degree ordering ([ordering_amd()]), and the nested dissection ordering ([ordering_nested_dissection()]). These functions return permutation vectors which may be used with any solver; for instance, the matrices may be symmetrically permuted by [coo_permute_symmetric()] or [csr_permute_symmetric()], and the bandwidths given by [csr_bandwidth()] may be used with the dense banded storage of russell_lab.
+//!
+//! Structural operations are available for COO, CSR, and CSC matrices: the extraction of submatrices A[I, J] (e.g., [csr_submatrix()]), general row and column permutations (e.g., [csr_permute()]), the elimination of prescribed DOFs (e.g., [csr_eliminate_dofs()] together with [free_dofs()] and [expand_dofs()]), and the assembly of block matrices such as [[A, B], [C, D]] (e.g., [csr_block_assemble()]). The symmetric (triangular) representation is preserved whenever possible.
+//!
 //! We recommend using the [SparseMatrix] directly unless your computations need a more specialized interaction with the CSC or CSR formats. Also, the [SparseMatrix] returns "pointers" to the CSC and CSR structures (constant access and mutable access).
 //!
 //! We call the actual linear system solver implementation [Genie] because they work like "magic" after being "wrapped" via a C-interface. Note that these fantastic solvers are implemented in Fortran and C. You may easily access the linear solvers directly via the following structures:
 //!
 //! * [SolverMUMPS] -- thin wrapper to the MUMPS solver
 //! * [SolverUMFPACK] -- thin wrapper to the UMFPACK solver
+//! * [SolverCHOLMOD] -- thin wrapper to the CHOLMOD solver (sparse Cholesky factorization of symmetric positive-definite matrices, with log-determinant, update/downdate, and partial solves)
+//! * [SolverSPQR] -- thin wrapper to the SuiteSparseQR solver (sparse QR factorization for least-squares problems; see [LsqSolver])
+//!
+//! Because the above solvers depend on external libraries, this crate also implements a native (pure Rust) sparse direct solver, [SolverNative] (and [ComplexSolverNative]), selected via [Genie::Native]. This solver computes a fill-reducing ordering (approximate minimum degree or nested dissection) and then performs the sparse LU factorization with threshold partial pivoting or, for symmetric positive-definite matrices, the sparse Cholesky factorization. The performance is adequate for small- and medium-sized problems. The SuiteSparse solvers (CHOLMOD, KLU, and UMFPACK) are enabled by the `with_suitesparse` feature, which is on by default; thus, if the C libraries are not available, the crate may be compiled with `default-features = false` and [Genie::Native] will still work.
+//!
+//! For very large systems, for which the memory needed by the factors is prohibitive, this library also implements (in Rust) the iterative Krylov subspace methods CG, MINRES, restarted GMRES, and BiCGStab via [NumSolverKrylov] (see the alias [SolverKrylov]), which are selected via [Genie::Cg], [Genie::Minres], [Genie::Gmres], and [Genie::BiCgStab]. These solvers only need matrix-vector products and thus can also be used with matrix-free operators (see [SolverKrylov::solve_with_operator()]). The sparse matrices also implement the [russell_lab::LinearOperator] trait, which may be combined via [russell_lab::OperatorSum], [russell_lab::OperatorProduct], and [russell_lab::OperatorBlock] without assembling the resulting matrix, and solved by [SolverKrylov::solve_with_linear_operator()]. The matrix-vector products of CSR matrices may be computed by multiple threads, with the rows partitioned by the number of non-zeros, using [NumCsrMatrix::mat_vec_mul_parallel()] (see [LinSolParams::krylov_num_threads]); symmetric matrices with triangular storage are handled without assembling the full matrix. For repeated products (e.g., in the Krylov solvers), [ParallelMatVec] keeps the threads and the workspace across calls. The transposed products are available via `mat_t_vec_mul` (e.g., [NumSparseMatrix::mat_t_vec_mul()]).
+//!
+//! The same solvers work with complex matrices via the alias [ComplexSolverKrylov] (also selected via [ComplexLinSolver]), which is useful for frequency-domain acoustic and electromagnetic problems that are too large for the direct solvers. For complex symmetric matrices (`A = Aᵀ`), the conjugate orthogonal conjugate gradient method (COCG) is selected via [Genie::Cocg]. For Hermitian matrices (`A = Aᴴ`), CG and MINRES are selected via [Genie::Cg] and [Genie::Minres]. GMRES and BiCGStab work with general complex matrices.
+//!
+//! The convergence of the iterative solvers may be accelerated by the preconditioners (also implemented in Rust) [PrecondJacobi], [PrecondSSOR], [PrecondILU0], [PrecondILUT], [PrecondIC0], and [PrecondAMG] (algebraic multigrid), which are selected via [LinSolParams::krylov_precond]. All preconditioners implement the [PrecondTrait], which splits the computations into `setup` and `apply`; thus, a preconditioner may be set up again whenever the values of the matrix change (e.g., in Newton iterations), or only updated via [PrecondTrait::update] (see [LinSolParams::krylov_precond_reuse]), which keeps the AMG hierarchy. The AMG preconditioner may also be given a near-null space (e.g., the rigid-body modes of elasticity problems) via [PrecondAMG::set_near_null_space]. The [NumPreconditioner] structure holds a "pointer" to one of these implementations; the preconditioners and the iterative solvers are generic over the [FactorScalar] trait and thus work with real and complex matrices (see the aliases [Preconditioner] and [ComplexPreconditioner]).
+//!
+//! A few eigenvalues (and eigenvectors) of large sparse matrices, for the standard problem `K · x = λ · x` or the generalized problem `K · x = λ · M · x`, may be computed by the [EigenSolver] (implemented in Rust). The available methods are the thick-restart Lanczos method and LOBPCG for symmetric matrices, and the implicitly restarted Arnoldi method for general matrices (see [EigenMethod]). The shift-invert mode, which quickly finds the eigenvalues nearest to a shift σ, factorizes `K - σ·M` once via a [LinSolver] and reuses the factorization in all iterations. The configuration parameters are collected in [EigenParams].
+//!
+//! Systems of nonlinear algebraic equations `F(x) = 0`, defined by a [NonlinSystem] with an optional (sparse) Jacobian function, may be solved by the [NonlinSolver]. The Newton-Raphson method uses the analytical or numerical Jacobian matrix and a [LinSolver], whereas the Jacobian-free Newton-Krylov method only needs the residual function (see [NonlinMethod]). The convergence from poor initial guesses may be improved by a backtracking line search or a dogleg trust region (see [NonlinGlobal]). The configuration parameters are collected in [NonlinParams] and the convergence statistics in [StatsNonlin].
 //!
 //! This library also provides a unifying Trait called [LinSolTrait], which the above structures implement. In addition, the [LinSolver] structure holds a "pointer" to one of the above structures and is a more convenient way to use the linear solvers in generic codes when we need to switch from solver to solver (e.g., for benchmarking). After allocating a [LinSolver], if needed, we can access the actual implementations (interfaces/thin wrappers) via the [LinSolver::actual] data member.
 //!
+//! Once the matrix is factorized, the direct solvers can also solve the transposed system `Aᵀ · x = b` (and `Aᴴ · x = b` for complex matrices) via [LinSolTrait::solve_transpose()], and many right-hand sides at once, given as a dense [russell_lab::Matrix] or as a sparse [CscMatrix], via [LinSolTrait::solve_multiple()] and [LinSolTrait::solve_sparse_rhs()]. UMFPACK, KLU, and MUMPS perform these operations natively (e.g., KLU and MUMPS handle all right-hand sides in a single call, and MUMPS exploits the sparsity of the right-hand sides); the other solvers fall back to a loop over the columns, which, for sparse right-hand sides, expands only one column at a time.
+//!
+//! The accuracy of the solution may be improved by the iterative refinement performed by [LinSolver::solve()] (and [ComplexLinSolver::solve()]), which computes the residual `r = b - A · x`, solves `A · d = r` reusing the factorization, and updates `x ← x + d` until the normwise backward error is smaller than [LinSolParams::refinement_tolerance] (see [LinSolParams::refinement_max_iterations]). The native solver also offers a mixed-precision mode ([LinSolParams::mixed_precision]), in which the factorization is computed in single precision (halving the memory of the factors) and the accuracy is recovered by the refinement in double precision. The history of the backward errors is reported in [StatsLinSolRefinement].
+//!
+//! Singular matrices (e.g., the stiffness matrix of a floating structure or the matrix of a pure-Neumann problem) may be handled by enabling the null pivot detection with [LinSolParams::null_pivot_detection]. Then, the native solver replaces the null pivots by one and computes an orthonormal basis of the null space ([LinSolTrait::null_space()]) and the minimum-norm least-squares solution ([LinSolTrait::solve_pseudo_inverse()]). MUMPS also computes the null space (via ICNTL(25)) and the pseudo-inverse solution; however, for unsymmetric matrices, the system must be compatible because MUMPS does not return the null space of Aᵀ. The rank is reported in [StatsLinSolRank] and the determinant (zero for singular matrices) in [StatsLinSolDeterminant], normalized as `mantissa · 10^exponent` for all solvers.
+//!
+//! Finite-element style assembly is performed by the [NumAssembler] (aliases [Assembler] and [ComplexAssembler]), which computes the sparsity pattern (CSR or CSC) once from the DOFs of the elements (see [element_dofs_from_connectivity()]) and stores the position of each entry of the element matrices in the global matrix. Thus, repeated assemblies (e.g., at every Newton iteration) scatter the element matrices directly into the values, without duplicates, optionally in parallel using element coloring. The CSC matrix can be given directly to the linear solvers.
+//!
+//! Rectangular (overdetermined or underdetermined) and rank-deficient systems may be solved in the least-squares sense, i.e., `min ‖A · x - b‖`, by the [LsqSolver] (configured by [LsqParams]). The direct method employs the sparse QR factorization by [SolverSPQR] (SuiteSparseQR), which computes the least-squares solution if `nrow ≥ ncol` and the minimum-norm solution if `nrow < ncol`, and also estimates the rank of A. The iterative methods LSQR and LSMR (see [LsqMethod]) only require the products with A and Aᵀ and thus also work with matrix-free operators ([russell_lab::LinearOperator]); optionally, they solve the damped (Tikhonov-regularized) problem.
+//!
+//! For sequences of linear systems with the same sparsity pattern (e.g., Newton iterations), the symbolic analysis may be performed once via [LinSolTrait::analyze()], followed by [LinSolTrait::factorize_numeric()] whenever the values change. In addition, [LinSolTrait::refactorize()] reuses the previous pivot sequence (e.g., via `klu_refactor`) and is thus faster, although it may be less accurate if the values change significantly. All steps check that the sparsity pattern is unchanged (see [CscMatrix::get_pattern_fingerprint()]; MUMPS compares the COO indices given to `analyze`) and the computer times are reported in [StatsLinSol]. These steps are available in the native, CHOLMOD, KLU, MUMPS, and UMFPACK solvers (MUMPS performs the symbolic analysis with JOB=1 and the numeric factorization with JOB=2; since MUMPS cannot reuse the pivot sequence, `refactorize` is equivalent to `factorize_numeric`).
+//!
 //! The [LinSolTrait] has two main functions (that should be called in this order):
 //!
 //! * [LinSolTrait::factorize()] -- performs the initialization of the linear solver, if needed, analysis, and symbolic and numerical factorization of the coefficient matrix A from A * x = b
@@ -62,10 +94,14 @@
 //!
 //! The linear solvers have numerous configuration parameters; however, we can use the default parameters initially. The configuration parameters are collected in the [LinSolParams] structures, which is an input to the [LinSolTrait::factorize()]. The parameters include options such as [Ordering] and [Scaling].
 //!
-//! This library also provides functions to read and write Matrix Market files containing (huge) sparse matrices that can be used in performance benchmarking or other studies. The [read_matrix_market()] function reads a Matrix Market file and returns a [CooMatrix]. To write a Matrix Market file, we can use [CscMatrix::write_matrix_market()] (and similar), which automatically converts COO to CSC or COO to CSR, also performing the sum of duplicates. The `write_matrix_market` can also writs an SMAT file (almost like the Matrix Market format) without the header and with zero-based indices. The SMAT file can be given to the fantastic [Vismatrix](https://github.com/cpmech/vismatrix) tool to visualize the sparse matrix structure and values interactively; see the example below.
+//! This library also provides functions to read and write Matrix Market files containing (huge) sparse matrices that can be used in performance benchmarking or other studies. The [read_matrix_market()] function reads a Matrix Market file and returns a [CooMatrix]. To write a Matrix Market file, we can use [CscMatrix::write_matrix_market()] (and similar), which automatically converts COO to CSC or COO to CSR, also performing the sum of duplicates. The `write_matrix_market` can also writs an SMAT file (almost like the Matrix Market format) without the header and with zero-based indices. The SMAT file can be given to the fantastic [Vismatrix](https://github.com/cpmech/vismatrix) tool to visualize the sparse matrix structure and values interactively; see the example below. Without external tools, the sparsity pattern (spy plot) may be written to an SVG file by [CscMatrix::write_spy_svg()] (and [ComplexCscMatrix::write_spy_svg()]), which colors the entries by magnitude, draws block boundaries, downsamples huge matrices, and shows the fill-in of the factorization with or without a permutation (see [SpyParams]).
 //!
 //! ![doc-example-vismatrix](https://raw.githubusercontent.com/cpmech/russell/main/russell_sparse/data/figures/doc-example-vismatrix.png)
 //!
+//! Besides the Matrix Market format (including the `array` and `pattern` variants and gzip-compressed files), the Rutherford-Boeing and Harwell-Boeing formats can be read by [read_rutherford_boeing()] and written by [CscMatrix::write_rutherford_boeing()]. For (very) large matrices, the versioned binary format written by [CscMatrix::write_binary()] or [CsrMatrix::write_binary()] is much faster to read than the text formats.
+//!
+//! Before choosing a solver, the structural and numerical properties of a matrix (symmetry, bandwidth, profile, diagonal dominance, empty rows and columns, duplicates, and an estimate of the 1-norm condition number) can be obtained with [MatrixAnalysis]. The report is also available via the `--analyze` option of the `solve_matrix_market` binary.
+//!
 //! # Examples
 //!
 //! ## Create CSR matrix from COO
@@ -240,49 +276,143 @@
 pub type StrError = &'static str;
 
 mod aliases;
+mod assembler;
 mod complex_coo_matrix;
 mod complex_lin_solver;
-mod complex_solver_klu;
-mod complex_solver_umfpack;
+mod complex_solver_native;
 mod constants;
 mod coo_matrix;
 mod csc_matrix;
 mod csr_matrix;
+mod eigen_params;
+mod eigen_solver;
 mod enums;
 mod lin_sol_params;
+mod lin_sol_refinement;
 mod lin_solver;
+mod lsq_params;
+mod lsq_solver;
+mod matrix_analysis;
+mod nonlin_params;
+mod nonlin_solver;
+mod nonlin_system;
 mod numerical_jacobian;
+mod parallel_mat_vec;
+mod precond_amg;
+mod precond_ic0;
+mod precond_ilu0;
+mod precond_ilut;
+mod precond_jacobi;
+mod precond_ssor;
+mod preconditioner;
 pub mod prelude;
 mod read_matrix_market;
+mod read_rutherford_boeing;
+mod read_write_binary;
 mod samples;
-mod solver_klu;
-mod solver_umfpack;
+mod solver_krylov;
+mod solver_native;
+mod sparse_algebra;
+mod sparse_blocks;
+mod sparse_factor;
 mod sparse_matrix;
+mod sparse_ordering;
 mod stats_lin_sol;
 mod stats_lin_sol_mumps;
+mod stats_nonlin;
 mod verify_lin_sys;
+mod worker_pool;
 mod write_matrix_market;
+mod write_rutherford_boeing;
+mod write_spy_svg;
 
 pub use aliases::*;
+pub use assembler::*;
 pub use complex_lin_solver::*;
-pub use complex_solver_klu::*;
-pub use complex_solver_umfpack::*;
+pub use complex_solver_native::*;
 use constants::*;
 pub use coo_matrix::*;
 pub use csc_matrix::*;
 pub use csr_matrix::*;
+pub use eigen_params::*;
+pub use eigen_solver::*;
 pub use enums::*;
 pub use lin_sol_params::*;
+use lin_sol_refinement::*;
 pub use lin_solver::*;
+pub use lsq_params::*;
+pub use lsq_solver::*;
+pub use matrix_analysis::*;
+pub use nonlin_params::*;
+pub use nonlin_solver::*;
+pub use nonlin_system::*;
 pub use numerical_jacobian::*;
+pub use parallel_mat_vec::*;
+pub use precond_amg::*;
+pub use precond_ic0::*;
+pub use precond_ilu0::*;
+pub use precond_ilut::*;
+pub use precond_jacobi::*;
+pub use precond_ssor::*;
+pub use preconditioner::*;
 pub use read_matrix_market::*;
+pub use read_rutherford_boeing::*;
 pub use samples::*;
-pub use solver_klu::*;
-pub use solver_umfpack::*;
+pub use solver_krylov::*;
+pub use solver_native::*;
+pub use sparse_algebra::*;
+pub use sparse_blocks::*;
+pub use sparse_factor::FactorScalar;
+use sparse_factor::*;
 pub use sparse_matrix::*;
+pub use sparse_ordering::*;
 pub use stats_lin_sol::*;
 pub use stats_lin_sol_mumps::*;
+pub use stats_nonlin::*;
 pub use verify_lin_sys::*;
+pub use write_spy_svg::*;
+
+#[cfg(feature = "with_suitesparse")]
+mod complex_solver_cholmod;
+
+#[cfg(feature = "with_suitesparse")]
+mod complex_solver_klu;
+
+#[cfg(feature = "with_suitesparse")]
+mod complex_solver_umfpack;
+
+#[cfg(feature = "with_suitesparse")]
+mod solver_cholmod;
+
+#[cfg(feature = "with_suitesparse")]
+mod solver_klu;
+
+#[cfg(feature = "with_suitesparse")]
+mod solver_spqr;
+
+#[cfg(feature = "with_suitesparse")]
+mod solver_umfpack;
+
+#[cfg(feature = "with_suitesparse")]
+pub use complex_solver_cholmod::*;
+
+#[cfg(feature = "with_suitesparse")]
+pub use complex_solver_klu::*;
+
+#[cfg(feature = "with_suitesparse")]
+pub use complex_solver_umfpack::*;
+
+#[cfg(feature = "with_suitesparse")]
+pub use solver_cholmod::*;
+
+#[cfg(feature = "with_suitesparse")]
+pub use solver_klu::*;
+
+#[cfg(feature = "with_suitesparse")]
+pub use solver_spqr::*;
+
+#[cfg(feature = "with_suitesparse")]
+pub use solver_umfpack::*;
 
 #[cfg(feature = "with_mumps")]
 mod complex_solver_mumps;
@@ -1,7 +1,7 @@
-use super::{Ordering, Scaling};
+use super::{Ordering, Precond, Scaling};
 
 /// Defines the configuration parameters for the linear system solver
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LinSolParams {
     /// Defines the symmetric permutation (ordering)
     pub ordering: Ordering,
@@ -48,6 +48,115 @@ pub struct LinSolParams {
     /// Enforces the unsymmetric strategy, even for symmetric matrices (not recommended; UMFPACK only)
     pub umfpack_enforce_unsymmetric_strategy: bool,
 
+    /// Selects the supernodal (`Some(true)`) or simplicial (`Some(false)`) factorization (CHOLMOD only)
+    ///
+    /// **Note:** If None, CHOLMOD selects the method automatically, based on the ratio of the number of
+    /// floating-point operations to the number of non-zeros of the factor.
+    pub cholmod_supernodal: Option<bool>,
+
+    /// Defines the threshold τ ∈ (0, 1] of the partial pivoting of the native LU factorization
+    ///
+    /// **Note:** The diagonal entry is selected as pivot if its modulus is at least τ times the
+    /// largest modulus in the column. Thus, τ = 1 corresponds to the standard partial pivoting.
+    pub native_pivot_threshold: f64,
+
+    /// Defines the relative tolerance on the residual norm `‖b - A·x‖ ≤ rtol·‖b‖` (iterative solvers only)
+    pub krylov_rel_tol: f64,
+
+    /// Defines the absolute tolerance on the residual norm `‖b - A·x‖ ≤ atol` (iterative solvers only)
+    ///
+    /// **Note:** Convergence is reached when the residual norm is smaller than `max(rtol·‖b‖, atol)`
+    pub krylov_abs_tol: f64,
+
+    /// Defines the maximum number of iterations (iterative solvers only)
+    pub krylov_max_iterations: usize,
+
+    /// Defines the number of iterations before GMRES is restarted (the dimension of the Krylov subspace)
+    pub krylov_gmres_restart: usize,
+
+    /// Uses the values in `x` as the initial guess; otherwise, starts with `x = 0` (iterative solvers only)
+    pub krylov_nonzero_initial_guess: bool,
+
+    /// Defines the preconditioner (iterative solvers only)
+    ///
+    /// **Note:** The preconditioner is computed in `factorize` and reused by the subsequent calls to `solve`
+    pub krylov_precond: Precond,
+
+    /// Reuses the structure of the preconditioner in the subsequent calls to `factorize` (iterative solvers only)
+    ///
+    /// If true and [LinSolParams::krylov_precond] is unchanged, `factorize` only updates the preconditioner
+    /// with the new values of the matrix (see [crate::PrecondTrait::update]); e.g., the AMG aggregates and
+    /// prolongators are kept and only the coarse matrices are recomputed. Otherwise, the preconditioner is
+    /// computed from scratch.
+    pub krylov_precond_reuse: bool,
+
+    /// Defines the number of threads of the matrix-vector products (iterative solvers only); use 0 to select the available parallelism
+    ///
+    /// **Note:** The products are only computed in parallel if the matrix is given in CSR format (or in COO format,
+    /// from which the CSR matrix is computed). The threads are spawned by `factorize` and reused by the subsequent
+    /// calls to `solve`. See [crate::ParallelMatVec].
+    pub krylov_num_threads: usize,
+
+    /// Defines the relaxation factor ω ∈ (0, 2) of the SSOR preconditioner
+    pub precond_ssor_omega: f64,
+
+    /// Defines the drop tolerance (relative to the norm of the row) of the ILUT preconditioner
+    pub precond_ilut_drop_tol: f64,
+
+    /// Defines the max number of entries (in addition to the original ones) kept in each row of L and U by ILUT
+    pub precond_ilut_fill: usize,
+
+    /// Defines a relative shift applied to the diagonal, `aii·(1 + shift)`, by the IC(0) preconditioner
+    ///
+    /// **Note:** A positive shift may avoid the breakdown of the incomplete factorization
+    pub precond_ic0_shift: f64,
+
+    /// Defines the strength-of-connection threshold θ of the AMG preconditioner: `|aij| ≥ θ·√|aii·ajj|`
+    pub precond_amg_strength: f64,
+
+    /// Defines the maximum number of levels of the AMG preconditioner
+    pub precond_amg_max_levels: usize,
+
+    /// Defines the number of rows of the coarsest matrix in AMG, below which coarsening stops
+    pub precond_amg_coarse_size: usize,
+
+    /// Defines the number of (Gauss-Seidel) smoothing sweeps in the AMG V-cycle
+    pub precond_amg_sweeps: usize,
+
+    /// Defines the maximum number of iterative refinement steps performed by `solve` (0 = no refinement)
+    ///
+    /// **Note:** Each step computes the residual `r = b - A·x` in double precision, solves `A·d = r`
+    /// with the available factorization (or preconditioned Krylov method), and updates `x ← x + d`.
+    pub refinement_max_iterations: usize,
+
+    /// Defines the tolerance on the backward error `‖b - A·x‖∞ / (‖A‖∞·‖x‖∞ + ‖b‖∞)` of the iterative refinement
+    ///
+    /// **Note:** The refinement also stops if the backward error does not decrease (stagnation).
+    pub refinement_tolerance: f64,
+
+    /// Requests the factorization in single precision (f32) followed by the refinement in double precision
+    ///
+    /// **Note:** Only the native solver (real matrices) is able to factorize in single precision; the other
+    /// solvers ignore this option. Since the single-precision factors yield about 7 significant digits,
+    /// `refinement_max_iterations` should be positive. The precision is fixed by the first factorization.
+    pub mixed_precision: bool,
+
+    /// Requests the detection of null pivots, i.e., the rank-revealing factorization of singular matrices
+    ///
+    /// **Note:** Only the native solver and MUMPS (`ICNTL(24)`) detect null pivots; the other solvers
+    /// ignore this option. With the native solver, the null pivots are replaced by one, thus the
+    /// factorization of a singular matrix (e.g., the stiffness matrix of a floating structure or the
+    /// matrix of a pure-Neumann problem) succeeds; then, the null-space basis and the pseudo-inverse
+    /// solution are available (see [crate::LinSolTrait::null_space] and [crate::LinSolTrait::solve_pseudo_inverse]).
+    /// With MUMPS, only the rank is reported; the null-space basis is not available. UMFPACK and KLU
+    /// fail on singular matrices. The rank is reported in [crate::StatsLinSolRank].
+    pub null_pivot_detection: bool,
+
+    /// Defines the tolerance of the null pivot detection relative to the largest absolute value of `A`
+    ///
+    /// A pivot is null if `|pivot| ≤ tol · max|aij|` (this is MUMPS' `CNTL(3)`)
+    pub null_pivot_tolerance: f64,
+
     /// Show additional messages
     pub verbose: bool,
 }
@@ -67,6 +176,29 @@ impl LinSolParams {
             mumps_num_threads: 0,
             mumps_override_prevent_nt_issue_with_openblas: false,
             umfpack_enforce_unsymmetric_strategy: false,
+            cholmod_supernodal: None,
+            native_pivot_threshold: 0.1,
+            krylov_rel_tol: 1e-10,
+            krylov_abs_tol: 0.0,
+            krylov_max_iterations: 1000,
+            krylov_gmres_restart: 30,
+            krylov_nonzero_initial_guess: false,
+            krylov_precond: Precond::No,
+            krylov_precond_reuse: false,
+            krylov_num_threads: 1,
+            precond_ssor_omega: 1.0,
+            precond_ilut_drop_tol: 1e-4,
+            precond_ilut_fill: 10,
+            precond_ic0_shift: 0.0,
+            precond_amg_strength: 0.08,
+            precond_amg_max_levels: 10,
+            precond_amg_coarse_size: 50,
+            precond_amg_sweeps: 1,
+            refinement_max_iterations: 0,
+            refinement_tolerance: 1e-15,
+            mixed_precision: false,
+            null_pivot_detection: false,
+            null_pivot_tolerance: 1e-10,
             verbose: false,
         }
     }
@@ -77,7 +209,7 @@ impl LinSolParams {
 #[cfg(test)]
 mod tests {
     use super::LinSolParams;
-    use crate::{Ordering, Scaling};
+    use crate::{Ordering, Precond, Scaling};
 
     #[test]
     fn clone_copy_and_debug_work() {
@@ -99,5 +231,28 @@ mod tests {
         assert_eq!(params.mumps_max_work_memory, 0);
         assert_eq!(params.mumps_num_threads, 0);
         assert!(!params.umfpack_enforce_unsymmetric_strategy);
+        assert_eq!(params.cholmod_supernodal, None);
+        assert_eq!(params.native_pivot_threshold, 0.1);
+        assert_eq!(params.krylov_rel_tol, 1e-10);
+        assert_eq!(params.krylov_abs_tol, 0.0);
+        assert_eq!(params.krylov_max_iterations, 1000);
+        assert_eq!(params.krylov_gmres_restart, 30);
+        assert!(!params.krylov_nonzero_initial_guess);
+        assert_eq!(params.krylov_precond, Precond::No);
+        assert!(!params.krylov_precond_reuse);
+        assert_eq!(params.krylov_num_threads, 1);
+        assert_eq!(params.precond_ssor_omega, 1.0);
+        assert_eq!(params.precond_ilut_drop_tol, 1e-4);
+        assert_eq!(params.precond_ilut_fill, 10);
+        assert_eq!(params.precond_ic0_shift, 0.0);
+        assert_eq!(params.precond_amg_strength, 0.08);
+        assert_eq!(params.precond_amg_max_levels, 10);
+        assert_eq!(params.precond_amg_coarse_size, 50);
+        assert_eq!(params.precond_amg_sweeps, 1);
+        assert_eq!(params.refinement_max_iterations, 0);
+        assert_eq!(params.refinement_tolerance, 1e-15);
+        assert!(!params.mixed_precision);
+        assert!(!params.null_pivot_detection);
+        assert_eq!(params.null_pivot_tolerance, 1e-10);
     }
 }
@@ -0,0 +1,214 @@
+use super::{FactorScalar, LinSolParams, NumSparseMatrix, StatsLinSolRefinement};
+use crate::StrError;
+use russell_lab::NumVector;
+
+/// Performs the iterative refinement of the solution of `A · x = rhs`
+///
+/// Each iteration computes the residual `r = rhs - A · x` in double precision, solves `A · d = r`
+/// (reusing the factorization via `solve`), and updates `x ← x + d`. The iterations stop when the
+/// normwise backward error
+///
+/// ```text
+///         ‖rhs - A · x‖∞
+/// ω = ─────────────────────
+///     ‖A‖∞ · ‖x‖∞ + ‖rhs‖∞
+/// ```
+///
+/// is smaller than or equal to [LinSolParams::refinement_tolerance], or when ω does not decrease
+/// (stagnation). In the latter case, the last update is rejected.
+///
+/// # Input/Output
+///
+/// * `x` -- the solution computed by the solver (input) and the refined solution (output)
+///
+/// # Input
+///
+/// * `mat` -- the coefficient matrix A
+/// * `rhs` -- the right-hand side vector
+/// * `params` -- the refinement parameters
+/// * `stats` -- the refinement statistics (will be overwritten)
+/// * `solve` -- solves `A · d = r` using the existing factorization (or preconditioner)
+pub(crate) fn refine_solution<T, F>(
+    x: &mut NumVector<T>,
+    mat: &NumSparseMatrix<T>,
+    rhs: &NumVector<T>,
+    params: &LinSolParams,
+    stats: &mut StatsLinSolRefinement,
+    mut solve: F,
+) -> Result<(), StrError>
+where
+    T: FactorScalar,
+    F: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+{
+    // norms and residual of the initial solution
+    let n = x.dim();
+    let norm_a = mat_norm_inf(mat);
+    let norm_rhs = vec_norm_inf(rhs);
+    let mut r = NumVector::<T>::new(rhs.dim());
+    let mut omega = backward_error(&mut r, x, mat, rhs, norm_a, norm_rhs)?;
+    stats.converged = false;
+    stats.iterations = 0;
+    stats.tolerance = params.refinement_tolerance;
+    stats.history = vec![omega];
+
+    // iterations
+    let mut d = NumVector::<T>::new(n);
+    let mut x_new = NumVector::<T>::new(n);
+    let mut r_new = NumVector::<T>::new(rhs.dim());
+    while omega > params.refinement_tolerance && stats.iterations < params.refinement_max_iterations {
+        d.fill(T::zero());
+        solve(&mut d, &r)?;
+        for i in 0..n {
+            x_new[i] = x[i] + d[i];
+        }
+        let omega_new = backward_error(&mut r_new, &x_new, mat, rhs, norm_a, norm_rhs)?;
+        if omega_new.is_nan() || omega_new >= omega {
+            break; // stagnation (the update is rejected)
+        }
+        std::mem::swap(x, &mut x_new);
+        std::mem::swap(&mut r, &mut r_new);
+        omega = omega_new;
+        stats.iterations += 1;
+        stats.history.push(omega);
+    }
+    stats.converged = omega <= params.refinement_tolerance;
+    stats.backward_error = omega;
+    Ok(())
+}
+
+/// Computes the residual `r = rhs - A · x` and returns the normwise backward error
+fn backward_error<T: FactorScalar>(
+    r: &mut NumVector<T>,
+    x: &NumVector<T>,
+    mat: &NumSparseMatrix<T>,
+    rhs: &NumVector<T>,
+    norm_a: f64,
+    norm_rhs: f64,
+) -> Result<f64, StrError> {
+    mat.mat_vec_mul(r, T::one(), x)?;
+    for i in 0..r.dim() {
+        r[i] = rhs[i] - r[i];
+    }
+    let den = norm_a * vec_norm_inf(x) + norm_rhs;
+    let num = vec_norm_inf(r);
+    if den > 0.0 {
+        Ok(num / den)
+    } else {
+        Ok(num)
+    }
+}
+
+/// Returns the max modulus of the components of a vector
+fn vec_norm_inf<T: FactorScalar>(u: &NumVector<T>) -> f64 {
+    u.as_data().iter().fold(0.0, |acc, v| f64::max(acc, v.modulus()))
+}
+
+/// Returns the infinity norm (max row sum of moduli) of a sparse matrix
+///
+/// **Note:** The other triangle of symmetric matrices stored as triangular is also accounted for.
+fn mat_norm_inf<T: FactorScalar>(mat: &NumSparseMatrix<T>) -> f64 {
+    let (nrow, _, _, sym) = mat.get_info();
+    let mirror = sym.triangular();
+    let mut sums = vec![0.0; nrow];
+    let mut add = |i: usize, j: usize, aij: T| {
+        sums[i] += aij.modulus();
+        if mirror && i != j {
+            sums[j] += aij.modulus();
+        }
+    };
+    if let Ok(csc) = mat.get_csc() {
+        for j in 0..csc.ncol {
+            for p in (csc.col_pointers[j] as usize)..(csc.col_pointers[j + 1] as usize) {
+                add(csc.row_indices[p] as usize, j, csc.values[p]);
+            }
+        }
+    } else if let Ok(csr) = mat.get_csr() {
+        for i in 0..csr.nrow {
+            for p in (csr.row_pointers[i] as usize)..(csr.row_pointers[i + 1] as usize) {
+                add(i, csr.col_indices[p] as usize, csr.values[p]);
+            }
+        }
+    } else if let Ok(coo) = mat.get_coo() {
+        for p in 0..coo.nnz {
+            add(coo.indices_i[p] as usize, coo.indices_j[p] as usize, coo.values[p]);
+        }
+    }
+    sums.iter().fold(0.0, |acc, s| f64::max(acc, *s))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{mat_norm_inf, refine_solution};
+    use crate::{Genie, LinSolParams, LinSolver, Samples, SparseMatrix, StatsLinSolRefinement};
+    use russell_lab::{vec_approx_eq, Vector};
+
+    #[test]
+    fn mat_norm_inf_works() {
+        // full storage
+        let (coo, csc, csr, _) = Samples::umfpack_unsymmetric_5x5();
+        let a = coo.as_dense();
+        let mut correct = 0.0;
+        for i in 0..5 {
+            let sum: f64 = (0..5).map(|j| f64::abs(a.get(i, j))).sum();
+            correct = f64::max(correct, sum);
+        }
+        assert_eq!(mat_norm_inf(&SparseMatrix::from_coo(coo)), correct);
+        assert_eq!(mat_norm_inf(&SparseMatrix::from_csc(csc)), correct);
+        assert_eq!(mat_norm_inf(&SparseMatrix::from_csr(csr)), correct);
+
+        // triangular storage
+        let (coo, _, _, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let a = coo.as_dense();
+        let mut correct = 0.0;
+        for i in 0..5 {
+            let sum: f64 = (0..5).map(|j| f64::abs(a.get(i, j))).sum();
+            correct = f64::max(correct, sum);
+        }
+        assert_eq!(mat_norm_inf(&SparseMatrix::from_coo(coo)), correct);
+    }
+
+    #[test]
+    fn refine_solution_works() {
+        // perturbed initial solution corrected by the native solver
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut solver = LinSolver::new(Genie::Native).unwrap();
+        solver.actual.factorize(&mut mat, None).unwrap();
+        let rhs = Vector::from(&[8.0, 45.0, -3.0, 3.0, 19.0]);
+        let x_correct = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut x = Vector::from(&[1.01, 2.0, 2.99, 4.0, 5.02]);
+        let mut params = LinSolParams::new();
+        params.refinement_max_iterations = 5;
+        let mut stats = StatsLinSolRefinement::default();
+        refine_solution(&mut x, &mat, &rhs, &params, &mut stats, |d, r| {
+            solver.actual.solve(d, &mat, r, false)
+        })
+        .unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-14);
+        assert!(stats.converged);
+        assert!(stats.iterations >= 1);
+        assert_eq!(stats.history.len(), stats.iterations + 1);
+        assert!(stats.history[0] > 1e-4);
+        assert_eq!(stats.backward_error, *stats.history.last().unwrap());
+
+        // stagnation: a useless correction is rejected
+        let mut x = Vector::from(&[1.01, 2.0, 2.99, 4.0, 5.02]);
+        refine_solution(&mut x, &mat, &rhs, &params, &mut stats, |d, _| {
+            d.fill(1.0);
+            Ok(())
+        })
+        .unwrap();
+        assert!(!stats.converged);
+        assert_eq!(stats.iterations, 0);
+        assert_eq!(stats.history.len(), 1);
+        assert_eq!(x.as_data(), &[1.01, 2.0, 2.99, 4.0, 5.02]);
+
+        // no iterations requested
+        params.refinement_max_iterations = 0;
+        refine_solution(&mut x, &mat, &rhs, &params, &mut stats, |_, _| Err("unreachable")).unwrap();
+        assert_eq!(stats.iterations, 0);
+        assert_eq!(stats.history.len(), 1);
+    }
+}
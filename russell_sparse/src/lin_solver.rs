@@ -1,10 +1,13 @@
 #[cfg(feature = "with_mumps")]
 use super::SolverMUMPS;
 
-use super::{Genie, LinSolParams, SparseMatrix, StatsLinSol};
-use super::{SolverKLU, SolverUMFPACK};
+#[cfg(feature = "with_suitesparse")]
+use super::{SolverCHOLMOD, SolverKLU, SolverUMFPACK};
+
+use super::{refine_solution, CscMatrix, Genie, LinSolParams, SolverKrylov, SolverNative, SparseMatrix};
+use super::{StatsLinSol, StatsLinSolRefinement, Sym};
 use crate::StrError;
-use russell_lab::Vector;
+use russell_lab::{Matrix, Vector};
 
 /// Defines a unified interface for linear system solvers
 pub trait LinSolTrait: Send {
@@ -26,6 +29,49 @@ pub trait LinSolTrait: Send {
     ///    be "dropped" and a new solver allocated.
     fn factorize(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError>;
 
+    /// Performs the symbolic analysis (e.g., fill-reducing ordering) only
+    ///
+    /// Calling `analyze` followed by `factorize_numeric` is equivalent to calling `factorize` once.
+    /// The symbolic analysis is performed just once; afterwards, the numeric factorization
+    /// may be repeated with matrices having the same sparsity pattern (e.g., in Newton iterations).
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- The sparse matrix (COO, CSC, or CSR). Only the sparsity pattern is analyzed;
+    ///   however, the values should be present since some solvers may use them (e.g., for scaling).
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The default implementation returns an error because not all solvers
+    /// expose the symbolic analysis as a separate step.
+    fn analyze(&mut self, _mat: &mut SparseMatrix, _params: Option<LinSolParams>) -> Result<(), StrError> {
+        Err("the analyze step is not available for this solver")
+    }
+
+    /// Performs the numeric factorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- The sparse matrix with the same sparsity pattern as the one given to `analyze`
+    ///   (the values may differ, nonetheless)
+    ///
+    /// **Note:** The parameters given to `analyze` (or `factorize`) are used.
+    fn factorize_numeric(&mut self, _mat: &mut SparseMatrix) -> Result<(), StrError> {
+        Err("the numeric-only factorization is not available for this solver")
+    }
+
+    /// Performs the numeric refactorization reusing the symbolic analysis and the pivot sequence
+    ///
+    /// This is faster than `factorize_numeric` because no pivot search is performed. However, the
+    /// previous pivot sequence may yield an inaccurate factorization if the values change significantly.
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- The sparse matrix with the same sparsity pattern as the one previously factorized
+    ///   (the values may differ, nonetheless)
+    fn refactorize(&mut self, _mat: &mut SparseMatrix) -> Result<(), StrError> {
+        Err("the refactorization is not available for this solver")
+    }
+
     /// Computes the solution of the linear system
     ///
     /// Solves the linear system:
@@ -48,6 +94,194 @@ pub trait LinSolTrait: Send {
     /// **Warning:** the matrix must be same one used in `factorize`.
     fn solve(&mut self, x: &mut Vector, mat: &SparseMatrix, rhs: &Vector, verbose: bool) -> Result<(), StrError>;
 
+    /// Computes the solution of the transposed linear system (reusing the factorization)
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   Aᵀ  · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (not transposed).
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    ///
+    /// **Note:** The default implementation returns an error; i.e., the solver does not support transpose solves.
+    fn solve_transpose(
+        &mut self,
+        _x: &mut Vector,
+        _mat: &SparseMatrix,
+        _rhs: &Vector,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        Err("the transpose solve is not available for this solver")
+    }
+
+    /// Computes the solution of the linear system with multiple right-hand sides (reusing the factorization)
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   ·  X  = RHS
+    /// (m,m)  (m,k)  (m,k)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the (m,k) matrix of unknown values; each column corresponds to a column of `rhs`
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A.
+    /// * `rhs` -- the (m,k) matrix of right-hand sides
+    /// * `transpose` -- solves `Aᵀ · X = RHS` instead
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    ///
+    /// **Note:** The default implementation calls `solve` (or `solve_transpose`) for each column.
+    fn solve_multiple(
+        &mut self,
+        x: &mut Matrix,
+        mat: &SparseMatrix,
+        rhs: &Matrix,
+        transpose: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        let (m, k) = rhs.dims();
+        if x.dims() != (m, k) {
+            return Err("the dimensions of the matrix of unknown values X are incorrect");
+        }
+        let mut xj = Vector::new(m);
+        let mut bj = Vector::new(m);
+        for j in 0..k {
+            for i in 0..m {
+                bj[i] = rhs.get(i, j);
+            }
+            if transpose {
+                self.solve_transpose(&mut xj, mat, &bj, verbose)?;
+            } else {
+                self.solve(&mut xj, mat, &bj, verbose)?;
+            }
+            for i in 0..m {
+                x.set(i, j, xj[i]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system with a sparse matrix of right-hand sides
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   ·  X  = RHS
+    /// (m,m)  (m,k)  (m,k)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the (m,k) (dense) matrix of unknown values
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A.
+    /// * `rhs` -- the (m,k) sparse matrix of right-hand sides (with [crate::Sym::No])
+    /// * `transpose` -- solves `Aᵀ · X = RHS` instead
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    ///
+    /// **Note:** The default implementation calls `solve` (or `solve_transpose`) for each column;
+    /// thus, only one dense column of the right-hand sides is allocated.
+    fn solve_sparse_rhs(
+        &mut self,
+        x: &mut Matrix,
+        mat: &SparseMatrix,
+        rhs: &CscMatrix,
+        transpose: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        if rhs.symmetric != Sym::No {
+            return Err("the sparse matrix of right-hand sides must have Sym::No");
+        }
+        let (m, k) = (rhs.nrow, rhs.ncol);
+        if x.dims() != (m, k) {
+            return Err("the dimensions of the matrix of unknown values X are incorrect");
+        }
+        let mut xj = Vector::new(m);
+        let mut bj = Vector::new(m);
+        for j in 0..k {
+            bj.fill(0.0);
+            for p in rhs.col_pointers[j]..rhs.col_pointers[j + 1] {
+                bj[rhs.row_indices[p as usize] as usize] += rhs.values[p as usize];
+            }
+            if transpose {
+                self.solve_transpose(&mut xj, mat, &bj, verbose)?;
+            } else {
+                self.solve(&mut xj, mat, &bj, verbose)?;
+            }
+            for i in 0..m {
+                x.set(i, j, xj[i]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an orthonormal basis of the null space of A (after factorize with null pivot detection)
+    ///
+    /// # Output
+    ///
+    /// Returns the (m,d) matrix whose columns span the null space of A, where d is the dimension of the
+    /// null space (d = 0 if A is non-singular).
+    ///
+    /// **Note:** The default implementation returns an error; i.e., the solver does not compute the null space.
+    /// See [LinSolParams::null_pivot_detection]. The native solver and MUMPS (ICNTL(25)) implement this function
+    /// and [LinSolTrait::solve_pseudo_inverse]; however, with MUMPS and unsymmetric matrices, the pseudo-inverse
+    /// solution requires a compatible system because MUMPS does not return the null space of Aᵀ. UMFPACK and
+    /// KLU fail on singular matrices.
+    fn null_space(&self) -> Result<Matrix, StrError> {
+        Err("the null space is not available for this solver")
+    }
+
+    /// Computes the minimum-norm least-squares solution of a (possibly singular) linear system
+    ///
+    /// Computes `x = A⁺ · rhs`, where `A⁺` is the Moore-Penrose pseudo-inverse. Thus, if the system is
+    /// compatible, `x` is the solution orthogonal to the null space of A; otherwise, the component of `rhs`
+    /// orthogonal to the range of A is discarded.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square.
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize` (with null pivot detection).
+    ///
+    /// **Note:** The default implementation returns an error; i.e., the solver does not compute pseudo-inverse solutions.
+    fn solve_pseudo_inverse(
+        &mut self,
+        _x: &mut Vector,
+        _mat: &SparseMatrix,
+        _rhs: &Vector,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        Err("the pseudo-inverse solution is not available for this solver")
+    }
+
     /// Updates the stats structure (should be called after solve)
     fn update_stats(&self, stats: &mut StatsLinSol);
 
@@ -59,12 +293,30 @@ pub trait LinSolTrait: Send {
 
     /// Returns the nanoseconds spent on solve
     fn get_ns_solve(&self) -> u128;
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        0
+    }
+
+    /// Indicates whether the factorization has been computed in single precision or not
+    ///
+    /// **Note:** The default implementation returns false; i.e., [LinSolParams::mixed_precision] is ignored.
+    fn get_mixed_precision(&self) -> bool {
+        false
+    }
 }
 
 /// Unifies the access to linear system solvers
 pub struct LinSolver<'a> {
     /// Holds the actual implementation
     pub actual: Box<dyn Send + LinSolTrait + 'a>,
+
+    /// Holds the parameters given to factorize (used by the iterative refinement)
+    params: LinSolParams,
+
+    /// Holds the statistics of the last iterative refinement
+    refinement: StatsLinSolRefinement,
 }
 
 impl<'a> LinSolver<'a> {
@@ -74,19 +326,99 @@ impl<'a> LinSolver<'a> {
     ///
     /// * `genie` -- the actual implementation that does all the magic
     pub fn new(genie: Genie) -> Result<Self, StrError> {
-        #[cfg(feature = "with_mumps")]
         let actual: Box<dyn Send + LinSolTrait> = match genie {
+            Genie::BiCgStab => Box::new(SolverKrylov::new(genie)?),
+            Genie::Cg => Box::new(SolverKrylov::new(genie)?),
+            #[cfg(feature = "with_suitesparse")]
+            Genie::Cholmod => Box::new(SolverCHOLMOD::new()?),
+            #[cfg(not(feature = "with_suitesparse"))]
+            Genie::Cholmod => return Err("CHOLMOD solver is not available"),
+            Genie::Cocg => Box::new(SolverKrylov::new(genie)?),
+            Genie::Gmres => Box::new(SolverKrylov::new(genie)?),
+            #[cfg(feature = "with_suitesparse")]
             Genie::Klu => Box::new(SolverKLU::new()?),
+            #[cfg(not(feature = "with_suitesparse"))]
+            Genie::Klu => return Err("KLU solver is not available"),
+            Genie::Minres => Box::new(SolverKrylov::new(genie)?),
+            #[cfg(feature = "with_mumps")]
             Genie::Mumps => Box::new(SolverMUMPS::new()?),
-            Genie::Umfpack => Box::new(SolverUMFPACK::new()?),
-        };
-        #[cfg(not(feature = "with_mumps"))]
-        let actual: Box<dyn Send + LinSolTrait> = match genie {
-            Genie::Klu => Box::new(SolverKLU::new()?),
+            #[cfg(not(feature = "with_mumps"))]
             Genie::Mumps => return Err("MUMPS solver is not available"),
+            Genie::Native => Box::new(SolverNative::new()?),
+            #[cfg(feature = "with_suitesparse")]
             Genie::Umfpack => Box::new(SolverUMFPACK::new()?),
+            #[cfg(not(feature = "with_suitesparse"))]
+            Genie::Umfpack => return Err("UMFPACK solver is not available"),
         };
-        Ok(LinSolver { actual })
+        Ok(LinSolver {
+            actual,
+            params: LinSolParams::new(),
+            refinement: StatsLinSolRefinement::default(),
+        })
+    }
+
+    /// Performs the factorization (and analysis/initialization if needed)
+    ///
+    /// This function calls [LinSolTrait::factorize()] and saves the parameters needed by
+    /// the iterative refinement performed by [LinSolver::solve()].
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- The sparse matrix (COO, CSC, or CSR).
+    /// * `params` -- configuration parameters; None => use default
+    pub fn factorize(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if par.refinement_tolerance < 0.0 {
+            return Err("the refinement tolerance must be ≥ 0");
+        }
+        self.actual.factorize(mat, params)?;
+        self.params = par;
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system followed by the iterative refinement (if requested)
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A.
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows messages
+    ///
+    /// # Notes
+    ///
+    /// 1. The refinement is performed if [LinSolParams::refinement_max_iterations] > 0 (given to `factorize`)
+    /// 2. Calling `actual.solve` directly skips the refinement
+    /// 3. The refinement history is saved in [StatsLinSol::refinement] by [LinSolver::update_stats()]
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    pub fn solve(&mut self, x: &mut Vector, mat: &SparseMatrix, rhs: &Vector, verbose: bool) -> Result<(), StrError> {
+        self.actual.solve(x, mat, rhs, verbose)?;
+        self.refinement = StatsLinSolRefinement::default();
+        if self.params.refinement_max_iterations > 0 {
+            let actual = &mut self.actual;
+            refine_solution(x, mat, rhs, &self.params, &mut self.refinement, |d, r| {
+                actual.solve(d, mat, r, verbose)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Updates the stats structure (should be called after solve), including the refinement statistics
+    pub fn update_stats(&self, stats: &mut StatsLinSol) {
+        self.actual.update_stats(stats);
+        stats.refinement = self.refinement.clone();
+        stats.refinement.mixed_precision = self.actual.get_mixed_precision();
     }
 
     /// Computes the solution of a linear system
@@ -113,8 +445,11 @@ impl<'a> LinSolver<'a> {
     ///
     /// 1. For symmetric matrices, `MUMPS` requires [crate::Sym::YesLower]
     /// 2. For symmetric matrices, `UMFPACK` requires [crate::Sym::YesFull]
-    /// 4. This function calls the actual implementation (genie) via the functions `factorize`, and `solve`.
-    /// 5. This function is best for a **single-use**, whereas the actual
+    /// 3. The native solver ([Genie::Native]) accepts any [crate::Sym]; `CHOLMOD` accepts any symmetric representation
+    /// 4. The iterative solvers (e.g., [Genie::Cg]) accept any [crate::Sym] and only need matrix-vector products
+    /// 5. This function calls the actual implementation (genie) via the functions `factorize`, and `solve`,
+    ///    followed by the iterative refinement if [LinSolParams::refinement_max_iterations] > 0.
+    /// 6. This function is best for a **single-use**, whereas the actual
     ///    solver should be considered for a recurrent use (e.g., inside a loop).
     ///
     /// # Examples
@@ -165,9 +500,9 @@ impl<'a> LinSolver<'a> {
         params: Option<LinSolParams>,
     ) -> Result<Self, StrError> {
         let mut solver = LinSolver::new(genie)?;
-        solver.actual.factorize(mat, params)?;
+        solver.factorize(mat, params)?;
         let verbose = if let Some(p) = params { p.verbose } else { false };
-        solver.actual.solve(x, mat, rhs, verbose)?;
+        solver.solve(x, mat, rhs, verbose)?;
         Ok(solver)
     }
 }
@@ -177,13 +512,26 @@ impl<'a> LinSolver<'a> {
 #[cfg(test)]
 mod tests {
     use super::LinSolver;
-    use crate::{Genie, Samples, SparseMatrix};
-    use russell_lab::{vec_approx_eq, Vector};
+    use crate::{CooMatrix, CscMatrix, Genie, LinSolParams, Samples, SparseMatrix, StatsLinSol, Sym};
+    use russell_lab::{mat_approx_eq, vec_approx_eq, Matrix, Vector};
 
     #[cfg(feature = "with_mumps")]
     use serial_test::serial;
 
     #[test]
+    #[cfg(feature = "with_suitesparse")]
+    fn lin_solver_compute_works_cholmod() {
+        let (coo, _, _, _) = Samples::mkl_positive_definite_5x5_lower();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut x = Vector::new(5);
+        let rhs = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        LinSolver::compute(Genie::Cholmod, &mut x, &mut mat, &rhs, None).unwrap();
+        let x_correct = vec![-979.0 / 3.0, 983.0, 1961.0 / 12.0, 398.0, 123.0 / 2.0];
+        vec_approx_eq(&x, &x_correct, 1e-10);
+    }
+
+    #[test]
+    #[cfg(feature = "with_suitesparse")]
     fn lin_solver_compute_works_klu() {
         let (coo, _, _, _) = Samples::mkl_symmetric_5x5_full();
         let mut mat = SparseMatrix::from_coo(coo);
@@ -194,6 +542,19 @@ mod tests {
         vec_approx_eq(&x, &x_correct, 1e-10);
     }
 
+    #[test]
+    fn lin_solver_compute_works_iterative() {
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let rhs = Vector::from(&[8.0, 45.0, -3.0, 3.0, 19.0]);
+        let x_correct = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        for genie in [Genie::BiCgStab, Genie::Gmres] {
+            let mut mat = SparseMatrix::from_coo(coo.clone());
+            let mut x = Vector::new(5);
+            LinSolver::compute(genie, &mut x, &mut mat, &rhs, None).unwrap();
+            vec_approx_eq(&x, &x_correct, 1e-8);
+        }
+    }
+
     #[test]
     #[serial]
     #[cfg(feature = "with_mumps")]
@@ -208,6 +569,91 @@ mod tests {
     }
 
     #[test]
+    fn lin_solver_compute_works_native() {
+        let (coo, _, _, _) = Samples::mkl_symmetric_5x5_lower(true, false);
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut x = Vector::new(5);
+        let rhs = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        LinSolver::compute(Genie::Native, &mut x, &mut mat, &rhs, None).unwrap();
+        let x_correct = vec![-979.0 / 3.0, 983.0, 1961.0 / 12.0, 398.0, 123.0 / 2.0];
+        vec_approx_eq(&x, &x_correct, 1e-10);
+    }
+
+    #[test]
+    fn lin_solver_refinement_works() {
+        // the refinement is skipped by default
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let rhs = Vector::from(&[8.0, 45.0, -3.0, 3.0, 19.0]);
+        let x_correct = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut x = Vector::new(5);
+        let solver = LinSolver::compute(Genie::Native, &mut x, &mut mat, &rhs, None).unwrap();
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.refinement.iterations, 0);
+        assert_eq!(stats.refinement.history.len(), 0);
+
+        // refinement of an inexact (iterative) solution
+        let mut params = LinSolParams::new();
+        params.krylov_rel_tol = 1e-3;
+        params.refinement_max_iterations = 10;
+        let mut x = Vector::new(5);
+        let solver = LinSolver::compute(Genie::BiCgStab, &mut x, &mut mat, &rhs, Some(params)).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-13);
+        solver.update_stats(&mut stats);
+        assert!(stats.refinement.converged);
+        assert!(stats.refinement.iterations > 0);
+        assert_eq!(stats.refinement.history.len(), stats.refinement.iterations + 1);
+        assert!(stats.refinement.history[0] > stats.refinement.backward_error);
+        assert!(!stats.refinement.mixed_precision);
+
+        // errors
+        let mut solver = LinSolver::new(Genie::Native).unwrap();
+        params.refinement_tolerance = -1.0;
+        assert_eq!(
+            solver.factorize(&mut mat, Some(params)).err(),
+            Some("the refinement tolerance must be ≥ 0")
+        );
+    }
+
+    #[test]
+    fn lin_solver_mixed_precision_works() {
+        let (coo, _, _, _) = Samples::mkl_symmetric_5x5_lower(true, false);
+        let mut mat = SparseMatrix::from_coo(coo);
+        let rhs = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let x_correct = vec![-979.0 / 3.0, 983.0, 1961.0 / 12.0, 398.0, 123.0 / 2.0];
+        let mut params = LinSolParams::new();
+        params.mixed_precision = true;
+        params.compute_determinant = true;
+
+        // without refinement, the single-precision solution is inaccurate
+        let mut x = Vector::new(5);
+        LinSolver::compute(Genie::Native, &mut x, &mut mat, &rhs, Some(params)).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-1);
+
+        // the refinement in double precision recovers the accuracy
+        params.refinement_max_iterations = 20;
+        let mut solver = LinSolver::new(Genie::Native).unwrap();
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-10);
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert!(stats.refinement.mixed_precision);
+        assert!(stats.refinement.converged);
+        assert!(stats.refinement.iterations > 0);
+        assert_eq!(stats.main.solver, "Native-LU");
+        assert!(f64::abs(stats.determinant.mantissa_real) > 0.0);
+
+        // the other solvers ignore the mixed-precision flag
+        let mut krylov = LinSolver::new(Genie::BiCgStab).unwrap();
+        krylov.factorize(&mut mat, Some(params)).unwrap();
+        krylov.update_stats(&mut stats);
+        assert!(!stats.refinement.mixed_precision);
+    }
+
+    #[test]
+    #[cfg(feature = "with_suitesparse")]
     fn lin_solver_compute_works_umfpack() {
         let (coo, _, _, _) = Samples::mkl_symmetric_5x5_full();
         let mut mat = SparseMatrix::from_coo(coo);
@@ -217,4 +663,69 @@ mod tests {
         let x_correct = vec![-979.0 / 3.0, 983.0, 1961.0 / 12.0, 398.0, 123.0 / 2.0];
         vec_approx_eq(&x, &x_correct, 1e-10);
     }
+
+    #[test]
+    fn lin_solver_solve_sparse_rhs_works() {
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut solver = LinSolver::new(Genie::Native).unwrap();
+        solver.actual.factorize(&mut mat, None).unwrap();
+
+        // the columns of RHS are [8, 45, -3, 3, 19] (x = [1, 2, 3, 4, 5]) and e₂ (x = A⁻¹ e₂)
+        let mut coo_rhs = CooMatrix::new(5, 2, 6, Sym::No).unwrap();
+        for (i, value) in [8.0, 45.0, -3.0, 3.0, 19.0].iter().enumerate() {
+            coo_rhs.put(i, 0, *value).unwrap();
+        }
+        coo_rhs.put(2, 1, 1.0).unwrap();
+        let rhs = CscMatrix::from_coo(&coo_rhs).unwrap();
+        let mut xx = Matrix::new(5, 2);
+        solver
+            .actual
+            .solve_sparse_rhs(&mut xx, &mat, &rhs, false, false)
+            .unwrap();
+        let mut e2 = Vector::new(5);
+        e2[2] = 1.0;
+        let mut x = Vector::new(5);
+        solver.actual.solve(&mut x, &mat, &e2, false).unwrap();
+        let xx_correct = Matrix::initialized(5, 2, |i, j| if j == 0 { (i + 1) as f64 } else { x[i] });
+        mat_approx_eq(&xx, &xx_correct, 1e-13);
+
+        // errors
+        let (coo_sym, _, _, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let rhs_sym = CscMatrix::from_coo(&coo_sym).unwrap();
+        let mut xx = Matrix::new(5, 5);
+        assert_eq!(
+            solver
+                .actual
+                .solve_sparse_rhs(&mut xx, &mat, &rhs_sym, false, false)
+                .err(),
+            Some("the sparse matrix of right-hand sides must have Sym::No")
+        );
+        let mut krylov = LinSolver::new(Genie::BiCgStab).unwrap();
+        krylov.actual.factorize(&mut mat, None).unwrap();
+        assert_eq!(
+            krylov.actual.solve_transpose(&mut x, &mat, &e2, false).err(),
+            Some("the transpose solve is not available for this solver")
+        );
+    }
+
+    #[test]
+    fn lin_solver_default_analyze_and_refactorize_return_errors() {
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut krylov = LinSolver::new(Genie::BiCgStab).unwrap();
+        assert_eq!(
+            krylov.actual.analyze(&mut mat, None).err(),
+            Some("the analyze step is not available for this solver")
+        );
+        assert_eq!(
+            krylov.actual.factorize_numeric(&mut mat).err(),
+            Some("the numeric-only factorization is not available for this solver")
+        );
+        assert_eq!(
+            krylov.actual.refactorize(&mut mat).err(),
+            Some("the refactorization is not available for this solver")
+        );
+        assert_eq!(krylov.actual.get_ns_refact(), 0);
+    }
 }
@@ -0,0 +1,91 @@
+use super::{LsqMethod, Ordering};
+
+/// Defines the configuration parameters for the sparse least-squares solver
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LsqParams {
+    /// Defines the method
+    pub method: LsqMethod,
+
+    /// Defines the column ordering of the sparse QR factorization (SPQR only)
+    pub ordering: Ordering,
+
+    /// Defines the tolerance to detect the (numerically) null columns of R, i.e., the rank (SPQR only)
+    ///
+    /// **Note:** If None, SPQR uses its default `20·(m + n)·ε·max‖A(:,j)‖`.
+    pub rank_tolerance: Option<f64>,
+
+    /// Defines the relative accuracy of A (iterative methods only)
+    ///
+    /// **Note:** The least-squares problem is converged if `‖Aᵀ·r‖ ≤ atol·‖A‖·‖r‖`.
+    pub atol: f64,
+
+    /// Defines the relative accuracy of b (iterative methods only)
+    ///
+    /// **Note:** The compatible problem is converged if `‖r‖ ≤ btol·‖b‖ + atol·‖A‖·‖x‖`.
+    pub btol: f64,
+
+    /// Defines the limit of the condition number estimate of A (iterative methods only)
+    pub conlim: f64,
+
+    /// Defines the maximum number of iterations (iterative methods only)
+    ///
+    /// **Note:** Set this value to 0 for an automatic configuration: `4·max(m, n)`.
+    pub max_iterations: usize,
+
+    /// Defines the damping (Tikhonov regularization) factor (iterative methods only)
+    ///
+    /// **Note:** If positive, `‖A·x - b‖² + damp²·‖x‖²` is minimized.
+    pub damp: f64,
+
+    /// Show additional messages
+    pub verbose: bool,
+}
+
+impl LsqParams {
+    /// Allocates a new instance with default values
+    pub fn new() -> Self {
+        LsqParams {
+            method: LsqMethod::Lsmr,
+            ordering: Ordering::Auto,
+            rank_tolerance: None,
+            atol: 1e-10,
+            btol: 1e-10,
+            conlim: 1e8,
+            max_iterations: 0,
+            damp: 0.0,
+            verbose: false,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::LsqParams;
+    use crate::{LsqMethod, Ordering};
+
+    #[test]
+    fn clone_copy_and_debug_work() {
+        let params = LsqParams::new();
+        let copy = params;
+        let clone = params.clone();
+        assert!(format!("{:?}", params).len() > 0);
+        assert_eq!(copy, params);
+        assert_eq!(clone, params);
+    }
+
+    #[test]
+    fn lsq_params_new_works() {
+        let params = LsqParams::new();
+        assert_eq!(params.method, LsqMethod::Lsmr);
+        assert_eq!(params.ordering, Ordering::Auto);
+        assert_eq!(params.rank_tolerance, None);
+        assert_eq!(params.atol, 1e-10);
+        assert_eq!(params.btol, 1e-10);
+        assert_eq!(params.conlim, 1e8);
+        assert_eq!(params.max_iterations, 0);
+        assert_eq!(params.damp, 0.0);
+        assert!(!params.verbose);
+    }
+}
@@ -0,0 +1,806 @@
+#[cfg(feature = "with_suitesparse")]
+use super::SolverSPQR;
+use super::{LsqMethod, LsqParams, SparseMatrix};
+use crate::StrError;
+use russell_lab::{vec_norm, LinearOperator, Norm, Stopwatch, Vector};
+
+/// Implements solvers for sparse least-squares problems
+///
+/// Solves the (possibly overdetermined, underdetermined, or rank-deficient) linear system:
+///
+/// ```text
+///   A   · x = b    in the least-squares sense, i.e.,  min ‖A·x - b‖
+/// (m,n)  (n)  (m)
+/// ```
+///
+/// The available methods are (see [LsqParams::method]):
+///
+/// * [LsqMethod::Spqr] -- sparse QR factorization via [SolverSPQR] (requires the `with_suitesparse` feature);
+///   the least-squares solution is computed if m ≥ n and the minimum-norm solution if m < n;
+///   the rank is estimated (see [LsqSolver::get_rank()])
+/// * [LsqMethod::Lsqr] -- the iterative LSQR method (Paige and Saunders)
+/// * [LsqMethod::Lsmr] -- the iterative LSMR method (Fong and Saunders); `‖Aᵀ·r‖` decreases monotonically,
+///   thus LSMR may be stopped earlier than LSQR
+///
+/// The iterative methods only need the products with A and Aᵀ; thus, they can also be used with
+/// matrix-free operators (see [LsqSolver::solve_with_linear_operator()]). Starting from `x = 0`, they
+/// converge to the minimum-norm least-squares solution. Optionally, the damped (Tikhonov-regularized)
+/// problem `min ‖A·x - b‖² + damp²·‖x‖²` is solved (see [LsqParams::damp]).
+///
+/// # Notes
+///
+/// 1. An error is returned if the iterative method does not converge within the maximum number of iterations
+///    or if the condition number estimate exceeds [LsqParams::conlim]; nonetheless, the (approximate)
+///    solution remains available in `x`.
+/// 2. After `solve`, the norms of the residual `r = b - A·x` and of `Aᵀ·r` are available via
+///    [LsqSolver::get_residual_norm()] and [LsqSolver::get_normal_residual_norm()].
+///
+/// # References
+///
+/// 1. Paige CC, Saunders MA (1982) LSQR: An algorithm for sparse linear equations and sparse least squares,
+///    ACM Transactions on Mathematical Software, 8(1):43-71
+/// 2. Fong DCL, Saunders MA (2011) LSMR: An iterative algorithm for sparse least-squares problems,
+///    SIAM Journal on Scientific Computing, 33(5):2950-2971
+pub struct LsqSolver {
+    /// Holds the parameters
+    params: LsqParams,
+
+    /// Holds the SPQR solver
+    #[cfg(feature = "with_suitesparse")]
+    spqr: Option<SolverSPQR>,
+
+    /// Holds the number of iterations of the last solve (iterative methods)
+    iterations: usize,
+
+    /// Holds the norm of the residual r = b - A·x (after solve)
+    residual_norm: f64,
+
+    /// Holds the norm of Aᵀ·r (after solve)
+    normal_residual_norm: f64,
+
+    /// Holds the estimate of the Frobenius norm of A (iterative methods)
+    norm_a_estimate: f64,
+
+    /// Holds the estimate of the condition number of A (iterative methods)
+    cond_a_estimate: f64,
+
+    /// Stopwatch to measure computation times
+    stopwatch: Stopwatch,
+
+    /// Time spent on solve in nanoseconds
+    time_solve_ns: u128,
+}
+
+impl LsqSolver {
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `params` -- configuration parameters; None => use default
+    pub fn new(params: Option<LsqParams>) -> Result<Self, StrError> {
+        let par = if let Some(p) = params { p } else { LsqParams::new() };
+        if par.atol < 0.0 || par.btol < 0.0 {
+            return Err("the tolerances atol and btol must be ≥ 0");
+        }
+        if par.damp < 0.0 {
+            return Err("the damping factor must be ≥ 0");
+        }
+        if par.conlim <= 1.0 {
+            return Err("the condition number limit must be > 1");
+        }
+        #[cfg(not(feature = "with_suitesparse"))]
+        if par.method == LsqMethod::Spqr {
+            return Err("SPQR solver is not available");
+        }
+        Ok(LsqSolver {
+            params: par,
+            #[cfg(feature = "with_suitesparse")]
+            spqr: None,
+            iterations: 0,
+            residual_norm: 0.0,
+            normal_residual_norm: 0.0,
+            norm_a_estimate: 0.0,
+            cond_a_estimate: 0.0,
+            stopwatch: Stopwatch::new(),
+            time_solve_ns: 0,
+        })
+    }
+
+    /// Performs the factorization (SPQR only; the iterative methods do nothing)
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the (m,n) coefficient matrix A (**COO** or **CSC** for SPQR)
+    ///
+    /// **Note:** `solve` calls `factorize` if SPQR has not factorized the matrix yet.
+    pub fn factorize(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        #[cfg(feature = "with_suitesparse")]
+        if self.params.method == LsqMethod::Spqr {
+            let mut spqr = SolverSPQR::new()?;
+            spqr.factorize(mat, Some(self.params))?;
+            self.spqr = Some(spqr);
+        }
+        #[cfg(not(feature = "with_suitesparse"))]
+        let _ = mat;
+        Ok(())
+    }
+
+    /// Computes the least-squares (or minimum-norm) solution
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to n (ncol). With the iterative
+    ///   methods, the initial values are ignored (the iterations start with `x = 0`).
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the (m,n) coefficient matrix A
+    /// * `rhs` -- the right-hand side vector with dimension equal to m (nrow)
+    pub fn solve(&mut self, x: &mut Vector, mat: &mut SparseMatrix, rhs: &Vector) -> Result<(), StrError> {
+        let (nrow, ncol, _, _) = mat.get_info();
+        if x.dim() != ncol {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.dim() != nrow {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+        if self.params.method.is_iterative() {
+            return self.solve_with_linear_operator(x, rhs, mat);
+        }
+        #[cfg(feature = "with_suitesparse")]
+        {
+            if self.spqr.is_none() {
+                self.factorize(mat)?;
+            }
+            self.stopwatch.reset();
+            self.spqr.as_mut().unwrap().solve(x, rhs)?; // unwrap OK because factorize has been called
+            self.time_solve_ns = self.stopwatch.stop();
+            self.iterations = 0;
+            self.compute_residual_norms(x, rhs, mat)
+        }
+        #[cfg(not(feature = "with_suitesparse"))]
+        Err("SPQR solver is not available")
+    }
+
+    /// Computes the least-squares (or minimum-norm) solution using a linear operator (iterative methods only)
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to n (ncol)
+    ///
+    /// # Input
+    ///
+    /// * `rhs` -- the right-hand side vector with dimension equal to m (nrow)
+    /// * `op` -- the (m,n) operator A (e.g., a sparse matrix or a combination of operators)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_lab::{vec_approx_eq, Vector};
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // fit a line y = c₀ + c₁·t to the points (0, 1), (1, 3), (2, 5), (3, 7)
+    ///     let mut coo = CooMatrix::new(4, 2, 8, Sym::No)?;
+    ///     for i in 0..4 {
+    ///         coo.put(i, 0, 1.0)?;
+    ///         coo.put(i, 1, i as f64)?;
+    ///     }
+    ///     let rhs = Vector::from(&[1.0, 3.0, 5.0, 7.0]);
+    ///     let mut x = Vector::new(2);
+    ///     let mut solver = LsqSolver::new(None)?;
+    ///     solver.solve_with_linear_operator(&mut x, &rhs, &coo)?;
+    ///     vec_approx_eq(&x, &[1.0, 2.0], 1e-10);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn solve_with_linear_operator(
+        &mut self,
+        x: &mut Vector,
+        rhs: &Vector,
+        op: &dyn LinearOperator,
+    ) -> Result<(), StrError> {
+        let (nrow, ncol) = op.dims();
+        if x.dim() != ncol {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.dim() != nrow {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+        let max_iterations = if self.params.max_iterations == 0 {
+            4 * usize::max(nrow, ncol)
+        } else {
+            self.params.max_iterations
+        };
+        self.stopwatch.reset();
+        let res = match self.params.method {
+            LsqMethod::Lsqr => self.lsqr(x, rhs, op, max_iterations),
+            LsqMethod::Lsmr => self.lsmr(x, rhs, op, max_iterations),
+            LsqMethod::Spqr => return Err("the linear operator requires an iterative method (Lsqr or Lsmr)"),
+        };
+        self.time_solve_ns = self.stopwatch.stop();
+        self.compute_residual_norms(x, rhs, op)?;
+        res
+    }
+
+    /// Returns the rank estimate (SPQR only; after factorize or solve)
+    pub fn get_rank(&self) -> Option<usize> {
+        #[cfg(feature = "with_suitesparse")]
+        if let Some(spqr) = &self.spqr {
+            return Some(spqr.get_rank());
+        }
+        None
+    }
+
+    /// Returns the number of iterations of the last solve (iterative methods)
+    pub fn get_iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the norm of the residual `‖b - A·x‖` (after solve)
+    pub fn get_residual_norm(&self) -> f64 {
+        self.residual_norm
+    }
+
+    /// Returns the norm `‖Aᵀ·(b - A·x)‖`, which is zero at the least-squares solution (after solve)
+    pub fn get_normal_residual_norm(&self) -> f64 {
+        self.normal_residual_norm
+    }
+
+    /// Returns the estimates of the Frobenius norm and of the condition number of A (iterative methods)
+    pub fn get_norm_and_cond_estimates(&self) -> (f64, f64) {
+        (self.norm_a_estimate, self.cond_a_estimate)
+    }
+
+    /// Returns the nanoseconds spent on solve
+    pub fn get_ns_solve(&self) -> u128 {
+        self.time_solve_ns
+    }
+
+    /// Computes the norms of the residual r = b - A·x and of Aᵀ·r
+    fn compute_residual_norms(&mut self, x: &Vector, rhs: &Vector, op: &dyn LinearOperator) -> Result<(), StrError> {
+        let (nrow, ncol) = op.dims();
+        let mut r = Vector::new(nrow);
+        op.apply(&mut r, x)?;
+        for i in 0..nrow {
+            r[i] = rhs[i] - r[i];
+        }
+        let mut atr = Vector::new(ncol);
+        op.apply_transpose(&mut atr, &r)?;
+        self.residual_norm = vec_norm(&r, Norm::Euc);
+        self.normal_residual_norm = vec_norm(&atr, Norm::Euc);
+        Ok(())
+    }
+
+    /// Runs the LSQR method
+    fn lsqr(
+        &mut self,
+        x: &mut Vector,
+        b: &Vector,
+        op: &dyn LinearOperator,
+        max_iterations: usize,
+    ) -> Result<(), StrError> {
+        let (m, n) = op.dims();
+        let (atol, btol, damp) = (self.params.atol, self.params.btol, self.params.damp);
+        let ctol = 1.0 / self.params.conlim;
+        x.fill(0.0);
+        self.iterations = 0;
+
+        // bidiagonalization: β·u = b and α·v = Aᵀ·u
+        let mut u = b.clone();
+        let mut beta = vec_norm(&u, Norm::Euc);
+        let bnorm = beta;
+        if beta > 0.0 {
+            scale(&mut u, 1.0 / beta);
+        }
+        let mut v = Vector::new(n);
+        op.apply_transpose(&mut v, &u)?;
+        let mut alpha = vec_norm(&v, Norm::Euc);
+        if alpha > 0.0 {
+            scale(&mut v, 1.0 / alpha);
+        }
+        if alpha * beta == 0.0 {
+            return Ok(()); // x = 0 is the solution
+        }
+        let mut w = v.clone();
+        let mut au = Vector::new(m);
+        let mut atv = Vector::new(n);
+
+        // auxiliary variables
+        let (mut rhobar, mut phibar) = (alpha, beta);
+        let (mut anorm, mut ddnorm, mut res2, mut xxnorm, mut z) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        let (mut cs2, mut sn2) = (-1.0, 0.0);
+
+        // iterations
+        for iteration in 0..max_iterations {
+            self.iterations = iteration + 1;
+
+            // continue the bidiagonalization
+            op.apply(&mut au, &v)?;
+            for i in 0..m {
+                u[i] = au[i] - alpha * u[i];
+            }
+            beta = vec_norm(&u, Norm::Euc);
+            if beta > 0.0 {
+                scale(&mut u, 1.0 / beta);
+                anorm = f64::sqrt(anorm * anorm + alpha * alpha + beta * beta + damp * damp);
+                op.apply_transpose(&mut atv, &u)?;
+                for j in 0..n {
+                    v[j] = atv[j] - beta * v[j];
+                }
+                alpha = vec_norm(&v, Norm::Euc);
+                if alpha > 0.0 {
+                    scale(&mut v, 1.0 / alpha);
+                }
+            }
+
+            // eliminate the damping parameter
+            let (rhobar1, psi) = if damp > 0.0 {
+                let rhobar1 = f64::hypot(rhobar, damp);
+                let (cs1, sn1) = (rhobar / rhobar1, damp / rhobar1);
+                let psi = sn1 * phibar;
+                phibar *= cs1;
+                (rhobar1, psi)
+            } else {
+                (rhobar, 0.0)
+            };
+
+            // plane rotation to eliminate the subdiagonal element β
+            let (cs, sn, rho) = sym_ortho(rhobar1, beta);
+            let theta = sn * alpha;
+            rhobar = -cs * alpha;
+            let phi = cs * phibar;
+            phibar *= sn;
+            let tau = sn * phi;
+
+            // update x and w
+            let t1 = phi / rho;
+            let t2 = -theta / rho;
+            let mut dknorm2 = 0.0;
+            for j in 0..n {
+                dknorm2 += (w[j] / rho) * (w[j] / rho);
+                x[j] += t1 * w[j];
+                w[j] = v[j] + t2 * w[j];
+            }
+            ddnorm += dknorm2;
+
+            // estimate ‖x‖ using the plane rotation on the right
+            let delta = sn2 * rho;
+            let gambar = -cs2 * rho;
+            let rhs = phi - delta * z;
+            let zbar = rhs / gambar;
+            let xnorm = f64::sqrt(xxnorm + zbar * zbar);
+            let gamma = f64::hypot(gambar, theta);
+            cs2 = gambar / gamma;
+            sn2 = theta / gamma;
+            z = rhs / gamma;
+            xxnorm += z * z;
+
+            // estimate cond(A), ‖r‖, and ‖Aᵀ·r‖
+            let acond = anorm * f64::sqrt(ddnorm);
+            res2 += psi * psi;
+            let rnorm = f64::sqrt(phibar * phibar + res2);
+            let arnorm = alpha * f64::abs(tau);
+            self.norm_a_estimate = anorm;
+            self.cond_a_estimate = acond;
+
+            // check convergence
+            let test1 = rnorm / bnorm;
+            let test2 = arnorm / (anorm * rnorm + f64::EPSILON);
+            let test3 = 1.0 / (acond + f64::EPSILON);
+            let rtol = btol + atol * anorm * xnorm / bnorm;
+            if self.params.verbose {
+                println!(
+                    "LSQR: iteration = {:>5}, ‖r‖ = {:.6e}, ‖Aᵀ·r‖ = {:.6e}, cond(A) ≈ {:.3e}",
+                    self.iterations, rnorm, arnorm, acond
+                );
+            }
+            if test1 <= rtol || test2 <= atol {
+                return Ok(());
+            }
+            if test3 <= ctol {
+                return Err("the condition number estimate of A exceeds the limit (conlim)");
+            }
+        }
+        Err("LSQR did not converge within the maximum number of iterations")
+    }
+
+    /// Runs the LSMR method
+    fn lsmr(
+        &mut self,
+        x: &mut Vector,
+        b: &Vector,
+        op: &dyn LinearOperator,
+        max_iterations: usize,
+    ) -> Result<(), StrError> {
+        let (m, n) = op.dims();
+        let (atol, btol, damp) = (self.params.atol, self.params.btol, self.params.damp);
+        let ctol = 1.0 / self.params.conlim;
+        x.fill(0.0);
+        self.iterations = 0;
+
+        // bidiagonalization: β·u = b and α·v = Aᵀ·u
+        let mut u = b.clone();
+        let normb = vec_norm(&u, Norm::Euc);
+        let mut beta = normb;
+        if beta > 0.0 {
+            scale(&mut u, 1.0 / beta);
+        }
+        let mut v = Vector::new(n);
+        op.apply_transpose(&mut v, &u)?;
+        let mut alpha = vec_norm(&v, Norm::Euc);
+        if alpha > 0.0 {
+            scale(&mut v, 1.0 / alpha);
+        }
+        if alpha * beta == 0.0 {
+            return Ok(()); // x = 0 is the solution
+        }
+        let mut au = Vector::new(m);
+        let mut atv = Vector::new(n);
+
+        // auxiliary variables
+        let (mut zetabar, mut alphabar) = (alpha * beta, alpha);
+        let (mut rho, mut rhobar, mut cbar, mut sbar) = (1.0, 1.0, 1.0, 0.0);
+        let mut h = v.clone();
+        let mut hbar = Vector::new(n);
+
+        // variables for the estimation of ‖r‖
+        let (mut betadd, mut betad, mut rhodold) = (beta, 0.0, 1.0);
+        let (mut tautildeold, mut thetatilde, mut zeta, mut d) = (0.0, 0.0, 0.0, 0.0);
+
+        // variables for the estimation of ‖A‖ and cond(A)
+        let mut norm_a2 = alpha * alpha;
+        let (mut maxrbar, mut minrbar) = (0.0, f64::MAX);
+
+        // iterations
+        for iteration in 0..max_iterations {
+            self.iterations = iteration + 1;
+
+            // continue the bidiagonalization
+            op.apply(&mut au, &v)?;
+            for i in 0..m {
+                u[i] = au[i] - alpha * u[i];
+            }
+            beta = vec_norm(&u, Norm::Euc);
+            if beta > 0.0 {
+                scale(&mut u, 1.0 / beta);
+                op.apply_transpose(&mut atv, &u)?;
+                for j in 0..n {
+                    v[j] = atv[j] - beta * v[j];
+                }
+                alpha = vec_norm(&v, Norm::Euc);
+                if alpha > 0.0 {
+                    scale(&mut v, 1.0 / alpha);
+                }
+            }
+
+            // construct the rotation P̂ (eliminates the damping parameter)
+            let (chat, shat, alphahat) = sym_ortho(alphabar, damp);
+
+            // construct and apply the rotation P
+            let rhoold = rho;
+            let (c, s, rho_new) = sym_ortho(alphahat, beta);
+            rho = rho_new;
+            let thetanew = s * alpha;
+            alphabar = c * alpha;
+
+            // construct and apply the rotation P̄
+            let rhobarold = rhobar;
+            let zetaold = zeta;
+            let thetabar = sbar * rho;
+            let rhotemp = cbar * rho;
+            let (cbar_new, sbar_new, rhobar_new) = sym_ortho(cbar * rho, thetanew);
+            (cbar, sbar, rhobar) = (cbar_new, sbar_new, rhobar_new);
+            zeta = cbar * zetabar;
+            zetabar *= -sbar;
+
+            // update h, hbar, and x
+            let fhbar = thetabar * rho / (rhoold * rhobarold);
+            let fx = zeta / (rho * rhobar);
+            let fh = thetanew / rho;
+            for j in 0..n {
+                hbar[j] = h[j] - fhbar * hbar[j];
+                x[j] += fx * hbar[j];
+                h[j] = v[j] - fh * h[j];
+            }
+
+            // estimate ‖r‖
+            let betaacute = chat * betadd;
+            let betacheck = -shat * betadd;
+            let betahat = c * betaacute;
+            betadd = -s * betaacute;
+            let thetatildeold = thetatilde;
+            let (ctildeold, stildeold, rhotildeold) = sym_ortho(rhodold, thetabar);
+            thetatilde = stildeold * rhobar;
+            rhodold = ctildeold * rhobar;
+            betad = -stildeold * betad + ctildeold * betahat;
+            tautildeold = (zetaold - thetatildeold * tautildeold) / rhotildeold;
+            let taud = (zeta - thetatilde * tautildeold) / rhodold;
+            d += betacheck * betacheck;
+            let normr = f64::sqrt(d + (betad - taud) * (betad - taud) + betadd * betadd);
+
+            // estimate ‖A‖ and cond(A)
+            norm_a2 += beta * beta;
+            let norm_a = f64::sqrt(norm_a2);
+            norm_a2 += alpha * alpha;
+            maxrbar = f64::max(maxrbar, rhobarold);
+            if iteration > 0 {
+                minrbar = f64::min(minrbar, rhobarold);
+            }
+            let cond_a = f64::max(maxrbar, rhotemp) / f64::min(minrbar, rhotemp);
+            self.norm_a_estimate = norm_a;
+            self.cond_a_estimate = cond_a;
+
+            // check convergence
+            let normar = f64::abs(zetabar);
+            let normx = vec_norm(x, Norm::Euc);
+            let test1 = normr / normb;
+            let test2 = if norm_a * normr != 0.0 {
+                normar / (norm_a * normr)
+            } else {
+                f64::INFINITY
+            };
+            let test3 = 1.0 / cond_a;
+            let rtol = btol + atol * norm_a * normx / normb;
+            if self.params.verbose {
+                println!(
+                    "LSMR: iteration = {:>5}, ‖r‖ = {:.6e}, ‖Aᵀ·r‖ = {:.6e}, cond(A) ≈ {:.3e}",
+                    self.iterations, normr, normar, cond_a
+                );
+            }
+            if test1 <= rtol || test2 <= atol || normar == 0.0 {
+                return Ok(());
+            }
+            if test3 <= ctol {
+                return Err("the condition number estimate of A exceeds the limit (conlim)");
+            }
+        }
+        Err("LSMR did not converge within the maximum number of iterations")
+    }
+}
+
+/// Scales a vector in place
+fn scale(v: &mut Vector, alpha: f64) {
+    for value in v.as_mut_data().iter_mut() {
+        *value *= alpha;
+    }
+}
+
+/// Computes a stable Givens rotation `(c, s, r)` such that `[c s; -s c]·[a; b] = [r; 0]`
+fn sym_ortho(a: f64, b: f64) -> (f64, f64, f64) {
+    if b == 0.0 {
+        (if a < 0.0 { -1.0 } else { 1.0 }, 0.0, f64::abs(a))
+    } else if a == 0.0 {
+        (0.0, f64::signum(b), f64::abs(b))
+    } else if f64::abs(b) > f64::abs(a) {
+        let tau = a / b;
+        let s = f64::signum(b) / f64::sqrt(1.0 + tau * tau);
+        let c = s * tau;
+        (c, s, b / s)
+    } else {
+        let tau = b / a;
+        let c = f64::signum(a) / f64::sqrt(1.0 + tau * tau);
+        let s = c * tau;
+        (c, s, a / c)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CooMatrix, Sym};
+    use russell_lab::{approx_eq, vec_approx_eq};
+
+    /// Returns the (6,3) matrix of a polynomial fit and the right-hand side
+    fn polynomial_fit() -> (SparseMatrix, Vector) {
+        let mut coo = CooMatrix::new(6, 3, 18, Sym::No).unwrap();
+        for i in 0..6 {
+            let t = i as f64;
+            coo.put(i, 0, 1.0).unwrap();
+            coo.put(i, 1, t).unwrap();
+            coo.put(i, 2, t * t).unwrap();
+        }
+        let rhs = Vector::from(&[1.0, 2.2, 4.8, 10.1, 16.9, 26.0]);
+        (SparseMatrix::from_coo(coo), rhs)
+    }
+
+    #[test]
+    fn sym_ortho_works() {
+        for (a, b) in [
+            (3.0, 4.0),
+            (-3.0, 4.0),
+            (4.0, -3.0),
+            (0.0, -2.0),
+            (-2.0, 0.0),
+            (1e-20, 1e20),
+        ] {
+            let (c, s, r) = sym_ortho(a, b);
+            approx_eq(c * c + s * s, 1.0, 1e-15);
+            approx_eq(c * a + s * b, r, 1e-15 * f64::max(1.0, r));
+            approx_eq(-s * a + c * b, 0.0, 1e-15 * f64::max(1.0, r));
+            assert!(r >= 0.0);
+        }
+    }
+
+    #[test]
+    fn new_and_solve_capture_errors() {
+        let mut params = LsqParams::new();
+        params.atol = -1.0;
+        assert_eq!(
+            LsqSolver::new(Some(params)).err(),
+            Some("the tolerances atol and btol must be ≥ 0")
+        );
+        params.atol = 1e-10;
+        params.damp = -1.0;
+        assert_eq!(
+            LsqSolver::new(Some(params)).err(),
+            Some("the damping factor must be ≥ 0")
+        );
+        params.damp = 0.0;
+        params.conlim = 1.0;
+        assert_eq!(
+            LsqSolver::new(Some(params)).err(),
+            Some("the condition number limit must be > 1")
+        );
+        let (mut mat, rhs) = polynomial_fit();
+        let mut solver = LsqSolver::new(None).unwrap();
+        let mut x = Vector::new(2);
+        assert_eq!(
+            solver.solve(&mut x, &mut mat, &rhs).err(),
+            Some("the dimension of the vector of unknown values x is incorrect")
+        );
+        let mut x = Vector::new(3);
+        let wrong = Vector::new(3);
+        assert_eq!(
+            solver.solve(&mut x, &mut mat, &wrong).err(),
+            Some("the dimension of the right-hand side vector is incorrect")
+        );
+        params.conlim = 1e8;
+        params.max_iterations = 1;
+        let mut solver = LsqSolver::new(Some(params)).unwrap();
+        assert_eq!(
+            solver.solve(&mut x, &mut mat, &rhs).err(),
+            Some("LSMR did not converge within the maximum number of iterations")
+        );
+        params.method = LsqMethod::Lsqr;
+        let mut solver = LsqSolver::new(Some(params)).unwrap();
+        assert_eq!(
+            solver.solve(&mut x, &mut mat, &rhs).err(),
+            Some("LSQR did not converge within the maximum number of iterations")
+        );
+    }
+
+    #[test]
+    fn iterative_methods_work_overdetermined() {
+        // normal equations: Aᵀ·A·x = Aᵀ·b
+        let (mut mat, rhs) = polynomial_fit();
+        let a = mat.as_dense();
+        let mut ata = russell_lab::Matrix::new(3, 3);
+        russell_lab::mat_t_mat_mul(&mut ata, 1.0, &a, &a, 0.0).unwrap();
+        let mut atb = Vector::new(3);
+        russell_lab::vec_mat_mul(&mut atb, 1.0, &rhs, &a).unwrap();
+        let mut x_correct = atb.clone();
+        russell_lab::solve_lin_sys(&mut x_correct, &mut ata).unwrap();
+        for method in [LsqMethod::Lsqr, LsqMethod::Lsmr] {
+            let mut params = LsqParams::new();
+            params.method = method;
+            params.atol = 1e-14;
+            params.btol = 1e-14;
+            let mut solver = LsqSolver::new(Some(params)).unwrap();
+            let mut x = Vector::new(3);
+            solver.solve(&mut x, &mut mat, &rhs).unwrap();
+            vec_approx_eq(&x, &x_correct, 1e-10);
+            assert!(solver.get_iterations() <= 6);
+            assert!(solver.get_residual_norm() > 0.0);
+            assert!(solver.get_normal_residual_norm() < 1e-9);
+            assert_eq!(solver.get_rank(), None);
+            let (norm_a, cond_a) = solver.get_norm_and_cond_estimates();
+            assert!(norm_a > 0.0 && cond_a > 1.0);
+        }
+    }
+
+    #[test]
+    fn iterative_methods_work_underdetermined_and_rank_deficient() {
+        // x₀ + x₁ + x₂ = 3 and x₀ - x₂ = 0 => the minimum-norm solution is (1, 1, 1)
+        let mut coo = CooMatrix::new(2, 3, 5, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(0, 1, 1.0).unwrap();
+        coo.put(0, 2, 1.0).unwrap();
+        coo.put(1, 0, 1.0).unwrap();
+        coo.put(1, 2, -1.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let rhs = Vector::from(&[3.0, 0.0]);
+
+        // rank-deficient: the third column is the sum of the first two (minimum-norm solution)
+        let mut coo = CooMatrix::new(3, 3, 7, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(0, 2, 1.0).unwrap();
+        coo.put(1, 1, 1.0).unwrap();
+        coo.put(1, 2, 1.0).unwrap();
+        coo.put(2, 0, 1.0).unwrap();
+        coo.put(2, 1, 1.0).unwrap();
+        coo.put(2, 2, 2.0).unwrap();
+        let mut mat_rd = SparseMatrix::from_coo(coo);
+        let rhs_rd = Vector::from(&[2.0, 2.0, 4.0]); // = A·(1, 1, 1)
+
+        for method in [LsqMethod::Lsqr, LsqMethod::Lsmr] {
+            let mut params = LsqParams::new();
+            params.method = method;
+            let mut solver = LsqSolver::new(Some(params)).unwrap();
+            let mut x = Vector::new(3);
+            solver.solve(&mut x, &mut mat, &rhs).unwrap();
+            vec_approx_eq(&x, &[1.0, 1.0, 1.0], 1e-10);
+
+            // the null space is spanned by (1, 1, -1); thus, x = (1, 1, 1) - (1/3)·(1, 1, -1)
+            solver.solve(&mut x, &mut mat_rd, &rhs_rd).unwrap();
+            vec_approx_eq(&x, &[2.0 / 3.0, 2.0 / 3.0, 4.0 / 3.0], 1e-10);
+        }
+    }
+
+    #[test]
+    fn damping_works() {
+        // min ‖x - b‖² + damp²·‖x‖² with A = I => x = b / (1 + damp²)
+        let mut coo = CooMatrix::new(3, 3, 3, Sym::No).unwrap();
+        for i in 0..3 {
+            coo.put(i, i, 1.0).unwrap();
+        }
+        let rhs = Vector::from(&[1.0, 2.0, 3.0]);
+        for method in [LsqMethod::Lsqr, LsqMethod::Lsmr] {
+            let mut params = LsqParams::new();
+            params.method = method;
+            params.damp = 1.0;
+            let mut solver = LsqSolver::new(Some(params)).unwrap();
+            let mut x = Vector::new(3);
+            solver.solve_with_linear_operator(&mut x, &rhs, &coo).unwrap();
+            vec_approx_eq(&x, &[0.5, 1.0, 1.5], 1e-10);
+        }
+    }
+
+    #[test]
+    fn spqr_and_linear_operator_errors_work() {
+        let mut params = LsqParams::new();
+        params.method = LsqMethod::Spqr;
+        #[cfg(not(feature = "with_suitesparse"))]
+        assert_eq!(LsqSolver::new(Some(params)).err(), Some("SPQR solver is not available"));
+        #[cfg(feature = "with_suitesparse")]
+        {
+            let (coo, _) = polynomial_fit();
+            let mut solver = LsqSolver::new(Some(params)).unwrap();
+            let mut x = Vector::new(3);
+            let rhs = Vector::new(6);
+            assert_eq!(
+                solver.solve_with_linear_operator(&mut x, &rhs, &coo).err(),
+                Some("the linear operator requires an iterative method (Lsqr or Lsmr)")
+            );
+        }
+    }
+
+    #[test]
+    fn spqr_works() {
+        let (mut mat, rhs) = polynomial_fit();
+        let mut params = LsqParams::new();
+        params.method = LsqMethod::Spqr;
+        #[cfg(feature = "with_suitesparse")]
+        {
+            let mut solver = LsqSolver::new(Some(params)).unwrap();
+            let mut x = Vector::new(3);
+            solver.solve(&mut x, &mut mat, &rhs).unwrap();
+            assert_eq!(solver.get_rank(), Some(3));
+            assert!(solver.get_normal_residual_norm() < 1e-10);
+
+            // compare with LSMR
+            params.method = LsqMethod::Lsmr;
+            params.atol = 1e-14;
+            params.btol = 1e-14;
+            let mut lsmr = LsqSolver::new(Some(params)).unwrap();
+            let mut x_lsmr = Vector::new(3);
+            lsmr.solve(&mut x_lsmr, &mut mat, &rhs).unwrap();
+            vec_approx_eq(&x, &x_lsmr, 1e-10);
+        }
+        #[cfg(not(feature = "with_suitesparse"))]
+        let _ = (&mut mat, &rhs, &params);
+    }
+}
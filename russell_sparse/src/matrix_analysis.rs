@@ -0,0 +1,510 @@
+use super::{LinSolver, SparseMatrix, Sym};
+use crate::StrError;
+use russell_lab::{vec_norm, Norm, Vector};
+use serde::{Deserialize, Serialize};
+
+/// Defines the relative tolerance to compare a(i,j) and a(j,i) when computing the numerical symmetry
+const SYMMETRY_TOLERANCE: f64 = 1e-14;
+
+/// Defines the maximum number of iterations of the Hager/Higham estimator
+const COND_ESTIMATE_MAX_ITERATIONS: usize = 5;
+
+/// Holds a report with the structural and numerical properties of a (real) sparse matrix
+///
+/// The analysis is performed on the full matrix; i.e., if only the lower or upper triangle is stored
+/// (see [Sym]), the other triangle is taken into account by symmetry. The duplicate entries (of a COO matrix)
+/// are summed up before the analysis.
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     //  4  -1   .
+///     // -1   4  -1
+///     //  .  -1   4
+///     let mut coo = CooMatrix::new(3, 3, 7, Sym::No)?;
+///     coo.put(0, 0, 4.0)?;
+///     coo.put(0, 1, -1.0)?;
+///     coo.put(1, 0, -1.0)?;
+///     coo.put(1, 1, 4.0)?;
+///     coo.put(1, 2, -1.0)?;
+///     coo.put(2, 1, -1.0)?;
+///     coo.put(2, 2, 4.0)?;
+///     let mut mat = SparseMatrix::from_coo(coo);
+///
+///     // structural and numerical properties
+///     let mut analysis = MatrixAnalysis::new(&mat)?;
+///     assert_eq!(analysis.numerically_symmetric, true);
+///     assert_eq!(analysis.lower_bandwidth, 1);
+///     assert_eq!(analysis.strictly_diagonally_dominant, true);
+///
+///     // condition number estimate using an existing factorization
+///     let mut solver = LinSolver::new(Genie::Native)?;
+///     solver.actual.factorize(&mut mat, None)?;
+///     let cond = analysis.estimate_cond_one(&mut solver, &mat)?;
+///     assert!(cond > 1.0 && cond < 3.0);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MatrixAnalysis {
+    /// Number of rows
+    pub nrow: usize,
+
+    /// Number of columns
+    pub ncol: usize,
+
+    /// Number of stored entries (including duplicates and only one triangle if symmetric)
+    pub nnz_stored: usize,
+
+    /// Number of (non-duplicate) entries of the full matrix
+    pub nnz: usize,
+
+    /// Number of duplicate entries (repeated (i,j) pairs in the stored data)
+    pub duplicate_entries: usize,
+
+    /// Symmetric storage type (see [Sym])
+    pub symmetric: String,
+
+    /// Fraction of the off-diagonal entries a(i,j) for which a(j,i) is also present (1.0 means structurally symmetric)
+    pub structural_symmetry: f64,
+
+    /// Fraction of the off-diagonal entries a(i,j) such that a(i,j) = a(j,i) (1.0 means numerically symmetric)
+    pub numerical_symmetry: f64,
+
+    /// Indicates that the matrix is square and structurally symmetric
+    pub structurally_symmetric: bool,
+
+    /// Indicates that the matrix is square and numerically symmetric
+    pub numerically_symmetric: bool,
+
+    /// Lower bandwidth: max(i - j) over all entries with i > j
+    pub lower_bandwidth: usize,
+
+    /// Upper bandwidth: max(j - i) over all entries with j > i
+    pub upper_bandwidth: usize,
+
+    /// Profile (envelope size) of the lower triangle: Σᵢ (i - fᵢ) where fᵢ = min(i, first column of row i)
+    pub profile: usize,
+
+    /// Number of empty rows
+    pub empty_rows: usize,
+
+    /// Number of empty columns
+    pub empty_cols: usize,
+
+    /// Number of missing or zero diagonal entries (of the leading square part)
+    pub zero_diagonal_entries: usize,
+
+    /// Number of rows satisfying |a(i,i)| ≥ Σⱼ |a(i,j)| (j ≠ i)
+    pub diagonally_dominant_rows: usize,
+
+    /// Number of rows satisfying |a(i,i)| > Σⱼ |a(i,j)| (j ≠ i)
+    pub strictly_diagonally_dominant_rows: usize,
+
+    /// Indicates that all rows are diagonally dominant
+    pub diagonally_dominant: bool,
+
+    /// Indicates that all rows are strictly diagonally dominant
+    pub strictly_diagonally_dominant: bool,
+
+    /// Minimum number of entries per row
+    pub nnz_per_row_min: usize,
+
+    /// Maximum number of entries per row
+    pub nnz_per_row_max: usize,
+
+    /// Mean number of entries per row
+    pub nnz_per_row_mean: f64,
+
+    /// Standard deviation of the number of entries per row
+    pub nnz_per_row_std_dev: f64,
+
+    /// 1-norm: maximum absolute column sum
+    pub norm_one: f64,
+
+    /// Infinity-norm: maximum absolute row sum
+    pub norm_inf: f64,
+
+    /// Estimate of the 1-norm condition number (zero if not computed; see [MatrixAnalysis::estimate_cond_one])
+    pub cond_one_estimate: f64,
+}
+
+impl MatrixAnalysis {
+    /// Performs the analysis of a sparse matrix
+    ///
+    /// **Note:** The COO matrix is used if available (thus, the duplicates are detected);
+    /// otherwise, the CSC or CSR matrix is used.
+    pub fn new(mat: &SparseMatrix) -> Result<Self, StrError> {
+        // collect the stored triplets
+        let (nrow, ncol, _, sym) = mat.get_info();
+        let mut triplets: Vec<(usize, usize, f64)> = Vec::new();
+        if let Ok(coo) = mat.get_coo() {
+            for k in 0..coo.nnz {
+                triplets.push((coo.indices_i[k] as usize, coo.indices_j[k] as usize, coo.values[k]));
+            }
+        } else if let Ok(csc) = mat.get_csc() {
+            for j in 0..ncol {
+                for p in csc.col_pointers[j]..csc.col_pointers[j + 1] {
+                    let p = p as usize;
+                    triplets.push((csc.row_indices[p] as usize, j, csc.values[p]));
+                }
+            }
+        } else {
+            let csr = mat.get_csr()?;
+            for i in 0..nrow {
+                for p in csr.row_pointers[i]..csr.row_pointers[i + 1] {
+                    let p = p as usize;
+                    triplets.push((i, csr.col_indices[p] as usize, csr.values[p]));
+                }
+            }
+        }
+        let nnz_stored = triplets.len();
+
+        // sum duplicates
+        triplets.sort_by_key(|e| (e.0, e.1));
+        let mut entries: Vec<(usize, usize, f64)> = Vec::with_capacity(2 * nnz_stored);
+        for (i, j, aij) in triplets {
+            match entries.last_mut() {
+                Some(last) if last.0 == i && last.1 == j => last.2 += aij,
+                _ => entries.push((i, j, aij)),
+            }
+        }
+        let duplicate_entries = nnz_stored - entries.len();
+
+        // mirror the triangle if symmetric
+        if sym == Sym::YesLower || sym == Sym::YesUpper {
+            let n = entries.len();
+            for k in 0..n {
+                let (i, j, aij) = entries[k];
+                if i != j {
+                    entries.push((j, i, aij));
+                }
+            }
+            entries.sort_by_key(|e| (e.0, e.1));
+        }
+        let nnz = entries.len();
+
+        // symmetry
+        let mut n_off_diagonal = 0;
+        let mut n_structural = 0;
+        let mut n_numerical = 0;
+        for (i, j, aij) in &entries {
+            if i == j {
+                continue;
+            }
+            n_off_diagonal += 1;
+            let aji = match entries.binary_search_by(|e| (e.0, e.1).cmp(&(*j, *i))) {
+                Ok(k) => {
+                    n_structural += 1;
+                    entries[k].2
+                }
+                Err(_) => 0.0,
+            };
+            let scale = f64::max(f64::abs(*aij), f64::abs(aji));
+            if f64::abs(aij - aji) <= SYMMETRY_TOLERANCE * scale {
+                n_numerical += 1;
+            }
+        }
+        let (structural_symmetry, numerical_symmetry) = if n_off_diagonal == 0 {
+            (1.0, 1.0)
+        } else {
+            (
+                n_structural as f64 / n_off_diagonal as f64,
+                n_numerical as f64 / n_off_diagonal as f64,
+            )
+        };
+
+        // bandwidth, profile, norms, and row statistics
+        let mut lower_bandwidth = 0;
+        let mut upper_bandwidth = 0;
+        let mut row_count = vec![0_usize; nrow];
+        let mut col_count = vec![0_usize; ncol];
+        let mut row_first = (0..nrow).collect::<Vec<_>>();
+        let mut row_abs_sum = vec![0.0; nrow];
+        let mut col_abs_sum = vec![0.0; ncol];
+        let mut diagonal = vec![0.0; nrow];
+        for (i, j, aij) in &entries {
+            let (i, j) = (*i, *j);
+            if i > j {
+                lower_bandwidth = usize::max(lower_bandwidth, i - j);
+            } else if j > i {
+                upper_bandwidth = usize::max(upper_bandwidth, j - i);
+            } else {
+                diagonal[i] = *aij;
+            }
+            row_count[i] += 1;
+            col_count[j] += 1;
+            row_first[i] = usize::min(row_first[i], j);
+            row_abs_sum[i] += f64::abs(*aij);
+            col_abs_sum[j] += f64::abs(*aij);
+        }
+        let profile = (0..nrow).map(|i| i - row_first[i]).sum();
+        let empty_rows = row_count.iter().filter(|c| **c == 0).count();
+        let empty_cols = col_count.iter().filter(|c| **c == 0).count();
+        let zero_diagonal_entries = (0..usize::min(nrow, ncol)).filter(|i| diagonal[*i] == 0.0).count();
+        let mut diagonally_dominant_rows = 0;
+        let mut strictly_diagonally_dominant_rows = 0;
+        for i in 0..nrow {
+            let d = f64::abs(diagonal[i]);
+            let off = row_abs_sum[i] - d;
+            if d >= off {
+                diagonally_dominant_rows += 1;
+            }
+            if d > off {
+                strictly_diagonally_dominant_rows += 1;
+            }
+        }
+        let nnz_per_row_mean = nnz as f64 / nrow as f64;
+        let variance = row_count
+            .iter()
+            .map(|c| f64::powi(*c as f64 - nnz_per_row_mean, 2))
+            .sum::<f64>()
+            / nrow as f64;
+
+        Ok(MatrixAnalysis {
+            nrow,
+            ncol,
+            nnz_stored,
+            nnz,
+            duplicate_entries,
+            symmetric: format!("{:?}", sym),
+            structural_symmetry,
+            numerical_symmetry,
+            structurally_symmetric: nrow == ncol && n_structural == n_off_diagonal,
+            numerically_symmetric: nrow == ncol && n_numerical == n_off_diagonal,
+            lower_bandwidth,
+            upper_bandwidth,
+            profile,
+            empty_rows,
+            empty_cols,
+            zero_diagonal_entries,
+            diagonally_dominant_rows,
+            strictly_diagonally_dominant_rows,
+            diagonally_dominant: diagonally_dominant_rows == nrow,
+            strictly_diagonally_dominant: strictly_diagonally_dominant_rows == nrow,
+            nnz_per_row_min: row_count.iter().copied().min().unwrap_or(0),
+            nnz_per_row_max: row_count.iter().copied().max().unwrap_or(0),
+            nnz_per_row_mean,
+            nnz_per_row_std_dev: f64::sqrt(variance),
+            norm_one: col_abs_sum.iter().fold(0.0, |acc, v| f64::max(acc, *v)),
+            norm_inf: row_abs_sum.iter().fold(0.0, |acc, v| f64::max(acc, *v)),
+            cond_one_estimate: 0.0,
+        })
+    }
+
+    /// Estimates the 1-norm condition number using an existing factorization
+    ///
+    /// Computes `κ₁(A) ≈ ‖A‖₁ · est(‖A⁻¹‖₁)` where `‖A⁻¹‖₁` is estimated by the Hager/Higham algorithm.
+    /// The estimator requires a few solves with A and Aᵀ (the latter only if A is not symmetric); thus, the
+    /// solver must support `solve_transpose` for unsymmetric matrices. The result is also saved in `cond_one_estimate`.
+    ///
+    /// # Input
+    ///
+    /// * `solver` -- a linear solver holding the factorization of `mat`
+    /// * `mat` -- the matrix (the same one used in the factorization and in [MatrixAnalysis::new()])
+    ///
+    /// **Note:** The estimate is a lower bound of the 1-norm condition number, which is usually within a factor of 3.
+    ///
+    /// # References
+    ///
+    /// 1. Hager WW (1984) Condition estimates, SIAM Journal on Scientific and Statistical Computing, 5(2):311-316
+    /// 2. Higham NJ (1988) FORTRAN codes for estimating the one-norm of a real or complex matrix, with applications
+    ///    to condition estimation, ACM Transactions on Mathematical Software, 14(4):381-396
+    pub fn estimate_cond_one(&mut self, solver: &mut LinSolver, mat: &SparseMatrix) -> Result<f64, StrError> {
+        let (nrow, ncol, _, sym) = mat.get_info();
+        if nrow != ncol {
+            return Err("the condition number estimate requires a square matrix");
+        }
+        if nrow != self.nrow {
+            return Err("the matrix is incompatible with this analysis");
+        }
+        let n = nrow;
+        let symmetric = sym != Sym::No;
+
+        // Hager's algorithm
+        let mut x = Vector::filled(n, 1.0 / n as f64);
+        let mut y = Vector::new(n);
+        let mut z = Vector::new(n);
+        let mut xi = Vector::new(n);
+        let mut estimate = 0.0;
+        for k in 0..COND_ESTIMATE_MAX_ITERATIONS {
+            solver.actual.solve(&mut y, mat, &x, false)?;
+            let y_norm = vec_norm(&y, Norm::One);
+            if k > 0 && y_norm <= estimate {
+                break;
+            }
+            estimate = y_norm;
+            for i in 0..n {
+                xi[i] = if y[i] >= 0.0 { 1.0 } else { -1.0 };
+            }
+            if symmetric {
+                solver.actual.solve(&mut z, mat, &xi, false)?;
+            } else {
+                solver.actual.solve_transpose(&mut z, mat, &xi, false)?;
+            }
+            let mut j_max = 0;
+            let mut z_dot_x = 0.0;
+            for i in 0..n {
+                if f64::abs(z[i]) > f64::abs(z[j_max]) {
+                    j_max = i;
+                }
+                z_dot_x += z[i] * x[i];
+            }
+            if f64::abs(z[j_max]) <= z_dot_x {
+                break;
+            }
+            x.fill(0.0);
+            x[j_max] = 1.0;
+        }
+
+        // Higham's alternative estimate (guards against the worst cases of Hager's algorithm)
+        for i in 0..n {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            x[i] = if n > 1 {
+                sign * (1.0 + i as f64 / (n - 1) as f64)
+            } else {
+                1.0
+            };
+        }
+        solver.actual.solve(&mut y, mat, &x, false)?;
+        let alternative = 2.0 * vec_norm(&y, Norm::One) / (3.0 * n as f64);
+        estimate = f64::max(estimate, alternative);
+
+        // done
+        self.cond_one_estimate = self.norm_one * estimate;
+        Ok(self.cond_one_estimate)
+    }
+
+    /// Gets a JSON representation of the analysis
+    pub fn get_json(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::MatrixAnalysis;
+    use crate::{CooMatrix, CscMatrix, Genie, LinSolver, Samples, SparseMatrix, Sym};
+    use russell_lab::{approx_eq, mat_inverse, Matrix};
+
+    #[test]
+    fn new_works_unsymmetric() {
+        //  2  3  .  .  .
+        //  3  .  4  .  6
+        //  . -1 -3  2  .
+        //  .  .  1  .  .
+        //  .  4  2  .  1
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mat = SparseMatrix::from_coo(coo);
+        let res = MatrixAnalysis::new(&mat).unwrap();
+        assert_eq!((res.nrow, res.ncol), (5, 5));
+        assert_eq!(res.nnz, 12);
+        assert_eq!(res.symmetric, "No");
+        // off-diagonal: (1,0) (0,1) (2,1) (4,1) (1,2) (3,2) (4,2) (2,3) (1,4)
+        // with transpose: all but (4,2) => 8 of 9; numerically: (1,0) (0,1) => 2 of 9
+        approx_eq(res.structural_symmetry, 8.0 / 9.0, 1e-15);
+        approx_eq(res.numerical_symmetry, 2.0 / 9.0, 1e-15);
+        assert!(!res.structurally_symmetric);
+        assert!(!res.numerically_symmetric);
+        assert_eq!(res.lower_bandwidth, 3);
+        assert_eq!(res.upper_bandwidth, 3);
+        assert_eq!(res.profile, 6); // 0 + 1 + 1 + 1 + 3
+        assert_eq!((res.empty_rows, res.empty_cols), (0, 0));
+        assert_eq!(res.zero_diagonal_entries, 2);
+        assert_eq!(res.diagonally_dominant_rows, 1); // row 2: |-3| ≥ |-1| + |2|
+        assert_eq!(res.strictly_diagonally_dominant_rows, 0);
+        assert_eq!((res.nnz_per_row_min, res.nnz_per_row_max), (1, 3));
+        approx_eq(res.nnz_per_row_mean, 12.0 / 5.0, 1e-15);
+        assert_eq!(res.norm_one, 10.0);
+        assert_eq!(res.norm_inf, 13.0);
+        assert!(res.get_json().contains("\"lower_bandwidth\": 3"));
+    }
+
+    #[test]
+    fn new_works_duplicates_and_empty() {
+        // 1+1  .  .
+        //  .   .  .
+        //  .   .  2
+        let mut coo = CooMatrix::new(3, 3, 3, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(2, 2, 2.0).unwrap();
+        let mat = SparseMatrix::from_coo(coo);
+        let res = MatrixAnalysis::new(&mat).unwrap();
+        assert_eq!(res.nnz_stored, 3);
+        assert_eq!(res.nnz, 2);
+        assert_eq!(res.duplicate_entries, 1);
+        assert_eq!((res.empty_rows, res.empty_cols), (1, 1));
+        assert_eq!(res.zero_diagonal_entries, 1);
+        assert_eq!(res.diagonally_dominant_rows, 3);
+        assert_eq!(res.strictly_diagonally_dominant_rows, 2);
+        assert!(res.structurally_symmetric);
+        assert_eq!(res.norm_one, 2.0);
+    }
+
+    #[test]
+    fn new_works_symmetric_csc() {
+        // 2  1  .
+        // 1  2  1
+        // .  1  2
+        let mut coo = CooMatrix::new(3, 3, 5, Sym::YesLower).unwrap();
+        coo.put(0, 0, 2.0).unwrap();
+        coo.put(1, 0, 1.0).unwrap();
+        coo.put(1, 1, 2.0).unwrap();
+        coo.put(2, 1, 1.0).unwrap();
+        coo.put(2, 2, 2.0).unwrap();
+        let mat = SparseMatrix::from_csc(CscMatrix::from_coo(&coo).unwrap());
+        let res = MatrixAnalysis::new(&mat).unwrap();
+        assert_eq!(res.symmetric, "YesLower");
+        assert_eq!((res.nnz_stored, res.nnz), (5, 7));
+        assert!(res.numerically_symmetric);
+        assert_eq!((res.lower_bandwidth, res.upper_bandwidth, res.profile), (1, 1, 2));
+        assert!(res.diagonally_dominant);
+        assert!(!res.strictly_diagonally_dominant);
+        assert_eq!(res.norm_one, 4.0);
+        approx_eq(res.nnz_per_row_std_dev, f64::sqrt(2.0 / 9.0), 1e-15);
+    }
+
+    #[test]
+    fn estimate_cond_one_works() {
+        //  2  3  .  .  .
+        //  3  .  4  .  6
+        //  . -1 -3  2  .
+        //  .  .  1  .  .
+        //  .  4  2  .  1
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut analysis = MatrixAnalysis::new(&mat).unwrap();
+        let mut solver = LinSolver::new(Genie::Native).unwrap();
+        solver.actual.factorize(&mut mat, None).unwrap();
+        let estimate = analysis.estimate_cond_one(&mut solver, &mat).unwrap();
+
+        // exact 1-norm condition number
+        let a = mat.as_dense();
+        let mut ai = Matrix::new(5, 5);
+        mat_inverse(&mut ai, &a).unwrap();
+        let mut inv_norm: f64 = 0.0;
+        for j in 0..5 {
+            inv_norm = inv_norm.max((0..5).map(|i| f64::abs(ai.get(i, j))).sum());
+        }
+        let exact = analysis.norm_one * inv_norm;
+        assert!(estimate <= exact * (1.0 + 1e-12));
+        assert!(estimate >= exact / 3.0);
+        assert_eq!(analysis.cond_one_estimate, estimate);
+
+        // errors
+        let mut rect = CooMatrix::new(2, 3, 1, Sym::No).unwrap();
+        rect.put(0, 0, 1.0).unwrap();
+        let rect = SparseMatrix::from_coo(rect);
+        assert_eq!(
+            analysis.estimate_cond_one(&mut solver, &rect).err(),
+            Some("the condition number estimate requires a square matrix")
+        );
+    }
+}
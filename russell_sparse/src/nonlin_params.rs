@@ -0,0 +1,217 @@
+#[cfg(doc)]
+use super::NonlinSolver;
+use super::{Genie, LinSolParams, NonlinGlobal, NonlinMethod};
+use crate::StrError;
+
+/// Defines the configuration parameters for the nonlinear system solver
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NonlinParams {
+    /// Defines the method
+    pub method: NonlinMethod,
+
+    /// Defines the globalization strategy
+    ///
+    /// **Note:** [NonlinGlobal::TrustRegion] is only available with [NonlinMethod::Newton] because the
+    /// dogleg step needs the Jacobian matrix; otherwise, [crate::NonlinSolver::new] returns an error.
+    pub global: NonlinGlobal,
+
+    /// Defines the maximum number of (nonlinear) iterations
+    pub max_iterations: usize,
+
+    /// Defines the absolute tolerance on the residual norm `‖F(x)‖ ≤ atol`
+    ///
+    /// **Note:** Convergence is reached when the residual norm is smaller than `max(rtol·‖F(x₀)‖, atol)`
+    pub tol_abs: f64,
+
+    /// Defines the relative tolerance on the residual norm `‖F(x)‖ ≤ rtol·‖F(x₀)‖`
+    pub tol_rel: f64,
+
+    /// Defines the coefficient c ∈ (0, ½) of the Armijo condition `‖F(x + α·Δx)‖² ≤ (1 - 2·c·α)·‖F(x)‖²`
+    pub line_search_c: f64,
+
+    /// Defines the maximum number of step reductions (backtracks) of the line search
+    pub line_search_max_backtracks: usize,
+
+    /// Defines the initial radius Δ₀ of the trust region
+    pub trust_region_radius: f64,
+
+    /// Defines the maximum radius of the trust region
+    pub trust_region_max_radius: f64,
+
+    /// Defines the minimum radius of the trust region, below which the solver fails
+    pub trust_region_min_radius: f64,
+
+    /// Defines the linear solver
+    ///
+    /// **Note:** The Newton method may use any solver compatible with the symmetric type of the
+    /// Jacobian (see [Genie::get_sym]). The Newton-Krylov method uses this solver if it is iterative
+    /// (see [Genie::is_iterative]); otherwise, GMRES is used.
+    pub genie: Genie,
+
+    /// Defines the parameters of the linear solver
+    ///
+    /// **Note:** In the Newton-Krylov method, [LinSolParams::krylov_rel_tol] is the forcing term, i.e.,
+    /// the linear systems are solved with `‖F + J·Δx‖ ≤ η·‖F‖`. Also, the preconditioner
+    /// ([LinSolParams::krylov_precond]) is only computed if the (analytical) Jacobian is available.
+    pub lin_sol_params: LinSolParams,
+
+    /// Defines the relative perturbation ε of the finite-difference Jacobian-vector product (Newton-Krylov)
+    ///
+    /// ```text
+    ///         F(x + h·v) - F(x)
+    /// J·v ≈ ———————————————————     with     h = ε · (1 + ‖x‖) / ‖v‖
+    ///                 h
+    /// ```
+    pub newton_krylov_epsilon: f64,
+
+    /// Defines the ratio `‖F(xₖ₊₁)‖ / ‖F(xₖ)‖` above which the preconditioner is recomputed (Newton-Krylov)
+    ///
+    /// The preconditioner (and the Jacobian matrix used to compute it) is kept across the iterations
+    /// (lagged preconditioning) and only recomputed when the convergence stalls, i.e., when the residual
+    /// norm is not reduced by at least this factor. Zero means that the preconditioner is recomputed at
+    /// every iteration.
+    pub newton_krylov_precond_stall_ratio: f64,
+
+    /// Show the residual norm at each iteration
+    pub verbose: bool,
+}
+
+impl NonlinParams {
+    /// Allocates a new instance with default values
+    pub fn new() -> Self {
+        NonlinParams {
+            method: NonlinMethod::Newton,
+            global: NonlinGlobal::LineSearch,
+            max_iterations: 50,
+            tol_abs: 1e-10,
+            tol_rel: 0.0,
+            line_search_c: 1e-4,
+            line_search_max_backtracks: 30,
+            trust_region_radius: 1.0,
+            trust_region_max_radius: 1e10,
+            trust_region_min_radius: 1e-14,
+            genie: Genie::Native,
+            lin_sol_params: LinSolParams::new(),
+            newton_krylov_epsilon: f64::sqrt(f64::EPSILON),
+            newton_krylov_precond_stall_ratio: 0.5,
+            verbose: false,
+        }
+    }
+
+    /// Validates the parameters
+    pub(crate) fn validate(&self) -> Result<(), StrError> {
+        if self.max_iterations < 1 {
+            return Err("the maximum number of iterations must be ≥ 1");
+        }
+        if self.tol_abs < 0.0 || self.tol_rel < 0.0 {
+            return Err("the tolerances must be ≥ 0");
+        }
+        if self.line_search_c <= 0.0 || self.line_search_c >= 0.5 {
+            return Err("the Armijo coefficient must satisfy 0 < c < ½");
+        }
+        if self.trust_region_min_radius <= 0.0
+            || self.trust_region_radius < self.trust_region_min_radius
+            || self.trust_region_max_radius < self.trust_region_radius
+        {
+            return Err("the trust region radii must satisfy 0 < Δmin ≤ Δ₀ ≤ Δmax");
+        }
+        if self.newton_krylov_epsilon <= 0.0 {
+            return Err("the finite-difference perturbation must be > 0");
+        }
+        if self.newton_krylov_precond_stall_ratio < 0.0 {
+            return Err("the preconditioner stall ratio must be ≥ 0");
+        }
+        if self.method == NonlinMethod::NewtonKrylov && self.global == NonlinGlobal::TrustRegion {
+            return Err("the trust region requires the Newton method (the Jacobian matrix is needed)");
+        }
+        Ok(())
+    }
+}
+
+impl Default for NonlinParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::NonlinParams;
+    use crate::{Genie, NonlinGlobal, NonlinMethod};
+
+    #[test]
+    fn copy_debug_and_default_work() {
+        let params = NonlinParams::new();
+        let copy = params;
+        assert!(!format!("{:?}", params).is_empty());
+        assert_eq!(copy, params);
+        assert_eq!(NonlinParams::default(), params);
+    }
+
+    #[test]
+    fn nonlin_params_new_works() {
+        let params = NonlinParams::new();
+        assert_eq!(params.method, NonlinMethod::Newton);
+        assert_eq!(params.global, NonlinGlobal::LineSearch);
+        assert_eq!(params.max_iterations, 50);
+        assert_eq!(params.tol_abs, 1e-10);
+        assert_eq!(params.tol_rel, 0.0);
+        assert_eq!(params.line_search_c, 1e-4);
+        assert_eq!(params.line_search_max_backtracks, 30);
+        assert_eq!(params.trust_region_radius, 1.0);
+        assert_eq!(params.trust_region_max_radius, 1e10);
+        assert_eq!(params.trust_region_min_radius, 1e-14);
+        assert_eq!(params.genie, Genie::Native);
+        assert_eq!(params.newton_krylov_epsilon, f64::sqrt(f64::EPSILON));
+        assert_eq!(params.newton_krylov_precond_stall_ratio, 0.5);
+        assert!(!params.verbose);
+    }
+
+    #[test]
+    fn validate_captures_errors() {
+        let mut params = NonlinParams::new();
+        params.max_iterations = 0;
+        assert_eq!(
+            params.validate().err(),
+            Some("the maximum number of iterations must be ≥ 1")
+        );
+        params = NonlinParams::new();
+        params.tol_abs = -1.0;
+        assert_eq!(params.validate().err(), Some("the tolerances must be ≥ 0"));
+        params = NonlinParams::new();
+        params.line_search_c = 0.5;
+        assert_eq!(
+            params.validate().err(),
+            Some("the Armijo coefficient must satisfy 0 < c < ½")
+        );
+        params = NonlinParams::new();
+        params.trust_region_radius = 1e20;
+        assert_eq!(
+            params.validate().err(),
+            Some("the trust region radii must satisfy 0 < Δmin ≤ Δ₀ ≤ Δmax")
+        );
+        params = NonlinParams::new();
+        params.newton_krylov_epsilon = 0.0;
+        assert_eq!(
+            params.validate().err(),
+            Some("the finite-difference perturbation must be > 0")
+        );
+        params = NonlinParams::new();
+        params.newton_krylov_precond_stall_ratio = -1.0;
+        assert_eq!(
+            params.validate().err(),
+            Some("the preconditioner stall ratio must be ≥ 0")
+        );
+        params = NonlinParams::new();
+        params.method = NonlinMethod::NewtonKrylov;
+        params.global = NonlinGlobal::TrustRegion;
+        assert_eq!(
+            params.validate().err(),
+            Some("the trust region requires the Newton method (the Jacobian matrix is needed)")
+        );
+        params = NonlinParams::new();
+        assert_eq!(params.validate().err(), None);
+    }
+}
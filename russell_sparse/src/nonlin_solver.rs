@@ -0,0 +1,627 @@
+use super::{numerical_jacobian, CooMatrix, Genie, LinSolver, NonlinGlobal, NonlinMethod};
+use super::{NonlinParams, NonlinSystem, Precond, Preconditioner, SolverKrylov, SparseMatrix, StatsLinSol};
+use super::{StatsNonlin, Sym};
+use crate::StrError;
+use russell_lab::{vec_copy, vec_inner, vec_norm, vec_update, Norm, Stopwatch, Vector};
+
+/// Minimum ratio between the actual and predicted reductions for accepting a trust-region step
+const TRUST_REGION_ETA: f64 = 1e-4;
+
+/// Implements a solver for systems of nonlinear algebraic equations `F(x) = 0`
+///
+/// The following methods are available (see [NonlinMethod]):
+///
+/// * [NonlinMethod::Newton] -- Newton-Raphson method with the sparse Jacobian matrix, computed
+///   analytically (see [NonlinSystem::set_jacobian]) or numerically (see [crate::numerical_jacobian]).
+///   The linear systems `J·Δx = -F` are solved by a [LinSolver] and the symbolic analysis is reused.
+/// * [NonlinMethod::NewtonKrylov] -- Jacobian-free Newton-Krylov method; the linear systems are
+///   solved (inexactly) by a Krylov method with the Jacobian-vector products approximated by finite
+///   differences. If the Jacobian function is available, it is only used to compute the preconditioner,
+///   which is kept across the iterations and only recomputed when the convergence stalls
+///   (see [NonlinParams::newton_krylov_precond_stall_ratio]).
+///
+/// The convergence from poor initial guesses may be improved by the globalization strategies
+/// (see [NonlinGlobal]):
+///
+/// * [NonlinGlobal::LineSearch] -- backtracking (with safeguarded quadratic interpolation) until the
+///   Armijo condition `‖F(x + α·Δx)‖² ≤ (1 - 2·c·α)·‖F(x)‖²` is satisfied
+/// * [NonlinGlobal::TrustRegion] -- Powell's dogleg method combining the Newton step with the
+///   steepest-descent (Cauchy) step of `½‖F‖²`, within a trust region of adaptive radius Δ
+///   (only with [NonlinMethod::Newton])
+///
+/// The iterations stop when `‖F(x)‖ ≤ max(rtol·‖F(x₀)‖, atol)` (Euclidean norms).
+///
+/// # References
+///
+/// 1. Dennis JE, Schnabel RB (1996) Numerical Methods for Unconstrained Optimization and
+///    Nonlinear Equations, SIAM, Philadelphia
+/// 2. Knoll DA, Keyes DE (2004) Jacobian-free Newton-Krylov methods: a survey of approaches
+///    and applications, Journal of Computational Physics, 193(2):357-397
+pub struct NonlinSolver {
+    /// Holds the parameters
+    params: NonlinParams,
+
+    /// Holds the statistics of the last solve
+    stats: StatsNonlin,
+}
+
+impl NonlinSolver {
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `params` -- configuration parameters; None => use default
+    pub fn new(params: Option<NonlinParams>) -> Result<Self, StrError> {
+        let par = params.unwrap_or_default();
+        par.validate()?;
+        Ok(NonlinSolver {
+            params: par,
+            stats: StatsNonlin::default(),
+        })
+    }
+
+    /// Solves the nonlinear system `F(x) = 0`
+    ///
+    /// # Input/Output
+    ///
+    /// * `x` -- the initial guess (input) and the solution (output)
+    ///
+    /// # Input
+    ///
+    /// * `system` -- the nonlinear system
+    /// * `args` -- extra arguments for the F(x) and Jacobian functions
+    ///
+    /// **Note:** An error is returned if the method does not converge; nonetheless, the
+    /// statistics (see [NonlinSolver::get_stats]) are available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_lab::{approx_eq, NoArgs, Vector};
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // intersection of the circle x² + y² = 4 with the line x = y
+    ///     let mut system = NonlinSystem::new(2, |f, x, _args: &mut NoArgs| {
+    ///         f[0] = x[0] * x[0] + x[1] * x[1] - 4.0;
+    ///         f[1] = x[0] - x[1];
+    ///         Ok(())
+    ///     });
+    ///     system.set_jacobian(Some(4), Sym::No, |jj, x, _args: &mut NoArgs| {
+    ///         jj.reset();
+    ///         jj.put(0, 0, 2.0 * x[0])?;
+    ///         jj.put(0, 1, 2.0 * x[1])?;
+    ///         jj.put(1, 0, 1.0)?;
+    ///         jj.put(1, 1, -1.0)?;
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     // solve
+    ///     let mut solver = NonlinSolver::new(None)?;
+    ///     let mut x = Vector::from(&[1.0, 0.5]);
+    ///     solver.solve(&mut x, &system, &mut 0)?;
+    ///     approx_eq(x[0], f64::sqrt(2.0), 1e-12);
+    ///     approx_eq(x[1], f64::sqrt(2.0), 1e-12);
+    ///     assert!(solver.get_stats().converged);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn solve<A>(&mut self, x: &mut Vector, system: &NonlinSystem<A>, args: &mut A) -> Result<(), StrError> {
+        let mut stopwatch = Stopwatch::new();
+        self.stats = StatsNonlin::default();
+        let res = self.run(x, system, args);
+        self.stats.nanos_total = stopwatch.stop();
+        res
+    }
+
+    /// Returns the statistics of the last solve
+    pub fn get_stats(&self) -> &StatsNonlin {
+        &self.stats
+    }
+
+    /// Runs the Newton (or Newton-Krylov) iterations
+    fn run<A>(&mut self, x: &mut Vector, system: &NonlinSystem<A>, args: &mut A) -> Result<(), StrError> {
+        // check
+        let ndim = system.ndim;
+        if x.dim() != ndim {
+            return Err("the dimension of the vector of unknowns x is incorrect");
+        }
+
+        // configuration
+        let par = self.params;
+        let newton_krylov = par.method == NonlinMethod::NewtonKrylov;
+        let mut lsp = par.lin_sol_params;
+        lsp.krylov_nonzero_initial_guess = false;
+
+        // Jacobian matrix and linear solver (Newton) or Krylov solver (Newton-Krylov)
+        let numerical = system.jacobian.is_none();
+        let (nnz, sym) = if numerical {
+            (ndim * ndim, Sym::No)
+        } else {
+            (system.jac_nnz, system.jac_sym)
+        };
+        let with_precond = newton_krylov && !numerical && lsp.krylov_precond != Precond::No;
+        let mut jj = if !newton_krylov || with_precond {
+            Some(SparseMatrix::new_coo(ndim, ndim, nnz, sym)?)
+        } else {
+            None
+        };
+        let mut solver = if newton_krylov {
+            None
+        } else {
+            Some(LinSolver::new(par.genie)?)
+        };
+        let mut krylov = if newton_krylov {
+            let genie = if par.genie.is_iterative() {
+                par.genie
+            } else {
+                Genie::Gmres
+            };
+            Some(SolverKrylov::new(genie)?)
+        } else {
+            None
+        };
+
+        // workspace
+        let mut f = Vector::new(ndim);
+        let mut dx = Vector::new(ndim);
+        let mut x_trial = Vector::new(ndim);
+        let mut f_trial = Vector::new(ndim);
+        let mut w1 = Vector::new(ndim);
+        let mut w2 = Vector::new(ndim);
+
+        // initial residual
+        (system.function)(&mut f, x, args)?;
+        self.stats.n_function += 1;
+        let mut norm_f = vec_norm(&f, Norm::Euc);
+        let tolerance = f64::max(par.tol_rel * norm_f, par.tol_abs);
+        let mut radius = par.trust_region_radius;
+        let mut refresh_precond = true;
+        self.stats.tolerance = tolerance;
+        self.stats.history.push(norm_f);
+        if par.verbose {
+            println!("{:>5}{:>15}{:>15}", "it", "‖F‖", "‖Δx‖");
+            println!("{:>5}{:>15.6e}{:>15}", 0, norm_f, "");
+        }
+
+        // iterations
+        while self.stats.iterations < par.max_iterations {
+            // exit point
+            if !norm_f.is_finite() {
+                break;
+            }
+            if norm_f <= tolerance {
+                self.stats.converged = true;
+                break;
+            }
+
+            // Jacobian matrix (Newton or preconditioned Newton-Krylov, if the preconditioner must be refreshed)
+            if let Some(jj) = jj.as_mut().filter(|_| !newton_krylov || refresh_precond) {
+                match system.jacobian.as_ref() {
+                    Some(jacobian) => (jacobian)(jj.get_coo_mut()?, x, args)?,
+                    None => {
+                        let function = |f: &mut Vector, _: f64, y: &Vector, a: &mut A| (system.function)(f, y, a);
+                        numerical_jacobian(jj.get_coo_mut()?, 1.0, 0.0, x, &mut w1, &mut w2, args, function)?;
+                        self.stats.n_function += 1 + ndim;
+                    }
+                }
+                self.stats.n_jacobian += 1;
+            }
+
+            // Newton step: J·Δx = -F
+            if let Some(solver) = solver.as_mut() {
+                let jj = jj.as_mut().unwrap(); // unwrap OK because the Jacobian is allocated for Newton
+                solver.factorize(jj, Some(lsp))?;
+                solver.solve(&mut dx, jj, &f, lsp.verbose)?;
+                if par.genie.is_iterative() {
+                    let mut stats = StatsLinSol::new();
+                    solver.update_stats(&mut stats);
+                    self.stats.n_lin_iterations += stats.iterative.iterations;
+                }
+            } else {
+                let krylov = krylov.as_mut().unwrap(); // unwrap OK because the Krylov solver is allocated for Newton-Krylov
+                if let Some(jj) = jj.as_ref().filter(|_| refresh_precond) {
+                    let mut precond = Preconditioner::new(lsp.krylov_precond, Some(lsp))?;
+                    precond.setup(jj)?;
+                    krylov.set_preconditioner(Some(precond));
+                }
+                let norm_x = vec_norm(x, Norm::Euc);
+                let mut n_function = 0;
+                let res = krylov.solve_with_operator(&mut dx, &f, Some(lsp), |v, u| {
+                    let norm_u = vec_norm(u, Norm::Euc);
+                    if norm_u == 0.0 {
+                        v.fill(0.0);
+                        return Ok(());
+                    }
+                    let h = par.newton_krylov_epsilon * (1.0 + norm_x) / norm_u;
+                    for i in 0..ndim {
+                        w1[i] = x[i] + h * u[i];
+                    }
+                    (system.function)(v, &w1, args)?;
+                    n_function += 1;
+                    for i in 0..ndim {
+                        v[i] = (v[i] - f[i]) / h;
+                    }
+                    Ok(())
+                });
+                self.stats.n_function += n_function;
+                self.stats.n_lin_iterations += krylov.get_iterations();
+                res?;
+            }
+            self.stats.n_lin_sol += 1;
+            for i in 0..ndim {
+                dx[i] = -dx[i];
+            }
+
+            // globalization
+            match par.global {
+                NonlinGlobal::No => {
+                    vec_update(x, 1.0, &dx)?;
+                    (system.function)(&mut f, x, args)?;
+                    self.stats.n_function += 1;
+                }
+                NonlinGlobal::LineSearch => {
+                    let norm_f_sq = norm_f * norm_f;
+                    let mut alpha = 1.0;
+                    let mut backtracks = 0;
+                    loop {
+                        for i in 0..ndim {
+                            x_trial[i] = x[i] + alpha * dx[i];
+                        }
+                        (system.function)(&mut f_trial, &x_trial, args)?;
+                        self.stats.n_function += 1;
+                        let phi = vec_inner(&f_trial, &f_trial);
+                        if phi.is_finite() && phi <= (1.0 - 2.0 * par.line_search_c * alpha) * norm_f_sq {
+                            break;
+                        }
+                        if backtracks == par.line_search_max_backtracks {
+                            return Err("the line search failed to reduce the residual");
+                        }
+                        // safeguarded minimizer of the quadratic model of ‖F(x + α·Δx)‖²
+                        let alpha_new = if phi.is_finite() {
+                            norm_f_sq * alpha * alpha / (phi - norm_f_sq + 2.0 * norm_f_sq * alpha)
+                        } else {
+                            0.0
+                        };
+                        alpha = f64::min(f64::max(alpha_new, 0.1 * alpha), 0.5 * alpha);
+                        backtracks += 1;
+                    }
+                    self.stats.n_backtracks += backtracks;
+                    vec_copy(x, &x_trial)?;
+                    vec_copy(&mut f, &f_trial)?;
+                }
+                NonlinGlobal::TrustRegion => {
+                    // gradient of ½‖F‖² and Cauchy point
+                    let jj = jj.as_ref().unwrap(); // unwrap OK because the trust region requires Newton (see validate)
+                    let coo = jj.get_coo()?;
+                    let mut g = Vector::new(ndim);
+                    coo_mat_t_vec_mul(&mut g, coo, &f);
+                    let mut jg = Vector::new(ndim);
+                    coo.mat_vec_mul(&mut jg, 1.0, &g)?;
+                    let norm_g = vec_norm(&g, Norm::Euc);
+                    let norm_jg_sq = vec_inner(&jg, &jg);
+                    let mut p_cauchy = Vector::new(ndim);
+                    if norm_jg_sq > 0.0 {
+                        vec_update(&mut p_cauchy, -norm_g * norm_g / norm_jg_sq, &g)?;
+                    }
+                    let norm_newton = vec_norm(&dx, Norm::Euc);
+                    let norm_cauchy = vec_norm(&p_cauchy, Norm::Euc);
+
+                    // dogleg steps until the actual reduction agrees with the predicted reduction
+                    let mut p = Vector::new(ndim);
+                    let mut jp = Vector::new(ndim);
+                    loop {
+                        if norm_newton <= radius {
+                            vec_copy(&mut p, &dx)?;
+                        } else if norm_cauchy >= radius || norm_g == 0.0 {
+                            p.fill(0.0);
+                            if norm_g > 0.0 {
+                                vec_update(&mut p, -radius / norm_g, &g)?;
+                            }
+                        } else {
+                            // p = pc + τ·(pn - pc) with ‖p‖ = Δ
+                            let mut d = Vector::new(ndim);
+                            for i in 0..ndim {
+                                d[i] = dx[i] - p_cauchy[i];
+                            }
+                            let a = vec_inner(&d, &d);
+                            let b = 2.0 * vec_inner(&p_cauchy, &d);
+                            let c = norm_cauchy * norm_cauchy - radius * radius;
+                            let tau = (-b + f64::sqrt(b * b - 4.0 * a * c)) / (2.0 * a);
+                            for i in 0..ndim {
+                                p[i] = p_cauchy[i] + tau * d[i];
+                            }
+                        }
+                        let norm_p = vec_norm(&p, Norm::Euc);
+
+                        // actual and predicted reductions of ½‖F‖²
+                        for i in 0..ndim {
+                            x_trial[i] = x[i] + p[i];
+                        }
+                        (system.function)(&mut f_trial, &x_trial, args)?;
+                        self.stats.n_function += 1;
+                        coo.mat_vec_mul(&mut jp, 1.0, &p)?;
+                        for i in 0..ndim {
+                            jp[i] += f[i];
+                        }
+                        let actual = 0.5 * (norm_f * norm_f - vec_inner(&f_trial, &f_trial));
+                        let predicted = 0.5 * (norm_f * norm_f - vec_inner(&jp, &jp));
+                        let rho = if predicted > 0.0 { actual / predicted } else { -1.0 };
+
+                        // update the radius
+                        if !rho.is_finite() || rho < 0.25 {
+                            radius = 0.25 * norm_p;
+                        } else if rho > 0.75 && norm_p >= 0.99 * radius {
+                            radius = f64::min(2.0 * radius, par.trust_region_max_radius);
+                        }
+                        if rho.is_finite() && rho > TRUST_REGION_ETA {
+                            break;
+                        }
+                        self.stats.n_rejected += 1;
+                        if radius < par.trust_region_min_radius {
+                            return Err("the trust region radius became smaller than the minimum radius");
+                        }
+                    }
+                    vec_copy(&mut dx, &p)?;
+                    vec_copy(x, &x_trial)?;
+                    vec_copy(&mut f, &f_trial)?;
+                }
+            }
+
+            // next iteration (the preconditioner is refreshed if the convergence stalls)
+            let norm_f_old = norm_f;
+            norm_f = vec_norm(&f, Norm::Euc);
+            refresh_precond = norm_f > par.newton_krylov_precond_stall_ratio * norm_f_old;
+            self.stats.iterations += 1;
+            self.stats.history.push(norm_f);
+            if par.verbose {
+                let norm_dx = vec_norm(&dx, Norm::Euc);
+                println!("{:>5}{:>15.6e}{:>15.6e}", self.stats.iterations, norm_f, norm_dx);
+            }
+        }
+
+        // done
+        self.stats.residual_norm = norm_f;
+        if norm_f <= tolerance {
+            self.stats.converged = true;
+        }
+        if self.stats.converged {
+            Ok(())
+        } else if !norm_f.is_finite() {
+            Err("the residual norm is not finite")
+        } else {
+            Err("the nonlinear solver did not converge (max number of iterations reached)")
+        }
+    }
+}
+
+/// Computes `v := Jᵀ·u` (the other triangle is also accounted for if J is stored as triangular)
+fn coo_mat_t_vec_mul(v: &mut Vector, jj: &CooMatrix, u: &Vector) {
+    let mirror = jj.symmetric.triangular();
+    v.fill(0.0);
+    for p in 0..jj.nnz {
+        let i = jj.indices_i[p] as usize;
+        let j = jj.indices_j[p] as usize;
+        v[j] += jj.values[p] * u[i];
+        if mirror && i != j {
+            v[i] += jj.values[p] * u[j];
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::NonlinSolver;
+    use crate::{CooMatrix, Genie, NonlinGlobal, NonlinMethod, NonlinParams, NonlinSystem, Precond, Sym};
+    use russell_lab::{vec_approx_eq, NoArgs, Vector};
+
+    /// Returns the system with four equations of the nonlinear_system_4eqs example
+    fn four_equations<'a>(with_jacobian: bool) -> NonlinSystem<'a, NoArgs> {
+        let mut system = NonlinSystem::new(4, |rr, uu, _args: &mut NoArgs| {
+            let (d1, d2, d3, d4) = (uu[0], uu[1], uu[2], uu[3]);
+            rr[0] = 2.0 * d1 + d1 * d1 * d1 * d1 + d2 + 3.0 * d1 * d2 * d2 - 9.0 * d4 + d4 * d4 * d4 * d4 - 0.2;
+            rr[1] = d1 + 3.0 * d1 * d1 * d2 + 10.0 * d2 + 4.0 * d2 * d2 + 2.0 * d2 * d3 - 8.0 * d3 + 7.0 * d4 + 0.1;
+            rr[2] = -8.0 * d2 + d2 * d2 + 3.0 * d3 + d3 * d3 + 2.0 * d4;
+            rr[3] = -9.0 * d1 + 4.0 * d1 * d4 * d4 * d4 + 7.0 * d2 + 2.0 * d3 + 5.0 * d4 - 0.5;
+            Ok(())
+        });
+        if with_jacobian {
+            system
+                .set_jacobian(None, Sym::No, |jj: &mut CooMatrix, uu: &Vector, _args: &mut NoArgs| {
+                    let (d1, d2, d3, d4) = (uu[0], uu[1], uu[2], uu[3]);
+                    jj.reset();
+                    jj.put(0, 0, 2.0 + 4.0 * d1 * d1 * d1 + 3.0 * d2 * d2)?;
+                    jj.put(0, 1, 1.0 + 6.0 * d1 * d2)?;
+                    jj.put(0, 3, -9.0 + 4.0 * d4 * d4 * d4)?;
+                    jj.put(1, 0, 1.0 + 6.0 * d1 * d2)?;
+                    jj.put(1, 1, 10.0 + 3.0 * d1 * d1 + 8.0 * d2 + 2.0 * d3)?;
+                    jj.put(1, 2, -8.0 + 2.0 * d2)?;
+                    jj.put(1, 3, 7.0)?;
+                    jj.put(2, 1, -8.0 + 2.0 * d2)?;
+                    jj.put(2, 2, 3.0 + 2.0 * d3)?;
+                    jj.put(2, 3, 2.0)?;
+                    jj.put(3, 0, -9.0 + 4.0 * d4 * d4 * d4)?;
+                    jj.put(3, 1, 7.0)?;
+                    jj.put(3, 2, 2.0)?;
+                    jj.put(3, 3, 5.0 + 12.0 * d1 * d4 * d4)?;
+                    Ok(())
+                })
+                .unwrap();
+        }
+        system
+    }
+
+    #[test]
+    fn new_and_solve_capture_errors() {
+        let mut params = NonlinParams::new();
+        params.max_iterations = 0;
+        assert_eq!(
+            NonlinSolver::new(Some(params)).err(),
+            Some("the maximum number of iterations must be ≥ 1")
+        );
+        let system = four_equations(true);
+        let mut solver = NonlinSolver::new(None).unwrap();
+        let mut x = Vector::new(3);
+        assert_eq!(
+            solver.solve(&mut x, &system, &mut 0).err(),
+            Some("the dimension of the vector of unknowns x is incorrect")
+        );
+        params = NonlinParams::new();
+        params.max_iterations = 1;
+        let mut solver = NonlinSolver::new(Some(params)).unwrap();
+        let mut x = Vector::new(4);
+        assert_eq!(
+            solver.solve(&mut x, &system, &mut 0).err(),
+            Some("the nonlinear solver did not converge (max number of iterations reached)")
+        );
+        assert!(!solver.get_stats().converged);
+        assert_eq!(solver.get_stats().iterations, 1);
+        assert_eq!(solver.get_stats().history.len(), 2);
+    }
+
+    #[test]
+    fn newton_works() {
+        let x_correct = &[
+            -0.19438648487190846,
+            -0.07740560514997982,
+            -0.16836354961368752,
+            -0.07424605245296569,
+        ];
+        for global in [NonlinGlobal::No, NonlinGlobal::LineSearch, NonlinGlobal::TrustRegion] {
+            for with_jacobian in [true, false] {
+                let system = four_equations(with_jacobian);
+                let mut params = NonlinParams::new();
+                params.global = global;
+                params.tol_abs = 1e-13;
+                let mut solver = NonlinSolver::new(Some(params)).unwrap();
+                let mut x = Vector::new(4);
+                solver.solve(&mut x, &system, &mut 0).unwrap();
+                vec_approx_eq(&x, x_correct, 1e-12);
+                let stats = solver.get_stats();
+                assert!(stats.converged);
+                assert!(stats.iterations < 10);
+                assert_eq!(stats.history.len(), stats.iterations + 1);
+                assert_eq!(stats.n_jacobian, stats.iterations);
+                assert_eq!(stats.n_lin_sol, stats.iterations);
+                assert!(stats.residual_norm <= 1e-13);
+                if !with_jacobian {
+                    assert!(stats.n_function > 5 * stats.iterations);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn globalization_works() {
+        // F(x) = atan(x) with x₀ = 2 diverges with full Newton steps
+        let mut system = NonlinSystem::new(1, |f, x, _args: &mut NoArgs| {
+            f[0] = f64::atan(x[0]);
+            Ok(())
+        });
+        system
+            .set_jacobian(Some(1), Sym::No, |jj, x, _args: &mut NoArgs| {
+                jj.reset();
+                jj.put(0, 0, 1.0 / (1.0 + x[0] * x[0]))
+            })
+            .unwrap();
+        let mut params = NonlinParams::new();
+        params.global = NonlinGlobal::No;
+        params.max_iterations = 20;
+        let mut solver = NonlinSolver::new(Some(params)).unwrap();
+        let mut x = Vector::from(&[2.0]);
+        assert!(solver.solve(&mut x, &system, &mut 0).is_err());
+        for global in [NonlinGlobal::LineSearch, NonlinGlobal::TrustRegion] {
+            params.global = global;
+            params.trust_region_radius = 10.0; // the first (full) Newton step is rejected
+            let mut solver = NonlinSolver::new(Some(params)).unwrap();
+            let mut x = Vector::from(&[2.0]);
+            solver.solve(&mut x, &system, &mut 0).unwrap();
+            assert!(f64::abs(x[0]) < 1e-10);
+            let stats = solver.get_stats();
+            if global == NonlinGlobal::LineSearch {
+                assert!(stats.n_backtracks > 0);
+            } else {
+                assert!(stats.n_rejected > 0);
+            }
+        }
+    }
+
+    #[test]
+    fn newton_krylov_works() {
+        let x_correct = &[
+            -0.19438648487190846,
+            -0.07740560514997982,
+            -0.16836354961368752,
+            -0.07424605245296569,
+        ];
+        for (genie, precond) in [
+            (Genie::Native, Precond::No),
+            (Genie::BiCgStab, Precond::No),
+            (Genie::Gmres, Precond::Ilu0),
+        ] {
+            let system = four_equations(precond != Precond::No);
+            let mut params = NonlinParams::new();
+            params.method = NonlinMethod::NewtonKrylov;
+            params.genie = genie;
+            params.lin_sol_params.krylov_precond = precond;
+            params.tol_abs = 1e-12;
+            let mut solver = NonlinSolver::new(Some(params)).unwrap();
+            let mut x = Vector::new(4);
+            solver.solve(&mut x, &system, &mut 0).unwrap();
+            vec_approx_eq(&x, x_correct, 1e-10);
+            let stats = solver.get_stats();
+            assert!(stats.converged);
+            assert!(stats.n_lin_iterations > 0);
+            assert_eq!(stats.n_rejected, 0);
+            if precond == Precond::No {
+                assert_eq!(stats.n_jacobian, 0);
+            } else {
+                assert!(stats.n_jacobian >= 1);
+                assert!(stats.n_jacobian < stats.iterations);
+            }
+        }
+
+        // the trust region is not available
+        let mut params = NonlinParams::new();
+        params.method = NonlinMethod::NewtonKrylov;
+        params.global = NonlinGlobal::TrustRegion;
+        assert_eq!(
+            NonlinSolver::new(Some(params)).err(),
+            Some("the trust region requires the Newton method (the Jacobian matrix is needed)")
+        );
+    }
+
+    #[test]
+    fn newton_krylov_lags_the_preconditioner() {
+        let system = four_equations(true);
+        let mut params = NonlinParams::new();
+        params.method = NonlinMethod::NewtonKrylov;
+        params.genie = Genie::Gmres;
+        params.lin_sol_params.krylov_precond = Precond::Ilu0;
+        params.tol_abs = 1e-12;
+
+        // refresh at every iteration
+        params.newton_krylov_precond_stall_ratio = 0.0;
+        let mut solver = NonlinSolver::new(Some(params)).unwrap();
+        let mut x = Vector::new(4);
+        solver.solve(&mut x, &system, &mut 0).unwrap();
+        let stats = solver.get_stats();
+        assert_eq!(stats.n_jacobian, stats.iterations);
+
+        // refresh only when the convergence stalls
+        params.newton_krylov_precond_stall_ratio = 0.5;
+        let mut solver = NonlinSolver::new(Some(params)).unwrap();
+        let mut x_lagged = Vector::new(4);
+        solver.solve(&mut x_lagged, &system, &mut 0).unwrap();
+        vec_approx_eq(&x_lagged, &x, 1e-10);
+        let stats = solver.get_stats();
+        assert!(stats.n_jacobian < stats.iterations);
+        let n_stalls = (1..stats.iterations)
+            .filter(|&k| stats.history[k] > 0.5 * stats.history[k - 1])
+            .count();
+        assert_eq!(stats.n_jacobian, 1 + n_stalls);
+    }
+}
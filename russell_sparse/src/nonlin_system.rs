@@ -0,0 +1,189 @@
+use super::{CooMatrix, Sym};
+use crate::StrError;
+use russell_lab::Vector;
+
+/// Defines the residual function `f := F(x)`
+type NonlinFunction<'a, A> = Box<dyn Fn(&mut Vector, &Vector, &mut A) -> Result<(), StrError> + 'a>;
+
+/// Defines the Jacobian function `J := ∂F/∂x`
+type NonlinJacobian<'a, A> = Box<dyn Fn(&mut CooMatrix, &Vector, &mut A) -> Result<(), StrError> + 'a>;
+
+/// Defines a system of nonlinear algebraic equations
+///
+/// The system is defined by:
+///
+/// ```text
+/// {F}({x}) = {0}
+/// ```
+///
+/// where `{x}` is the vector of unknowns and `{F}` is the vector of residuals, with dimension `ndim`.
+///
+/// The (optional) sparse Jacobian matrix is defined by:
+///
+/// ```text
+///          ∂{F}
+/// [J]({x}) = ————
+///          ∂{x}
+/// ```
+///
+/// **Note:** If the (analytical) Jacobian function is not configured, the Newton method
+/// computes the Jacobian matrix numerically (see [crate::numerical_jacobian]) and the
+/// Newton-Krylov method does not need the Jacobian matrix at all.
+///
+/// # Generics
+///
+/// * `A` -- generic argument to assist in the F(x) and Jacobian functions.
+///   It may be simply [russell_lab::NoArgs] indicating that no arguments are needed.
+pub struct NonlinSystem<'a, A> {
+    /// System dimension
+    pub(crate) ndim: usize,
+
+    /// Residual function
+    pub(crate) function: NonlinFunction<'a, A>,
+
+    /// Jacobian function
+    pub(crate) jacobian: Option<NonlinJacobian<'a, A>>,
+
+    /// Number of non-zeros in the Jacobian matrix
+    pub(crate) jac_nnz: usize,
+
+    /// Symmetric type of the Jacobian matrix
+    pub(crate) jac_sym: Sym,
+}
+
+impl<'a, A> NonlinSystem<'a, A> {
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `ndim` -- dimension of the system (number of equations = number of unknowns)
+    /// * `function` -- implements the residual function: `F(x)`; use `|f, x, args|` to calculate `f := F(x)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_lab::NoArgs;
+    /// use russell_sparse::prelude::*;
+    ///
+    /// let system = NonlinSystem::new(2, |f, x, _args: &mut NoArgs| {
+    ///     f[0] = x[0] * x[0] + x[1] * x[1] - 4.0;
+    ///     f[1] = x[0] - x[1];
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn new(ndim: usize, function: impl Fn(&mut Vector, &Vector, &mut A) -> Result<(), StrError> + 'a) -> Self {
+        NonlinSystem {
+            ndim,
+            function: Box::new(function),
+            jacobian: None,
+            jac_nnz: ndim * ndim,
+            jac_sym: Sym::No,
+        }
+    }
+
+    /// Sets a function to calculate the Jacobian matrix (analytical Jacobian)
+    ///
+    /// Use `|jj, x, args|` or `|jj: &mut CooMatrix, x: &Vector, args: &mut A|`
+    ///
+    /// # Input
+    ///
+    /// * `nnz` -- the number of non-zeros in the Jacobian; use None to indicate a dense matrix with:
+    ///     * `nnz = (ndim + ndim²) / 2` if triangular
+    ///     * `nnz = ndim²` otherwise
+    /// * `symmetric` -- specifies the symmetric type of the Jacobian matrix (see [crate::Genie::get_sym])
+    /// * `callback` -- the function to calculate the Jacobian matrix. **Note:** the function
+    ///   must call `jj.reset()` before putting the values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_lab::NoArgs;
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut system = NonlinSystem::new(2, |f, x, _args: &mut NoArgs| {
+    ///         f[0] = x[0] * x[0] + x[1] * x[1] - 4.0;
+    ///         f[1] = x[0] - x[1];
+    ///         Ok(())
+    ///     });
+    ///     system.set_jacobian(Some(4), Sym::No, |jj, x, _args: &mut NoArgs| {
+    ///         jj.reset();
+    ///         jj.put(0, 0, 2.0 * x[0])?;
+    ///         jj.put(0, 1, 2.0 * x[1])?;
+    ///         jj.put(1, 0, 1.0)?;
+    ///         jj.put(1, 1, -1.0)?;
+    ///         Ok(())
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_jacobian(
+        &mut self,
+        nnz: Option<usize>,
+        symmetric: Sym,
+        callback: impl Fn(&mut CooMatrix, &Vector, &mut A) -> Result<(), StrError> + 'a,
+    ) -> Result<(), StrError> {
+        if let Some(value) = nnz {
+            if value < 1 {
+                return Err("the number of non-zeros of the Jacobian matrix must be ≥ 1");
+            }
+        }
+        self.jac_nnz = match nnz {
+            Some(value) => value,
+            None => {
+                if symmetric.triangular() {
+                    (self.ndim + self.ndim * self.ndim) / 2
+                } else {
+                    self.ndim * self.ndim
+                }
+            }
+        };
+        self.jac_sym = symmetric;
+        self.jacobian = Some(Box::new(callback));
+        Ok(())
+    }
+
+    /// Returns the dimension of the system
+    pub fn get_ndim(&self) -> usize {
+        self.ndim
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::NonlinSystem;
+    use crate::{CooMatrix, Sym};
+    use russell_lab::{NoArgs, Vector};
+
+    #[test]
+    fn new_and_set_jacobian_work() {
+        let mut system = NonlinSystem::new(3, |f, x, _args: &mut NoArgs| {
+            f[0] = x[0];
+            Ok(())
+        });
+        assert_eq!(system.get_ndim(), 3);
+        assert_eq!(system.jac_nnz, 9);
+        assert_eq!(system.jac_sym, Sym::No);
+        assert!(system.jacobian.is_none());
+        let jacobian = |jj: &mut CooMatrix, _x: &Vector, _args: &mut NoArgs| {
+            jj.reset();
+            jj.put(0, 0, 1.0)
+        };
+        assert_eq!(
+            system.set_jacobian(Some(0), Sym::No, jacobian).err(),
+            Some("the number of non-zeros of the Jacobian matrix must be ≥ 1")
+        );
+        system.set_jacobian(None, Sym::YesLower, jacobian).unwrap();
+        assert_eq!(system.jac_nnz, 6);
+        assert_eq!(system.jac_sym, Sym::YesLower);
+        system.set_jacobian(Some(1), Sym::No, jacobian).unwrap();
+        assert_eq!(system.jac_nnz, 1);
+        let mut jj = CooMatrix::new(3, 3, 1, Sym::No).unwrap();
+        let x = Vector::new(3);
+        (system.jacobian.as_ref().unwrap())(&mut jj, &x, &mut 0).unwrap();
+        assert_eq!(jj.get_info().2, 1);
+    }
+}
@@ -0,0 +1,346 @@
+use super::{partition_by_nnz, NumCsrMatrix, Sym};
+use crate::worker_pool::WorkerPool;
+use crate::StrError;
+use num_traits::{Num, NumCast};
+use russell_lab::NumVector;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ops::{AddAssign, MulAssign};
+
+/// Holds the threads and the workspace for repeated parallel matrix-vector products with a CSR matrix
+///
+/// The rows are partitioned into contiguous blocks with (approximately) the same number of non-zeros
+/// and each block is computed by a persistent thread. Thus, the threads are spawned once by
+/// [ParallelMatVec::new] and reused by all calls to [ParallelMatVec::mat_vec_mul] (e.g., in the
+/// iterations of a Krylov solver or in a time-stepping loop).
+///
+/// If the matrix is symmetric and triangular (lower or upper), the full matrix is not assembled.
+/// Instead, the transposed pattern of the stored triangle is computed once; then, each thread
+/// computes the complete rows of its block (the stored part plus the mirrored part) and writes
+/// only to its own rows. Thus, no buffer (nor reduction) is required.
+///
+/// **Note:** The structure of the matrix (dimensions, symmetric flag, and sparsity pattern) must
+/// be the same as the one given to [ParallelMatVec::new]; the values may change.
+///
+/// # Examples
+///
+/// ```
+/// use russell_lab::{vec_approx_eq, Vector};
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // ┌          ┐
+///     // │  2 -1  . │
+///     // │ -1  2 -1 │
+///     // │  . -1  2 │
+///     // └          ┘
+///     let mut coo = CooMatrix::new(3, 3, 5, Sym::YesLower)?;
+///     coo.put(0, 0, 2.0)?;
+///     coo.put(1, 0, -1.0)?;
+///     coo.put(1, 1, 2.0)?;
+///     coo.put(2, 1, -1.0)?;
+///     coo.put(2, 2, 2.0)?;
+///     let csr = CsrMatrix::from_coo(&coo)?;
+///     let plan = ParallelMatVec::new(&csr, 2);
+///     let u = Vector::from(&[1.0, 2.0, 3.0]);
+///     let mut v = Vector::new(3);
+///     for _ in 0..3 {
+///         plan.mat_vec_mul(&mut v, 1.0, &csr, &u)?;
+///         vec_approx_eq(&v, &[0.0, 0.0, 4.0], 1e-15);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct ParallelMatVec {
+    /// Holds the symmetric flag of the matrix
+    symmetric: Sym,
+
+    /// Holds the number of rows of the matrix
+    nrow: usize,
+
+    /// Holds the number of columns of the matrix
+    ncol: usize,
+
+    /// Holds the number of non-zeros (with duplicates) of the matrix
+    nnz: usize,
+
+    /// Holds the boundaries of the blocks of rows (one block per thread)
+    bounds: Vec<usize>,
+
+    /// Holds the pointers of the transposed pattern of the stored triangle (len = nrow + 1; empty if not triangular)
+    mirror_pointers: Vec<usize>,
+
+    /// Holds the column indices of the transposed pattern of the stored triangle (the diagonal is excluded)
+    mirror_indices: Vec<usize>,
+
+    /// Holds the positions of the corresponding values in the CSR matrix
+    mirror_positions: Vec<usize>,
+
+    /// Holds the worker threads (None if only one thread is required)
+    pool: Option<WorkerPool>,
+}
+
+impl ParallelMatVec {
+    /// Allocates a new instance (spawns the threads and computes the workspace)
+    ///
+    /// # Input
+    ///
+    /// * `csr` -- the CSR matrix
+    /// * `num_threads` -- number of threads; use 0 to select the available parallelism of the machine
+    ///
+    /// **Note:** If only one thread is required (e.g., `num_threads = 1` or `nrow = 1`), no thread is
+    /// spawned and [NumCsrMatrix::mat_vec_mul] is called.
+    pub fn new<T>(csr: &NumCsrMatrix<T>, num_threads: usize) -> Self
+    where
+        T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+    {
+        let nt = if num_threads == 0 {
+            std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+        } else {
+            num_threads
+        };
+        let nt = usize::min(nt, csr.nrow);
+        let nnz = csr.row_pointers[csr.nrow] as usize;
+        let mut plan = ParallelMatVec {
+            symmetric: csr.symmetric,
+            nrow: csr.nrow,
+            ncol: csr.ncol,
+            nnz,
+            bounds: Vec::new(),
+            mirror_pointers: Vec::new(),
+            mirror_indices: Vec::new(),
+            mirror_positions: Vec::new(),
+            pool: None,
+        };
+        if nt <= 1 {
+            return plan;
+        }
+        plan.bounds = partition_by_nnz(&csr.row_pointers, nt);
+        if csr.symmetric.triangular() {
+            // count the off-diagonal entries of each column of the stored triangle
+            let mut pointers = vec![0; csr.nrow + 1];
+            for i in 0..csr.nrow {
+                for p in csr.row_pointers[i]..csr.row_pointers[i + 1] {
+                    let j = csr.col_indices[p as usize] as usize;
+                    if i != j {
+                        pointers[j + 1] += 1;
+                    }
+                }
+            }
+            for j in 0..csr.nrow {
+                pointers[j + 1] += pointers[j];
+            }
+            // scatter the row indices and the positions of the values
+            let mut next = pointers.clone();
+            let count = pointers[csr.nrow];
+            let mut indices = vec![0; count];
+            let mut positions = vec![0; count];
+            for i in 0..csr.nrow {
+                for p in csr.row_pointers[i]..csr.row_pointers[i + 1] {
+                    let j = csr.col_indices[p as usize] as usize;
+                    if i != j {
+                        indices[next[j]] = i;
+                        positions[next[j]] = p as usize;
+                        next[j] += 1;
+                    }
+                }
+            }
+            plan.mirror_pointers = pointers;
+            plan.mirror_indices = indices;
+            plan.mirror_positions = positions;
+        }
+        plan.pool = Some(WorkerPool::new(nt));
+        plan
+    }
+
+    /// Returns the number of threads (one if no thread has been spawned)
+    pub fn num_threads(&self) -> usize {
+        match &self.pool {
+            Some(pool) => pool.num_threads(),
+            None => 1,
+        }
+    }
+
+    /// Performs the matrix-vector multiplication in parallel
+    ///
+    /// ```text
+    ///  v  :=  α ⋅  a   ⋅  u
+    /// (m)        (m,n)   (n)
+    /// ```
+    ///
+    /// # Input
+    ///
+    /// * `csr` -- the CSR matrix with the same structure as the one given to [ParallelMatVec::new]
+    /// * `u` -- Vector with dimension equal to the number of columns of the matrix
+    ///
+    /// # Output
+    ///
+    /// * `v` -- Vector with dimension equal to the number of rows of the matrix
+    pub fn mat_vec_mul<T>(
+        &self,
+        v: &mut NumVector<T>,
+        alpha: T,
+        csr: &NumCsrMatrix<T>,
+        u: &NumVector<T>,
+    ) -> Result<(), StrError>
+    where
+        T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize + Send + Sync,
+    {
+        if csr.symmetric != self.symmetric
+            || csr.nrow != self.nrow
+            || csr.ncol != self.ncol
+            || csr.row_pointers[csr.nrow] as usize != self.nnz
+        {
+            return Err("the CSR matrix must have the same structure as the one given to ParallelMatVec::new");
+        }
+        if u.dim() != self.ncol {
+            return Err("u vector is incompatible");
+        }
+        if v.dim() != self.nrow {
+            return Err("v vector is incompatible");
+        }
+        let pool = match &self.pool {
+            Some(pool) => pool,
+            None => return csr.mat_vec_mul(v, alpha, u),
+        };
+        let mirror = !self.mirror_pointers.is_empty();
+        let (mirror_pointers, mirror_indices, mirror_positions) =
+            (&self.mirror_pointers, &self.mirror_indices, &self.mirror_positions);
+        let uu = u.as_data();
+        let mut jobs: Vec<Box<dyn FnOnce() + Send + '_>> = Vec::with_capacity(pool.num_threads());
+        let mut rest = v.as_mut_data().as_mut_slice();
+        for t in 0..pool.num_threads() {
+            let (start, end) = (self.bounds[t], self.bounds[t + 1]);
+            let (chunk, tail) = rest.split_at_mut(end - start);
+            rest = tail;
+            jobs.push(Box::new(move || {
+                for (i, vi) in (start..end).zip(chunk.iter_mut()) {
+                    let mut sum = T::zero();
+                    for p in csr.row_pointers[i]..csr.row_pointers[i + 1] {
+                        let j = csr.col_indices[p as usize] as usize;
+                        sum += csr.values[p as usize] * uu[j];
+                    }
+                    if mirror {
+                        for q in mirror_pointers[i]..mirror_pointers[i + 1] {
+                            sum += csr.values[mirror_positions[q]] * uu[mirror_indices[q]];
+                        }
+                    }
+                    *vi = alpha * sum;
+                }
+            }));
+        }
+        pool.run(jobs);
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::ParallelMatVec;
+    use crate::{CooMatrix, CsrMatrix, Samples, Sym};
+    use russell_lab::{complex_vec_approx_eq, cpx, vec_approx_eq, Complex64, ComplexVector, Vector};
+
+    #[test]
+    fn mat_vec_mul_captures_errors() {
+        let (_, _, csr, _) = Samples::rectangular_3x4();
+        let plan = ParallelMatVec::new(&csr, 2);
+        let (_, _, other, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let u = Vector::new(4);
+        let mut v = Vector::new(3);
+        assert_eq!(
+            plan.mat_vec_mul(&mut v, 1.0, &other, &u).err(),
+            Some("the CSR matrix must have the same structure as the one given to ParallelMatVec::new")
+        );
+        let u = Vector::new(3);
+        assert_eq!(
+            plan.mat_vec_mul(&mut v, 1.0, &csr, &u).err(),
+            Some("u vector is incompatible")
+        );
+        let u = Vector::new(4);
+        let mut v = Vector::new(2);
+        assert_eq!(
+            plan.mat_vec_mul(&mut v, 1.0, &csr, &u).err(),
+            Some("v vector is incompatible")
+        );
+    }
+
+    #[test]
+    fn mat_vec_mul_works() {
+        //   5  -2  .  1
+        //  10  -4  .  2
+        //  15  -6  .  3
+        let (_, _, csr, _) = Samples::rectangular_3x4();
+        let u = Vector::from(&[1.0, 3.0, 8.0, 5.0]);
+        let mut v = Vector::new(3);
+        for nt in [0, 1, 2, 3, 8] {
+            let plan = ParallelMatVec::new(&csr, nt);
+            assert!(plan.num_threads() <= 3);
+            for _ in 0..3 {
+                plan.mat_vec_mul(&mut v, 2.0, &csr, &u).unwrap();
+                vec_approx_eq(&v, &[8.0, 16.0, 24.0], 1e-15);
+            }
+        }
+        assert_eq!(ParallelMatVec::new(&csr, 1).num_threads(), 1);
+        assert_eq!(ParallelMatVec::new(&csr, 2).num_threads(), 2);
+    }
+
+    #[test]
+    fn mat_vec_mul_works_symmetric() {
+        // the lower, upper, and full representations yield the same results
+        let (_, _, lower, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let (_, _, upper, _) = Samples::mkl_symmetric_5x5_upper(false, false);
+        let (_, _, full, _) = Samples::mkl_symmetric_5x5_full();
+        let u = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut v = Vector::new(5);
+        for csr in [&lower, &upper, &full] {
+            for nt in [2, 3, 5] {
+                let plan = ParallelMatVec::new(csr, nt);
+                plan.mat_vec_mul(&mut v, 2.0, csr, &u).unwrap();
+                vec_approx_eq(&v, &[96.0, 5.0, 84.0, 6.5, 166.0], 1e-15);
+            }
+        }
+
+        // larger matrix with duplicates: compare with the serial version
+        let n = 50;
+        let mut coo = CooMatrix::new(n, n, 4 * n, Sym::YesLower).unwrap();
+        for i in 0..n {
+            coo.put(i, i, 4.0 + i as f64).unwrap();
+            if i > 0 {
+                coo.put(i, i - 1, -1.0).unwrap();
+                coo.put(i, i - 1, -0.5).unwrap(); // duplicate
+            }
+            if i > 7 {
+                coo.put(i, i - 7, 0.25 * i as f64).unwrap();
+            }
+        }
+        let csr = CsrMatrix::from_coo(&coo).unwrap();
+        let u = Vector::initialized(n, |i| f64::sin(i as f64));
+        let mut correct = Vector::new(n);
+        csr.mat_vec_mul(&mut correct, 1.5, &u).unwrap();
+        let plan = ParallelMatVec::new(&csr, 4);
+        let mut v = Vector::new(n);
+        for _ in 0..3 {
+            plan.mat_vec_mul(&mut v, 1.5, &csr, &u).unwrap();
+            vec_approx_eq(&v, &correct, 1e-13);
+        }
+    }
+
+    #[test]
+    fn mat_vec_mul_works_complex() {
+        let (_, _, csr, _) = Samples::complex_rectangular_4x3();
+        let u = ComplexVector::from(&[cpx!(1.0, 1.0), cpx!(3.0, 1.0), cpx!(5.0, -1.0)]);
+        let mut v = ComplexVector::new(4);
+        let plan = ParallelMatVec::new(&csr, 3);
+        plan.mat_vec_mul(&mut v, cpx!(2.0, 4.0), &csr, &u).unwrap();
+        let correct = &[
+            cpx!(-40.0, 80.0),
+            cpx!(-10.0, 110.0),
+            cpx!(-64.0, 112.0),
+            cpx!(-2.0, 6.0),
+        ];
+        complex_vec_approx_eq(&v, correct, 1e-15);
+    }
+}
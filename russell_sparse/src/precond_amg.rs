@@ -0,0 +1,702 @@
+use super::{FactorKind, FactorScalar, NumCooMatrix, NumCscMatrix, NumCsrMatrix, Ordering, PrecondTrait, RowMatrix};
+use super::{SparseFactor, Sym};
+use crate::StrError;
+use russell_lab::{Matrix, NumVector};
+
+/// Defines the relative tolerance below which a (locally orthogonalized) near-null-space vector is dropped
+const NEAR_NULL_SPACE_DROP_TOL: f64 = 1e-10;
+
+/// Holds the rows of the tentative prolongator, the number of coarse unknowns, and the coarse near-null space
+type Tentative<T> = (Vec<Vec<(usize, T)>>, usize, Option<Matrix>);
+
+/// Implements an algebraic multigrid (AMG) preconditioner based on smoothed aggregation
+///
+/// The hierarchy of levels is built as follows:
+///
+/// 1. The strength of connection is `|aᵢⱼ| ≥ θ √|aᵢᵢ aⱼⱼ|` (θ is the strength threshold)
+/// 2. The nodes are grouped into aggregates using the strong connections
+/// 3. The tentative prolongator P₀ is the (normalized) piecewise-constant interpolation over the aggregates;
+///    alternatively, if a near-null space B is given (see [PrecondAMG::set_near_null_space]), the columns of P₀
+///    are the orthonormalized restrictions of B to the aggregates (i.e., `B = P₀·Bc` with Bc being the near-null
+///    space of the coarse level)
+/// 4. The prolongator is smoothed by a damped Jacobi step: `P = (I - ω D⁻¹ A) P₀` with `ω = 4 / (3 ρ(D⁻¹A))`
+/// 5. The restriction is `R = Pᵀ` and the coarse matrix is `Ac = R A P`
+///
+/// The preconditioner applies one V-cycle with Gauss-Seidel smoothing (forward sweeps before
+/// and backward sweeps after the coarse correction). The coarsest level is solved with the native LU factorization.
+///
+/// If only the values of the matrix change (e.g., in Newton iterations), [PrecondTrait::update] reuses the
+/// aggregates and the prolongators and only recomputes the coarse matrices and the coarse factorization.
+///
+/// **Note:** This preconditioner is best suited to symmetric positive-definite matrices
+/// arising from elliptic problems (e.g., diffusion or elasticity). For complex matrices,
+/// the moduli replace the absolute values above; since `R = Pᵀ` (not `Pᴴ`), the coarse matrices
+/// of a complex symmetric matrix are also complex symmetric.
+///
+/// Reference: Vaněk P, Mandel J, Brezina M (1996) Algebraic multigrid by smoothed aggregation
+/// for second and fourth order elliptic problems, Computing, 56:179-196
+pub struct PrecondAMG<T>
+where
+    T: FactorScalar,
+{
+    /// Holds the strength threshold θ
+    strength: f64,
+
+    /// Holds the maximum number of levels
+    max_levels: usize,
+
+    /// Holds the maximum dimension of the coarsest level
+    coarse_size: usize,
+
+    /// Holds the number of pre- and post-smoothing sweeps
+    sweeps: usize,
+
+    /// Holds the near-null space B (n × m), e.g., the rigid-body modes (None => constant vector)
+    near_null_space: Option<Matrix>,
+
+    /// Holds the matrices of all levels (the last one is the coarsest)
+    matrices: Vec<RowMatrix<T>>,
+
+    /// Holds the diagonal of the matrices (except the coarsest)
+    diagonals: Vec<Vec<T>>,
+
+    /// Holds the prolongation operators (one fewer than the number of levels)
+    prolongators: Vec<RowMatrix<T>>,
+
+    /// Holds the restriction operators (one fewer than the number of levels)
+    restrictors: Vec<RowMatrix<T>>,
+
+    /// Holds the LU factors of the coarsest matrix
+    coarse_factor: Option<SparseFactor<T>>,
+
+    /// Holds the approximate solutions of all levels
+    xx: Vec<Vec<T>>,
+
+    /// Holds the right-hand sides of all levels
+    bb: Vec<Vec<T>>,
+
+    /// Holds the residuals of all levels
+    rr: Vec<Vec<T>>,
+}
+
+impl<T> PrecondAMG<T>
+where
+    T: FactorScalar,
+{
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `strength` -- the strength threshold θ ≥ 0
+    /// * `max_levels` -- the maximum number of levels (≥ 1)
+    /// * `coarse_size` -- the maximum dimension of the coarsest level (≥ 1)
+    /// * `sweeps` -- the number of pre- and post-smoothing sweeps (≥ 1)
+    pub fn new(strength: f64, max_levels: usize, coarse_size: usize, sweeps: usize) -> Result<Self, StrError> {
+        if strength < 0.0 {
+            return Err("the AMG strength threshold must be non-negative");
+        }
+        if max_levels < 1 {
+            return Err("the AMG max number of levels must be ≥ 1");
+        }
+        if coarse_size < 1 {
+            return Err("the AMG coarse size must be ≥ 1");
+        }
+        if sweeps < 1 {
+            return Err("the AMG number of smoothing sweeps must be ≥ 1");
+        }
+        Ok(PrecondAMG {
+            strength,
+            max_levels,
+            coarse_size,
+            sweeps,
+            near_null_space: None,
+            matrices: Vec::new(),
+            diagonals: Vec::new(),
+            prolongators: Vec::new(),
+            restrictors: Vec::new(),
+            coarse_factor: None,
+            xx: Vec::new(),
+            bb: Vec::new(),
+            rr: Vec::new(),
+        })
+    }
+
+    /// Sets (or removes) the near-null space used to build the tentative prolongator
+    ///
+    /// The columns of B (n × m) should approximate the vectors with `A·b ≈ 0`, e.g., the rigid-body modes
+    /// of an elasticity problem (3 columns in 2D and 6 columns in 3D). Each aggregate then yields up to m
+    /// coarse unknowns. By default (None), B is the constant vector.
+    ///
+    /// **Note:** The new near-null space is only used by the next call to `setup`. To use this
+    /// preconditioner with a Krylov solver, allocate a [crate::NumPreconditioner] with `actual` set to
+    /// this structure and call [crate::NumSolverKrylov::set_preconditioner].
+    pub fn set_near_null_space(&mut self, near_null_space: Option<Matrix>) -> Result<(), StrError> {
+        if let Some(bb) = near_null_space.as_ref() {
+            if bb.ncol() < 1 {
+                return Err("the near-null space must have at least one column");
+            }
+        }
+        self.near_null_space = near_null_space;
+        Ok(())
+    }
+
+    /// Returns the number of levels (available after setup)
+    pub fn get_num_levels(&self) -> usize {
+        self.matrices.len()
+    }
+
+    /// Returns the dimension of the matrix of each level (available after setup)
+    pub fn get_level_dims(&self) -> Vec<usize> {
+        self.matrices.iter().map(|m| m.nrow).collect()
+    }
+
+    /// Groups the nodes into aggregates and returns the aggregate of each node and the number of aggregates
+    fn aggregate(&self, a: &RowMatrix<T>, diag: &[T]) -> (Vec<usize>, usize) {
+        const NONE: usize = usize::MAX;
+        let n = a.nrow;
+
+        // strong connections
+        let mut strong = vec![Vec::new(); n];
+        for i in 0..n {
+            for p in a.row_pointers[i]..a.row_pointers[i + 1] {
+                let j = a.col_indices[p];
+                if j != i && a.values[p].modulus() >= self.strength * f64::sqrt((diag[i] * diag[j]).modulus()) {
+                    strong[i].push(j);
+                }
+            }
+        }
+
+        // pass 1: nodes whose strong neighbors are all free become the root of a new aggregate
+        let mut agg = vec![NONE; n];
+        let mut count = 0;
+        for i in 0..n {
+            if agg[i] == NONE && strong[i].iter().all(|j| agg[*j] == NONE) {
+                agg[i] = count;
+                for j in &strong[i] {
+                    agg[*j] = count;
+                }
+                count += 1;
+            }
+        }
+
+        // pass 2: the remaining nodes join a neighboring aggregate
+        let first_pass = agg.clone();
+        for i in 0..n {
+            if agg[i] == NONE {
+                if let Some(j) = strong[i].iter().find(|j| first_pass[**j] != NONE) {
+                    agg[i] = first_pass[*j];
+                }
+            }
+        }
+
+        // pass 3: the still remaining nodes form new aggregates with their free neighbors
+        for i in 0..n {
+            if agg[i] == NONE {
+                agg[i] = count;
+                for j in &strong[i] {
+                    if agg[*j] == NONE {
+                        agg[*j] = count;
+                    }
+                }
+                count += 1;
+            }
+        }
+        (agg, count)
+    }
+
+    /// Computes the tentative prolongator P₀ and the near-null space of the coarse level
+    fn tentative(agg: &[usize], count: usize, bb: Option<&Matrix>) -> Tentative<T> {
+        let bb = match bb {
+            Some(bb) => bb,
+            None => {
+                // normalized piecewise-constant interpolation
+                let mut sizes = vec![0_usize; count];
+                for k in agg {
+                    sizes[*k] += 1;
+                }
+                let rows = agg
+                    .iter()
+                    .map(|k| vec![(*k, T::from_real(1.0 / f64::sqrt(sizes[*k] as f64)))])
+                    .collect();
+                return (rows, count, None);
+            }
+        };
+
+        // nodes of each aggregate
+        let mut members = vec![Vec::new(); count];
+        for (i, k) in agg.iter().enumerate() {
+            members[*k].push(i);
+        }
+
+        // local QR decompositions B_k = Q_k·R_k by the modified Gram-Schmidt method
+        let m = bb.ncol();
+        let mut rows = vec![Vec::new(); agg.len()];
+        let mut coarse_rows: Vec<Vec<f64>> = Vec::new();
+        for nodes in &members {
+            let mut basis: Vec<Vec<f64>> = Vec::new();
+            for c in 0..m {
+                let mut v: Vec<f64> = nodes.iter().map(|i| bb.get(*i, c)).collect();
+                let norm_initial = f64::sqrt(v.iter().map(|x| x * x).sum());
+                for q in &basis {
+                    let r: f64 = q.iter().zip(&v).map(|(a, b)| a * b).sum();
+                    v.iter_mut().zip(q).for_each(|(x, y)| *x -= r * y);
+                }
+                let norm = f64::sqrt(v.iter().map(|x| x * x).sum());
+                if norm > 0.0 && norm > NEAR_NULL_SPACE_DROP_TOL * norm_initial {
+                    v.iter_mut().for_each(|x| *x /= norm);
+                    basis.push(v);
+                }
+            }
+            for q in &basis {
+                let jc = coarse_rows.len();
+                for (loc, i) in nodes.iter().enumerate() {
+                    rows[*i].push((jc, T::from_real(q[loc])));
+                }
+                coarse_rows.push(
+                    (0..m)
+                        .map(|c| nodes.iter().zip(q).map(|(i, qi)| qi * bb.get(*i, c)).sum())
+                        .collect(),
+                );
+            }
+        }
+        let ncoarse = coarse_rows.len();
+        let coarse = Matrix::from(&coarse_rows);
+        (rows, ncoarse, Some(coarse))
+    }
+
+    /// Computes the smoothed prolongator from the tentative prolongator
+    fn prolongator(a: &RowMatrix<T>, diag: &[T], tentative: &[Vec<(usize, T)>], ncoarse: usize) -> RowMatrix<T> {
+        let n = a.nrow;
+        let p0 = RowMatrix::from_rows(ncoarse, tentative);
+
+        // spectral radius estimate of D⁻¹A (Gershgorin)
+        let mut rho: f64 = 0.0;
+        for (i, d) in diag.iter().enumerate() {
+            let sum: f64 = (a.row_pointers[i]..a.row_pointers[i + 1])
+                .map(|p| a.values[p].modulus())
+                .sum();
+            rho = f64::max(rho, sum / d.modulus());
+        }
+        let omega = 4.0 / (3.0 * rho);
+
+        // P = P₀ - ω D⁻¹ A P₀
+        let ap0 = a.mat_mat_mul(&p0);
+        let mut rows = vec![Vec::new(); n];
+        for i in 0..n {
+            let factor = T::from_real(-omega) / diag[i];
+            let mut row: Vec<(usize, T)> = (ap0.row_pointers[i]..ap0.row_pointers[i + 1])
+                .map(|p| (ap0.col_indices[p], factor * ap0.values[p]))
+                .collect();
+            for &(k, v) in &tentative[i] {
+                match row.binary_search_by(|e| e.0.cmp(&k)) {
+                    Ok(pos) => row[pos].1 += v,
+                    Err(pos) => row.insert(pos, (k, v)),
+                }
+            }
+            rows[i] = row;
+        }
+        RowMatrix::from_rows(ncoarse, &rows)
+    }
+
+    /// Performs Gauss-Seidel sweeps on A·x = b
+    fn gauss_seidel(a: &RowMatrix<T>, diag: &[T], x: &mut [T], b: &[T], sweeps: usize, forward: bool) {
+        let n = a.nrow;
+        for _ in 0..sweeps {
+            for s in 0..n {
+                let i = if forward { s } else { n - 1 - s };
+                let mut sum = b[i];
+                for p in a.row_pointers[i]..a.row_pointers[i + 1] {
+                    let j = a.col_indices[p];
+                    if j != i {
+                        sum -= a.values[p] * x[j];
+                    }
+                }
+                x[i] = sum / diag[i];
+            }
+        }
+    }
+}
+
+impl<T> PrecondTrait<T> for PrecondAMG<T>
+where
+    T: FactorScalar,
+{
+    /// Builds the multigrid hierarchy
+    fn setup(&mut self, csr: &NumCsrMatrix<T>) -> Result<(), StrError> {
+        self.matrices.clear();
+        self.diagonals.clear();
+        self.prolongators.clear();
+        self.restrictors.clear();
+        self.coarse_factor = None;
+        let mut a = RowMatrix::from_csr(csr)?;
+        let mut bb = self.near_null_space.clone();
+        if let Some(bb) = bb.as_ref() {
+            if bb.nrow() != a.nrow {
+                return Err("the number of rows of the near-null space must be equal to the dimension of the matrix");
+            }
+        }
+        while self.matrices.len() + 1 < self.max_levels && a.nrow > self.coarse_size {
+            let diag = a.diagonal();
+            if diag.iter().any(|d| d.modulus() == 0.0) {
+                return Err("the AMG preconditioner requires non-zero diagonal entries");
+            }
+            let (agg, count) = self.aggregate(&a, &diag);
+            let (tentative, ncoarse, bb_coarse) = PrecondAMG::tentative(&agg, count, bb.as_ref());
+            if ncoarse == 0 || ncoarse >= a.nrow {
+                break; // no coarsening is possible
+            }
+            let p = PrecondAMG::prolongator(&a, &diag, &tentative, ncoarse);
+            let r = p.transpose();
+            let coarse = r.mat_mat_mul(&a.mat_mat_mul(&p));
+            self.matrices.push(a);
+            self.diagonals.push(diag);
+            self.prolongators.push(p);
+            self.restrictors.push(r);
+            a = coarse;
+            bb = bb_coarse;
+        }
+
+        // coarsest level
+        let csc = coarse_csc(&a)?;
+        let mut factor = SparseFactor::analyze(&csc, FactorKind::Lu, Ordering::No)?;
+        factor.factorize(&csc, 1.0)?;
+        self.coarse_factor = Some(factor);
+        self.matrices.push(a);
+
+        // work vectors
+        self.xx = self.matrices.iter().map(|m| vec![T::zero(); m.nrow]).collect();
+        self.bb = self.xx.clone();
+        self.rr = self.xx.clone();
+        Ok(())
+    }
+
+    /// Applies one V-cycle: z := M⁻¹·r
+    fn apply(&mut self, z: &mut NumVector<T>, r: &NumVector<T>) -> Result<(), StrError> {
+        let coarse_factor = match self.coarse_factor.as_mut() {
+            Some(f) => f,
+            None => return Err("the preconditioner must be set up first"),
+        };
+        let n = self.matrices[0].nrow;
+        if z.dim() != n || r.dim() != n {
+            return Err("vectors are incompatible with the preconditioner");
+        }
+        let coarsest = self.matrices.len() - 1;
+        self.bb[0].copy_from_slice(r.as_data());
+
+        // restriction phase
+        for l in 0..coarsest {
+            let a = &self.matrices[l];
+            let x = &mut self.xx[l];
+            let res = &mut self.rr[l];
+            x.fill(T::zero());
+            PrecondAMG::gauss_seidel(a, &self.diagonals[l], x, &self.bb[l], self.sweeps, true);
+            a.mat_vec_mul(res, x);
+            for (ri, bi) in res.iter_mut().zip(&self.bb[l]) {
+                *ri = *bi - *ri;
+            }
+            let (_, coarse) = self.bb.split_at_mut(l + 1);
+            self.restrictors[l].mat_vec_mul(&mut coarse[0], res);
+        }
+
+        // coarse solution
+        coarse_factor.solve(&mut self.xx[coarsest], &self.bb[coarsest])?;
+
+        // prolongation phase
+        for l in (0..coarsest).rev() {
+            let (fine, coarse) = self.xx.split_at_mut(l + 1);
+            let x = &mut fine[l];
+            let res = &mut self.rr[l];
+            self.prolongators[l].mat_vec_mul(res, &coarse[0]);
+            for (xi, ei) in x.iter_mut().zip(res.iter()) {
+                *xi += *ei;
+            }
+            let a = &self.matrices[l];
+            PrecondAMG::gauss_seidel(a, &self.diagonals[l], x, &self.bb[l], self.sweeps, false);
+        }
+        z.as_mut_data().copy_from_slice(&self.xx[0]);
+        Ok(())
+    }
+
+    /// Updates the hierarchy with the new values of the matrix (reusing the aggregates and the prolongators)
+    ///
+    /// **Note:** The structure of the matrix must be the same as the one given to `setup`.
+    fn update(&mut self, csr: &NumCsrMatrix<T>) -> Result<(), StrError> {
+        if self.coarse_factor.is_none() {
+            return self.setup(csr);
+        }
+        let mut a = RowMatrix::from_csr(csr)?;
+        if a.row_pointers != self.matrices[0].row_pointers || a.col_indices != self.matrices[0].col_indices {
+            return Err("the AMG hierarchy can only be updated if the structure of the matrix is unchanged");
+        }
+        let coarsest = self.matrices.len() - 1;
+        for l in 0..coarsest {
+            let diag = a.diagonal();
+            if diag.iter().any(|d| d.modulus() == 0.0) {
+                return Err("the AMG preconditioner requires non-zero diagonal entries");
+            }
+            let coarse = self.restrictors[l].mat_mat_mul(&a.mat_mat_mul(&self.prolongators[l]));
+            self.matrices[l] = a;
+            self.diagonals[l] = diag;
+            a = coarse;
+        }
+        let csc = coarse_csc(&a)?;
+        self.coarse_factor.as_mut().unwrap().factorize(&csc, 1.0)?; // unwrap OK because of the check above
+        self.matrices[coarsest] = a;
+        Ok(())
+    }
+}
+
+/// Converts the coarsest matrix to the CSC format
+fn coarse_csc<T>(a: &RowMatrix<T>) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: FactorScalar,
+{
+    let n = a.nrow;
+    let mut coo = NumCooMatrix::new(n, n, usize::max(a.values.len(), 1), Sym::No)?;
+    for i in 0..n {
+        for p in a.row_pointers[i]..a.row_pointers[i + 1] {
+            coo.put(i, a.col_indices[p], a.values[p])?;
+        }
+    }
+    NumCscMatrix::from_coo(&coo)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::PrecondAMG;
+    use crate::{CooMatrix, CsrMatrix, PrecondTrait, Sym};
+    use russell_lab::{approx_eq, vec_approx_eq, vec_norm, Matrix, Norm, Vector};
+
+    /// Returns the matrix of the 2D Poisson problem on an (m × m) grid (5-point stencil)
+    fn poisson_2d(m: usize) -> CsrMatrix {
+        let n = m * m;
+        let mut coo = CooMatrix::new(n, n, 5 * n, Sym::No).unwrap();
+        for i in 0..m {
+            for j in 0..m {
+                let k = i * m + j;
+                coo.put(k, k, 4.0).unwrap();
+                if i > 0 {
+                    coo.put(k, k - m, -1.0).unwrap();
+                }
+                if i + 1 < m {
+                    coo.put(k, k + m, -1.0).unwrap();
+                }
+                if j > 0 {
+                    coo.put(k, k - 1, -1.0).unwrap();
+                }
+                if j + 1 < m {
+                    coo.put(k, k + 1, -1.0).unwrap();
+                }
+            }
+        }
+        CsrMatrix::from_coo(&coo).unwrap()
+    }
+
+    #[test]
+    fn new_and_apply_capture_errors() {
+        assert_eq!(
+            PrecondAMG::<f64>::new(-1.0, 10, 50, 1).err(),
+            Some("the AMG strength threshold must be non-negative")
+        );
+        assert_eq!(
+            PrecondAMG::<f64>::new(0.08, 0, 50, 1).err(),
+            Some("the AMG max number of levels must be ≥ 1")
+        );
+        assert_eq!(
+            PrecondAMG::<f64>::new(0.08, 10, 0, 1).err(),
+            Some("the AMG coarse size must be ≥ 1")
+        );
+        assert_eq!(
+            PrecondAMG::<f64>::new(0.08, 10, 50, 0).err(),
+            Some("the AMG number of smoothing sweeps must be ≥ 1")
+        );
+        let mut pre = PrecondAMG::new(0.08, 10, 50, 1).unwrap();
+        let r = Vector::new(2);
+        let mut z = Vector::new(2);
+        assert_eq!(
+            pre.apply(&mut z, &r).err(),
+            Some("the preconditioner must be set up first")
+        );
+    }
+
+    #[test]
+    fn multilevel_hierarchy_works() {
+        let csr = poisson_2d(20);
+        let mut pre = PrecondAMG::new(0.08, 10, 10, 1).unwrap();
+        pre.setup(&csr).unwrap();
+        assert!(pre.get_num_levels() > 2);
+        for l in 1..pre.get_num_levels() {
+            assert!(pre.matrices[l].nrow < pre.matrices[l - 1].nrow);
+        }
+        assert!(pre.matrices.last().unwrap().nrow <= 10);
+
+        // stationary iterations x ← x + M⁻¹(b - A·x) must converge quickly
+        let n = 400;
+        let b = Vector::filled(n, 1.0);
+        let mut x = Vector::new(n);
+        let mut ax = Vector::new(n);
+        let mut res = Vector::new(n);
+        let mut z = Vector::new(n);
+        for _ in 0..30 {
+            csr.mat_vec_mul(&mut ax, 1.0, &x).unwrap();
+            for i in 0..n {
+                res[i] = b[i] - ax[i];
+            }
+            pre.apply(&mut z, &res).unwrap();
+            for i in 0..n {
+                x[i] += z[i];
+            }
+        }
+        csr.mat_vec_mul(&mut ax, 1.0, &x).unwrap();
+        for i in 0..n {
+            res[i] = b[i] - ax[i];
+        }
+        assert!(vec_norm(&res, Norm::Euc) < 1e-8);
+
+        // a single level with coarse_size ≥ n is the exact solution
+        let mut pre = PrecondAMG::new(0.08, 10, 400, 1).unwrap();
+        pre.setup(&csr).unwrap();
+        assert_eq!(pre.get_num_levels(), 1);
+        pre.apply(&mut z, &b).unwrap();
+        csr.mat_vec_mul(&mut ax, 1.0, &z).unwrap();
+        vec_approx_eq(&ax, b.as_data(), 1e-12);
+    }
+
+    /// Returns the residual norm after a number of stationary iterations x ← x + M⁻¹(b - A·x)
+    fn stationary_residual(pre: &mut PrecondAMG<f64>, csr: &CsrMatrix, iterations: usize) -> f64 {
+        let n = csr.nrow;
+        let b = Vector::filled(n, 1.0);
+        let mut x = Vector::new(n);
+        let mut ax = Vector::new(n);
+        let mut res = Vector::new(n);
+        let mut z = Vector::new(n);
+        for it in 0..=iterations {
+            csr.mat_vec_mul(&mut ax, 1.0, &x).unwrap();
+            for i in 0..n {
+                res[i] = b[i] - ax[i];
+            }
+            if it == iterations {
+                break;
+            }
+            pre.apply(&mut z, &res).unwrap();
+            for i in 0..n {
+                x[i] += z[i];
+            }
+        }
+        vec_norm(&res, Norm::Euc)
+    }
+
+    #[test]
+    fn near_null_space_captures_errors() {
+        let mut pre = PrecondAMG::<f64>::new(0.08, 10, 10, 1).unwrap();
+        assert_eq!(
+            pre.set_near_null_space(Some(Matrix::new(4, 0))).err(),
+            Some("the near-null space must have at least one column")
+        );
+        pre.set_near_null_space(Some(Matrix::new(4, 1))).unwrap();
+        let csr = poisson_2d(3);
+        assert_eq!(
+            pre.setup(&csr).err(),
+            Some("the number of rows of the near-null space must be equal to the dimension of the matrix")
+        );
+    }
+
+    #[test]
+    fn tentative_reproduces_the_near_null_space() {
+        // two aggregates: {0, 1, 2} and {3, 4}; the last column is linearly dependent
+        let agg = [0, 0, 0, 1, 1];
+        let bb = Matrix::from(&[
+            [1.0, 0.0, 2.0],
+            [1.0, 1.0, 2.0],
+            [1.0, 2.0, 2.0],
+            [1.0, 3.0, 2.0],
+            [1.0, 4.0, 2.0],
+        ]);
+        let (rows, ncoarse, bb_coarse) = PrecondAMG::<f64>::tentative(&agg, 2, Some(&bb));
+        let bb_coarse = bb_coarse.unwrap();
+        assert_eq!(ncoarse, 4);
+        assert_eq!(bb_coarse.dims(), (4, 3));
+        for (i, row) in rows.iter().enumerate() {
+            for c in 0..3 {
+                let value: f64 = row.iter().map(|(j, pij)| pij * bb_coarse.get(*j, c)).sum();
+                approx_eq(value, bb.get(i, c), 1e-14);
+            }
+        }
+
+        // the constant vector yields the default tentative prolongator
+        let ones = Matrix::filled(5, 1, 1.0);
+        let (rows, ncoarse, _) = PrecondAMG::<f64>::tentative(&agg, 2, Some(&ones));
+        let (rows_default, ncoarse_default, none) = PrecondAMG::<f64>::tentative(&agg, 2, None);
+        assert_eq!(ncoarse, ncoarse_default);
+        assert!(none.is_none());
+        for i in 0..5 {
+            assert_eq!(rows[i].len(), 1);
+            assert_eq!(rows[i][0].0, rows_default[i][0].0);
+            approx_eq(rows[i][0].1, rows_default[i][0].1, 1e-15);
+        }
+    }
+
+    #[test]
+    fn near_null_space_works() {
+        // constant and linear modes of the 2D Poisson problem
+        let m = 20;
+        let csr = poisson_2d(m);
+        let bb = Matrix::initialized(m * m, 3, |k, c| match c {
+            0 => 1.0,
+            1 => (k / m) as f64,
+            _ => (k % m) as f64,
+        });
+        let mut pre = PrecondAMG::new(0.08, 10, 10, 1).unwrap();
+        pre.set_near_null_space(Some(bb)).unwrap();
+        pre.setup(&csr).unwrap();
+        let dims = pre.get_level_dims();
+        assert!(dims.len() > 1);
+        for l in 1..dims.len() {
+            assert!(dims[l] < dims[l - 1]);
+        }
+        assert!(stationary_residual(&mut pre, &csr, 30) < 1e-8);
+
+        // removing the near-null space recovers the piecewise-constant interpolation
+        pre.set_near_null_space(None).unwrap();
+        pre.setup(&csr).unwrap();
+        let mut pre_default = PrecondAMG::new(0.08, 10, 10, 1).unwrap();
+        pre_default.setup(&csr).unwrap();
+        assert_eq!(pre.get_level_dims(), pre_default.get_level_dims());
+    }
+
+    #[test]
+    fn update_works() {
+        let csr = poisson_2d(20);
+        let n = csr.nrow;
+        let r = Vector::initialized(n, |i| 1.0 + (i % 7) as f64);
+        let mut z = Vector::new(n);
+        let mut z_update = Vector::new(n);
+
+        // update calls setup if needed
+        let mut pre = PrecondAMG::new(0.08, 10, 10, 1).unwrap();
+        pre.update(&csr).unwrap();
+        assert!(pre.get_num_levels() > 2);
+        pre.apply(&mut z, &r).unwrap();
+
+        // the V-cycle of 2·A (with the same hierarchy) is half the V-cycle of A
+        let mut csr_twice = csr.clone();
+        csr_twice.values.iter_mut().for_each(|v| *v *= 2.0);
+        let dims = pre.get_level_dims();
+        pre.update(&csr_twice).unwrap();
+        assert_eq!(pre.get_level_dims(), dims);
+        pre.apply(&mut z_update, &r).unwrap();
+        for i in 0..n {
+            approx_eq(z_update[i], 0.5 * z[i], 1e-14);
+        }
+        assert!(stationary_residual(&mut pre, &csr_twice, 30) < 1e-8);
+
+        // the structure must not change
+        let other = poisson_2d(10);
+        assert_eq!(
+            pre.update(&other).err(),
+            Some("the AMG hierarchy can only be updated if the structure of the matrix is unchanged")
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use super::{FactorScalar, NumCsrMatrix, PrecondTrait, RowMatrix};
+use crate::StrError;
+use russell_lab::NumVector;
+
+/// Implements the incomplete Cholesky factorization with zero fill-in, IC(0)
+///
+/// ```text
+/// M = L · Lᵀ
+/// ```
+///
+/// where L has the same sparsity pattern as the lower triangle of A.
+///
+/// **Note:** The matrix must be symmetric positive-definite; otherwise, the factorization may break down.
+/// A diagonal shift α may be used to improve the stability, i.e., `aᵢᵢ (1 + α)` replaces `aᵢᵢ`.
+/// For complex matrices, the factorization is the complex symmetric one (`Lᵀ`, not `Lᴴ`); thus,
+/// the matrix must be complex symmetric (e.g., for COCG) and only zero pivots cause a breakdown.
+pub struct PrecondIC0<T> {
+    /// Holds the diagonal shift α
+    shift: f64,
+
+    /// Holds the lower triangular factor (the diagonal is the last entry of each row)
+    lower: Option<RowMatrix<T>>,
+}
+
+impl<T> PrecondIC0<T> {
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `shift` -- the diagonal shift α ≥ 0
+    pub fn new(shift: f64) -> Result<Self, StrError> {
+        if shift < 0.0 {
+            return Err("the IC(0) diagonal shift must be non-negative");
+        }
+        Ok(PrecondIC0 { shift, lower: None })
+    }
+}
+
+impl<T> PrecondTrait<T> for PrecondIC0<T>
+where
+    T: FactorScalar,
+{
+    /// Computes the incomplete factorization
+    fn setup(&mut self, csr: &NumCsrMatrix<T>) -> Result<(), StrError> {
+        let a = RowMatrix::from_csr(csr)?;
+        a.diagonal_positions()?;
+        let n = a.nrow;
+        let mut rows: Vec<Vec<(usize, T)>> = vec![Vec::new(); n];
+        for i in 0..n {
+            let mut row: Vec<(usize, T)> = Vec::new();
+            for p in a.row_pointers[i]..a.row_pointers[i + 1] {
+                let j = a.col_indices[p];
+                if j > i {
+                    break;
+                }
+                // sparse dot product of the (already computed) parts of the rows i and j of L
+                let mut sum = T::zero();
+                let (mut s, mut t) = (0, 0);
+                let other = if j < i { &rows[j] } else { &row };
+                while s < row.len() && t < other.len() && row[s].0 < j && other[t].0 < j {
+                    if row[s].0 == other[t].0 {
+                        sum += row[s].1 * other[t].1;
+                        s += 1;
+                        t += 1;
+                    } else if row[s].0 < other[t].0 {
+                        s += 1;
+                    } else {
+                        t += 1;
+                    }
+                }
+                if j < i {
+                    let ljj = rows[j].last().unwrap().1;
+                    row.push((j, (a.values[p] - sum) / ljj));
+                } else {
+                    let pivot = a.values[p].scale(1.0 + self.shift) - sum;
+                    match pivot.pivot_sqrt() {
+                        Some(lii) => row.push((i, lii)),
+                        None => return Err("IC(0) failed due to a non-positive pivot"),
+                    }
+                }
+            }
+            rows[i] = row;
+        }
+        self.lower = Some(RowMatrix::from_rows(n, &rows));
+        Ok(())
+    }
+
+    /// Applies the preconditioner: z := (L·Lᵀ)⁻¹·r
+    fn apply(&mut self, z: &mut NumVector<T>, r: &NumVector<T>) -> Result<(), StrError> {
+        let l = match &self.lower {
+            Some(l) => l,
+            None => return Err("the preconditioner must be set up first"),
+        };
+        let n = l.nrow;
+        if z.dim() != n || r.dim() != n {
+            return Err("vectors are incompatible with the preconditioner");
+        }
+        // solve L·y = r
+        for i in 0..n {
+            let last = l.row_pointers[i + 1] - 1;
+            let mut sum = r[i];
+            for p in l.row_pointers[i]..last {
+                sum -= l.values[p] * z[l.col_indices[p]];
+            }
+            z[i] = sum / l.values[last];
+        }
+        // solve Lᵀ·z = y (column-oriented because L is stored by rows)
+        for i in (0..n).rev() {
+            let last = l.row_pointers[i + 1] - 1;
+            z[i] /= l.values[last];
+            let zi = z[i];
+            for p in l.row_pointers[i]..last {
+                z[l.col_indices[p]] -= l.values[p] * zi;
+            }
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::PrecondIC0;
+    use crate::{CooMatrix, CsrMatrix, PrecondTrait, Samples, Sym};
+    use russell_lab::{mat_vec_mul, vec_approx_eq, Vector};
+
+    #[test]
+    fn new_and_setup_capture_errors() {
+        assert_eq!(
+            PrecondIC0::<f64>::new(-1.0).err(),
+            Some("the IC(0) diagonal shift must be non-negative")
+        );
+        let mut pre = PrecondIC0::new(0.0).unwrap();
+        let r = Vector::new(2);
+        let mut z = Vector::new(2);
+        assert_eq!(
+            pre.apply(&mut z, &r).err(),
+            Some("the preconditioner must be set up first")
+        );
+        let (_, _, csr, _) = Samples::umfpack_unsymmetric_5x5();
+        assert_eq!(
+            pre.setup(&csr).err(),
+            Some("all diagonal entries must be present in the sparse matrix")
+        );
+        let mut coo = CooMatrix::new(2, 2, 3, Sym::YesLower).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 0, 2.0).unwrap();
+        coo.put(1, 1, 1.0).unwrap();
+        let csr = CsrMatrix::from_coo(&coo).unwrap();
+        assert_eq!(pre.setup(&csr).err(), Some("IC(0) failed due to a non-positive pivot"));
+    }
+
+    #[test]
+    fn apply_works() {
+        // IC(0) is exact for tridiagonal matrices
+        let (_, _, csr, _) = Samples::positive_definite_3x3_lower();
+        let mut pre = PrecondIC0::new(0.0).unwrap();
+        pre.setup(&csr).unwrap();
+        let x_correct = Vector::from(&[1.0, 2.0, 3.0]);
+        let a = csr.as_dense();
+        let mut r = Vector::new(3);
+        mat_vec_mul(&mut r, 1.0, &a, &x_correct).unwrap();
+        let mut z = Vector::new(3);
+        pre.apply(&mut z, &r).unwrap();
+        vec_approx_eq(&z, x_correct.as_data(), 1e-14);
+        let mut z_wrong = Vector::new(2);
+        assert_eq!(
+            pre.apply(&mut z_wrong, &r).err(),
+            Some("vectors are incompatible with the preconditioner")
+        );
+
+        // the factor of a dense symmetric matrix is the complete Cholesky factor
+        let mut coo = CooMatrix::new(3, 3, 6, Sym::YesUpper).unwrap();
+        coo.put(0, 0, 4.0).unwrap();
+        coo.put(0, 1, 2.0).unwrap();
+        coo.put(0, 2, -2.0).unwrap();
+        coo.put(1, 1, 5.0).unwrap();
+        coo.put(1, 2, 1.0).unwrap();
+        coo.put(2, 2, 6.0).unwrap();
+        let csr = CsrMatrix::from_coo(&coo).unwrap();
+        pre.setup(&csr).unwrap();
+        let a = csr.as_dense();
+        mat_vec_mul(&mut r, 1.0, &a, &x_correct).unwrap();
+        pre.apply(&mut z, &r).unwrap();
+        vec_approx_eq(&z, x_correct.as_data(), 1e-14);
+    }
+}
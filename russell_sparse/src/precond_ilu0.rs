@@ -0,0 +1,169 @@
+use super::{FactorScalar, IncompleteLU, NumCsrMatrix, PrecondTrait, RowMatrix};
+use crate::StrError;
+use russell_lab::NumVector;
+
+/// Implements the incomplete LU factorization with zero fill-in, ILU(0)
+///
+/// The factors L and U have the same sparsity pattern as the lower and upper parts of A, respectively.
+///
+/// **Note:** All diagonal entries must be present in the sparse matrix.
+pub struct PrecondILU0<T> {
+    /// Holds the incomplete factors
+    factors: Option<IncompleteLU<T>>,
+}
+
+impl<T> PrecondILU0<T> {
+    /// Allocates a new instance
+    pub fn new() -> Self {
+        PrecondILU0 { factors: None }
+    }
+}
+
+impl<T> Default for PrecondILU0<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PrecondTrait<T> for PrecondILU0<T>
+where
+    T: FactorScalar,
+{
+    /// Computes the incomplete factorization
+    fn setup(&mut self, csr: &NumCsrMatrix<T>) -> Result<(), StrError> {
+        let mut a = RowMatrix::from_csr(csr)?;
+        let diag = a.diagonal_positions()?;
+        let n = a.nrow;
+
+        // IKJ variant of the Gaussian elimination restricted to the pattern of A
+        const NONE: usize = usize::MAX;
+        let mut position = vec![NONE; n];
+        for i in 0..n {
+            let (start, end) = (a.row_pointers[i], a.row_pointers[i + 1]);
+            for p in start..end {
+                position[a.col_indices[p]] = p;
+            }
+            for p in start..diag[i] {
+                let k = a.col_indices[p];
+                let ukk = a.values[diag[k]];
+                if ukk.modulus() == 0.0 {
+                    return Err("ILU(0) failed due to a zero pivot");
+                }
+                let lik = a.values[p] / ukk;
+                a.values[p] = lik;
+                for q in (diag[k] + 1)..a.row_pointers[k + 1] {
+                    let pos = position[a.col_indices[q]];
+                    if pos != NONE {
+                        let ukj = a.values[q];
+                        a.values[pos] -= lik * ukj;
+                    }
+                }
+            }
+            if a.values[diag[i]].modulus() == 0.0 {
+                return Err("ILU(0) failed due to a zero pivot");
+            }
+            for p in start..end {
+                position[a.col_indices[p]] = NONE;
+            }
+        }
+
+        // split the factors
+        let mut lower = vec![Vec::new(); n];
+        let mut upper = vec![Vec::new(); n];
+        for i in 0..n {
+            for p in a.row_pointers[i]..a.row_pointers[i + 1] {
+                let j = a.col_indices[p];
+                if j < i {
+                    lower[i].push((j, a.values[p]));
+                } else {
+                    upper[i].push((j, a.values[p]));
+                }
+            }
+        }
+        self.factors = Some(IncompleteLU {
+            lower: RowMatrix::from_rows(n, &lower),
+            upper: RowMatrix::from_rows(n, &upper),
+        });
+        Ok(())
+    }
+
+    /// Applies the preconditioner: z := (L·U)⁻¹·r
+    fn apply(&mut self, z: &mut NumVector<T>, r: &NumVector<T>) -> Result<(), StrError> {
+        match &self.factors {
+            Some(f) => f.solve(z, r),
+            None => Err("the preconditioner must be set up first"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::PrecondILU0;
+    use crate::{CooMatrix, CsrMatrix, PrecondTrait, Samples, Sym};
+    use russell_lab::{approx_eq, mat_mat_mul, mat_vec_mul, vec_approx_eq, Matrix, Vector};
+
+    #[test]
+    fn setup_and_apply_capture_errors() {
+        let mut pre = PrecondILU0::new();
+        let r = Vector::new(3);
+        let mut z = Vector::new(3);
+        assert_eq!(
+            pre.apply(&mut z, &r).err(),
+            Some("the preconditioner must be set up first")
+        );
+        let (_, _, csr, _) = Samples::umfpack_unsymmetric_5x5();
+        assert_eq!(
+            pre.setup(&csr).err(),
+            Some("all diagonal entries must be present in the sparse matrix")
+        );
+        let mut coo = CooMatrix::new(2, 2, 4, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(0, 1, 1.0).unwrap();
+        coo.put(1, 0, 1.0).unwrap();
+        coo.put(1, 1, 1.0).unwrap();
+        let csr = CsrMatrix::from_coo(&coo).unwrap();
+        assert_eq!(pre.setup(&csr).err(), Some("ILU(0) failed due to a zero pivot"));
+    }
+
+    #[test]
+    fn apply_works() {
+        // ILU(0) is exact for tridiagonal matrices
+        let (_, _, csr, _) = Samples::positive_definite_3x3_lower();
+        let mut pre = PrecondILU0::new();
+        pre.setup(&csr).unwrap();
+        let x_correct = Vector::from(&[1.0, 2.0, 3.0]);
+        let a = csr.as_dense();
+        let mut r = Vector::new(3);
+        mat_vec_mul(&mut r, 1.0, &a, &x_correct).unwrap();
+        let mut z = Vector::new(3);
+        pre.apply(&mut z, &r).unwrap();
+        vec_approx_eq(&z, x_correct.as_data(), 1e-14);
+
+        // the product L·U must match A at the positions of the non-zero entries of A
+        let (_, _, csr, _) = Samples::mkl_unsymmetric_5x5();
+        pre.setup(&csr).unwrap();
+        let a = csr.as_dense();
+        let f = pre.factors.as_ref().unwrap();
+        let mut l = Matrix::identity(5);
+        let mut u = Matrix::new(5, 5);
+        for i in 0..5 {
+            for p in f.lower.row_pointers[i]..f.lower.row_pointers[i + 1] {
+                l.set(i, f.lower.col_indices[p], f.lower.values[p]);
+            }
+            for p in f.upper.row_pointers[i]..f.upper.row_pointers[i + 1] {
+                u.set(i, f.upper.col_indices[p], f.upper.values[p]);
+            }
+        }
+        let mut lu = Matrix::new(5, 5);
+        mat_mat_mul(&mut lu, 1.0, &l, &u, 0.0).unwrap();
+        for i in 0..5 {
+            for j in 0..5 {
+                if a.get(i, j) != 0.0 {
+                    approx_eq(lu.get(i, j), a.get(i, j), 1e-14);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use super::{FactorScalar, IncompleteLU, NumCsrMatrix, PrecondTrait, RowMatrix};
+use crate::StrError;
+use russell_lab::NumVector;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Implements the incomplete LU factorization with threshold dropping, ILUT(τ, p)
+///
+/// For each row i, the entries of L and U smaller than `τ·‖aᵢ‖₂` are dropped (where aᵢ is the i-th row of A),
+/// and only the `p` largest entries (in modulus) of each of L and U are kept. The diagonal is always kept.
+///
+/// Reference: Saad Y (2003) Iterative Methods for Sparse Linear Systems, 2nd Edition, SIAM, Algorithm 10.6
+pub struct PrecondILUT<T> {
+    /// Holds the drop tolerance τ
+    drop_tol: f64,
+
+    /// Holds the maximum number of entries p in each row of L and U (excluding the diagonal)
+    fill: usize,
+
+    /// Holds the incomplete factors
+    factors: Option<IncompleteLU<T>>,
+}
+
+impl<T> PrecondILUT<T> {
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `drop_tol` -- the drop tolerance τ ≥ 0
+    /// * `fill` -- the maximum number of entries p ≥ 1 in each row of L and U (excluding the diagonal)
+    pub fn new(drop_tol: f64, fill: usize) -> Result<Self, StrError> {
+        if drop_tol < 0.0 {
+            return Err("the ILUT drop tolerance must be non-negative");
+        }
+        if fill < 1 {
+            return Err("the ILUT fill parameter must be ≥ 1");
+        }
+        Ok(PrecondILUT {
+            drop_tol,
+            fill,
+            factors: None,
+        })
+    }
+}
+
+/// Keeps the `fill` largest entries (in modulus) and sorts the row by column index
+fn keep_largest<T: FactorScalar>(row: &mut Vec<(usize, T)>, fill: usize) {
+    if row.len() > fill {
+        row.sort_by(|a, b| b.1.modulus().total_cmp(&a.1.modulus()));
+        row.truncate(fill);
+    }
+    row.sort_by_key(|e| e.0);
+}
+
+impl<T> PrecondTrait<T> for PrecondILUT<T>
+where
+    T: FactorScalar,
+{
+    /// Computes the incomplete factorization
+    fn setup(&mut self, csr: &NumCsrMatrix<T>) -> Result<(), StrError> {
+        let a = RowMatrix::from_csr(csr)?;
+        let n = a.nrow;
+        let mut lower: Vec<Vec<(usize, T)>> = vec![Vec::new(); n];
+        let mut upper: Vec<Vec<(usize, T)>> = vec![Vec::new(); n]; // diagonal first
+
+        // work row (dense values and list of non-zero positions)
+        let mut w = vec![T::zero(); n];
+        let mut in_pattern = vec![false; n];
+        let mut pattern = Vec::new();
+        let mut heap = BinaryHeap::new();
+
+        for i in 0..n {
+            // load the row
+            let mut norm = 0.0;
+            for p in a.row_pointers[i]..a.row_pointers[i + 1] {
+                let j = a.col_indices[p];
+                w[j] = a.values[p];
+                in_pattern[j] = true;
+                pattern.push(j);
+                if j < i {
+                    heap.push(Reverse(j));
+                }
+                norm += a.values[p].modulus() * a.values[p].modulus();
+            }
+            let tau = self.drop_tol * f64::sqrt(norm);
+
+            // eliminate the lower entries in increasing column order
+            while let Some(Reverse(k)) = heap.pop() {
+                let wk = w[k] / upper[k][0].1;
+                if wk.modulus() < tau {
+                    w[k] = T::zero();
+                    continue;
+                }
+                w[k] = wk;
+                for (j, ukj) in upper[k].iter().skip(1) {
+                    if !in_pattern[*j] {
+                        in_pattern[*j] = true;
+                        pattern.push(*j);
+                        if *j < i {
+                            heap.push(Reverse(*j));
+                        }
+                    }
+                    w[*j] -= wk * *ukj;
+                }
+            }
+
+            // apply the dropping rules and store the row
+            let mut diag = T::zero();
+            for j in pattern.drain(..) {
+                let wj = w[j];
+                w[j] = T::zero();
+                in_pattern[j] = false;
+                if j == i {
+                    diag = wj;
+                } else if wj.modulus() != 0.0 && wj.modulus() >= tau {
+                    if j < i {
+                        lower[i].push((j, wj));
+                    } else {
+                        upper[i].push((j, wj));
+                    }
+                }
+            }
+            if diag.modulus() == 0.0 {
+                return Err("ILUT failed due to a zero pivot");
+            }
+            keep_largest(&mut lower[i], self.fill);
+            keep_largest(&mut upper[i], self.fill);
+            upper[i].insert(0, (i, diag));
+        }
+        self.factors = Some(IncompleteLU {
+            lower: RowMatrix::from_rows(n, &lower),
+            upper: RowMatrix::from_rows(n, &upper),
+        });
+        Ok(())
+    }
+
+    /// Applies the preconditioner: z := (L·U)⁻¹·r
+    fn apply(&mut self, z: &mut NumVector<T>, r: &NumVector<T>) -> Result<(), StrError> {
+        match &self.factors {
+            Some(f) => f.solve(z, r),
+            None => Err("the preconditioner must be set up first"),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::PrecondILUT;
+    use crate::{CooMatrix, CsrMatrix, PrecondTrait, Samples, Sym};
+    use russell_lab::{mat_vec_mul, vec_approx_eq, Vector};
+
+    #[test]
+    fn new_and_setup_capture_errors() {
+        assert_eq!(
+            PrecondILUT::<f64>::new(-1.0, 10).err(),
+            Some("the ILUT drop tolerance must be non-negative")
+        );
+        assert_eq!(
+            PrecondILUT::<f64>::new(1e-4, 0).err(),
+            Some("the ILUT fill parameter must be ≥ 1")
+        );
+        let mut pre = PrecondILUT::new(1e-4, 10).unwrap();
+        let r = Vector::new(2);
+        let mut z = Vector::new(2);
+        assert_eq!(
+            pre.apply(&mut z, &r).err(),
+            Some("the preconditioner must be set up first")
+        );
+        let mut coo = CooMatrix::new(2, 2, 4, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(0, 1, 1.0).unwrap();
+        coo.put(1, 0, 1.0).unwrap();
+        coo.put(1, 1, 1.0).unwrap();
+        let csr = CsrMatrix::from_coo(&coo).unwrap();
+        assert_eq!(pre.setup(&csr).err(), Some("ILUT failed due to a zero pivot"));
+    }
+
+    #[test]
+    fn apply_works() {
+        // without dropping, ILUT is the complete LU factorization (no pivoting)
+        let (_, _, csr, _) = Samples::mkl_unsymmetric_5x5();
+        let mut pre = PrecondILUT::new(0.0, 5).unwrap();
+        pre.setup(&csr).unwrap();
+        let x_correct = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let a = csr.as_dense();
+        let mut r = Vector::new(5);
+        mat_vec_mul(&mut r, 1.0, &a, &x_correct).unwrap();
+        let mut z = Vector::new(5);
+        pre.apply(&mut z, &r).unwrap();
+        vec_approx_eq(&z, x_correct.as_data(), 1e-13);
+
+        // with a limited fill, the factors are sparser
+        let mut pre = PrecondILUT::new(0.0, 1).unwrap();
+        pre.setup(&csr).unwrap();
+        let f = pre.factors.as_ref().unwrap();
+        for i in 0..5 {
+            assert!(f.lower.row_pointers[i + 1] - f.lower.row_pointers[i] <= 1);
+            assert!(f.upper.row_pointers[i + 1] - f.upper.row_pointers[i] <= 2);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use super::{FactorScalar, NumCsrMatrix, PrecondTrait, RowMatrix};
+use crate::StrError;
+use russell_lab::NumVector;
+
+/// Implements the Jacobi (diagonal) preconditioner
+///
+/// ```text
+/// M = diag(A)
+/// ```
+pub struct PrecondJacobi<T> {
+    /// Holds the inverse of the diagonal entries
+    inv_diag: Vec<T>,
+}
+
+impl<T> PrecondJacobi<T> {
+    /// Allocates a new instance
+    pub fn new() -> Self {
+        PrecondJacobi { inv_diag: Vec::new() }
+    }
+}
+
+impl<T> Default for PrecondJacobi<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PrecondTrait<T> for PrecondJacobi<T>
+where
+    T: FactorScalar,
+{
+    /// Computes the inverse of the diagonal entries
+    fn setup(&mut self, csr: &NumCsrMatrix<T>) -> Result<(), StrError> {
+        let a = RowMatrix::from_csr(csr)?;
+        self.inv_diag = a.diagonal();
+        for d in self.inv_diag.iter_mut() {
+            if d.modulus() == 0.0 {
+                return Err("the Jacobi preconditioner requires non-zero diagonal entries");
+            }
+            *d = T::one() / *d;
+        }
+        Ok(())
+    }
+
+    /// Applies the preconditioner: z := M⁻¹·r
+    fn apply(&mut self, z: &mut NumVector<T>, r: &NumVector<T>) -> Result<(), StrError> {
+        let n = self.inv_diag.len();
+        if z.dim() != n || r.dim() != n {
+            return Err("vectors are incompatible with the preconditioner");
+        }
+        for i in 0..n {
+            z[i] = self.inv_diag[i] * r[i];
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::PrecondJacobi;
+    use crate::{PrecondTrait, Samples};
+    use russell_lab::{vec_approx_eq, Vector};
+
+    #[test]
+    fn setup_captures_errors() {
+        let mut pre = PrecondJacobi::new();
+        let (_, _, csr, _) = Samples::umfpack_unsymmetric_5x5();
+        assert_eq!(
+            pre.setup(&csr).err(),
+            Some("the Jacobi preconditioner requires non-zero diagonal entries")
+        );
+        let (_, _, csr, _) = Samples::rectangular_3x4();
+        assert_eq!(pre.setup(&csr).err(), Some("the matrix must be square"));
+    }
+
+    #[test]
+    fn apply_works() {
+        let mut pre = PrecondJacobi::new();
+        let (_, _, csr, _) = Samples::positive_definite_3x3_lower();
+        pre.setup(&csr).unwrap();
+        let r = Vector::from(&[2.0, 2.0, 2.0]);
+        let mut z = Vector::new(3);
+        pre.apply(&mut z, &r).unwrap();
+        let a = csr.as_dense();
+        let correct = &[2.0 / a.get(0, 0), 2.0 / a.get(1, 1), 2.0 / a.get(2, 2)];
+        vec_approx_eq(&z, correct, 1e-15);
+        let mut z_wrong = Vector::new(2);
+        assert_eq!(
+            pre.apply(&mut z_wrong, &r).err(),
+            Some("vectors are incompatible with the preconditioner")
+        );
+    }
+}
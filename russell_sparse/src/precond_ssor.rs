@@ -0,0 +1,167 @@
+use super::{FactorScalar, NumCsrMatrix, PrecondTrait, RowMatrix};
+use crate::StrError;
+use russell_lab::NumVector;
+
+/// Implements the symmetric successive over-relaxation (SSOR) preconditioner
+///
+/// ```text
+///        ω     ⎛ D     ⎞ ⎛ D ⎞⁻¹ ⎛ D     ⎞
+/// M = ——————— ⎜ — + L ⎟ ⎜ — ⎟   ⎜ — + U ⎟
+///      2 - ω   ⎝ ω     ⎠ ⎝ ω ⎠   ⎝ ω     ⎠
+/// ```
+///
+/// where `A = L + D + U`, D is the diagonal, and L and U are the strictly lower and upper triangular parts.
+///
+/// **Note:** With ω = 1, this is the symmetric Gauss-Seidel preconditioner.
+pub struct PrecondSSOR<T> {
+    /// Holds the relaxation factor
+    omega: f64,
+
+    /// Holds the coefficient matrix in compressed-row form
+    a: RowMatrix<T>,
+
+    /// Holds the positions of the diagonal entries
+    diag: Vec<usize>,
+}
+
+impl<T> PrecondSSOR<T>
+where
+    T: FactorScalar,
+{
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `omega` -- the relaxation factor (0 < ω < 2)
+    pub fn new(omega: f64) -> Result<Self, StrError> {
+        if omega <= 0.0 || omega >= 2.0 {
+            return Err("the SSOR relaxation factor must satisfy 0 < ω < 2");
+        }
+        Ok(PrecondSSOR {
+            omega,
+            a: RowMatrix::from_rows(0, &[]),
+            diag: Vec::new(),
+        })
+    }
+}
+
+impl<T> PrecondTrait<T> for PrecondSSOR<T>
+where
+    T: FactorScalar,
+{
+    /// Saves the coefficient matrix and checks the diagonal entries
+    fn setup(&mut self, csr: &NumCsrMatrix<T>) -> Result<(), StrError> {
+        self.a = RowMatrix::from_csr(csr)?;
+        self.diag = self.a.diagonal_positions()?;
+        for p in &self.diag {
+            if self.a.values[*p].modulus() == 0.0 {
+                return Err("the SSOR preconditioner requires non-zero diagonal entries");
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the preconditioner: z := M⁻¹·r
+    fn apply(&mut self, z: &mut NumVector<T>, r: &NumVector<T>) -> Result<(), StrError> {
+        let n = self.a.nrow;
+        if z.dim() != n || r.dim() != n {
+            return Err("vectors are incompatible with the preconditioner");
+        }
+        let a = &self.a;
+        let w = self.omega;
+
+        // forward sweep: (D/ω + L)·y = r, then y := (D/ω)·y
+        for i in 0..n {
+            let mut sum = r[i];
+            for p in a.row_pointers[i]..self.diag[i] {
+                sum -= a.values[p] * z[a.col_indices[p]];
+            }
+            z[i] = sum.scale(w) / a.values[self.diag[i]];
+        }
+        for i in 0..n {
+            z[i] *= a.values[self.diag[i]].scale(1.0 / w);
+        }
+
+        // backward sweep: (D/ω + U)·z = y, then z := (2 - ω)/ω·z
+        for i in (0..n).rev() {
+            let mut sum = z[i];
+            for p in (self.diag[i] + 1)..a.row_pointers[i + 1] {
+                sum -= a.values[p] * z[a.col_indices[p]];
+            }
+            z[i] = sum.scale(w) / a.values[self.diag[i]];
+        }
+        for i in 0..n {
+            z[i] = z[i].scale((2.0 - w) / w);
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::PrecondSSOR;
+    use crate::{CooMatrix, CsrMatrix, PrecondTrait, Samples, Sym};
+    use russell_lab::{mat_mat_mul, mat_vec_mul, vec_approx_eq, Matrix, Vector};
+
+    #[test]
+    fn new_and_setup_capture_errors() {
+        assert_eq!(
+            PrecondSSOR::<f64>::new(0.0).err(),
+            Some("the SSOR relaxation factor must satisfy 0 < ω < 2")
+        );
+        let mut pre = PrecondSSOR::new(1.0).unwrap();
+        let (_, _, csr, _) = Samples::umfpack_unsymmetric_5x5();
+        assert_eq!(
+            pre.setup(&csr).err(),
+            Some("all diagonal entries must be present in the sparse matrix")
+        );
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 1, 0.0).unwrap();
+        let csr = CsrMatrix::from_coo(&coo).unwrap();
+        assert_eq!(
+            pre.setup(&csr).err(),
+            Some("the SSOR preconditioner requires non-zero diagonal entries")
+        );
+    }
+
+    #[test]
+    fn apply_works() {
+        // compare M·z with r, where M is computed with dense matrices
+        let (_, _, csr, _) = Samples::mkl_unsymmetric_5x5();
+        let a = csr.as_dense();
+        let n = 5;
+        for omega in [0.5, 1.0, 1.5] {
+            let mut lower = Matrix::new(n, n); // D/ω + L
+            let mut dinv = Matrix::new(n, n); // (D/ω)⁻¹
+            let mut upper = Matrix::new(n, n); // D/ω + U
+            for i in 0..n {
+                for j in 0..n {
+                    if i > j {
+                        lower.set(i, j, a.get(i, j));
+                    } else if i < j {
+                        upper.set(i, j, a.get(i, j));
+                    } else {
+                        lower.set(i, i, a.get(i, i) / omega);
+                        upper.set(i, i, a.get(i, i) / omega);
+                        dinv.set(i, i, omega / a.get(i, i));
+                    }
+                }
+            }
+            let mut temp = Matrix::new(n, n);
+            let mut mm = Matrix::new(n, n);
+            mat_mat_mul(&mut temp, 1.0, &lower, &dinv, 0.0).unwrap();
+            mat_mat_mul(&mut mm, omega / (2.0 - omega), &temp, &upper, 0.0).unwrap();
+            let mut pre = PrecondSSOR::new(omega).unwrap();
+            pre.setup(&csr).unwrap();
+            let r = Vector::from(&[1.0, -2.0, 3.0, -4.0, 5.0]);
+            let mut z = Vector::new(n);
+            pre.apply(&mut z, &r).unwrap();
+            let mut mz = Vector::new(n);
+            mat_vec_mul(&mut mz, 1.0, &mm, &z).unwrap();
+            vec_approx_eq(&mz, r.as_data(), 1e-13);
+        }
+    }
+}
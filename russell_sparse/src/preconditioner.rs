@@ -0,0 +1,443 @@
+use super::{FactorScalar, LinSolParams, NumCsrMatrix, NumSparseMatrix, Precond};
+use super::{PrecondAMG, PrecondIC0, PrecondILU0, PrecondILUT, PrecondJacobi, PrecondSSOR};
+use crate::StrError;
+use russell_lab::NumVector;
+
+/// Defines a unified interface for preconditioners of iterative solvers
+///
+/// A preconditioner approximates the inverse of the coefficient matrix A such that
+/// `M⁻¹·A ≈ I`, accelerating the convergence of Krylov methods.
+///
+/// The computations are split into two phases:
+///
+/// 1. `setup` -- computes the preconditioner from the coefficient matrix (e.g., an incomplete factorization).
+///    This function may be called again whenever the values of the matrix change (e.g., in Newton iterations).
+/// 2. `apply` -- computes `z := M⁻¹·r` using the data computed by `setup`.
+///
+/// The preconditioners work with real (`f64`) and complex (`Complex64`) matrices.
+pub trait PrecondTrait<T>: Send
+where
+    T: FactorScalar,
+{
+    /// Computes the preconditioner from the coefficient matrix
+    ///
+    /// # Input
+    ///
+    /// * `csr` -- the square coefficient matrix A; if symmetric, any [crate::Sym] representation may be used
+    fn setup(&mut self, csr: &NumCsrMatrix<T>) -> Result<(), StrError>;
+
+    /// Applies the preconditioner
+    ///
+    /// ```text
+    /// z := M⁻¹ · r
+    /// ```
+    ///
+    /// **Note:** `setup` must be called first.
+    fn apply(&mut self, z: &mut NumVector<T>, r: &NumVector<T>) -> Result<(), StrError>;
+
+    /// Updates the preconditioner after a change of the values (but not the structure) of the matrix
+    ///
+    /// The default implementation calls `setup`. Preconditioners with an expensive symbolic phase
+    /// (e.g., the AMG hierarchy) reuse it.
+    fn update(&mut self, csr: &NumCsrMatrix<T>) -> Result<(), StrError> {
+        self.setup(csr)
+    }
+}
+
+/// Unifies the access to preconditioners
+///
+/// See also the aliases [crate::Preconditioner] and [crate::ComplexPreconditioner].
+pub struct NumPreconditioner<'a, T>
+where
+    T: FactorScalar,
+{
+    /// Holds the actual implementation
+    pub actual: Box<dyn Send + PrecondTrait<T> + 'a>,
+}
+
+impl<'a, T> NumPreconditioner<'a, T>
+where
+    T: FactorScalar + 'a,
+{
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `precond` -- the kind of preconditioner (must not be [Precond::No])
+    /// * `params` -- configuration parameters (e.g., [LinSolParams::precond_ssor_omega]); None => use default
+    pub fn new(precond: Precond, params: Option<LinSolParams>) -> Result<Self, StrError> {
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        let actual: Box<dyn Send + PrecondTrait<T>> = match precond {
+            Precond::Amg => Box::new(PrecondAMG::new(
+                par.precond_amg_strength,
+                par.precond_amg_max_levels,
+                par.precond_amg_coarse_size,
+                par.precond_amg_sweeps,
+            )?),
+            Precond::Ic0 => Box::new(PrecondIC0::new(par.precond_ic0_shift)?),
+            Precond::Ilu0 => Box::new(PrecondILU0::new()),
+            Precond::Ilut => Box::new(PrecondILUT::new(par.precond_ilut_drop_tol, par.precond_ilut_fill)?),
+            Precond::Jacobi => Box::new(PrecondJacobi::new()),
+            Precond::No => return Err("cannot allocate a preconditioner with Precond::No"),
+            Precond::Ssor => Box::new(PrecondSSOR::new(par.precond_ssor_omega)?),
+        };
+        Ok(NumPreconditioner { actual })
+    }
+
+    /// Computes the preconditioner from a sparse matrix
+    ///
+    /// **Priority**: CSR -> CSC -> COO (the CSC and COO matrices are converted to CSR)
+    pub fn setup(&mut self, mat: &NumSparseMatrix<T>) -> Result<(), StrError> {
+        if let Ok(csr) = mat.get_csr() {
+            return self.actual.setup(csr);
+        }
+        if let Ok(csc) = mat.get_csc() {
+            return self.actual.setup(&NumCsrMatrix::from_csc(csc)?);
+        }
+        self.actual.setup(&NumCsrMatrix::from_coo(mat.get_coo()?)?)
+    }
+
+    /// Updates the preconditioner after a change of the values (but not the structure) of the matrix
+    ///
+    /// **Priority**: CSR -> CSC -> COO (the CSC and COO matrices are converted to CSR)
+    pub fn update(&mut self, mat: &NumSparseMatrix<T>) -> Result<(), StrError> {
+        if let Ok(csr) = mat.get_csr() {
+            return self.actual.update(csr);
+        }
+        if let Ok(csc) = mat.get_csc() {
+            return self.actual.update(&NumCsrMatrix::from_csc(csc)?);
+        }
+        self.actual.update(&NumCsrMatrix::from_coo(mat.get_coo()?)?)
+    }
+
+    /// Applies the preconditioner: z := M⁻¹·r
+    pub fn apply(&mut self, z: &mut NumVector<T>, r: &NumVector<T>) -> Result<(), StrError> {
+        self.actual.apply(z, r)
+    }
+}
+
+/// Holds a matrix in compressed-row form with full (non-triangular) storage and sorted column indices
+///
+/// This is an auxiliary structure for the preconditioners: the column indices are `usize`,
+/// duplicates are summed up, and the triangular representations of symmetric matrices are mirrored.
+#[derive(Clone, Debug)]
+pub(crate) struct RowMatrix<T> {
+    pub(crate) nrow: usize,
+    pub(crate) ncol: usize,
+    pub(crate) row_pointers: Vec<usize>,
+    pub(crate) col_indices: Vec<usize>,
+    pub(crate) values: Vec<T>,
+}
+
+impl<T> RowMatrix<T>
+where
+    T: FactorScalar,
+{
+    /// Allocates a new instance from rows given as (sorted, unique) column-value pairs
+    pub(crate) fn from_rows(ncol: usize, rows: &[Vec<(usize, T)>]) -> Self {
+        let nrow = rows.len();
+        let nnz = rows.iter().map(|r| r.len()).sum();
+        let mut mat = RowMatrix {
+            nrow,
+            ncol,
+            row_pointers: vec![0; nrow + 1],
+            col_indices: Vec::with_capacity(nnz),
+            values: Vec::with_capacity(nnz),
+        };
+        for (i, row) in rows.iter().enumerate() {
+            for (j, aij) in row {
+                mat.col_indices.push(*j);
+                mat.values.push(*aij);
+            }
+            mat.row_pointers[i + 1] = mat.col_indices.len();
+        }
+        mat
+    }
+
+    /// Allocates a new instance from a CSR matrix (the matrix must be square)
+    pub(crate) fn from_csr(csr: &NumCsrMatrix<T>) -> Result<Self, StrError> {
+        let (nrow, ncol, _, sym) = csr.get_info();
+        if nrow != ncol {
+            return Err("the matrix must be square");
+        }
+        let mirror = sym.triangular();
+        let mut rows = vec![Vec::new(); nrow];
+        for i in 0..nrow {
+            for p in csr.row_pointers[i]..csr.row_pointers[i + 1] {
+                let j = csr.col_indices[p as usize] as usize;
+                let aij = csr.values[p as usize];
+                rows[i].push((j, aij));
+                if mirror && i != j {
+                    rows[j].push((i, aij));
+                }
+            }
+        }
+        for row in rows.iter_mut() {
+            row.sort_by_key(|e| e.0);
+            row.dedup_by(|b, a| {
+                if a.0 == b.0 {
+                    a.1 += b.1;
+                    true
+                } else {
+                    false
+                }
+            });
+        }
+        Ok(RowMatrix::from_rows(ncol, &rows))
+    }
+
+    /// Returns the positions of the diagonal entries in the arrays of column indices and values
+    ///
+    /// Returns an error if a diagonal entry is missing.
+    pub(crate) fn diagonal_positions(&self) -> Result<Vec<usize>, StrError> {
+        let mut diag = Vec::with_capacity(self.nrow);
+        for i in 0..self.nrow {
+            let row = &self.col_indices[self.row_pointers[i]..self.row_pointers[i + 1]];
+            match row.binary_search(&i) {
+                Ok(k) => diag.push(self.row_pointers[i] + k),
+                Err(_) => return Err("all diagonal entries must be present in the sparse matrix"),
+            }
+        }
+        Ok(diag)
+    }
+
+    /// Returns the diagonal values (zero if missing)
+    pub(crate) fn diagonal(&self) -> Vec<T> {
+        (0..self.nrow)
+            .map(|i| {
+                (self.row_pointers[i]..self.row_pointers[i + 1])
+                    .find(|p| self.col_indices[*p] == i)
+                    .map_or(T::zero(), |p| self.values[p])
+            })
+            .collect()
+    }
+
+    /// Computes v := A·u
+    pub(crate) fn mat_vec_mul(&self, v: &mut [T], u: &[T]) {
+        for (i, vi) in v.iter_mut().enumerate().take(self.nrow) {
+            let mut sum = T::zero();
+            for p in self.row_pointers[i]..self.row_pointers[i + 1] {
+                sum += self.values[p] * u[self.col_indices[p]];
+            }
+            *vi = sum;
+        }
+    }
+
+    /// Returns the transpose matrix
+    pub(crate) fn transpose(&self) -> Self {
+        let mut rows = vec![Vec::new(); self.ncol];
+        for i in 0..self.nrow {
+            for p in self.row_pointers[i]..self.row_pointers[i + 1] {
+                rows[self.col_indices[p]].push((i, self.values[p])); // sorted because i increases
+            }
+        }
+        RowMatrix::from_rows(self.nrow, &rows)
+    }
+
+    /// Returns the matrix-matrix product C := A·B
+    pub(crate) fn mat_mat_mul(&self, other: &RowMatrix<T>) -> Self {
+        const NONE: usize = usize::MAX;
+        let mut position = vec![NONE; other.ncol];
+        let mut rows = vec![Vec::new(); self.nrow];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for p in self.row_pointers[i]..self.row_pointers[i + 1] {
+                let k = self.col_indices[p];
+                let aik = self.values[p];
+                for q in other.row_pointers[k]..other.row_pointers[k + 1] {
+                    let j = other.col_indices[q];
+                    if position[j] == NONE {
+                        position[j] = row.len();
+                        row.push((j, aik * other.values[q]));
+                    } else {
+                        row[position[j]].1 += aik * other.values[q];
+                    }
+                }
+            }
+            for (j, _) in row.iter() {
+                position[*j] = NONE;
+            }
+            row.sort_by_key(|e| e.0);
+        }
+        RowMatrix::from_rows(other.ncol, &rows)
+    }
+}
+
+/// Holds incomplete LU factors: L (unit lower triangular; diagonal not stored) and U (upper triangular)
+///
+/// The column indices of each row are sorted; thus, the diagonal of U is the first entry of each row.
+#[derive(Clone, Debug)]
+pub(crate) struct IncompleteLU<T> {
+    pub(crate) lower: RowMatrix<T>,
+    pub(crate) upper: RowMatrix<T>,
+}
+
+impl<T> IncompleteLU<T>
+where
+    T: FactorScalar,
+{
+    /// Solves L·U·z = r
+    pub(crate) fn solve(&self, z: &mut NumVector<T>, r: &NumVector<T>) -> Result<(), StrError> {
+        let n = self.lower.nrow;
+        if z.dim() != n || r.dim() != n {
+            return Err("vectors are incompatible with the preconditioner");
+        }
+        let (l, u) = (&self.lower, &self.upper);
+        for i in 0..n {
+            let mut sum = r[i];
+            for p in l.row_pointers[i]..l.row_pointers[i + 1] {
+                sum -= l.values[p] * z[l.col_indices[p]];
+            }
+            z[i] = sum;
+        }
+        for i in (0..n).rev() {
+            let (start, end) = (u.row_pointers[i], u.row_pointers[i + 1]);
+            let mut sum = z[i];
+            for p in (start + 1)..end {
+                sum -= u.values[p] * z[u.col_indices[p]];
+            }
+            z[i] = sum / u.values[start];
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::RowMatrix;
+    use crate::{ComplexCooMatrix, ComplexPreconditioner, ComplexSparseMatrix, CooMatrix, CsrMatrix, LinSolParams};
+    use crate::{Precond, Preconditioner, Samples, SparseMatrix, Sym};
+    use russell_lab::{complex_vec_approx_eq, cpx, mat_approx_eq, vec_approx_eq, Complex64, ComplexVector};
+    use russell_lab::{Matrix, Vector};
+
+    /// Converts a RowMatrix to a dense matrix
+    fn dense(a: &RowMatrix<f64>) -> Matrix {
+        let mut d = Matrix::new(a.nrow, a.ncol);
+        for i in 0..a.nrow {
+            for p in a.row_pointers[i]..a.row_pointers[i + 1] {
+                d.add(i, a.col_indices[p], a.values[p]);
+            }
+        }
+        d
+    }
+
+    #[test]
+    fn row_matrix_from_csr_works() {
+        let (_, _, csr, _) = Samples::positive_definite_3x3_lower();
+        let a = RowMatrix::from_csr(&csr).unwrap();
+        mat_approx_eq(&dense(&a), &csr.as_dense(), 1e-15);
+        let (_, _, csr, _) = Samples::umfpack_unsymmetric_5x5();
+        let a = RowMatrix::from_csr(&csr).unwrap();
+        mat_approx_eq(&dense(&a), &csr.as_dense(), 1e-15);
+        assert_eq!(a.diagonal(), &[2.0, 0.0, -3.0, 0.0, 1.0]);
+        assert_eq!(
+            a.diagonal_positions().err(),
+            Some("all diagonal entries must be present in the sparse matrix")
+        );
+        let (_, _, csr, _) = Samples::rectangular_3x4();
+        assert_eq!(RowMatrix::from_csr(&csr).err(), Some("the matrix must be square"));
+    }
+
+    #[test]
+    fn row_matrix_operations_work() {
+        let (_, _, csr, _) = Samples::mkl_unsymmetric_5x5();
+        let a = RowMatrix::from_csr(&csr).unwrap();
+        let dense_a = csr.as_dense();
+        let at = a.transpose();
+        let mut dense_at = Matrix::new(5, 5);
+        for i in 0..5 {
+            for j in 0..5 {
+                dense_at.set(i, j, dense_a.get(j, i));
+            }
+        }
+        mat_approx_eq(&dense(&at), &dense_at, 1e-15);
+        let ata = at.mat_mat_mul(&a);
+        let mut dense_ata = Matrix::new(5, 5);
+        for i in 0..5 {
+            for j in 0..5 {
+                for k in 0..5 {
+                    dense_ata.add(i, j, dense_at.get(i, k) * dense_a.get(k, j));
+                }
+            }
+        }
+        mat_approx_eq(&dense(&ata), &dense_ata, 1e-13);
+        let u = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut v = [0.0; 5];
+        a.mat_vec_mul(&mut v, &u);
+        let mut v_correct = Vector::new(5);
+        csr.mat_vec_mul(&mut v_correct, 1.0, &Vector::from(&u)).unwrap();
+        vec_approx_eq(&v_correct, &v, 1e-15);
+    }
+
+    #[test]
+    fn new_captures_errors() {
+        assert_eq!(
+            Preconditioner::new(Precond::No, None).err(),
+            Some("cannot allocate a preconditioner with Precond::No")
+        );
+        let mut params = LinSolParams::new();
+        params.precond_ssor_omega = 2.0;
+        assert_eq!(
+            Preconditioner::new(Precond::Ssor, Some(params)).err(),
+            Some("the SSOR relaxation factor must satisfy 0 < ω < 2")
+        );
+    }
+
+    #[test]
+    fn setup_and_apply_work() {
+        // diagonal matrix => all preconditioners are exact
+        let mut coo = CooMatrix::new(3, 3, 3, Sym::No).unwrap();
+        coo.put(0, 0, 2.0).unwrap();
+        coo.put(1, 1, 4.0).unwrap();
+        coo.put(2, 2, 8.0).unwrap();
+        let csr = CsrMatrix::from_coo(&coo).unwrap();
+        let r = Vector::from(&[2.0, 2.0, 2.0]);
+        let mut z = Vector::new(3);
+        for precond in [
+            Precond::Amg,
+            Precond::Ic0,
+            Precond::Ilu0,
+            Precond::Ilut,
+            Precond::Jacobi,
+            Precond::Ssor,
+        ] {
+            // COO
+            let mut pre = Preconditioner::new(precond, None).unwrap();
+            let mat = SparseMatrix::from_coo(coo.clone());
+            pre.setup(&mat).unwrap();
+            pre.apply(&mut z, &r).unwrap();
+            vec_approx_eq(&z, &[1.0, 0.5, 0.25], 1e-15);
+            // CSR
+            let mat = SparseMatrix::from_csr(csr.clone());
+            pre.setup(&mat).unwrap();
+            pre.apply(&mut z, &r).unwrap();
+            vec_approx_eq(&z, &[1.0, 0.5, 0.25], 1e-15);
+        }
+    }
+
+    #[test]
+    fn setup_and_apply_work_complex() {
+        // diagonal matrix => all preconditioners are exact
+        let mut coo = ComplexCooMatrix::new(3, 3, 3, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(2.0, 0.0)).unwrap();
+        coo.put(1, 1, cpx!(0.0, 4.0)).unwrap();
+        coo.put(2, 2, cpx!(4.0, 4.0)).unwrap();
+        let mat = ComplexSparseMatrix::from_coo(coo);
+        let r = ComplexVector::from(&[cpx!(2.0, 0.0), cpx!(2.0, 0.0), cpx!(8.0, 0.0)]);
+        let mut z = ComplexVector::new(3);
+        for precond in [
+            Precond::Amg,
+            Precond::Ic0,
+            Precond::Ilu0,
+            Precond::Ilut,
+            Precond::Jacobi,
+            Precond::Ssor,
+        ] {
+            let mut pre = ComplexPreconditioner::new(precond, None).unwrap();
+            pre.setup(&mat).unwrap();
+            pre.apply(&mut z, &r).unwrap();
+            complex_vec_approx_eq(&z, &[cpx!(1.0, 0.0), cpx!(0.0, -0.5), cpx!(1.0, -1.0)], 1e-15);
+        }
+    }
+}
@@ -4,20 +4,52 @@
 //! access to commonly used functionality.
 
 pub use crate::aliases::*;
+pub use crate::assembler::{element_dofs_from_connectivity, NumAssembler};
 pub use crate::complex_lin_solver::*;
-pub use crate::complex_solver_umfpack::ComplexSolverUMFPACK;
+pub use crate::complex_solver_native::ComplexSolverNative;
 pub use crate::coo_matrix::NumCooMatrix;
 pub use crate::csc_matrix::NumCscMatrix;
 pub use crate::csr_matrix::NumCsrMatrix;
+pub use crate::eigen_params::EigenParams;
+pub use crate::eigen_solver::EigenSolver;
 pub use crate::enums::*;
 pub use crate::lin_sol_params::LinSolParams;
 pub use crate::lin_solver::*;
+pub use crate::lsq_params::LsqParams;
+pub use crate::lsq_solver::LsqSolver;
+pub use crate::matrix_analysis::MatrixAnalysis;
+pub use crate::nonlin_params::NonlinParams;
+pub use crate::nonlin_solver::NonlinSolver;
+pub use crate::nonlin_system::NonlinSystem;
 pub use crate::numerical_jacobian::numerical_jacobian;
+pub use crate::parallel_mat_vec::ParallelMatVec;
+pub use crate::preconditioner::{NumPreconditioner, PrecondTrait};
 pub use crate::read_matrix_market;
-pub use crate::solver_umfpack::SolverUMFPACK;
+pub use crate::read_rutherford_boeing;
+pub use crate::solver_krylov::NumSolverKrylov;
+pub use crate::solver_native::SolverNative;
+pub use crate::sparse_algebra::*;
+pub use crate::sparse_blocks::*;
 pub use crate::sparse_matrix::NumSparseMatrix;
+pub use crate::sparse_ordering::*;
 pub use crate::stats_lin_sol::StatsLinSol;
 pub use crate::verify_lin_sys::VerifyLinSys;
+pub use crate::write_spy_svg::SpyParams;
+
+#[cfg(feature = "with_suitesparse")]
+pub use crate::complex_solver_cholmod::ComplexSolverCHOLMOD;
+
+#[cfg(feature = "with_suitesparse")]
+pub use crate::complex_solver_umfpack::ComplexSolverUMFPACK;
+
+#[cfg(feature = "with_suitesparse")]
+pub use crate::solver_cholmod::{CholmodSystem, SolverCHOLMOD};
+
+#[cfg(feature = "with_suitesparse")]
+pub use crate::solver_spqr::SolverSPQR;
+
+#[cfg(feature = "with_suitesparse")]
+pub use crate::solver_umfpack::SolverUMFPACK;
 
 #[cfg(feature = "with_mumps")]
 pub use crate::complex_solver_mumps::ComplexSolverMUMPS;
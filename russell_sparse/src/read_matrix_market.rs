@@ -1,15 +1,36 @@
 use super::{CooMatrix, MMsym, Sym};
 use crate::{ComplexCooMatrix, StrError};
+use flate2::read::GzDecoder;
 use russell_lab::{cpx, Complex64};
 use std::ffi::OsStr;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
+/// Opens a text file for buffered reading, decompressing it on the fly if it is gzip-compressed
+///
+/// **Note:** The compression is detected by the gzip magic number; thus, the extension of the file is irrelevant.
+pub(crate) fn open_text_file<P>(full_path: &P) -> Result<Box<dyn BufRead>, StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let path = Path::new(full_path).to_path_buf();
+    let input = File::open(path).map_err(|_| "cannot open file")?;
+    let mut buffered = BufReader::new(input);
+    let magic = buffered.fill_buf().map_err(|_| "cannot read file")?;
+    if magic.len() >= 2 && magic[0] == 0x1f && magic[1] == 0x8b {
+        Ok(Box::new(BufReader::new(GzDecoder::new(buffered))))
+    } else {
+        Ok(Box::new(buffered))
+    }
+}
+
 struct MatrixMarketData {
     // header
-    complex: bool,
-    symmetric: bool,
+    array: bool,     // dense (array) format instead of coordinate format
+    complex: bool,   // complex values
+    pattern: bool,   // pattern only (no values)
+    symmetric: bool, // only the lower triangle is given
 
     // dimensions
     m: i32,   // number of rows
@@ -22,12 +43,18 @@ struct MatrixMarketData {
     aij: f64, // current aij-value (real part if complex)
     bij: f64, // current imaginary part of the aij-value (if complex)
     pos: i32, // current position in the list of triples
+
+    // next indices (array format only)
+    next_i: i32,
+    next_j: i32,
 }
 
 impl MatrixMarketData {
     fn new() -> Self {
         MatrixMarketData {
+            array: false,
             complex: false,
+            pattern: false,
             symmetric: false,
             m: 0,
             n: 0,
@@ -37,6 +64,8 @@ impl MatrixMarketData {
             aij: 0.0,
             bij: 0.0,
             pos: 0,
+            next_i: 0,
+            next_j: 0,
         }
     }
 
@@ -63,19 +92,22 @@ impl MatrixMarketData {
         }
 
         match data.next() {
-            Some(v) => {
-                if v != "coordinate" {
-                    return Err("after %%MatrixMarket, the second option must be \"coordinate\"");
-                }
-            }
+            Some(v) => match v {
+                "coordinate" => self.array = false,
+                "array" => self.array = true,
+                _ => return Err("after %%MatrixMarket, the second option must be \"coordinate\" or \"array\""),
+            },
             None => return Err("cannot find the second option in the header line"),
         }
 
         match data.next() {
             Some(v) => match v {
-                "real" => self.complex = false,
+                "real" | "integer" => self.complex = false,
                 "complex" => self.complex = true,
-                _ => return Err("after %%MatrixMarket, the third option must be \"real\" or \"complex\""),
+                "pattern" => self.pattern = true,
+                _ => return Err(
+                    "after %%MatrixMarket, the third option must be \"real\", \"integer\", \"complex\", or \"pattern\"",
+                ),
             },
             None => return Err("cannot find the third option in the header line"),
         }
@@ -89,6 +121,10 @@ impl MatrixMarketData {
             None => return Err("cannot find the fourth option in the header line"),
         }
 
+        if self.array && self.pattern {
+            return Err("the \"pattern\" option is not allowed with the \"array\" format");
+        }
+
         Ok(())
     }
 
@@ -112,10 +148,19 @@ impl MatrixMarketData {
             None => return Err("cannot read number of columns"),
         };
 
-        match data.next() {
-            Some(v) => self.nnz = v.parse().map_err(|_| "cannot parse number of non-zeros")?,
-            None => return Err("cannot read number of non-zeros"),
-        };
+        if self.array {
+            // all entries (or the lower triangle + diagonal if symmetric) are given in column-major order
+            self.nnz = if self.symmetric && self.m == self.n {
+                self.n * (self.n + 1) / 2
+            } else {
+                self.m * self.n
+            };
+        } else {
+            match data.next() {
+                Some(v) => self.nnz = v.parse().map_err(|_| "cannot parse number of non-zeros")?,
+                None => return Err("cannot read number of non-zeros"),
+            };
+        }
 
         if self.m < 1 || self.n < 1 || self.nnz < 1 {
             return Err("found invalid (zero or negative) dimensions");
@@ -137,6 +182,10 @@ impl MatrixMarketData {
 
         let mut data = maybe_data.split_whitespace();
 
+        if self.array {
+            return self.parse_array_value(data);
+        }
+
         self.i = data
             .next()
             .unwrap() // must panic because no error expected here
@@ -148,6 +197,17 @@ impl MatrixMarketData {
             None => return Err("cannot read j"),
         };
 
+        if self.pattern {
+            self.aij = 1.0;
+            self.i -= 1; // MatrixMarket is one-based, so make it zero-based here
+            self.j -= 1;
+            if self.i < 0 || self.i >= self.m || self.j < 0 || self.j >= self.n {
+                return Err("found an invalid index");
+            }
+            self.pos += 1;
+            return Ok(true);
+        }
+
         match data.next() {
             Some(v) => self.aij = v.parse().map_err(|_| "cannot parse aij")?,
             None => return Err("cannot read aij"),
@@ -171,10 +231,41 @@ impl MatrixMarketData {
 
         Ok(true) // returns true == parsed
     }
+
+    #[inline]
+    fn parse_array_value(&mut self, mut data: std::str::SplitWhitespace) -> Result<bool, StrError> {
+        self.aij = data
+            .next()
+            .unwrap() // must panic because no error expected here
+            .parse()
+            .map_err(|_| "cannot parse aij")?;
+
+        if self.complex {
+            match data.next() {
+                Some(v) => self.bij = v.parse().map_err(|_| "cannot parse bij")?,
+                None => return Err("cannot read bij"),
+            };
+        }
+
+        // column-major order (lower triangle + diagonal if symmetric)
+        self.i = self.next_i;
+        self.j = self.next_j;
+        self.next_i += 1;
+        if self.next_i == self.m {
+            self.next_j += 1;
+            self.next_i = if self.symmetric { self.next_j } else { 0 };
+        }
+
+        self.pos += 1; // next position
+
+        Ok(true) // returns true == parsed
+    }
 }
 
 /// Reads a MatrixMarket file into a CooMatrix
 ///
+/// The file may be gzip-compressed (e.g., `matrix.mtx.gz`), in which case it is decompressed on the fly.
+///
 /// # Input
 ///
 /// * `full_path` -- may be a String, &str, or Path
@@ -238,7 +329,7 @@ impl MatrixMarketData {
 /// ## Remarks
 ///
 /// * The first line is the **header line**, in the following format:
-///     * `%%MatrixMarket matrix coordinate {real, complex} {general, symmetric}` where only
+///     * `%%MatrixMarket matrix {coordinate, array} {real, integer, complex, pattern} {general, symmetric}` where only
 ///       one option within curly braces is present
 /// * After the header line, the percentage character begins a comment line
 /// * After the header line, a line with dimensions `m n nnz` must follow
@@ -246,6 +337,11 @@ impl MatrixMarketData {
 /// * After the dimensions line, `nnz` data lines containing either `i j aij` (real) or `i j aij bij` (complex)
 ///   must follow. For the complex case, `aij` is the real part and `bij` is the imaginary part
 /// * The indices start at one (1-based indices)
+/// * The `integer` field is read as `real`
+/// * The `pattern` field has no values, i.e., the data lines contain only `i j`; all values are set to 1.0
+/// * The `array` format corresponds to a dense matrix whose values are given in column-major order, one per line,
+///   after the line with the dimensions `m n`. If symmetric, only the lower triangle + diagonal is given.
+///   The zero values of an `array` file are not stored in the COO matrix.
 ///
 /// # Reference
 ///
@@ -340,9 +436,7 @@ pub fn read_matrix_market<P>(
 where
     P: AsRef<OsStr> + ?Sized,
 {
-    let path = Path::new(full_path).to_path_buf();
-    let input = File::open(path).map_err(|_| "cannot open file")?;
-    let buffered = BufReader::new(input);
+    let buffered = open_text_file(full_path)?;
     let mut lines_iter = buffered.lines();
 
     // auxiliary data structure
@@ -350,7 +444,7 @@ where
 
     // read first line
     let header = match lines_iter.next() {
-        Some(v) => v.unwrap(), // must panic because no error expected here
+        Some(v) => v.map_err(|_| "cannot read file")?,
         None => return Err("the file is empty"),
     };
 
@@ -359,7 +453,10 @@ where
 
     // read and parse dimensions
     loop {
-        let line = lines_iter.next().unwrap().unwrap(); // must panic because no error expected here
+        let line = match lines_iter.next() {
+            Some(v) => v.map_err(|_| "cannot read file")?,
+            None => return Err("cannot find the dimensions line"),
+        };
         if data.parse_dimensions(&line)? {
             break;
         }
@@ -391,8 +488,8 @@ where
         loop {
             match lines_iter.next() {
                 Some(v) => {
-                    let line = v.unwrap(); // must panic because no error expected here
-                    if data.parse_values(&line)? {
+                    let line = v.map_err(|_| "cannot read file")?;
+                    if data.parse_values(&line)? && !(data.array && data.aij == 0.0 && data.bij == 0.0) {
                         if data.symmetric {
                             match symmetric_handling {
                                 MMsym::LeaveAsLower => {
@@ -430,8 +527,8 @@ where
         loop {
             match lines_iter.next() {
                 Some(v) => {
-                    let line = v.unwrap(); // must panic because no error expected here
-                    if data.parse_values(&line)? {
+                    let line = v.map_err(|_| "cannot read file")?;
+                    if data.parse_values(&line)? && !(data.array && data.aij == 0.0 && data.bij == 0.0) {
                         if data.symmetric {
                             match symmetric_handling {
                                 MMsym::LeaveAsLower => {
@@ -498,7 +595,7 @@ mod tests {
         );
         assert_eq!(
             data.parse_header("%%MatrixMarket   matrix wrong"),
-            Err("after %%MatrixMarket, the second option must be \"coordinate\""),
+            Err("after %%MatrixMarket, the second option must be \"coordinate\" or \"array\""),
         );
 
         assert_eq!(
@@ -507,7 +604,7 @@ mod tests {
         );
         assert_eq!(
             data.parse_header("%%MatrixMarket matrix    coordinate  wrong"),
-            Err("after %%MatrixMarket, the third option must be \"real\" or \"complex\""),
+            Err("after %%MatrixMarket, the third option must be \"real\", \"integer\", \"complex\", or \"pattern\""),
         );
 
         assert_eq!(
@@ -518,6 +615,10 @@ mod tests {
             data.parse_header("  %%MatrixMarket matrix coordinate real wrong"),
             Err("after %%MatrixMarket, the fourth option must be either \"general\" or \"symmetric\""),
         );
+        assert_eq!(
+            data.parse_header("%%MatrixMarket matrix array pattern general"),
+            Err("the \"pattern\" option is not allowed with the \"array\" format"),
+        );
     }
 
     #[test]
@@ -823,4 +924,58 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn read_matrix_market_array_works() {
+        let h = MMsym::LeaveAsLower;
+        let (coo_real, _) = read_matrix_market("./data/matrix_market/ok_array_general.mtx", h).unwrap();
+        let coo = coo_real.unwrap();
+        assert_eq!(coo.symmetric, Sym::No);
+        assert_eq!((coo.nrow, coo.ncol, coo.nnz, coo.max_nnz), (3, 2, 4, 6));
+        let a = coo.as_dense();
+        assert_eq!(a.as_data(), &[1.0, 0.0, 3.0, 2.0, 4.0, 0.0]);
+
+        let h = MMsym::MakeItFull;
+        let (coo_real, _) = read_matrix_market("./data/matrix_market/ok_array_symmetric.mtx", h).unwrap();
+        let coo = coo_real.unwrap();
+        assert_eq!(coo.symmetric, Sym::YesFull);
+        assert_eq!((coo.nrow, coo.ncol, coo.nnz), (3, 3, 7));
+        let correct = Matrix::from(&[[1.0, 2.0, 0.0], [2.0, 3.0, 4.0], [0.0, 4.0, 5.0]]);
+        assert_eq!(coo.as_dense().as_data(), correct.as_data());
+
+        let h = MMsym::LeaveAsLower;
+        let (_, coo_cpx) = read_matrix_market("./data/matrix_market/ok_array_complex.mtx", h).unwrap();
+        let coo = coo_cpx.unwrap();
+        assert_eq!((coo.nrow, coo.ncol, coo.nnz), (2, 2, 3));
+        assert_eq!(coo.indices_i[..3], [0, 0, 1]);
+        assert_eq!(coo.indices_j[..3], [0, 1, 1]);
+        assert_eq!(coo.values[..3], [cpx!(1.0, 1.0), cpx!(2.0, -1.0), cpx!(3.0, 0.5)]);
+    }
+
+    #[test]
+    fn read_matrix_market_pattern_and_integer_work() {
+        let h = MMsym::LeaveAsLower;
+        let (coo_real, _) = read_matrix_market("./data/matrix_market/ok_pattern_general.mtx", h).unwrap();
+        let coo = coo_real.unwrap();
+        assert_eq!(coo.symmetric, Sym::No);
+        assert_eq!(coo.indices_i, &[0, 1, 1, 2]);
+        assert_eq!(coo.indices_j, &[0, 0, 1, 2]);
+        assert_eq!(coo.values, &[1.0, 1.0, 1.0, 1.0]);
+
+        let (coo_real, _) = read_matrix_market("./data/matrix_market/ok_integer_symmetric.mtx", h).unwrap();
+        let coo = coo_real.unwrap();
+        assert_eq!(coo.symmetric, Sym::YesLower);
+        assert_eq!(coo.values, &[1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn read_matrix_market_gzip_works() {
+        let h = MMsym::LeaveAsLower;
+        let (coo_gz, _) = read_matrix_market("./data/matrix_market/ok_simple_general.mtx.gz", h).unwrap();
+        let (coo_txt, _) = read_matrix_market("./data/matrix_market/ok_simple_general.mtx", h).unwrap();
+        let (coo_gz, coo_txt) = (coo_gz.unwrap(), coo_txt.unwrap());
+        assert_eq!(coo_gz.indices_i, coo_txt.indices_i);
+        assert_eq!(coo_gz.indices_j, coo_txt.indices_j);
+        assert_eq!(coo_gz.values, coo_txt.values);
+    }
 }
@@ -0,0 +1,507 @@
+use super::{open_text_file, CooMatrix, MMsym, Sym};
+use crate::{ComplexCooMatrix, StrError};
+use russell_lab::{cpx, Complex64};
+use std::ffi::OsStr;
+use std::io::{BufRead, Lines};
+
+/// Holds the description of a fixed-width Fortran format such as `(10I8)` or `(1P,4E20.12)`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct FortranFormat {
+    /// Number of fields per line
+    pub(crate) count: usize,
+
+    /// Width of each field
+    pub(crate) width: usize,
+}
+
+impl FortranFormat {
+    /// Parses a Fortran format string
+    ///
+    /// The scale factor (e.g., `1P,`) and the number of decimal digits are ignored.
+    pub(crate) fn parse(format: &str) -> Result<Self, StrError> {
+        let upper: String = format
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_uppercase();
+        let mut spec = upper.trim_start_matches('(').trim_end_matches(')');
+        if let Some(p) = spec.find('P') {
+            spec = spec[(p + 1)..].trim_start_matches(',');
+        }
+        let letter = match spec.find(['I', 'E', 'D', 'F', 'G']) {
+            Some(p) => p,
+            None => return Err("cannot find the data descriptor (I, E, D, F, or G) in the Fortran format"),
+        };
+        let count = if letter == 0 {
+            1
+        } else {
+            spec[..letter]
+                .parse()
+                .map_err(|_| "cannot parse the repeat count in the Fortran format")?
+        };
+        let width_str: String = spec[(letter + 1)..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        let width: usize = width_str
+            .parse()
+            .map_err(|_| "cannot parse the field width in the Fortran format")?;
+        if count < 1 || width < 1 {
+            return Err("found invalid (zero) count or width in the Fortran format");
+        }
+        Ok(FortranFormat { count, width })
+    }
+
+    /// Reads a number of fixed-width fields, possibly spanning several lines
+    fn read_fields<B: BufRead>(&self, lines: &mut Lines<B>, total: usize) -> Result<Vec<String>, StrError> {
+        let mut fields = Vec::with_capacity(total);
+        while fields.len() < total {
+            let line = match lines.next() {
+                Some(v) => v.map_err(|_| "cannot read file")?,
+                None => return Err("not all values have been found"),
+            };
+            let bytes = line.trim_end().as_bytes();
+            for k in 0..self.count {
+                let start = k * self.width;
+                if start >= bytes.len() || fields.len() == total {
+                    break;
+                }
+                let end = usize::min(start + self.width, bytes.len());
+                let field = std::str::from_utf8(&bytes[start..end]).map_err(|_| "found an invalid character")?;
+                fields.push(field.trim().to_string());
+            }
+        }
+        Ok(fields)
+    }
+
+    /// Reads a number of integers, possibly spanning several lines
+    pub(crate) fn read_ints<B: BufRead>(&self, lines: &mut Lines<B>, total: usize) -> Result<Vec<i64>, StrError> {
+        let fields = self.read_fields(lines, total)?;
+        fields
+            .iter()
+            .map(|f| f.parse().map_err(|_| "cannot parse integer"))
+            .collect()
+    }
+
+    /// Reads a number of reals, possibly spanning several lines
+    ///
+    /// Handles the `D` exponent and the exponent without letter (e.g., `1.0-100`).
+    pub(crate) fn read_reals<B: BufRead>(&self, lines: &mut Lines<B>, total: usize) -> Result<Vec<f64>, StrError> {
+        let fields = self.read_fields(lines, total)?;
+        fields.iter().map(|f| parse_fortran_real(f)).collect()
+    }
+}
+
+/// Parses a real number written by Fortran
+fn parse_fortran_real(field: &str) -> Result<f64, StrError> {
+    let mut s = field.trim().replace(['D', 'd'], "E");
+    if let Ok(v) = s.parse() {
+        return Ok(v);
+    }
+    // exponent without letter, e.g., 1.0-100 or 1.0+100 (the first character may be the sign)
+    let start = s.chars().next().map_or(0, |c| c.len_utf8());
+    if let Some(p) = s[start..].rfind(['+', '-']) {
+        let p = p + start;
+        if !s[..p].ends_with('E') {
+            s.insert(p, 'E');
+        }
+    }
+    s.parse().map_err(|_| "cannot parse real number")
+}
+
+/// Returns the parenthesized groups (e.g., Fortran formats) found in a line
+fn parenthesized_groups(line: &str) -> Vec<String> {
+    let mut groups = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in line.chars() {
+        if c == '(' {
+            depth += 1;
+        }
+        if depth > 0 {
+            current.push(c);
+        }
+        if c == ')' && depth > 0 {
+            depth -= 1;
+            if depth == 0 {
+                groups.push(current.clone());
+                current.clear();
+            }
+        }
+    }
+    groups
+}
+
+/// Reads a header line with integers
+fn read_header_ints<B: BufRead>(lines: &mut Lines<B>) -> Result<Vec<i64>, StrError> {
+    let line = match lines.next() {
+        Some(v) => v.map_err(|_| "cannot read file")?,
+        None => return Err("the header is incomplete"),
+    };
+    line.split_whitespace()
+        .map(|v| v.parse().map_err(|_| "cannot parse the header"))
+        .collect()
+}
+
+/// Reads a Rutherford-Boeing or Harwell-Boeing file into a CooMatrix
+///
+/// The file may be gzip-compressed, in which case it is decompressed on the fly.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+/// * `symmetric_handling` -- Options to handle symmetric matrices (see [crate::read_matrix_market()])
+///
+/// # Output
+///
+/// Returns either a [CooMatrix] or a [ComplexCooMatrix]. One of each will be `Some` while
+/// the other will be `None`. Pattern (and integer) matrices are returned as real matrices
+/// with the values of a pattern matrix set to 1.0.
+///
+/// # File format
+///
+/// ```text
+/// Line 1: TITLE (A72), KEY (A8)
+/// Line 2: TOTCRD, PTRCRD, INDCRD, VALCRD, [RHSCRD] (I14 each; RHSCRD only in Harwell-Boeing)
+/// Line 3: MXTYPE (A3), blank (11X), NROW, NCOL, NNZERO, NELTVL (I14 each)
+/// Line 4: PTRFMT, INDFMT (A16 each), VALFMT, [RHSFMT] (A20 each)
+/// Line 5: only in Harwell-Boeing, if RHSCRD > 0 (the right-hand sides are ignored)
+/// Then: the column pointers, the row indices, and the values (unless pattern) in fixed-width Fortran formats
+/// ```
+///
+/// The three-character MXTYPE is:
+///
+/// 1. `R` real, `C` complex, `P` pattern, or `I` integer
+/// 2. `U` unsymmetric, `R` rectangular, `S` symmetric, `H` Hermitian, or `Z` skew-symmetric
+/// 3. `A` assembled (elemental matrices, `E`, are not supported)
+///
+/// For symmetric, Hermitian, and skew-symmetric matrices, only the lower triangle is stored in the file.
+/// Hermitian and skew-symmetric matrices require `MMsym::MakeItFull` because they are not symmetric
+/// in the sense of [Sym].
+///
+/// # References
+///
+/// * Duff IS, Grimes RG, Lewis JG (1997) The Rutherford-Boeing sparse matrix collection,
+///   Technical Report RAL-TR-97-031, Rutherford Appleton Laboratory
+/// * Duff IS, Grimes RG, Lewis JG (1992) Users' guide for the Harwell-Boeing sparse matrix collection,
+///   Technical Report TR/PA/92/86, CERFACS
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     let name = "./data/rutherford_boeing/ok_pattern_symmetric.psa";
+///     let (coo_real, _) = read_rutherford_boeing(name, MMsym::MakeItFull)?;
+///     let coo = coo_real.unwrap();
+///     let a = coo.as_dense();
+///     let correct = "┌       ┐\n\
+///                    │ 1 1 0 │\n\
+///                    │ 1 1 1 │\n\
+///                    │ 0 1 0 │\n\
+///                    └       ┘";
+///     assert_eq!(format!("{}", a), correct);
+///     Ok(())
+/// }
+/// ```
+pub fn read_rutherford_boeing<P>(
+    full_path: &P,
+    symmetric_handling: MMsym,
+) -> Result<(Option<CooMatrix>, Option<ComplexCooMatrix>), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let buffered = open_text_file(full_path)?;
+    let mut lines = buffered.lines();
+
+    // title and key
+    match lines.next() {
+        Some(v) => v.map_err(|_| "cannot read file")?,
+        None => return Err("the file is empty"),
+    };
+
+    // number of cards (only RHSCRD is needed)
+    let cards = read_header_ints(&mut lines)?;
+    if cards.len() < 4 {
+        return Err("the second line of the header must contain at least four integers");
+    }
+    let rhscrd = if cards.len() > 4 { cards[4] } else { 0 };
+
+    // matrix type and dimensions
+    let line = match lines.next() {
+        Some(v) => v.map_err(|_| "cannot read file")?,
+        None => return Err("the header is incomplete"),
+    };
+    if line.len() < 3 {
+        return Err("cannot read the matrix type");
+    }
+    let mxtype: Vec<char> = line[..3].to_uppercase().chars().collect();
+    let dims: Vec<i64> = line[3..]
+        .split_whitespace()
+        .map(|v| v.parse().map_err(|_| "cannot parse the dimensions"))
+        .collect::<Result<_, _>>()?;
+    if dims.len() < 3 {
+        return Err("cannot read the dimensions");
+    }
+    let (nrow, ncol, nnz) = (dims[0], dims[1], dims[2]);
+    if nrow < 1 || ncol < 1 || nnz < 1 {
+        return Err("found invalid (zero or negative) dimensions");
+    }
+    let (nrow, ncol, nnz) = (nrow as usize, ncol as usize, nnz as usize);
+
+    // check the matrix type
+    let (complex, pattern) = match mxtype[0] {
+        'R' | 'I' => (false, false),
+        'C' => (true, false),
+        'P' => (false, true),
+        _ => return Err("the first character of the matrix type must be R, C, P, or I"),
+    };
+    let (symmetric, hermitian, skew) = match mxtype[1] {
+        'U' | 'R' => (false, false, false),
+        'S' => (true, false, false),
+        'H' => (true, true, false),
+        'Z' => (true, false, true),
+        _ => return Err("the second character of the matrix type must be U, R, S, H, or Z"),
+    };
+    match mxtype[2] {
+        'A' => (),
+        'E' => return Err("elemental matrices are not supported"),
+        _ => return Err("the third character of the matrix type must be A"),
+    }
+    if symmetric && nrow != ncol {
+        return Err("the number of rows must equal the number of columns for symmetric matrices");
+    }
+    if (hermitian || skew) && symmetric_handling != MMsym::MakeItFull {
+        return Err("Hermitian and skew-symmetric matrices require MMsym::MakeItFull");
+    }
+
+    // formats
+    let line = match lines.next() {
+        Some(v) => v.map_err(|_| "cannot read file")?,
+        None => return Err("the header is incomplete"),
+    };
+    let formats = parenthesized_groups(&line);
+    let required = if pattern { 2 } else { 3 };
+    if formats.len() < required {
+        return Err("cannot find the Fortran formats in the header");
+    }
+    let ptr_fmt = FortranFormat::parse(&formats[0])?;
+    let ind_fmt = FortranFormat::parse(&formats[1])?;
+
+    // right-hand side information (Harwell-Boeing only)
+    if rhscrd > 0 {
+        lines.next();
+    }
+
+    // pointers and indices
+    let col_pointers = ptr_fmt.read_ints(&mut lines, ncol + 1)?;
+    let row_indices = ind_fmt.read_ints(&mut lines, nnz)?;
+    if col_pointers[0] != 1 || col_pointers[ncol] != (nnz + 1) as i64 {
+        return Err("found invalid column pointers");
+    }
+
+    // values
+    let values = if pattern {
+        vec![1.0; nnz]
+    } else {
+        let val_fmt = FortranFormat::parse(&formats[2])?;
+        let factor = if complex { 2 } else { 1 };
+        val_fmt.read_reals(&mut lines, factor * nnz)?
+    };
+
+    // symmetric type
+    let sym = if symmetric {
+        match symmetric_handling {
+            MMsym::LeaveAsLower => Sym::YesLower,
+            MMsym::SwapToUpper => Sym::YesUpper,
+            MMsym::MakeItFull => Sym::YesFull,
+        }
+    } else {
+        Sym::No
+    };
+    let max = if symmetric && symmetric_handling == MMsym::MakeItFull {
+        2 * nnz
+    } else {
+        nnz
+    };
+
+    // triplets
+    let mut triplets = Vec::with_capacity(nnz);
+    for j in 0..ncol {
+        let (start, end) = (col_pointers[j], col_pointers[j + 1]);
+        if end < start {
+            return Err("found invalid column pointers");
+        }
+        for p in (start - 1)..(end - 1) {
+            let i = row_indices[p as usize] - 1;
+            if i < 0 || i >= nrow as i64 {
+                return Err("found an invalid index");
+            }
+            if symmetric && (i as usize) < j {
+                return Err("found an entry in the upper triangle of a symmetric matrix");
+            }
+            triplets.push((i as usize, j, p as usize));
+        }
+    }
+
+    // matrix
+    if complex {
+        let mut coo = ComplexCooMatrix::new(nrow, ncol, max, sym)?;
+        for (i, j, p) in triplets {
+            let aij = cpx!(values[2 * p], values[2 * p + 1]);
+            if !symmetric {
+                coo.put(i, j, aij)?;
+                continue;
+            }
+            match symmetric_handling {
+                MMsym::LeaveAsLower => coo.put(i, j, aij)?,
+                MMsym::SwapToUpper => coo.put(j, i, aij)?,
+                MMsym::MakeItFull => {
+                    coo.put(i, j, aij)?;
+                    if i != j {
+                        let aji = if hermitian {
+                            aij.conj()
+                        } else if skew {
+                            -aij
+                        } else {
+                            aij
+                        };
+                        coo.put(j, i, aji)?;
+                    }
+                }
+            }
+        }
+        Ok((None, Some(coo)))
+    } else {
+        if hermitian {
+            return Err("the Hermitian type requires complex values");
+        }
+        let mut coo = CooMatrix::new(nrow, ncol, max, sym)?;
+        for (i, j, p) in triplets {
+            let aij = values[p];
+            if !symmetric {
+                coo.put(i, j, aij)?;
+                continue;
+            }
+            match symmetric_handling {
+                MMsym::LeaveAsLower => coo.put(i, j, aij)?,
+                MMsym::SwapToUpper => coo.put(j, i, aij)?,
+                MMsym::MakeItFull => {
+                    coo.put(i, j, aij)?;
+                    if i != j {
+                        coo.put(j, i, if skew { -aij } else { aij })?;
+                    }
+                }
+            }
+        }
+        Ok((Some(coo), None))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_fortran_real, read_rutherford_boeing, FortranFormat};
+    use crate::{MMsym, Samples, Sym};
+    use russell_lab::{cpx, Complex64};
+
+    #[test]
+    fn fortran_format_works() {
+        assert_eq!(
+            FortranFormat::parse("(10I8)").unwrap(),
+            FortranFormat { count: 10, width: 8 }
+        );
+        assert_eq!(
+            FortranFormat::parse("(1P,4E20.12)").unwrap(),
+            FortranFormat { count: 4, width: 20 }
+        );
+        assert_eq!(
+            FortranFormat::parse(" (1p5d16.8) ").unwrap(),
+            FortranFormat { count: 5, width: 16 }
+        );
+        assert_eq!(
+            FortranFormat::parse("(E26.16E3)").unwrap(),
+            FortranFormat { count: 1, width: 26 }
+        );
+        assert_eq!(
+            FortranFormat::parse("(10X8)").err(),
+            Some("cannot find the data descriptor (I, E, D, F, or G) in the Fortran format")
+        );
+        assert_eq!(
+            FortranFormat::parse("(10I)").err(),
+            Some("cannot parse the field width in the Fortran format")
+        );
+        assert_eq!(parse_fortran_real("1.5D+02").unwrap(), 150.0);
+        assert_eq!(parse_fortran_real("-2.5-101").unwrap(), -2.5e-101);
+        assert_eq!(parse_fortran_real("wrong").err(), Some("cannot parse real number"));
+        assert_eq!(parse_fortran_real(" 3.0D0 ").unwrap(), 3.0);
+        assert_eq!(parse_fortran_real("").err(), Some("cannot parse real number"));
+        assert_eq!(parse_fortran_real("    ").err(), Some("cannot parse real number"));
+        assert_eq!(parse_fortran_real("é-1").err(), Some("cannot parse real number"));
+    }
+
+    #[test]
+    fn read_rutherford_boeing_handles_errors() {
+        let h = MMsym::LeaveAsLower;
+        assert_eq!(read_rutherford_boeing("__wrong__", h).err(), Some("cannot open file"));
+        assert_eq!(
+            read_rutherford_boeing("./data/matrix_market/bad_empty_file.mtx", h).err(),
+            Some("the file is empty")
+        );
+        assert_eq!(
+            read_rutherford_boeing("./data/rutherford_boeing/bad_elemental.rue", h).err(),
+            Some("elemental matrices are not supported")
+        );
+        assert_eq!(
+            read_rutherford_boeing("./data/rutherford_boeing/ok_complex_hermitian.cha", h).err(),
+            Some("Hermitian and skew-symmetric matrices require MMsym::MakeItFull")
+        );
+    }
+
+    #[test]
+    fn read_harwell_boeing_works() {
+        let h = MMsym::LeaveAsLower;
+        let (coo_real, coo_cpx) = read_rutherford_boeing("./data/rutherford_boeing/ok_unsymmetric_hb.rua", h).unwrap();
+        assert!(coo_cpx.is_none());
+        let coo = coo_real.unwrap();
+        let (correct, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        assert_eq!(coo.symmetric, Sym::No);
+        assert_eq!(coo.as_dense().as_data(), correct.as_dense().as_data());
+    }
+
+    #[test]
+    fn read_rutherford_boeing_pattern_works() {
+        let h = MMsym::LeaveAsLower;
+        let (coo_real, _) = read_rutherford_boeing("./data/rutherford_boeing/ok_pattern_symmetric.psa", h).unwrap();
+        let coo = coo_real.unwrap();
+        assert_eq!(coo.symmetric, Sym::YesLower);
+        assert_eq!(coo.indices_i, &[0, 1, 1, 2]);
+        assert_eq!(coo.indices_j, &[0, 0, 1, 1]);
+        assert_eq!(coo.values, &[1.0, 1.0, 1.0, 1.0]);
+
+        let h = MMsym::SwapToUpper;
+        let (coo_real, _) = read_rutherford_boeing("./data/rutherford_boeing/ok_pattern_symmetric.psa", h).unwrap();
+        let coo = coo_real.unwrap();
+        assert_eq!(coo.symmetric, Sym::YesUpper);
+        assert_eq!(coo.indices_i, &[0, 0, 1, 1]);
+        assert_eq!(coo.indices_j, &[0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn read_rutherford_boeing_hermitian_works() {
+        let h = MMsym::MakeItFull;
+        let (coo_real, coo_cpx) =
+            read_rutherford_boeing("./data/rutherford_boeing/ok_complex_hermitian.cha", h).unwrap();
+        assert!(coo_real.is_none());
+        let coo = coo_cpx.unwrap();
+        assert_eq!(coo.symmetric, Sym::YesFull);
+        let a = coo.as_dense();
+        assert_eq!(a.get(0, 0), cpx!(2.0, 0.0));
+        assert_eq!(a.get(1, 0), cpx!(1.0, 1.0));
+        assert_eq!(a.get(0, 1), cpx!(1.0, -1.0));
+        assert_eq!(a.get(1, 1), cpx!(3.0, 0.0));
+    }
+}
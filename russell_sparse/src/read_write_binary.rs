@@ -0,0 +1,460 @@
+use super::{ComplexCscMatrix, ComplexCsrMatrix, CscMatrix, CsrMatrix, Sym};
+use crate::StrError;
+use russell_lab::{cpx, Complex64};
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+/// Magic number at the beginning of the binary files
+const BINARY_MAGIC: &[u8; 8] = b"RSSPARSE";
+
+/// Version of the binary format
+///
+/// Layout of version 1 (little-endian):
+///
+/// ```text
+/// magic       8 bytes   "RSSPARSE"
+/// version     u32       1
+/// storage     u8        0 = CSC, 1 = CSR
+/// value type  u8        0 = f64, 1 = Complex64 (real and imaginary parts as f64)
+/// symmetric   u8        0 = No, 1 = YesFull, 2 = YesLower, 3 = YesUpper
+/// reserved    u8        0
+/// nrow        u64
+/// ncol        u64
+/// nnz         u64
+/// pointers    i32 × (ncol + 1) for CSC or i32 × (nrow + 1) for CSR
+/// indices     i32 × nnz
+/// values      f64 × nnz (or f64 × 2·nnz if complex)
+/// ```
+const BINARY_VERSION: u32 = 1;
+
+/// Size of the header in bytes
+const BINARY_HEADER_SIZE: usize = 8 + 4 + 4 + 3 * 8;
+
+const STORAGE_CSC: u8 = 0;
+const STORAGE_CSR: u8 = 1;
+const VALUE_REAL: u8 = 0;
+const VALUE_COMPLEX: u8 = 1;
+
+/// Holds the header and the raw arrays read from a binary file
+struct BinaryData {
+    symmetric: Sym,
+    nrow: usize,
+    ncol: usize,
+    pointers: Vec<i32>,
+    indices: Vec<i32>,
+    values: Vec<f64>, // flattened if complex
+}
+
+fn sym_to_code(symmetric: Sym) -> u8 {
+    match symmetric {
+        Sym::No => 0,
+        Sym::YesFull => 1,
+        Sym::YesLower => 2,
+        Sym::YesUpper => 3,
+    }
+}
+
+fn code_to_sym(code: u8) -> Result<Sym, StrError> {
+    match code {
+        0 => Ok(Sym::No),
+        1 => Ok(Sym::YesFull),
+        2 => Ok(Sym::YesLower),
+        3 => Ok(Sym::YesUpper),
+        _ => Err("the binary file contains an invalid symmetric type"),
+    }
+}
+
+/// Writes the binary file
+fn write_binary_file(
+    full_path: &Path,
+    storage: u8,
+    value_type: u8,
+    symmetric: Sym,
+    nrow: usize,
+    ncol: usize,
+    pointers: &[i32],
+    indices: &[i32],
+    values: &[f64],
+) -> Result<(), StrError> {
+    let nnz = indices.len();
+    let mut buffer = Vec::with_capacity(BINARY_HEADER_SIZE + 4 * (pointers.len() + nnz) + 8 * values.len());
+    buffer.extend_from_slice(BINARY_MAGIC);
+    buffer.extend_from_slice(&BINARY_VERSION.to_le_bytes());
+    buffer.extend_from_slice(&[storage, value_type, sym_to_code(symmetric), 0]);
+    buffer.extend_from_slice(&(nrow as u64).to_le_bytes());
+    buffer.extend_from_slice(&(ncol as u64).to_le_bytes());
+    buffer.extend_from_slice(&(nnz as u64).to_le_bytes());
+    for v in pointers {
+        buffer.extend_from_slice(&v.to_le_bytes());
+    }
+    for v in indices {
+        buffer.extend_from_slice(&v.to_le_bytes());
+    }
+    for v in values {
+        buffer.extend_from_slice(&v.to_le_bytes());
+    }
+
+    // create directory
+    if let Some(p) = full_path.parent() {
+        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    }
+
+    // write file
+    let mut file = File::create(full_path).map_err(|_| "cannot create file")?;
+    file.write_all(&buffer).map_err(|_| "cannot write file")?;
+
+    // force sync
+    file.sync_all().map_err(|_| "cannot sync file")?;
+    Ok(())
+}
+
+/// Reads the binary file
+fn read_binary_file(full_path: &Path, storage: u8, value_type: u8) -> Result<BinaryData, StrError> {
+    let bytes = fs::read(full_path).map_err(|_| "cannot open file")?;
+    if bytes.len() < BINARY_HEADER_SIZE || &bytes[0..8] != BINARY_MAGIC {
+        return Err("the file is not a russell_sparse binary file");
+    }
+    let u64_at = |p: usize| u64::from_le_bytes(bytes[p..(p + 8)].try_into().unwrap()) as usize; // unwrap OK because the size is 8
+    let version = u32::from_le_bytes(bytes[8..12].try_into().unwrap()); // unwrap OK because the size is 4
+    if version != BINARY_VERSION {
+        return Err("the version of the binary file is not supported");
+    }
+    if bytes[12] != storage {
+        return Err("the storage (CSC or CSR) of the binary file does not match the matrix type");
+    }
+    if bytes[13] != value_type {
+        return Err("the value type (real or complex) of the binary file does not match the matrix type");
+    }
+    let symmetric = code_to_sym(bytes[14])?;
+    let nrow = u64_at(16);
+    let ncol = u64_at(24);
+    let nnz = u64_at(32);
+    // (checked arithmetic because the header may be corrupted)
+    let sizes = || {
+        let npointer = if storage == STORAGE_CSC { ncol } else { nrow }.checked_add(1)?;
+        let nvalue = if value_type == VALUE_COMPLEX {
+            nnz.checked_mul(2)?
+        } else {
+            nnz
+        };
+        let size = npointer
+            .checked_add(nnz)?
+            .checked_mul(4)?
+            .checked_add(nvalue.checked_mul(8)?)?
+            .checked_add(BINARY_HEADER_SIZE)?;
+        Some((npointer, nvalue, size))
+    };
+    let (npointer, nvalue) = match sizes() {
+        Some((npointer, nvalue, size)) if size == bytes.len() => (npointer, nvalue),
+        _ => return Err("the size of the binary file is incorrect"),
+    };
+    let mut p = BINARY_HEADER_SIZE;
+    let mut pointers = Vec::with_capacity(npointer);
+    for _ in 0..npointer {
+        pointers.push(i32::from_le_bytes(bytes[p..(p + 4)].try_into().unwrap())); // unwrap OK because the size is 4
+        p += 4;
+    }
+    let mut indices = Vec::with_capacity(nnz);
+    for _ in 0..nnz {
+        indices.push(i32::from_le_bytes(bytes[p..(p + 4)].try_into().unwrap())); // unwrap OK because the size is 4
+        p += 4;
+    }
+    let mut values = Vec::with_capacity(nvalue);
+    for _ in 0..nvalue {
+        values.push(f64::from_le_bytes(bytes[p..(p + 8)].try_into().unwrap())); // unwrap OK because the size is 8
+        p += 8;
+    }
+    Ok(BinaryData {
+        symmetric,
+        nrow,
+        ncol,
+        pointers,
+        indices,
+        values,
+    })
+}
+
+/// Converts the values of complex matrix into a flattened array
+fn flatten(values: &[Complex64]) -> Vec<f64> {
+    values.iter().flat_map(|v| [v.re, v.im]).collect()
+}
+
+/// Converts a flattened array into complex values
+fn unflatten(values: &[f64]) -> Vec<Complex64> {
+    values.chunks_exact(2).map(|v| cpx!(v[0], v[1])).collect()
+}
+
+impl CscMatrix {
+    /// Writes a (versioned) binary file
+    ///
+    /// The binary format is much faster to read and write than the text formats (e.g., MatrixMarket).
+    /// The numbers are stored in little-endian order.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    ///
+    /// See also [CscMatrix::read_binary()]
+    pub fn write_binary<P>(&self, full_path: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let nnz = self.col_pointers[self.ncol] as usize;
+        write_binary_file(
+            Path::new(full_path),
+            STORAGE_CSC,
+            VALUE_REAL,
+            self.symmetric,
+            self.nrow,
+            self.ncol,
+            &self.col_pointers,
+            &self.row_indices[..nnz],
+            &self.values[..nnz],
+        )
+    }
+
+    /// Reads a binary file written by [CscMatrix::write_binary()]
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn read_binary<P>(full_path: &P) -> Result<Self, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let d = read_binary_file(Path::new(full_path), STORAGE_CSC, VALUE_REAL)?;
+        CscMatrix::new(d.nrow, d.ncol, d.pointers, d.indices, d.values, d.symmetric)
+    }
+}
+
+impl ComplexCscMatrix {
+    /// Writes a (versioned) binary file
+    ///
+    /// The binary format is much faster to read and write than the text formats (e.g., MatrixMarket).
+    /// The numbers are stored in little-endian order.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    ///
+    /// See also [ComplexCscMatrix::read_binary()]
+    pub fn write_binary<P>(&self, full_path: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let nnz = self.col_pointers[self.ncol] as usize;
+        write_binary_file(
+            Path::new(full_path),
+            STORAGE_CSC,
+            VALUE_COMPLEX,
+            self.symmetric,
+            self.nrow,
+            self.ncol,
+            &self.col_pointers,
+            &self.row_indices[..nnz],
+            &flatten(&self.values[..nnz]),
+        )
+    }
+
+    /// Reads a binary file written by [ComplexCscMatrix::write_binary()]
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn read_binary<P>(full_path: &P) -> Result<Self, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let d = read_binary_file(Path::new(full_path), STORAGE_CSC, VALUE_COMPLEX)?;
+        ComplexCscMatrix::new(d.nrow, d.ncol, d.pointers, d.indices, unflatten(&d.values), d.symmetric)
+    }
+}
+
+impl CsrMatrix {
+    /// Writes a (versioned) binary file
+    ///
+    /// The binary format is much faster to read and write than the text formats (e.g., MatrixMarket).
+    /// The numbers are stored in little-endian order.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    ///
+    /// See also [CsrMatrix::read_binary()]
+    pub fn write_binary<P>(&self, full_path: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let nnz = self.row_pointers[self.nrow] as usize;
+        write_binary_file(
+            Path::new(full_path),
+            STORAGE_CSR,
+            VALUE_REAL,
+            self.symmetric,
+            self.nrow,
+            self.ncol,
+            &self.row_pointers,
+            &self.col_indices[..nnz],
+            &self.values[..nnz],
+        )
+    }
+
+    /// Reads a binary file written by [CsrMatrix::write_binary()]
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn read_binary<P>(full_path: &P) -> Result<Self, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let d = read_binary_file(Path::new(full_path), STORAGE_CSR, VALUE_REAL)?;
+        CsrMatrix::new(d.nrow, d.ncol, d.pointers, d.indices, d.values, d.symmetric)
+    }
+}
+
+impl ComplexCsrMatrix {
+    /// Writes a (versioned) binary file
+    ///
+    /// The binary format is much faster to read and write than the text formats (e.g., MatrixMarket).
+    /// The numbers are stored in little-endian order.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    ///
+    /// See also [ComplexCsrMatrix::read_binary()]
+    pub fn write_binary<P>(&self, full_path: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let nnz = self.row_pointers[self.nrow] as usize;
+        write_binary_file(
+            Path::new(full_path),
+            STORAGE_CSR,
+            VALUE_COMPLEX,
+            self.symmetric,
+            self.nrow,
+            self.ncol,
+            &self.row_pointers,
+            &self.col_indices[..nnz],
+            &flatten(&self.values[..nnz]),
+        )
+    }
+
+    /// Reads a binary file written by [ComplexCsrMatrix::write_binary()]
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn read_binary<P>(full_path: &P) -> Result<Self, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let d = read_binary_file(Path::new(full_path), STORAGE_CSR, VALUE_COMPLEX)?;
+        ComplexCsrMatrix::new(d.nrow, d.ncol, d.pointers, d.indices, unflatten(&d.values), d.symmetric)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ComplexCooMatrix, Samples};
+
+    #[test]
+    fn read_binary_handles_errors() {
+        assert_eq!(CscMatrix::read_binary("__wrong__").err(), Some("cannot open file"));
+        assert_eq!(
+            CscMatrix::read_binary("./data/matrix_market/ok_simple_general.mtx").err(),
+            Some("the file is not a russell_sparse binary file")
+        );
+        let (_, csc, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let full_path = "/tmp/russell_sparse/test_read_binary_handles_errors.bin";
+        csc.write_binary(full_path).unwrap();
+        assert_eq!(
+            CsrMatrix::read_binary(full_path).err(),
+            Some("the storage (CSC or CSR) of the binary file does not match the matrix type")
+        );
+        assert_eq!(
+            ComplexCscMatrix::read_binary(full_path).err(),
+            Some("the value type (real or complex) of the binary file does not match the matrix type")
+        );
+        let mut bytes = fs::read(full_path).unwrap();
+        bytes[8] = 2;
+        fs::write(full_path, &bytes).unwrap();
+        assert_eq!(
+            CscMatrix::read_binary(full_path).err(),
+            Some("the version of the binary file is not supported")
+        );
+        bytes[8] = 1;
+        let last = bytes.pop().unwrap();
+        fs::write(full_path, &bytes).unwrap();
+        assert_eq!(
+            CscMatrix::read_binary(full_path).err(),
+            Some("the size of the binary file is incorrect")
+        );
+        bytes.push(last);
+        // corrupted header: ncol + 1 overflows
+        let mut corrupted = bytes.clone();
+        corrupted[24..32].copy_from_slice(&u64::MAX.to_le_bytes());
+        fs::write(full_path, &corrupted).unwrap();
+        assert_eq!(
+            CscMatrix::read_binary(full_path).err(),
+            Some("the size of the binary file is incorrect")
+        );
+        // corrupted header: 4 * (npointer + nnz) overflows
+        let mut corrupted = bytes.clone();
+        corrupted[32..40].copy_from_slice(&(u64::MAX / 4).to_le_bytes());
+        fs::write(full_path, &corrupted).unwrap();
+        assert_eq!(
+            CscMatrix::read_binary(full_path).err(),
+            Some("the size of the binary file is incorrect")
+        );
+    }
+
+    #[test]
+    fn csc_and_csr_binary_work() {
+        let (_, csc, csr, _) = Samples::umfpack_unsymmetric_5x5();
+        let full_path = "/tmp/russell_sparse/test_csc_binary.bin";
+        csc.write_binary(full_path).unwrap();
+        let res = CscMatrix::read_binary(full_path).unwrap();
+        assert_eq!(res.get_info(), csc.get_info());
+        assert_eq!(res.get_col_pointers(), csc.get_col_pointers());
+        assert_eq!(res.get_row_indices(), csc.get_row_indices());
+        assert_eq!(res.get_values(), csc.get_values());
+
+        let full_path = "/tmp/russell_sparse/test_csr_binary.bin";
+        csr.write_binary(full_path).unwrap();
+        let res = CsrMatrix::read_binary(full_path).unwrap();
+        assert_eq!(res.get_info(), csr.get_info());
+        assert_eq!(res.get_row_pointers(), csr.get_row_pointers());
+        assert_eq!(res.get_col_indices(), csr.get_col_indices());
+        assert_eq!(res.get_values(), csr.get_values());
+    }
+
+    #[test]
+    fn complex_csc_and_csr_binary_work() {
+        let mut coo = ComplexCooMatrix::new(3, 3, 4, Sym::YesLower).unwrap();
+        coo.put(0, 0, cpx!(1.0, 0.5)).unwrap();
+        coo.put(1, 0, cpx!(2.0, -1.0)).unwrap();
+        coo.put(1, 1, cpx!(3.0, 0.0)).unwrap();
+        coo.put(2, 2, cpx!(4.0, 1.0 / 3.0)).unwrap();
+        let csc = ComplexCscMatrix::from_coo(&coo).unwrap();
+        let full_path = "/tmp/russell_sparse/test_complex_csc_binary.bin";
+        csc.write_binary(full_path).unwrap();
+        let res = ComplexCscMatrix::read_binary(full_path).unwrap();
+        assert_eq!(res.get_info(), csc.get_info());
+        assert_eq!(res.as_dense().as_data(), csc.as_dense().as_data());
+
+        let csr = ComplexCsrMatrix::from_coo(&coo).unwrap();
+        let full_path = "/tmp/russell_sparse/test_complex_csr_binary.bin";
+        csr.write_binary(full_path).unwrap();
+        let res = ComplexCsrMatrix::read_binary(full_path).unwrap();
+        assert_eq!(res.get_info(), csr.get_info());
+        assert_eq!(res.as_dense().as_data(), csr.as_dense().as_data());
+    }
+}
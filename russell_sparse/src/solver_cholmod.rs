@@ -0,0 +1,1031 @@
+use super::{CscMatrix, LinSolParams, LinSolTrait, Ordering, SparseMatrix, StatsLinSol, Sym};
+use crate::constants::*;
+use crate::StrError;
+use russell_lab::{Stopwatch, Vector};
+
+/// Opaque struct holding a C-pointer to InterfaceCHOLMOD
+///
+/// Reference: <https://doc.rust-lang.org/nomicon/ffi.html#representing-opaque-structs>
+#[repr(C)]
+struct InterfaceCHOLMOD {
+    _data: [u8; 0],
+    _marker: core::marker::PhantomData<(*mut u8, core::marker::PhantomPinned)>,
+}
+
+/// Enforce Send on the C structure
+///
+/// <https://stackoverflow.com/questions/50258359/can-a-struct-containing-a-raw-pointer-implement-send-and-be-ffi-safe>
+unsafe impl Send for InterfaceCHOLMOD {}
+
+/// Enforce Send on the Rust structure
+///
+/// <https://stackoverflow.com/questions/50258359/can-a-struct-containing-a-raw-pointer-implement-send-and-be-ffi-safe>
+unsafe impl Send for SolverCHOLMOD {}
+
+extern "C" {
+    fn solver_cholmod_new() -> *mut InterfaceCHOLMOD;
+    fn solver_cholmod_drop(solver: *mut InterfaceCHOLMOD);
+    fn solver_cholmod_initialize(
+        solver: *mut InterfaceCHOLMOD,
+        ordering: i32,
+        supernodal: i32,
+        stype: i32,
+        ndim: i32,
+        col_pointers: *const i32,
+        row_indices: *const i32,
+    ) -> i32;
+    fn solver_cholmod_factorize(
+        solver: *mut InterfaceCHOLMOD,
+        effective_ordering: *mut i32,
+        effective_supernodal: *mut CcBool,
+        ndim: i32,
+        col_pointers: *const i32,
+        row_indices: *const i32,
+        values: *const f64,
+    ) -> i32;
+    fn solver_cholmod_solve(solver: *mut InterfaceCHOLMOD, system: i32, ndim: i32, x: *mut f64, rhs: *const f64)
+        -> i32;
+    fn solver_cholmod_log_det(solver: *mut InterfaceCHOLMOD, log_det: *mut f64) -> i32;
+    fn solver_cholmod_update_downdate(
+        solver: *mut InterfaceCHOLMOD,
+        update: CcBool,
+        ndim: i32,
+        ncol: i32,
+        col_pointers: *const i32,
+        row_indices: *const i32,
+        values: *const f64,
+    ) -> i32;
+}
+
+/// Specifies the (partial) system to be solved with the CHOLMOD factorization
+///
+/// The factorization is `P·A·Pᵀ = L·Lᴴ`, where P is the fill-reducing permutation.
+/// Thus, the solution of `A·x = b` is equivalent to the sequence:
+///
+/// ```text
+/// y := P·b,  solve L·z = y,  solve Lᴴ·w = z,  x := Pᵀ·w
+/// ```
+///
+/// (for real matrices, `Lᴴ = Lᵀ`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CholmodSystem {
+    /// Solves `A·x = b`
+    A,
+
+    /// Solves `L·x = b`
+    L,
+
+    /// Solves `Lᴴ·x = b`
+    Lt,
+
+    /// Computes `x = P·b`
+    P,
+
+    /// Computes `x = Pᵀ·b`
+    Pt,
+}
+
+/// Wraps the CHOLMOD solver for sparse symmetric (Hermitian) positive-definite linear systems
+///
+/// CHOLMOD computes the sparse Cholesky factorization `P·A·Pᵀ = L·Lᵀ` by the supernodal or
+/// the simplicial (left-looking) method. The factor may also be modified by rank-k updates
+/// or downdates (see [SolverCHOLMOD::update()] and [SolverCHOLMOD::downdate()]).
+///
+/// **Warning:** This solver may "run out of memory" for very large matrices.
+///
+/// Reference: Chen Y, Davis TA, Hager WW, Rajamanickam S (2008) Algorithm 887: CHOLMOD, supernodal
+/// sparse Cholesky factorization and update/downdate, ACM Transactions on Mathematical Software, 35(3)
+pub struct SolverCHOLMOD {
+    /// Holds a pointer to the C interface to CHOLMOD
+    solver: *mut InterfaceCHOLMOD,
+
+    /// Indicates whether the solver has been initialized or not (just once)
+    initialized: bool,
+
+    /// Indicates whether the sparse matrix has been factorized or not
+    factorized: bool,
+
+    /// Holds the symmetric flag saved in initialize
+    initialized_sym: Sym,
+
+    /// Holds the matrix dimension saved in initialize
+    initialized_ndim: usize,
+
+    /// Holds the number of non-zeros saved in initialize
+    initialized_nnz: usize,
+
+    /// Holds the fingerprint of the sparsity pattern saved in initialize
+    initialized_fingerprint: u64,
+
+    /// Holds the parameters given to analyze (or factorize)
+    params: LinSolParams,
+
+    /// Holds the used ordering (after factorize)
+    effective_ordering: i32,
+
+    /// Indicates whether the supernodal method has been used or not (after factorize)
+    effective_supernodal: CcBool,
+
+    /// Holds the determinant coefficient (if requested)
+    ///
+    /// det = coefficient * pow(10, exponent)
+    determinant_coefficient: f64,
+
+    /// Holds the determinant exponent (if requested)
+    ///
+    /// det = coefficient * pow(10, exponent)
+    determinant_exponent: f64,
+
+    /// Stopwatch to measure computation times
+    stopwatch: Stopwatch,
+
+    /// Time spent on initialize in nanoseconds
+    time_initialize_ns: u128,
+
+    /// Time spent on factorize in nanoseconds
+    time_factorize_ns: u128,
+
+    /// Time spent on solve in nanoseconds
+    time_solve_ns: u128,
+
+    /// Time spent on refactorize in nanoseconds
+    time_refactorize_ns: u128,
+}
+
+impl Drop for SolverCHOLMOD {
+    /// Tells the c-code to release memory
+    fn drop(&mut self) {
+        unsafe {
+            solver_cholmod_drop(self.solver);
+        }
+    }
+}
+
+impl SolverCHOLMOD {
+    /// Allocates a new instance
+    pub fn new() -> Result<Self, StrError> {
+        unsafe {
+            let solver = solver_cholmod_new();
+            if solver.is_null() {
+                return Err("c-code failed to allocate the CHOLMOD solver");
+            }
+            Ok(SolverCHOLMOD {
+                solver,
+                initialized: false,
+                factorized: false,
+                initialized_sym: Sym::No,
+                initialized_ndim: 0,
+                initialized_nnz: 0,
+                initialized_fingerprint: 0,
+                params: LinSolParams::new(),
+                effective_ordering: -1,
+                effective_supernodal: 0,
+                determinant_coefficient: 0.0,
+                determinant_exponent: 0.0,
+                stopwatch: Stopwatch::new(),
+                time_initialize_ns: 0,
+                time_factorize_ns: 0,
+                time_solve_ns: 0,
+                time_refactorize_ns: 0,
+            })
+        }
+    }
+
+    /// Checks whether the matrix has the same structure as the one given to analyze (or the first factorize)
+    fn check_pattern(&self, csc: &CscMatrix) -> Result<(), StrError> {
+        if csc.symmetric != self.initialized_sym {
+            return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+        }
+        if csc.nrow != self.initialized_ndim {
+            return Err("subsequent factorizations must use the same matrix (ndim differs)");
+        }
+        if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
+            return Err("subsequent factorizations must use the same matrix (nnz differs)");
+        }
+        if csc.get_pattern_fingerprint() != self.initialized_fingerprint {
+            return Err("subsequent factorizations must use the same matrix (pattern differs)");
+        }
+        Ok(())
+    }
+
+    /// Checks the matrix and performs the numeric factorization reusing the symbolic factor
+    ///
+    /// Returns the elapsed time in nanoseconds.
+    fn numeric(&mut self, mat: &mut SparseMatrix) -> Result<u128, StrError> {
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        self.check_pattern(csc)?;
+
+        // call factorize
+        self.factorized = false;
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_cholmod_factorize(
+                self.solver,
+                &mut self.effective_ordering,
+                &mut self.effective_supernodal,
+                to_i32(csc.nrow),
+                csc.col_pointers.as_ptr(),
+                csc.row_indices.as_ptr(),
+                csc.values.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_cholmod_error_code(status));
+            }
+        }
+        let elapsed = self.stopwatch.stop();
+        self.factorized = true;
+
+        // determinant
+        if self.params.compute_determinant {
+            let exponent = self.log_determinant()? / f64::ln(10.0);
+            self.determinant_exponent = f64::floor(exponent);
+            self.determinant_coefficient = f64::powf(10.0, exponent - self.determinant_exponent);
+        }
+        Ok(elapsed)
+    }
+
+    /// Solves a (partial) system with the factorization
+    ///
+    /// See [CholmodSystem] for the available systems. For instance, [CholmodSystem::L] and
+    /// [CholmodSystem::Lt] may be used to apply the "square root" of A (or of its inverse).
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the solution vector with dimension equal to the dimension of A
+    ///
+    /// # Input
+    ///
+    /// * `rhs` -- the right-hand side vector with dimension equal to the dimension of A
+    /// * `system` -- the system to be solved
+    pub fn solve_system(&mut self, x: &mut Vector, rhs: &Vector, system: CholmodSystem) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+        if x.dim() != self.initialized_ndim {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.dim() != self.initialized_ndim {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+        let ndim = to_i32(self.initialized_ndim);
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_cholmod_solve(
+                self.solver,
+                cholmod_system(system),
+                ndim,
+                x.as_mut_data().as_mut_ptr(),
+                rhs.as_data().as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_cholmod_error_code(status));
+            }
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+        Ok(())
+    }
+
+    /// Returns the natural logarithm of the determinant of A
+    ///
+    /// **Note:** Since A is positive-definite, `ln(det(A)) = 2 Σ ln(Lᵢᵢ)` is always defined,
+    /// even if the determinant itself would overflow or underflow.
+    pub fn log_determinant(&self) -> Result<f64, StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before log_determinant");
+        }
+        let mut log_det = 0.0;
+        unsafe {
+            let status = solver_cholmod_log_det(self.solver, &mut log_det);
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_cholmod_error_code(status));
+            }
+        }
+        Ok(log_det)
+    }
+
+    /// Updates the factorization such that it corresponds to `A + C·Cᵀ`
+    ///
+    /// # Input
+    ///
+    /// * `c` -- the (ndim × k) matrix C with [Sym::No]
+    ///
+    /// # Notes
+    ///
+    /// 1. The factor is modified in place (this is much faster than a new factorization for small k).
+    /// 2. The factor is converted to the simplicial form.
+    /// 3. A subsequent call to `factorize` computes the factorization of the given A again.
+    pub fn update(&mut self, c: &CscMatrix) -> Result<(), StrError> {
+        self.update_downdate(c, true)
+    }
+
+    /// Downdates the factorization such that it corresponds to `A - C·Cᵀ`
+    ///
+    /// # Input
+    ///
+    /// * `c` -- the (ndim × k) matrix C with [Sym::No]
+    ///
+    /// # Notes
+    ///
+    /// 1. The factor is modified in place (this is much faster than a new factorization for small k).
+    /// 2. The factor is converted to the simplicial form.
+    /// 3. A subsequent call to `factorize` computes the factorization of the given A again.
+    /// 4. The resulting matrix must remain positive-definite.
+    pub fn downdate(&mut self, c: &CscMatrix) -> Result<(), StrError> {
+        self.update_downdate(c, false)
+    }
+
+    /// Implements the update and downdate functions
+    fn update_downdate(&mut self, c: &CscMatrix, update: bool) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before update or downdate");
+        }
+        if c.nrow != self.initialized_ndim {
+            return Err("the number of rows of C must equal the dimension of A");
+        }
+        if c.symmetric != Sym::No {
+            return Err("the matrix C must have Sym::No");
+        }
+        unsafe {
+            let status = solver_cholmod_update_downdate(
+                self.solver,
+                if update { 1 } else { 0 },
+                to_i32(c.nrow),
+                to_i32(c.ncol),
+                c.col_pointers.as_ptr(),
+                c.row_indices.as_ptr(),
+                c.values.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                self.factorized = false;
+                return Err(handle_cholmod_error_code(status));
+            }
+        }
+        self.effective_supernodal = 0;
+        Ok(())
+    }
+}
+
+impl LinSolTrait for SolverCHOLMOD {
+    /// Performs the factorization (and analysis/initialization if needed)
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`), symmetric, and positive-definite
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// # Notes
+    ///
+    /// 1. The structure of the matrix (nrow, ncol, nnz, sym, and the sparsity pattern) must be
+    ///    exactly the same among multiple calls to `factorize`. The values may differ
+    ///    from call to call, nonetheless.
+    /// 2. The first call to `factorize` will define the structure which must be
+    ///    kept the same for the next calls.
+    /// 3. If the structure of the matrix needs to be changed, the solver must
+    ///    be "dropped" and a new solver allocated.
+    /// 4. For symmetric matrices, `CHOLMOD` accepts any representation; with [Sym::YesFull],
+    ///    only the upper triangle is accessed
+    /// 5. The method (supernodal or simplicial) is selected by [LinSolParams::cholmod_supernodal]
+    fn factorize(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if !self.initialized {
+            self.analyze(mat, Some(par))?;
+        }
+        self.params = par;
+        self.factorize_numeric(mat)
+    }
+
+    /// Performs the symbolic analysis (fill-reducing ordering and elimination tree) only
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square and symmetric (any [Sym] representation except [Sym::No])
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The analysis is performed just once; i.e., the solver must be
+    /// "dropped" and a new solver allocated if the structure of the matrix changes.
+    fn analyze(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        // check
+        if self.initialized {
+            return Err("the analysis has been performed already");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        if csc.nrow != csc.ncol {
+            return Err("the matrix must be square");
+        }
+        if csc.symmetric == Sym::No {
+            return Err("CHOLMOD requires a symmetric matrix");
+        }
+        self.initialized_sym = csc.symmetric;
+        self.initialized_ndim = csc.nrow;
+        self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
+        self.initialized_fingerprint = csc.get_pattern_fingerprint();
+
+        // parameters
+        self.params = if let Some(p) = params { p } else { LinSolParams::new() };
+
+        // input parameters
+        let ordering = cholmod_ordering(self.params.ordering);
+        let supernodal = cholmod_supernodal(self.params.cholmod_supernodal);
+
+        // call initialize
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_cholmod_initialize(
+                self.solver,
+                ordering,
+                supernodal,
+                cholmod_stype(csc.symmetric),
+                to_i32(csc.nrow),
+                csc.col_pointers.as_ptr(),
+                csc.row_indices.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_cholmod_error_code(status));
+            }
+        }
+        self.time_initialize_ns = self.stopwatch.stop();
+
+        // done
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric factorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one given to `analyze`
+    fn factorize_numeric(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        if !self.initialized {
+            return Err("the function analyze must be called before factorize_numeric");
+        }
+        self.time_factorize_ns = self.numeric(mat)?;
+        Ok(())
+    }
+
+    /// Performs the numeric refactorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one previously factorized
+    ///
+    /// **Note:** Since the Cholesky factorization does not pivot, this function is equivalent
+    /// to `factorize_numeric`; however, the time is recorded as the refactorization time.
+    fn refactorize(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before refactorize");
+        }
+        self.time_refactorize_ns = self.numeric(mat)?;
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and symmetric
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve(&mut self, x: &mut Vector, mat: &SparseMatrix, rhs: &Vector, _verbose: bool) -> Result<(), StrError> {
+        // check
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+
+        // access CSC matrix
+        // (possibly already converted from COO, because factorize was (should have been) called)
+        let csc = mat.get_csc()?;
+
+        // check already factorized data
+        let (nrow, ncol, nnz, sym) = csc.get_info();
+        if sym != self.initialized_sym {
+            return Err("solve must use the same matrix (symmetric differs)");
+        }
+        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
+            return Err("solve must use the same matrix (ndim differs)");
+        }
+        if nnz != self.initialized_nnz {
+            return Err("solve must use the same matrix (nnz differs)");
+        }
+
+        // call CHOLMOD solve
+        self.solve_system(x, rhs, CholmodSystem::A)
+    }
+
+    /// Computes the solution of the transposed linear system
+    ///
+    /// Since A is symmetric, `Aᵀ · x = rhs` is the same as `A · x = rhs`.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and symmetric
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_transpose(
+        &mut self,
+        x: &mut Vector,
+        mat: &SparseMatrix,
+        rhs: &Vector,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve(x, mat, rhs, verbose)
+    }
+
+    /// Updates the stats structure (should be called after solve)
+    fn update_stats(&self, stats: &mut StatsLinSol) {
+        stats.main.solver = if cfg!(feature = "local_suitesparse") {
+            "CHOLMOD-local".to_string()
+        } else {
+            "CHOLMOD".to_string()
+        };
+        stats.output.effective_ordering = cholmod_ordering_name(self.effective_ordering);
+        stats.output.effective_scaling = "No".to_string();
+        stats.output.cholmod_method = if self.effective_supernodal != 0 {
+            "Supernodal".to_string()
+        } else {
+            "Simplicial".to_string()
+        };
+        stats
+            .determinant
+            .set(self.determinant_coefficient, 0.0, 10.0, self.determinant_exponent);
+        stats.time_nanoseconds.initialize = self.time_initialize_ns;
+        stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.refactorize = self.time_refactorize_ns;
+        stats.time_nanoseconds.solve = self.time_solve_ns;
+    }
+
+    /// Returns the nanoseconds spent on initialize
+    fn get_ns_init(&self) -> u128 {
+        self.time_initialize_ns
+    }
+
+    /// Returns the nanoseconds spent on factorize
+    fn get_ns_fact(&self) -> u128 {
+        self.time_factorize_ns
+    }
+
+    /// Returns the nanoseconds spent on solve
+    fn get_ns_solve(&self) -> u128 {
+        self.time_solve_ns
+    }
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        self.time_refactorize_ns
+    }
+}
+
+pub(crate) const CHOLMOD_ORDERING_AUTO: i32 = -1; // (code defined here) use defaults
+pub(crate) const CHOLMOD_ORDERING_BEST: i32 = -2; // (code defined here) try all methods
+pub(crate) const CHOLMOD_ORDERING_NATURAL: i32 = 0; // (from CHOLMOD manual) no permutation
+pub(crate) const CHOLMOD_ORDERING_AMD: i32 = 2; // (from CHOLMOD manual) AMD
+pub(crate) const CHOLMOD_ORDERING_METIS: i32 = 3; // (from CHOLMOD manual) METIS
+pub(crate) const CHOLMOD_ORDERING_NESDIS: i32 = 4; // (from CHOLMOD manual) nested dissection
+pub(crate) const CHOLMOD_ORDERING_COLAMD: i32 = 5; // (from CHOLMOD manual) COLAMD
+
+pub(crate) const CHOLMOD_METHOD_AUTO: i32 = -1; // (code defined here) use defaults
+pub(crate) const CHOLMOD_METHOD_SIMPLICIAL: i32 = 0; // (from CHOLMOD manual) simplicial
+pub(crate) const CHOLMOD_METHOD_SUPERNODAL: i32 = 2; // (from CHOLMOD manual) supernodal
+
+pub(crate) const CHOLMOD_SYS_A: i32 = 0; // (from CHOLMOD manual) solve Ax=b
+pub(crate) const CHOLMOD_SYS_L: i32 = 4; // (from CHOLMOD manual) solve Lx=b
+pub(crate) const CHOLMOD_SYS_LT: i32 = 5; // (from CHOLMOD manual) solve L'x=b
+pub(crate) const CHOLMOD_SYS_P: i32 = 7; // (from CHOLMOD manual) permute x=Px
+pub(crate) const CHOLMOD_SYS_PT: i32 = 8; // (from CHOLMOD manual) permute x=P'x
+
+/// Returns the CHOLMOD ordering constant
+pub(crate) fn cholmod_ordering(ordering: Ordering) -> i32 {
+    match ordering {
+        Ordering::Amd => CHOLMOD_ORDERING_AMD,
+        Ordering::Amf => CHOLMOD_ORDERING_AUTO,
+        Ordering::Auto => CHOLMOD_ORDERING_AUTO,
+        Ordering::Best => CHOLMOD_ORDERING_BEST,
+        Ordering::Cholmod => CHOLMOD_ORDERING_AUTO,
+        Ordering::Colamd => CHOLMOD_ORDERING_COLAMD,
+        Ordering::Metis => CHOLMOD_ORDERING_METIS,
+        Ordering::No => CHOLMOD_ORDERING_NATURAL,
+        Ordering::Pord => CHOLMOD_ORDERING_AUTO,
+        Ordering::Qamd => CHOLMOD_ORDERING_AUTO,
+        Ordering::Scotch => CHOLMOD_ORDERING_AUTO,
+    }
+}
+
+/// Returns the name of the CHOLMOD ordering
+pub(crate) fn cholmod_ordering_name(ordering: i32) -> String {
+    match ordering {
+        CHOLMOD_ORDERING_NATURAL => "No".to_string(),
+        CHOLMOD_ORDERING_AMD => "Amd".to_string(),
+        CHOLMOD_ORDERING_METIS => "Metis".to_string(),
+        CHOLMOD_ORDERING_NESDIS => "Nesdis".to_string(),
+        CHOLMOD_ORDERING_COLAMD => "Colamd".to_string(),
+        _ => "Unknown".to_string(),
+    }
+}
+
+/// Returns the CHOLMOD method (supernodal) constant
+pub(crate) fn cholmod_supernodal(supernodal: Option<bool>) -> i32 {
+    match supernodal {
+        Some(true) => CHOLMOD_METHOD_SUPERNODAL,
+        Some(false) => CHOLMOD_METHOD_SIMPLICIAL,
+        None => CHOLMOD_METHOD_AUTO,
+    }
+}
+
+/// Returns the CHOLMOD symmetric type (stype)
+///
+/// **Note:** With [Sym::YesFull], only the upper triangle is accessed.
+pub(crate) fn cholmod_stype(sym: Sym) -> i32 {
+    match sym {
+        Sym::YesLower => -1,
+        Sym::YesUpper => 1,
+        Sym::YesFull => 1,
+        Sym::No => 0,
+    }
+}
+
+/// Returns the CHOLMOD system constant
+pub(crate) fn cholmod_system(system: CholmodSystem) -> i32 {
+    match system {
+        CholmodSystem::A => CHOLMOD_SYS_A,
+        CholmodSystem::L => CHOLMOD_SYS_L,
+        CholmodSystem::Lt => CHOLMOD_SYS_LT,
+        CholmodSystem::P => CHOLMOD_SYS_P,
+        CholmodSystem::Pt => CHOLMOD_SYS_PT,
+    }
+}
+
+/// Handles CHOLMOD error code
+pub(crate) fn handle_cholmod_error_code(err: i32) -> StrError {
+    match err {
+        -19 => "cholmod_analyze failed",
+        -18 => "cholmod_factorize failed",
+        -17 => "the matrix is not positive-definite (CHOLMOD)",
+        -16 => "cholmod_solve failed",
+        -15 => "cholmod_updown failed",
+        ERROR_NULL_POINTER => "CHOLMOD failed due to NULL POINTER error",
+        ERROR_MALLOC => "CHOLMOD failed due to MALLOC error",
+        ERROR_VERSION => "CHOLMOD failed due to VERSION error",
+        ERROR_NOT_AVAILABLE => "CHOLMOD is not AVAILABLE",
+        ERROR_NEED_INITIALIZATION => "CHOLMOD failed because INITIALIZATION is needed",
+        ERROR_NEED_FACTORIZATION => "CHOLMOD failed because FACTORIZATION is needed",
+        ERROR_ALREADY_INITIALIZED => "CHOLMOD failed because INITIALIZATION has been completed already",
+        _ => "Error: unknown error returned by c-code (CHOLMOD)",
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CooMatrix, Samples};
+    use russell_lab::{approx_eq, vec_approx_eq};
+
+    #[test]
+    fn new_and_drop_work() {
+        // you may debug into the C-code to see that drop is working
+        let solver = SolverCHOLMOD::new().unwrap();
+        assert!(!solver.factorized);
+    }
+
+    #[test]
+    fn factorize_handles_errors() {
+        let mut solver = SolverCHOLMOD::new().unwrap();
+        assert!(!solver.factorized);
+
+        // COO to CSC errors
+        let coo = CooMatrix::new(1, 1, 1, Sym::No).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("COO to CSC requires nnz > 0")
+        );
+
+        // check CSC matrix
+        let (coo, _, _, _) = Samples::rectangular_1x7();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("the matrix must be square")
+        );
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("CHOLMOD requires a symmetric matrix")
+        );
+
+        // check already factorized data
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::YesLower).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 1, 2.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        // ... factorize once => OK
+        solver.factorize(&mut mat, None).unwrap();
+        // ... change matrix (symmetric)
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::YesFull).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 1, 2.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (symmetric differs)")
+        );
+        // ... change matrix (ndim)
+        let mut coo = CooMatrix::new(1, 1, 1, Sym::YesLower).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (ndim differs)")
+        );
+        // ... change matrix (nnz)
+        let mut coo = CooMatrix::new(2, 2, 1, Sym::YesLower).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (nnz differs)")
+        );
+    }
+
+    #[test]
+    fn factorize_fails_on_indefinite_matrix() {
+        let mut solver = SolverCHOLMOD::new().unwrap();
+        let mut coo = CooMatrix::new(2, 2, 3, Sym::YesLower).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 0, 2.0).unwrap();
+        coo.put(1, 1, 1.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None),
+            Err("the matrix is not positive-definite (CHOLMOD)")
+        );
+        let mut x = Vector::new(2);
+        let rhs = Vector::new(2);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false),
+            Err("the function factorize must be called before solve")
+        );
+    }
+
+    #[test]
+    fn solve_handles_errors() {
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::YesLower).unwrap();
+        coo.put(0, 0, 123.0).unwrap();
+        coo.put(1, 1, 456.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut solver = SolverCHOLMOD::new().unwrap();
+        assert!(!solver.factorized);
+        let mut x = Vector::new(2);
+        let rhs = Vector::new(2);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false),
+            Err("the function factorize must be called before solve")
+        );
+        assert_eq!(
+            solver.log_determinant().err(),
+            Some("the function factorize must be called before log_determinant")
+        );
+        let (_, c, _, _) = Samples::rectangular_7x1();
+        assert_eq!(
+            solver.update(&c).err(),
+            Some("the function factorize must be called before update or downdate")
+        );
+        let mut x = Vector::new(1);
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false),
+            Err("the dimension of the vector of unknown values x is incorrect")
+        );
+        let mut x = Vector::new(2);
+        let rhs = Vector::new(1);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false),
+            Err("the dimension of the right-hand side vector is incorrect")
+        );
+        assert_eq!(
+            solver.update(&c).err(),
+            Some("the number of rows of C must equal the dimension of A")
+        );
+        // wrong symmetric
+        let rhs = Vector::new(2);
+        let mut coo_wrong = CooMatrix::new(2, 2, 2, Sym::YesFull).unwrap();
+        coo_wrong.put(0, 0, 123.0).unwrap();
+        coo_wrong.put(1, 1, 456.0).unwrap();
+        let mut mat_wrong = SparseMatrix::from_coo(coo_wrong);
+        mat_wrong.get_csc_or_from_coo().unwrap(); // make sure to convert to CSC (because we're not calling factorize on this wrong matrix)
+        assert_eq!(
+            solver.solve(&mut x, &mat_wrong, &rhs, false),
+            Err("solve must use the same matrix (symmetric differs)")
+        );
+        let c_wrong = mat_wrong.get_csc().unwrap();
+        assert_eq!(solver.update(c_wrong).err(), Some("the matrix C must have Sym::No"));
+    }
+
+    #[test]
+    fn solve_works() {
+        let x_correct = vec![-979.0 / 3.0, 983.0, 1961.0 / 12.0, 398.0, 123.0 / 2.0];
+        let rhs = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        for (coo, _, _, det) in [
+            Samples::mkl_positive_definite_5x5_lower(),
+            Samples::mkl_positive_definite_5x5_upper(),
+        ] {
+            for (ordering, supernodal) in [
+                (Ordering::Auto, None),
+                (Ordering::Amd, Some(true)),
+                (Ordering::No, Some(false)),
+            ] {
+                let mut solver = SolverCHOLMOD::new().unwrap();
+                let mut mat = SparseMatrix::from_coo(coo.clone());
+                let mut params = LinSolParams::new();
+                params.ordering = ordering;
+                params.cholmod_supernodal = supernodal;
+                params.compute_determinant = true;
+                solver.factorize(&mut mat, Some(params)).unwrap();
+                let mut x = Vector::new(5);
+                solver.solve(&mut x, &mat, &rhs, false).unwrap();
+                vec_approx_eq(&x, &x_correct, 1e-10);
+
+                // determinant
+                approx_eq(solver.log_determinant().unwrap(), f64::ln(det), 1e-13);
+                let mut stats = StatsLinSol::new();
+                solver.update_stats(&mut stats);
+                let d = stats.determinant.mantissa_real * f64::powf(10.0, stats.determinant.exponent);
+                approx_eq(d, det, 1e-12 * det);
+                assert_eq!(stats.output.effective_scaling, "No");
+                if let Some(s) = supernodal {
+                    let method = if s { "Supernodal" } else { "Simplicial" };
+                    assert_eq!(stats.output.cholmod_method, method);
+                }
+                if ordering == Ordering::No {
+                    assert_eq!(stats.output.effective_ordering, "No");
+                }
+                if ordering == Ordering::Amd {
+                    assert_eq!(stats.output.effective_ordering, "Amd");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solve_system_works() {
+        let (coo, _, _, _) = Samples::mkl_positive_definite_5x5_lower();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut solver = SolverCHOLMOD::new().unwrap();
+        solver.factorize(&mut mat, None).unwrap();
+        let rhs = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut x = Vector::new(5);
+        solver.solve_system(&mut x, &rhs, CholmodSystem::A).unwrap();
+
+        // x = Pᵀ·L⁻ᵀ·L⁻¹·P·b
+        let mut y = Vector::new(5);
+        let mut z = Vector::new(5);
+        solver.solve_system(&mut y, &rhs, CholmodSystem::P).unwrap();
+        solver.solve_system(&mut z, &y, CholmodSystem::L).unwrap();
+        solver.solve_system(&mut y, &z, CholmodSystem::Lt).unwrap();
+        solver.solve_system(&mut z, &y, CholmodSystem::Pt).unwrap();
+        vec_approx_eq(&z, &x, 1e-10);
+    }
+
+    #[test]
+    fn update_and_downdate_work() {
+        let (coo, _, _, _) = Samples::mkl_positive_definite_5x5_lower();
+        let a = coo.as_dense();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut solver = SolverCHOLMOD::new().unwrap();
+        let mut params = LinSolParams::new();
+        params.cholmod_supernodal = Some(true);
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        let log_det = solver.log_determinant().unwrap();
+
+        // C = [1, 0, 0, 0, 2]ᵀ
+        let mut coo_c = CooMatrix::new(5, 1, 2, Sym::No).unwrap();
+        coo_c.put(0, 0, 1.0).unwrap();
+        coo_c.put(4, 0, 2.0).unwrap();
+        let c = CscMatrix::from_coo(&coo_c).unwrap();
+
+        // update: (A + C·Cᵀ)·x = rhs
+        solver.update(&c).unwrap();
+        let x_correct = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut rhs = Vector::new(5);
+        for i in 0..5 {
+            for j in 0..5 {
+                rhs[i] += a.get(i, j) * x_correct[j];
+            }
+        }
+        let c_dot_x = x_correct[0] + 2.0 * x_correct[4];
+        rhs[0] += c_dot_x;
+        rhs[4] += 2.0 * c_dot_x;
+        let mut x = Vector::new(5);
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-10);
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.output.cholmod_method, "Simplicial");
+
+        // downdate: back to A
+        solver.downdate(&c).unwrap();
+        approx_eq(solver.log_determinant().unwrap(), log_det, 1e-12);
+    }
+
+    #[test]
+    fn handle_cholmod_error_code_works() {
+        let default = "Error: unknown error returned by c-code (CHOLMOD)";
+        for i in 1..15 {
+            let res = handle_cholmod_error_code(-i);
+            assert_eq!(res, default);
+        }
+        assert_eq!(handle_cholmod_error_code(-19), "cholmod_analyze failed");
+        assert_eq!(handle_cholmod_error_code(-18), "cholmod_factorize failed");
+        assert_eq!(
+            handle_cholmod_error_code(-17),
+            "the matrix is not positive-definite (CHOLMOD)"
+        );
+        assert_eq!(handle_cholmod_error_code(-16), "cholmod_solve failed");
+        assert_eq!(handle_cholmod_error_code(-15), "cholmod_updown failed");
+        assert_eq!(
+            handle_cholmod_error_code(ERROR_NULL_POINTER),
+            "CHOLMOD failed due to NULL POINTER error"
+        );
+        assert_eq!(
+            handle_cholmod_error_code(ERROR_MALLOC),
+            "CHOLMOD failed due to MALLOC error"
+        );
+        assert_eq!(
+            handle_cholmod_error_code(ERROR_VERSION),
+            "CHOLMOD failed due to VERSION error"
+        );
+        assert_eq!(
+            handle_cholmod_error_code(ERROR_NOT_AVAILABLE),
+            "CHOLMOD is not AVAILABLE"
+        );
+        assert_eq!(
+            handle_cholmod_error_code(ERROR_NEED_INITIALIZATION),
+            "CHOLMOD failed because INITIALIZATION is needed"
+        );
+        assert_eq!(
+            handle_cholmod_error_code(ERROR_NEED_FACTORIZATION),
+            "CHOLMOD failed because FACTORIZATION is needed"
+        );
+        assert_eq!(
+            handle_cholmod_error_code(ERROR_ALREADY_INITIALIZED),
+            "CHOLMOD failed because INITIALIZATION has been completed already"
+        );
+    }
+
+    #[test]
+    fn cholmod_constants_work() {
+        assert_eq!(cholmod_ordering(Ordering::Amd), CHOLMOD_ORDERING_AMD);
+        assert_eq!(cholmod_ordering(Ordering::Amf), CHOLMOD_ORDERING_AUTO);
+        assert_eq!(cholmod_ordering(Ordering::Auto), CHOLMOD_ORDERING_AUTO);
+        assert_eq!(cholmod_ordering(Ordering::Best), CHOLMOD_ORDERING_BEST);
+        assert_eq!(cholmod_ordering(Ordering::Cholmod), CHOLMOD_ORDERING_AUTO);
+        assert_eq!(cholmod_ordering(Ordering::Colamd), CHOLMOD_ORDERING_COLAMD);
+        assert_eq!(cholmod_ordering(Ordering::Metis), CHOLMOD_ORDERING_METIS);
+        assert_eq!(cholmod_ordering(Ordering::No), CHOLMOD_ORDERING_NATURAL);
+        assert_eq!(cholmod_ordering(Ordering::Pord), CHOLMOD_ORDERING_AUTO);
+        assert_eq!(cholmod_ordering(Ordering::Qamd), CHOLMOD_ORDERING_AUTO);
+        assert_eq!(cholmod_ordering(Ordering::Scotch), CHOLMOD_ORDERING_AUTO);
+
+        assert_eq!(cholmod_ordering_name(CHOLMOD_ORDERING_NESDIS), "Nesdis");
+        assert_eq!(cholmod_ordering_name(123), "Unknown");
+
+        assert_eq!(cholmod_supernodal(None), CHOLMOD_METHOD_AUTO);
+        assert_eq!(cholmod_supernodal(Some(true)), CHOLMOD_METHOD_SUPERNODAL);
+        assert_eq!(cholmod_supernodal(Some(false)), CHOLMOD_METHOD_SIMPLICIAL);
+
+        assert_eq!(cholmod_stype(Sym::YesLower), -1);
+        assert_eq!(cholmod_stype(Sym::YesUpper), 1);
+        assert_eq!(cholmod_stype(Sym::YesFull), 1);
+        assert_eq!(cholmod_stype(Sym::No), 0);
+    }
+}
@@ -1,7 +1,7 @@
-use super::{LinSolParams, LinSolTrait, Ordering, Scaling, SparseMatrix, StatsLinSol, Sym};
+use super::{CscMatrix, LinSolParams, LinSolTrait, Ordering, Scaling, SparseMatrix, StatsLinSol, Sym};
 use crate::constants::*;
 use crate::StrError;
-use russell_lab::{vec_copy, Stopwatch, Vector};
+use russell_lab::{Matrix, Stopwatch, Vector};
 
 /// Opaque struct holding a C-pointer to InterfaceKLU
 ///
@@ -43,7 +43,21 @@ extern "C" {
         row_indices: *const i32,
         values: *const f64,
     ) -> i32;
-    fn solver_klu_solve(solver: *mut InterfaceKLU, ndim: i32, in_rhs_out_x: *mut f64) -> i32;
+    fn solver_klu_refactorize(
+        solver: *mut InterfaceKLU,
+        cond_estimate: *mut f64,
+        compute_cond: CcBool,
+        col_pointers: *const i32,
+        row_indices: *const i32,
+        values: *const f64,
+    ) -> i32;
+    fn solver_klu_solve(
+        solver: *mut InterfaceKLU,
+        ndim: i32,
+        nrhs: i32,
+        transpose: CcBool,
+        in_rhs_out_x: *mut f64,
+    ) -> i32;
 }
 
 /// Wraps the KLU solver for sparse linear systems
@@ -68,6 +82,12 @@ pub struct SolverKLU {
     /// Holds the number of non-zeros saved in initialize
     initialized_nnz: usize,
 
+    /// Holds the fingerprint of the sparsity pattern saved in initialize
+    initialized_fingerprint: u64,
+
+    /// Holds the parameters given to analyze (or factorize)
+    params: LinSolParams,
+
     /// Holds the used ordering (after factorize)
     effective_ordering: i32,
 
@@ -88,6 +108,9 @@ pub struct SolverKLU {
 
     /// Time spent on solve in nanoseconds
     time_solve_ns: u128,
+
+    /// Time spent on refactorize in nanoseconds
+    time_refactorize_ns: u128,
 }
 
 impl Drop for SolverKLU {
@@ -114,6 +137,8 @@ impl SolverKLU {
                 initialized_sym: Sym::No,
                 initialized_ndim: 0,
                 initialized_nnz: 0,
+                initialized_fingerprint: 0,
+                params: LinSolParams::new(),
                 effective_ordering: -1,
                 effective_scaling: -1,
                 cond_estimate: 0.0,
@@ -121,9 +146,87 @@ impl SolverKLU {
                 time_initialize_ns: 0,
                 time_factorize_ns: 0,
                 time_solve_ns: 0,
+                time_refactorize_ns: 0,
             })
         }
     }
+
+    /// Checks whether the matrix has the same structure as the one given to analyze (or the first factorize)
+    fn check_pattern(&self, csc: &CscMatrix) -> Result<(), StrError> {
+        if csc.symmetric != self.initialized_sym {
+            return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+        }
+        if csc.nrow != self.initialized_ndim {
+            return Err("subsequent factorizations must use the same matrix (ndim differs)");
+        }
+        if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
+            return Err("subsequent factorizations must use the same matrix (nnz differs)");
+        }
+        if csc.get_pattern_fingerprint() != self.initialized_fingerprint {
+            return Err("subsequent factorizations must use the same matrix (pattern differs)");
+        }
+        Ok(())
+    }
+
+    /// Checks the data and solves `A · X = RHS` or `Aᵀ · X = RHS` (col-major arrays)
+    fn solve_internal(
+        &mut self,
+        x: &mut [f64],
+        mat: &SparseMatrix,
+        rhs: &[f64],
+        nrhs: usize,
+        transpose: bool,
+    ) -> Result<(), StrError> {
+        // check
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+
+        // access CSC matrix
+        // (possibly already converted from COO, because factorize was (should have been) called)
+        let csc = mat.get_csc()?;
+
+        // check already factorized data
+        let (nrow, ncol, nnz, sym) = csc.get_info();
+        if sym != self.initialized_sym {
+            return Err("solve must use the same matrix (symmetric differs)");
+        }
+        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
+            return Err("solve must use the same matrix (ndim differs)");
+        }
+        if nnz != self.initialized_nnz {
+            return Err("solve must use the same matrix (nnz differs)");
+        }
+
+        // check vectors
+        if x.len() != self.initialized_ndim * nrhs {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.len() != self.initialized_ndim * nrhs {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+
+        // call KLU solve
+        let ndim = to_i32(self.initialized_ndim);
+        x.copy_from_slice(rhs);
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_klu_solve(
+                self.solver,
+                ndim,
+                to_i32(nrhs),
+                if transpose { 1 } else { 0 },
+                x.as_mut_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_klu_error_code(status));
+            }
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+
+        // done
+        Ok(())
+    }
 }
 
 impl LinSolTrait for SolverKLU {
@@ -138,7 +241,7 @@ impl LinSolTrait for SolverKLU {
     ///
     /// # Notes
     ///
-    /// 1. The structure of the matrix (nrow, ncol, nnz, sym) must be
+    /// 1. The structure of the matrix (nrow, ncol, nnz, sym, and the sparsity pattern) must be
     ///    exactly the same among multiple calls to `factorize`. The values may differ
     ///    from call to call, nonetheless.
     /// 2. The first call to `factorize` will define the structure which must be
@@ -147,65 +250,96 @@ impl LinSolTrait for SolverKLU {
     ///    be "dropped" and a new solver allocated.
     /// 4. For symmetric matrices, `KLU` requires [Sym::YesFull]
     fn factorize(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
-        // get CSC matrix
-        // (or convert from COO if CSC is not available and COO is available)
-        let csc = mat.get_csc_or_from_coo()?;
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if !self.initialized {
+            self.analyze(mat, Some(par))?;
+        }
+        self.params = par;
+        self.factorize_numeric(mat)
+    }
 
+    /// Performs the symbolic analysis (ordering) only
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`) and, if symmetric,
+    ///   the symmetric flag must be [Sym::YesFull]
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The analysis is performed just once; i.e., the solver must be
+    /// "dropped" and a new solver allocated if the structure of the matrix changes.
+    fn analyze(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
         // check
         if self.initialized {
-            if csc.symmetric != self.initialized_sym {
-                return Err("subsequent factorizations must use the same matrix (symmetric differs)");
-            }
-            if csc.nrow != self.initialized_ndim {
-                return Err("subsequent factorizations must use the same matrix (ndim differs)");
-            }
-            if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
-                return Err("subsequent factorizations must use the same matrix (nnz differs)");
-            }
-        } else {
-            if csc.nrow != csc.ncol {
-                return Err("the matrix must be square");
-            }
-            if csc.symmetric == Sym::YesLower || csc.symmetric == Sym::YesUpper {
-                return Err("KLU requires Sym::YesFull for symmetric matrices");
-            }
-            self.initialized_sym = csc.symmetric;
-            self.initialized_ndim = csc.nrow;
-            self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
+            return Err("the analysis has been performed already");
         }
 
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        if csc.nrow != csc.ncol {
+            return Err("the matrix must be square");
+        }
+        if csc.symmetric == Sym::YesLower || csc.symmetric == Sym::YesUpper {
+            return Err("KLU requires Sym::YesFull for symmetric matrices");
+        }
+        self.initialized_sym = csc.symmetric;
+        self.initialized_ndim = csc.nrow;
+        self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
+        self.initialized_fingerprint = csc.get_pattern_fingerprint();
+
         // parameters
-        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        self.params = if let Some(p) = params { p } else { LinSolParams::new() };
 
         // input parameters
-        let ordering = klu_ordering(par.ordering);
-        let scaling = klu_scaling(par.scaling);
-
-        // requests
-        let compute_cond = if par.compute_condition_numbers { 1 } else { 0 };
+        let ordering = klu_ordering(self.params.ordering);
+        let scaling = klu_scaling(self.params.scaling);
 
         // matrix config
         let ndim = to_i32(csc.nrow);
 
-        // call initialize just once
-        if !self.initialized {
-            self.stopwatch.reset();
-            unsafe {
-                let status = solver_klu_initialize(
-                    self.solver,
-                    ordering,
-                    scaling,
-                    ndim,
-                    csc.col_pointers.as_ptr(),
-                    csc.row_indices.as_ptr(),
-                );
-                if status != SUCCESSFUL_EXIT {
-                    return Err(handle_klu_error_code(status));
-                }
+        // call initialize
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_klu_initialize(
+                self.solver,
+                ordering,
+                scaling,
+                ndim,
+                csc.col_pointers.as_ptr(),
+                csc.row_indices.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_klu_error_code(status));
             }
-            self.time_initialize_ns = self.stopwatch.stop();
-            self.initialized = true;
         }
+        self.time_initialize_ns = self.stopwatch.stop();
+
+        // done
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric factorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one given to `analyze`
+    fn factorize_numeric(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        // check
+        if !self.initialized {
+            return Err("the function analyze must be called before factorize_numeric");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        self.check_pattern(csc)?;
+
+        // requests
+        let compute_cond = if self.params.compute_condition_numbers { 1 } else { 0 };
 
         // call factorize
         self.stopwatch.reset();
@@ -231,6 +365,48 @@ impl LinSolTrait for SolverKLU {
         Ok(())
     }
 
+    /// Performs the numeric refactorization reusing the pivot sequence (via `klu_refactor`)
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one previously factorized
+    ///
+    /// **Warning:** No pivoting is performed; thus, the factorization may be inaccurate if the
+    /// values have changed significantly. In this case, call `factorize_numeric` instead.
+    fn refactorize(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        // check
+        if !self.factorized {
+            return Err("the function factorize must be called before refactorize");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        self.check_pattern(csc)?;
+
+        // requests
+        let compute_cond = if self.params.compute_condition_numbers { 1 } else { 0 };
+
+        // call refactorize
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_klu_refactorize(
+                self.solver,
+                &mut self.cond_estimate,
+                compute_cond,
+                csc.col_pointers.as_ptr(),
+                csc.row_indices.as_ptr(),
+                csc.values.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_klu_error_code(status));
+            }
+        }
+        self.time_refactorize_ns = self.stopwatch.stop();
+        Ok(())
+    }
+
     /// Computes the solution of the linear system
     ///
     /// Solves the linear system:
@@ -252,49 +428,63 @@ impl LinSolTrait for SolverKLU {
     ///
     /// **Warning:** the matrix must be same one used in `factorize`.
     fn solve(&mut self, x: &mut Vector, mat: &SparseMatrix, rhs: &Vector, _verbose: bool) -> Result<(), StrError> {
-        // check
-        if !self.factorized {
-            return Err("the function factorize must be called before solve");
-        }
-
-        // access CSC matrix
-        // (possibly already converted from COO, because factorize was (should have been) called)
-        let csc = mat.get_csc()?;
-
-        // check already factorized data
-        let (nrow, ncol, nnz, sym) = csc.get_info();
-        if sym != self.initialized_sym {
-            return Err("solve must use the same matrix (symmetric differs)");
-        }
-        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
-            return Err("solve must use the same matrix (ndim differs)");
-        }
-        if nnz != self.initialized_nnz {
-            return Err("solve must use the same matrix (nnz differs)");
-        }
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), 1, false)
+    }
 
-        // check vectors
-        if x.dim() != self.initialized_ndim {
-            return Err("the dimension of the vector of unknown values x is incorrect");
-        }
-        if rhs.dim() != self.initialized_ndim {
-            return Err("the dimension of the right-hand side vector is incorrect");
-        }
+    /// Computes the solution of the transposed linear system
+    ///
+    /// Solves `Aᵀ · x = rhs` using the same factorization of A.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (not transposed); it must be square and, if symmetric, [Sym::YesFull].
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_transpose(
+        &mut self,
+        x: &mut Vector,
+        mat: &SparseMatrix,
+        rhs: &Vector,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), 1, true)
+    }
 
-        // call KLU solve
-        let ndim = to_i32(self.initialized_ndim);
-        vec_copy(x, rhs).unwrap();
-        self.stopwatch.reset();
-        unsafe {
-            let status = solver_klu_solve(self.solver, ndim, x.as_mut_data().as_mut_ptr());
-            if status != SUCCESSFUL_EXIT {
-                return Err(handle_klu_error_code(status));
-            }
+    /// Computes the solution of the linear system with multiple right-hand sides (reusing the factorization)
+    ///
+    /// All right-hand sides are handled by a single call to KLU.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the (m,k) matrix of unknown values; each column corresponds to a column of `rhs`
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesFull].
+    /// * `rhs` -- the (m,k) matrix of right-hand sides
+    /// * `transpose` -- solves `Aᵀ · X = RHS` instead
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_multiple(
+        &mut self,
+        x: &mut Matrix,
+        mat: &SparseMatrix,
+        rhs: &Matrix,
+        transpose: bool,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        let (m, k) = rhs.dims();
+        if x.dims() != (m, k) {
+            return Err("the dimensions of the matrix of unknown values X are incorrect");
         }
-        self.time_solve_ns = self.stopwatch.stop();
-
-        // done
-        Ok(())
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), k, transpose)
     }
 
     /// Updates the stats structure (should be called after solve)
@@ -318,6 +508,7 @@ impl LinSolTrait for SolverKLU {
         };
         stats.time_nanoseconds.initialize = self.time_initialize_ns;
         stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.refactorize = self.time_refactorize_ns;
         stats.time_nanoseconds.solve = self.time_solve_ns;
     }
 
@@ -335,6 +526,11 @@ impl LinSolTrait for SolverKLU {
     fn get_ns_solve(&self) -> u128 {
         self.time_solve_ns
     }
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        self.time_refactorize_ns
+    }
 }
 
 pub(crate) const KLU_ORDERING_AUTO: i32 = -10; // (code defined here) use defaults
@@ -384,6 +580,7 @@ pub(crate) fn handle_klu_error_code(err: i32) -> StrError {
         -9 => "klu_analyze failed",
         -8 => "klu_factor failed",
         -7 => "klu_condest failed",
+        -6 => "klu_refactor failed",
         ERROR_NULL_POINTER => "KLU failed due to NULL POINTER error",
         ERROR_MALLOC => "KLU failed due to MALLOC error",
         ERROR_VERSION => "KLU failed due to VERSION error",
@@ -401,7 +598,7 @@ pub(crate) fn handle_klu_error_code(err: i32) -> StrError {
 mod tests {
     use super::*;
     use crate::{CooMatrix, Samples};
-    use russell_lab::vec_approx_eq;
+    use russell_lab::{mat_approx_eq, mat_mat_mul, mat_t_mat_mul, vec_approx_eq, vec_mat_mul};
 
     #[test]
     fn new_and_drop_work() {
@@ -469,6 +666,15 @@ mod tests {
             solver.factorize(&mut mat, None).err(),
             Some("subsequent factorizations must use the same matrix (nnz differs)")
         );
+        // ... change matrix (pattern)
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 1, 1.0).unwrap();
+        coo.put(1, 1, 2.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (pattern differs)")
+        );
     }
 
     #[test]
@@ -589,6 +795,79 @@ mod tests {
         assert_eq!(stats.output.effective_scaling, "Max");
     }
 
+    #[test]
+    fn analyze_factorize_numeric_and_refactorize_work() {
+        let mut solver = SolverKLU::new().unwrap();
+        let (_, csc, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_csc(csc);
+        let mut x = Vector::new(5);
+        let rhs = Vector::from(&[8.0, 45.0, -3.0, 3.0, 19.0]);
+        let x_correct = &[1.0, 2.0, 3.0, 4.0, 5.0];
+
+        // errors
+        assert_eq!(
+            solver.factorize_numeric(&mut mat).err(),
+            Some("the function analyze must be called before factorize_numeric")
+        );
+        assert_eq!(
+            solver.refactorize(&mut mat).err(),
+            Some("the function factorize must be called before refactorize")
+        );
+
+        // analyze and factorize
+        solver.analyze(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.analyze(&mut mat, None).err(),
+            Some("the analysis has been performed already")
+        );
+        solver.factorize_numeric(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, x_correct, 1e-14);
+
+        // refactorize with other values (same pattern)
+        mat.get_csc_mut()
+            .unwrap()
+            .get_values_mut()
+            .iter_mut()
+            .for_each(|v| *v *= 2.0);
+        solver.refactorize(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &[0.5, 1.0, 1.5, 2.0, 2.5], 1e-14);
+
+        // update stats
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.time_nanoseconds.refactorize, solver.get_ns_refact());
+    }
+
+    #[test]
+    fn solve_transpose_and_multiple_works() {
+        let mut solver = SolverKLU::new().unwrap();
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let a = coo.as_dense();
+        let mut mat = SparseMatrix::from_coo(coo);
+        solver.factorize(&mut mat, None).unwrap();
+
+        // Aᵀ · x = rhs
+        let x_correct = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut rhs = Vector::new(5);
+        vec_mat_mul(&mut rhs, 1.0, &x_correct, &a).unwrap();
+        let mut x = Vector::new(5);
+        solver.solve_transpose(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-13);
+
+        // A · X = RHS and Aᵀ · X = RHS
+        let xx_correct = Matrix::from(&[[1.0, 5.0], [2.0, 4.0], [3.0, 3.0], [4.0, 2.0], [5.0, 1.0]]);
+        let mut rhs_mat = Matrix::new(5, 2);
+        mat_mat_mul(&mut rhs_mat, 1.0, &a, &xx_correct, 0.0).unwrap();
+        let mut xx = Matrix::new(5, 2);
+        solver.solve_multiple(&mut xx, &mat, &rhs_mat, false, false).unwrap();
+        mat_approx_eq(&xx, &xx_correct, 1e-13);
+        mat_t_mat_mul(&mut rhs_mat, 1.0, &a, &xx_correct, 0.0).unwrap();
+        solver.solve_multiple(&mut xx, &mat, &rhs_mat, true, false).unwrap();
+        mat_approx_eq(&xx, &xx_correct, 1e-13);
+    }
+
     #[test]
     fn solve_works_symmetric() {
         let mut solver = SolverKLU::new().unwrap();
@@ -649,6 +928,7 @@ mod tests {
         assert_eq!(handle_klu_error_code(-9), "klu_analyze failed");
         assert_eq!(handle_klu_error_code(-8), "klu_factor failed");
         assert_eq!(handle_klu_error_code(-7), "klu_condest failed");
+        assert_eq!(handle_klu_error_code(-6), "klu_refactor failed");
         assert_eq!(
             handle_klu_error_code(ERROR_NULL_POINTER),
             "KLU failed due to NULL POINTER error"
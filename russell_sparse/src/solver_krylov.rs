@@ -0,0 +1,2038 @@
+use super::{ComplexLinSolTrait, ComplexSparseMatrix, FactorScalar, Genie, LinSolParams, LinSolTrait};
+use super::{
+    NumPreconditioner, NumSparseMatrix, ParallelMatVec, Precond, SparseMatrix, StatsLinSol, StatsLinSolIterative, Sym,
+};
+use crate::StrError;
+use russell_lab::{Complex64, ComplexVector, LinearOperator, NumMatrix, NumVector, Stopwatch, Vector};
+
+/// Implements iterative (Krylov subspace) solvers for real and complex sparse linear systems
+///
+/// The available methods are:
+///
+/// * [Genie::Cg] -- conjugate gradient; requires a symmetric (Hermitian if complex) positive-definite matrix
+/// * [Genie::Cocg] -- conjugate orthogonal conjugate gradient; requires a complex symmetric matrix (`A = Aᵀ`)
+/// * [Genie::Minres] -- minimal residual; requires a symmetric (Hermitian if complex) and possibly indefinite matrix
+/// * [Genie::Gmres] -- restarted generalized minimal residual; works with general matrices
+/// * [Genie::BiCgStab] -- biconjugate gradient stabilized; works with general matrices
+///
+/// COCG is the CG method with the unconjugated bilinear form `uᵀ·v` replacing the inner product `uᴴ·v`.
+/// It is the method of choice for the complex symmetric matrices arising from frequency-domain acoustic
+/// and electromagnetic problems (e.g., the Helmholtz equation with absorbing boundaries). For real
+/// matrices, COCG yields the same iterates as CG (without checking the positive-definiteness).
+///
+/// See also the aliases [crate::SolverKrylov] and [crate::ComplexSolverKrylov].
+///
+/// The coefficient matrix is only accessed via matrix-vector products. Thus, the solver may
+/// be used with a sparse matrix (via [LinSolTrait] or [ComplexLinSolTrait]) or with a matrix-free
+/// operator (via [NumSolverKrylov::solve_with_operator]).
+///
+/// Convergence is reached when:
+///
+/// ```text
+/// ‖b - A·x‖ ≤ max(rtol·‖b‖, atol)
+/// ```
+///
+/// where `rtol` and `atol` are given by [LinSolParams::krylov_rel_tol] and [LinSolParams::krylov_abs_tol].
+///
+/// A preconditioner M may be selected via [LinSolParams::krylov_precond] or given directly
+/// via [NumSolverKrylov::set_preconditioner] (e.g., for matrix-free operators). CG and MINRES
+/// use M symmetrically (thus, M must be symmetric or Hermitian positive-definite), COCG requires
+/// a complex symmetric M, whereas GMRES and BiCGStab use right preconditioning (i.e., they solve
+/// `A·M⁻¹·y = b` with `x = M⁻¹·y`).
+///
+/// # Notes
+///
+/// 1. The `factorize` function does not factorize the matrix; it only checks the matrix structure,
+///    converts the COO matrix to CSR (if needed), sets up the preconditioner (if any), and saves the parameters.
+/// 2. The symmetry of the matrix is not checked; the methods may fail (or not converge) if the
+///    matrix does not have the required symmetry.
+/// 3. The complex matrix-vector product interprets the triangular storage ([Sym::YesLower] or [Sym::YesUpper])
+///    as complex symmetric. Thus, with complex matrices, CG and MINRES (Hermitian matrices) require
+///    [Sym::No] or [Sym::YesFull]. Likewise, the IC(0) preconditioner (`M = L·Lᵀ`) is complex symmetric;
+///    thus, it should be combined with COCG (and not with CG or MINRES) in the complex case.
+/// 4. An error is returned if the method does not converge within the maximum number of iterations;
+///    nonetheless, the convergence history remains available (see [NumSolverKrylov::get_history]).
+pub struct NumSolverKrylov<T>
+where
+    T: FactorScalar,
+{
+    /// Holds the selected method
+    genie: Genie,
+
+    /// Indicates whether the solver has been initialized or not (just once)
+    initialized: bool,
+
+    /// Holds the symmetric flag saved in initialize
+    initialized_sym: Sym,
+
+    /// Holds the matrix dimension saved in initialize
+    initialized_ndim: usize,
+
+    /// Holds the number of non-zeros saved in initialize
+    initialized_nnz: usize,
+
+    /// Holds the parameters saved in factorize
+    params: LinSolParams,
+
+    /// Holds the preconditioner
+    precond: Option<NumPreconditioner<'static, T>>,
+
+    /// Holds the kind of preconditioner allocated in factorize (No if none or given by set_preconditioner)
+    precond_kind: Precond,
+
+    /// Holds the threads and the workspace of the parallel matrix-vector product (kept across solves)
+    mat_vec: Option<ParallelMatVec>,
+
+    /// Holds the number of threads requested when mat_vec was allocated
+    mat_vec_num_threads: usize,
+
+    /// Indicates that the last solve has converged
+    converged: bool,
+
+    /// Holds the number of iterations of the last solve
+    iterations: usize,
+
+    /// Holds the number of matrix-vector products of the last solve
+    mat_vec_mul_count: usize,
+
+    /// Holds the tolerance on the residual norm: max(rtol·‖b‖, atol)
+    tolerance: f64,
+
+    /// Holds the norm of the right-hand side vector
+    rhs_norm: f64,
+
+    /// Holds the (last) residual norm
+    residual_norm: f64,
+
+    /// Holds the residual norms computed at each iteration (including the initial residual)
+    history: Vec<f64>,
+
+    /// Stopwatch to measure computation times
+    stopwatch: Stopwatch,
+
+    /// Time spent on initialize (the first call to factorize: conversion to the compressed format and checks) in nanoseconds
+    time_initialize_ns: u128,
+
+    /// Time spent on factorize in nanoseconds
+    time_factorize_ns: u128,
+
+    /// Time spent on solve in nanoseconds
+    time_solve_ns: u128,
+}
+
+impl<T> NumSolverKrylov<T>
+where
+    T: FactorScalar + 'static,
+{
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `genie` -- the iterative method: [Genie::BiCgStab], [Genie::Cg], [Genie::Cocg], [Genie::Gmres], or [Genie::Minres]
+    pub fn new(genie: Genie) -> Result<Self, StrError> {
+        if !genie.is_iterative() {
+            return Err("the genie must be an iterative solver (BiCgStab, Cg, Cocg, Gmres, or Minres)");
+        }
+        Ok(NumSolverKrylov {
+            genie,
+            initialized: false,
+            initialized_sym: Sym::No,
+            initialized_ndim: 0,
+            initialized_nnz: 0,
+            params: LinSolParams::new(),
+            precond: None,
+            precond_kind: Precond::No,
+            mat_vec: None,
+            mat_vec_num_threads: 1,
+            converged: false,
+            iterations: 0,
+            mat_vec_mul_count: 0,
+            tolerance: 0.0,
+            rhs_norm: 0.0,
+            residual_norm: 0.0,
+            history: Vec::new(),
+            stopwatch: Stopwatch::new(),
+            time_initialize_ns: 0,
+            time_factorize_ns: 0,
+            time_solve_ns: 0,
+        })
+    }
+
+    /// Solves a linear system given by a matrix-free operator
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// where A is only available via the matrix-vector product `v := A·u` computed by `operator(v, u)`.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values (also the initial guess if [LinSolParams::krylov_nonzero_initial_guess])
+    ///
+    /// # Input
+    ///
+    /// * `rhs` -- the right-hand side vector with dimension equal to the dimension of x
+    /// * `params` -- configuration parameters; None => use default
+    /// * `operator` -- a function `(v, u)` computing `v := A·u`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_lab::{vec_approx_eq, Vector};
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // tridiagonal matrix with 2 on the diagonal and -1 off the diagonal
+    ///     let n = 5;
+    ///     let rhs = Vector::from(&[1.0, 0.0, 0.0, 0.0, 1.0]);
+    ///     let mut x = Vector::new(n);
+    ///     let mut solver = SolverKrylov::new(Genie::Cg)?;
+    ///     solver.solve_with_operator(&mut x, &rhs, None, |v, u| {
+    ///         for i in 0..n {
+    ///             v[i] = 2.0 * u[i];
+    ///             if i > 0 {
+    ///                 v[i] -= u[i - 1];
+    ///             }
+    ///             if i < n - 1 {
+    ///                 v[i] -= u[i + 1];
+    ///             }
+    ///         }
+    ///         Ok(())
+    ///     })?;
+    ///     vec_approx_eq(&x, &[1.0, 1.0, 1.0, 1.0, 1.0], 1e-14);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// And with a complex symmetric matrix:
+    ///
+    /// ```
+    /// use russell_lab::{complex_vec_approx_eq, cpx, Complex64, ComplexVector};
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // complex symmetric tridiagonal matrix with 2+i on the diagonal and -1 off the diagonal
+    ///     let n = 5;
+    ///     let d = cpx!(2.0, 1.0);
+    ///     let rhs = ComplexVector::from(&[
+    ///         cpx!(1.0, 1.0),
+    ///         cpx!(0.0, 1.0),
+    ///         cpx!(0.0, 1.0),
+    ///         cpx!(0.0, 1.0),
+    ///         cpx!(1.0, 1.0),
+    ///     ]);
+    ///     let mut x = ComplexVector::new(n);
+    ///     let mut solver = ComplexSolverKrylov::new(Genie::Cocg)?;
+    ///     solver.solve_with_operator(&mut x, &rhs, None, |v, u| {
+    ///         for i in 0..n {
+    ///             v[i] = d * u[i];
+    ///             if i > 0 {
+    ///                 v[i] -= u[i - 1];
+    ///             }
+    ///             if i < n - 1 {
+    ///                 v[i] -= u[i + 1];
+    ///             }
+    ///         }
+    ///         Ok(())
+    ///     })?;
+    ///     let one = cpx!(1.0, 0.0);
+    ///     complex_vec_approx_eq(&x, &[one, one, one, one, one], 1e-13);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn solve_with_operator<F>(
+        &mut self,
+        x: &mut NumVector<T>,
+        rhs: &NumVector<T>,
+        params: Option<LinSolParams>,
+        mut operator: F,
+    ) -> Result<(), StrError>
+    where
+        F: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+    {
+        self.params = if let Some(p) = params { p } else { LinSolParams::new() };
+        self.run(x, rhs, &mut operator)
+    }
+
+    /// Sets (or removes) the preconditioner
+    ///
+    /// This function is useful with [NumSolverKrylov::solve_with_operator] because the preconditioner
+    /// cannot be computed from a matrix-free operator. The preconditioner must be set up already.
+    ///
+    /// **Note:** A subsequent call to `factorize` with [LinSolParams::krylov_precond] other than [Precond::No]
+    /// replaces this preconditioner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_lab::{vec_approx_eq, Vector};
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // tridiagonal matrix with 2 on the diagonal and -1 off the diagonal
+    ///     let n = 5;
+    ///     let mut coo = CooMatrix::new(n, n, 3 * n, Sym::No)?;
+    ///     for i in 0..n {
+    ///         coo.put(i, i, 2.0)?;
+    ///         if i > 0 {
+    ///             coo.put(i, i - 1, -1.0)?;
+    ///         }
+    ///         if i < n - 1 {
+    ///             coo.put(i, i + 1, -1.0)?;
+    ///         }
+    ///     }
+    ///     let mat = SparseMatrix::from_coo(coo);
+    ///
+    ///     // the IC(0) factorization of a tridiagonal matrix is exact
+    ///     let mut precond = Preconditioner::new(Precond::Ic0, None)?;
+    ///     precond.setup(&mat)?;
+    ///
+    ///     // solve
+    ///     let rhs = Vector::from(&[1.0, 0.0, 0.0, 0.0, 1.0]);
+    ///     let mut x = Vector::new(n);
+    ///     let mut solver = SolverKrylov::new(Genie::Cg)?;
+    ///     solver.set_preconditioner(Some(precond));
+    ///     solver.solve_with_operator(&mut x, &rhs, None, |v, u| mat.mat_vec_mul(v, 1.0, u))?;
+    ///     vec_approx_eq(&x, &[1.0, 1.0, 1.0, 1.0, 1.0], 1e-14);
+    ///     assert_eq!(solver.get_iterations(), 1);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_preconditioner(&mut self, precond: Option<NumPreconditioner<'static, T>>) {
+        self.precond = precond;
+        self.precond_kind = Precond::No;
+    }
+
+    /// Returns whether the last solve has converged or not
+    pub fn get_converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Returns the number of iterations of the last solve
+    pub fn get_iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the number of matrix-vector products of the last solve
+    pub fn get_mat_vec_mul_count(&self) -> usize {
+        self.mat_vec_mul_count
+    }
+
+    /// Returns the (last) residual norm `‖b - A·x‖`
+    ///
+    /// **Note:** GMRES and MINRES compute an estimate of the residual norm (exact in exact arithmetic).
+    pub fn get_residual_norm(&self) -> f64 {
+        self.residual_norm
+    }
+
+    /// Returns the residual norms of the last solve (starting with the initial residual)
+    pub fn get_history(&self) -> &[f64] {
+        &self.history
+    }
+
+    /// Checks the matrix, sets up the preconditioner (if any), and saves the parameters
+    fn initialize_and_setup(
+        &mut self,
+        mat: &mut NumSparseMatrix<T>,
+        params: Option<LinSolParams>,
+    ) -> Result<(), StrError> {
+        // update the compressed matrix from COO, if available
+        self.stopwatch.reset();
+        if mat.get_csc().is_ok() {
+            mat.get_csc_or_from_coo()?;
+        } else {
+            mat.get_csr_or_from_coo()?;
+        }
+        let (nrow, ncol, nnz, sym) = mat.get_info();
+
+        // check
+        if self.initialized {
+            if sym != self.initialized_sym {
+                return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+            }
+            if nrow != self.initialized_ndim {
+                return Err("subsequent factorizations must use the same matrix (ndim differs)");
+            }
+            if nnz != self.initialized_nnz {
+                return Err("subsequent factorizations must use the same matrix (nnz differs)");
+            }
+        } else {
+            if nrow != ncol {
+                return Err("the matrix must be square");
+            }
+            self.initialized_sym = sym;
+            self.initialized_ndim = nrow;
+            self.initialized_nnz = nnz;
+            self.initialized = true;
+            self.time_initialize_ns = self.stopwatch.stop();
+            self.stopwatch.reset();
+        }
+
+        // parameters
+        self.params = if let Some(p) = params { p } else { LinSolParams::new() };
+
+        // preconditioner (one given by set_preconditioner is kept if krylov_precond is No)
+        if self.params.krylov_precond != Precond::No {
+            let reuse = self.params.krylov_precond_reuse && self.precond_kind == self.params.krylov_precond;
+            match self.precond.as_mut() {
+                Some(precond) if reuse => precond.update(mat)?,
+                _ => {
+                    let mut precond = NumPreconditioner::new(self.params.krylov_precond, Some(self.params))?;
+                    precond.setup(mat)?;
+                    self.precond = Some(precond);
+                }
+            }
+        } else if self.precond_kind != Precond::No {
+            self.precond = None;
+        }
+        self.precond_kind = self.params.krylov_precond;
+
+        // parallel matrix-vector product (the threads are spawned once and reused by all solves)
+        let num_threads = self.params.krylov_num_threads;
+        if num_threads == 1 {
+            self.mat_vec = None;
+        } else if self.mat_vec.is_none() || num_threads != self.mat_vec_num_threads {
+            self.mat_vec = match mat.get_csr() {
+                Ok(csr) => Some(ParallelMatVec::new(csr, num_threads)),
+                Err(_) => None,
+            };
+        }
+        self.mat_vec_num_threads = num_threads;
+        self.time_factorize_ns = self.stopwatch.stop();
+        Ok(())
+    }
+
+    /// Checks the matrix and vectors and runs the selected method
+    fn solve_with_matrix(
+        &mut self,
+        x: &mut NumVector<T>,
+        mat: &NumSparseMatrix<T>,
+        rhs: &NumVector<T>,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        // check
+        if !self.initialized {
+            return Err("the function factorize must be called before solve");
+        }
+        let (nrow, ncol, nnz, sym) = mat.get_info();
+        if sym != self.initialized_sym {
+            return Err("solve must use the same matrix (symmetric differs)");
+        }
+        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
+            return Err("solve must use the same matrix (ndim differs)");
+        }
+        if nnz != self.initialized_nnz {
+            return Err("solve must use the same matrix (nnz differs)");
+        }
+        if x.dim() != self.initialized_ndim {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.dim() != self.initialized_ndim {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+
+        // run
+        let mat_vec = self.mat_vec.take();
+        let res = self.run(x, rhs, &mut |v, u| match (&mat_vec, mat.get_csr()) {
+            (Some(plan), Ok(csr)) => plan.mat_vec_mul(v, T::one(), csr, u),
+            _ => mat.mat_vec_mul(v, T::one(), u),
+        });
+        self.mat_vec = mat_vec;
+        if verbose {
+            println!(
+                "{}: converged = {}, iterations = {}, residual norm = {:.6e}",
+                self.genie.to_string(),
+                self.converged,
+                self.iterations,
+                self.residual_norm
+            );
+        }
+        res
+    }
+
+    /// Updates the stats structure (should be called after solve)
+    fn update_stats_iterative(&self, stats: &mut StatsLinSol) {
+        stats.main.solver = match self.genie {
+            Genie::BiCgStab => "BiCGStab".to_string(),
+            Genie::Cg => "CG".to_string(),
+            Genie::Cocg => "COCG".to_string(),
+            Genie::Gmres => "GMRES".to_string(),
+            Genie::Minres => "MINRES".to_string(),
+            _ => "Unknown".to_string(),
+        };
+        stats.iterative = StatsLinSolIterative {
+            converged: self.converged,
+            iterations: self.iterations,
+            mat_vec_mul_count: self.mat_vec_mul_count,
+            tolerance: self.tolerance,
+            rhs_norm: self.rhs_norm,
+            residual_norm: self.residual_norm,
+            history: self.history.clone(),
+        };
+        stats.time_nanoseconds.initialize = self.time_initialize_ns;
+        stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.solve = self.time_solve_ns;
+    }
+
+    /// Runs the selected method
+    fn run<F>(&mut self, x: &mut NumVector<T>, rhs: &NumVector<T>, operator: &mut F) -> Result<(), StrError>
+    where
+        F: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+    {
+        // check
+        if x.dim() != rhs.dim() {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if self.params.krylov_rel_tol < 0.0 || self.params.krylov_abs_tol < 0.0 {
+            return Err("the tolerances must be non-negative");
+        }
+        if self.params.krylov_max_iterations < 1 {
+            return Err("the maximum number of iterations must be ≥ 1");
+        }
+        if self.params.krylov_gmres_restart < 1 {
+            return Err("the number of iterations before restarting GMRES must be ≥ 1");
+        }
+
+        // reset the convergence data
+        self.converged = false;
+        self.iterations = 0;
+        self.mat_vec_mul_count = 0;
+        self.history.clear();
+        self.rhs_norm = norm(rhs);
+        self.tolerance = f64::max(self.params.krylov_rel_tol * self.rhs_norm, self.params.krylov_abs_tol);
+        self.residual_norm = 0.0;
+
+        // initial guess
+        if !self.params.krylov_nonzero_initial_guess {
+            x.fill(T::zero());
+        }
+
+        // preconditioner: z := M⁻¹·r (identity if None)
+        let mut precond = self.precond.take();
+        let mut psolve = |z: &mut NumVector<T>, r: &NumVector<T>| match precond.as_mut() {
+            Some(p) => p.apply(z, r),
+            None => {
+                if z.dim() != r.dim() {
+                    return Err("vectors are incompatible");
+                }
+                z.as_mut_data().copy_from_slice(r.as_data());
+                Ok(())
+            }
+        };
+
+        // run
+        self.stopwatch.reset();
+        let res = match self.genie {
+            Genie::BiCgStab => self.run_bicgstab(x, rhs, operator, &mut psolve),
+            Genie::Cg => self.run_cg(x, rhs, operator, &mut psolve),
+            Genie::Cocg => self.run_cocg(x, rhs, operator, &mut psolve),
+            Genie::Gmres => self.run_gmres(x, rhs, operator, &mut psolve),
+            Genie::Minres => self.run_minres(x, rhs, operator, &mut psolve),
+            _ => Err("the genie must be an iterative solver (BiCgStab, Cg, Cocg, Gmres, or Minres)"),
+        };
+        self.time_solve_ns = self.stopwatch.stop();
+        self.precond = precond;
+        res?;
+        if !self.converged {
+            return Err("the iterative solver did not converge (max number of iterations reached)");
+        }
+        Ok(())
+    }
+
+    /// Computes v := A·u and increments the counter of matrix-vector products
+    fn mat_vec_mul<F>(&mut self, v: &mut NumVector<T>, u: &NumVector<T>, operator: &mut F) -> Result<(), StrError>
+    where
+        F: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+    {
+        self.mat_vec_mul_count += 1;
+        operator(v, u)
+    }
+
+    /// Computes the residual r := b - A·x
+    fn residual<F>(
+        &mut self,
+        r: &mut NumVector<T>,
+        x: &NumVector<T>,
+        rhs: &NumVector<T>,
+        operator: &mut F,
+    ) -> Result<(), StrError>
+    where
+        F: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+    {
+        self.mat_vec_mul(r, x, operator)?;
+        for i in 0..r.dim() {
+            r[i] = rhs[i] - r[i];
+        }
+        Ok(())
+    }
+
+    /// Records the residual norm and returns true if convergence has been reached
+    fn check_convergence(&mut self, residual_norm: f64) -> bool {
+        self.residual_norm = residual_norm;
+        self.history.push(residual_norm);
+        self.converged = residual_norm <= self.tolerance;
+        self.converged
+    }
+
+    /// Implements the (preconditioned) conjugate gradient method for symmetric (Hermitian) positive-definite matrices
+    fn run_cg<F, P>(
+        &mut self,
+        x: &mut NumVector<T>,
+        rhs: &NumVector<T>,
+        operator: &mut F,
+        psolve: &mut P,
+    ) -> Result<(), StrError>
+    where
+        F: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+        P: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+    {
+        let n = rhs.dim();
+        let mut r = NumVector::<T>::new(n);
+        let mut z = NumVector::<T>::new(n);
+        let mut p = NumVector::<T>::new(n);
+        let mut ap = NumVector::<T>::new(n);
+        self.residual(&mut r, x, rhs, operator)?;
+        if self.check_convergence(norm(&r)) {
+            return Ok(());
+        }
+        psolve(&mut z, &r)?;
+        let mut rz = dotc(&r, &z).real();
+        p.as_mut_data().copy_from_slice(z.as_data());
+        while self.iterations < self.params.krylov_max_iterations {
+            if rz <= 0.0 {
+                return Err("CG breakdown: the preconditioner is not positive-definite");
+            }
+            self.mat_vec_mul(&mut ap, &p, operator)?;
+            let pap = dotc(&p, &ap).real();
+            if pap <= 0.0 {
+                return Err("CG breakdown: the matrix is not positive-definite");
+            }
+            let alpha = rz / pap;
+            for i in 0..n {
+                x[i] += p[i].scale(alpha);
+                r[i] -= ap[i].scale(alpha);
+            }
+            self.iterations += 1;
+            if self.check_convergence(norm(&r)) {
+                return Ok(());
+            }
+            psolve(&mut z, &r)?;
+            let rz_new = dotc(&r, &z).real();
+            let beta = rz_new / rz;
+            for i in 0..n {
+                p[i] = z[i] + p[i].scale(beta);
+            }
+            rz = rz_new;
+        }
+        Ok(())
+    }
+
+    /// Implements the (preconditioned) conjugate orthogonal conjugate gradient method for complex symmetric matrices
+    ///
+    /// This is the CG method with the bilinear form `uᵀ·v` instead of the inner product `uᴴ·v`.
+    /// Thus, the residuals are "conjugate orthogonal" (`r̄ᵢᴴ·rⱼ = 0` for `i ≠ j`).
+    ///
+    /// Reference: van der Vorst and Melissen (1990) A Petrov-Galerkin type method for solving Ax = b,
+    /// where A is symmetric complex, IEEE Transactions on Magnetics 26(2):706-708
+    fn run_cocg<F, P>(
+        &mut self,
+        x: &mut NumVector<T>,
+        rhs: &NumVector<T>,
+        operator: &mut F,
+        psolve: &mut P,
+    ) -> Result<(), StrError>
+    where
+        F: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+        P: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+    {
+        let n = rhs.dim();
+        let mut r = NumVector::<T>::new(n);
+        let mut z = NumVector::<T>::new(n);
+        let mut p = NumVector::<T>::new(n);
+        let mut ap = NumVector::<T>::new(n);
+        self.residual(&mut r, x, rhs, operator)?;
+        if self.check_convergence(norm(&r)) {
+            return Ok(());
+        }
+        psolve(&mut z, &r)?;
+        let mut rz = dotu(&r, &z);
+        p.as_mut_data().copy_from_slice(z.as_data());
+        while self.iterations < self.params.krylov_max_iterations {
+            if rz.modulus() == 0.0 {
+                return Err("COCG breakdown: (r, z) = 0");
+            }
+            self.mat_vec_mul(&mut ap, &p, operator)?;
+            let pap = dotu(&p, &ap);
+            if pap.modulus() == 0.0 {
+                return Err("COCG breakdown: (p, A·p) = 0");
+            }
+            let alpha = rz / pap;
+            for i in 0..n {
+                x[i] += alpha * p[i];
+                r[i] -= alpha * ap[i];
+            }
+            self.iterations += 1;
+            if self.check_convergence(norm(&r)) {
+                return Ok(());
+            }
+            psolve(&mut z, &r)?;
+            let rz_new = dotu(&r, &z);
+            let beta = rz_new / rz;
+            for i in 0..n {
+                p[i] = z[i] + beta * p[i];
+            }
+            rz = rz_new;
+        }
+        Ok(())
+    }
+
+    /// Implements the (preconditioned) minimal residual method for symmetric (Hermitian) matrices
+    ///
+    /// With a preconditioner, the method minimizes the M⁻¹-norm of the residual. Thus, the
+    /// residual norm estimate is scaled by the ratio between the Euclidean and the M⁻¹ norms
+    /// of the initial residual. Since the matrix is symmetric (Hermitian), the Lanczos tridiagonal
+    /// matrix is real and the Givens rotations are real.
+    ///
+    /// Reference: Algorithm 2.4 of Elman, Silvester, and Wathen (2014) Finite Elements and Fast
+    /// Iterative Solvers, 2nd edition, Oxford University Press
+    fn run_minres<F, P>(
+        &mut self,
+        x: &mut NumVector<T>,
+        rhs: &NumVector<T>,
+        operator: &mut F,
+        psolve: &mut P,
+    ) -> Result<(), StrError>
+    where
+        F: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+        P: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+    {
+        let n = rhs.dim();
+        let mut v_prev = NumVector::<T>::new(n);
+        let mut v = NumVector::<T>::new(n);
+        let mut v_next = NumVector::<T>::new(n);
+        let mut z = NumVector::<T>::new(n);
+        let mut z_next = NumVector::<T>::new(n);
+        let mut w_prev = NumVector::<T>::new(n);
+        let mut w = NumVector::<T>::new(n);
+        let mut w_new = NumVector::<T>::new(n);
+        self.residual(&mut v, x, rhs, operator)?;
+        let r_norm = norm(&v);
+        if self.check_convergence(r_norm) {
+            return Ok(());
+        }
+        psolve(&mut z, &v)?;
+        let vz = dotc(&z, &v).real();
+        if vz <= 0.0 {
+            return Err("MINRES breakdown: the preconditioner is not positive-definite");
+        }
+        let mut gamma = f64::sqrt(vz);
+        let mut gamma_prev = 1.0;
+        let scale = r_norm / gamma; // converts the M⁻¹-norm to the Euclidean norm (exact at the first iteration)
+        let (mut c_prev, mut c, mut s_prev, mut s) = (1.0, 1.0, 0.0, 0.0);
+        let mut eta = gamma;
+        while self.iterations < self.params.krylov_max_iterations {
+            // Lanczos step
+            for i in 0..n {
+                z[i] = z[i].scale(1.0 / gamma);
+            }
+            self.mat_vec_mul(&mut v_next, &z, operator)?;
+            let delta = dotc(&z, &v_next).real();
+            for i in 0..n {
+                v_next[i] -= v[i].scale(delta / gamma) + v_prev[i].scale(gamma / gamma_prev);
+            }
+            psolve(&mut z_next, &v_next)?;
+            let vz_next = dotc(&z_next, &v_next).real();
+            if vz_next < 0.0 {
+                return Err("MINRES breakdown: the preconditioner is not positive-definite");
+            }
+            let gamma_next = f64::sqrt(vz_next);
+
+            // QR factorization via Givens rotations
+            let alpha0 = c * delta - c_prev * s * gamma;
+            let alpha1 = f64::hypot(alpha0, gamma_next);
+            let alpha2 = s * delta + c_prev * c * gamma;
+            let alpha3 = s_prev * gamma;
+            if alpha1 == 0.0 {
+                return Err("MINRES breakdown: the matrix is singular");
+            }
+            let c_new = alpha0 / alpha1;
+            let s_new = gamma_next / alpha1;
+
+            // update the solution
+            for i in 0..n {
+                w_new[i] = (z[i] - w_prev[i].scale(alpha3) - w[i].scale(alpha2)) / T::from_real(alpha1);
+                x[i] += w_new[i].scale(c_new * eta);
+            }
+            eta *= -s_new;
+            self.iterations += 1;
+            if self.check_convergence(scale * f64::abs(eta)) {
+                return Ok(());
+            }
+
+            // shift the data for the next iteration
+            (c_prev, c, s_prev, s) = (c, c_new, s, s_new);
+            std::mem::swap(&mut w_prev, &mut w);
+            std::mem::swap(&mut w, &mut w_new);
+            std::mem::swap(&mut v_prev, &mut v);
+            std::mem::swap(&mut v, &mut v_next);
+            std::mem::swap(&mut z, &mut z_next);
+            (gamma_prev, gamma) = (gamma, gamma_next);
+        }
+        Ok(())
+    }
+
+    /// Implements the restarted generalized minimal residual method (with right preconditioning)
+    ///
+    /// The Hessenberg matrix is reduced to the triangular form by (complex) Givens rotations:
+    ///
+    /// ```text
+    /// G = │ c̄  s̄ │    with    c = a / ρ,  s = b / ρ,  ρ = √(|a|² + |b|²)
+    ///     │ -s  c │
+    /// ```
+    ///
+    /// Reference: Algorithms 6.11 and 9.5 of Saad (2003) Iterative Methods for Sparse Linear Systems,
+    /// 2nd edition, SIAM
+    fn run_gmres<F, P>(
+        &mut self,
+        x: &mut NumVector<T>,
+        rhs: &NumVector<T>,
+        operator: &mut F,
+        psolve: &mut P,
+    ) -> Result<(), StrError>
+    where
+        F: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+        P: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+    {
+        let zero = T::zero();
+        let n = rhs.dim();
+        let m = usize::min(self.params.krylov_gmres_restart, n);
+        let mut vv = vec![NumVector::<T>::new(n); m + 1]; // Krylov basis
+        let mut hh = NumMatrix::<T>::new(m + 1, m); // Hessenberg matrix
+        let mut cs = vec![zero; m]; // Givens rotations (c)
+        let mut sn = vec![zero; m]; // Givens rotations (s)
+        let mut g = vec![zero; m + 1]; // rotated right-hand side
+        let mut y = vec![zero; m];
+        let mut w = NumVector::<T>::new(n);
+        let mut z = NumVector::<T>::new(n);
+        let mut r = NumVector::<T>::new(n);
+        self.residual(&mut r, x, rhs, operator)?;
+        let mut beta = norm(&r);
+        if self.check_convergence(beta) {
+            return Ok(());
+        }
+        loop {
+            // Arnoldi process with modified Gram-Schmidt
+            for i in 0..n {
+                vv[0][i] = r[i] / T::from_real(beta);
+            }
+            g.fill(zero);
+            g[0] = T::from_real(beta);
+            let mut k = 0;
+            for j in 0..m {
+                psolve(&mut z, &vv[j])?;
+                self.mat_vec_mul(&mut w, &z, operator)?;
+                for (i, vi) in vv.iter().enumerate().take(j + 1) {
+                    let hij = dotc(vi, &w);
+                    hh.set(i, j, hij);
+                    for l in 0..n {
+                        w[l] -= hij * vi[l];
+                    }
+                }
+                let h_next = norm(&w);
+                hh.set(j + 1, j, T::from_real(h_next));
+                if h_next > 0.0 {
+                    for l in 0..n {
+                        vv[j + 1][l] = w[l] / T::from_real(h_next);
+                    }
+                }
+
+                // apply the previous rotations to the new column
+                for i in 0..j {
+                    let temp = cs[i].conjugate() * hh.get(i, j) + sn[i].conjugate() * hh.get(i + 1, j);
+                    hh.set(i + 1, j, cs[i] * hh.get(i + 1, j) - sn[i] * hh.get(i, j));
+                    hh.set(i, j, temp);
+                }
+
+                // compute and apply the new rotation
+                let denom = f64::hypot(hh.get(j, j).modulus(), hh.get(j + 1, j).modulus());
+                if denom == 0.0 {
+                    return Err("GMRES breakdown: the matrix is singular");
+                }
+                cs[j] = hh.get(j, j) / T::from_real(denom);
+                sn[j] = hh.get(j + 1, j) / T::from_real(denom);
+                hh.set(j, j, T::from_real(denom));
+                hh.set(j + 1, j, zero);
+                g[j + 1] = zero - sn[j] * g[j];
+                g[j] *= cs[j].conjugate();
+
+                // check convergence
+                k = j + 1;
+                self.iterations += 1;
+                if self.check_convergence(g[j + 1].modulus()) || self.iterations >= self.params.krylov_max_iterations {
+                    break;
+                }
+            }
+
+            // solve the upper triangular system H·y = g and update the solution
+            for i in (0..k).rev() {
+                let mut sum = g[i];
+                for (l, yl) in y.iter().enumerate().take(k).skip(i + 1) {
+                    sum -= hh.get(i, l) * *yl;
+                }
+                y[i] = sum / hh.get(i, i);
+            }
+            w.fill(zero);
+            for i in 0..k {
+                for l in 0..n {
+                    w[l] += y[i] * vv[i][l];
+                }
+            }
+            psolve(&mut z, &w)?;
+            for l in 0..n {
+                x[l] += z[l];
+            }
+            if self.converged || self.iterations >= self.params.krylov_max_iterations {
+                return Ok(());
+            }
+
+            // restart
+            self.residual(&mut r, x, rhs, operator)?;
+            beta = norm(&r);
+            self.residual_norm = beta;
+            if beta <= self.tolerance {
+                self.converged = true;
+                return Ok(());
+            }
+        }
+    }
+
+    /// Implements the biconjugate gradient stabilized method (with right preconditioning)
+    ///
+    /// Reference: Algorithm 7.7 of Saad (2003) Iterative Methods for Sparse Linear Systems,
+    /// 2nd edition, SIAM
+    fn run_bicgstab<F, P>(
+        &mut self,
+        x: &mut NumVector<T>,
+        rhs: &NumVector<T>,
+        operator: &mut F,
+        psolve: &mut P,
+    ) -> Result<(), StrError>
+    where
+        F: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+        P: FnMut(&mut NumVector<T>, &NumVector<T>) -> Result<(), StrError>,
+    {
+        let n = rhs.dim();
+        let mut r = NumVector::<T>::new(n);
+        let mut p = NumVector::<T>::new(n);
+        let mut p_hat = NumVector::<T>::new(n);
+        let mut s_hat = NumVector::<T>::new(n);
+        let mut v = NumVector::<T>::new(n);
+        let mut s = NumVector::<T>::new(n);
+        let mut t = NumVector::<T>::new(n);
+        self.residual(&mut r, x, rhs, operator)?;
+        if self.check_convergence(norm(&r)) {
+            return Ok(());
+        }
+        let r_hat = r.clone();
+        let one = T::one();
+        let (mut rho, mut alpha, mut omega) = (one, one, one);
+        while self.iterations < self.params.krylov_max_iterations {
+            let rho_new = dotc(&r_hat, &r);
+            if rho_new.modulus() == 0.0 {
+                return Err("BiCGStab breakdown: rho = 0");
+            }
+            let beta = (rho_new / rho) * (alpha / omega);
+            for i in 0..n {
+                p[i] = r[i] + beta * (p[i] - omega * v[i]);
+            }
+            psolve(&mut p_hat, &p)?;
+            self.mat_vec_mul(&mut v, &p_hat, operator)?;
+            let r_hat_v = dotc(&r_hat, &v);
+            if r_hat_v.modulus() == 0.0 {
+                return Err("BiCGStab breakdown: (r̂, v) = 0");
+            }
+            alpha = rho_new / r_hat_v;
+            for i in 0..n {
+                s[i] = r[i] - alpha * v[i];
+            }
+            self.iterations += 1;
+            let s_norm = norm(&s);
+            if s_norm <= self.tolerance {
+                for i in 0..n {
+                    x[i] += alpha * p_hat[i];
+                }
+                self.check_convergence(s_norm);
+                return Ok(());
+            }
+            psolve(&mut s_hat, &s)?;
+            self.mat_vec_mul(&mut t, &s_hat, operator)?;
+            let tt = dotc(&t, &t);
+            if tt.modulus() == 0.0 {
+                return Err("BiCGStab breakdown: (t, t) = 0");
+            }
+            omega = dotc(&t, &s) / tt;
+            for i in 0..n {
+                x[i] += alpha * p_hat[i] + omega * s_hat[i];
+                r[i] = s[i] - omega * t[i];
+            }
+            if self.check_convergence(norm(&r)) {
+                return Ok(());
+            }
+            if omega.modulus() == 0.0 {
+                return Err("BiCGStab breakdown: omega = 0");
+            }
+            rho = rho_new;
+        }
+        Ok(())
+    }
+}
+
+impl NumSolverKrylov<f64> {
+    /// Computes the solution of the linear system given by a linear operator (matrix-free)
+    ///
+    /// Same as [NumSolverKrylov::solve_with_operator] but with the operator given by [LinearOperator],
+    /// which is implemented, for instance, by [russell_lab::Matrix], [crate::CooMatrix], [crate::CscMatrix],
+    /// [crate::CsrMatrix], and by the compositions [russell_lab::OperatorSum], [russell_lab::OperatorProduct], etc.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_lab::{vec_approx_eq, OperatorScaled, OperatorSum, Vector};
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // A = 2·I + L, where L is the tridiagonal matrix with 2 on the diagonal and -1 off the diagonal
+    ///     let n = 4;
+    ///     let mut identity = CooMatrix::new(n, n, n, Sym::No)?;
+    ///     let mut ll = CooMatrix::new(n, n, 3 * n, Sym::No)?;
+    ///     for i in 0..n {
+    ///         identity.put(i, i, 1.0)?;
+    ///         ll.put(i, i, 2.0)?;
+    ///         if i > 0 {
+    ///             ll.put(i, i - 1, -1.0)?;
+    ///         }
+    ///         if i < n - 1 {
+    ///             ll.put(i, i + 1, -1.0)?;
+    ///         }
+    ///     }
+    ///     let aa = OperatorSum::new(1.0, OperatorScaled::new(2.0, &identity), 1.0, &ll)?;
+    ///     let rhs = Vector::from(&[3.0, 2.0, 2.0, 3.0]);
+    ///     let mut x = Vector::new(n);
+    ///     let mut solver = SolverKrylov::new(Genie::Cg)?;
+    ///     solver.solve_with_linear_operator(&mut x, &rhs, None, &aa)?;
+    ///     vec_approx_eq(&x, &[1.0, 1.0, 1.0, 1.0], 1e-14);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn solve_with_linear_operator(
+        &mut self,
+        x: &mut Vector,
+        rhs: &Vector,
+        params: Option<LinSolParams>,
+        operator: &dyn LinearOperator,
+    ) -> Result<(), StrError> {
+        let (nrow, ncol) = operator.dims();
+        if nrow != ncol || x.dim() != nrow {
+            return Err("the operator must be square with dimension equal to the dimension of x");
+        }
+        self.solve_with_operator(x, rhs, params, |v, u| operator.apply(v, u))
+    }
+}
+
+impl LinSolTrait for NumSolverKrylov<f64> {
+    /// Checks the matrix, sets up the preconditioner (if any), and saves the parameters
+    ///
+    /// **Note:** No factorization is performed, except for the incomplete factorizations of some preconditioners.
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (COO, CSC, or CSR). The matrix must be square.
+    ///   If only COO is available, a CSR matrix is computed (or updated) for faster matrix-vector products.
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// # Notes
+    ///
+    /// 1. The structure of the matrix (nrow, ncol, nnz, sym) must be
+    ///    exactly the same among multiple calls to `factorize`. The values may differ
+    ///    from call to call, nonetheless.
+    /// 2. The first call to `factorize` will define the structure which must be
+    ///    kept the same for the next calls.
+    fn factorize(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        self.initialize_and_setup(mat, params)
+    }
+
+    /// Computes the solution of the linear system
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///   (also the initial guess if [LinSolParams::krylov_nonzero_initial_guess])
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows the number of iterations and the final residual norm
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve(&mut self, x: &mut Vector, mat: &SparseMatrix, rhs: &Vector, verbose: bool) -> Result<(), StrError> {
+        self.solve_with_matrix(x, mat, rhs, verbose)
+    }
+
+    /// Updates the stats structure (should be called after solve)
+    fn update_stats(&self, stats: &mut StatsLinSol) {
+        self.update_stats_iterative(stats);
+    }
+
+    /// Returns the nanoseconds spent on initialize
+    fn get_ns_init(&self) -> u128 {
+        self.time_initialize_ns
+    }
+
+    /// Returns the nanoseconds spent on factorize
+    fn get_ns_fact(&self) -> u128 {
+        self.time_factorize_ns
+    }
+
+    /// Returns the nanoseconds spent on solve
+    fn get_ns_solve(&self) -> u128 {
+        self.time_solve_ns
+    }
+}
+
+impl ComplexLinSolTrait for NumSolverKrylov<Complex64> {
+    /// Checks the matrix, sets up the preconditioner (if any), and saves the parameters
+    ///
+    /// **Note:** No factorization is performed, except for the incomplete factorizations of some preconditioners.
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (COO, CSC, or CSR). The matrix must be square.
+    ///   If only COO is available, a CSR matrix is computed (or updated) for faster matrix-vector products.
+    ///   CG and MINRES (Hermitian matrices) require [Sym::No] or [Sym::YesFull].
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// # Notes
+    ///
+    /// 1. The structure of the matrix (nrow, ncol, nnz, sym) must be
+    ///    exactly the same among multiple calls to `factorize`. The values may differ
+    ///    from call to call, nonetheless.
+    /// 2. The first call to `factorize` will define the structure which must be
+    ///    kept the same for the next calls.
+    fn factorize(&mut self, mat: &mut ComplexSparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        let (_, _, _, sym) = mat.get_info();
+        if sym.triangular() && (self.genie == Genie::Cg || self.genie == Genie::Minres) {
+            return Err("CG and MINRES require a Hermitian matrix with Sym::No or Sym::YesFull");
+        }
+        self.initialize_and_setup(mat, params)
+    }
+
+    /// Computes the solution of the linear system
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///   (also the initial guess if [LinSolParams::krylov_nonzero_initial_guess])
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows the number of iterations and the final residual norm
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve(
+        &mut self,
+        x: &mut ComplexVector,
+        mat: &ComplexSparseMatrix,
+        rhs: &ComplexVector,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_with_matrix(x, mat, rhs, verbose)
+    }
+
+    /// Updates the stats structure (should be called after solve)
+    fn update_stats(&self, stats: &mut StatsLinSol) {
+        self.update_stats_iterative(stats);
+    }
+
+    /// Returns the nanoseconds spent on initialize
+    fn get_ns_init(&self) -> u128 {
+        self.time_initialize_ns
+    }
+
+    /// Returns the nanoseconds spent on factorize
+    fn get_ns_fact(&self) -> u128 {
+        self.time_factorize_ns
+    }
+
+    /// Returns the nanoseconds spent on solve
+    fn get_ns_solve(&self) -> u128 {
+        self.time_solve_ns
+    }
+}
+
+/// Computes the inner product uᴴ·v (uᵀ·v if real)
+fn dotc<T: FactorScalar>(u: &NumVector<T>, v: &NumVector<T>) -> T {
+    let mut sum = T::zero();
+    for i in 0..u.dim() {
+        sum += u[i].conjugate() * v[i];
+    }
+    sum
+}
+
+/// Computes the (unconjugated) bilinear form uᵀ·v
+fn dotu<T: FactorScalar>(u: &NumVector<T>, v: &NumVector<T>) -> T {
+    let mut sum = T::zero();
+    for i in 0..u.dim() {
+        sum += u[i] * v[i];
+    }
+    sum
+}
+
+/// Computes the Euclidean norm ‖u‖ = √(uᴴ·u)
+fn norm<T: FactorScalar>(u: &NumVector<T>) -> f64 {
+    f64::sqrt(dotc(u, u).real())
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{ComplexCooMatrix, ComplexLinSolTrait, ComplexSolverKrylov, ComplexSparseMatrix, CooMatrix, CscMatrix};
+    use crate::{CsrMatrix, Genie, LinSolParams, LinSolTrait, Precond, Preconditioner, Samples, SolverKrylov};
+    use crate::{SparseMatrix, StatsLinSol, Sym};
+    use russell_lab::{complex_vec_approx_eq, cpx, vec_approx_eq, Complex64, ComplexVector, LinearOperator, Matrix};
+    use russell_lab::{OperatorScaled, OperatorSum, Vector};
+
+    /// Returns the (n,n) tridiagonal matrix with 2 on the diagonal and -1 off the diagonal
+    fn tridiagonal(n: usize, sym: Sym) -> CooMatrix {
+        let mut coo = CooMatrix::new(n, n, 3 * n, sym).unwrap();
+        for i in 0..n {
+            coo.put(i, i, 2.0).unwrap();
+            if i > 0 && sym != Sym::YesUpper {
+                coo.put(i, i - 1, -1.0).unwrap();
+            }
+            if i < n - 1 && sym != Sym::YesLower {
+                coo.put(i, i + 1, -1.0).unwrap();
+            }
+        }
+        coo
+    }
+
+    /// Returns the matrix of the 2D Poisson problem on an (m × m) grid (5-point stencil)
+    fn poisson_2d(m: usize) -> CooMatrix {
+        let n = m * m;
+        let mut coo = CooMatrix::new(n, n, 5 * n, Sym::No).unwrap();
+        for i in 0..m {
+            for j in 0..m {
+                let k = i * m + j;
+                coo.put(k, k, 4.0).unwrap();
+                if i > 0 {
+                    coo.put(k, k - m, -1.0).unwrap();
+                }
+                if i + 1 < m {
+                    coo.put(k, k + m, -1.0).unwrap();
+                }
+                if j > 0 {
+                    coo.put(k, k - 1, -1.0).unwrap();
+                }
+                if j + 1 < m {
+                    coo.put(k, k + 1, -1.0).unwrap();
+                }
+            }
+        }
+        coo
+    }
+
+    #[test]
+    fn new_captures_errors() {
+        assert_eq!(
+            SolverKrylov::new(Genie::Umfpack).err(),
+            Some("the genie must be an iterative solver (BiCgStab, Cg, Cocg, Gmres, or Minres)")
+        );
+    }
+
+    #[test]
+    fn new_works() {
+        let solver = SolverKrylov::new(Genie::Cg).unwrap();
+        assert_eq!(solver.genie, Genie::Cg);
+        assert!(!solver.initialized);
+        assert!(!solver.get_converged());
+        assert_eq!(solver.get_iterations(), 0);
+        assert_eq!(solver.get_history().len(), 0);
+    }
+
+    #[test]
+    fn factorize_handles_errors() {
+        let mut solver = SolverKrylov::new(Genie::Gmres).unwrap();
+        let mut mat = SparseMatrix::new_coo(1, 2, 2, Sym::No).unwrap();
+        mat.put(0, 0, 1.0).unwrap();
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("the matrix must be square")
+        );
+        let mut mat = SparseMatrix::from_coo(tridiagonal(3, Sym::No));
+        solver.factorize(&mut mat, None).unwrap();
+        assert!(solver.get_ns_init() > 0);
+        let mut mat = SparseMatrix::from_coo(tridiagonal(3, Sym::YesFull));
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (symmetric differs)")
+        );
+        let mut mat = SparseMatrix::from_coo(tridiagonal(4, Sym::No));
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (ndim differs)")
+        );
+    }
+
+    #[test]
+    fn solve_handles_errors() {
+        let mut solver = SolverKrylov::new(Genie::Cg).unwrap();
+        let mut mat = SparseMatrix::from_coo(tridiagonal(3, Sym::No));
+        let mut x = Vector::new(3);
+        let rhs = Vector::new(3);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false).err(),
+            Some("the function factorize must be called before solve")
+        );
+        solver.factorize(&mut mat, None).unwrap();
+        let mut x_wrong = Vector::new(2);
+        assert_eq!(
+            solver.solve(&mut x_wrong, &mat, &rhs, false).err(),
+            Some("the dimension of the vector of unknown values x is incorrect")
+        );
+        let rhs_wrong = Vector::new(2);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs_wrong, false).err(),
+            Some("the dimension of the right-hand side vector is incorrect")
+        );
+        let mat_wrong = SparseMatrix::from_coo(tridiagonal(4, Sym::No));
+        assert_eq!(
+            solver.solve(&mut x, &mat_wrong, &rhs, false).err(),
+            Some("solve must use the same matrix (ndim differs)")
+        );
+        let mut params = LinSolParams::new();
+        params.krylov_max_iterations = 0;
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false).err(),
+            Some("the maximum number of iterations must be ≥ 1")
+        );
+    }
+
+    #[test]
+    fn solve_works_with_zero_rhs() {
+        for genie in [Genie::BiCgStab, Genie::Cg, Genie::Gmres, Genie::Minres] {
+            let mut solver = SolverKrylov::new(genie).unwrap();
+            let mut mat = SparseMatrix::from_coo(tridiagonal(3, Sym::No));
+            let mut x = Vector::from(&[1.0, 2.0, 3.0]);
+            let rhs = Vector::new(3);
+            solver.factorize(&mut mat, None).unwrap();
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            vec_approx_eq(&x, &[0.0, 0.0, 0.0], 1e-15);
+            assert_eq!(solver.get_iterations(), 0);
+            assert!(solver.get_converged());
+        }
+    }
+
+    #[test]
+    fn solve_works_symmetric() {
+        // the solution of the 1D Poisson problem with u(0) = u(n+1) = 1 is u = 1
+        let n = 10;
+        let mut rhs = Vector::new(n);
+        rhs[0] = 1.0;
+        rhs[n - 1] = 1.0;
+        let x_correct = vec![1.0; n];
+        for sym in [Sym::No, Sym::YesFull, Sym::YesLower, Sym::YesUpper] {
+            for genie in [Genie::BiCgStab, Genie::Cg, Genie::Cocg, Genie::Gmres, Genie::Minres] {
+                let mut solver = SolverKrylov::new(genie).unwrap();
+                let mut mat = SparseMatrix::from_coo(tridiagonal(n, sym));
+                let mut x = Vector::new(n);
+                solver.factorize(&mut mat, None).unwrap();
+                solver.solve(&mut x, &mat, &rhs, false).unwrap();
+                vec_approx_eq(&x, &x_correct, 1e-10);
+                assert!(solver.get_converged());
+                assert!(solver.get_iterations() <= n);
+                assert_eq!(solver.get_history().len(), solver.get_iterations() + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn solve_works_unsymmetric() {
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let rhs = Vector::from(&[8.0, 45.0, -3.0, 3.0, 19.0]);
+        let x_correct = &[1.0, 2.0, 3.0, 4.0, 5.0];
+        for (genie, num_threads) in [(Genie::BiCgStab, 1), (Genie::Gmres, 1), (Genie::Gmres, 3)] {
+            let mut solver = SolverKrylov::new(genie).unwrap();
+            let mut mat = SparseMatrix::from_coo(coo.clone());
+            let mut x = Vector::new(5);
+            let mut params = LinSolParams::new();
+            params.krylov_rel_tol = 1e-12;
+            params.krylov_num_threads = num_threads;
+            solver.factorize(&mut mat, Some(params)).unwrap();
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            vec_approx_eq(&x, x_correct, 1e-10);
+            let mut stats = StatsLinSol::new();
+            solver.update_stats(&mut stats);
+            assert!(stats.iterative.converged);
+            assert_eq!(stats.iterative.iterations, solver.get_iterations());
+            assert!(stats.iterative.residual_norm <= stats.iterative.tolerance);
+        }
+    }
+
+    #[test]
+    fn gmres_restart_works() {
+        // unsymmetric matrix with positive-definite symmetric part (GMRES(m) converges for any m ≥ 1)
+        let n = 20;
+        let mut coo = CooMatrix::new(n, n, 3 * n, Sym::No).unwrap();
+        for i in 0..n {
+            coo.put(i, i, 4.0).unwrap();
+            if i > 0 {
+                coo.put(i, i - 1, -1.5).unwrap();
+            }
+            if i < n - 1 {
+                coo.put(i, i + 1, -0.5).unwrap();
+            }
+        }
+        let mut mat = SparseMatrix::from_coo(coo);
+        let x_correct = Vector::filled(n, 1.0);
+        let mut rhs = Vector::new(n);
+        mat.mat_vec_mul(&mut rhs, 1.0, &x_correct).unwrap();
+        let mut x = Vector::new(n);
+        let mut params = LinSolParams::new();
+        params.krylov_gmres_restart = 3;
+        let mut solver = SolverKrylov::new(Genie::Gmres).unwrap();
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, x_correct.as_data(), 1e-9);
+        assert!(solver.get_iterations() > 3);
+    }
+
+    #[test]
+    fn solve_captures_non_convergence() {
+        let n = 20;
+        let mut mat = SparseMatrix::from_coo(tridiagonal(n, Sym::YesFull));
+        let rhs = Vector::filled(n, 1.0);
+        let mut x = Vector::new(n);
+        let mut params = LinSolParams::new();
+        params.krylov_max_iterations = 2;
+        let mut solver = SolverKrylov::new(Genie::Cg).unwrap();
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false).err(),
+            Some("the iterative solver did not converge (max number of iterations reached)")
+        );
+        assert!(!solver.get_converged());
+        assert_eq!(solver.get_iterations(), 2);
+        assert_eq!(solver.get_history().len(), 3);
+    }
+
+    #[test]
+    fn cg_captures_indefinite_matrix() {
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::YesFull).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 1, -1.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let rhs = Vector::from(&[1.0, 1.0]);
+        let mut x = Vector::new(2);
+        let mut solver = SolverKrylov::new(Genie::Cg).unwrap();
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false).err(),
+            Some("CG breakdown: the matrix is not positive-definite")
+        );
+        // MINRES works with indefinite matrices
+        let mut solver = SolverKrylov::new(Genie::Minres).unwrap();
+        solver.factorize(&mut mat, None).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &[1.0, -1.0], 1e-14);
+    }
+
+    #[test]
+    fn nonzero_initial_guess_works() {
+        let n = 5;
+        let mut mat = SparseMatrix::from_coo(tridiagonal(n, Sym::YesFull));
+        let mut rhs = Vector::new(n);
+        rhs[0] = 1.0;
+        rhs[n - 1] = 1.0;
+        let mut x = Vector::filled(n, 1.0);
+        let mut params = LinSolParams::new();
+        params.krylov_nonzero_initial_guess = true;
+        let mut solver = SolverKrylov::new(Genie::Cg).unwrap();
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        assert_eq!(solver.get_iterations(), 0);
+        assert_eq!(solver.get_mat_vec_mul_count(), 1);
+        vec_approx_eq(&x, &[1.0, 1.0, 1.0, 1.0, 1.0], 1e-15);
+    }
+
+    #[test]
+    fn solve_with_operator_works() {
+        let n = 50;
+        let mut rhs = Vector::new(n);
+        rhs[0] = 1.0;
+        rhs[n - 1] = 1.0;
+        let x_correct = vec![1.0; n];
+        for genie in [Genie::BiCgStab, Genie::Cg, Genie::Gmres, Genie::Minres] {
+            let mut params = LinSolParams::new();
+            params.krylov_gmres_restart = 60;
+            let mut x = Vector::new(n);
+            let mut solver = SolverKrylov::new(genie).unwrap();
+            solver
+                .solve_with_operator(&mut x, &rhs, Some(params), |v, u| {
+                    for i in 0..n {
+                        v[i] = 2.0 * u[i];
+                        if i > 0 {
+                            v[i] -= u[i - 1];
+                        }
+                        if i < n - 1 {
+                            v[i] -= u[i + 1];
+                        }
+                    }
+                    Ok(())
+                })
+                .unwrap();
+            vec_approx_eq(&x, &x_correct, 1e-9);
+            assert!(solver.get_residual_norm() <= 1e-10 * f64::sqrt(2.0));
+        }
+    }
+
+    #[test]
+    fn solve_with_linear_operator_works() {
+        let m = 8;
+        let n = m * m;
+        let coo = poisson_2d(m);
+        let csr = CsrMatrix::from_coo(&coo).unwrap();
+        let csc = CscMatrix::from_coo(&coo).unwrap();
+        let x_correct = Vector::filled(n, 1.0);
+        let mut rhs = Vector::new(n);
+        coo.mat_vec_mul(&mut rhs, 1.0, &x_correct).unwrap();
+        let mut solver = SolverKrylov::new(Genie::Cg).unwrap();
+        let mut x = Vector::new(n);
+        let rectangular = Matrix::new(n, 1);
+        assert_eq!(
+            solver
+                .solve_with_linear_operator(&mut x, &rhs, None, &rectangular)
+                .err(),
+            Some("the operator must be square with dimension equal to the dimension of x")
+        );
+        let operators: [&dyn LinearOperator; 3] = [&coo, &csr, &csc];
+        for op in operators {
+            solver.solve_with_linear_operator(&mut x, &rhs, None, op).unwrap();
+            vec_approx_eq(&x, &x_correct, 1e-9);
+        }
+        // composition: (2·A - A)·x = rhs
+        let twice = OperatorScaled::new(2.0, &csr);
+        let op = OperatorSum::new(1.0, twice, -1.0, &coo).unwrap();
+        solver.solve_with_linear_operator(&mut x, &rhs, None, &op).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-9);
+    }
+
+    #[test]
+    fn preconditioners_work() {
+        let m = 16;
+        let n = m * m;
+        let mut mat = SparseMatrix::from_coo(poisson_2d(m));
+        let x_correct = Vector::filled(n, 1.0);
+        let mut rhs = Vector::new(n);
+        mat.mat_vec_mul(&mut rhs, 1.0, &x_correct).unwrap();
+
+        // reference: no preconditioner
+        let mut solver = SolverKrylov::new(Genie::Cg).unwrap();
+        let mut x = Vector::new(n);
+        solver.factorize(&mut mat, None).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        let iterations_no_precond = solver.get_iterations();
+
+        // all methods with all preconditioners
+        for genie in [Genie::BiCgStab, Genie::Cg, Genie::Gmres, Genie::Minres] {
+            for precond in [
+                Precond::Amg,
+                Precond::Ic0,
+                Precond::Ilu0,
+                Precond::Ilut,
+                Precond::Jacobi,
+                Precond::Ssor,
+            ] {
+                let mut params = LinSolParams::new();
+                params.krylov_precond = precond;
+                params.precond_amg_coarse_size = 20;
+                params.krylov_gmres_restart = 50;
+                let mut solver = SolverKrylov::new(genie).unwrap();
+                let mut x = Vector::new(n);
+                solver.factorize(&mut mat, Some(params)).unwrap();
+                solver.solve(&mut x, &mat, &rhs, false).unwrap();
+                vec_approx_eq(&x, x_correct.as_data(), 1e-8);
+                if genie == Genie::Cg && precond != Precond::Jacobi {
+                    // (Jacobi does not help because the diagonal is constant)
+                    assert!(solver.get_iterations() < iterations_no_precond);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reuse_preconditioner_works() {
+        let m = 16;
+        let n = m * m;
+        let mut mat = SparseMatrix::from_coo(poisson_2d(m));
+        let x_correct = Vector::filled(n, 1.0);
+        let mut rhs = Vector::new(n);
+        let mut params = LinSolParams::new();
+        params.krylov_precond = Precond::Amg;
+        params.krylov_precond_reuse = true;
+        params.precond_amg_coarse_size = 20;
+        let mut solver = SolverKrylov::new(Genie::Cg).unwrap();
+        let mut x = Vector::new(n);
+        let mut iterations = Vec::new();
+        for factor in [1.0, 2.0, 5.0] {
+            // only the values change
+            if factor > 1.0 {
+                let coo = mat.get_coo_mut().unwrap();
+                coo.values.iter_mut().for_each(|v| *v *= factor);
+            }
+            solver.factorize(&mut mat, Some(params)).unwrap();
+            mat.mat_vec_mul(&mut rhs, 1.0, &x_correct).unwrap();
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            vec_approx_eq(&x, &x_correct, 1e-8);
+            iterations.push(solver.get_iterations());
+        }
+        // scaling A does not change the preconditioned iterations
+        assert_eq!(iterations[1], iterations[0]);
+        assert_eq!(iterations[2], iterations[0]);
+    }
+
+    #[test]
+    fn exact_preconditioners_converge_in_one_iteration() {
+        // IC(0) and ILU(0) are exact for tridiagonal matrices
+        let n = 10;
+        let mut rhs = Vector::new(n);
+        rhs[0] = 1.0;
+        rhs[n - 1] = 1.0;
+        for (genie, precond) in [
+            (Genie::BiCgStab, Precond::Ilu0),
+            (Genie::Cg, Precond::Ic0),
+            (Genie::Gmres, Precond::Ilu0),
+            (Genie::Minres, Precond::Ic0),
+        ] {
+            let mut params = LinSolParams::new();
+            params.krylov_precond = precond;
+            let mut solver = SolverKrylov::new(genie).unwrap();
+            let mut mat = SparseMatrix::from_coo(tridiagonal(n, Sym::YesLower));
+            let mut x = Vector::new(n);
+            solver.factorize(&mut mat, Some(params)).unwrap();
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            vec_approx_eq(&x, &vec![1.0; n], 1e-13);
+            assert_eq!(solver.get_iterations(), 1);
+
+            // the preconditioner is removed by factorize with Precond::No
+            solver.factorize(&mut mat, None).unwrap();
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            assert!(solver.get_iterations() > 1);
+        }
+    }
+
+    #[test]
+    fn set_preconditioner_works() {
+        let n = 10;
+        let mut rhs = Vector::new(n);
+        rhs[0] = 1.0;
+        rhs[n - 1] = 1.0;
+        let mat = SparseMatrix::from_coo(tridiagonal(n, Sym::No));
+        let mut precond = Preconditioner::new(Precond::Ilu0, None).unwrap();
+        precond.setup(&mat).unwrap();
+        let mut solver = SolverKrylov::new(Genie::Gmres).unwrap();
+        solver.set_preconditioner(Some(precond));
+        let mut x = Vector::new(n);
+        solver
+            .solve_with_operator(&mut x, &rhs, None, |v, u| mat.mat_vec_mul(v, 1.0, u))
+            .unwrap();
+        vec_approx_eq(&x, &vec![1.0; n], 1e-13);
+        assert_eq!(solver.get_iterations(), 1);
+        solver.set_preconditioner(None);
+        solver
+            .solve_with_operator(&mut x, &rhs, None, |v, u| mat.mat_vec_mul(v, 1.0, u))
+            .unwrap();
+        assert!(solver.get_iterations() > 1);
+    }
+
+    /// Returns the (n,n) tridiagonal matrix with d on the diagonal and -1 off the diagonal
+    ///
+    /// The matrix is complex symmetric (and Hermitian if d is real).
+    fn complex_tridiagonal(n: usize, d: Complex64, sym: Sym) -> ComplexCooMatrix {
+        let mut coo = ComplexCooMatrix::new(n, n, 3 * n, sym).unwrap();
+        for i in 0..n {
+            coo.put(i, i, d).unwrap();
+            if i > 0 && sym != Sym::YesUpper {
+                coo.put(i, i - 1, cpx!(-1.0, 0.0)).unwrap();
+            }
+            if i < n - 1 && sym != Sym::YesLower {
+                coo.put(i, i + 1, cpx!(-1.0, 0.0)).unwrap();
+            }
+        }
+        coo
+    }
+
+    /// Returns the matrix of the 2D Helmholtz problem `-∇²u - k²u = f` on an (m × m) grid
+    ///
+    /// The 5-point stencil is used with grid spacing h = 1/(m+1) and a complex shift (damping)
+    /// such that the matrix is complex symmetric (but not Hermitian) and indefinite (real part).
+    fn complex_helmholtz_2d(m: usize, kh: f64, damping: f64) -> ComplexCooMatrix {
+        let n = m * m;
+        let mut coo = ComplexCooMatrix::new(n, n, 5 * n, Sym::No).unwrap();
+        let d = cpx!(4.0 - kh * kh, damping);
+        for i in 0..m {
+            for j in 0..m {
+                let k = i * m + j;
+                coo.put(k, k, d).unwrap();
+                if i > 0 {
+                    coo.put(k, k - m, cpx!(-1.0, 0.0)).unwrap();
+                }
+                if i + 1 < m {
+                    coo.put(k, k + m, cpx!(-1.0, 0.0)).unwrap();
+                }
+                if j > 0 {
+                    coo.put(k, k - 1, cpx!(-1.0, 0.0)).unwrap();
+                }
+                if j + 1 < m {
+                    coo.put(k, k + 1, cpx!(-1.0, 0.0)).unwrap();
+                }
+            }
+        }
+        coo
+    }
+
+    /// Returns a Hermitian (n,n) tridiagonal matrix with d on the diagonal and ±i off the diagonal
+    fn complex_hermitian(n: usize, d: f64) -> ComplexCooMatrix {
+        let mut coo = ComplexCooMatrix::new(n, n, 3 * n, Sym::YesFull).unwrap();
+        for i in 0..n {
+            coo.put(i, i, cpx!(d, 0.0)).unwrap();
+            if i > 0 {
+                coo.put(i, i - 1, cpx!(0.0, -1.0)).unwrap();
+            }
+            if i < n - 1 {
+                coo.put(i, i + 1, cpx!(0.0, 1.0)).unwrap();
+            }
+        }
+        coo
+    }
+
+    /// Returns the right-hand side such that the solution is x[i] = (1 + i, 1 - i)
+    fn complex_rhs_and_solution(mat: &ComplexSparseMatrix) -> (ComplexVector, ComplexVector) {
+        let n = mat.get_info().0;
+        let x_correct = ComplexVector::initialized(n, |i| cpx!(1.0 + i as f64 / n as f64, 1.0 - i as f64 / n as f64));
+        let mut rhs = ComplexVector::new(n);
+        mat.mat_vec_mul(&mut rhs, cpx!(1.0, 0.0), &x_correct).unwrap();
+        (rhs, x_correct)
+    }
+
+    #[test]
+    fn new_captures_errors_complex() {
+        assert_eq!(
+            ComplexSolverKrylov::new(Genie::Native).err(),
+            Some("the genie must be an iterative solver (BiCgStab, Cg, Cocg, Gmres, or Minres)")
+        );
+    }
+
+    #[test]
+    fn new_works_complex() {
+        let solver = ComplexSolverKrylov::new(Genie::Cocg).unwrap();
+        assert_eq!(solver.genie, Genie::Cocg);
+        assert!(!solver.initialized);
+        assert!(!solver.get_converged());
+        assert_eq!(solver.get_iterations(), 0);
+        assert_eq!(solver.get_history().len(), 0);
+    }
+
+    #[test]
+    fn factorize_handles_errors_complex() {
+        let d = cpx!(2.0, 1.0);
+        let mut solver = ComplexSolverKrylov::new(Genie::Gmres).unwrap();
+        let mut mat = ComplexSparseMatrix::new_coo(1, 2, 2, Sym::No).unwrap();
+        mat.put(0, 0, d).unwrap();
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("the matrix must be square")
+        );
+        let mut mat = ComplexSparseMatrix::from_coo(complex_tridiagonal(3, d, Sym::No));
+        solver.factorize(&mut mat, None).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(complex_tridiagonal(3, d, Sym::YesFull));
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (symmetric differs)")
+        );
+        let mut mat = ComplexSparseMatrix::from_coo(complex_tridiagonal(4, d, Sym::No));
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (ndim differs)")
+        );
+        let mut params = LinSolParams::new();
+        params.krylov_precond = Precond::Jacobi;
+        let mut mat = ComplexSparseMatrix::from_coo(complex_tridiagonal(3, cpx!(0.0, 0.0), Sym::No));
+        assert_eq!(
+            solver.factorize(&mut mat, Some(params)).err(),
+            Some("the Jacobi preconditioner requires non-zero diagonal entries")
+        );
+        for genie in [Genie::Cg, Genie::Minres] {
+            let mut solver = ComplexSolverKrylov::new(genie).unwrap();
+            let mut mat = ComplexSparseMatrix::from_coo(complex_tridiagonal(3, d, Sym::YesLower));
+            assert_eq!(
+                solver.factorize(&mut mat, None).err(),
+                Some("CG and MINRES require a Hermitian matrix with Sym::No or Sym::YesFull")
+            );
+        }
+    }
+
+    #[test]
+    fn solve_handles_errors_complex() {
+        let mut solver = ComplexSolverKrylov::new(Genie::Cocg).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(complex_tridiagonal(3, cpx!(2.0, 1.0), Sym::No));
+        let mut x = ComplexVector::new(3);
+        let rhs = ComplexVector::new(3);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false).err(),
+            Some("the function factorize must be called before solve")
+        );
+        solver.factorize(&mut mat, None).unwrap();
+        let mut x_wrong = ComplexVector::new(2);
+        assert_eq!(
+            solver.solve(&mut x_wrong, &mat, &rhs, false).err(),
+            Some("the dimension of the vector of unknown values x is incorrect")
+        );
+        let rhs_wrong = ComplexVector::new(2);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs_wrong, false).err(),
+            Some("the dimension of the right-hand side vector is incorrect")
+        );
+        let mut params = LinSolParams::new();
+        params.krylov_max_iterations = 0;
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false).err(),
+            Some("the maximum number of iterations must be ≥ 1")
+        );
+    }
+
+    #[test]
+    fn solve_works_with_zero_rhs_complex() {
+        for genie in [Genie::BiCgStab, Genie::Cg, Genie::Cocg, Genie::Gmres, Genie::Minres] {
+            let mut solver = ComplexSolverKrylov::new(genie).unwrap();
+            let mut mat = ComplexSparseMatrix::from_coo(complex_tridiagonal(3, cpx!(2.0, 0.0), Sym::No));
+            let mut x = ComplexVector::from(&[cpx!(1.0, 1.0), cpx!(2.0, 2.0), cpx!(3.0, 3.0)]);
+            let rhs = ComplexVector::new(3);
+            solver.factorize(&mut mat, None).unwrap();
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            let zero = cpx!(0.0, 0.0);
+            complex_vec_approx_eq(&x, &[zero, zero, zero], 1e-15);
+            assert_eq!(solver.get_iterations(), 0);
+            assert!(solver.get_converged());
+        }
+    }
+
+    #[test]
+    fn solve_works_complex_symmetric() {
+        let n = 20;
+        for sym in [Sym::No, Sym::YesFull, Sym::YesLower, Sym::YesUpper] {
+            for genie in [Genie::BiCgStab, Genie::Cocg, Genie::Gmres] {
+                let mut solver = ComplexSolverKrylov::new(genie).unwrap();
+                let mut mat = ComplexSparseMatrix::from_coo(complex_tridiagonal(n, cpx!(2.0, 0.5), sym));
+                let (rhs, x_correct) = complex_rhs_and_solution(&mat);
+                let mut x = ComplexVector::new(n);
+                solver.factorize(&mut mat, None).unwrap();
+                solver.solve(&mut x, &mat, &rhs, false).unwrap();
+                complex_vec_approx_eq(&x, x_correct.as_data(), 1e-9);
+                assert!(solver.get_converged());
+                assert!(solver.get_iterations() <= n);
+                assert_eq!(solver.get_history().len(), solver.get_iterations() + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn solve_works_hermitian_complex() {
+        let n = 20;
+        for (genie, d) in [(Genie::Cg, 3.0), (Genie::Minres, 3.0), (Genie::Minres, 0.5)] {
+            // d = 0.5 yields an indefinite matrix (the eigenvalues are d + 2·cos(kπ/(n+1)))
+            let mut solver = ComplexSolverKrylov::new(genie).unwrap();
+            let mut mat = ComplexSparseMatrix::from_coo(complex_hermitian(n, d));
+            let (rhs, x_correct) = complex_rhs_and_solution(&mat);
+            let mut x = ComplexVector::new(n);
+            solver.factorize(&mut mat, None).unwrap();
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            complex_vec_approx_eq(&x, x_correct.as_data(), 1e-9);
+            assert!(solver.get_iterations() <= n);
+        }
+        // CG fails with an indefinite matrix
+        let mut solver = ComplexSolverKrylov::new(Genie::Cg).unwrap();
+        let mut mat = ComplexSparseMatrix::from_coo(complex_hermitian(n, 0.5));
+        let (rhs, _) = complex_rhs_and_solution(&mat);
+        let mut x = ComplexVector::new(n);
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false).err(),
+            Some("CG breakdown: the matrix is not positive-definite")
+        );
+    }
+
+    #[test]
+    fn solve_works_unsymmetric_complex() {
+        let (coo, _, _, _) = Samples::complex_symmetric_3x3_full();
+        let mut unsymmetric = ComplexCooMatrix::new(3, 3, 9, Sym::No).unwrap();
+        let a = coo.as_dense();
+        for i in 0..3 {
+            for j in 0..3 {
+                let aij = a.get(i, j) + if j > i { cpx!(0.5, 0.25) } else { cpx!(0.0, 0.0) };
+                if aij.norm() > 0.0 {
+                    unsymmetric.put(i, j, aij).unwrap();
+                }
+            }
+        }
+        for (genie, num_threads) in [(Genie::BiCgStab, 1), (Genie::Gmres, 1), (Genie::Gmres, 2)] {
+            let mut solver = ComplexSolverKrylov::new(genie).unwrap();
+            let mut mat = ComplexSparseMatrix::from_coo(unsymmetric.clone());
+            let (rhs, x_correct) = complex_rhs_and_solution(&mat);
+            let mut x = ComplexVector::new(3);
+            let mut params = LinSolParams::new();
+            params.krylov_rel_tol = 1e-12;
+            params.krylov_num_threads = num_threads;
+            solver.factorize(&mut mat, Some(params)).unwrap();
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            complex_vec_approx_eq(&x, x_correct.as_data(), 1e-10);
+            let mut stats = StatsLinSol::new();
+            solver.update_stats(&mut stats);
+            assert!(stats.iterative.converged);
+            assert_eq!(stats.iterative.iterations, solver.get_iterations());
+            assert!(stats.iterative.residual_norm <= stats.iterative.tolerance);
+        }
+    }
+
+    #[test]
+    fn helmholtz_works_complex() {
+        let m = 12;
+        let n = m * m;
+        let mut mat = ComplexSparseMatrix::from_coo(complex_helmholtz_2d(m, 0.8, 0.5));
+        let (rhs, x_correct) = complex_rhs_and_solution(&mat);
+        for genie in [Genie::BiCgStab, Genie::Cocg, Genie::Gmres] {
+            for precond in [
+                Precond::No,
+                Precond::Jacobi,
+                Precond::Ssor,
+                Precond::Ilu0,
+                Precond::Ilut,
+                Precond::Ic0,
+                Precond::Amg,
+            ] {
+                let mut params = LinSolParams::new();
+                params.krylov_precond = precond;
+                params.krylov_gmres_restart = 50;
+                let mut solver = ComplexSolverKrylov::new(genie).unwrap();
+                let mut x = ComplexVector::new(n);
+                solver.factorize(&mut mat, Some(params)).unwrap();
+                solver.solve(&mut x, &mat, &rhs, false).unwrap();
+                complex_vec_approx_eq(&x, x_correct.as_data(), 1e-8);
+                let mut stats = StatsLinSol::new();
+                solver.update_stats(&mut stats);
+                assert_eq!(
+                    stats.main.solver,
+                    genie.to_string().to_uppercase().replace("BICGSTAB", "BiCGStab")
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn gmres_restart_works_complex() {
+        let n = 20;
+        let mut coo = ComplexCooMatrix::new(n, n, 3 * n, Sym::No).unwrap();
+        for i in 0..n {
+            coo.put(i, i, cpx!(4.0, 1.0)).unwrap();
+            if i > 0 {
+                coo.put(i, i - 1, cpx!(-1.5, 0.5)).unwrap();
+            }
+            if i < n - 1 {
+                coo.put(i, i + 1, cpx!(-0.5, 0.0)).unwrap();
+            }
+        }
+        let mut mat = ComplexSparseMatrix::from_coo(coo);
+        let (rhs, x_correct) = complex_rhs_and_solution(&mat);
+        let mut x = ComplexVector::new(n);
+        let mut params = LinSolParams::new();
+        params.krylov_gmres_restart = 3;
+        let mut solver = ComplexSolverKrylov::new(Genie::Gmres).unwrap();
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        complex_vec_approx_eq(&x, x_correct.as_data(), 1e-9);
+        assert!(solver.get_iterations() > 3);
+    }
+
+    #[test]
+    fn solve_captures_non_convergence_complex() {
+        let n = 20;
+        let mut mat = ComplexSparseMatrix::from_coo(complex_tridiagonal(n, cpx!(2.0, 0.1), Sym::YesFull));
+        let rhs = ComplexVector::filled(n, cpx!(1.0, 1.0));
+        let mut x = ComplexVector::new(n);
+        let mut params = LinSolParams::new();
+        params.krylov_max_iterations = 2;
+        let mut solver = ComplexSolverKrylov::new(Genie::Cocg).unwrap();
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false).err(),
+            Some("the iterative solver did not converge (max number of iterations reached)")
+        );
+        assert!(!solver.get_converged());
+        assert_eq!(solver.get_iterations(), 2);
+        assert_eq!(solver.get_history().len(), 3);
+    }
+
+    #[test]
+    fn solve_with_operator_works_complex() {
+        let n = 30;
+        let d = cpx!(2.0, 1.0);
+        let mat = ComplexSparseMatrix::from_coo(complex_tridiagonal(n, d, Sym::No));
+        let (rhs, x_correct) = complex_rhs_and_solution(&mat);
+        for genie in [Genie::BiCgStab, Genie::Cocg, Genie::Gmres] {
+            let mut x = ComplexVector::new(n);
+            let mut solver = ComplexSolverKrylov::new(genie).unwrap();
+            solver
+                .solve_with_operator(&mut x, &rhs, None, |v, u| {
+                    for i in 0..n {
+                        v[i] = d * u[i];
+                        if i > 0 {
+                            v[i] -= u[i - 1];
+                        }
+                        if i < n - 1 {
+                            v[i] -= u[i + 1];
+                        }
+                    }
+                    Ok(())
+                })
+                .unwrap();
+            complex_vec_approx_eq(&x, x_correct.as_data(), 1e-9);
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use super::{LinSolParams, LinSolTrait, Ordering, Scaling, SparseMatrix, StatsLinSol, Sym};
+use super::{CooMatrix, CscMatrix, LinSolParams, LinSolTrait, Ordering, Scaling, SparseMatrix, StatsLinSol, Sym};
 use crate::constants::*;
+use crate::solver_native::remove_component;
 use crate::StrError;
-use russell_lab::{using_intel_mkl, vec_copy, Stopwatch, Vector};
+use russell_lab::{using_intel_mkl, Matrix, Stopwatch, Vector};
 
 /// Opaque struct holding a C-pointer to InterfaceMUMPS
 ///
@@ -40,23 +41,51 @@ extern "C" {
         indices_i: *const i32,
         indices_j: *const i32,
         values_aij: *const f64,
+        size_schur: i32,
+        listvar_schur: *const i32,
+        schur: *mut f64,
     ) -> i32;
     fn solver_mumps_factorize(
         solver: *mut InterfaceMUMPS,
+        values_aij: *const f64,
         effective_ordering: *mut i32,
         effective_scaling: *mut i32,
         determinant_coefficient: *mut f64,
         determinant_exponent: *mut f64,
         compute_determinant: CcBool,
+        null_pivot_detection: CcBool,
+        null_pivot_threshold: f64,
+        number_of_null_pivots: *mut i32,
         verbose: CcBool,
     ) -> i32;
     fn solver_mumps_solve(
         solver: *mut InterfaceMUMPS,
         rhs: *mut f64,
+        nrhs: i32,
+        transpose: CcBool,
         error_analysis_array_len_8: *mut f64,
         error_analysis_option: i32,
         verbose: CcBool,
     ) -> i32;
+    fn solver_mumps_solve_schur(
+        solver: *mut InterfaceMUMPS,
+        rhs: *mut f64,
+        reduced_rhs: *mut f64,
+        expansion: CcBool,
+        verbose: CcBool,
+    ) -> i32;
+    fn solver_mumps_solve_sparse_rhs(
+        solver: *mut InterfaceMUMPS,
+        x: *mut f64,
+        nrhs: i32,
+        nz_rhs: i32,
+        irhs_ptr: *const i32,
+        irhs_sparse: *const i32,
+        rhs_sparse: *const f64,
+        transpose: CcBool,
+        verbose: CcBool,
+    ) -> i32;
+    fn solver_mumps_null_space(solver: *mut InterfaceMUMPS, basis: *mut f64, num_null: i32, verbose: CcBool) -> i32;
 }
 
 /// Wraps the MUMPS solver for (very large) sparse linear systems
@@ -72,6 +101,9 @@ pub struct SolverMUMPS {
     /// Indicates whether the sparse matrix has been factorized or not
     factorized: bool,
 
+    /// Holds the parameters saved in analyze (or factorize)
+    params: LinSolParams,
+
     /// Holds the symmetric flag saved in initialize
     initialized_sym: Sym,
 
@@ -100,6 +132,15 @@ pub struct SolverMUMPS {
     /// det = coefficient * pow(2, exponent)
     determinant_exponent: f64,
 
+    /// Indicates that the null pivot detection has been requested (ICNTL(24))
+    null_pivot_detection: bool,
+
+    /// Holds the number of null pivots (INFOG(28); if the null pivot detection is requested)
+    number_of_null_pivots: i32,
+
+    /// Holds an orthonormal basis of the null space of A (ICNTL(25) = -1; if the null pivot detection is requested)
+    null_space: Matrix,
+
     /// MUMPS code for error analysis (after solve)
     ///
     /// ICNTL(11): 0 (nothing), 1 (all; slow), 2 (just errors)
@@ -117,6 +158,9 @@ pub struct SolverMUMPS {
     /// Time spent on factorize in nanoseconds
     time_factorize_ns: u128,
 
+    /// Time spent on refactorize in nanoseconds
+    time_refactorize_ns: u128,
+
     /// Time spent on solve in nanoseconds
     time_solve_ns: u128,
 
@@ -125,6 +169,21 @@ pub struct SolverMUMPS {
 
     /// Holds the (one-based/Fortran) column indices j
     fortran_indices_j: Vec<i32>,
+
+    /// Holds the (one-based/Fortran) indices of the Schur variables (empty if the Schur complement is not requested)
+    fortran_schur_indices: Vec<i32>,
+
+    /// Holds the Schur complement (by rows; lower triangle if symmetric) computed by factorize
+    ///
+    /// **Note:** This array is allocated before the initialization and must not be reallocated
+    /// because MUMPS holds a pointer to it.
+    schur_values: Vec<f64>,
+
+    /// Holds the right-hand side array between the condensation and expansion steps
+    schur_work: Vec<f64>,
+
+    /// Indicates that the condensation step has been performed
+    schur_condensed: bool,
 }
 
 impl Drop for SolverMUMPS {
@@ -148,6 +207,7 @@ impl SolverMUMPS {
                 solver,
                 initialized: false,
                 factorized: false,
+                params: LinSolParams::new(),
                 initialized_sym: Sym::No,
                 initialized_ndim: 0,
                 initialized_nnz: 0,
@@ -156,17 +216,393 @@ impl SolverMUMPS {
                 effective_num_threads: 0,
                 determinant_coefficient: 0.0,
                 determinant_exponent: 0.0,
+                null_pivot_detection: false,
+                number_of_null_pivots: 0,
+                null_space: Matrix::new(0, 0),
                 error_analysis_option: 0,
                 error_analysis_array_len_8: vec![0.0; 8],
                 stopwatch: Stopwatch::new(),
                 time_initialize_ns: 0,
                 time_factorize_ns: 0,
+                time_refactorize_ns: 0,
                 time_solve_ns: 0,
                 fortran_indices_i: Vec::new(),
                 fortran_indices_j: Vec::new(),
+                fortran_schur_indices: Vec::new(),
+                schur_values: Vec::new(),
+                schur_work: Vec::new(),
+                schur_condensed: false,
             })
         }
     }
+
+    /// Checks that the matrix has been factorized and is the same one used in factorize
+    fn check_solve(&self, mat: &SparseMatrix) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+        let coo = mat.get_coo()?;
+        let (nrow, ncol, nnz, sym) = coo.get_info();
+        if sym != self.initialized_sym {
+            return Err("solve must use the same matrix (symmetric differs)");
+        }
+        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
+            return Err("solve must use the same matrix (ndim differs)");
+        }
+        if nnz != self.initialized_nnz {
+            return Err("solve must use the same matrix (nnz differs)");
+        }
+        Ok(())
+    }
+
+    /// Checks the data and solves `A · X = RHS` or `Aᵀ · X = RHS` (col-major arrays)
+    fn solve_internal(
+        &mut self,
+        x: &mut [f64],
+        mat: &SparseMatrix,
+        rhs: &[f64],
+        nrhs: usize,
+        transpose: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        // check
+        self.check_solve(mat)?;
+
+        // check vectors
+        if x.len() != self.initialized_ndim * nrhs {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.len() != self.initialized_ndim * nrhs {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+
+        // call MUMPS solve
+        x.copy_from_slice(rhs);
+        let verb = if verbose { 1 } else { 0 };
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_mumps_solve(
+                self.solver,
+                x.as_mut_ptr(),
+                to_i32(nrhs),
+                if transpose { 1 } else { 0 },
+                self.error_analysis_array_len_8.as_mut_ptr(),
+                self.error_analysis_option,
+                verb,
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_mumps_error_code(status));
+            }
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+
+        // done
+        Ok(())
+    }
+
+    /// Requests the Schur complement with respect to a set of variables (partial factorization)
+    ///
+    /// Considering the following partition of the coefficient matrix, where the index 2 corresponds
+    /// to the Schur variables and the index 1 corresponds to the interior variables:
+    ///
+    /// ```text
+    ///     ┌          ┐
+    ///     │ A₁₁  A₁₂ │
+    /// A = │          │
+    ///     │ A₂₁  A₂₂ │
+    ///     └          ┘
+    /// ```
+    ///
+    /// `factorize` will only factorize A₁₁ and will compute the Schur complement:
+    ///
+    /// ```text
+    /// S = A₂₂ - A₂₁ · A₁₁⁻¹ · A₁₂
+    /// ```
+    ///
+    /// The Schur complement is then available via [SolverMUMPS::get_schur_complement()] and
+    /// [SolverMUMPS::get_schur_complement_sparse()]. The solution of `A · x = rhs` is obtained
+    /// by the following steps:
+    ///
+    /// 1. Call [SolverMUMPS::schur_condense()] to compute the reduced right-hand side `r₂ = rhs₂ - A₂₁ · A₁₁⁻¹ · rhs₁`
+    /// 2. Solve the (dense and small) system `S · x₂ = r₂` (e.g., on the interface of a substructure)
+    /// 3. Call [SolverMUMPS::schur_expand()] to compute `x₁ = A₁₁⁻¹ · (rhs₁ - A₁₂ · x₂)` and the full solution
+    ///
+    /// # Input
+    ///
+    /// * `indices` -- the (zero-based) indices of the Schur variables; the rows and columns of S follow this order
+    ///
+    /// # Notes
+    ///
+    /// 1. This function must be called before the first call to `factorize`.
+    /// 2. With the Schur complement, `solve` only computes the solution of the interior problem `A₁₁ · x₁ = rhs₁`.
+    pub fn set_schur_indices(&mut self, indices: &[usize]) -> Result<(), StrError> {
+        if self.initialized {
+            return Err("the Schur indices must be set before the first call to factorize");
+        }
+        if indices.len() < 1 {
+            return Err("the list of Schur indices must not be empty");
+        }
+        let mut sorted = indices.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        if sorted.len() != indices.len() {
+            return Err("the list of Schur indices must not contain duplicates");
+        }
+        let size = indices.len();
+        self.fortran_schur_indices = indices.iter().map(|i| to_i32(*i + 1)).collect();
+        self.schur_values = vec![0.0; size * size];
+        Ok(())
+    }
+
+    /// Returns the Schur complement as a dense matrix
+    ///
+    /// See [SolverMUMPS::set_schur_indices()]. The rows and columns of S correspond to the Schur
+    /// indices in the order given to `set_schur_indices`.
+    pub fn get_schur_complement(&self) -> Result<Matrix, StrError> {
+        self.check_schur()?;
+        let size = self.fortran_schur_indices.len();
+        let mut s = Matrix::new(size, size);
+        for i in 0..size {
+            for j in 0..size {
+                if self.initialized_sym == Sym::YesLower {
+                    if j <= i {
+                        s.set(i, j, self.schur_values[i * size + j]);
+                        s.set(j, i, self.schur_values[i * size + j]);
+                    }
+                } else {
+                    s.set(i, j, self.schur_values[i * size + j]);
+                }
+            }
+        }
+        Ok(s)
+    }
+
+    /// Returns the Schur complement as a sparse matrix
+    ///
+    /// See [SolverMUMPS::set_schur_indices()]. The rows and columns of S correspond to the Schur
+    /// indices in the order given to `set_schur_indices`. If the coefficient matrix is symmetric,
+    /// only the lower triangle of S is stored ([Sym::YesLower]).
+    ///
+    /// # Input
+    ///
+    /// * `drop_tol` -- the off-diagonal entries with `|sij| ≤ drop_tol` are not stored (the diagonal is always stored)
+    pub fn get_schur_complement_sparse(&self, drop_tol: f64) -> Result<SparseMatrix, StrError> {
+        self.check_schur()?;
+        let size = self.fortran_schur_indices.len();
+        let symmetric = self.initialized_sym == Sym::YesLower;
+        let mut nnz = 0;
+        for i in 0..size {
+            for j in 0..size {
+                let keep = !symmetric || j <= i;
+                if keep && (i == j || f64::abs(self.schur_values[i * size + j]) > drop_tol) {
+                    nnz += 1;
+                }
+            }
+        }
+        let sym = if symmetric { Sym::YesLower } else { Sym::No };
+        let mut coo = CooMatrix::new(size, size, nnz, sym)?;
+        for i in 0..size {
+            for j in 0..size {
+                let keep = !symmetric || j <= i;
+                let sij = self.schur_values[i * size + j];
+                if keep && (i == j || f64::abs(sij) > drop_tol) {
+                    coo.put(i, j, sij)?;
+                }
+            }
+        }
+        Ok(SparseMatrix::from_coo(coo))
+    }
+
+    /// Performs the condensation step of the solution with the Schur complement
+    ///
+    /// Computes the reduced right-hand side on the Schur variables:
+    ///
+    /// ```text
+    /// r₂ = rhs₂ - A₂₁ · A₁₁⁻¹ · rhs₁
+    /// ```
+    ///
+    /// See [SolverMUMPS::set_schur_indices()].
+    ///
+    /// # Output
+    ///
+    /// * `reduced_rhs` -- the reduced right-hand side r₂ with dimension equal to the number of Schur variables
+    ///
+    /// # Input
+    ///
+    /// * `rhs` -- the right-hand side vector with dimension equal to the matrix dimension
+    /// * `verbose` -- shows messages
+    pub fn schur_condense(&mut self, reduced_rhs: &mut Vector, rhs: &Vector, verbose: bool) -> Result<(), StrError> {
+        self.check_schur()?;
+        if reduced_rhs.dim() != self.fortran_schur_indices.len() {
+            return Err("the dimension of the reduced right-hand side vector is incorrect");
+        }
+        if rhs.dim() != self.initialized_ndim {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+        self.schur_work = rhs.as_data().clone();
+        let verb = if verbose { 1 } else { 0 };
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_mumps_solve_schur(
+                self.solver,
+                self.schur_work.as_mut_ptr(),
+                reduced_rhs.as_mut_data().as_mut_ptr(),
+                0,
+                verb,
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_mumps_error_code(status));
+            }
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+        self.schur_condensed = true;
+        Ok(())
+    }
+
+    /// Performs the expansion step of the solution with the Schur complement
+    ///
+    /// Given the solution x₂ of `S · x₂ = r₂`, computes:
+    ///
+    /// ```text
+    /// x₁ = A₁₁⁻¹ · (rhs₁ - A₁₂ · x₂)
+    /// ```
+    ///
+    /// and assembles the full solution x. See [SolverMUMPS::set_schur_indices()].
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to the matrix dimension
+    ///
+    /// # Input
+    ///
+    /// * `x_schur` -- the solution x₂ on the Schur variables
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** [SolverMUMPS::schur_condense()] must be called first (with the same factorization).
+    pub fn schur_expand(&mut self, x: &mut Vector, x_schur: &Vector, verbose: bool) -> Result<(), StrError> {
+        self.check_schur()?;
+        if !self.schur_condensed {
+            return Err("the function schur_condense must be called before schur_expand");
+        }
+        if x_schur.dim() != self.fortran_schur_indices.len() {
+            return Err("the dimension of the vector with the solution on the Schur variables is incorrect");
+        }
+        if x.dim() != self.initialized_ndim {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        let mut reduced = x_schur.as_data().clone();
+        let verb = if verbose { 1 } else { 0 };
+        self.stopwatch.reset();
+        unsafe {
+            let status =
+                solver_mumps_solve_schur(self.solver, self.schur_work.as_mut_ptr(), reduced.as_mut_ptr(), 1, verb);
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_mumps_error_code(status));
+            }
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+        x.as_mut_data().copy_from_slice(&self.schur_work);
+        for (k, i) in self.fortran_schur_indices.iter().enumerate() {
+            x[(*i - 1) as usize] = x_schur[k];
+        }
+        self.schur_condensed = false;
+        Ok(())
+    }
+
+    /// Checks the matrix and performs the numeric factorization (JOB=2) reusing the analysis
+    ///
+    /// Returns the elapsed time in nanoseconds.
+    fn numeric(&mut self, mat: &mut SparseMatrix) -> Result<u128, StrError> {
+        // get COO matrix
+        let coo = mat.get_coo()?;
+
+        // check
+        if coo.symmetric != self.initialized_sym {
+            return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+        }
+        if coo.nrow != self.initialized_ndim {
+            return Err("subsequent factorizations must use the same matrix (ndim differs)");
+        }
+        if coo.nnz != self.initialized_nnz {
+            return Err("subsequent factorizations must use the same matrix (nnz differs)");
+        }
+        let current = coo.indices_i[..coo.nnz].iter().zip(&coo.indices_j[..coo.nnz]);
+        let saved = self.fortran_indices_i.iter().zip(&self.fortran_indices_j);
+        if current
+            .zip(saved)
+            .any(|((i, j), (fi, fj))| *i + 1 != *fi || *j + 1 != *fj)
+        {
+            return Err("subsequent factorizations must use the same matrix (pattern differs)");
+        }
+
+        // error analysis option
+        self.error_analysis_option = if self.params.compute_condition_numbers {
+            1 // all the statistics (very expensive) (page 40)
+        } else if self.params.compute_error_estimates {
+            2 // main statistics are computed (page 40)
+        } else {
+            0 // nothing
+        };
+
+        // requests
+        let compute_determinant = if self.params.compute_determinant { 1 } else { 0 };
+        let null_pivot_detection = if self.params.null_pivot_detection { 1 } else { 0 };
+        self.null_pivot_detection = self.params.null_pivot_detection;
+        let verbose = if self.params.verbose { 1 } else { 0 };
+
+        // call factorize
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_mumps_factorize(
+                self.solver,
+                coo.values.as_ptr(),
+                &mut self.effective_ordering,
+                &mut self.effective_scaling,
+                &mut self.determinant_coefficient,
+                &mut self.determinant_exponent,
+                compute_determinant,
+                null_pivot_detection,
+                self.params.null_pivot_tolerance,
+                &mut self.number_of_null_pivots,
+                verbose,
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_mumps_error_code(status));
+            }
+        }
+        let elapsed = self.stopwatch.stop();
+
+        // null space (the solve phase with ICNTL(25) = -1 returns all the basis vectors)
+        let ndim = self.initialized_ndim;
+        let count = usize::min(self.number_of_null_pivots as usize, ndim);
+        self.null_space = Matrix::new(ndim, 0);
+        if self.null_pivot_detection && count > 0 && self.fortran_schur_indices.is_empty() {
+            let mut basis = Matrix::new(ndim, count);
+            unsafe {
+                let status =
+                    solver_mumps_null_space(self.solver, basis.as_mut_data().as_mut_ptr(), to_i32(count), verbose);
+                if status != SUCCESSFUL_EXIT {
+                    return Err(handle_mumps_error_code(status));
+                }
+            }
+            self.null_space = orthonormal_basis(&basis);
+        }
+
+        // done
+        self.schur_condensed = false;
+        Ok(elapsed)
+    }
+
+    /// Checks that the Schur complement has been requested and computed
+    fn check_schur(&self) -> Result<(), StrError> {
+        if self.fortran_schur_indices.len() < 1 {
+            return Err("the Schur complement has not been requested (see set_schur_indices)");
+        }
+        if !self.factorized {
+            return Err("the function factorize must be called before using the Schur complement");
+        }
+        Ok(())
+    }
 }
 
 impl LinSolTrait for SolverMUMPS {
@@ -191,134 +627,322 @@ impl LinSolTrait for SolverMUMPS {
     /// 4. For symmetric matrices, `MUMPS` requires [Sym::YesLower].
     /// 5. The COO matrix must be one-based.
     fn factorize(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
-        // get COO matrix
-        let coo = mat.get_coo()?;
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if !self.initialized {
+            self.analyze(mat, Some(par))?;
+        }
+        self.params = par;
+        self.factorize_numeric(mat)
+    }
 
+    /// Performs the symbolic analysis (MUMPS JOB=1) only
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (one-base **COO** only, not CSC and not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`) and, if symmetric,
+    ///   the symmetric flag must be [Sym::YesLower]
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The analysis is performed just once; i.e., the solver must be
+    /// "dropped" and a new solver allocated if the structure of the matrix changes.
+    /// The time spent on the analysis is recorded as the initialization time.
+    fn analyze(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
         // check
         if self.initialized {
-            if coo.symmetric != self.initialized_sym {
-                return Err("subsequent factorizations must use the same matrix (symmetric differs)");
-            }
-            if coo.nrow != self.initialized_ndim {
-                return Err("subsequent factorizations must use the same matrix (ndim differs)");
-            }
-            if coo.nnz != self.initialized_nnz {
-                return Err("subsequent factorizations must use the same matrix (nnz differs)");
-            }
-        } else {
-            if coo.nrow != coo.ncol {
-                return Err("the COO matrix must be square");
-            }
-            if coo.nnz < 1 {
-                return Err("the COO matrix must have at least one non-zero value");
-            }
-            if coo.symmetric == Sym::YesFull || coo.symmetric == Sym::YesUpper {
-                return Err("MUMPS requires Sym::YesLower for symmetric matrices");
-            }
-            self.initialized_sym = coo.symmetric;
-            self.initialized_ndim = coo.nrow;
-            self.initialized_nnz = coo.nnz;
-            self.fortran_indices_i = vec![0; coo.nnz];
-            self.fortran_indices_j = vec![0; coo.nnz];
-            for k in 0..coo.nnz {
-                self.fortran_indices_i[k] = coo.indices_i[k] + 1;
-                self.fortran_indices_j[k] = coo.indices_j[k] + 1;
-            }
+            return Err("the analysis has been performed already");
         }
 
-        // configuration parameters
-        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        // get COO matrix
+        let coo = mat.get_coo()?;
+        if coo.nrow != coo.ncol {
+            return Err("the COO matrix must be square");
+        }
+        if coo.nnz < 1 {
+            return Err("the COO matrix must have at least one non-zero value");
+        }
+        if coo.symmetric == Sym::YesFull || coo.symmetric == Sym::YesUpper {
+            return Err("MUMPS requires Sym::YesLower for symmetric matrices");
+        }
+        if self.fortran_schur_indices.len() >= coo.nrow {
+            return Err("the number of Schur variables must be smaller than the matrix dimension");
+        }
+        if self.fortran_schur_indices.iter().any(|i| *i as usize > coo.nrow) {
+            return Err("the Schur indices must be smaller than the matrix dimension");
+        }
+        self.initialized_sym = coo.symmetric;
+        self.initialized_ndim = coo.nrow;
+        self.initialized_nnz = coo.nnz;
+        self.fortran_indices_i = vec![0; coo.nnz];
+        self.fortran_indices_j = vec![0; coo.nnz];
+        for k in 0..coo.nnz {
+            self.fortran_indices_i[k] = coo.indices_i[k] + 1;
+            self.fortran_indices_j[k] = coo.indices_j[k] + 1;
+        }
 
-        // error analysis option
-        self.error_analysis_option = if par.compute_condition_numbers {
-            1 // all the statistics (very expensive) (page 40)
-        } else if par.compute_error_estimates {
-            2 // main statistics are computed (page 40)
-        } else {
-            0 // nothing
-        };
+        // parameters
+        self.params = if let Some(p) = params { p } else { LinSolParams::new() };
 
         // input parameters
-        let ordering = mumps_ordering(par.ordering);
-        let scaling = mumps_scaling(par.scaling);
-        let pct_inc_workspace = to_i32(par.mumps_pct_inc_workspace);
-        let max_work_memory = to_i32(par.mumps_max_work_memory);
-        self.effective_num_threads =
-            if using_intel_mkl() || par.mumps_num_threads != 0 || par.mumps_override_prevent_nt_issue_with_openblas {
-                to_i32(par.mumps_num_threads)
-            } else {
-                1 // avoid bug with OpenBLAS
-            };
+        let ordering = mumps_ordering(self.params.ordering);
+        let scaling = mumps_scaling(self.params.scaling);
+        let pct_inc_workspace = to_i32(self.params.mumps_pct_inc_workspace);
+        let max_work_memory = to_i32(self.params.mumps_max_work_memory);
+        self.effective_num_threads = if using_intel_mkl()
+            || self.params.mumps_num_threads != 0
+            || self.params.mumps_override_prevent_nt_issue_with_openblas
+        {
+            to_i32(self.params.mumps_num_threads)
+        } else {
+            1 // avoid bug with OpenBLAS
+        };
 
         // requests
-        let compute_determinant = if par.compute_determinant { 1 } else { 0 };
-        let verbose = if par.verbose { 1 } else { 0 };
+        let verbose = if self.params.verbose { 1 } else { 0 };
 
         // matrix config
         let general_symmetric = if coo.symmetric == Sym::YesLower { 1 } else { 0 };
-        let positive_definite = if par.positive_definite { 1 } else { 0 };
+        let positive_definite = if self.params.positive_definite { 1 } else { 0 };
         let ndim = to_i32(coo.nrow);
         let nnz = to_i32(coo.nnz);
 
-        // call initialize just once
-        if !self.initialized {
-            self.stopwatch.reset();
-            unsafe {
-                let status = solver_mumps_initialize(
-                    self.solver,
-                    ordering,
-                    scaling,
-                    pct_inc_workspace,
-                    max_work_memory,
-                    self.effective_num_threads,
-                    verbose,
-                    general_symmetric,
-                    positive_definite,
-                    ndim,
-                    nnz,
-                    self.fortran_indices_i.as_ptr(),
-                    self.fortran_indices_j.as_ptr(),
-                    coo.values.as_ptr(),
-                );
-                if status != SUCCESSFUL_EXIT {
-                    return Err(handle_mumps_error_code(status));
-                }
+        // call initialize (JOB=-1) and analyze (JOB=1)
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_mumps_initialize(
+                self.solver,
+                ordering,
+                scaling,
+                pct_inc_workspace,
+                max_work_memory,
+                self.effective_num_threads,
+                verbose,
+                general_symmetric,
+                positive_definite,
+                ndim,
+                nnz,
+                self.fortran_indices_i.as_ptr(),
+                self.fortran_indices_j.as_ptr(),
+                coo.values.as_ptr(),
+                to_i32(self.fortran_schur_indices.len()),
+                self.fortran_schur_indices.as_ptr(),
+                self.schur_values.as_mut_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_mumps_error_code(status));
             }
-            self.time_initialize_ns = self.stopwatch.stop();
-            self.initialized = true;
         }
+        self.time_initialize_ns = self.stopwatch.stop();
+
+        // done
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric factorization (MUMPS JOB=2) reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (one-base **COO** only) with the same
+    ///   structure as the one given to `analyze`
+    fn factorize_numeric(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        if !self.initialized {
+            return Err("the function analyze must be called before factorize_numeric");
+        }
+        self.time_factorize_ns = self.numeric(mat)?;
+        self.factorized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric refactorization (MUMPS JOB=2) reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (one-base **COO** only) with the same
+    ///   structure as the one previously factorized
+    ///
+    /// **Note:** MUMPS does not offer a refactorization without pivoting; thus, this function
+    /// performs a new numeric factorization (with pivoting) reusing the analysis.
+    /// The time is recorded as the refactorization time.
+    fn refactorize(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before refactorize");
+        }
+        self.time_refactorize_ns = self.numeric(mat)?;
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesLower].
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve(&mut self, x: &mut Vector, mat: &SparseMatrix, rhs: &Vector, verbose: bool) -> Result<(), StrError> {
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), 1, false, verbose)
+    }
+
+    /// Computes the solution of the transposed linear system
+    ///
+    /// Solves `Aᵀ · x = rhs` using the same factorization of A.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (not transposed); it must be square and, if symmetric, [Sym::YesLower].
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_transpose(
+        &mut self,
+        x: &mut Vector,
+        mat: &SparseMatrix,
+        rhs: &Vector,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), 1, true, verbose)
+    }
+
+    /// Computes the solution of the linear system with multiple right-hand sides (reusing the factorization)
+    ///
+    /// All right-hand sides are handled by a single call to MUMPS.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the (m,k) matrix of unknown values; each column corresponds to a column of `rhs`
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesLower].
+    /// * `rhs` -- the (m,k) matrix of right-hand sides
+    /// * `transpose` -- solves `Aᵀ · X = RHS` instead
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    ///
+    /// **Note:** The error analysis (if requested) is not performed with more than one right-hand side.
+    fn solve_multiple(
+        &mut self,
+        x: &mut Matrix,
+        mat: &SparseMatrix,
+        rhs: &Matrix,
+        transpose: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        let (m, k) = rhs.dims();
+        if x.dims() != (m, k) {
+            return Err("the dimensions of the matrix of unknown values X are incorrect");
+        }
+        self.solve_internal(x.as_mut_data(), mat, rhs.as_data(), k, transpose, verbose)
+    }
+
+    /// Computes the solution of the linear system with a sparse matrix of right-hand sides
+    ///
+    /// The sparse right-hand sides are given directly to MUMPS (ICNTL(20) = 1), which exploits
+    /// their sparsity; thus, the dense matrix of right-hand sides is not allocated.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the (m,k) (dense) matrix of unknown values
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesLower].
+    /// * `rhs` -- the (m,k) sparse matrix of right-hand sides (with [Sym::No])
+    /// * `transpose` -- solves `Aᵀ · X = RHS` instead
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    ///
+    /// **Note:** The error analysis (if requested) is not performed with sparse right-hand sides.
+    fn solve_sparse_rhs(
+        &mut self,
+        x: &mut Matrix,
+        mat: &SparseMatrix,
+        rhs: &CscMatrix,
+        transpose: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        // check
+        if rhs.symmetric != Sym::No {
+            return Err("the sparse matrix of right-hand sides must have Sym::No");
+        }
+        self.check_solve(mat)?;
+        let (m, k) = (rhs.nrow, rhs.ncol);
+        if m != self.initialized_ndim {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+        if x.dims() != (m, k) {
+            return Err("the dimensions of the matrix of unknown values X are incorrect");
+        }
+
+        // the (one-based) arrays of the right-hand sides
+        let nz_rhs = rhs.col_pointers[k] as usize;
+        if nz_rhs == 0 {
+            x.fill(0.0);
+            return Ok(());
+        }
+        let irhs_ptr: Vec<i32> = rhs.col_pointers.iter().map(|p| *p + 1).collect();
+        let irhs_sparse: Vec<i32> = rhs.row_indices[..nz_rhs].iter().map(|i| *i + 1).collect();
 
-        // call factorize
+        // call MUMPS solve
+        let verb = if verbose { 1 } else { 0 };
         self.stopwatch.reset();
         unsafe {
-            let status = solver_mumps_factorize(
+            let status = solver_mumps_solve_sparse_rhs(
                 self.solver,
-                &mut self.effective_ordering,
-                &mut self.effective_scaling,
-                &mut self.determinant_coefficient,
-                &mut self.determinant_exponent,
-                compute_determinant,
-                verbose,
+                x.as_mut_data().as_mut_ptr(),
+                to_i32(k),
+                to_i32(nz_rhs),
+                irhs_ptr.as_ptr(),
+                irhs_sparse.as_ptr(),
+                rhs.values.as_ptr(),
+                if transpose { 1 } else { 0 },
+                verb,
             );
             if status != SUCCESSFUL_EXIT {
                 return Err(handle_mumps_error_code(status));
             }
         }
-        self.time_factorize_ns = self.stopwatch.stop();
-
-        // done
-        self.factorized = true;
+        self.time_solve_ns = self.stopwatch.stop();
         Ok(())
     }
 
-    /// Computes the solution of the linear system
-    ///
-    /// Solves the linear system:
+    /// Returns an orthonormal basis of the null space of A (after factorize with null pivot detection)
     ///
-    /// ```text
-    ///   A   · x = rhs
-    /// (m,m)  (m)  (m)
-    /// ```
+    /// The basis vectors are computed by MUMPS (ICNTL(25) = -1) and then orthonormalized.
+    fn null_space(&self) -> Result<Matrix, StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before null_space");
+        }
+        if !self.null_pivot_detection {
+            return Err("the null pivot detection must be enabled to compute the null space");
+        }
+        if !self.fortran_schur_indices.is_empty() {
+            return Err("the null space is not available with the Schur complement");
+        }
+        Ok(self.null_space.clone())
+    }
+
+    /// Computes the minimum-norm least-squares solution `x = A⁺ · rhs`
     ///
     /// # Output
     ///
@@ -330,65 +954,82 @@ impl LinSolTrait for SolverMUMPS {
     /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
     /// * `verbose` -- shows messages
     ///
+    /// # Notes
+    ///
+    /// 1. If A is symmetric, the component of `rhs` along the null space is removed; thus, `b_r = rhs - N·Nᵀ·rhs`.
+    /// 2. Then, MUMPS computes a particular solution `x₀` of `A·x = b_r` (the null pivots are fixed).
+    /// 3. Finally, the component of `x₀` along the null space of A is removed; thus, `x = x₀ - N·Nᵀ·x₀`.
+    ///
+    /// **Note:** MUMPS does not return the null space of Aᵀ; thus, if A is unsymmetric, the system must be
+    /// compatible (i.e., rhs must be in the range of A); otherwise, an error is returned.
+    ///
     /// **Warning:** the matrix must be same one used in `factorize`.
-    fn solve(&mut self, x: &mut Vector, mat: &SparseMatrix, rhs: &Vector, verbose: bool) -> Result<(), StrError> {
-        // check
+    fn solve_pseudo_inverse(
+        &mut self,
+        x: &mut Vector,
+        mat: &SparseMatrix,
+        rhs: &Vector,
+        verbose: bool,
+    ) -> Result<(), StrError> {
         if !self.factorized {
-            return Err("the function factorize must be called before solve");
+            return Err("the function factorize must be called before solve_pseudo_inverse");
         }
-
-        // access COO matrix
-        let coo = mat.get_coo()?;
-
-        // check already factorized data
-        let (nrow, ncol, nnz, sym) = coo.get_info();
-        if sym != self.initialized_sym {
-            return Err("solve must use the same matrix (symmetric differs)");
+        if !self.null_pivot_detection {
+            return Err("the null pivot detection must be enabled to compute the pseudo-inverse solution");
         }
-        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
-            return Err("solve must use the same matrix (ndim differs)");
+        if !self.fortran_schur_indices.is_empty() {
+            return Err("the pseudo-inverse solution is not available with the Schur complement");
         }
-        if nnz != self.initialized_nnz {
-            return Err("solve must use the same matrix (nnz differs)");
+        let n = self.initialized_ndim;
+        if rhs.dim() != n {
+            return Err("the dimension of the right-hand side vector is incorrect");
         }
 
-        // check vectors
-        if x.dim() != self.initialized_ndim {
-            return Err("the dimension of the vector of unknown values x is incorrect");
-        }
-        if rhs.dim() != self.initialized_ndim {
-            return Err("the dimension of the right-hand side vector is incorrect");
+        // b_r = rhs - N·Nᵀ·rhs (the left and right null spaces coincide if A is symmetric)
+        let symmetric = self.initialized_sym != Sym::No;
+        let mut b_r = rhs.clone();
+        if symmetric {
+            remove_component(&mut b_r, &self.null_space);
         }
 
-        // call MUMPS solve
-        vec_copy(x, rhs).unwrap();
-        let verb = if verbose { 1 } else { 0 };
-        self.stopwatch.reset();
-        unsafe {
-            let status = solver_mumps_solve(
-                self.solver,
-                x.as_mut_data().as_mut_ptr(),
-                self.error_analysis_array_len_8.as_mut_ptr(),
-                self.error_analysis_option,
-                verb,
-            );
-            if status != SUCCESSFUL_EXIT {
-                return Err(handle_mumps_error_code(status));
+        // x₀ = particular solution of A·x = b_r
+        self.solve(x, mat, &b_r, verbose)?;
+
+        // check the compatibility of the unsymmetric system
+        let (_, dim) = self.null_space.dims();
+        if !symmetric && dim > 0 {
+            let mut ax = Vector::new(n);
+            mat.mat_vec_mul(&mut ax, 1.0, x)?;
+            let scale = b_r.as_data().iter().fold(0.0, |acc, v| f64::max(acc, f64::abs(*v)));
+            let residual = (0..n).fold(0.0, |acc, i| f64::max(acc, f64::abs(ax[i] - b_r[i])));
+            if residual > f64::sqrt(f64::EPSILON) * f64::max(1.0, scale) {
+                return Err("the pseudo-inverse solution with MUMPS requires a compatible system if A is unsymmetric");
             }
         }
-        self.time_solve_ns = self.stopwatch.stop();
 
-        // done
+        // x = x₀ - N·Nᵀ·x₀
+        remove_component(x, &self.null_space);
         Ok(())
     }
 
     /// Updates the stats structure (should be called after solve)
     fn update_stats(&self, stats: &mut StatsLinSol) {
         stats.main.solver = "MUMPS".to_string();
-        stats.determinant.mantissa_real = self.determinant_coefficient;
-        stats.determinant.mantissa_imag = 0.0;
-        stats.determinant.base = 2.0;
-        stats.determinant.exponent = self.determinant_exponent;
+        stats
+            .determinant
+            .set(self.determinant_coefficient, 0.0, 2.0, self.determinant_exponent);
+        if self.null_pivot_detection {
+            let ndim = self.initialized_ndim;
+            let count = usize::min(self.number_of_null_pivots as usize, ndim);
+            if count > 0 {
+                // MUMPS excludes the null pivots from the determinant (ICNTL(33))
+                stats.determinant.set(0.0, 0.0, 10.0, 0.0);
+            }
+            stats.rank.null_pivot_detection = true;
+            stats.rank.number_of_null_pivots = count;
+            stats.rank.null_space_dimension = count;
+            stats.rank.rank = ndim - count;
+        }
         stats.output.effective_ordering = match self.effective_ordering {
             MUMPS_ORDERING_AMD => "Amd".to_string(),
             MUMPS_ORDERING_AMF => "Amf".to_string(),
@@ -421,6 +1062,7 @@ impl LinSolTrait for SolverMUMPS {
         stats.mumps_stats.condition_number2 = self.error_analysis_array_len_8[7];
         stats.time_nanoseconds.initialize = self.time_initialize_ns;
         stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.refactorize = self.time_refactorize_ns;
         stats.time_nanoseconds.solve = self.time_solve_ns;
     }
 
@@ -438,6 +1080,41 @@ impl LinSolTrait for SolverMUMPS {
     fn get_ns_solve(&self) -> u128 {
         self.time_solve_ns
     }
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        self.time_refactorize_ns
+    }
+}
+
+/// Computes an orthonormal basis of the space spanned by the columns of `a` (modified Gram-Schmidt)
+fn orthonormal_basis(a: &Matrix) -> Matrix {
+    let (n, p) = a.dims();
+    let mut basis: Vec<Vector> = Vec::new();
+    for j in 0..p {
+        let mut v = Vector::initialized(n, |i| a.get(i, j));
+        let norm0 = f64::sqrt((0..n).fold(0.0, |acc, i| acc + v[i] * v[i]));
+        for q in &basis {
+            let dot = (0..n).fold(0.0, |acc, i| acc + q[i] * v[i]);
+            for i in 0..n {
+                v[i] -= dot * q[i];
+            }
+        }
+        let norm = f64::sqrt((0..n).fold(0.0, |acc, i| acc + v[i] * v[i]));
+        if norm > f64::sqrt(f64::EPSILON) * norm0 {
+            for i in 0..n {
+                v[i] /= norm;
+            }
+            basis.push(v);
+        }
+    }
+    let mut res = Matrix::new(n, basis.len());
+    for (j, q) in basis.iter().enumerate() {
+        for i in 0..n {
+            res.set(i, j, q[i]);
+        }
+    }
+    res
 }
 
 pub(crate) const MUMPS_ORDERING_AMD: i32 = 0; // Amd (page 35)
@@ -578,7 +1255,7 @@ pub(crate) fn handle_mumps_error_code(err: i32) -> StrError {
 mod tests {
     use super::*;
     use crate::{CooMatrix, Samples};
-    use russell_lab::{approx_eq, vec_approx_eq};
+    use russell_lab::{approx_eq, mat_approx_eq, mat_mat_mul, mat_t_mat_mul, vec_approx_eq, vec_mat_mul};
     use serial_test::serial;
 
     // IMPORTANT:
@@ -666,6 +1343,156 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn factorize_with_null_pivot_detection_works() {
+        // singular matrix (pure-Neumann Laplacian): null(A) = span{(1,1,1,1,1)}
+        let n = 5;
+        let mut coo = CooMatrix::new(n, n, 3 * n - 2, Sym::No).unwrap();
+        for i in 0..n {
+            let d = if i == 0 || i == n - 1 { 1.0 } else { 2.0 };
+            coo.put(i, i, d).unwrap();
+            if i > 0 {
+                coo.put(i, i - 1, -1.0).unwrap();
+                coo.put(i - 1, i, -1.0).unwrap();
+            }
+        }
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut params = LinSolParams::new();
+        params.compute_determinant = true;
+        params.null_pivot_detection = true;
+        let mut solver = SolverMUMPS::new().unwrap();
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert!(stats.rank.null_pivot_detection);
+        assert_eq!(stats.rank.number_of_null_pivots, 1);
+        assert_eq!(stats.rank.null_space_dimension, 1);
+        assert_eq!(stats.rank.rank, n - 1);
+        assert_eq!(stats.determinant.base, 10.0);
+        assert_eq!(stats.determinant.value(), 0.0);
+
+        // non-singular matrix: no null pivots and the determinant is given in base 10
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut solver = SolverMUMPS::new().unwrap();
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.rank.number_of_null_pivots, 0);
+        assert_eq!(stats.rank.rank, 5);
+        assert_eq!(stats.determinant.base, 10.0);
+        approx_eq(stats.determinant.mantissa_real, 1.14, 1e-13);
+        assert_eq!(stats.determinant.exponent, 2.0);
+    }
+
+    #[test]
+    #[serial]
+    fn null_space_and_pseudo_inverse_capture_errors() {
+        let mut solver = SolverMUMPS::new().unwrap();
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut x = Vector::new(5);
+        let rhs = Vector::new(5);
+        assert_eq!(
+            solver.null_space().err(),
+            Some("the function factorize must be called before null_space")
+        );
+        assert_eq!(
+            solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).err(),
+            Some("the function factorize must be called before solve_pseudo_inverse")
+        );
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.null_space().err(),
+            Some("the null pivot detection must be enabled to compute the null space")
+        );
+        assert_eq!(
+            solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).err(),
+            Some("the null pivot detection must be enabled to compute the pseudo-inverse solution")
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn null_space_and_pseudo_inverse_work_symmetric() {
+        // 1D Laplacian with free ends (pure Neumann problem): the null space is spanned by constant vectors
+        let n = 5;
+        let mut coo = CooMatrix::new(n, n, 2 * n - 1, Sym::YesLower).unwrap();
+        for i in 0..n {
+            coo.put(i, i, if i == 0 || i == n - 1 { 1.0 } else { 2.0 }).unwrap();
+            if i > 0 {
+                coo.put(i, i - 1, -1.0).unwrap();
+            }
+        }
+        let mut mat = SparseMatrix::from_coo(coo);
+        let a = mat.as_dense();
+        let mut params = LinSolParams::new();
+        params.null_pivot_detection = true;
+        let mut solver = SolverMUMPS::new().unwrap();
+        solver.factorize(&mut mat, Some(params)).unwrap();
+
+        // null space
+        let null = solver.null_space().unwrap();
+        assert_eq!(null.dims(), (n, 1));
+        let s = f64::signum(null.get(0, 0)) / f64::sqrt(n as f64);
+        for i in 0..n {
+            approx_eq(null.get(i, 0), s, 1e-12);
+        }
+
+        // compatible system: x is the solution with zero mean
+        let rhs = Vector::from(&[1.0, 0.0, 0.0, 0.0, -1.0]);
+        let mut x = Vector::new(n);
+        solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &[2.0, 1.0, 0.0, -1.0, -2.0], 1e-12);
+
+        // incompatible system: the mean of rhs is discarded
+        let rhs = Vector::from(&[2.0, 1.0, 1.0, 1.0, 0.0]);
+        solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &[2.0, 1.0, 0.0, -1.0, -2.0], 1e-12);
+        let mut ax = Vector::new(n);
+        russell_lab::mat_vec_mul(&mut ax, 1.0, &a, &x).unwrap();
+        vec_approx_eq(&ax, &[1.0, 0.0, 0.0, 0.0, -1.0], 1e-12);
+    }
+
+    #[test]
+    #[serial]
+    fn null_space_and_pseudo_inverse_work_unsymmetric() {
+        // rank-deficient matrix: null(A) = span{(1,-2,1)}
+        let mut coo = CooMatrix::new(3, 3, 9, Sym::No).unwrap();
+        let values = [[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [1.0, 1.0, 1.0]];
+        for (i, row) in values.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                coo.put(i, j, *value).unwrap();
+            }
+        }
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut params = LinSolParams::new();
+        params.null_pivot_detection = true;
+        let mut solver = SolverMUMPS::new().unwrap();
+        solver.factorize(&mut mat, Some(params)).unwrap();
+
+        // null space
+        let null = solver.null_space().unwrap();
+        assert_eq!(null.dims(), (3, 1));
+        let s = f64::signum(null.get(0, 0)) / f64::sqrt(6.0);
+        let v = Vector::initialized(3, |i| null.get(i, 0));
+        vec_approx_eq(&v, &[s, -2.0 * s, s], 1e-12);
+
+        // compatible system: rhs = A·(1,1,1) and (1,1,1) ⟂ null(A)
+        let rhs = Vector::from(&[6.0, 12.0, 3.0]);
+        let mut x = Vector::new(3);
+        solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &[1.0, 1.0, 1.0], 1e-12);
+
+        // incompatible system
+        let rhs = Vector::from(&[1.0, 1.0, 1.0]);
+        assert_eq!(
+            solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).err(),
+            Some("the pseudo-inverse solution with MUMPS requires a compatible system if A is unsymmetric")
+        );
+    }
+
     #[test]
     #[serial]
     fn solve_handles_errors() {
@@ -790,6 +1617,144 @@ mod tests {
         vec_approx_eq(&x, x_correct, 1e-10);
     }
 
+    #[test]
+    #[serial]
+    fn analyze_factorize_numeric_and_refactorize_work() {
+        let mut solver = SolverMUMPS::new().unwrap();
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut x = Vector::new(5);
+        let rhs = Vector::from(&[8.0, 45.0, -3.0, 3.0, 19.0]);
+        let x_correct = &[1.0, 2.0, 3.0, 4.0, 5.0];
+
+        // errors
+        assert_eq!(
+            solver.factorize_numeric(&mut mat).err(),
+            Some("the function analyze must be called before factorize_numeric")
+        );
+        assert_eq!(
+            solver.refactorize(&mut mat).err(),
+            Some("the function factorize must be called before refactorize")
+        );
+
+        // analyze and factorize
+        solver.analyze(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.analyze(&mut mat, None).err(),
+            Some("the analysis has been performed already")
+        );
+        solver.factorize_numeric(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, x_correct, 1e-14);
+
+        // refactorize with other values (same structure)
+        mat.get_coo_mut()
+            .unwrap()
+            .get_values_mut()
+            .iter_mut()
+            .for_each(|v| *v *= 2.0);
+        solver.refactorize(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &[0.5, 1.0, 1.5, 2.0, 2.5], 1e-14);
+
+        // the pattern must not change
+        let mut other = mat.get_coo().unwrap().clone();
+        other.indices_i.swap(0, 1);
+        let mut other = SparseMatrix::from_coo(other);
+        assert_eq!(
+            solver.refactorize(&mut other).err(),
+            Some("subsequent factorizations must use the same matrix (pattern differs)")
+        );
+        assert_eq!(
+            solver.factorize_numeric(&mut other).err(),
+            Some("subsequent factorizations must use the same matrix (pattern differs)")
+        );
+
+        // update stats
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.time_nanoseconds.refactorize, solver.get_ns_refact());
+    }
+
+    #[test]
+    #[serial]
+    fn solve_transpose_and_multiple_works() {
+        let mut solver = SolverMUMPS::new().unwrap();
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let a = coo.as_dense();
+        let mut mat = SparseMatrix::from_coo(coo);
+        solver.factorize(&mut mat, None).unwrap();
+
+        // Aᵀ · x = rhs
+        let x_correct = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut rhs = Vector::new(5);
+        vec_mat_mul(&mut rhs, 1.0, &x_correct, &a).unwrap();
+        let mut x = Vector::new(5);
+        solver.solve_transpose(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-13);
+
+        // A · X = RHS and Aᵀ · X = RHS
+        let xx_correct = Matrix::from(&[[1.0, 5.0], [2.0, 4.0], [3.0, 3.0], [4.0, 2.0], [5.0, 1.0]]);
+        let mut rhs_mat = Matrix::new(5, 2);
+        mat_mat_mul(&mut rhs_mat, 1.0, &a, &xx_correct, 0.0).unwrap();
+        let mut xx = Matrix::new(5, 2);
+        solver.solve_multiple(&mut xx, &mat, &rhs_mat, false, false).unwrap();
+        mat_approx_eq(&xx, &xx_correct, 1e-13);
+        mat_t_mat_mul(&mut rhs_mat, 1.0, &a, &xx_correct, 0.0).unwrap();
+        solver.solve_multiple(&mut xx, &mat, &rhs_mat, true, false).unwrap();
+        mat_approx_eq(&xx, &xx_correct, 1e-13);
+    }
+
+    #[test]
+    #[serial]
+    fn solve_sparse_rhs_works() {
+        let mut solver = SolverMUMPS::new().unwrap();
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut coo_rhs = CooMatrix::new(5, 3, 6, Sym::No).unwrap();
+        for (i, value) in [8.0, 45.0, -3.0, 3.0, 19.0].iter().enumerate() {
+            coo_rhs.put(i, 0, *value).unwrap();
+        }
+        coo_rhs.put(2, 2, 1.0).unwrap(); // the second column is empty
+        let rhs = CscMatrix::from_coo(&coo_rhs).unwrap();
+        let mut xx = Matrix::new(5, 3);
+
+        // errors
+        assert_eq!(
+            solver.solve_sparse_rhs(&mut xx, &mat, &rhs, false, false).err(),
+            Some("the function factorize must be called before solve")
+        );
+        solver.factorize(&mut mat, None).unwrap();
+        let (coo_sym, _, _, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let rhs_sym = CscMatrix::from_coo(&coo_sym).unwrap();
+        assert_eq!(
+            solver.solve_sparse_rhs(&mut xx, &mat, &rhs_sym, false, false).err(),
+            Some("the sparse matrix of right-hand sides must have Sym::No")
+        );
+        let mut wrong = Matrix::new(5, 2);
+        assert_eq!(
+            solver.solve_sparse_rhs(&mut wrong, &mat, &rhs, false, false).err(),
+            Some("the dimensions of the matrix of unknown values X are incorrect")
+        );
+
+        // A · X = RHS and Aᵀ · X = RHS (compared with the dense right-hand sides)
+        let rhs_dense = rhs.as_dense();
+        let mut xx_correct = Matrix::new(5, 3);
+        for transpose in [false, true] {
+            solver
+                .solve_multiple(&mut xx_correct, &mat, &rhs_dense, transpose, false)
+                .unwrap();
+            solver.solve_sparse_rhs(&mut xx, &mat, &rhs, transpose, false).unwrap();
+            mat_approx_eq(&xx, &xx_correct, 1e-13);
+        }
+        for i in 0..5 {
+            approx_eq(xx.get(i, 1), 0.0, 1e-15);
+        }
+        solver.solve_sparse_rhs(&mut xx, &mat, &rhs, false, false).unwrap();
+        let x0 = Vector::initialized(5, |i| xx.get(i, 0));
+        vec_approx_eq(&x0, &[1.0, 2.0, 3.0, 4.0, 5.0], 1e-13);
+    }
+
     #[test]
     #[serial]
     fn solve_works_symmetric() {
@@ -820,6 +1785,139 @@ mod tests {
         vec_approx_eq(&x_again, x_correct, 1e-11);
     }
 
+    #[test]
+    #[serial]
+    fn schur_functions_handle_errors() {
+        let mut solver = SolverMUMPS::new().unwrap();
+        assert_eq!(
+            solver.set_schur_indices(&[]).err(),
+            Some("the list of Schur indices must not be empty")
+        );
+        assert_eq!(
+            solver.set_schur_indices(&[1, 1]).err(),
+            Some("the list of Schur indices must not contain duplicates")
+        );
+        assert_eq!(
+            solver.get_schur_complement().err(),
+            Some("the Schur complement has not been requested (see set_schur_indices)")
+        );
+        solver.set_schur_indices(&[4]).unwrap();
+        assert_eq!(
+            solver.get_schur_complement().err(),
+            Some("the function factorize must be called before using the Schur complement")
+        );
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 1, 1.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("the Schur indices must be smaller than the matrix dimension")
+        );
+        let mut solver = SolverMUMPS::new().unwrap();
+        solver.set_schur_indices(&[0, 1]).unwrap();
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("the number of Schur variables must be smaller than the matrix dimension")
+        );
+        let mut solver = SolverMUMPS::new().unwrap();
+        solver.set_schur_indices(&[1]).unwrap();
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.set_schur_indices(&[0]).err(),
+            Some("the Schur indices must be set before the first call to factorize")
+        );
+        let mut x = Vector::new(2);
+        let mut reduced = Vector::new(1);
+        let rhs = Vector::new(2);
+        assert_eq!(
+            solver.schur_expand(&mut x, &reduced, false).err(),
+            Some("the function schur_condense must be called before schur_expand")
+        );
+        let mut wrong = Vector::new(2);
+        assert_eq!(
+            solver.schur_condense(&mut wrong, &rhs, false).err(),
+            Some("the dimension of the reduced right-hand side vector is incorrect")
+        );
+        let wrong_rhs = Vector::new(3);
+        assert_eq!(
+            solver.schur_condense(&mut reduced, &wrong_rhs, false).err(),
+            Some("the dimension of the right-hand side vector is incorrect")
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn schur_complement_works() {
+        // ┌         ┐
+        // │ 4 0 1 0 │
+        // │ 0 2 0 1 │  Schur variables: 2 and 3
+        // │ 1 0 3 1 │
+        // │ 0 2 1 5 │
+        // └         ┘
+        let mut coo = CooMatrix::new(4, 4, 9, Sym::No).unwrap();
+        coo.put(0, 0, 4.0).unwrap();
+        coo.put(0, 2, 1.0).unwrap();
+        coo.put(1, 1, 2.0).unwrap();
+        coo.put(1, 3, 1.0).unwrap();
+        coo.put(2, 0, 1.0).unwrap();
+        coo.put(2, 2, 3.0).unwrap();
+        coo.put(2, 3, 1.0).unwrap();
+        coo.put(3, 1, 2.0).unwrap();
+        coo.put(3, 2, 1.0).unwrap();
+        coo.put(3, 3, 5.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut solver = SolverMUMPS::new().unwrap();
+        solver.set_schur_indices(&[2, 3]).unwrap();
+        solver.factorize(&mut mat, None).unwrap();
+
+        // dense and sparse Schur complement
+        let s = solver.get_schur_complement().unwrap();
+        mat_approx_eq(&s, &[[2.75, 1.0], [1.0, 4.0]], 1e-14);
+        let s_sparse = solver.get_schur_complement_sparse(0.0).unwrap();
+        mat_approx_eq(&s_sparse.as_dense(), &[[2.75, 1.0], [1.0, 4.0]], 1e-14);
+
+        // condensation
+        let rhs = Vector::from(&[4.0, 2.0, 5.0, 7.0]);
+        let mut reduced = Vector::new(2);
+        solver.schur_condense(&mut reduced, &rhs, false).unwrap();
+        vec_approx_eq(&reduced, &[4.0, 5.0], 1e-14);
+
+        // expansion (the solution of S · x₂ = r₂ is x₂ = [1.1, 0.975])
+        let x_schur = Vector::from(&[1.1, 0.975]);
+        let mut x = Vector::new(4);
+        solver.schur_expand(&mut x, &x_schur, false).unwrap();
+        vec_approx_eq(&x, &[0.725, 0.5125, 1.1, 0.975], 1e-14);
+    }
+
+    #[test]
+    #[serial]
+    fn schur_complement_works_symmetric() {
+        // ┌         ┐
+        // │ 4 0 1 0 │
+        // │ 0 2 0 1 │  Schur variables: 2 and 3
+        // │ 1 0 3 1 │
+        // │ 0 1 1 5 │
+        // └         ┘
+        let mut coo = CooMatrix::new(4, 4, 7, Sym::YesLower).unwrap();
+        coo.put(0, 0, 4.0).unwrap();
+        coo.put(1, 1, 2.0).unwrap();
+        coo.put(2, 0, 1.0).unwrap();
+        coo.put(2, 2, 3.0).unwrap();
+        coo.put(3, 1, 1.0).unwrap();
+        coo.put(3, 2, 1.0).unwrap();
+        coo.put(3, 3, 5.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut solver = SolverMUMPS::new().unwrap();
+        solver.set_schur_indices(&[2, 3]).unwrap();
+        solver.factorize(&mut mat, None).unwrap();
+        let s = solver.get_schur_complement().unwrap();
+        mat_approx_eq(&s, &[[2.75, 1.0], [1.0, 4.5]], 1e-14);
+        let s_sparse = solver.get_schur_complement_sparse(0.0).unwrap();
+        assert_eq!(s_sparse.get_info().3, Sym::YesLower);
+        mat_approx_eq(&s_sparse.as_dense(), &[[2.75, 1.0], [1.0, 4.5]], 1e-14);
+    }
+
     #[test]
     fn ordering_and_scaling_works() {
         assert_eq!(mumps_ordering(Ordering::Amd), MUMPS_ORDERING_AMD);
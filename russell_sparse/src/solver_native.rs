@@ -0,0 +1,1308 @@
+use super::{
+    CscMatrix, FactorKind, LinSolParams, LinSolTrait, NumCscMatrix, Ordering, SparseFactor, SparseMatrix, StatsLinSol,
+    Sym,
+};
+use crate::StrError;
+use russell_lab::{Matrix, Stopwatch, Vector};
+
+/// Implements a native (pure Rust) sparse direct solver
+///
+/// This solver does not depend on external libraries. The matrix is first reordered by a
+/// fill-reducing symmetric permutation (approximate minimum degree or nested dissection)
+/// computed from the pattern of `A + Aᵀ`.
+/// Then, symmetric positive-definite matrices are factorized by the up-looking Cholesky method
+/// and all other matrices are factorized by the left-looking (Gilbert-Peierls) LU method with
+/// threshold partial pivoting.
+///
+/// **Note:** The Cholesky factorization is selected if the matrix is symmetric and
+/// [LinSolParams::positive_definite] is true.
+///
+/// If [LinSolParams::mixed_precision] is true, the factors are computed and applied in single
+/// precision (f32); then, the accuracy should be recovered by the iterative refinement performed
+/// by [crate::LinSolver::solve] (see [LinSolParams::refinement_max_iterations]).
+///
+/// If [LinSolParams::null_pivot_detection] is true (and the factorization is computed in double precision),
+/// the null pivots are replaced by one; thus, the factors correspond to `A' = A + E_r·E_cᵀ`, where
+/// `E_r` and `E_c` collect the unit vectors associated with the rows and columns of the null pivots.
+/// Then, the null spaces of `A` and `Aᵀ` are computed from `Z = A'⁻¹·E_r` and `W = A'⁻ᵀ·E_c` (see
+/// [LinSolTrait::null_space]), and the minimum-norm least-squares solution is available
+/// (see [LinSolTrait::solve_pseudo_inverse]).
+///
+/// Reference: Davis TA (2006) Direct Methods for Sparse Linear Systems, SIAM, Philadelphia
+pub struct SolverNative {
+    /// Holds the symbolic analysis and the factors
+    factor: Option<SparseFactor<f64>>,
+
+    /// Holds the symbolic analysis and the factors in single precision (mixed-precision mode)
+    factor_single: Option<SparseFactor<f32>>,
+
+    /// Indicates whether the sparse matrix has been factorized or not
+    factorized: bool,
+
+    /// Holds the symmetric flag saved in initialize
+    initialized_sym: Sym,
+
+    /// Holds the matrix dimension saved in initialize
+    initialized_ndim: usize,
+
+    /// Holds the number of non-zeros saved in initialize
+    initialized_nnz: usize,
+
+    /// Holds the fingerprint of the sparsity pattern saved in initialize
+    initialized_fingerprint: u64,
+
+    /// Holds the parameters given to analyze (or factorize)
+    params: LinSolParams,
+
+    /// Holds the used ordering (after factorize)
+    effective_ordering: Ordering,
+
+    /// Holds the determinant coefficient (if requested)
+    ///
+    /// det = coefficient * pow(10, exponent)
+    determinant_coefficient: f64,
+
+    /// Holds the determinant exponent (if requested)
+    ///
+    /// det = coefficient * pow(10, exponent)
+    determinant_exponent: f64,
+
+    /// Holds the (row, column) indices of the null pivots (after factorize with null pivot detection)
+    null_pivots: Vec<(usize, usize)>,
+
+    /// Holds Z = A'⁻¹·E_r (one column per null pivot)
+    null_z: Matrix,
+
+    /// Holds K = I - E_cᵀ·Z (the null space of A is Z·null(K))
+    null_k: Matrix,
+
+    /// Holds an orthonormal basis of the null space of A
+    null_space: Matrix,
+
+    /// Holds an orthonormal basis of the null space of Aᵀ
+    left_null_space: Matrix,
+
+    /// Stopwatch to measure computation times
+    stopwatch: Stopwatch,
+
+    /// Time spent on initialize in nanoseconds
+    time_initialize_ns: u128,
+
+    /// Time spent on factorize in nanoseconds
+    time_factorize_ns: u128,
+
+    /// Time spent on solve in nanoseconds
+    time_solve_ns: u128,
+
+    /// Time spent on refactorize in nanoseconds
+    time_refactorize_ns: u128,
+}
+
+impl SolverNative {
+    /// Allocates a new instance
+    pub fn new() -> Result<Self, StrError> {
+        Ok(SolverNative {
+            factor: None,
+            factor_single: None,
+            factorized: false,
+            initialized_sym: Sym::No,
+            initialized_ndim: 0,
+            initialized_nnz: 0,
+            initialized_fingerprint: 0,
+            params: LinSolParams::new(),
+            effective_ordering: Ordering::No,
+            determinant_coefficient: 0.0,
+            determinant_exponent: 0.0,
+            null_pivots: Vec::new(),
+            null_z: Matrix::new(0, 0),
+            null_k: Matrix::new(0, 0),
+            null_space: Matrix::new(0, 0),
+            left_null_space: Matrix::new(0, 0),
+            stopwatch: Stopwatch::new(),
+            time_initialize_ns: 0,
+            time_factorize_ns: 0,
+            time_solve_ns: 0,
+            time_refactorize_ns: 0,
+        })
+    }
+
+    /// Checks whether the matrix has the same structure as the one given to analyze (or the first factorize)
+    fn check_pattern(&self, csc: &CscMatrix) -> Result<(), StrError> {
+        if csc.symmetric != self.initialized_sym {
+            return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+        }
+        if csc.nrow != self.initialized_ndim {
+            return Err("subsequent factorizations must use the same matrix (ndim differs)");
+        }
+        if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
+            return Err("subsequent factorizations must use the same matrix (nnz differs)");
+        }
+        if csc.get_pattern_fingerprint() != self.initialized_fingerprint {
+            return Err("subsequent factorizations must use the same matrix (pattern differs)");
+        }
+        Ok(())
+    }
+
+    /// Returns the number of non-zeros in the factors (after factorize)
+    pub fn get_nnz_factors(&self) -> usize {
+        match (&self.factor, &self.factor_single) {
+            (Some(f), _) => f.nnz_factors(),
+            (None, Some(f)) => f.nnz_factors(),
+            (None, None) => 0,
+        }
+    }
+
+    /// Indicates whether the symbolic analysis has been performed or not
+    fn analyzed(&self) -> bool {
+        self.factor.is_some() || self.factor_single.is_some()
+    }
+
+    /// Returns the selected factorization method (after analyze)
+    fn kind(&self) -> Option<FactorKind> {
+        match (&self.factor, &self.factor_single) {
+            (Some(f), _) => Some(f.kind()),
+            (None, Some(f)) => Some(f.kind()),
+            (None, None) => None,
+        }
+    }
+
+    /// Performs the numeric factorization (or refactorization) with the available factors
+    fn factorize_values(&mut self, csc: &CscMatrix, refactorize: bool) -> Result<(), StrError> {
+        let tau = self.params.native_pivot_threshold;
+        let det = match (self.factor.as_mut(), self.factor_single.as_mut()) {
+            (Some(factor), _) => {
+                if self.params.null_pivot_detection {
+                    factor.set_null_pivot_tolerance(Some(self.params.null_pivot_tolerance));
+                } else {
+                    factor.set_null_pivot_tolerance(None);
+                }
+                if refactorize {
+                    factor.refactorize(csc)?;
+                } else {
+                    factor.factorize(csc, tau)?;
+                }
+                self.null_pivots = factor.null_pivots();
+                if self.params.null_pivot_detection {
+                    self.compute_null_spaces(csc.symmetric != Sym::No)?;
+                }
+                if !self.params.compute_determinant {
+                    return Ok(());
+                }
+                let factor = self.factor.as_ref().unwrap(); // unwrap OK because of the match
+                factor.determinant()
+            }
+            (None, Some(factor)) => {
+                let csc_single = csc_to_single(csc)?;
+                if refactorize {
+                    factor.refactorize(&csc_single)?;
+                } else {
+                    factor.factorize(&csc_single, tau)?;
+                }
+                if !self.params.compute_determinant {
+                    return Ok(());
+                }
+                let (c, e) = factor.determinant();
+                (c as f64, e)
+            }
+            (None, None) => return Err("the function analyze must be called before factorize_numeric"),
+        };
+        (self.determinant_coefficient, self.determinant_exponent) = det;
+        Ok(())
+    }
+
+    /// Computes the null spaces of A and Aᵀ using the factors of A' = A + E_r·E_cᵀ
+    ///
+    /// Since `A = A' - E_r·E_cᵀ`, the vector `v = Z·y` with `Z = A'⁻¹·E_r` satisfies `A·v = E_r·(I - E_cᵀ·Z)·y`.
+    /// Thus, the null space of A is `Z·null(K)` with `K = I - E_cᵀ·Z`. Likewise, the null space of Aᵀ
+    /// is `W·null(I - E_rᵀ·W)` with `W = A'⁻ᵀ·E_c`.
+    fn compute_null_spaces(&mut self, symmetric: bool) -> Result<(), StrError> {
+        let factor = self.factor.as_mut().unwrap(); // unwrap OK because this is called after factorize
+        let n = self.initialized_ndim;
+        let p = self.null_pivots.len();
+        let mut e = vec![0.0; n];
+        let mut z = vec![0.0; n];
+        self.null_z = Matrix::new(n, p);
+        self.null_k = Matrix::new(p, p);
+        let mut kt = Matrix::new(p, p);
+        let mut w = Matrix::new(n, p);
+        for m in 0..p {
+            let (r, c) = self.null_pivots[m];
+            e[r] = 1.0;
+            factor.solve(&mut z, &e)?;
+            e[r] = 0.0;
+            for (i, zi) in z.iter().enumerate() {
+                self.null_z.set(i, m, *zi);
+            }
+            if !symmetric {
+                e[c] = 1.0;
+                factor.solve_transpose(&mut z, &e, false)?;
+                e[c] = 0.0;
+                for (i, zi) in z.iter().enumerate() {
+                    w.set(i, m, *zi);
+                }
+            }
+        }
+        for m in 0..p {
+            for mm in 0..p {
+                let (r, c) = self.null_pivots[mm];
+                let delta = if m == mm { 1.0 } else { 0.0 };
+                self.null_k.set(mm, m, delta - self.null_z.get(c, m));
+                if !symmetric {
+                    kt.set(mm, m, delta - w.get(r, m));
+                }
+            }
+        }
+        self.null_space = null_space_basis(&self.null_z, &self.null_k);
+        self.left_null_space = if symmetric {
+            self.null_space.clone()
+        } else {
+            null_space_basis(&w, &kt)
+        };
+        Ok(())
+    }
+
+    /// Checks the data and solves `A · x = rhs` or `Aᵀ · x = rhs` using the factors
+    fn solve_internal(
+        &mut self,
+        x: &mut Vector,
+        mat: &SparseMatrix,
+        rhs: &Vector,
+        transpose: bool,
+    ) -> Result<(), StrError> {
+        // check
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+
+        // access CSC matrix
+        // (possibly already converted from COO, because factorize was (should have been) called)
+        let csc = mat.get_csc()?;
+
+        // check already factorized data
+        let (nrow, ncol, nnz, sym) = csc.get_info();
+        if sym != self.initialized_sym {
+            return Err("solve must use the same matrix (symmetric differs)");
+        }
+        if nrow != self.initialized_ndim || ncol != self.initialized_ndim {
+            return Err("solve must use the same matrix (ndim differs)");
+        }
+        if nnz != self.initialized_nnz {
+            return Err("solve must use the same matrix (nnz differs)");
+        }
+
+        // check vectors
+        if x.dim() != self.initialized_ndim {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.dim() != self.initialized_ndim {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+
+        // solve using the factors
+        self.stopwatch.reset();
+        if let Some(factor) = self.factor.as_mut() {
+            if transpose {
+                factor.solve_transpose(x.as_mut_data(), rhs.as_data(), false)?;
+            } else {
+                factor.solve(x.as_mut_data(), rhs.as_data())?;
+            }
+        } else {
+            let factor = self.factor_single.as_mut().unwrap(); // unwrap OK because factorized is true
+            let rhs_single: Vec<f32> = rhs.as_data().iter().map(|v| *v as f32).collect();
+            let mut x_single = vec![0.0_f32; self.initialized_ndim];
+            if transpose {
+                factor.solve_transpose(&mut x_single, &rhs_single, false)?;
+            } else {
+                factor.solve(&mut x_single, &rhs_single)?;
+            }
+            for i in 0..self.initialized_ndim {
+                x[i] = x_single[i] as f64;
+            }
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+
+        // done
+        Ok(())
+    }
+}
+
+impl LinSolTrait for SolverNative {
+    /// Performs the factorization (and analysis/initialization if needed)
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`). Symmetric matrices
+    ///   may have any [Sym] representation.
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// # Notes
+    ///
+    /// 1. The structure of the matrix (nrow, ncol, nnz, sym, and the sparsity pattern) must be
+    ///    exactly the same among multiple calls to `factorize`. The values may differ
+    ///    from call to call, nonetheless.
+    /// 2. The first call to `factorize` will define the structure which must be
+    ///    kept the same for the next calls. The first call also selects the
+    ///    factorization method (Cholesky or LU) and computes the ordering.
+    /// 3. If the structure of the matrix needs to be changed, the solver must
+    ///    be "dropped" and a new solver allocated.
+    /// 4. [Ordering::No] disables the fill-reducing ordering; [Ordering::Metis] selects the
+    ///    native nested dissection ordering (METIS is not called); the other options
+    ///    select the approximate minimum degree ordering.
+    fn factorize(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        // parameters
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if par.native_pivot_threshold <= 0.0 || par.native_pivot_threshold > 1.0 {
+            return Err("the native pivot threshold must satisfy 0 < τ ≤ 1");
+        }
+
+        // perform the symbolic analysis just once
+        if !self.analyzed() {
+            self.analyze(mat, Some(par))?;
+        }
+
+        // numeric factorization
+        self.params = par;
+        self.factorize_numeric(mat)
+    }
+
+    /// Performs the symbolic analysis (ordering and selection of the factorization method) only
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`). Symmetric matrices
+    ///   may have any [Sym] representation.
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The analysis is performed just once; i.e., the solver must be
+    /// "dropped" and a new solver allocated if the structure of the matrix changes.
+    fn analyze(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        // check
+        if self.analyzed() {
+            return Err("the analysis has been performed already");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        if csc.nrow != csc.ncol {
+            return Err("the matrix must be square");
+        }
+
+        // parameters
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if par.native_pivot_threshold <= 0.0 || par.native_pivot_threshold > 1.0 {
+            return Err("the native pivot threshold must satisfy 0 < τ ≤ 1");
+        }
+        self.params = par;
+
+        // symbolic analysis
+        self.stopwatch.reset();
+        let kind = if csc.symmetric != Sym::No && par.positive_definite {
+            FactorKind::Cholesky
+        } else {
+            FactorKind::Lu
+        };
+        if par.mixed_precision {
+            self.factor_single = Some(SparseFactor::analyze(&csc_to_single(csc)?, kind, par.ordering)?);
+        } else {
+            self.factor = Some(SparseFactor::analyze(csc, kind, par.ordering)?);
+        }
+        self.effective_ordering = match par.ordering {
+            Ordering::No | Ordering::Metis => par.ordering,
+            _ => Ordering::Amd,
+        };
+        self.time_initialize_ns = self.stopwatch.stop();
+
+        // done
+        self.initialized_sym = csc.symmetric;
+        self.initialized_ndim = csc.nrow;
+        self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
+        self.initialized_fingerprint = csc.get_pattern_fingerprint();
+        Ok(())
+    }
+
+    /// Performs the numeric factorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one given to `analyze`
+    fn factorize_numeric(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        // check
+        if !self.analyzed() {
+            return Err("the function analyze must be called before factorize_numeric");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        self.check_pattern(csc)?;
+
+        // numeric factorization
+        self.factorized = false;
+        self.stopwatch.reset();
+        self.factorize_values(csc, false)?;
+        self.time_factorize_ns = self.stopwatch.stop();
+
+        // done
+        self.factorized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric refactorization reusing the pivot sequence and the patterns of the factors
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one previously factorized
+    ///
+    /// **Warning:** No pivoting is performed; thus, the factorization may be inaccurate if the
+    /// values have changed significantly. In this case, call `factorize_numeric` instead.
+    fn refactorize(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        // check
+        if !self.factorized {
+            return Err("the function factorize must be called before refactorize");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        self.check_pattern(csc)?;
+
+        // numeric refactorization
+        self.factorized = false;
+        self.stopwatch.reset();
+        self.factorize_values(csc, true)?;
+        self.time_refactorize_ns = self.stopwatch.stop();
+
+        // done
+        self.factorized = true;
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square.
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve(&mut self, x: &mut Vector, mat: &SparseMatrix, rhs: &Vector, _verbose: bool) -> Result<(), StrError> {
+        self.solve_internal(x, mat, rhs, false)
+    }
+
+    /// Computes the solution of the transposed linear system
+    ///
+    /// Solves `Aᵀ · x = rhs` using the same factorization of A.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (not transposed); it must be square.
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_transpose(
+        &mut self,
+        x: &mut Vector,
+        mat: &SparseMatrix,
+        rhs: &Vector,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_internal(x, mat, rhs, true)
+    }
+
+    /// Returns an orthonormal basis of the null space of A (after factorize with null pivot detection)
+    fn null_space(&self) -> Result<Matrix, StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before null_space");
+        }
+        if !self.params.null_pivot_detection || self.factor.is_none() {
+            return Err("the null pivot detection must be enabled (in double precision) to compute the null space");
+        }
+        Ok(self.null_space.clone())
+    }
+
+    /// Computes the minimum-norm least-squares solution `x = A⁺ · rhs`
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square.
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- NOT AVAILABLE
+    ///
+    /// # Notes
+    ///
+    /// 1. The component of `rhs` along the null space of Aᵀ is removed; thus, `b_r = rhs - M·Mᵀ·rhs`.
+    /// 2. Then, `x₀ = A'⁻¹·b_r` is corrected by `x = x₀ + Z·c` with `K·c = E_cᵀ·x₀`, yielding `A·x = b_r`.
+    /// 3. Finally, the component of `x` along the null space of A is removed; thus, `x = x - N·Nᵀ·x`.
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_pseudo_inverse(
+        &mut self,
+        x: &mut Vector,
+        mat: &SparseMatrix,
+        rhs: &Vector,
+        _verbose: bool,
+    ) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before solve_pseudo_inverse");
+        }
+        if !self.params.null_pivot_detection || self.factor.is_none() {
+            return Err(
+                "the null pivot detection must be enabled (in double precision) to compute the pseudo-inverse solution",
+            );
+        }
+        let n = self.initialized_ndim;
+        if rhs.dim() != n {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+
+        // b_r = rhs - M·Mᵀ·rhs
+        let mut b_r = rhs.clone();
+        remove_component(&mut b_r, &self.left_null_space);
+
+        // x₀ = A'⁻¹·b_r
+        self.solve_internal(x, mat, &b_r, false)?;
+
+        // x = x₀ + Z·c with K·c = E_cᵀ·x₀
+        let p = self.null_pivots.len();
+        if p > 0 {
+            let mut g = Vector::initialized(p, |m| x[self.null_pivots[m].1]);
+            let mut k = self.null_k.clone();
+            let tol = null_tolerance(&k);
+            let pivots = gauss_jordan(&mut k, &mut g, tol);
+            for (i, &col) in pivots.iter().enumerate() {
+                for row in 0..n {
+                    x[row] += self.null_z.get(row, col) * g[i];
+                }
+            }
+        }
+
+        // x = x - N·Nᵀ·x
+        remove_component(x, &self.null_space);
+        Ok(())
+    }
+
+    /// Updates the stats structure (should be called after solve)
+    fn update_stats(&self, stats: &mut StatsLinSol) {
+        stats.main.solver = match self.kind() {
+            Some(FactorKind::Cholesky) => "Native-Cholesky".to_string(),
+            _ => "Native-LU".to_string(),
+        };
+        stats
+            .determinant
+            .set(self.determinant_coefficient, 0.0, 10.0, self.determinant_exponent);
+        if self.params.null_pivot_detection && self.factor.is_some() {
+            let (_, dim) = self.null_space.dims();
+            stats.rank.null_pivot_detection = true;
+            stats.rank.number_of_null_pivots = self.null_pivots.len();
+            stats.rank.null_space_dimension = dim;
+            stats.rank.rank = self.initialized_ndim - dim;
+        }
+        stats.output.effective_ordering = format!("{:?}", self.effective_ordering);
+        stats.output.effective_scaling = "No".to_string();
+        stats.time_nanoseconds.initialize = self.time_initialize_ns;
+        stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.refactorize = self.time_refactorize_ns;
+        stats.time_nanoseconds.solve = self.time_solve_ns;
+    }
+
+    /// Returns the nanoseconds spent on initialize
+    fn get_ns_init(&self) -> u128 {
+        self.time_initialize_ns
+    }
+
+    /// Returns the nanoseconds spent on factorize
+    fn get_ns_fact(&self) -> u128 {
+        self.time_factorize_ns
+    }
+
+    /// Returns the nanoseconds spent on solve
+    fn get_ns_solve(&self) -> u128 {
+        self.time_solve_ns
+    }
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        self.time_refactorize_ns
+    }
+
+    /// Indicates whether the factorization has been computed in single precision or not
+    fn get_mixed_precision(&self) -> bool {
+        self.factor_single.is_some()
+    }
+}
+
+/// Returns the tolerance to identify the zero entries of the small dense matrix K
+fn null_tolerance(k: &Matrix) -> f64 {
+    let kmax = k.as_data().iter().fold(0.0, |acc, v| f64::max(acc, f64::abs(*v)));
+    f64::sqrt(f64::EPSILON) * f64::max(1.0, kmax)
+}
+
+/// Reduces the small dense matrix K to the reduced row echelon form by the Gauss-Jordan elimination
+///
+/// The elimination uses complete pivoting; entries with modulus smaller than or equal to `tol` are
+/// considered null. The same row operations are applied to `g`.
+///
+/// Returns the pivot columns (one per row of the reduced form); thus, a particular solution of `K·c = g`
+/// is `c[pivots[i]] = g[i]` (with zero free components).
+fn gauss_jordan(k: &mut Matrix, g: &mut Vector, tol: f64) -> Vec<usize> {
+    let (p, _) = k.dims();
+    let mut free = vec![true; p];
+    let mut pivots = Vec::new();
+    for row in 0..p {
+        // find the largest entry in the remaining rows and free columns
+        let (mut ip, mut jp, mut largest) = (0, 0, -1.0);
+        for i in row..p {
+            for (j, is_free) in free.iter().enumerate() {
+                if *is_free && f64::abs(k.get(i, j)) > largest {
+                    (ip, jp, largest) = (i, j, f64::abs(k.get(i, j)));
+                }
+            }
+        }
+        if largest <= tol {
+            break;
+        }
+
+        // swap rows and normalize
+        for j in 0..p {
+            let (a, b) = (k.get(row, j), k.get(ip, j));
+            k.set(row, j, b);
+            k.set(ip, j, a);
+        }
+        (g[row], g[ip]) = (g[ip], g[row]);
+        let pivot = k.get(row, jp);
+        for j in 0..p {
+            k.set(row, j, k.get(row, j) / pivot);
+        }
+        g[row] /= pivot;
+
+        // eliminate the other rows
+        for i in 0..p {
+            let factor = k.get(i, jp);
+            if i != row && factor != 0.0 {
+                for j in 0..p {
+                    k.set(i, j, k.get(i, j) - factor * k.get(row, j));
+                }
+                g[i] -= factor * g[row];
+            }
+        }
+        free[jp] = false;
+        pivots.push(jp);
+    }
+    pivots
+}
+
+/// Computes an orthonormal basis of `Z·null(K)` (the columns of Z are the candidate vectors)
+fn null_space_basis(z: &Matrix, k: &Matrix) -> Matrix {
+    let (n, p) = z.dims();
+    let mut reduced = k.clone();
+    let mut g = Vector::new(p);
+    let pivots = gauss_jordan(&mut reduced, &mut g, null_tolerance(k));
+
+    // null(K): one vector per free column
+    let mut basis: Vec<Vector> = Vec::new();
+    for f in 0..p {
+        if pivots.contains(&f) {
+            continue;
+        }
+        let mut y = Vector::new(p);
+        y[f] = 1.0;
+        for (i, &col) in pivots.iter().enumerate() {
+            y[col] = -reduced.get(i, f);
+        }
+        let mut v = Vector::initialized(n, |row| (0..p).fold(0.0, |acc, m| acc + z.get(row, m) * y[m]));
+        let norm0 = f64::sqrt((0..n).fold(0.0, |acc, i| acc + v[i] * v[i]));
+
+        // modified Gram-Schmidt
+        for q in &basis {
+            let dot = (0..n).fold(0.0, |acc, i| acc + q[i] * v[i]);
+            for i in 0..n {
+                v[i] -= dot * q[i];
+            }
+        }
+        let norm = f64::sqrt((0..n).fold(0.0, |acc, i| acc + v[i] * v[i]));
+        if norm > f64::sqrt(f64::EPSILON) * norm0 {
+            for i in 0..n {
+                v[i] /= norm;
+            }
+            basis.push(v);
+        }
+    }
+    let mut res = Matrix::new(n, basis.len());
+    for (j, q) in basis.iter().enumerate() {
+        for i in 0..n {
+            res.set(i, j, q[i]);
+        }
+    }
+    res
+}
+
+/// Removes the component of v along the orthonormal basis Q, i.e., v = v - Q·Qᵀ·v
+pub(crate) fn remove_component(v: &mut Vector, q: &Matrix) {
+    let (n, d) = q.dims();
+    for j in 0..d {
+        let dot = (0..n).fold(0.0, |acc, i| acc + q.get(i, j) * v[i]);
+        for i in 0..n {
+            v[i] -= dot * q.get(i, j);
+        }
+    }
+}
+
+/// Converts the values of a CSC matrix to single precision
+fn csc_to_single(csc: &CscMatrix) -> Result<NumCscMatrix<f32>, StrError> {
+    let nnz = csc.col_pointers[csc.ncol] as usize;
+    let mut values = Vec::with_capacity(nnz);
+    for p in 0..nnz {
+        let value = csc.values[p] as f32;
+        if !value.is_finite() {
+            return Err("the matrix values cannot be represented in single precision");
+        }
+        values.push(value);
+    }
+    NumCscMatrix::new(
+        csc.nrow,
+        csc.ncol,
+        csc.col_pointers.clone(),
+        csc.row_indices[..nnz].to_vec(),
+        values,
+        csc.symmetric,
+    )
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CooMatrix, Samples};
+    use russell_lab::{approx_eq, mat_approx_eq, mat_mat_mul, mat_t_mat_mul, vec_approx_eq, vec_mat_mul, Matrix};
+
+    #[test]
+    fn factorize_handles_errors() {
+        let mut solver = SolverNative::new().unwrap();
+        assert!(!solver.factorized);
+
+        // COO to CSC errors
+        let coo = CooMatrix::new(1, 1, 1, Sym::No).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("COO to CSC requires nnz > 0")
+        );
+
+        // check parameters
+        let (coo, _, _, _) = Samples::tiny_1x1();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut params = LinSolParams::new();
+        params.native_pivot_threshold = 0.0;
+        assert_eq!(
+            solver.factorize(&mut mat, Some(params)).err(),
+            Some("the native pivot threshold must satisfy 0 < τ ≤ 1")
+        );
+
+        // check CSC matrix
+        let (coo, _, _, _) = Samples::rectangular_1x7();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("the matrix must be square")
+        );
+
+        // check already factorized data
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 1, 2.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        // ... factorize once => OK
+        solver.factorize(&mut mat, None).unwrap();
+        // ... change matrix (symmetric)
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::YesFull).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 1, 2.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (symmetric differs)")
+        );
+        // ... change matrix (ndim)
+        let mut coo = CooMatrix::new(1, 1, 1, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (ndim differs)")
+        );
+        // ... change matrix (nnz)
+        let mut coo = CooMatrix::new(2, 2, 1, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (nnz differs)")
+        );
+        // ... change matrix (pattern)
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 1, 1.0).unwrap();
+        coo.put(1, 1, 2.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (pattern differs)")
+        );
+    }
+
+    #[test]
+    fn factorize_fails_on_singular_matrix() {
+        let mut solver = SolverNative::new().unwrap();
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 1, 0.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None),
+            Err("the matrix is singular (LU factorization failed)")
+        );
+    }
+
+    #[test]
+    fn factorize_fails_on_indefinite_matrix() {
+        let mut solver = SolverNative::new().unwrap();
+        let mut coo = CooMatrix::new(2, 2, 3, Sym::YesLower).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 0, 2.0).unwrap();
+        coo.put(1, 1, 1.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut params = LinSolParams::new();
+        params.positive_definite = true;
+        assert_eq!(
+            solver.factorize(&mut mat, Some(params)),
+            Err("the matrix is not positive-definite (Cholesky factorization failed)")
+        );
+    }
+
+    #[test]
+    fn solve_handles_errors() {
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 0, 123.0).unwrap();
+        coo.put(1, 1, 456.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut solver = SolverNative::new().unwrap();
+        assert!(!solver.factorized);
+        let mut x = Vector::new(2);
+        let rhs = Vector::new(2);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false),
+            Err("the function factorize must be called before solve")
+        );
+        let mut x = Vector::new(1);
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false),
+            Err("the dimension of the vector of unknown values x is incorrect")
+        );
+        let mut x = Vector::new(2);
+        let rhs = Vector::new(1);
+        assert_eq!(
+            solver.solve(&mut x, &mat, &rhs, false),
+            Err("the dimension of the right-hand side vector is incorrect")
+        );
+        // wrong symmetric
+        let rhs = Vector::new(2);
+        let mut coo_wrong = CooMatrix::new(2, 2, 2, Sym::YesFull).unwrap();
+        coo_wrong.put(0, 0, 123.0).unwrap();
+        coo_wrong.put(1, 1, 456.0).unwrap();
+        let mut mat_wrong = SparseMatrix::from_coo(coo_wrong);
+        mat_wrong.get_csc_or_from_coo().unwrap(); // make sure to convert to CSC (because we're not calling factorize on this wrong matrix)
+        assert_eq!(
+            solver.solve(&mut x, &mat_wrong, &rhs, false),
+            Err("solve must use the same matrix (symmetric differs)")
+        );
+        // wrong ndim
+        let mut coo_wrong = CooMatrix::new(1, 1, 1, Sym::No).unwrap();
+        coo_wrong.put(0, 0, 123.0).unwrap();
+        let mut mat_wrong = SparseMatrix::from_coo(coo_wrong);
+        mat_wrong.get_csc_or_from_coo().unwrap(); // make sure to convert to CSC (because we're not calling factorize on this wrong matrix)
+        assert_eq!(
+            solver.solve(&mut x, &mat_wrong, &rhs, false),
+            Err("solve must use the same matrix (ndim differs)")
+        );
+        // wrong nnz
+        let mut coo_wrong = CooMatrix::new(2, 2, 3, Sym::No).unwrap();
+        coo_wrong.put(0, 0, 123.0).unwrap();
+        coo_wrong.put(1, 1, 123.0).unwrap();
+        coo_wrong.put(0, 1, 100.0).unwrap();
+        let mut mat_wrong = SparseMatrix::from_coo(coo_wrong);
+        mat_wrong.get_csc_or_from_coo().unwrap(); // make sure to convert to CSC (because we're not calling factorize on this wrong matrix)
+        assert_eq!(
+            solver.solve(&mut x, &mat_wrong, &rhs, false),
+            Err("solve must use the same matrix (nnz differs)")
+        );
+    }
+
+    #[test]
+    fn solve_works() {
+        let mut solver = SolverNative::new().unwrap();
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut x = Vector::new(5);
+        let rhs = Vector::from(&[8.0, 45.0, -3.0, 3.0, 19.0]);
+        let x_correct = &[1.0, 2.0, 3.0, 4.0, 5.0];
+
+        let mut params = LinSolParams::new();
+        params.compute_determinant = true;
+
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, x_correct, 1e-14);
+        let det = solver.determinant_coefficient * f64::powf(10.0, solver.determinant_exponent);
+        approx_eq(det, 114.0, 1e-13);
+
+        // calling solve again works
+        let mut x_again = Vector::new(5);
+        solver.solve(&mut x_again, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x_again, x_correct, 1e-14);
+
+        // update stats
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.main.solver, "Native-LU");
+        assert_eq!(stats.output.effective_ordering, "Amd");
+        assert_eq!(stats.output.effective_scaling, "No");
+        assert!(solver.get_nnz_factors() >= 12);
+    }
+
+    #[test]
+    fn analyze_factorize_numeric_and_refactorize_work() {
+        let mut solver = SolverNative::new().unwrap();
+        let (_, csc, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_csc(csc);
+        let mut x = Vector::new(5);
+        let rhs = Vector::from(&[8.0, 45.0, -3.0, 3.0, 19.0]);
+        let x_correct = &[1.0, 2.0, 3.0, 4.0, 5.0];
+
+        // errors
+        assert_eq!(
+            solver.factorize_numeric(&mut mat).err(),
+            Some("the function analyze must be called before factorize_numeric")
+        );
+        assert_eq!(
+            solver.refactorize(&mut mat).err(),
+            Some("the function factorize must be called before refactorize")
+        );
+
+        // analyze and factorize
+        solver.analyze(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.analyze(&mut mat, None).err(),
+            Some("the analysis has been performed already")
+        );
+        solver.factorize_numeric(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, x_correct, 1e-14);
+
+        // refactorize with other values (same pattern)
+        mat.get_csc_mut()
+            .unwrap()
+            .get_values_mut()
+            .iter_mut()
+            .for_each(|v| *v *= 2.0);
+        solver.refactorize(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &[0.5, 1.0, 1.5, 2.0, 2.5], 1e-14);
+
+        // update stats
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.time_nanoseconds.refactorize, solver.get_ns_refact());
+    }
+
+    #[test]
+    fn solve_transpose_and_multiple_works() {
+        let mut solver = SolverNative::new().unwrap();
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let a = coo.as_dense();
+        let mut mat = SparseMatrix::from_coo(coo);
+        solver.factorize(&mut mat, None).unwrap();
+
+        // Aᵀ · x = rhs
+        let x_correct = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut rhs = Vector::new(5);
+        vec_mat_mul(&mut rhs, 1.0, &x_correct, &a).unwrap();
+        let mut x = Vector::new(5);
+        solver.solve_transpose(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-13);
+
+        // A · X = RHS and Aᵀ · X = RHS
+        let xx_correct = Matrix::from(&[[1.0, 5.0], [2.0, 4.0], [3.0, 3.0], [4.0, 2.0], [5.0, 1.0]]);
+        let mut rhs_mat = Matrix::new(5, 2);
+        mat_mat_mul(&mut rhs_mat, 1.0, &a, &xx_correct, 0.0).unwrap();
+        let mut xx = Matrix::new(5, 2);
+        solver.solve_multiple(&mut xx, &mat, &rhs_mat, false, false).unwrap();
+        mat_approx_eq(&xx, &xx_correct, 1e-13);
+        mat_t_mat_mul(&mut rhs_mat, 1.0, &a, &xx_correct, 0.0).unwrap();
+        solver.solve_multiple(&mut xx, &mat, &rhs_mat, true, false).unwrap();
+        mat_approx_eq(&xx, &xx_correct, 1e-13);
+
+        // error
+        let mut xx_wrong = Matrix::new(5, 1);
+        assert_eq!(
+            solver.solve_multiple(&mut xx_wrong, &mat, &rhs_mat, false, false).err(),
+            Some("the dimensions of the matrix of unknown values X are incorrect")
+        );
+    }
+
+    #[test]
+    fn solve_works_symmetric() {
+        let x_correct = &[-979.0 / 3.0, 983.0, 1961.0 / 12.0, 398.0, 123.0 / 2.0];
+        let rhs = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        for (coo, ordering) in [
+            (Samples::mkl_symmetric_5x5_full().0, Ordering::Auto),
+            (Samples::mkl_symmetric_5x5_lower(false, false).0, Ordering::No),
+            (Samples::mkl_symmetric_5x5_upper(false, false).0, Ordering::Amd),
+        ] {
+            let mut solver = SolverNative::new().unwrap();
+            let mut mat = SparseMatrix::from_coo(coo);
+            let mut x = Vector::new(5);
+            let mut params = LinSolParams::new();
+            params.ordering = ordering;
+            solver.factorize(&mut mat, Some(params)).unwrap();
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            vec_approx_eq(&x, x_correct, 1e-10);
+        }
+    }
+
+    #[test]
+    fn solve_works_positive_definite() {
+        let mut solver = SolverNative::new().unwrap();
+        let (_, csc, _, _) = Samples::mkl_positive_definite_5x5_lower();
+        let mut mat = SparseMatrix::from_csc(csc);
+        let a = mat.as_dense();
+        let x_correct = Vector::from(&[1.0, -2.0, 3.0, -4.0, 5.0]);
+        let mut rhs = Vector::new(5);
+        russell_lab::mat_vec_mul(&mut rhs, 1.0, &a, &x_correct).unwrap();
+
+        let mut params = LinSolParams::new();
+        params.positive_definite = true;
+        params.compute_determinant = true;
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        let mut x = Vector::new(5);
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-12);
+
+        // check the determinant
+        let (_, _, _, det_correct) = Samples::mkl_positive_definite_5x5_lower();
+        let det = solver.determinant_coefficient * f64::powf(10.0, solver.determinant_exponent);
+        approx_eq(det, det_correct, 1e-10 * det_correct);
+
+        // update stats
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.main.solver, "Native-Cholesky");
+
+        // calling factorize again with new values works
+        mat.get_csc_mut().unwrap().values.iter_mut().for_each(|v| *v *= 2.0);
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        let half: Vec<_> = x_correct.as_data().iter().map(|v| v / 2.0).collect();
+        vec_approx_eq(&x, &half, 1e-12);
+    }
+
+    #[test]
+    fn null_space_and_pseudo_inverse_capture_errors() {
+        let mut solver = SolverNative::new().unwrap();
+        let (coo, _, _, _) = Samples::mkl_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut x = Vector::new(5);
+        let rhs = Vector::new(5);
+        assert_eq!(
+            solver.null_space().err(),
+            Some("the function factorize must be called before null_space")
+        );
+        assert_eq!(
+            solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).err(),
+            Some("the function factorize must be called before solve_pseudo_inverse")
+        );
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.null_space().err(),
+            Some("the null pivot detection must be enabled (in double precision) to compute the null space")
+        );
+        assert_eq!(
+            solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).err(),
+            Some(
+                "the null pivot detection must be enabled (in double precision) to compute the pseudo-inverse solution"
+            )
+        );
+    }
+
+    #[test]
+    fn null_space_and_pseudo_inverse_work_symmetric() {
+        // 1D Laplacian with free ends (pure Neumann problem): the null space is spanned by constant vectors
+        let n = 5;
+        let mut coo = CooMatrix::new(n, n, 2 * n - 1, Sym::YesLower).unwrap();
+        for i in 0..n {
+            coo.put(i, i, if i == 0 || i == n - 1 { 1.0 } else { 2.0 }).unwrap();
+            if i > 0 {
+                coo.put(i, i - 1, -1.0).unwrap();
+            }
+        }
+        let mut mat = SparseMatrix::from_coo(coo);
+        let a = mat.as_dense();
+        for positive_definite in [true, false] {
+            let mut solver = SolverNative::new().unwrap();
+            let mut params = LinSolParams::new();
+            params.positive_definite = positive_definite;
+            params.compute_determinant = true;
+            assert!(solver.factorize(&mut mat, Some(params)).is_err());
+            let mut solver = SolverNative::new().unwrap();
+            params.null_pivot_detection = true;
+            solver.factorize(&mut mat, Some(params)).unwrap();
+
+            // null space
+            let null = solver.null_space().unwrap();
+            assert_eq!(null.dims(), (n, 1));
+            let s = f64::signum(null.get(0, 0)) / f64::sqrt(n as f64);
+            for i in 0..n {
+                approx_eq(null.get(i, 0), s, 1e-14);
+            }
+
+            // stats
+            let mut stats = StatsLinSol::new();
+            solver.update_stats(&mut stats);
+            assert!(stats.rank.null_pivot_detection);
+            assert_eq!(stats.rank.number_of_null_pivots, 1);
+            assert_eq!(stats.rank.null_space_dimension, 1);
+            assert_eq!(stats.rank.rank, n - 1);
+            assert_eq!(stats.determinant.value(), 0.0);
+
+            // compatible system: x is the solution with zero mean
+            let rhs = Vector::from(&[1.0, 0.0, 0.0, 0.0, -1.0]);
+            let mut x = Vector::new(n);
+            solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).unwrap();
+            vec_approx_eq(&x, &[2.0, 1.0, 0.0, -1.0, -2.0], 1e-13);
+
+            // incompatible system: the mean of rhs is discarded
+            let rhs = Vector::from(&[2.0, 1.0, 1.0, 1.0, 0.0]);
+            solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).unwrap();
+            vec_approx_eq(&x, &[2.0, 1.0, 0.0, -1.0, -2.0], 1e-13);
+            let mut ax = Vector::new(n);
+            russell_lab::mat_vec_mul(&mut ax, 1.0, &a, &x).unwrap();
+            vec_approx_eq(&ax, &[1.0, 0.0, 0.0, 0.0, -1.0], 1e-13);
+        }
+    }
+
+    #[test]
+    fn null_space_and_pseudo_inverse_work_unsymmetric() {
+        // rank-deficient matrix: null(A) = span{(1,-2,1)} and null(Aᵀ) = span{(2,-1,0)}
+        let mut coo = CooMatrix::new(3, 3, 9, Sym::No).unwrap();
+        let values = [[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [1.0, 1.0, 1.0]];
+        for (i, row) in values.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                coo.put(i, j, *value).unwrap();
+            }
+        }
+        let mut mat = SparseMatrix::from_coo(coo);
+        let a = mat.as_dense();
+        for ordering in [Ordering::No, Ordering::Amd] {
+            let mut solver = SolverNative::new().unwrap();
+            let mut params = LinSolParams::new();
+            params.ordering = ordering;
+            params.null_pivot_detection = true;
+            solver.factorize(&mut mat, Some(params)).unwrap();
+
+            // null space
+            let null = solver.null_space().unwrap();
+            assert_eq!(null.dims(), (3, 1));
+            let s = f64::signum(null.get(0, 0)) / f64::sqrt(6.0);
+            let v = Vector::initialized(3, |i| null.get(i, 0));
+            vec_approx_eq(&v, &[s, -2.0 * s, s], 1e-14);
+
+            // least-squares solution: Aᵀ·(A·x - b) = 0 and x ⟂ null(A)
+            let rhs = Vector::from(&[1.0, 1.0, 1.0]);
+            let mut x = Vector::new(3);
+            solver.solve_pseudo_inverse(&mut x, &mat, &rhs, false).unwrap();
+            let mut r = Vector::new(3);
+            russell_lab::mat_vec_mul(&mut r, 1.0, &a, &x).unwrap();
+            for i in 0..3 {
+                r[i] -= rhs[i];
+            }
+            let mut atr = Vector::new(3);
+            vec_mat_mul(&mut atr, 1.0, &r, &a).unwrap();
+            vec_approx_eq(&atr, &[0.0, 0.0, 0.0], 1e-13);
+            approx_eq(x[0] - 2.0 * x[1] + x[2], 0.0, 1e-14);
+        }
+    }
+
+    #[test]
+    fn solve_works_larger_system() {
+        // 2D Poisson equation (5-point stencil) on a 20 × 20 grid
+        let m = 20;
+        let n = m * m;
+        let mut coo = CooMatrix::new(n, n, 5 * n, Sym::No).unwrap();
+        for i in 0..m {
+            for j in 0..m {
+                let k = i * m + j;
+                coo.put(k, k, 4.0).unwrap();
+                if i > 0 {
+                    coo.put(k, k - m, -1.0).unwrap();
+                }
+                if i + 1 < m {
+                    coo.put(k, k + m, -1.0).unwrap();
+                }
+                if j > 0 {
+                    coo.put(k, k - 1, -1.0).unwrap();
+                }
+                if j + 1 < m {
+                    coo.put(k, k + 1, -1.0).unwrap();
+                }
+            }
+        }
+        let mut mat = SparseMatrix::from_coo(coo);
+        let x_correct = Vector::initialized(n, |i| f64::sin(i as f64));
+        let mut rhs = Vector::new(n);
+        mat.mat_vec_mul(&mut rhs, 1.0, &x_correct).unwrap();
+
+        // the fill-reducing ordering reduces the number of non-zeros in the factors
+        let mut nnz_factors = Vec::new();
+        for (ordering, effective) in [("No", "No"), ("Auto", "Amd"), ("Metis", "Metis")] {
+            let mut solver = SolverNative::new().unwrap();
+            let mut params = LinSolParams::new();
+            params.ordering = Ordering::from(ordering);
+            solver.factorize(&mut mat, Some(params)).unwrap();
+            let mut x = Vector::new(n);
+            solver.solve(&mut x, &mat, &rhs, false).unwrap();
+            vec_approx_eq(&x, &x_correct, 1e-12);
+            nnz_factors.push(solver.get_nnz_factors());
+            let mut stats = StatsLinSol::new();
+            solver.update_stats(&mut stats);
+            assert_eq!(stats.output.effective_ordering, effective);
+        }
+        assert!(nnz_factors[1] < nnz_factors[0]);
+        assert!(nnz_factors[2] < nnz_factors[0]);
+    }
+}
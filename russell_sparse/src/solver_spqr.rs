@@ -0,0 +1,471 @@
+use super::{csc_transpose, LsqParams, Ordering, SparseMatrix};
+use crate::constants::*;
+use crate::StrError;
+use russell_lab::{Stopwatch, Vector};
+
+/// Opaque struct holding a C-pointer to InterfaceSPQR
+///
+/// Reference: <https://doc.rust-lang.org/nomicon/ffi.html#representing-opaque-structs>
+#[repr(C)]
+struct InterfaceSPQR {
+    _data: [u8; 0],
+    _marker: core::marker::PhantomData<(*mut u8, core::marker::PhantomPinned)>,
+}
+
+/// Enforce Send on the C structure
+///
+/// <https://stackoverflow.com/questions/50258359/can-a-struct-containing-a-raw-pointer-implement-send-and-be-ffi-safe>
+unsafe impl Send for InterfaceSPQR {}
+
+/// Enforce Send on the Rust structure
+///
+/// <https://stackoverflow.com/questions/50258359/can-a-struct-containing-a-raw-pointer-implement-send-and-be-ffi-safe>
+unsafe impl Send for SolverSPQR {}
+
+extern "C" {
+    fn solver_spqr_new() -> *mut InterfaceSPQR;
+    fn solver_spqr_drop(solver: *mut InterfaceSPQR);
+    fn solver_spqr_factorize(
+        solver: *mut InterfaceSPQR,
+        rank: *mut i32,
+        effective_ordering: *mut i32,
+        ordering: i32,
+        tolerance: f64,
+        nrow: i32,
+        ncol: i32,
+        col_pointers: *const i64,
+        row_indices: *const i64,
+        values: *const f64,
+    ) -> i32;
+    fn solver_spqr_solve(solver: *mut InterfaceSPQR, min_norm: CcBool, x: *mut f64, rhs: *mut f64) -> i32;
+}
+
+/// Wraps the SuiteSparseQR (SPQR) solver for sparse least-squares problems
+///
+/// Computes the sparse QR factorization `A·E = Q·R`, where E is a fill-reducing column permutation,
+/// and solves:
+///
+/// * the least-squares problem `min ‖A·x - b‖` if A is square or overdetermined (m ≥ n), i.e.,
+///   `x = E·R⁻¹·Qᵀ·b`
+/// * the minimum-norm problem `min ‖x‖ subject to A·x = b` if A is underdetermined (m < n).
+///   In this case, Aᵀ is factorized, i.e., `Aᵀ·E = Q·R`, and `x = Q·R⁻ᵀ·Eᵀ·b`
+///
+/// The rank is estimated by considering the columns of R with norm smaller than or equal to the
+/// tolerance as null (see [LsqParams::rank_tolerance]). If A is rank-deficient (and m ≥ n), the
+/// "basic" solution is computed, i.e., the components corresponding to the null columns are zero.
+///
+/// **Note:** Symmetric matrices must be given with the full representation ([Sym::YesFull](crate::Sym::YesFull)).
+///
+/// Reference: Davis TA (2011) Algorithm 915, SuiteSparseQR: Multifrontal multithreaded rank-revealing
+/// sparse QR factorization, ACM Transactions on Mathematical Software, 38(1):8:1-8:22
+pub struct SolverSPQR {
+    /// Holds a pointer to the C interface to SPQR
+    solver: *mut InterfaceSPQR,
+
+    /// Indicates whether the matrix has been factorized or not
+    factorized: bool,
+
+    /// Indicates that Aᵀ has been factorized (underdetermined problem)
+    min_norm: bool,
+
+    /// Holds the number of rows of A
+    nrow: usize,
+
+    /// Holds the number of columns of A
+    ncol: usize,
+
+    /// Holds the rank estimate (after factorize)
+    rank: usize,
+
+    /// Holds the used ordering (after factorize)
+    effective_ordering: i32,
+
+    /// Stopwatch to measure computation times
+    stopwatch: Stopwatch,
+
+    /// Time spent on factorize in nanoseconds
+    time_factorize_ns: u128,
+
+    /// Time spent on solve in nanoseconds
+    time_solve_ns: u128,
+}
+
+impl Drop for SolverSPQR {
+    /// Tells the c-code to release memory
+    fn drop(&mut self) {
+        unsafe {
+            solver_spqr_drop(self.solver);
+        }
+    }
+}
+
+impl SolverSPQR {
+    /// Allocates a new instance
+    pub fn new() -> Result<Self, StrError> {
+        unsafe {
+            let solver = solver_spqr_new();
+            if solver.is_null() {
+                return Err("c-code failed to allocate the SPQR solver");
+            }
+            Ok(SolverSPQR {
+                solver,
+                factorized: false,
+                min_norm: false,
+                nrow: 0,
+                ncol: 0,
+                rank: 0,
+                effective_ordering: -1,
+                stopwatch: Stopwatch::new(),
+                time_factorize_ns: 0,
+                time_solve_ns: 0,
+            })
+        }
+    }
+
+    /// Computes the sparse QR factorization
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the (m,n) coefficient matrix A (**COO** or **CSC**, but not CSR) with [Sym::No](crate::Sym::No) or [Sym::YesFull](crate::Sym::YesFull)
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The matrix may be factorized again (e.g., with different values or structure).
+    pub fn factorize(&mut self, mat: &mut SparseMatrix, params: Option<LsqParams>) -> Result<(), StrError> {
+        // parameters
+        let par = if let Some(p) = params { p } else { LsqParams::new() };
+        let ordering = spqr_ordering(par.ordering);
+        let tolerance = match par.rank_tolerance {
+            Some(tol) => {
+                if tol < 0.0 {
+                    return Err("the rank tolerance must be ≥ 0");
+                }
+                tol
+            }
+            None => -1.0, // default
+        };
+
+        // get CSC matrix
+        let csc = mat.get_csc_or_from_coo()?;
+        if csc.symmetric.triangular() {
+            return Err("SPQR requires Sym::No or Sym::YesFull");
+        }
+        let (nrow, ncol) = (csc.nrow, csc.ncol);
+        let min_norm = nrow < ncol;
+        let transposed = if min_norm { Some(csc_transpose(csc)?) } else { None };
+        let factorized = transposed.as_ref().unwrap_or(csc);
+
+        // the SuiteSparseQR C API only accepts int64 (SuiteSparse_long) indices
+        let col_pointers: Vec<i64> = factorized.col_pointers.iter().map(|p| *p as i64).collect();
+        let row_indices: Vec<i64> = factorized.row_indices.iter().map(|i| *i as i64).collect();
+
+        // call factorize
+        self.factorized = false;
+        self.stopwatch.reset();
+        let mut rank: i32 = 0;
+        unsafe {
+            let status = solver_spqr_factorize(
+                self.solver,
+                &mut rank,
+                &mut self.effective_ordering,
+                ordering,
+                tolerance,
+                to_i32(factorized.nrow),
+                to_i32(factorized.ncol),
+                col_pointers.as_ptr(),
+                row_indices.as_ptr(),
+                factorized.values.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_spqr_error_code(status));
+            }
+        }
+        self.time_factorize_ns = self.stopwatch.stop();
+
+        // done
+        self.min_norm = min_norm;
+        self.nrow = nrow;
+        self.ncol = ncol;
+        self.rank = rank as usize;
+        self.factorized = true;
+        Ok(())
+    }
+
+    /// Computes the least-squares (m ≥ n) or minimum-norm (m < n) solution
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to n (ncol)
+    ///
+    /// # Input
+    ///
+    /// * `rhs` -- the right-hand side vector with dimension equal to m (nrow)
+    pub fn solve(&mut self, x: &mut Vector, rhs: &Vector) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before solve");
+        }
+        if x.dim() != self.ncol {
+            return Err("the dimension of the vector of unknown values x is incorrect");
+        }
+        if rhs.dim() != self.nrow {
+            return Err("the dimension of the right-hand side vector is incorrect");
+        }
+        let mut b = rhs.clone(); // the c-code needs a mutable pointer
+        let min_norm = if self.min_norm { 1 } else { 0 };
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_spqr_solve(
+                self.solver,
+                min_norm,
+                x.as_mut_data().as_mut_ptr(),
+                b.as_mut_data().as_mut_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_spqr_error_code(status));
+            }
+        }
+        self.time_solve_ns = self.stopwatch.stop();
+        Ok(())
+    }
+
+    /// Returns the rank estimate (after factorize)
+    pub fn get_rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Returns the name of the ordering used by SPQR (after factorize)
+    pub fn get_effective_ordering(&self) -> String {
+        spqr_ordering_name(self.effective_ordering)
+    }
+
+    /// Returns the nanoseconds spent on factorize
+    pub fn get_ns_fact(&self) -> u128 {
+        self.time_factorize_ns
+    }
+
+    /// Returns the nanoseconds spent on solve
+    pub fn get_ns_solve(&self) -> u128 {
+        self.time_solve_ns
+    }
+}
+
+pub(crate) const SPQR_ORDERING_NATURAL: i32 = 1; // (from SPQR manual) no permutation
+pub(crate) const SPQR_ORDERING_COLAMD: i32 = 2; // (from SPQR manual) COLAMD
+pub(crate) const SPQR_ORDERING_CHOLMOD: i32 = 4; // (from SPQR manual) CHOLMOD best-effort (AMD, then METIS)
+pub(crate) const SPQR_ORDERING_AMD: i32 = 5; // (from SPQR manual) AMD of Aᵀ·A
+pub(crate) const SPQR_ORDERING_METIS: i32 = 6; // (from SPQR manual) METIS of Aᵀ·A
+pub(crate) const SPQR_ORDERING_DEFAULT: i32 = 7; // (from SPQR manual) COLAMD or AMD
+pub(crate) const SPQR_ORDERING_BEST: i32 = 8; // (from SPQR manual) try COLAMD, AMD, and METIS
+
+/// Returns the SPQR ordering constant
+pub(crate) fn spqr_ordering(ordering: Ordering) -> i32 {
+    match ordering {
+        Ordering::Amd => SPQR_ORDERING_AMD,
+        Ordering::Amf => SPQR_ORDERING_DEFAULT,
+        Ordering::Auto => SPQR_ORDERING_DEFAULT,
+        Ordering::Best => SPQR_ORDERING_BEST,
+        Ordering::Cholmod => SPQR_ORDERING_CHOLMOD,
+        Ordering::Colamd => SPQR_ORDERING_COLAMD,
+        Ordering::Metis => SPQR_ORDERING_METIS,
+        Ordering::No => SPQR_ORDERING_NATURAL,
+        Ordering::Pord => SPQR_ORDERING_DEFAULT,
+        Ordering::Qamd => SPQR_ORDERING_DEFAULT,
+        Ordering::Scotch => SPQR_ORDERING_DEFAULT,
+    }
+}
+
+/// Returns the name of the SPQR ordering
+pub(crate) fn spqr_ordering_name(ordering: i32) -> String {
+    match ordering {
+        SPQR_ORDERING_NATURAL => "No".to_string(),
+        SPQR_ORDERING_COLAMD => "Colamd".to_string(),
+        SPQR_ORDERING_CHOLMOD => "Cholmod".to_string(),
+        SPQR_ORDERING_AMD => "Amd".to_string(),
+        SPQR_ORDERING_METIS => "Metis".to_string(),
+        _ => "Unknown".to_string(),
+    }
+}
+
+/// Handles SPQR error code
+pub(crate) fn handle_spqr_error_code(err: i32) -> StrError {
+    match err {
+        -29 => "SuiteSparseQR_C_factorize failed",
+        -28 => "SuiteSparseQR_C_solve failed",
+        ERROR_NULL_POINTER => "SPQR failed due to NULL POINTER error",
+        ERROR_NEED_FACTORIZATION => "SPQR failed because FACTORIZATION is needed",
+        _ => "Error: unknown error returned by c-code (SPQR)",
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CooMatrix, Samples, Sym};
+    use russell_lab::{approx_eq, vec_approx_eq};
+
+    #[test]
+    fn new_and_drop_work() {
+        // you may debug into the C-code to see that drop is working
+        let solver = SolverSPQR::new().unwrap();
+        assert!(!solver.factorized);
+    }
+
+    #[test]
+    fn factorize_and_solve_capture_errors() {
+        let mut solver = SolverSPQR::new().unwrap();
+        let mut x = Vector::new(3);
+        let rhs = Vector::new(4);
+        assert_eq!(
+            solver.solve(&mut x, &rhs).err(),
+            Some("the function factorize must be called before solve")
+        );
+        let (coo, _, _, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("SPQR requires Sym::No or Sym::YesFull")
+        );
+        let mut params = LsqParams::new();
+        params.rank_tolerance = Some(-1.0);
+        assert_eq!(
+            solver.factorize(&mut mat, Some(params)).err(),
+            Some("the rank tolerance must be ≥ 0")
+        );
+        let (coo, _, _, _) = Samples::rectangular_3x4();
+        let mut mat = SparseMatrix::from_coo(coo);
+        solver.factorize(&mut mat, None).unwrap();
+        let mut x = Vector::new(3);
+        let rhs = Vector::new(3);
+        assert_eq!(
+            solver.solve(&mut x, &rhs).err(),
+            Some("the dimension of the vector of unknown values x is incorrect")
+        );
+        let mut x = Vector::new(4);
+        let rhs = Vector::new(4);
+        assert_eq!(
+            solver.solve(&mut x, &rhs).err(),
+            Some("the dimension of the right-hand side vector is incorrect")
+        );
+    }
+
+    #[test]
+    fn factorize_and_solve_work() {
+        // square system (COO input)
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let rhs = Vector::from(&[8.0, 45.0, -3.0, 3.0, 19.0]);
+        let x_correct = &[1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut solver = SolverSPQR::new().unwrap();
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(solver.get_rank(), 5);
+        let mut x = Vector::new(5);
+        solver.solve(&mut x, &rhs).unwrap();
+        vec_approx_eq(&x, x_correct, 1e-13);
+
+        // calling solve again works
+        let mut x_again = Vector::new(5);
+        solver.solve(&mut x_again, &rhs).unwrap();
+        vec_approx_eq(&x_again, x_correct, 1e-13);
+
+        // factorize again with another matrix (CSC input)
+        let (_, csc, _, _) = Samples::mkl_symmetric_5x5_full();
+        let mut mat = SparseMatrix::from_csc(csc);
+        let rhs = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let x_correct = &[-979.0 / 3.0, 983.0, 1961.0 / 12.0, 398.0, 123.0 / 2.0];
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(solver.get_rank(), 5);
+        solver.solve(&mut x, &rhs).unwrap();
+        vec_approx_eq(&x, x_correct, 1e-10);
+        assert!(solver.get_ns_fact() > 0);
+    }
+
+    #[test]
+    fn solve_works_overdetermined() {
+        // line fitting: y = 1 + 2·t at t = 0, 1, 2, 3 with noise ±0.1
+        let mut coo = CooMatrix::new(4, 2, 8, Sym::No).unwrap();
+        for i in 0..4 {
+            coo.put(i, 0, 1.0).unwrap();
+            coo.put(i, 1, i as f64).unwrap();
+        }
+        let mut mat = SparseMatrix::from_coo(coo);
+        let rhs = Vector::from(&[1.1, 2.9, 5.1, 6.9]);
+        let mut solver = SolverSPQR::new().unwrap();
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(solver.get_rank(), 2);
+        let mut x = Vector::new(2);
+        solver.solve(&mut x, &rhs).unwrap();
+        vec_approx_eq(&x, &[1.06, 1.96], 1e-14);
+    }
+
+    #[test]
+    fn solve_works_underdetermined() {
+        // x₀ + x₁ + x₂ = 3 and x₀ - x₂ = 0 => the minimum-norm solution is (1, 1, 1)
+        let mut coo = CooMatrix::new(2, 3, 5, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(0, 1, 1.0).unwrap();
+        coo.put(0, 2, 1.0).unwrap();
+        coo.put(1, 0, 1.0).unwrap();
+        coo.put(1, 2, -1.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        let rhs = Vector::from(&[3.0, 0.0]);
+        let mut solver = SolverSPQR::new().unwrap();
+        solver.factorize(&mut mat, None).unwrap();
+        assert_eq!(solver.get_rank(), 2);
+        let mut x = Vector::new(3);
+        solver.solve(&mut x, &rhs).unwrap();
+        vec_approx_eq(&x, &[1.0, 1.0, 1.0], 1e-14);
+        approx_eq(x[0] + x[1] + x[2], 3.0, 1e-14);
+    }
+
+    #[test]
+    fn rank_estimate_works() {
+        // the third column is the sum of the first two
+        let mut coo = CooMatrix::new(4, 3, 12, Sym::No).unwrap();
+        let values = [[1.0, 0.0, 1.0], [0.0, 1.0, 1.0], [1.0, 1.0, 2.0], [2.0, -1.0, 1.0]];
+        for (i, row) in values.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                coo.put(i, j, *value).unwrap();
+            }
+        }
+        let mut mat = SparseMatrix::from_coo(coo);
+        let mut solver = SolverSPQR::new().unwrap();
+        let mut params = LsqParams::new();
+        params.ordering = Ordering::No;
+        solver.factorize(&mut mat, Some(params)).unwrap();
+        assert_eq!(solver.get_rank(), 2);
+        assert_eq!(solver.get_effective_ordering(), "No");
+    }
+
+    #[test]
+    fn spqr_ordering_works() {
+        assert_eq!(spqr_ordering(Ordering::Amd), SPQR_ORDERING_AMD);
+        assert_eq!(spqr_ordering(Ordering::Auto), SPQR_ORDERING_DEFAULT);
+        assert_eq!(spqr_ordering(Ordering::Best), SPQR_ORDERING_BEST);
+        assert_eq!(spqr_ordering(Ordering::Cholmod), SPQR_ORDERING_CHOLMOD);
+        assert_eq!(spqr_ordering(Ordering::Colamd), SPQR_ORDERING_COLAMD);
+        assert_eq!(spqr_ordering(Ordering::Metis), SPQR_ORDERING_METIS);
+        assert_eq!(spqr_ordering(Ordering::No), SPQR_ORDERING_NATURAL);
+        assert_eq!(spqr_ordering_name(SPQR_ORDERING_AMD), "Amd");
+        assert_eq!(spqr_ordering_name(SPQR_ORDERING_COLAMD), "Colamd");
+        assert_eq!(spqr_ordering_name(123), "Unknown");
+    }
+
+    #[test]
+    fn handle_spqr_error_code_works() {
+        let default = "Error: unknown error returned by c-code (SPQR)";
+        for i in 1..28 {
+            assert_eq!(handle_spqr_error_code(-i), default);
+        }
+        assert_eq!(handle_spqr_error_code(-29), "SuiteSparseQR_C_factorize failed");
+        assert_eq!(handle_spqr_error_code(-28), "SuiteSparseQR_C_solve failed");
+        assert_eq!(
+            handle_spqr_error_code(ERROR_NULL_POINTER),
+            "SPQR failed due to NULL POINTER error"
+        );
+        assert_eq!(
+            handle_spqr_error_code(ERROR_NEED_FACTORIZATION),
+            "SPQR failed because FACTORIZATION is needed"
+        );
+    }
+}
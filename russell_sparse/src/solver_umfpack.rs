@@ -1,4 +1,4 @@
-use super::{LinSolParams, LinSolTrait, Ordering, Scaling, SparseMatrix, StatsLinSol, Sym};
+use super::{CscMatrix, LinSolParams, LinSolTrait, Ordering, Scaling, SparseMatrix, StatsLinSol, Sym};
 use crate::constants::*;
 use crate::StrError;
 use russell_lab::{Stopwatch, Vector};
@@ -57,6 +57,7 @@ extern "C" {
         col_pointers: *const i32,
         row_indices: *const i32,
         values: *const f64,
+        transpose: CcBool,
         verbose: CcBool,
     ) -> i32;
 }
@@ -83,6 +84,12 @@ pub struct SolverUMFPACK {
     /// Holds the number of non-zeros saved in initialize
     initialized_nnz: usize,
 
+    /// Holds the fingerprint of the sparsity pattern saved in initialize
+    initialized_fingerprint: u64,
+
+    /// Holds the parameters given to analyze (or factorize)
+    params: LinSolParams,
+
     /// Holds the used strategy (after factorize)
     effective_strategy: i32,
 
@@ -116,6 +123,9 @@ pub struct SolverUMFPACK {
 
     /// Time spent on solve in nanoseconds
     time_solve_ns: u128,
+
+    /// Time spent on refactorize in nanoseconds
+    time_refactorize_ns: u128,
 }
 
 impl Drop for SolverUMFPACK {
@@ -142,6 +152,8 @@ impl SolverUMFPACK {
                 initialized_sym: Sym::No,
                 initialized_ndim: 0,
                 initialized_nnz: 0,
+                initialized_fingerprint: 0,
+                params: LinSolParams::new(),
                 effective_strategy: -1,
                 effective_ordering: -1,
                 effective_scaling: -1,
@@ -152,96 +164,40 @@ impl SolverUMFPACK {
                 time_initialize_ns: 0,
                 time_factorize_ns: 0,
                 time_solve_ns: 0,
+                time_refactorize_ns: 0,
             })
         }
     }
-}
 
-impl LinSolTrait for SolverUMFPACK {
-    /// Performs the factorization (and analysis/initialization if needed)
-    ///
-    /// # Input
-    ///
-    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
-    ///   Also, the matrix must be square (`nrow = ncol`) and, if symmetric,
-    ///   the symmetric flag must be [Sym::YesFull]
-    /// * `params` -- configuration parameters; None => use default
-    ///
-    /// # Notes
+    /// Checks whether the matrix has the same structure as the one given to analyze (or the first factorize)
+    fn check_pattern(&self, csc: &CscMatrix) -> Result<(), StrError> {
+        if csc.symmetric != self.initialized_sym {
+            return Err("subsequent factorizations must use the same matrix (symmetric differs)");
+        }
+        if csc.nrow != self.initialized_ndim {
+            return Err("subsequent factorizations must use the same matrix (ndim differs)");
+        }
+        if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
+            return Err("subsequent factorizations must use the same matrix (nnz differs)");
+        }
+        if csc.get_pattern_fingerprint() != self.initialized_fingerprint {
+            return Err("subsequent factorizations must use the same matrix (pattern differs)");
+        }
+        Ok(())
+    }
+
+    /// Checks the matrix and performs the numeric factorization reusing the symbolic object
     ///
-    /// 1. The structure of the matrix (nrow, ncol, nnz, sym) must be
-    ///    exactly the same among multiple calls to `factorize`. The values may differ
-    ///    from call to call, nonetheless.
-    /// 2. The first call to `factorize` will define the structure which must be
-    ///    kept the same for the next calls.
-    /// 3. If the structure of the matrix needs to be changed, the solver must
-    ///    be "dropped" and a new solver allocated.
-    /// 4. For symmetric matrices, `UMFPACK` requires [Sym::YesFull]
-    fn factorize(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+    /// Returns the elapsed time in nanoseconds.
+    fn numeric(&mut self, mat: &mut SparseMatrix) -> Result<u128, StrError> {
         // get CSC matrix
         // (or convert from COO if CSC is not available and COO is available)
         let csc = mat.get_csc_or_from_coo()?;
-
-        // check
-        if self.initialized {
-            if csc.symmetric != self.initialized_sym {
-                return Err("subsequent factorizations must use the same matrix (symmetric differs)");
-            }
-            if csc.nrow != self.initialized_ndim {
-                return Err("subsequent factorizations must use the same matrix (ndim differs)");
-            }
-            if (csc.col_pointers[csc.ncol] as usize) != self.initialized_nnz {
-                return Err("subsequent factorizations must use the same matrix (nnz differs)");
-            }
-        } else {
-            if csc.nrow != csc.ncol {
-                return Err("the matrix must be square");
-            }
-            if csc.symmetric == Sym::YesLower || csc.symmetric == Sym::YesUpper {
-                return Err("UMFPACK requires Sym::YesFull for symmetric matrices");
-            }
-            self.initialized_sym = csc.symmetric;
-            self.initialized_ndim = csc.nrow;
-            self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
-        }
-
-        // parameters
-        let par = if let Some(p) = params { p } else { LinSolParams::new() };
-
-        // input parameters
-        let ordering = umfpack_ordering(par.ordering);
-        let scaling = umfpack_scaling(par.scaling);
+        self.check_pattern(csc)?;
 
         // requests
-        let compute_determinant = if par.compute_determinant { 1 } else { 0 };
-        let verbose = if par.verbose { 1 } else { 0 };
-
-        // matrix config
-        let enforce_unsym = if par.umfpack_enforce_unsymmetric_strategy { 1 } else { 0 };
-        let ndim = to_i32(csc.nrow);
-
-        // call initialize just once
-        if !self.initialized {
-            self.stopwatch.reset();
-            unsafe {
-                let status = solver_umfpack_initialize(
-                    self.solver,
-                    ordering,
-                    scaling,
-                    verbose,
-                    enforce_unsym,
-                    ndim,
-                    csc.col_pointers.as_ptr(),
-                    csc.row_indices.as_ptr(),
-                    csc.values.as_ptr(),
-                );
-                if status != SUCCESSFUL_EXIT {
-                    return Err(handle_umfpack_error_code(status));
-                }
-            }
-            self.time_initialize_ns = self.stopwatch.stop();
-            self.initialized = true;
-        }
+        let compute_determinant = if self.params.compute_determinant { 1 } else { 0 };
+        let verbose = if self.params.verbose { 1 } else { 0 };
 
         // call factorize
         self.stopwatch.reset();
@@ -264,34 +220,18 @@ impl LinSolTrait for SolverUMFPACK {
                 return Err(handle_umfpack_error_code(status));
             }
         }
-        self.time_factorize_ns = self.stopwatch.stop();
-
-        // done
-        self.factorized = true;
-        Ok(())
+        Ok(self.stopwatch.stop())
     }
 
-    /// Computes the solution of the linear system
-    ///
-    /// Solves the linear system:
-    ///
-    /// ```text
-    ///   A   · x = rhs
-    /// (m,m)  (m)  (m)
-    /// ```
-    ///
-    /// # Output
-    ///
-    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
-    ///
-    /// # Input
-    ///
-    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesFull].
-    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
-    /// * `verbose` -- shows messages
-    ///
-    /// **Warning:** the matrix must be same one used in `factorize`.
-    fn solve(&mut self, x: &mut Vector, mat: &SparseMatrix, rhs: &Vector, verbose: bool) -> Result<(), StrError> {
+    /// Checks the data and solves `A · x = rhs` or `Aᵀ · x = rhs`
+    fn solve_internal(
+        &mut self,
+        x: &mut Vector,
+        mat: &SparseMatrix,
+        rhs: &Vector,
+        transpose: bool,
+        verbose: bool,
+    ) -> Result<(), StrError> {
         // check
         if !self.factorized {
             return Err("the function factorize must be called before solve");
@@ -332,6 +272,7 @@ impl LinSolTrait for SolverUMFPACK {
                 csc.col_pointers.as_ptr(),
                 csc.row_indices.as_ptr(),
                 csc.values.as_ptr(),
+                if transpose { 1 } else { 0 },
                 verb,
             );
             if status != SUCCESSFUL_EXIT {
@@ -343,6 +284,192 @@ impl LinSolTrait for SolverUMFPACK {
         // done
         Ok(())
     }
+}
+
+impl LinSolTrait for SolverUMFPACK {
+    /// Performs the factorization (and analysis/initialization if needed)
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`) and, if symmetric,
+    ///   the symmetric flag must be [Sym::YesFull]
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// # Notes
+    ///
+    /// 1. The structure of the matrix (nrow, ncol, nnz, sym, and the sparsity pattern) must be
+    ///    exactly the same among multiple calls to `factorize`. The values may differ
+    ///    from call to call, nonetheless.
+    /// 2. The first call to `factorize` will define the structure which must be
+    ///    kept the same for the next calls.
+    /// 3. If the structure of the matrix needs to be changed, the solver must
+    ///    be "dropped" and a new solver allocated.
+    /// 4. For symmetric matrices, `UMFPACK` requires [Sym::YesFull]
+    fn factorize(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        let par = if let Some(p) = params { p } else { LinSolParams::new() };
+        if !self.initialized {
+            self.analyze(mat, Some(par))?;
+        }
+        self.params = par;
+        self.factorize_numeric(mat)
+    }
+
+    /// Performs the symbolic analysis (ordering and strategy selection) only
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR).
+    ///   Also, the matrix must be square (`nrow = ncol`) and, if symmetric,
+    ///   the symmetric flag must be [Sym::YesFull]
+    /// * `params` -- configuration parameters; None => use default
+    ///
+    /// **Note:** The analysis is performed just once; i.e., the solver must be
+    /// "dropped" and a new solver allocated if the structure of the matrix changes.
+    fn analyze(&mut self, mat: &mut SparseMatrix, params: Option<LinSolParams>) -> Result<(), StrError> {
+        // check
+        if self.initialized {
+            return Err("the analysis has been performed already");
+        }
+
+        // get CSC matrix
+        // (or convert from COO if CSC is not available and COO is available)
+        let csc = mat.get_csc_or_from_coo()?;
+        if csc.nrow != csc.ncol {
+            return Err("the matrix must be square");
+        }
+        if csc.symmetric == Sym::YesLower || csc.symmetric == Sym::YesUpper {
+            return Err("UMFPACK requires Sym::YesFull for symmetric matrices");
+        }
+        self.initialized_sym = csc.symmetric;
+        self.initialized_ndim = csc.nrow;
+        self.initialized_nnz = csc.col_pointers[csc.ncol] as usize;
+        self.initialized_fingerprint = csc.get_pattern_fingerprint();
+
+        // parameters
+        self.params = if let Some(p) = params { p } else { LinSolParams::new() };
+
+        // input parameters
+        let ordering = umfpack_ordering(self.params.ordering);
+        let scaling = umfpack_scaling(self.params.scaling);
+
+        // requests
+        let verbose = if self.params.verbose { 1 } else { 0 };
+
+        // matrix config
+        let enforce_unsym = if self.params.umfpack_enforce_unsymmetric_strategy {
+            1
+        } else {
+            0
+        };
+        let ndim = to_i32(csc.nrow);
+
+        // call initialize
+        self.stopwatch.reset();
+        unsafe {
+            let status = solver_umfpack_initialize(
+                self.solver,
+                ordering,
+                scaling,
+                verbose,
+                enforce_unsym,
+                ndim,
+                csc.col_pointers.as_ptr(),
+                csc.row_indices.as_ptr(),
+                csc.values.as_ptr(),
+            );
+            if status != SUCCESSFUL_EXIT {
+                return Err(handle_umfpack_error_code(status));
+            }
+        }
+        self.time_initialize_ns = self.stopwatch.stop();
+
+        // done
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric factorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one given to `analyze`
+    fn factorize_numeric(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        if !self.initialized {
+            return Err("the function analyze must be called before factorize_numeric");
+        }
+        self.time_factorize_ns = self.numeric(mat)?;
+        self.factorized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric refactorization reusing the symbolic analysis
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (**COO** or **CSC**, but not CSR) with the
+    ///   same sparsity pattern as the one previously factorized
+    ///
+    /// **Note:** UMFPACK does not offer a refactorization without pivoting; thus, this function
+    /// performs a new numeric factorization (with pivoting) reusing the symbolic object,
+    /// which is as fast as UMFPACK allows. The time is recorded as the refactorization time.
+    fn refactorize(&mut self, mat: &mut SparseMatrix) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the function factorize must be called before refactorize");
+        }
+        self.time_refactorize_ns = self.numeric(mat)?;
+        Ok(())
+    }
+
+    /// Computes the solution of the linear system
+    ///
+    /// Solves the linear system:
+    ///
+    /// ```text
+    ///   A   · x = rhs
+    /// (m,m)  (m)  (m)
+    /// ```
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A; it must be square and, if symmetric, [Sym::YesFull].
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve(&mut self, x: &mut Vector, mat: &SparseMatrix, rhs: &Vector, verbose: bool) -> Result<(), StrError> {
+        self.solve_internal(x, mat, rhs, false, verbose)
+    }
+
+    /// Computes the solution of the transposed linear system
+    ///
+    /// Solves `Aᵀ · x = rhs` using the same factorization of A.
+    ///
+    /// # Output
+    ///
+    /// * `x` -- the vector of unknown values with dimension equal to mat.nrow
+    ///
+    /// # Input
+    ///
+    /// * `mat` -- the coefficient matrix A (not transposed); it must be square and, if symmetric, [Sym::YesFull].
+    /// * `rhs` -- the right-hand side vector with know values an dimension equal to mat.nrow
+    /// * `verbose` -- shows messages
+    ///
+    /// **Warning:** the matrix must be same one used in `factorize`.
+    fn solve_transpose(
+        &mut self,
+        x: &mut Vector,
+        mat: &SparseMatrix,
+        rhs: &Vector,
+        verbose: bool,
+    ) -> Result<(), StrError> {
+        self.solve_internal(x, mat, rhs, true, verbose)
+    }
 
     /// Updates the stats structure (should be called after solve)
     fn update_stats(&self, stats: &mut StatsLinSol) {
@@ -351,10 +478,9 @@ impl LinSolTrait for SolverUMFPACK {
         } else {
             "UMFPACK".to_string()
         };
-        stats.determinant.mantissa_real = self.determinant_coefficient;
-        stats.determinant.mantissa_imag = 0.0;
-        stats.determinant.base = 10.0;
-        stats.determinant.exponent = self.determinant_exponent;
+        stats
+            .determinant
+            .set(self.determinant_coefficient, 0.0, 10.0, self.determinant_exponent);
         stats.output.umfpack_rcond_estimate = self.rcond_estimate;
         stats.output.effective_ordering = match self.effective_ordering {
             UMFPACK_ORDERING_CHOLMOD => "Cholmod".to_string(),
@@ -378,6 +504,7 @@ impl LinSolTrait for SolverUMFPACK {
         };
         stats.time_nanoseconds.initialize = self.time_initialize_ns;
         stats.time_nanoseconds.factorize = self.time_factorize_ns;
+        stats.time_nanoseconds.refactorize = self.time_refactorize_ns;
         stats.time_nanoseconds.solve = self.time_solve_ns;
     }
 
@@ -395,6 +522,11 @@ impl LinSolTrait for SolverUMFPACK {
     fn get_ns_solve(&self) -> u128 {
         self.time_solve_ns
     }
+
+    /// Returns the nanoseconds spent on refactorize
+    fn get_ns_refact(&self) -> u128 {
+        self.time_refactorize_ns
+    }
 }
 
 pub(crate) const UMFPACK_STRATEGY_AUTO: i32 = 0; // use symmetric or unsymmetric strategy
@@ -480,7 +612,7 @@ pub(crate) fn handle_umfpack_error_code(err: i32) -> StrError {
 mod tests {
     use super::*;
     use crate::{CooMatrix, Samples};
-    use russell_lab::{approx_eq, vec_approx_eq};
+    use russell_lab::{approx_eq, mat_approx_eq, mat_mat_mul, mat_t_mat_mul, vec_approx_eq, vec_mat_mul, Matrix};
 
     #[test]
     fn new_and_drop_work() {
@@ -548,6 +680,15 @@ mod tests {
             solver.factorize(&mut mat, None).err(),
             Some("subsequent factorizations must use the same matrix (nnz differs)")
         );
+        // ... change matrix (pattern)
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 1, 1.0).unwrap();
+        coo.put(1, 1, 2.0).unwrap();
+        let mut mat = SparseMatrix::from_coo(coo);
+        assert_eq!(
+            solver.factorize(&mut mat, None).err(),
+            Some("subsequent factorizations must use the same matrix (pattern differs)")
+        );
     }
 
     #[test]
@@ -670,6 +811,79 @@ mod tests {
         assert_eq!(stats.output.effective_scaling, "Sum");
     }
 
+    #[test]
+    fn analyze_factorize_numeric_and_refactorize_work() {
+        let mut solver = SolverUMFPACK::new().unwrap();
+        let (_, csc, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut mat = SparseMatrix::from_csc(csc);
+        let mut x = Vector::new(5);
+        let rhs = Vector::from(&[8.0, 45.0, -3.0, 3.0, 19.0]);
+        let x_correct = &[1.0, 2.0, 3.0, 4.0, 5.0];
+
+        // errors
+        assert_eq!(
+            solver.factorize_numeric(&mut mat).err(),
+            Some("the function analyze must be called before factorize_numeric")
+        );
+        assert_eq!(
+            solver.refactorize(&mut mat).err(),
+            Some("the function factorize must be called before refactorize")
+        );
+
+        // analyze and factorize
+        solver.analyze(&mut mat, None).unwrap();
+        assert_eq!(
+            solver.analyze(&mut mat, None).err(),
+            Some("the analysis has been performed already")
+        );
+        solver.factorize_numeric(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, x_correct, 1e-14);
+
+        // refactorize with other values (same pattern)
+        mat.get_csc_mut()
+            .unwrap()
+            .get_values_mut()
+            .iter_mut()
+            .for_each(|v| *v *= 2.0);
+        solver.refactorize(&mut mat).unwrap();
+        solver.solve(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &[0.5, 1.0, 1.5, 2.0, 2.5], 1e-14);
+
+        // update stats
+        let mut stats = StatsLinSol::new();
+        solver.update_stats(&mut stats);
+        assert_eq!(stats.time_nanoseconds.refactorize, solver.get_ns_refact());
+    }
+
+    #[test]
+    fn solve_transpose_and_multiple_works() {
+        let mut solver = SolverUMFPACK::new().unwrap();
+        let (coo, _, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let a = coo.as_dense();
+        let mut mat = SparseMatrix::from_coo(coo);
+        solver.factorize(&mut mat, None).unwrap();
+
+        // Aᵀ · x = rhs
+        let x_correct = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut rhs = Vector::new(5);
+        vec_mat_mul(&mut rhs, 1.0, &x_correct, &a).unwrap();
+        let mut x = Vector::new(5);
+        solver.solve_transpose(&mut x, &mat, &rhs, false).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-13);
+
+        // A · X = RHS and Aᵀ · X = RHS
+        let xx_correct = Matrix::from(&[[1.0, 5.0], [2.0, 4.0], [3.0, 3.0], [4.0, 2.0], [5.0, 1.0]]);
+        let mut rhs_mat = Matrix::new(5, 2);
+        mat_mat_mul(&mut rhs_mat, 1.0, &a, &xx_correct, 0.0).unwrap();
+        let mut xx = Matrix::new(5, 2);
+        solver.solve_multiple(&mut xx, &mat, &rhs_mat, false, false).unwrap();
+        mat_approx_eq(&xx, &xx_correct, 1e-13);
+        mat_t_mat_mul(&mut rhs_mat, 1.0, &a, &xx_correct, 0.0).unwrap();
+        solver.solve_multiple(&mut xx, &mat, &rhs_mat, true, false).unwrap();
+        mat_approx_eq(&xx, &xx_correct, 1e-13);
+    }
+
     #[test]
     fn solve_works_symmetric() {
         let mut solver = SolverUMFPACK::new().unwrap();
@@ -0,0 +1,1474 @@
+use super::{to_i32, ComplexCscMatrix, ComplexCsrMatrix, NumCscMatrix, NumCsrMatrix, Sym};
+use crate::StrError;
+use num_traits::{Num, NumCast};
+use russell_lab::NumVector;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ops::{AddAssign, MulAssign};
+
+/// Indicates a free position in the workspace arrays
+const NONE: usize = usize::MAX;
+
+/// Holds compressed-row arrays used by the kernels of this module
+///
+/// A CSR matrix A is represented by its own arrays. A CSC matrix A is represented by the
+/// compressed-row arrays of Aᵀ (i.e., the same arrays with rows and columns swapped).
+/// Thus, all kernels are written for compressed rows only.
+#[derive(Clone)]
+pub(crate) struct Rows<T> {
+    pub(crate) nrow: usize,
+    pub(crate) ncol: usize,
+    pub(crate) ptr: Vec<usize>,
+    pub(crate) idx: Vec<usize>,
+    pub(crate) val: Vec<T>,
+    pub(crate) sym: Sym,
+}
+
+/// Swaps the lower and upper triangle representations
+///
+/// **Note:** The CSC arrays of a lower triangle are the CSR arrays of an upper triangle.
+pub(crate) fn swap_triangle(sym: Sym) -> Sym {
+    match sym {
+        Sym::YesLower => Sym::YesUpper,
+        Sym::YesUpper => Sym::YesLower,
+        _ => sym,
+    }
+}
+
+/// Returns true if the (i, j) entry belongs to the stored part of the matrix
+pub(crate) fn stored(sym: Sym, i: usize, j: usize) -> bool {
+    match sym {
+        Sym::YesLower => j <= i,
+        Sym::YesUpper => j >= i,
+        _ => true,
+    }
+}
+
+impl<T> Rows<T>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    /// Allocates a new instance from the arrays of a compressed matrix
+    pub(crate) fn new(nrow: usize, ncol: usize, ptr: &[i32], idx: &[i32], val: &[T], sym: Sym) -> Self {
+        let nnz = ptr[nrow] as usize;
+        Rows {
+            nrow,
+            ncol,
+            ptr: ptr.iter().map(|p| *p as usize).collect(),
+            idx: idx[..nnz].iter().map(|j| *j as usize).collect(),
+            val: val[..nnz].to_vec(),
+            sym,
+        }
+    }
+
+    /// Allocates a new instance from a CSR matrix (A)
+    pub(crate) fn from_csr(a: &NumCsrMatrix<T>) -> Self {
+        Rows::new(a.nrow, a.ncol, &a.row_pointers, &a.col_indices, &a.values, a.symmetric)
+    }
+
+    /// Allocates a new instance from a CSC matrix (Aᵀ)
+    pub(crate) fn from_csc(a: &NumCscMatrix<T>) -> Self {
+        Rows::new(
+            a.ncol,
+            a.nrow,
+            &a.col_pointers,
+            &a.row_indices,
+            &a.values,
+            swap_triangle(a.symmetric),
+        )
+    }
+
+    /// Converts the arrays to a CSR matrix
+    pub(crate) fn into_csr(self) -> Result<NumCsrMatrix<T>, StrError> {
+        let ptr = self.ptr.iter().map(|p| to_i32(*p)).collect();
+        let idx = self.idx.iter().map(|j| to_i32(*j)).collect();
+        NumCsrMatrix::new(self.nrow, self.ncol, ptr, idx, self.val, self.sym)
+    }
+
+    /// Converts the arrays (of Aᵀ) to a CSC matrix (of A)
+    pub(crate) fn into_csc(self) -> Result<NumCscMatrix<T>, StrError> {
+        let ptr = self.ptr.iter().map(|p| to_i32(*p)).collect();
+        let idx = self.idx.iter().map(|j| to_i32(*j)).collect();
+        NumCscMatrix::new(self.ncol, self.nrow, ptr, idx, self.val, swap_triangle(self.sym))
+    }
+
+    /// Returns the transpose (the column indices of the result are sorted)
+    pub(crate) fn transpose(&self) -> Self {
+        let nnz = self.ptr[self.nrow];
+        let mut ptr = vec![0; self.ncol + 1];
+        for j in &self.idx {
+            ptr[j + 1] += 1;
+        }
+        for j in 0..self.ncol {
+            ptr[j + 1] += ptr[j];
+        }
+        let mut next = ptr.clone();
+        let mut idx = vec![0; nnz];
+        let mut val = vec![T::zero(); nnz];
+        for i in 0..self.nrow {
+            for p in self.ptr[i]..self.ptr[i + 1] {
+                let j = self.idx[p];
+                idx[next[j]] = i;
+                val[next[j]] = self.val[p];
+                next[j] += 1;
+            }
+        }
+        Rows {
+            nrow: self.ncol,
+            ncol: self.nrow,
+            ptr,
+            idx,
+            val,
+            sym: swap_triangle(self.sym),
+        }
+    }
+
+    /// Returns the full representation (mirrors the triangle, if needed)
+    pub(crate) fn full(&self) -> Self {
+        if !self.sym.triangular() {
+            return self.clone();
+        }
+        let t = self.transpose();
+        let mut res = Rows {
+            nrow: self.nrow,
+            ncol: self.ncol,
+            ptr: vec![0; self.nrow + 1],
+            idx: Vec::with_capacity(2 * self.idx.len()),
+            val: Vec::with_capacity(2 * self.idx.len()),
+            sym: Sym::YesFull,
+        };
+        let mut row = Vec::new();
+        for i in 0..self.nrow {
+            row.clear();
+            for p in self.ptr[i]..self.ptr[i + 1] {
+                row.push((self.idx[p], self.val[p]));
+            }
+            for p in t.ptr[i]..t.ptr[i + 1] {
+                if t.idx[p] != i {
+                    row.push((t.idx[p], t.val[p]));
+                }
+            }
+            row.sort_by_key(|e| e.0);
+            for (j, aij) in &row {
+                res.idx.push(*j);
+                res.val.push(*aij);
+            }
+            res.ptr[i + 1] = res.idx.len();
+        }
+        res
+    }
+}
+
+/// Allocates the pattern of C = A + B (values are zero)
+fn add_pattern<T>(a: &Rows<T>, b: &Rows<T>, sym: Sym) -> Rows<T>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut marker = vec![NONE; a.ncol];
+    let mut ptr = vec![0; a.nrow + 1];
+    let mut idx = Vec::new();
+    for i in 0..a.nrow {
+        let start = idx.len();
+        for m in [a, b] {
+            for p in m.ptr[i]..m.ptr[i + 1] {
+                let j = m.idx[p];
+                if marker[j] != i {
+                    marker[j] = i;
+                    idx.push(j);
+                }
+            }
+        }
+        idx[start..].sort_unstable();
+        ptr[i + 1] = idx.len();
+    }
+    let nnz = idx.len();
+    Rows {
+        nrow: a.nrow,
+        ncol: a.ncol,
+        ptr,
+        idx,
+        val: vec![T::zero(); nnz],
+        sym,
+    }
+}
+
+/// Allocates the pattern of C = A · B (values are zero), keeping only the entries in the stored part of C
+fn mul_pattern<T>(a: &Rows<T>, b: &Rows<T>, sym: Sym) -> Rows<T>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut marker = vec![NONE; b.ncol];
+    let mut ptr = vec![0; a.nrow + 1];
+    let mut idx = Vec::new();
+    for i in 0..a.nrow {
+        let start = idx.len();
+        for p in a.ptr[i]..a.ptr[i + 1] {
+            let k = a.idx[p];
+            for q in b.ptr[k]..b.ptr[k + 1] {
+                let j = b.idx[q];
+                if marker[j] != i && stored(sym, i, j) {
+                    marker[j] = i;
+                    idx.push(j);
+                }
+            }
+        }
+        idx[start..].sort_unstable();
+        ptr[i + 1] = idx.len();
+    }
+    let nnz = idx.len();
+    Rows {
+        nrow: a.nrow,
+        ncol: b.ncol,
+        ptr,
+        idx,
+        val: vec![T::zero(); nnz],
+        sym,
+    }
+}
+
+/// Holds the arrays of the resulting matrix C in the numeric phase
+struct Target<'a, T> {
+    ptr: &'a [i32],
+    idx: &'a [i32],
+    val: &'a mut [T],
+    sym: Sym,
+}
+
+impl<'a, T> Target<'a, T>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    /// Allocates a new instance and sets all values to zero
+    fn new(nrow: usize, ptr: &'a [i32], idx: &'a [i32], val: &'a mut [T], sym: Sym) -> Self {
+        let nnz = ptr[nrow] as usize;
+        val[..nnz].fill(T::zero());
+        Target { ptr, idx, val, sym }
+    }
+
+    /// Saves the positions of the entries of row i (the other positions must be NONE)
+    fn scatter(&self, i: usize, position: &mut [usize]) {
+        for p in self.ptr[i]..self.ptr[i + 1] {
+            position[self.idx[p as usize] as usize] = p as usize;
+        }
+    }
+
+    /// Clears the positions of the entries of row i
+    fn clear(&self, i: usize, position: &mut [usize]) {
+        for p in self.ptr[i]..self.ptr[i + 1] {
+            position[self.idx[p as usize] as usize] = NONE;
+        }
+    }
+
+    /// Adds a value to the entry at a given position
+    fn add(&mut self, pos: usize, value: T) -> Result<(), StrError> {
+        if pos == NONE {
+            return Err("the pattern of the resulting matrix is incompatible (call the symbolic function first)");
+        }
+        self.val[pos] += value;
+        Ok(())
+    }
+}
+
+/// Computes C = α·A + β·B on the pattern of C
+fn add_values<T>(c: &mut Target<T>, alpha: T, a: &Rows<T>, beta: T, b: &Rows<T>) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut position = vec![NONE; a.ncol];
+    for i in 0..a.nrow {
+        c.scatter(i, &mut position);
+        for (m, s) in [(a, alpha), (b, beta)] {
+            for p in m.ptr[i]..m.ptr[i + 1] {
+                c.add(position[m.idx[p]], s * m.val[p])?;
+            }
+        }
+        c.clear(i, &mut position);
+    }
+    Ok(())
+}
+
+/// Computes C = α·A·B on the pattern of C (the entries outside the stored part of C are ignored)
+fn mul_values<T>(c: &mut Target<T>, alpha: T, a: &Rows<T>, b: &Rows<T>) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut position = vec![NONE; b.ncol];
+    for i in 0..a.nrow {
+        c.scatter(i, &mut position);
+        for p in a.ptr[i]..a.ptr[i + 1] {
+            let k = a.idx[p];
+            let aik = alpha * a.val[p];
+            for q in b.ptr[k]..b.ptr[k + 1] {
+                let j = b.idx[q];
+                if stored(c.sym, i, j) {
+                    c.add(position[j], aik * b.val[q])?;
+                }
+            }
+        }
+        c.clear(i, &mut position);
+    }
+    Ok(())
+}
+
+/// Computes C = Rᵀ·A·R on the pattern of C (the entries outside the stored part of C are ignored)
+///
+/// Uses the outer-product form `C = Σᵢ R(i,:)ᵀ · (A·R)(i,:)`; thus, only the rows of R are needed.
+fn triple_values<T>(c: &mut Target<T>, r: &Rows<T>, a: &Rows<T>) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut w = vec![T::zero(); r.ncol];
+    let mut marker = vec![NONE; r.ncol];
+    let mut list = Vec::new();
+    for i in 0..a.nrow {
+        // w := (A·R)(i,:)
+        list.clear();
+        for p in a.ptr[i]..a.ptr[i + 1] {
+            let k = a.idx[p];
+            for q in r.ptr[k]..r.ptr[k + 1] {
+                let j = r.idx[q];
+                if marker[j] != i {
+                    marker[j] = i;
+                    w[j] = T::zero();
+                    list.push(j);
+                }
+                w[j] += a.val[p] * r.val[q];
+            }
+        }
+        // C(k,:) += R(i,k) · w
+        for s in r.ptr[i]..r.ptr[i + 1] {
+            let k = r.idx[s];
+            let start = c.ptr[k] as usize;
+            let row = &c.idx[start..(c.ptr[k + 1] as usize)];
+            for j in &list {
+                if stored(c.sym, k, *j) {
+                    let pos = match row.binary_search(&to_i32(*j)) {
+                        Ok(t) => start + t,
+                        Err(_) => NONE,
+                    };
+                    c.add(pos, r.val[s] * w[*j])?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prepares the operands of the addition and returns the symmetric type of the result
+///
+/// If the representations differ, the operands are converted to the full representation.
+fn add_operands<T>(a: Rows<T>, b: Rows<T>) -> Result<(Rows<T>, Rows<T>, Sym), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.nrow != b.nrow || a.ncol != b.ncol {
+        return Err("matrices are incompatible");
+    }
+    if a.sym == b.sym {
+        let sym = a.sym;
+        return Ok((a, b, sym));
+    }
+    let sym = if a.sym != Sym::No && b.sym != Sym::No {
+        Sym::YesFull
+    } else {
+        Sym::No
+    };
+    Ok((a.full(), b.full(), sym))
+}
+
+/// Prepares the operands of the multiplication (converted to the full representation)
+fn mul_operands<T>(a: Rows<T>, b: Rows<T>) -> Result<(Rows<T>, Rows<T>), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.ncol != b.nrow {
+        return Err("matrices are incompatible");
+    }
+    Ok((a.full(), b.full()))
+}
+
+/// Prepares the operands of the triple product Rᵀ·A·R and returns the symmetric type of the result
+///
+/// The result has the same symmetric type as A.
+fn triple_operands<T>(r: Rows<T>, a: Rows<T>) -> Result<(Rows<T>, Rows<T>, Sym), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.nrow != a.ncol || a.ncol != r.nrow {
+        return Err("matrices are incompatible");
+    }
+    let sym = a.sym;
+    Ok((r.full(), a.full(), sym))
+}
+
+/// Returns an error if the result matrix has the wrong dimensions or symmetric type
+fn check_result(c: (usize, usize, Sym), nrow: usize, ncol: usize, sym: Sym) -> Result<(), StrError> {
+    if c.0 != nrow || c.1 != ncol {
+        return Err("the resulting matrix has incompatible dimensions");
+    }
+    if c.2 != sym {
+        return Err("the resulting matrix has an incorrect symmetric type");
+    }
+    Ok(())
+}
+
+/// Returns the transpose of a CSR matrix
+///
+/// ```text
+/// C := Aᵀ
+/// ```
+///
+/// **Note:** The transpose of a symmetric matrix with lower (upper) triangle representation
+/// is returned with the upper (lower) triangle representation.
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // ┌       ┐
+///     // │ 1 2 . │
+///     // │ . . 3 │
+///     // └       ┘
+///     let mut coo = CooMatrix::new(2, 3, 3, Sym::No)?;
+///     coo.put(0, 0, 1.0)?;
+///     coo.put(0, 1, 2.0)?;
+///     coo.put(1, 2, 3.0)?;
+///     let a = CsrMatrix::from_coo(&coo)?;
+///     let at = csr_transpose(&a)?;
+///     let correct = "┌     ┐\n\
+///                    │ 1 0 │\n\
+///                    │ 2 0 │\n\
+///                    │ 0 3 │\n\
+///                    └     ┘";
+///     assert_eq!(format!("{}", at.as_dense()), correct);
+///     Ok(())
+/// }
+/// ```
+pub fn csr_transpose<T>(a: &NumCsrMatrix<T>) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    Rows::from_csr(a).transpose().into_csr()
+}
+
+/// Computes the sparsity pattern of the sum of two CSR matrices (symbolic phase)
+///
+/// Returns the matrix C with the pattern of `A + B` and zero values. C may then be used
+/// (repeatedly) by [csr_add_numeric()].
+///
+/// The symmetric type of C is the same as the one of A and B, if they are equal. Otherwise,
+/// C has the [Sym::YesFull] representation if both A and B are symmetric, or [Sym::No] if not.
+pub fn csr_add_symbolic<T>(a: &NumCsrMatrix<T>, b: &NumCsrMatrix<T>) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (a, b, sym) = add_operands(Rows::from_csr(a), Rows::from_csr(b))?;
+    add_pattern(&a, &b, sym).into_csr()
+}
+
+/// Computes the sum of two CSR matrices using a known pattern (numeric phase)
+///
+/// ```text
+/// C := α⋅A + β⋅B
+/// ```
+///
+/// **Note:** The pattern of C must have been computed by [csr_add_symbolic()] (with matrices having
+/// the same structure as A and B); nonetheless, C may contain additional entries.
+pub fn csr_add_numeric<T>(
+    c: &mut NumCsrMatrix<T>,
+    alpha: T,
+    a: &NumCsrMatrix<T>,
+    beta: T,
+    b: &NumCsrMatrix<T>,
+) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (a, b, sym) = add_operands(Rows::from_csr(a), Rows::from_csr(b))?;
+    check_result((c.nrow, c.ncol, c.symmetric), a.nrow, a.ncol, sym)?;
+    let mut target = Target::new(c.nrow, &c.row_pointers, &c.col_indices, &mut c.values, sym);
+    add_values(&mut target, alpha, &a, beta, &b)
+}
+
+/// Computes the sum of two CSR matrices
+///
+/// ```text
+/// C := α⋅A + β⋅B
+/// ```
+///
+/// See [csr_add_symbolic()] regarding the symmetric type of C.
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // ┌     ┐     ┌     ┐
+///     // │ 1 . │     │ . 2 │
+///     // │ . 3 │     │ . 4 │
+///     // └     ┘     └     ┘
+///     let mut coo_a = CooMatrix::new(2, 2, 2, Sym::No)?;
+///     coo_a.put(0, 0, 1.0)?;
+///     coo_a.put(1, 1, 3.0)?;
+///     let mut coo_b = CooMatrix::new(2, 2, 2, Sym::No)?;
+///     coo_b.put(0, 1, 2.0)?;
+///     coo_b.put(1, 1, 4.0)?;
+///     let a = CsrMatrix::from_coo(&coo_a)?;
+///     let b = CsrMatrix::from_coo(&coo_b)?;
+///     let c = csr_add(1.0, &a, 10.0, &b)?;
+///     let correct = "┌       ┐\n\
+///                    │  1 20 │\n\
+///                    │  0 43 │\n\
+///                    └       ┘";
+///     assert_eq!(format!("{}", c.as_dense()), correct);
+///     Ok(())
+/// }
+/// ```
+pub fn csr_add<T>(alpha: T, a: &NumCsrMatrix<T>, beta: T, b: &NumCsrMatrix<T>) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut c = csr_add_symbolic(a, b)?;
+    csr_add_numeric(&mut c, alpha, a, beta, b)?;
+    Ok(c)
+}
+
+/// Computes the sparsity pattern of the product of two CSR matrices (symbolic phase)
+///
+/// Returns the matrix C with the pattern of `A ⋅ B` and zero values. C may then be used
+/// (repeatedly) by [csr_mat_mat_mul_numeric()].
+///
+/// **Note:** The symmetric matrices are converted to the full representation and the result is [Sym::No].
+pub fn csr_mat_mat_mul_symbolic<T>(a: &NumCsrMatrix<T>, b: &NumCsrMatrix<T>) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (a, b) = mul_operands(Rows::from_csr(a), Rows::from_csr(b))?;
+    mul_pattern(&a, &b, Sym::No).into_csr()
+}
+
+/// Computes the product of two CSR matrices using a known pattern (numeric phase)
+///
+/// ```text
+/// C := α⋅A⋅B
+/// ```
+///
+/// **Note:** The pattern of C must have been computed by [csr_mat_mat_mul_symbolic()] (with matrices
+/// having the same structure as A and B); nonetheless, C may contain additional entries.
+pub fn csr_mat_mat_mul_numeric<T>(
+    c: &mut NumCsrMatrix<T>,
+    alpha: T,
+    a: &NumCsrMatrix<T>,
+    b: &NumCsrMatrix<T>,
+) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (a, b) = mul_operands(Rows::from_csr(a), Rows::from_csr(b))?;
+    check_result((c.nrow, c.ncol, c.symmetric), a.nrow, b.ncol, Sym::No)?;
+    let mut target = Target::new(c.nrow, &c.row_pointers, &c.col_indices, &mut c.values, Sym::No);
+    mul_values(&mut target, alpha, &a, &b)
+}
+
+/// Computes the product of two CSR matrices (SpGEMM)
+///
+/// ```text
+/// C := α⋅A⋅B
+/// ```
+///
+/// **Note:** The symmetric matrices are converted to the full representation and the result is [Sym::No].
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // ┌     ┐     ┌       ┐
+///     // │ 1 2 │     │ 1 . 1 │
+///     // │ . 3 │     │ . 1 . │
+///     // └     ┘     └       ┘
+///     let mut coo_a = CooMatrix::new(2, 2, 3, Sym::No)?;
+///     coo_a.put(0, 0, 1.0)?;
+///     coo_a.put(0, 1, 2.0)?;
+///     coo_a.put(1, 1, 3.0)?;
+///     let mut coo_b = CooMatrix::new(2, 3, 3, Sym::No)?;
+///     coo_b.put(0, 0, 1.0)?;
+///     coo_b.put(0, 2, 1.0)?;
+///     coo_b.put(1, 1, 1.0)?;
+///     let a = CsrMatrix::from_coo(&coo_a)?;
+///     let b = CsrMatrix::from_coo(&coo_b)?;
+///     let c = csr_mat_mat_mul(1.0, &a, &b)?;
+///     let correct = "┌       ┐\n\
+///                    │ 1 2 1 │\n\
+///                    │ 0 3 0 │\n\
+///                    └       ┘";
+///     assert_eq!(format!("{}", c.as_dense()), correct);
+///     Ok(())
+/// }
+/// ```
+pub fn csr_mat_mat_mul<T>(alpha: T, a: &NumCsrMatrix<T>, b: &NumCsrMatrix<T>) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut c = csr_mat_mat_mul_symbolic(a, b)?;
+    csr_mat_mat_mul_numeric(&mut c, alpha, a, b)?;
+    Ok(c)
+}
+
+/// Computes the sparsity pattern of the triple product Pᵀ⋅A⋅P with CSR matrices (symbolic phase)
+///
+/// Returns the matrix C with the pattern of `Pᵀ⋅A⋅P` and zero values. C may then be used
+/// (repeatedly) by [csr_triple_product_numeric()].
+///
+/// **Note:** C has the same symmetric type as A (e.g., if A is stored as a lower triangle, so is C).
+pub fn csr_triple_product_symbolic<T>(p: &NumCsrMatrix<T>, a: &NumCsrMatrix<T>) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (r, a, sym) = triple_operands(Rows::from_csr(p), Rows::from_csr(a))?;
+    let ar = mul_pattern(&a, &r, Sym::No);
+    mul_pattern(&r.transpose(), &ar, sym).into_csr()
+}
+
+/// Computes the triple product Pᵀ⋅A⋅P with CSR matrices using a known pattern (numeric phase)
+///
+/// ```text
+/// C := Pᵀ⋅A⋅P
+/// ```
+///
+/// **Note:** The pattern of C must have been computed by [csr_triple_product_symbolic()] (with matrices
+/// having the same structure as P and A); nonetheless, C may contain additional entries.
+pub fn csr_triple_product_numeric<T>(
+    c: &mut NumCsrMatrix<T>,
+    p: &NumCsrMatrix<T>,
+    a: &NumCsrMatrix<T>,
+) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (r, a, sym) = triple_operands(Rows::from_csr(p), Rows::from_csr(a))?;
+    check_result((c.nrow, c.ncol, c.symmetric), r.ncol, r.ncol, sym)?;
+    let mut target = Target::new(c.nrow, &c.row_pointers, &c.col_indices, &mut c.values, sym);
+    triple_values(&mut target, &r, &a)
+}
+
+/// Computes the triple product Pᵀ⋅A⋅P with CSR matrices (e.g., Galerkin projection)
+///
+/// ```text
+/// C := Pᵀ⋅A⋅P
+/// ```
+///
+/// **Note:** C has the same symmetric type as A (e.g., if A is stored as a lower triangle, so is C).
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // A = tridiag(-1, 2, -1) and P = piecewise-constant interpolation
+///     let mut coo_a = CooMatrix::new(4, 4, 7, Sym::YesLower)?;
+///     let mut coo_p = CooMatrix::new(4, 2, 4, Sym::No)?;
+///     for i in 0..4 {
+///         coo_a.put(i, i, 2.0)?;
+///         if i > 0 {
+///             coo_a.put(i, i - 1, -1.0)?;
+///         }
+///         coo_p.put(i, i / 2, 1.0)?;
+///     }
+///     let a = CsrMatrix::from_coo(&coo_a)?;
+///     let p = CsrMatrix::from_coo(&coo_p)?;
+///     let c = csr_triple_product(&p, &a)?;
+///     assert_eq!(c.get_info(), (2, 2, 3, Sym::YesLower));
+///     let correct = "┌       ┐\n\
+///                    │  2 -1 │\n\
+///                    │ -1  2 │\n\
+///                    └       ┘";
+///     assert_eq!(format!("{}", c.as_dense()), correct);
+///     Ok(())
+/// }
+/// ```
+pub fn csr_triple_product<T>(p: &NumCsrMatrix<T>, a: &NumCsrMatrix<T>) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut c = csr_triple_product_symbolic(p, a)?;
+    csr_triple_product_numeric(&mut c, p, a)?;
+    Ok(c)
+}
+
+/// Scales a CSR matrix
+///
+/// ```text
+/// A := α⋅A
+/// ```
+pub fn csr_scale<T>(a: &mut NumCsrMatrix<T>, alpha: T)
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    for v in a.get_values_mut() {
+        *v *= alpha;
+    }
+}
+
+/// Scales the rows of a CSR matrix
+///
+/// ```text
+/// A := diag(r)⋅A
+/// ```
+///
+/// **Note:** The matrix must not be symmetric (see [csr_scale_symmetric()]).
+pub fn csr_scale_rows<T>(a: &mut NumCsrMatrix<T>, r: &NumVector<T>) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.symmetric != Sym::No {
+        return Err("the scaling of rows or columns is not available for symmetric matrices");
+    }
+    if r.dim() != a.nrow {
+        return Err("the dimension of the vector of scaling factors is incorrect");
+    }
+    for i in 0..a.nrow {
+        for p in a.row_pointers[i]..a.row_pointers[i + 1] {
+            a.values[p as usize] *= r[i];
+        }
+    }
+    Ok(())
+}
+
+/// Scales the columns of a CSR matrix
+///
+/// ```text
+/// A := A⋅diag(c)
+/// ```
+///
+/// **Note:** The matrix must not be symmetric (see [csr_scale_symmetric()]).
+pub fn csr_scale_columns<T>(a: &mut NumCsrMatrix<T>, c: &NumVector<T>) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.symmetric != Sym::No {
+        return Err("the scaling of rows or columns is not available for symmetric matrices");
+    }
+    if c.dim() != a.ncol {
+        return Err("the dimension of the vector of scaling factors is incorrect");
+    }
+    let nnz = a.row_pointers[a.nrow] as usize;
+    for p in 0..nnz {
+        a.values[p] *= c[a.col_indices[p] as usize];
+    }
+    Ok(())
+}
+
+/// Scales the rows and columns of a square CSR matrix by the same factors (preserves symmetry)
+///
+/// ```text
+/// A := diag(d)⋅A⋅diag(d)
+/// ```
+pub fn csr_scale_symmetric<T>(a: &mut NumCsrMatrix<T>, d: &NumVector<T>) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.nrow != a.ncol {
+        return Err("the matrix must be square");
+    }
+    if d.dim() != a.nrow {
+        return Err("the dimension of the vector of scaling factors is incorrect");
+    }
+    for i in 0..a.nrow {
+        for p in a.row_pointers[i]..a.row_pointers[i + 1] {
+            let j = a.col_indices[p as usize] as usize;
+            a.values[p as usize] *= d[i] * d[j];
+        }
+    }
+    Ok(())
+}
+
+/// Computes the complex conjugate of a CSR matrix
+///
+/// ```text
+/// A := Ā
+/// ```
+pub fn csr_conjugate(a: &mut ComplexCsrMatrix) {
+    for v in a.get_values_mut() {
+        *v = v.conj();
+    }
+}
+
+/// Returns the conjugate transpose (Hermitian adjoint) of a complex CSR matrix
+///
+/// ```text
+/// C := Aᴴ = Āᵀ
+/// ```
+///
+/// **Note:** The triangular representations of symmetric matrices (complex symmetric, not Hermitian)
+/// are handled as in [csr_transpose()].
+///
+/// # Examples
+///
+/// ```
+/// use russell_lab::{cpx, Complex64};
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // ┌             ┐
+///     // │ 1+i  2   0  │
+///     // │  0   0  3-i │
+///     // └             ┘
+///     let mut coo = ComplexCooMatrix::new(2, 3, 3, Sym::No)?;
+///     coo.put(0, 0, cpx!(1.0, 1.0))?;
+///     coo.put(0, 1, cpx!(2.0, 0.0))?;
+///     coo.put(1, 2, cpx!(3.0, -1.0))?;
+///     let a = ComplexCsrMatrix::from_coo(&coo)?;
+///     let ah = csr_conjugate_transpose(&a)?;
+///     let c = ah.as_dense();
+///     assert_eq!(c.dims(), (3, 2));
+///     assert_eq!(c.get(0, 0), cpx!(1.0, -1.0));
+///     assert_eq!(c.get(1, 0), cpx!(2.0, 0.0));
+///     assert_eq!(c.get(2, 1), cpx!(3.0, 1.0));
+///     Ok(())
+/// }
+/// ```
+pub fn csr_conjugate_transpose(a: &ComplexCsrMatrix) -> Result<ComplexCsrMatrix, StrError> {
+    let mut c = csr_transpose(a)?;
+    csr_conjugate(&mut c);
+    Ok(c)
+}
+
+/// Returns the transpose of a CSC matrix
+///
+/// ```text
+/// C := Aᵀ
+/// ```
+///
+/// **Note:** The transpose of a symmetric matrix with lower (upper) triangle representation
+/// is returned with the upper (lower) triangle representation.
+pub fn csc_transpose<T>(a: &NumCscMatrix<T>) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    Rows::from_csc(a).transpose().into_csc()
+}
+
+/// Computes the sparsity pattern of the sum of two CSC matrices (symbolic phase)
+///
+/// See [csr_add_symbolic()] for details.
+pub fn csc_add_symbolic<T>(a: &NumCscMatrix<T>, b: &NumCscMatrix<T>) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (at, bt, sym) = add_operands(Rows::from_csc(a), Rows::from_csc(b))?;
+    add_pattern(&at, &bt, sym).into_csc()
+}
+
+/// Computes the sum of two CSC matrices using a known pattern (numeric phase)
+///
+/// ```text
+/// C := α⋅A + β⋅B
+/// ```
+///
+/// See [csr_add_numeric()] for details.
+pub fn csc_add_numeric<T>(
+    c: &mut NumCscMatrix<T>,
+    alpha: T,
+    a: &NumCscMatrix<T>,
+    beta: T,
+    b: &NumCscMatrix<T>,
+) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (at, bt, sym) = add_operands(Rows::from_csc(a), Rows::from_csc(b))?;
+    check_result((c.ncol, c.nrow, swap_triangle(c.symmetric)), at.nrow, at.ncol, sym)?;
+    let mut target = Target::new(c.ncol, &c.col_pointers, &c.row_indices, &mut c.values, sym);
+    add_values(&mut target, alpha, &at, beta, &bt)
+}
+
+/// Computes the sum of two CSC matrices
+///
+/// ```text
+/// C := α⋅A + β⋅B
+/// ```
+///
+/// See [csr_add_symbolic()] regarding the symmetric type of C.
+pub fn csc_add<T>(alpha: T, a: &NumCscMatrix<T>, beta: T, b: &NumCscMatrix<T>) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut c = csc_add_symbolic(a, b)?;
+    csc_add_numeric(&mut c, alpha, a, beta, b)?;
+    Ok(c)
+}
+
+/// Computes the sparsity pattern of the product of two CSC matrices (symbolic phase)
+///
+/// See [csr_mat_mat_mul_symbolic()] for details.
+pub fn csc_mat_mat_mul_symbolic<T>(a: &NumCscMatrix<T>, b: &NumCscMatrix<T>) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    // Cᵀ = Bᵀ⋅Aᵀ
+    let (bt, at) = mul_operands(Rows::from_csc(b), Rows::from_csc(a))?;
+    mul_pattern(&bt, &at, Sym::No).into_csc()
+}
+
+/// Computes the product of two CSC matrices using a known pattern (numeric phase)
+///
+/// ```text
+/// C := α⋅A⋅B
+/// ```
+///
+/// See [csr_mat_mat_mul_numeric()] for details.
+pub fn csc_mat_mat_mul_numeric<T>(
+    c: &mut NumCscMatrix<T>,
+    alpha: T,
+    a: &NumCscMatrix<T>,
+    b: &NumCscMatrix<T>,
+) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (bt, at) = mul_operands(Rows::from_csc(b), Rows::from_csc(a))?;
+    check_result((c.ncol, c.nrow, c.symmetric), bt.nrow, at.ncol, Sym::No)?;
+    let mut target = Target::new(c.ncol, &c.col_pointers, &c.row_indices, &mut c.values, Sym::No);
+    mul_values(&mut target, alpha, &bt, &at)
+}
+
+/// Computes the product of two CSC matrices (SpGEMM)
+///
+/// ```text
+/// C := α⋅A⋅B
+/// ```
+///
+/// See [csr_mat_mat_mul()] for details.
+pub fn csc_mat_mat_mul<T>(alpha: T, a: &NumCscMatrix<T>, b: &NumCscMatrix<T>) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut c = csc_mat_mat_mul_symbolic(a, b)?;
+    csc_mat_mat_mul_numeric(&mut c, alpha, a, b)?;
+    Ok(c)
+}
+
+/// Computes the sparsity pattern of the triple product Pᵀ⋅A⋅P with CSC matrices (symbolic phase)
+///
+/// See [csr_triple_product_symbolic()] for details.
+pub fn csc_triple_product_symbolic<T>(p: &NumCscMatrix<T>, a: &NumCscMatrix<T>) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    // Cᵀ = Pᵀ⋅Aᵀ⋅P where the CSC arrays of P are the CSR arrays of Pᵀ
+    let (r, at, sym) = triple_operands(Rows::from_csc(p).transpose(), Rows::from_csc(a))?;
+    let ar = mul_pattern(&at, &r, Sym::No);
+    mul_pattern(&r.transpose(), &ar, sym).into_csc()
+}
+
+/// Computes the triple product Pᵀ⋅A⋅P with CSC matrices using a known pattern (numeric phase)
+///
+/// ```text
+/// C := Pᵀ⋅A⋅P
+/// ```
+///
+/// See [csr_triple_product_numeric()] for details.
+pub fn csc_triple_product_numeric<T>(
+    c: &mut NumCscMatrix<T>,
+    p: &NumCscMatrix<T>,
+    a: &NumCscMatrix<T>,
+) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (r, at, sym) = triple_operands(Rows::from_csc(p).transpose(), Rows::from_csc(a))?;
+    check_result((c.ncol, c.nrow, swap_triangle(c.symmetric)), r.ncol, r.ncol, sym)?;
+    let mut target = Target::new(c.ncol, &c.col_pointers, &c.row_indices, &mut c.values, sym);
+    triple_values(&mut target, &r, &at)
+}
+
+/// Computes the triple product Pᵀ⋅A⋅P with CSC matrices (e.g., Galerkin projection)
+///
+/// ```text
+/// C := Pᵀ⋅A⋅P
+/// ```
+///
+/// See [csr_triple_product()] for details.
+pub fn csc_triple_product<T>(p: &NumCscMatrix<T>, a: &NumCscMatrix<T>) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let mut c = csc_triple_product_symbolic(p, a)?;
+    csc_triple_product_numeric(&mut c, p, a)?;
+    Ok(c)
+}
+
+/// Scales a CSC matrix
+///
+/// ```text
+/// A := α⋅A
+/// ```
+pub fn csc_scale<T>(a: &mut NumCscMatrix<T>, alpha: T)
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    for v in a.get_values_mut() {
+        *v *= alpha;
+    }
+}
+
+/// Scales the rows of a CSC matrix
+///
+/// ```text
+/// A := diag(r)⋅A
+/// ```
+///
+/// **Note:** The matrix must not be symmetric (see [csc_scale_symmetric()]).
+pub fn csc_scale_rows<T>(a: &mut NumCscMatrix<T>, r: &NumVector<T>) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.symmetric != Sym::No {
+        return Err("the scaling of rows or columns is not available for symmetric matrices");
+    }
+    if r.dim() != a.nrow {
+        return Err("the dimension of the vector of scaling factors is incorrect");
+    }
+    let nnz = a.col_pointers[a.ncol] as usize;
+    for p in 0..nnz {
+        a.values[p] *= r[a.row_indices[p] as usize];
+    }
+    Ok(())
+}
+
+/// Scales the columns of a CSC matrix
+///
+/// ```text
+/// A := A⋅diag(c)
+/// ```
+///
+/// **Note:** The matrix must not be symmetric (see [csc_scale_symmetric()]).
+pub fn csc_scale_columns<T>(a: &mut NumCscMatrix<T>, c: &NumVector<T>) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.symmetric != Sym::No {
+        return Err("the scaling of rows or columns is not available for symmetric matrices");
+    }
+    if c.dim() != a.ncol {
+        return Err("the dimension of the vector of scaling factors is incorrect");
+    }
+    for j in 0..a.ncol {
+        for p in a.col_pointers[j]..a.col_pointers[j + 1] {
+            a.values[p as usize] *= c[j];
+        }
+    }
+    Ok(())
+}
+
+/// Scales the rows and columns of a square CSC matrix by the same factors (preserves symmetry)
+///
+/// ```text
+/// A := diag(d)⋅A⋅diag(d)
+/// ```
+pub fn csc_scale_symmetric<T>(a: &mut NumCscMatrix<T>, d: &NumVector<T>) -> Result<(), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.nrow != a.ncol {
+        return Err("the matrix must be square");
+    }
+    if d.dim() != a.ncol {
+        return Err("the dimension of the vector of scaling factors is incorrect");
+    }
+    for j in 0..a.ncol {
+        for p in a.col_pointers[j]..a.col_pointers[j + 1] {
+            let i = a.row_indices[p as usize] as usize;
+            a.values[p as usize] *= d[i] * d[j];
+        }
+    }
+    Ok(())
+}
+
+/// Computes the complex conjugate of a CSC matrix
+///
+/// ```text
+/// A := Ā
+/// ```
+pub fn csc_conjugate(a: &mut ComplexCscMatrix) {
+    for v in a.get_values_mut() {
+        *v = v.conj();
+    }
+}
+
+/// Returns the conjugate transpose (Hermitian adjoint) of a complex CSC matrix
+///
+/// ```text
+/// C := Aᴴ = Āᵀ
+/// ```
+///
+/// See [csr_conjugate_transpose()] for details.
+pub fn csc_conjugate_transpose(a: &ComplexCscMatrix) -> Result<ComplexCscMatrix, StrError> {
+    let mut c = csc_transpose(a)?;
+    csc_conjugate(&mut c);
+    Ok(c)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{csc_add, csc_mat_mat_mul, csc_scale, csc_scale_columns, csc_scale_rows, csc_scale_symmetric};
+    use super::{csc_conjugate_transpose, csr_conjugate, csr_conjugate_transpose};
+    use super::{csc_transpose, csc_triple_product, csr_add, csr_add_numeric, csr_add_symbolic, csr_mat_mat_mul};
+    use super::{csr_mat_mat_mul_numeric, csr_mat_mat_mul_symbolic, csr_scale, csr_scale_columns, csr_scale_rows};
+    use super::{csr_scale_symmetric, csr_transpose, csr_triple_product, csr_triple_product_numeric, swap_triangle};
+    use crate::{ComplexCooMatrix, ComplexCscMatrix, ComplexCsrMatrix, CooMatrix, CscMatrix, CsrMatrix, Samples, Sym};
+    use russell_lab::{complex_mat_approx_eq, cpx, mat_approx_eq, Complex64, ComplexMatrix, Matrix, Vector};
+
+    /// Returns the dense product C = op(A) ⋅ B with op(A) = A or Aᵀ
+    fn dense_mul(a: &Matrix, b: &Matrix, transpose_a: bool) -> Matrix {
+        let (m, k) = if transpose_a {
+            (a.ncol(), a.nrow())
+        } else {
+            (a.nrow(), a.ncol())
+        };
+        let mut c = Matrix::new(m, b.ncol());
+        for i in 0..m {
+            for j in 0..b.ncol() {
+                for l in 0..k {
+                    let ail = if transpose_a { a.get(l, i) } else { a.get(i, l) };
+                    c.add(i, j, ail * b.get(l, j));
+                }
+            }
+        }
+        c
+    }
+
+    /// Returns a rectangular (5 × 3) matrix (e.g., a prolongator)
+    fn prolongator() -> (CscMatrix, CsrMatrix) {
+        let mut coo = CooMatrix::new(5, 3, 7, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 0, 0.5).unwrap();
+        coo.put(1, 1, 0.5).unwrap();
+        coo.put(2, 1, 1.0).unwrap();
+        coo.put(3, 1, 0.5).unwrap();
+        coo.put(3, 2, 0.5).unwrap();
+        coo.put(4, 2, 1.0).unwrap();
+        (CscMatrix::from_coo(&coo).unwrap(), CsrMatrix::from_coo(&coo).unwrap())
+    }
+
+    #[test]
+    fn transpose_works() {
+        for (_, csc, csr, _) in [
+            Samples::rectangular_3x4(),
+            Samples::mkl_unsymmetric_5x5(),
+            Samples::mkl_positive_definite_5x5_lower(),
+            Samples::mkl_positive_definite_5x5_upper(),
+            Samples::mkl_symmetric_5x5_full(),
+        ] {
+            let a = csr.as_dense();
+            let mut a_t = Matrix::new(a.ncol(), a.nrow());
+            for i in 0..a.nrow() {
+                for j in 0..a.ncol() {
+                    a_t.set(j, i, a.get(i, j));
+                }
+            }
+            let csr_t = csr_transpose(&csr).unwrap();
+            let csc_t = csc_transpose(&csc).unwrap();
+            mat_approx_eq(&csr_t.as_dense(), &a_t, 1e-15);
+            mat_approx_eq(&csc_t.as_dense(), &a_t, 1e-15);
+            let sym_t = swap_triangle(csr.get_info().3);
+            assert_eq!(csr_t.get_info().3, sym_t);
+            assert_eq!(csc_t.get_info().3, sym_t);
+        }
+    }
+
+    #[test]
+    fn complex_functions_work() {
+        // A = ┌             ┐
+        //     │ 1+i  2   0  │
+        //     │  0  -i  3-i │
+        //     └             ┘
+        let mut coo = ComplexCooMatrix::new(2, 3, 4, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(1.0, 1.0)).unwrap();
+        coo.put(0, 1, cpx!(2.0, 0.0)).unwrap();
+        coo.put(1, 1, cpx!(0.0, -1.0)).unwrap();
+        coo.put(1, 2, cpx!(3.0, -1.0)).unwrap();
+        let csr = ComplexCsrMatrix::from_coo(&coo).unwrap();
+        let csc = ComplexCscMatrix::from_coo(&coo).unwrap();
+        let a = coo.as_dense();
+        let mut a_h = ComplexMatrix::new(3, 2);
+        for i in 0..2 {
+            for j in 0..3 {
+                a_h.set(j, i, a.get(i, j).conj());
+            }
+        }
+
+        // conjugate transpose
+        let csr_h = csr_conjugate_transpose(&csr).unwrap();
+        let csc_h = csc_conjugate_transpose(&csc).unwrap();
+        complex_mat_approx_eq(&csr_h.as_dense(), &a_h, 1e-15);
+        complex_mat_approx_eq(&csc_h.as_dense(), &a_h, 1e-15);
+        let mut csr_bar = csr.clone();
+        csr_conjugate(&mut csr_bar);
+        csr_conjugate(&mut csr_bar);
+        complex_mat_approx_eq(&csr_bar.as_dense(), &a, 1e-15);
+
+        // the generic functions work with complex matrices: B = Aᴴ·A is Hermitian
+        let b = csr_mat_mat_mul(cpx!(1.0, 0.0), &csr_h, &csr).unwrap();
+        let b_h = csr_conjugate_transpose(&b).unwrap();
+        complex_mat_approx_eq(&b.as_dense(), &b_h.as_dense(), 1e-15);
+        assert_eq!(b.as_dense().get(0, 0), cpx!(2.0, 0.0));
+        assert_eq!(b.as_dense().get(1, 2), cpx!(1.0, 3.0));
+        let b_csc = csc_mat_mat_mul(cpx!(1.0, 0.0), &csc_h, &csc).unwrap();
+        let b_csc_h = csc_conjugate_transpose(&b_csc).unwrap();
+        complex_mat_approx_eq(&b_csc.as_dense(), &b_csc_h.as_dense(), 1e-15);
+
+        // C = i·B - i·Bᴴ = 0 (because B is Hermitian)
+        let c = csr_add(cpx!(0.0, 1.0), &b, cpx!(0.0, -1.0), &b_h).unwrap();
+        complex_mat_approx_eq(&c.as_dense(), &ComplexMatrix::new(3, 3), 1e-15);
+    }
+
+    #[test]
+    fn add_captures_errors() {
+        let (_, csc_a, csr_a, _) = Samples::rectangular_3x4();
+        let (_, csc_b, csr_b, _) = Samples::mkl_unsymmetric_5x5();
+        assert_eq!(
+            csr_add(1.0, &csr_a, 1.0, &csr_b).err(),
+            Some("matrices are incompatible")
+        );
+        assert_eq!(
+            csc_add(1.0, &csc_a, 1.0, &csc_b).err(),
+            Some("matrices are incompatible")
+        );
+        let mut c = csr_add_symbolic(&csr_b, &csr_b).unwrap();
+        assert_eq!(
+            csr_add_numeric(&mut c, 1.0, &csr_a, 1.0, &csr_a).err(),
+            Some("the resulting matrix has incompatible dimensions")
+        );
+        let (_, _, csr_full, _) = Samples::mkl_symmetric_5x5_full();
+        assert_eq!(
+            csr_add_numeric(&mut c, 1.0, &csr_full, 1.0, &csr_full).err(),
+            Some("the resulting matrix has an incorrect symmetric type")
+        );
+        let mut coo = CooMatrix::new(5, 5, 5, Sym::No).unwrap();
+        for i in 0..5 {
+            coo.put(i, i, 1.0).unwrap();
+        }
+        let csr_d = CsrMatrix::from_coo(&coo).unwrap();
+        let mut c = csr_add_symbolic(&csr_d, &csr_d).unwrap();
+        assert_eq!(
+            csr_add_numeric(&mut c, 1.0, &csr_b, 1.0, &csr_b).err(),
+            Some("the pattern of the resulting matrix is incompatible (call the symbolic function first)")
+        );
+    }
+
+    #[test]
+    fn add_works() {
+        let samples = [
+            Samples::mkl_unsymmetric_5x5(),
+            Samples::umfpack_unsymmetric_5x5(),
+            Samples::mkl_positive_definite_5x5_lower(),
+            Samples::mkl_positive_definite_5x5_upper(),
+            Samples::mkl_symmetric_5x5_full(),
+        ];
+        for (_, csc_a, csr_a, _) in &samples {
+            for (_, csc_b, csr_b, _) in &samples {
+                let (sym_a, sym_b) = (csr_a.get_info().3, csr_b.get_info().3);
+                let sym = if sym_a == sym_b {
+                    sym_a
+                } else if sym_a != Sym::No && sym_b != Sym::No {
+                    Sym::YesFull
+                } else {
+                    Sym::No
+                };
+                let mut correct = csr_a.as_dense();
+                let b = csr_b.as_dense();
+                for i in 0..5 {
+                    for j in 0..5 {
+                        correct.set(i, j, 2.0 * correct.get(i, j) - 3.0 * b.get(i, j));
+                    }
+                }
+                let c = csr_add(2.0, csr_a, -3.0, csr_b).unwrap();
+                assert_eq!(c.get_info().3, sym);
+                mat_approx_eq(&c.as_dense(), &correct, 1e-15);
+                let c = csc_add(2.0, csc_a, -3.0, csc_b).unwrap();
+                assert_eq!(c.get_info().3, sym);
+                mat_approx_eq(&c.as_dense(), &correct, 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    fn add_numeric_reuses_the_pattern() {
+        let (coo, _, csr, _) = Samples::mkl_unsymmetric_5x5();
+        let mut c = csr_add_symbolic(&csr, &csr).unwrap();
+        let nnz = c.get_info().2;
+        for alpha in [1.0, 2.0, 3.0] {
+            csr_add_numeric(&mut c, alpha, &csr, 1.0, &csr).unwrap();
+            let mut correct = coo.as_dense();
+            for v in correct.as_mut_data() {
+                *v *= alpha + 1.0;
+            }
+            mat_approx_eq(&c.as_dense(), &correct, 1e-15);
+            assert_eq!(c.get_info().2, nnz);
+        }
+    }
+
+    #[test]
+    fn mat_mat_mul_captures_errors() {
+        let (_, csc_a, csr_a, _) = Samples::rectangular_3x4();
+        assert_eq!(
+            csr_mat_mat_mul(1.0, &csr_a, &csr_a).err(),
+            Some("matrices are incompatible")
+        );
+        assert_eq!(
+            csc_mat_mat_mul(1.0, &csc_a, &csc_a).err(),
+            Some("matrices are incompatible")
+        );
+        let (_, _, csr_b, _) = Samples::mkl_unsymmetric_5x5();
+        let mut c = csr_mat_mat_mul_symbolic(&csr_b, &csr_b).unwrap();
+        assert_eq!(
+            csr_mat_mat_mul_numeric(&mut c, 1.0, &csr_a, &csr_b).err(),
+            Some("matrices are incompatible")
+        );
+    }
+
+    #[test]
+    fn mat_mat_mul_works() {
+        let samples = [
+            Samples::mkl_unsymmetric_5x5(),
+            Samples::mkl_positive_definite_5x5_lower(),
+            Samples::mkl_positive_definite_5x5_upper(),
+            Samples::mkl_symmetric_5x5_full(),
+        ];
+        for (_, csc_a, csr_a, _) in &samples {
+            for (_, csc_b, csr_b, _) in &samples {
+                let mut correct = dense_mul(&csr_a.as_dense(), &csr_b.as_dense(), false);
+                for v in correct.as_mut_data() {
+                    *v *= 2.0;
+                }
+                let c = csr_mat_mat_mul(2.0, csr_a, csr_b).unwrap();
+                assert_eq!(c.get_info().3, Sym::No);
+                mat_approx_eq(&c.as_dense(), &correct, 1e-13);
+                let c = csc_mat_mat_mul(2.0, csc_a, csc_b).unwrap();
+                assert_eq!(c.get_info().3, Sym::No);
+                mat_approx_eq(&c.as_dense(), &correct, 1e-13);
+            }
+        }
+        // rectangular
+        let (csc_p, csr_p) = prolongator();
+        let (_, csc_a, csr_a, _) = Samples::rectangular_3x4();
+        let correct = dense_mul(&csr_p.as_dense(), &csr_a.as_dense(), false);
+        let c = csr_mat_mat_mul(1.0, &csr_p, &csr_a).unwrap();
+        mat_approx_eq(&c.as_dense(), &correct, 1e-15);
+        let c = csc_mat_mat_mul(1.0, &csc_p, &csc_a).unwrap();
+        mat_approx_eq(&c.as_dense(), &correct, 1e-15);
+    }
+
+    #[test]
+    fn triple_product_works() {
+        let (csc_p, csr_p) = prolongator();
+        let pp = csr_p.as_dense();
+        for (_, csc_a, csr_a, _) in [
+            Samples::mkl_unsymmetric_5x5(),
+            Samples::mkl_positive_definite_5x5_lower(),
+            Samples::mkl_positive_definite_5x5_upper(),
+            Samples::mkl_symmetric_5x5_full(),
+        ] {
+            let ap = dense_mul(&csr_a.as_dense(), &pp, false);
+            let correct = dense_mul(&pp, &ap, true);
+            let sym = csr_a.get_info().3;
+            let mut c = csr_triple_product(&csr_p, &csr_a).unwrap();
+            assert_eq!(c.get_info().3, sym);
+            mat_approx_eq(&c.as_dense(), &correct, 1e-14);
+            let c_csc = csc_triple_product(&csc_p, &csc_a).unwrap();
+            assert_eq!(c_csc.get_info().3, sym);
+            mat_approx_eq(&c_csc.as_dense(), &correct, 1e-14);
+
+            // numeric phase with scaled A
+            let mut a_scaled = csr_a.clone();
+            csr_scale(&mut a_scaled, 2.0);
+            csr_triple_product_numeric(&mut c, &csr_p, &a_scaled).unwrap();
+            let mut correct2 = correct.clone();
+            for v in correct2.as_mut_data() {
+                *v *= 2.0;
+            }
+            mat_approx_eq(&c.as_dense(), &correct2, 1e-14);
+        }
+        let (_, _, csr_a, _) = Samples::rectangular_3x4();
+        assert_eq!(
+            csr_triple_product(&csr_p, &csr_a).err(),
+            Some("matrices are incompatible")
+        );
+    }
+
+    #[test]
+    fn scale_works() {
+        let (_, mut csc, mut csr, _) = Samples::rectangular_3x4();
+        let a = csr.as_dense();
+        let r = Vector::from(&[1.0, 2.0, 3.0]);
+        let c = Vector::from(&[-1.0, 1.0, 10.0, 100.0]);
+        let mut correct = Matrix::new(3, 4);
+        for i in 0..3 {
+            for j in 0..4 {
+                correct.set(i, j, 0.5 * r[i] * a.get(i, j) * c[j]);
+            }
+        }
+        csr_scale(&mut csr, 0.5);
+        csr_scale_rows(&mut csr, &r).unwrap();
+        csr_scale_columns(&mut csr, &c).unwrap();
+        mat_approx_eq(&csr.as_dense(), &correct, 1e-15);
+        csc_scale(&mut csc, 0.5);
+        csc_scale_rows(&mut csc, &r).unwrap();
+        csc_scale_columns(&mut csc, &c).unwrap();
+        mat_approx_eq(&csc.as_dense(), &correct, 1e-15);
+        assert_eq!(
+            csr_scale_rows(&mut csr, &c).err(),
+            Some("the dimension of the vector of scaling factors is incorrect")
+        );
+        assert_eq!(
+            csc_scale_columns(&mut csc, &r).err(),
+            Some("the dimension of the vector of scaling factors is incorrect")
+        );
+        assert_eq!(
+            csr_scale_symmetric(&mut csr, &r).err(),
+            Some("the matrix must be square")
+        );
+
+        // symmetric
+        let d = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        for (_, mut csc, mut csr, _) in [
+            Samples::mkl_positive_definite_5x5_lower(),
+            Samples::mkl_positive_definite_5x5_upper(),
+            Samples::mkl_symmetric_5x5_full(),
+        ] {
+            let a = csr.as_dense();
+            let mut correct = Matrix::new(5, 5);
+            for i in 0..5 {
+                for j in 0..5 {
+                    correct.set(i, j, d[i] * a.get(i, j) * d[j]);
+                }
+            }
+            assert_eq!(
+                csr_scale_rows(&mut csr, &d).err(),
+                Some("the scaling of rows or columns is not available for symmetric matrices")
+            );
+            assert_eq!(
+                csc_scale_columns(&mut csc, &d).err(),
+                Some("the scaling of rows or columns is not available for symmetric matrices")
+            );
+            csr_scale_symmetric(&mut csr, &d).unwrap();
+            csc_scale_symmetric(&mut csc, &d).unwrap();
+            mat_approx_eq(&csr.as_dense(), &correct, 1e-13);
+            mat_approx_eq(&csc.as_dense(), &correct, 1e-13);
+        }
+    }
+}
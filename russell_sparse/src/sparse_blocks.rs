@@ -0,0 +1,1125 @@
+use super::{permutation_inverse, stored, NumCooMatrix, NumCscMatrix, NumCsrMatrix, Rows, Sym};
+use crate::StrError;
+use num_traits::{Num, NumCast};
+use russell_lab::NumVector;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ops::{AddAssign, MulAssign};
+
+/// Indicates a free position in the workspace arrays
+const NONE: usize = usize::MAX;
+
+/// Returns the map from the original indices to their position in the list (or NONE)
+fn index_map(n: usize, list: &[usize]) -> Result<Vec<usize>, StrError> {
+    if list.is_empty() {
+        return Err("the list of indices must not be empty");
+    }
+    let mut map = vec![NONE; n];
+    for (k, &i) in list.iter().enumerate() {
+        if i >= n {
+            return Err("the list of indices has an out-of-range index");
+        }
+        if map[i] != NONE {
+            return Err("the list of indices has a repeated index");
+        }
+        map[i] = k;
+    }
+    Ok(map)
+}
+
+/// Returns the symmetric type of A[I, J]
+///
+/// The symmetry (and the triangular representation) is only preserved if I = J.
+fn submatrix_sym(sym: Sym, rows: &[usize], cols: &[usize]) -> Sym {
+    if rows == cols {
+        sym
+    } else {
+        Sym::No
+    }
+}
+
+/// Extracts the submatrix B = A[I, J] from the compressed rows of A
+fn rows_submatrix<T>(a: &Rows<T>, rows: &[usize], cols: &[usize]) -> Result<Rows<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    index_map(a.nrow, rows)?;
+    let col_map = index_map(a.ncol, cols)?;
+    let sym = submatrix_sym(a.sym, rows, cols);
+    let full;
+    let a = if a.sym.triangular() {
+        full = a.full();
+        &full
+    } else {
+        a
+    };
+    let mut res = Rows {
+        nrow: rows.len(),
+        ncol: cols.len(),
+        ptr: vec![0; rows.len() + 1],
+        idx: Vec::new(),
+        val: Vec::new(),
+        sym,
+    };
+    let mut row = Vec::new();
+    for (k, &i) in rows.iter().enumerate() {
+        row.clear();
+        for p in a.ptr[i]..a.ptr[i + 1] {
+            let l = col_map[a.idx[p]];
+            if l != NONE && stored(sym, k, l) {
+                row.push((l, a.val[p]));
+            }
+        }
+        row.sort_by_key(|e| e.0);
+        for (l, bkl) in &row {
+            res.idx.push(*l);
+            res.val.push(*bkl);
+        }
+        res.ptr[k + 1] = res.idx.len();
+    }
+    Ok(res)
+}
+
+/// Checks the row and column permutations of a (nrow × ncol) matrix
+fn check_permutations(nrow: usize, ncol: usize, row_perm: &[usize], col_perm: &[usize]) -> Result<(), StrError> {
+    if row_perm.len() != nrow {
+        return Err("the row permutation length must equal the number of rows");
+    }
+    if col_perm.len() != ncol {
+        return Err("the column permutation length must equal the number of columns");
+    }
+    permutation_inverse(row_perm)?;
+    permutation_inverse(col_perm)?;
+    Ok(())
+}
+
+/// Holds the (nrow, ncol, sym) information of a block (None indicates a zero block)
+type BlockInfo = Option<(usize, usize, Sym)>;
+
+/// Holds the layout of a block matrix
+struct BlockLayout {
+    /// The offsets of the block rows (len = number of block rows + 1)
+    row_offsets: Vec<usize>,
+
+    /// The offsets of the block columns (len = number of block columns + 1)
+    col_offsets: Vec<usize>,
+
+    /// The symmetric type of the assembled matrix
+    sym: Sym,
+}
+
+impl BlockLayout {
+    /// Computes the layout from the (nrow, ncol, sym) information of each block
+    fn new(info: &[Vec<BlockInfo>]) -> Result<Self, StrError> {
+        let nbr = info.len();
+        if nbr == 0 {
+            return Err("there must be at least one block row");
+        }
+        let nbc = info[0].len();
+        if nbc == 0 {
+            return Err("there must be at least one block column");
+        }
+        if info.iter().any(|row| row.len() != nbc) {
+            return Err("all block rows must have the same number of blocks");
+        }
+        let mut row_sizes = vec![0; nbr];
+        let mut col_sizes = vec![0; nbc];
+        for (bi, row) in info.iter().enumerate() {
+            for (bj, block) in row.iter().enumerate() {
+                if let Some((m, n, _)) = block {
+                    if row_sizes[bi] == 0 {
+                        row_sizes[bi] = *m;
+                    } else if row_sizes[bi] != *m {
+                        return Err("the blocks in the same block row must have the same number of rows");
+                    }
+                    if col_sizes[bj] == 0 {
+                        col_sizes[bj] = *n;
+                    } else if col_sizes[bj] != *n {
+                        return Err("the blocks in the same block column must have the same number of columns");
+                    }
+                }
+            }
+        }
+        if row_sizes.contains(&0) {
+            return Err("each block row must have at least one block");
+        }
+        if col_sizes.contains(&0) {
+            return Err("each block column must have at least one block");
+        }
+        let mut row_offsets = vec![0; nbr + 1];
+        let mut col_offsets = vec![0; nbc + 1];
+        for bi in 0..nbr {
+            row_offsets[bi + 1] = row_offsets[bi] + row_sizes[bi];
+        }
+        for bj in 0..nbc {
+            col_offsets[bj + 1] = col_offsets[bj] + col_sizes[bj];
+        }
+        // the triangular representation is kept if all diagonal blocks share it
+        // and there are no blocks outside the stored triangle
+        let mut sym = Sym::No;
+        if nbr == nbc {
+            if let Some((_, _, s)) = info[0][0] {
+                let diagonal = (0..nbr).all(|k| matches!(info[k][k], Some((_, _, d)) if d == s));
+                let outside = (0..nbr).any(|bi| (0..nbc).any(|bj| info[bi][bj].is_some() && !stored(s, bi, bj)));
+                if s.triangular() && diagonal && !outside {
+                    sym = s;
+                }
+            }
+        }
+        Ok(BlockLayout {
+            row_offsets,
+            col_offsets,
+            sym,
+        })
+    }
+
+    /// Returns true if the triangle of the (bi, bj) block must be kept (i.e., not mirrored)
+    fn keep_triangle(&self, bi: usize, bj: usize) -> bool {
+        bi == bj && self.sym.triangular()
+    }
+}
+
+/// Assembles the compressed rows of a block matrix
+fn rows_block_assemble<T>(mut blocks: Vec<Vec<Option<Rows<T>>>>) -> Result<Rows<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let info: Vec<Vec<_>> = blocks
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|b| b.as_ref().map(|b| (b.nrow, b.ncol, b.sym)))
+                .collect()
+        })
+        .collect();
+    let layout = BlockLayout::new(&info)?;
+    for (bi, row) in blocks.iter_mut().enumerate() {
+        for (bj, block) in row.iter_mut().enumerate() {
+            if let Some(b) = block {
+                if b.sym.triangular() && !layout.keep_triangle(bi, bj) {
+                    *b = b.full();
+                }
+            }
+        }
+    }
+    let nrow = *layout.row_offsets.last().unwrap();
+    let ncol = *layout.col_offsets.last().unwrap();
+    let mut res = Rows {
+        nrow,
+        ncol,
+        ptr: Vec::with_capacity(nrow + 1),
+        idx: Vec::new(),
+        val: Vec::new(),
+        sym: layout.sym,
+    };
+    res.ptr.push(0);
+    for (bi, row) in blocks.iter().enumerate() {
+        for r in 0..(layout.row_offsets[bi + 1] - layout.row_offsets[bi]) {
+            for (bj, block) in row.iter().enumerate() {
+                if let Some(b) = block {
+                    for p in b.ptr[r]..b.ptr[r + 1] {
+                        res.idx.push(layout.col_offsets[bj] + b.idx[p]);
+                        res.val.push(b.val[p]);
+                    }
+                }
+            }
+            res.ptr.push(res.idx.len());
+        }
+    }
+    Ok(res)
+}
+
+/// Computes the reduced right-hand side `b_f := b[free] - A[free, prescribed] ⋅ x_p`
+fn reduced_rhs<T, I>(
+    entries: I,
+    triangular: bool,
+    free: &[usize],
+    prescribed: &[usize],
+    x_prescribed: &NumVector<T>,
+    rhs: &NumVector<T>,
+) -> NumVector<T>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+    I: Iterator<Item = (usize, usize, T)>,
+{
+    let n = rhs.dim();
+    let mut position = vec![NONE; n];
+    let mut is_prescribed = vec![false; n];
+    for (k, &i) in free.iter().enumerate() {
+        position[i] = k;
+    }
+    for (k, &i) in prescribed.iter().enumerate() {
+        position[i] = k;
+        is_prescribed[i] = true;
+    }
+    let mut rhs_free = NumVector::initialized(free.len(), |k| rhs[free[k]]);
+    for (i, j, aij) in entries {
+        if !is_prescribed[i] && is_prescribed[j] {
+            rhs_free[position[i]] = rhs_free[position[i]] - aij * x_prescribed[position[j]];
+        }
+        if triangular && i != j && is_prescribed[i] && !is_prescribed[j] {
+            rhs_free[position[j]] = rhs_free[position[j]] - aij * x_prescribed[position[i]];
+        }
+    }
+    rhs_free
+}
+
+/// Checks the data of the DOF elimination and returns the free DOFs
+fn check_elimination<T>(
+    nrow: usize,
+    ncol: usize,
+    prescribed: &[usize],
+    x_prescribed: &NumVector<T>,
+    rhs: &NumVector<T>,
+) -> Result<Vec<usize>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if nrow != ncol {
+        return Err("the matrix must be square");
+    }
+    if x_prescribed.dim() != prescribed.len() {
+        return Err("the number of prescribed values must equal the number of prescribed DOFs");
+    }
+    if rhs.dim() != nrow {
+        return Err("the right-hand side vector must have the same dimension as the matrix");
+    }
+    let free = free_dofs(nrow, prescribed)?;
+    if free.is_empty() {
+        return Err("there must be at least one free DOF");
+    }
+    Ok(free)
+}
+
+/// Returns the free DOFs (sorted) given the prescribed ones
+///
+/// # Input
+///
+/// * `ndof` -- the total number of DOFs
+/// * `prescribed` -- the prescribed DOFs (in any order; no repetitions)
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     let free = free_dofs(5, &[3, 0])?;
+///     assert_eq!(free, &[1, 2, 4]);
+///     Ok(())
+/// }
+/// ```
+pub fn free_dofs(ndof: usize, prescribed: &[usize]) -> Result<Vec<usize>, StrError> {
+    let mut is_prescribed = vec![false; ndof];
+    for &i in prescribed {
+        if i >= ndof {
+            return Err("the prescribed DOFs have an out-of-range index");
+        }
+        if is_prescribed[i] {
+            return Err("the prescribed DOFs have a repeated index");
+        }
+        is_prescribed[i] = true;
+    }
+    Ok((0..ndof).filter(|&i| !is_prescribed[i]).collect())
+}
+
+/// Assembles the full solution vector from the free and prescribed values
+///
+/// ```text
+/// x[free] := x_free  and  x[prescribed] := x_prescribed
+/// ```
+///
+/// This function is the counterpart of the `*_eliminate_dofs` functions.
+pub fn expand_dofs<T>(
+    x_free: &NumVector<T>,
+    prescribed: &[usize],
+    x_prescribed: &NumVector<T>,
+) -> Result<NumVector<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if x_prescribed.dim() != prescribed.len() {
+        return Err("the number of prescribed values must equal the number of prescribed DOFs");
+    }
+    let ndof = x_free.dim() + prescribed.len();
+    let free = free_dofs(ndof, prescribed)?;
+    let mut x = NumVector::new(ndof);
+    for (k, &i) in free.iter().enumerate() {
+        x[i] = x_free[k];
+    }
+    for (k, &i) in prescribed.iter().enumerate() {
+        x[i] = x_prescribed[k];
+    }
+    Ok(x)
+}
+
+/// Extracts a submatrix of a COO matrix
+///
+/// ```text
+/// B := A[I, J]  with  B[k][l] = A[rows[k]][cols[l]]
+/// ```
+///
+/// # Notes
+///
+/// 1. The lists of indices may be given in any order, but cannot have repeated entries
+/// 2. If `rows == cols`, B has the same symmetric type as A and the entries are moved to the
+///    stored triangle (if any). Otherwise, B has the [Sym::No] type (the triangle is mirrored)
+/// 3. The duplicate entries, if any, are preserved
+pub fn coo_submatrix<T>(a: &NumCooMatrix<T>, rows: &[usize], cols: &[usize]) -> Result<NumCooMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let row_map = index_map(a.nrow, rows)?;
+    let col_map = index_map(a.ncol, cols)?;
+    let sym = submatrix_sym(a.symmetric, rows, cols);
+    let mirror = a.symmetric.triangular();
+    let mut entries = Vec::new();
+    for p in 0..a.nnz {
+        let (i, j) = (a.indices_i[p] as usize, a.indices_j[p] as usize);
+        if row_map[i] != NONE && col_map[j] != NONE {
+            entries.push((row_map[i], col_map[j], a.values[p]));
+        }
+        if mirror && i != j && row_map[j] != NONE && col_map[i] != NONE {
+            entries.push((row_map[j], col_map[i], a.values[p]));
+        }
+    }
+    entries.retain(|&(k, l, _)| stored(sym, k, l));
+    let mut b = NumCooMatrix::new(rows.len(), cols.len(), usize::max(1, entries.len()), sym)?;
+    for (k, l, bkl) in entries {
+        b.put(k, l, bkl)?;
+    }
+    Ok(b)
+}
+
+/// Extracts a submatrix of a CSR matrix
+///
+/// ```text
+/// B := A[I, J]  with  B[k][l] = A[rows[k]][cols[l]]
+/// ```
+///
+/// # Notes
+///
+/// 1. The lists of indices may be given in any order, but cannot have repeated entries
+/// 2. If `rows == cols`, B has the same symmetric type as A and the entries are moved to the
+///    stored triangle (if any). Otherwise, B has the [Sym::No] type (the triangle is mirrored)
+/// 3. The column indices of B are sorted within each row
+/// 4. An error is returned if B has no non-zero entries
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // ┌       ┐
+///     // │ 1 2 0 │
+///     // │ 0 3 4 │
+///     // │ 5 0 6 │
+///     // └       ┘
+///     let a = CsrMatrix::new(
+///         3,
+///         3,
+///         vec![0, 2, 4, 6],
+///         vec![0, 1, 1, 2, 0, 2],
+///         vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+///         Sym::No,
+///     )?;
+///     let b = csr_submatrix(&a, &[2, 0], &[0, 2])?;
+///     let correct = "┌     ┐\n\
+///                    │ 5 6 │\n\
+///                    │ 1 0 │\n\
+///                    └     ┘";
+///     assert_eq!(format!("{}", b.as_dense()), correct);
+///     Ok(())
+/// }
+/// ```
+pub fn csr_submatrix<T>(a: &NumCsrMatrix<T>, rows: &[usize], cols: &[usize]) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    rows_submatrix(&Rows::from_csr(a), rows, cols)?.into_csr()
+}
+
+/// Extracts a submatrix of a CSC matrix
+///
+/// ```text
+/// B := A[I, J]  with  B[k][l] = A[rows[k]][cols[l]]
+/// ```
+///
+/// See [csr_submatrix()] for the notes.
+pub fn csc_submatrix<T>(a: &NumCscMatrix<T>, rows: &[usize], cols: &[usize]) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    rows_submatrix(&Rows::from_csc(a), cols, rows)?.into_csc()
+}
+
+/// Permutes the rows and columns of a COO matrix
+///
+/// ```text
+/// B := P⋅A⋅Qᵀ  with  B[k][l] = A[row_perm[k]][col_perm[l]]
+/// ```
+///
+/// **Note:** If `row_perm == col_perm`, the symmetric type of A is preserved (see [coo_permute_symmetric()](crate::coo_permute_symmetric)).
+/// Otherwise, B has the [Sym::No] type.
+pub fn coo_permute<T>(a: &NumCooMatrix<T>, row_perm: &[usize], col_perm: &[usize]) -> Result<NumCooMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    check_permutations(a.nrow, a.ncol, row_perm, col_perm)?;
+    coo_submatrix(a, row_perm, col_perm)
+}
+
+/// Permutes the rows and columns of a CSR matrix
+///
+/// ```text
+/// B := P⋅A⋅Qᵀ  with  B[k][l] = A[row_perm[k]][col_perm[l]]
+/// ```
+///
+/// **Note:** If `row_perm == col_perm`, the symmetric type of A is preserved (see [csr_permute_symmetric()](crate::csr_permute_symmetric)).
+/// Otherwise, B has the [Sym::No] type.
+pub fn csr_permute<T>(a: &NumCsrMatrix<T>, row_perm: &[usize], col_perm: &[usize]) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    check_permutations(a.nrow, a.ncol, row_perm, col_perm)?;
+    csr_submatrix(a, row_perm, col_perm)
+}
+
+/// Permutes the rows and columns of a CSC matrix
+///
+/// ```text
+/// B := P⋅A⋅Qᵀ  with  B[k][l] = A[row_perm[k]][col_perm[l]]
+/// ```
+///
+/// **Note:** If `row_perm == col_perm`, the symmetric type of A is preserved. Otherwise, B has the [Sym::No] type.
+pub fn csc_permute<T>(a: &NumCscMatrix<T>, row_perm: &[usize], col_perm: &[usize]) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    check_permutations(a.nrow, a.ncol, row_perm, col_perm)?;
+    csc_submatrix(a, row_perm, col_perm)
+}
+
+/// Eliminates the prescribed DOFs of the linear system `A ⋅ x = b` (COO matrix)
+///
+/// Splitting the DOFs into free (f) and prescribed (p) sets, the reduced system is
+///
+/// ```text
+/// A_ff ⋅ x_f = b_f - A_fp ⋅ x_p
+/// ```
+///
+/// # Input
+///
+/// * `a` -- the (square) coefficient matrix
+/// * `prescribed` -- the prescribed DOFs (in any order; no repetitions)
+/// * `x_prescribed` -- the prescribed values (len = prescribed.len())
+/// * `rhs` -- the right-hand side vector b
+///
+/// # Output
+///
+/// Returns `(A_ff, b_f - A_fp ⋅ x_p)`. The free DOFs are given by [free_dofs()] and the
+/// full solution may be recovered with [expand_dofs()].
+pub fn coo_eliminate_dofs<T>(
+    a: &NumCooMatrix<T>,
+    prescribed: &[usize],
+    x_prescribed: &NumVector<T>,
+    rhs: &NumVector<T>,
+) -> Result<(NumCooMatrix<T>, NumVector<T>), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let free = check_elimination(a.nrow, a.ncol, prescribed, x_prescribed, rhs)?;
+    let entries = (0..a.nnz).map(|p| (a.indices_i[p] as usize, a.indices_j[p] as usize, a.values[p]));
+    let triangular = a.symmetric.triangular();
+    let rhs_free = reduced_rhs(entries, triangular, &free, prescribed, x_prescribed, rhs);
+    Ok((coo_submatrix(a, &free, &free)?, rhs_free))
+}
+
+/// Eliminates the prescribed DOFs of the linear system `A ⋅ x = b` (CSR matrix)
+///
+/// Splitting the DOFs into free (f) and prescribed (p) sets, the reduced system is
+///
+/// ```text
+/// A_ff ⋅ x_f = b_f - A_fp ⋅ x_p
+/// ```
+///
+/// # Input
+///
+/// * `a` -- the (square) coefficient matrix
+/// * `prescribed` -- the prescribed DOFs (in any order; no repetitions)
+/// * `x_prescribed` -- the prescribed values (len = prescribed.len())
+/// * `rhs` -- the right-hand side vector b
+///
+/// # Output
+///
+/// Returns `(A_ff, b_f - A_fp ⋅ x_p)`. The free DOFs are given by [free_dofs()] and the
+/// full solution may be recovered with [expand_dofs()].
+///
+/// # Examples
+///
+/// ```
+/// use russell_lab::{vec_approx_eq, Vector};
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // ┌          ┐
+///     // │  2 -1  0 │
+///     // │ -1  2 -1 │
+///     // │  0 -1  2 │
+///     // └          ┘
+///     let a = CsrMatrix::new(
+///         3,
+///         3,
+///         vec![0, 2, 5, 7],
+///         vec![0, 1, 0, 1, 2, 1, 2],
+///         vec![2.0, -1.0, -1.0, 2.0, -1.0, -1.0, 2.0],
+///         Sym::No,
+///     )?;
+///
+///     // prescribe x[0] = 1 and x[2] = 3
+///     let prescribed = &[0, 2];
+///     let x_prescribed = Vector::from(&[1.0, 3.0]);
+///     let rhs = Vector::new(3);
+///     let (a_ff, b_f) = csr_eliminate_dofs(&a, prescribed, &x_prescribed, &rhs)?;
+///     assert_eq!(a_ff.get_info(), (1, 1, 1, Sym::No));
+///     vec_approx_eq(&b_f, &[4.0], 1e-15);
+///
+///     // solve the reduced system (x[1] = 2) and recover the full solution
+///     let x_free = Vector::from(&[b_f[0] / a_ff.get_values()[0]]);
+///     let x = expand_dofs(&x_free, prescribed, &x_prescribed)?;
+///     vec_approx_eq(&x, &[1.0, 2.0, 3.0], 1e-15);
+///     Ok(())
+/// }
+/// ```
+pub fn csr_eliminate_dofs<T>(
+    a: &NumCsrMatrix<T>,
+    prescribed: &[usize],
+    x_prescribed: &NumVector<T>,
+    rhs: &NumVector<T>,
+) -> Result<(NumCsrMatrix<T>, NumVector<T>), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let free = check_elimination(a.nrow, a.ncol, prescribed, x_prescribed, rhs)?;
+    let entries = (0..a.nrow).flat_map(|i| {
+        let range = (a.row_pointers[i] as usize)..(a.row_pointers[i + 1] as usize);
+        range.map(move |p| (i, a.col_indices[p] as usize, a.values[p]))
+    });
+    let triangular = a.symmetric.triangular();
+    let rhs_free = reduced_rhs(entries, triangular, &free, prescribed, x_prescribed, rhs);
+    Ok((csr_submatrix(a, &free, &free)?, rhs_free))
+}
+
+/// Eliminates the prescribed DOFs of the linear system `A ⋅ x = b` (CSC matrix)
+///
+/// Splitting the DOFs into free (f) and prescribed (p) sets, the reduced system is
+///
+/// ```text
+/// A_ff ⋅ x_f = b_f - A_fp ⋅ x_p
+/// ```
+///
+/// See [csr_eliminate_dofs()] for the input and output.
+pub fn csc_eliminate_dofs<T>(
+    a: &NumCscMatrix<T>,
+    prescribed: &[usize],
+    x_prescribed: &NumVector<T>,
+    rhs: &NumVector<T>,
+) -> Result<(NumCscMatrix<T>, NumVector<T>), StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let free = check_elimination(a.nrow, a.ncol, prescribed, x_prescribed, rhs)?;
+    let entries = (0..a.ncol).flat_map(|j| {
+        let range = (a.col_pointers[j] as usize)..(a.col_pointers[j + 1] as usize);
+        range.map(move |p| (a.row_indices[p] as usize, j, a.values[p]))
+    });
+    let triangular = a.symmetric.triangular();
+    let rhs_free = reduced_rhs(entries, triangular, &free, prescribed, x_prescribed, rhs);
+    Ok((csc_submatrix(a, &free, &free)?, rhs_free))
+}
+
+/// Assembles a block matrix from COO matrices
+///
+/// ```text
+///     ┌                 ┐
+///     │ A₀₀  A₀₁  ⋯ A₀ₙ │
+/// B = │ A₁₀  A₁₁  ⋯ A₁ₙ │
+///     │  ⋮    ⋮   ⋱  ⋮  │
+///     └                 ┘
+/// ```
+///
+/// See [csr_block_assemble()] for the notes.
+pub fn coo_block_assemble<T>(blocks: &[&[Option<&NumCooMatrix<T>>]]) -> Result<NumCooMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let info: Vec<Vec<_>> = blocks
+        .iter()
+        .map(|row| row.iter().map(|b| b.map(|b| (b.nrow, b.ncol, b.symmetric))).collect())
+        .collect();
+    let layout = BlockLayout::new(&info)?;
+    let mut max_nnz = 0;
+    for row in blocks {
+        for b in row.iter().flatten() {
+            max_nnz += if b.symmetric.triangular() { 2 * b.nnz } else { b.nnz };
+        }
+    }
+    let nrow = *layout.row_offsets.last().unwrap();
+    let ncol = *layout.col_offsets.last().unwrap();
+    let mut res = NumCooMatrix::new(nrow, ncol, usize::max(1, max_nnz), layout.sym)?;
+    for (bi, row) in blocks.iter().enumerate() {
+        for (bj, block) in row.iter().enumerate() {
+            if let Some(b) = block {
+                let (r0, c0) = (layout.row_offsets[bi], layout.col_offsets[bj]);
+                let mirror = b.symmetric.triangular() && !layout.keep_triangle(bi, bj);
+                for p in 0..b.nnz {
+                    let (i, j) = (b.indices_i[p] as usize, b.indices_j[p] as usize);
+                    res.put(r0 + i, c0 + j, b.values[p])?;
+                    if mirror && i != j {
+                        res.put(r0 + j, c0 + i, b.values[p])?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Assembles a block matrix from CSR matrices
+///
+/// ```text
+///     ┌                 ┐
+///     │ A₀₀  A₀₁  ⋯ A₀ₙ │
+/// B = │ A₁₀  A₁₁  ⋯ A₁ₙ │
+///     │  ⋮    ⋮   ⋱  ⋮  │
+///     └                 ┘
+/// ```
+///
+/// # Input
+///
+/// * `blocks` -- the grid of blocks (row-major); `None` indicates a zero block
+///
+/// # Notes
+///
+/// 1. The blocks in the same block row (column) must have the same number of rows (columns)
+/// 2. Each block row and each block column must have at least one block
+/// 3. If the grid is square, all diagonal blocks have the same triangular representation
+///    (e.g., [Sym::YesLower]), and there are no blocks outside the stored triangle, B has
+///    that representation too. This allows assembling symmetric saddle-point matrices such as
+///    `[[K, None], [G, M]]` representing `[[K, Gᵀ], [G, M]]`
+/// 4. Otherwise, the triangular blocks are mirrored and B has the [Sym::No] type
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     let a = CsrMatrix::new(2, 2, vec![0, 1, 2], vec![0, 1], vec![1.0, 2.0], Sym::No)?;
+///     let b = CsrMatrix::new(2, 1, vec![0, 1, 2], vec![0, 0], vec![3.0, 4.0], Sym::No)?;
+///     let c = CsrMatrix::new(1, 2, vec![0, 1], vec![1], vec![5.0], Sym::No)?;
+///     let m = csr_block_assemble(&[&[Some(&a), Some(&b)], &[Some(&c), None]])?;
+///     let correct = "┌       ┐\n\
+///                    │ 1 0 3 │\n\
+///                    │ 0 2 4 │\n\
+///                    │ 0 5 0 │\n\
+///                    └       ┘";
+///     assert_eq!(format!("{}", m.as_dense()), correct);
+///     Ok(())
+/// }
+/// ```
+pub fn csr_block_assemble<T>(blocks: &[&[Option<&NumCsrMatrix<T>>]]) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let grid = blocks
+        .iter()
+        .map(|row| row.iter().map(|b| b.map(Rows::from_csr)).collect())
+        .collect();
+    rows_block_assemble(grid)?.into_csr()
+}
+
+/// Assembles a block matrix from CSC matrices
+///
+/// ```text
+///     ┌                 ┐
+///     │ A₀₀  A₀₁  ⋯ A₀ₙ │
+/// B = │ A₁₀  A₁₁  ⋯ A₁ₙ │
+///     │  ⋮    ⋮   ⋱  ⋮  │
+///     └                 ┘
+/// ```
+///
+/// See [csr_block_assemble()] for the notes.
+pub fn csc_block_assemble<T>(blocks: &[&[Option<&NumCscMatrix<T>>]]) -> Result<NumCscMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    // the compressed rows of Bᵀ are assembled from the transposed grid
+    let nbc = blocks.first().map_or(0, |row| row.len());
+    if blocks.iter().any(|row| row.len() != nbc) {
+        return Err("all block rows must have the same number of blocks");
+    }
+    let grid = (0..nbc)
+        .map(|bj| blocks.iter().map(|row| row[bj].map(Rows::from_csc)).collect())
+        .collect();
+    rows_block_assemble(grid)?.into_csc()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{csr_permute_symmetric, csr_transpose, CooMatrix, CscMatrix, CsrMatrix, Samples};
+    use russell_lab::{mat_approx_eq, vec_approx_eq, Matrix, Vector};
+
+    /// Returns the dense submatrix A[I, J]
+    fn dense_sub(a: &Matrix, rows: &[usize], cols: &[usize]) -> Matrix {
+        Matrix::initialized(rows.len(), cols.len(), |k, l| a.get(rows[k], cols[l]))
+    }
+
+    #[test]
+    fn index_map_captures_errors() {
+        assert_eq!(index_map(3, &[]).err(), Some("the list of indices must not be empty"));
+        assert_eq!(
+            index_map(3, &[0, 3]).err(),
+            Some("the list of indices has an out-of-range index")
+        );
+        assert_eq!(
+            index_map(3, &[1, 1]).err(),
+            Some("the list of indices has a repeated index")
+        );
+        assert_eq!(index_map(4, &[2, 0]).unwrap(), &[1, NONE, 0, NONE]);
+    }
+
+    #[test]
+    fn free_and_expand_dofs_work() {
+        assert_eq!(
+            free_dofs(3, &[3]).err(),
+            Some("the prescribed DOFs have an out-of-range index")
+        );
+        assert_eq!(
+            free_dofs(3, &[1, 1]).err(),
+            Some("the prescribed DOFs have a repeated index")
+        );
+        assert_eq!(free_dofs(4, &[2, 0]).unwrap(), &[1, 3]);
+        assert_eq!(free_dofs(2, &[]).unwrap(), &[0, 1]);
+        let x_free = Vector::from(&[10.0, 30.0]);
+        let x_prescribed = Vector::from(&[2.0, 0.0]);
+        assert_eq!(
+            expand_dofs(&x_free, &[2], &x_prescribed).err(),
+            Some("the number of prescribed values must equal the number of prescribed DOFs")
+        );
+        let x = expand_dofs(&x_free, &[2, 0], &x_prescribed).unwrap();
+        assert_eq!(x.as_data(), &[0.0, 10.0, 2.0, 30.0]);
+    }
+
+    #[test]
+    fn submatrix_and_permute_capture_errors() {
+        let (coo, csc, csr, _) = Samples::rectangular_3x4();
+        assert_eq!(
+            csr_submatrix(&csr, &[0, 3], &[0]).err(),
+            Some("the list of indices has an out-of-range index")
+        );
+        assert_eq!(
+            csc_submatrix(&csc, &[0], &[1, 1]).err(),
+            Some("the list of indices has a repeated index")
+        );
+        assert_eq!(
+            coo_submatrix(&coo, &[], &[0]).err(),
+            Some("the list of indices must not be empty")
+        );
+        assert_eq!(
+            csr_permute(&csr, &[0, 1], &[0, 1, 2, 3]).err(),
+            Some("the row permutation length must equal the number of rows")
+        );
+        assert_eq!(
+            csc_permute(&csc, &[0, 1, 2], &[0, 1, 2]).err(),
+            Some("the column permutation length must equal the number of columns")
+        );
+        assert_eq!(
+            coo_permute(&coo, &[0, 1, 1], &[0, 1, 2, 3]).err(),
+            Some("the permutation has a repeated index")
+        );
+    }
+
+    #[test]
+    fn submatrix_works() {
+        let index_sets: [(&[usize], &[usize]); 4] = [
+            (&[0, 1, 2, 3, 4], &[0, 1, 2, 3, 4]),
+            (&[4, 1, 3], &[4, 1, 3]),
+            (&[0, 2, 3], &[3, 4]),
+            (&[3, 0], &[0, 3, 2]),
+        ];
+        for (coo, csc, csr, _) in [
+            Samples::mkl_unsymmetric_5x5(),
+            Samples::mkl_positive_definite_5x5_lower(),
+            Samples::mkl_positive_definite_5x5_upper(),
+            Samples::mkl_symmetric_5x5_full(),
+        ] {
+            let a = csr.as_dense();
+            let sym = csr.get_info().3;
+            for (rows, cols) in index_sets {
+                let correct = dense_sub(&a, rows, cols);
+                let sym_correct = if rows == cols { sym } else { Sym::No };
+                let b_coo = coo_submatrix(&coo, rows, cols).unwrap();
+                let b_csc = csc_submatrix(&csc, rows, cols).unwrap();
+                let b_csr = csr_submatrix(&csr, rows, cols).unwrap();
+                mat_approx_eq(&b_coo.as_dense(), &correct, 1e-15);
+                mat_approx_eq(&b_csc.as_dense(), &correct, 1e-15);
+                mat_approx_eq(&b_csr.as_dense(), &correct, 1e-15);
+                assert_eq!(b_coo.get_info().3, sym_correct);
+                assert_eq!(b_csc.get_info().3, sym_correct);
+                assert_eq!(b_csr.get_info().3, sym_correct);
+            }
+        }
+        let (coo, csc, csr, _) = Samples::rectangular_3x4();
+        let correct = dense_sub(&csr.as_dense(), &[2, 0], &[3, 1]);
+        mat_approx_eq(
+            &coo_submatrix(&coo, &[2, 0], &[3, 1]).unwrap().as_dense(),
+            &correct,
+            1e-15,
+        );
+        mat_approx_eq(
+            &csc_submatrix(&csc, &[2, 0], &[3, 1]).unwrap().as_dense(),
+            &correct,
+            1e-15,
+        );
+        mat_approx_eq(
+            &csr_submatrix(&csr, &[2, 0], &[3, 1]).unwrap().as_dense(),
+            &correct,
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn permute_works() {
+        let (coo, csc, csr, _) = Samples::rectangular_3x4();
+        let (row_perm, col_perm) = (&[2, 0, 1], &[3, 1, 0, 2]);
+        let correct = dense_sub(&csr.as_dense(), row_perm, col_perm);
+        mat_approx_eq(
+            &coo_permute(&coo, row_perm, col_perm).unwrap().as_dense(),
+            &correct,
+            1e-15,
+        );
+        mat_approx_eq(
+            &csc_permute(&csc, row_perm, col_perm).unwrap().as_dense(),
+            &correct,
+            1e-15,
+        );
+        mat_approx_eq(
+            &csr_permute(&csr, row_perm, col_perm).unwrap().as_dense(),
+            &correct,
+            1e-15,
+        );
+
+        let (coo, csc, csr, _) = Samples::mkl_positive_definite_5x5_lower();
+        let perm = &[3, 0, 4, 2, 1];
+        let correct = dense_sub(&csr.as_dense(), perm, perm);
+        let b_coo = coo_permute(&coo, perm, perm).unwrap();
+        let b_csc = csc_permute(&csc, perm, perm).unwrap();
+        let b_csr = csr_permute(&csr, perm, perm).unwrap();
+        mat_approx_eq(&b_coo.as_dense(), &correct, 1e-15);
+        mat_approx_eq(&b_csc.as_dense(), &correct, 1e-15);
+        mat_approx_eq(&b_csr.as_dense(), &correct, 1e-15);
+        assert_eq!(b_coo.get_info().3, Sym::YesLower);
+        assert_eq!(b_csc.get_info().3, Sym::YesLower);
+        assert_eq!(b_csr.get_info().3, Sym::YesLower);
+        let b_sym = csr_permute_symmetric(&csr, perm).unwrap();
+        assert_eq!(b_csr.get_info(), b_sym.get_info());
+        mat_approx_eq(&b_csr.as_dense(), &b_sym.as_dense(), 1e-15);
+
+        let other = &[0, 1, 2, 3, 4];
+        let correct = dense_sub(&csr.as_dense(), perm, other);
+        let b_csr = csr_permute(&csr, perm, other).unwrap();
+        mat_approx_eq(&b_csr.as_dense(), &correct, 1e-15);
+        assert_eq!(b_csr.get_info().3, Sym::No);
+    }
+
+    #[test]
+    fn eliminate_dofs_captures_errors() {
+        let (coo, csc, csr, _) = Samples::rectangular_3x4();
+        let x_p = Vector::new(1);
+        let rhs = Vector::new(3);
+        assert_eq!(
+            csr_eliminate_dofs(&csr, &[0], &x_p, &rhs).err(),
+            Some("the matrix must be square")
+        );
+        assert_eq!(
+            csc_eliminate_dofs(&csc, &[0], &x_p, &rhs).err(),
+            Some("the matrix must be square")
+        );
+        assert_eq!(
+            coo_eliminate_dofs(&coo, &[0], &x_p, &rhs).err(),
+            Some("the matrix must be square")
+        );
+        let (_, _, csr, _) = Samples::mkl_unsymmetric_5x5();
+        let rhs = Vector::new(5);
+        assert_eq!(
+            csr_eliminate_dofs(&csr, &[0, 1], &x_p, &rhs).err(),
+            Some("the number of prescribed values must equal the number of prescribed DOFs")
+        );
+        assert_eq!(
+            csr_eliminate_dofs(&csr, &[0], &x_p, &Vector::new(4)).err(),
+            Some("the right-hand side vector must have the same dimension as the matrix")
+        );
+        assert_eq!(
+            csr_eliminate_dofs(&csr, &[0, 1, 2, 3, 4], &Vector::new(5), &rhs).err(),
+            Some("there must be at least one free DOF")
+        );
+    }
+
+    #[test]
+    fn eliminate_dofs_works() {
+        let prescribed = &[3, 0];
+        let x_p = Vector::from(&[2.0, -1.0]);
+        let rhs = Vector::from(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let free = free_dofs(5, prescribed).unwrap();
+        for (coo, csc, csr, _) in [
+            Samples::mkl_unsymmetric_5x5(),
+            Samples::mkl_positive_definite_5x5_lower(),
+            Samples::mkl_positive_definite_5x5_upper(),
+        ] {
+            let a = csr.as_dense();
+            let a_ff = dense_sub(&a, &free, &free);
+            let mut b_f = Vector::initialized(free.len(), |k| rhs[free[k]]);
+            for (k, &i) in free.iter().enumerate() {
+                for (l, &j) in prescribed.iter().enumerate() {
+                    b_f[k] -= a.get(i, j) * x_p[l];
+                }
+            }
+            let (r_coo, b_coo) = coo_eliminate_dofs(&coo, prescribed, &x_p, &rhs).unwrap();
+            let (r_csc, b_csc) = csc_eliminate_dofs(&csc, prescribed, &x_p, &rhs).unwrap();
+            let (r_csr, b_csr) = csr_eliminate_dofs(&csr, prescribed, &x_p, &rhs).unwrap();
+            mat_approx_eq(&r_coo.as_dense(), &a_ff, 1e-15);
+            mat_approx_eq(&r_csc.as_dense(), &a_ff, 1e-15);
+            mat_approx_eq(&r_csr.as_dense(), &a_ff, 1e-15);
+            vec_approx_eq(&b_coo, &b_f, 1e-15);
+            vec_approx_eq(&b_csc, &b_f, 1e-15);
+            vec_approx_eq(&b_csr, &b_f, 1e-15);
+            assert_eq!(r_csr.get_info().3, csr.get_info().3);
+        }
+    }
+
+    #[test]
+    fn block_assemble_captures_errors() {
+        let a = CsrMatrix::new(2, 2, vec![0, 1, 2], vec![0, 1], vec![1.0, 2.0], Sym::No).unwrap();
+        let b = CsrMatrix::new(1, 1, vec![0, 1], vec![0], vec![3.0], Sym::No).unwrap();
+        assert_eq!(
+            csr_block_assemble::<f64>(&[]).err(),
+            Some("there must be at least one block row")
+        );
+        assert_eq!(
+            csr_block_assemble::<f64>(&[&[]]).err(),
+            Some("there must be at least one block column")
+        );
+        assert_eq!(
+            csr_block_assemble(&[&[Some(&a), None], &[Some(&a)]]).err(),
+            Some("all block rows must have the same number of blocks")
+        );
+        assert_eq!(
+            csr_block_assemble(&[&[Some(&a), Some(&b)]]).err(),
+            Some("the blocks in the same block row must have the same number of rows")
+        );
+        assert_eq!(
+            csr_block_assemble(&[&[Some(&a)], &[Some(&b)]]).err(),
+            Some("the blocks in the same block column must have the same number of columns")
+        );
+        assert_eq!(
+            csr_block_assemble(&[&[Some(&a), None], &[None, None]]).err(),
+            Some("each block row must have at least one block")
+        );
+        assert_eq!(
+            csr_block_assemble(&[&[Some(&a), None], &[Some(&a), None]]).err(),
+            Some("each block column must have at least one block")
+        );
+        let a = CscMatrix::from_csr(&a).unwrap();
+        assert_eq!(
+            csc_block_assemble(&[&[Some(&a), None], &[Some(&a)]]).err(),
+            Some("all block rows must have the same number of blocks")
+        );
+    }
+
+    #[test]
+    fn block_assemble_works() {
+        // general blocks (the triangular block is mirrored)
+        let (coo_a, csc_a, csr_a, _) = Samples::mkl_unsymmetric_5x5();
+        let (coo_b, csc_b, csr_b, _) = Samples::rectangular_3x4();
+        let (coo_c, csc_c, csr_c, _) = Samples::mkl_positive_definite_5x5_lower();
+        let (a, b, c) = (csr_a.as_dense(), csr_b.as_dense(), csr_c.as_dense());
+        let mut correct = Matrix::new(13, 9);
+        for i in 0..5 {
+            for j in 0..5 {
+                correct.set(i, j, a.get(i, j));
+                correct.set(8 + i, j, c.get(i, j));
+            }
+        }
+        for i in 0..3 {
+            for j in 0..4 {
+                correct.set(5 + i, 5 + j, b.get(i, j));
+            }
+        }
+        let m_coo = coo_block_assemble(&[&[Some(&coo_a), None], &[None, Some(&coo_b)], &[Some(&coo_c), None]]).unwrap();
+        let m_csc = csc_block_assemble(&[&[Some(&csc_a), None], &[None, Some(&csc_b)], &[Some(&csc_c), None]]).unwrap();
+        let m_csr = csr_block_assemble(&[&[Some(&csr_a), None], &[None, Some(&csr_b)], &[Some(&csr_c), None]]).unwrap();
+        mat_approx_eq(&m_coo.as_dense(), &correct, 1e-15);
+        mat_approx_eq(&m_csc.as_dense(), &correct, 1e-15);
+        mat_approx_eq(&m_csr.as_dense(), &correct, 1e-15);
+        assert_eq!(m_coo.get_info().3, Sym::No);
+        assert_eq!(m_csc.get_info().3, Sym::No);
+        assert_eq!(m_csr.get_info().3, Sym::No);
+
+        // symmetric saddle-point matrix [[K, Gᵀ], [G, M]] with lower triangular storage
+        let mut coo_g = CooMatrix::new(2, 5, 3, Sym::No).unwrap();
+        coo_g.put(0, 0, 1.0).unwrap();
+        coo_g.put(0, 3, -1.0).unwrap();
+        coo_g.put(1, 4, 2.0).unwrap();
+        let mut coo_m = CooMatrix::new(2, 2, 2, Sym::YesLower).unwrap();
+        coo_m.put(1, 0, 0.5).unwrap();
+        coo_m.put(1, 1, -1.0).unwrap();
+        let (csr_g, csr_m) = (
+            CsrMatrix::from_coo(&coo_g).unwrap(),
+            CsrMatrix::from_coo(&coo_m).unwrap(),
+        );
+        let (csc_g, csc_m) = (
+            CscMatrix::from_coo(&coo_g).unwrap(),
+            CscMatrix::from_coo(&coo_m).unwrap(),
+        );
+        let (g, m) = (csr_g.as_dense(), csr_m.as_dense());
+        let mut correct = Matrix::new(7, 7);
+        for i in 0..5 {
+            for j in 0..5 {
+                correct.set(i, j, c.get(i, j));
+            }
+        }
+        for i in 0..2 {
+            for j in 0..5 {
+                correct.set(5 + i, j, g.get(i, j));
+                correct.set(j, 5 + i, g.get(i, j));
+            }
+            for j in 0..2 {
+                correct.set(5 + i, 5 + j, m.get(i, j));
+            }
+        }
+        let m_coo = coo_block_assemble(&[&[Some(&coo_c), None], &[Some(&coo_g), Some(&coo_m)]]).unwrap();
+        let m_csc = csc_block_assemble(&[&[Some(&csc_c), None], &[Some(&csc_g), Some(&csc_m)]]).unwrap();
+        let m_csr = csr_block_assemble(&[&[Some(&csr_c), None], &[Some(&csr_g), Some(&csr_m)]]).unwrap();
+        mat_approx_eq(&m_coo.as_dense(), &correct, 1e-15);
+        mat_approx_eq(&m_csc.as_dense(), &correct, 1e-15);
+        mat_approx_eq(&m_csr.as_dense(), &correct, 1e-15);
+        assert_eq!(m_coo.get_info().3, Sym::YesLower);
+        assert_eq!(m_csc.get_info().3, Sym::YesLower);
+        assert_eq!(m_csr.get_info().3, Sym::YesLower);
+
+        // a block outside the stored triangle requires mirroring the diagonal blocks
+        let csr_gt = csr_transpose(&csr_g).unwrap();
+        let m_csr = csr_block_assemble(&[&[Some(&csr_c), Some(&csr_gt)], &[Some(&csr_g), Some(&csr_m)]]).unwrap();
+        mat_approx_eq(&m_csr.as_dense(), &correct, 1e-15);
+        assert_eq!(m_csr.get_info().3, Sym::No);
+    }
+}
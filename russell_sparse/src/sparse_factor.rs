@@ -0,0 +1,1205 @@
+use super::{approximate_minimum_degree, graph_adjacency, nested_dissection, NumCscMatrix, Ordering};
+use crate::StrError;
+use num_traits::{NumAssign, NumCast};
+use russell_lab::Complex64;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Indicates an unset index
+const NONE: usize = usize::MAX;
+
+/// Defines the operations on the scalar values needed by the native factorizations, the preconditioners, and the iterative solvers
+///
+/// This trait is implemented for `f64`, `f32`, and `Complex64`.
+pub trait FactorScalar: NumAssign + NumCast + Copy + Send + Sync + DeserializeOwned + Serialize {
+    /// Returns the modulus (absolute value)
+    fn modulus(self) -> f64;
+
+    /// Returns the real part (the value itself if real)
+    fn real(self) -> f64;
+
+    /// Returns a scalar with the given real part (and zero imaginary part)
+    fn from_real(value: f64) -> Self;
+
+    /// Returns the square root of a Cholesky pivot (None if the pivot is invalid)
+    fn pivot_sqrt(self) -> Option<Self>;
+
+    /// Multiplies the value by a real number
+    fn scale(self, alpha: f64) -> Self;
+
+    /// Returns the complex conjugate (the value itself if real)
+    fn conjugate(self) -> Self;
+}
+
+impl FactorScalar for f64 {
+    fn modulus(self) -> f64 {
+        f64::abs(self)
+    }
+    fn real(self) -> f64 {
+        self
+    }
+    fn from_real(value: f64) -> Self {
+        value
+    }
+    fn pivot_sqrt(self) -> Option<Self> {
+        if self > 0.0 {
+            Some(f64::sqrt(self))
+        } else {
+            None
+        }
+    }
+    fn scale(self, alpha: f64) -> Self {
+        self * alpha
+    }
+    fn conjugate(self) -> Self {
+        self
+    }
+}
+
+impl FactorScalar for f32 {
+    fn modulus(self) -> f64 {
+        f32::abs(self) as f64
+    }
+    fn real(self) -> f64 {
+        self as f64
+    }
+    fn from_real(value: f64) -> Self {
+        value as f32
+    }
+    fn pivot_sqrt(self) -> Option<Self> {
+        if self > 0.0 {
+            Some(f32::sqrt(self))
+        } else {
+            None
+        }
+    }
+    fn scale(self, alpha: f64) -> Self {
+        self * (alpha as f32)
+    }
+    fn conjugate(self) -> Self {
+        self
+    }
+}
+
+impl FactorScalar for Complex64 {
+    fn modulus(self) -> f64 {
+        self.norm()
+    }
+    fn real(self) -> f64 {
+        self.re
+    }
+    fn from_real(value: f64) -> Self {
+        Complex64::new(value, 0.0)
+    }
+    fn pivot_sqrt(self) -> Option<Self> {
+        if self.norm() > 0.0 {
+            Some(self.sqrt())
+        } else {
+            None
+        }
+    }
+    fn scale(self, alpha: f64) -> Self {
+        self * alpha
+    }
+    fn conjugate(self) -> Self {
+        self.conj()
+    }
+}
+
+/// Specifies the native factorization method
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum FactorKind {
+    /// Up-looking Cholesky factorization C = L·Lᵀ (symmetric positive-definite matrices)
+    Cholesky,
+
+    /// Left-looking (Gilbert-Peierls) LU factorization with threshold partial pivoting P·C = L·U
+    Lu,
+}
+
+/// Implements the native (pure Rust) sparse LU and Cholesky factorizations
+///
+/// The matrix is first reordered by a fill-reducing symmetric permutation, i.e., `C = P·A·Pᵀ`.
+/// Then, `C` is factorized by the left-looking LU method (with threshold partial pivoting)
+/// or by the up-looking Cholesky method. All factors are stored column-wise.
+///
+/// Reference: Davis TA (2006) Direct Methods for Sparse Linear Systems, SIAM, Philadelphia
+pub(crate) struct SparseFactor<T>
+where
+    T: FactorScalar,
+{
+    /// Holds the factorization method
+    kind: FactorKind,
+
+    /// Holds the dimension of the (square) matrix
+    ndim: usize,
+
+    /// Holds the number of non-zeros in the original (CSC) matrix
+    nnz: usize,
+
+    /// Holds the fill-reducing permutation (perm[k] is the original index of the k-th row and column)
+    perm: Vec<usize>,
+
+    /// Holds the column pointers of the permuted matrix C (full representation)
+    c_ptr: Vec<usize>,
+
+    /// Holds the row indices of the permuted matrix C
+    c_idx: Vec<usize>,
+
+    /// Holds the values of the permuted matrix C
+    c_val: Vec<T>,
+
+    /// Maps the position of a value in the original matrix to a position in C
+    c_map: Vec<(usize, usize)>,
+
+    /// Holds the elimination tree of C (Cholesky only)
+    parent: Vec<usize>,
+
+    /// Holds the inverse row permutation due to pivoting (LU only)
+    pinv: Vec<usize>,
+
+    /// Holds the column pointers of L
+    l_ptr: Vec<usize>,
+
+    /// Holds the row indices of L (the diagonal is the first entry of each column)
+    l_idx: Vec<usize>,
+
+    /// Holds the values of L
+    l_val: Vec<T>,
+
+    /// Holds the column pointers of U (LU only)
+    u_ptr: Vec<usize>,
+
+    /// Holds the row indices of U (the diagonal is the last entry of each column)
+    u_idx: Vec<usize>,
+
+    /// Holds the values of U
+    u_val: Vec<T>,
+
+    /// Workspace: dense values
+    x: Vec<T>,
+
+    /// Workspace: pattern of the sparse triangular solve (output stack)
+    xi: Vec<usize>,
+
+    /// Workspace: positions for the depth-first search or next free slots of L
+    pos: Vec<usize>,
+
+    /// Workspace: markers
+    mark: Vec<usize>,
+
+    /// Holds the tolerance of the null pivot detection relative to the largest modulus of C (None = no detection)
+    null_pivot_tol: Option<f64>,
+
+    /// Holds the pivot threshold of the last factorization (used by the refactorization with null pivots)
+    pivot_threshold: f64,
+
+    /// Holds the steps k at which null pivots were found (the pivots have been replaced by one)
+    null_steps: Vec<usize>,
+
+    /// Indicates that the pivot of step k is null
+    null_flag: Vec<bool>,
+
+    /// Indicates that the numeric factorization has been computed
+    factorized: bool,
+}
+
+impl<T> SparseFactor<T>
+where
+    T: FactorScalar,
+{
+    /// Performs the symbolic analysis (ordering and, for Cholesky, the elimination tree)
+    ///
+    /// # Input
+    ///
+    /// * `csc` -- the (square) matrix; symmetric matrices may be stored as a lower or upper triangle
+    /// * `kind` -- the factorization method
+    /// * `ordering` -- [Ordering::No] uses the identity, [Ordering::Metis] uses the nested dissection,
+    ///   and the other options use the approximate minimum degree ordering
+    pub(crate) fn analyze(csc: &NumCscMatrix<T>, kind: FactorKind, ordering: Ordering) -> Result<Self, StrError> {
+        if csc.nrow != csc.ncol {
+            return Err("the matrix must be square");
+        }
+        let n = csc.nrow;
+        let nnz = csc.col_pointers[n] as usize;
+        let triangular = csc.symmetric.triangular();
+
+        // fill-reducing ordering
+        let perm = match ordering {
+            Ordering::No => (0..n).collect(),
+            Ordering::Metis => nested_dissection(&graph_adjacency(n, &csc.col_pointers, &csc.row_indices)),
+            _ => approximate_minimum_degree(&graph_adjacency(n, &csc.col_pointers, &csc.row_indices)),
+        };
+        let mut iperm = vec![0; n];
+        for (k, &old) in perm.iter().enumerate() {
+            iperm[old] = k;
+        }
+
+        // pattern of C = P·A·Pᵀ and map from A
+        let mut entries = Vec::with_capacity(if triangular { 2 * nnz } else { nnz });
+        for j in 0..n {
+            for p in (csc.col_pointers[j] as usize)..(csc.col_pointers[j + 1] as usize) {
+                let i = csc.row_indices[p] as usize;
+                entries.push((iperm[i], iperm[j], p));
+                if triangular && i != j {
+                    entries.push((iperm[j], iperm[i], p));
+                }
+            }
+        }
+        let mut c_ptr = vec![0; n + 1];
+        for &(_, j, _) in &entries {
+            c_ptr[j + 1] += 1;
+        }
+        for j in 0..n {
+            c_ptr[j + 1] += c_ptr[j];
+        }
+        let mut next = c_ptr.clone();
+        let mut c_idx = vec![0; entries.len()];
+        let mut c_map = Vec::with_capacity(entries.len());
+        for &(i, j, p) in &entries {
+            c_idx[next[j]] = i;
+            c_map.push((p, next[j]));
+            next[j] += 1;
+        }
+
+        // allocate
+        let mut factor = SparseFactor {
+            kind,
+            ndim: n,
+            nnz,
+            perm,
+            c_ptr,
+            c_idx,
+            c_val: vec![T::zero(); entries.len()],
+            c_map,
+            parent: Vec::new(),
+            pinv: vec![NONE; n],
+            l_ptr: vec![0; n + 1],
+            l_idx: Vec::new(),
+            l_val: Vec::new(),
+            u_ptr: vec![0; n + 1],
+            u_idx: Vec::new(),
+            u_val: Vec::new(),
+            x: vec![T::zero(); n],
+            xi: vec![0; n],
+            pos: vec![0; n],
+            mark: vec![NONE; n],
+            null_pivot_tol: None,
+            pivot_threshold: 1.0,
+            null_steps: Vec::new(),
+            null_flag: vec![false; n],
+            factorized: false,
+        };
+
+        // elimination tree and column counts of L (Cholesky)
+        if kind == FactorKind::Cholesky {
+            factor.elimination_tree();
+            let mut count = vec![1; n]; // diagonal
+            for k in 0..n {
+                let top = factor.ereach(k);
+                for t in top..n {
+                    count[factor.xi[t]] += 1;
+                }
+            }
+            for (j, c) in count.iter().enumerate() {
+                factor.l_ptr[j + 1] = factor.l_ptr[j] + c;
+            }
+            let lnz = factor.l_ptr[n];
+            factor.l_idx = vec![0; lnz];
+            factor.l_val = vec![T::zero(); lnz];
+        }
+        Ok(factor)
+    }
+
+    /// Returns the factorization method
+    pub(crate) fn kind(&self) -> FactorKind {
+        self.kind
+    }
+
+    /// Returns the number of non-zeros in the factors (L + U)
+    pub(crate) fn nnz_factors(&self) -> usize {
+        match self.kind {
+            FactorKind::Cholesky => self.l_ptr[self.ndim],
+            FactorKind::Lu => self.l_ptr[self.ndim] + self.u_ptr[self.ndim],
+        }
+    }
+
+    /// Enables (or disables) the detection of null pivots
+    ///
+    /// A pivot is null if its modulus is smaller than or equal to `tol · max|cij|`. Then, the pivot
+    /// is replaced by one and the remaining entries of the column of L are discarded. Thus, the factors
+    /// correspond to the matrix `A' = A + Σ e_r·e_cᵀ` where `(r, c)` are given by [SparseFactor::null_pivots].
+    ///
+    /// # Input
+    ///
+    /// * `tol` -- the tolerance relative to the largest modulus of the matrix; None disables the detection
+    pub(crate) fn set_null_pivot_tolerance(&mut self, tol: Option<f64>) {
+        self.null_pivot_tol = tol;
+    }
+
+    /// Returns the (row, column) indices (in the original numbering) of the null pivots found by the last factorization
+    pub(crate) fn null_pivots(&self) -> Vec<(usize, usize)> {
+        let mut row_of_step = vec![0; self.ndim];
+        if self.kind == FactorKind::Lu {
+            for (i, &k) in self.pinv.iter().enumerate() {
+                if k != NONE {
+                    row_of_step[k] = i;
+                }
+            }
+        }
+        self.null_steps
+            .iter()
+            .map(|&k| match self.kind {
+                FactorKind::Cholesky => (self.perm[k], self.perm[k]),
+                FactorKind::Lu => (self.perm[row_of_step[k]], self.perm[k]),
+            })
+            .collect()
+    }
+
+    /// Performs the numeric factorization
+    ///
+    /// # Input
+    ///
+    /// * `csc` -- the matrix with the same structure as the one given to [SparseFactor::analyze]
+    /// * `pivot_threshold` -- (LU only) the diagonal entry is selected as pivot if
+    ///   its modulus is greater than or equal to `pivot_threshold` times the largest modulus in the column
+    pub(crate) fn factorize(&mut self, csc: &NumCscMatrix<T>, pivot_threshold: f64) -> Result<(), StrError> {
+        if csc.nrow != self.ndim || csc.ncol != self.ndim || csc.col_pointers[self.ndim] as usize != self.nnz {
+            return Err("the matrix structure differs from the analyzed one");
+        }
+        self.factorized = false;
+        self.c_val.fill(T::zero());
+        for &(p, q) in &self.c_map {
+            self.c_val[q] += csc.values[p];
+        }
+        self.pivot_threshold = pivot_threshold;
+        match self.kind {
+            FactorKind::Cholesky => self.cholesky()?,
+            FactorKind::Lu => self.lu(pivot_threshold)?,
+        }
+        self.factorized = true;
+        Ok(())
+    }
+
+    /// Performs the numeric refactorization reusing the pivot sequence and the patterns of the factors
+    ///
+    /// This function is faster than [SparseFactor::factorize] because no pivot search and no
+    /// depth-first search are carried out. However, no pivoting is performed; thus, the previous
+    /// pivot sequence may be inaccurate if the values have changed significantly.
+    ///
+    /// # Input
+    ///
+    /// * `csc` -- the matrix with the same structure as the one given to [SparseFactor::analyze]
+    pub(crate) fn refactorize(&mut self, csc: &NumCscMatrix<T>) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the numeric factorization must be computed before the refactorization");
+        }
+        if csc.nrow != self.ndim || csc.ncol != self.ndim || csc.col_pointers[self.ndim] as usize != self.nnz {
+            return Err("the matrix structure differs from the analyzed one");
+        }
+        self.factorized = false;
+        self.c_val.fill(T::zero());
+        for &(p, q) in &self.c_map {
+            self.c_val[q] += csc.values[p];
+        }
+        match self.kind {
+            FactorKind::Cholesky => self.cholesky()?,
+            FactorKind::Lu => {
+                if self.null_pivot_tol.is_some() {
+                    // the null pivots may change with the values; thus, the pivots are searched again
+                    self.lu(self.pivot_threshold)?
+                } else {
+                    self.lu_refactor()?
+                }
+            }
+        }
+        self.factorized = true;
+        Ok(())
+    }
+
+    /// Solves the linear system A·x = rhs using the computed factors
+    pub(crate) fn solve(&mut self, x: &mut [T], rhs: &[T]) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the numeric factorization must be computed first");
+        }
+        let n = self.ndim;
+        if x.len() != n || rhs.len() != n {
+            return Err("the vectors are incompatible with the factorization");
+        }
+        match self.kind {
+            FactorKind::Cholesky => {
+                for k in 0..n {
+                    self.x[k] = rhs[self.perm[k]];
+                }
+                self.lower_solve(true);
+                self.lower_transpose_solve(false);
+            }
+            FactorKind::Lu => {
+                for k in 0..n {
+                    self.x[self.pinv[k]] = rhs[self.perm[k]];
+                }
+                self.lower_solve(false);
+                self.upper_solve();
+            }
+        }
+        for k in 0..n {
+            x[self.perm[k]] = self.x[k];
+        }
+        Ok(())
+    }
+
+    /// Solves the linear system Aᵀ·x = rhs (or Aᴴ·x = rhs if `conjugate`) using the computed factors
+    ///
+    /// With `C = P·A·Pᵀ` and `Q·C = L·U` (Q is the pivoting permutation), `Cᵀ = Uᵀ·Lᵀ·Q`.
+    /// Thus, the same factors are used, but the triangular solves are performed in the reverse order.
+    pub(crate) fn solve_transpose(&mut self, x: &mut [T], rhs: &[T], conjugate: bool) -> Result<(), StrError> {
+        if !self.factorized {
+            return Err("the numeric factorization must be computed first");
+        }
+        let n = self.ndim;
+        if x.len() != n || rhs.len() != n {
+            return Err("the vectors are incompatible with the factorization");
+        }
+        match self.kind {
+            FactorKind::Cholesky => {
+                // A = Aᵀ; thus, Aᴴ·x = b is equivalent to A·conj(x) = conj(b)
+                for k in 0..n {
+                    self.x[k] = if conjugate {
+                        rhs[self.perm[k]].conjugate()
+                    } else {
+                        rhs[self.perm[k]]
+                    };
+                }
+                self.lower_solve(true);
+                self.lower_transpose_solve(false);
+                for k in 0..n {
+                    x[self.perm[k]] = if conjugate { self.x[k].conjugate() } else { self.x[k] };
+                }
+            }
+            FactorKind::Lu => {
+                for k in 0..n {
+                    self.x[k] = rhs[self.perm[k]];
+                }
+                self.upper_transpose_solve(conjugate);
+                self.lower_transpose_solve(conjugate);
+                for k in 0..n {
+                    x[self.perm[k]] = self.x[self.pinv[k]];
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes the determinant of the factorized matrix
+    ///
+    /// Returns `(mantissa, exponent)` such that `det = mantissa · 10^exponent` with `1 ≤ |mantissa| < 10`.
+    /// The determinant is zero if null pivots have been found. The mantissa is not normalized
+    /// (and thus may be infinite or NaN) if a non-finite value is found (e.g., an overflow in single precision).
+    pub(crate) fn determinant(&self) -> (T, f64) {
+        if !self.null_steps.is_empty() {
+            return (T::zero(), 0.0);
+        }
+        let n = self.ndim;
+        let mut mantissa = T::one();
+        let mut exponent = 0.0;
+        let mut multiply = |value: T| {
+            mantissa *= value;
+            let modulus = mantissa.modulus();
+            if modulus == 0.0 || !modulus.is_finite() {
+                return;
+            }
+            while mantissa.modulus() >= 10.0 {
+                mantissa = mantissa.scale(0.1);
+                exponent += 1.0;
+            }
+            while mantissa.modulus() < 1.0 {
+                mantissa = mantissa.scale(10.0);
+                exponent -= 1.0;
+            }
+        };
+        match self.kind {
+            FactorKind::Cholesky => {
+                for j in 0..n {
+                    let d = self.l_val[self.l_ptr[j]];
+                    multiply(d * d);
+                }
+            }
+            FactorKind::Lu => {
+                for j in 0..n {
+                    multiply(self.u_val[self.u_ptr[j + 1] - 1]);
+                }
+                if permutation_is_odd(&self.pinv) {
+                    multiply(T::zero() - T::one());
+                }
+            }
+        }
+        (mantissa, exponent)
+    }
+
+    /// Computes the elimination tree of C using its upper triangle
+    fn elimination_tree(&mut self) {
+        let n = self.ndim;
+        self.parent = vec![NONE; n];
+        let ancestor = &mut self.pos;
+        ancestor.fill(NONE);
+        for k in 0..n {
+            for p in self.c_ptr[k]..self.c_ptr[k + 1] {
+                let mut i = self.c_idx[p];
+                while i != NONE && i < k {
+                    let inext = ancestor[i];
+                    ancestor[i] = k;
+                    if inext == NONE {
+                        self.parent[i] = k;
+                    }
+                    i = inext;
+                }
+            }
+        }
+    }
+
+    /// Finds the pattern of the k-th row of L (stored in xi[top..n]) by traversing the elimination tree
+    fn ereach(&mut self, k: usize) -> usize {
+        let n = self.ndim;
+        let mut top = n;
+        self.mark[k] = k;
+        for p in self.c_ptr[k]..self.c_ptr[k + 1] {
+            let mut i = self.c_idx[p];
+            if i > k {
+                continue;
+            }
+            let mut len = 0;
+            while self.mark[i] != k {
+                self.xi[len] = i;
+                len += 1;
+                self.mark[i] = k;
+                i = self.parent[i];
+            }
+            while len > 0 {
+                top -= 1;
+                len -= 1;
+                self.xi[top] = self.xi[len];
+            }
+        }
+        top
+    }
+
+    /// Resets the record of null pivots and returns the absolute tolerance of the null pivot detection
+    fn start_null_pivot_detection(&mut self) -> Option<f64> {
+        self.null_steps.clear();
+        self.null_flag.fill(false);
+        self.null_pivot_tol.map(|tol| {
+            let cmax = self.c_val.iter().fold(0.0, |acc, v| f64::max(acc, v.modulus()));
+            tol * cmax
+        })
+    }
+
+    /// Computes the up-looking Cholesky factorization C = L·Lᵀ
+    fn cholesky(&mut self) -> Result<(), StrError> {
+        let n = self.ndim;
+        let null_tol = self.start_null_pivot_detection();
+        self.mark.fill(NONE);
+        self.pos[..n].copy_from_slice(&self.l_ptr[..n]);
+        for k in 0..n {
+            let top = self.ereach(k);
+            self.x[k] = T::zero();
+            for p in self.c_ptr[k]..self.c_ptr[k + 1] {
+                let i = self.c_idx[p];
+                if i <= k {
+                    self.x[i] += self.c_val[p];
+                }
+            }
+            let mut d = self.x[k];
+            self.x[k] = T::zero();
+            for t in top..n {
+                let i = self.xi[t];
+                let lki = if self.null_flag[i] {
+                    T::zero()
+                } else {
+                    self.x[i] / self.l_val[self.l_ptr[i]]
+                };
+                self.x[i] = T::zero();
+                for p in (self.l_ptr[i] + 1)..self.pos[i] {
+                    let r = self.l_idx[p];
+                    self.x[r] -= self.l_val[p] * lki;
+                }
+                d -= lki * lki;
+                let p = self.pos[i];
+                self.pos[i] += 1;
+                self.l_idx[p] = k;
+                self.l_val[p] = lki;
+            }
+            let lkk = match (d.pivot_sqrt(), null_tol) {
+                (_, Some(tol)) if d.modulus() <= tol => {
+                    self.null_steps.push(k);
+                    self.null_flag[k] = true;
+                    T::one()
+                }
+                (Some(v), _) => v,
+                (None, _) => return Err("the matrix is not positive-definite (Cholesky factorization failed)"),
+            };
+            let p = self.pos[k];
+            self.pos[k] += 1;
+            self.l_idx[p] = k;
+            self.l_val[p] = lkk;
+        }
+        Ok(())
+    }
+
+    /// Computes the left-looking LU factorization P·C = L·U with threshold partial pivoting
+    fn lu(&mut self, pivot_threshold: f64) -> Result<(), StrError> {
+        let n = self.ndim;
+        let null_tol = self.start_null_pivot_detection();
+        self.pinv.fill(NONE);
+        self.mark.fill(NONE);
+        self.l_idx.clear();
+        self.l_val.clear();
+        self.u_idx.clear();
+        self.u_val.clear();
+        for k in 0..n {
+            self.l_ptr[k] = self.l_idx.len();
+            self.u_ptr[k] = self.u_idx.len();
+
+            // x = L \ C(:,k)
+            let top = self.spsolve(k);
+
+            // find the pivot and save U(:,k)
+            let mut ipiv = NONE;
+            let mut largest = -1.0;
+            for t in top..n {
+                let i = self.xi[t];
+                if self.pinv[i] == NONE {
+                    let a = self.x[i].modulus();
+                    if a > largest {
+                        largest = a;
+                        ipiv = i;
+                    }
+                } else {
+                    self.u_idx.push(self.pinv[i]);
+                    self.u_val.push(self.x[i]);
+                }
+            }
+            if let Some(tol) = null_tol {
+                if ipiv == NONE || largest <= tol {
+                    // null pivot: select a free row (preferably the diagonal) and replace the pivot by one
+                    if self.pinv[k] == NONE {
+                        ipiv = k;
+                    } else if ipiv == NONE {
+                        ipiv = self.pinv.iter().position(|&s| s == NONE).unwrap();
+                        // OK because k < n
+                    }
+                    self.null_steps.push(k);
+                    self.null_flag[k] = true;
+                    self.u_idx.push(k);
+                    self.u_val.push(T::one());
+                    self.pinv[ipiv] = k;
+                    self.l_idx.push(ipiv);
+                    self.l_val.push(T::one());
+                    for t in top..n {
+                        self.x[self.xi[t]] = T::zero();
+                    }
+                    continue;
+                }
+            }
+            if ipiv == NONE || largest <= 0.0 {
+                return Err("the matrix is singular (LU factorization failed)");
+            }
+            if self.pinv[k] == NONE && self.mark[k] == k && self.x[k].modulus() >= pivot_threshold * largest {
+                ipiv = k;
+            }
+
+            // save the diagonal of U and compute L(:,k)
+            let pivot = self.x[ipiv];
+            self.u_idx.push(k);
+            self.u_val.push(pivot);
+            self.pinv[ipiv] = k;
+            self.l_idx.push(ipiv);
+            self.l_val.push(T::one());
+            for t in top..n {
+                let i = self.xi[t];
+                if self.pinv[i] == NONE {
+                    self.l_idx.push(i);
+                    self.l_val.push(self.x[i] / pivot);
+                }
+                self.x[i] = T::zero();
+            }
+        }
+        self.l_ptr[n] = self.l_idx.len();
+        self.u_ptr[n] = self.u_idx.len();
+
+        // use the final row numbering in L
+        for i in self.l_idx.iter_mut() {
+            *i = self.pinv[*i];
+        }
+        Ok(())
+    }
+
+    /// Computes the LU factorization with the pivot sequence and patterns of the previous one
+    ///
+    /// **Note:** The entries of U(:,k) are stored in a topological order of the
+    /// triangular solve; hence, they can be used directly to compute the new values.
+    fn lu_refactor(&mut self) -> Result<(), StrError> {
+        let n = self.ndim;
+        self.x.fill(T::zero());
+        for k in 0..n {
+            // scatter the (row permuted) column k of C into x
+            for p in self.c_ptr[k]..self.c_ptr[k + 1] {
+                self.x[self.pinv[self.c_idx[p]]] += self.c_val[p];
+            }
+
+            // x = L \ C(:,k) and U(:,k)
+            let diag = self.u_ptr[k + 1] - 1;
+            for p in self.u_ptr[k]..diag {
+                let j = self.u_idx[p];
+                let xj = self.x[j];
+                self.u_val[p] = xj;
+                self.x[j] = T::zero();
+                for q in (self.l_ptr[j] + 1)..self.l_ptr[j + 1] {
+                    let i = self.l_idx[q];
+                    self.x[i] -= self.l_val[q] * xj;
+                }
+            }
+
+            // diagonal of U and L(:,k)
+            let pivot = self.x[k];
+            self.x[k] = T::zero();
+            if pivot.modulus() <= 0.0 {
+                return Err("the matrix is singular (LU refactorization failed)");
+            }
+            self.u_val[diag] = pivot;
+            for q in (self.l_ptr[k] + 1)..self.l_ptr[k + 1] {
+                let i = self.l_idx[q];
+                self.l_val[q] = self.x[i] / pivot;
+                self.x[i] = T::zero();
+            }
+        }
+        Ok(())
+    }
+
+    /// Solves L·x = C(:,k) during the LU factorization (the pattern of x goes to xi[top..n])
+    ///
+    /// **Note:** Entries in xi[top..n] are marked with `mark[i] = k`.
+    fn spsolve(&mut self, k: usize) -> usize {
+        let n = self.ndim;
+
+        // find the pattern by depth-first search in the graph of L
+        let mut top = n;
+        for p in self.c_ptr[k]..self.c_ptr[k + 1] {
+            let i = self.c_idx[p];
+            if self.mark[i] != k {
+                top = self.dfs(i, k, top);
+            }
+        }
+
+        // numeric values
+        for t in top..n {
+            self.x[self.xi[t]] = T::zero();
+        }
+        for p in self.c_ptr[k]..self.c_ptr[k + 1] {
+            self.x[self.c_idx[p]] += self.c_val[p];
+        }
+        for t in top..n {
+            let j = self.xi[t];
+            let jj = self.pinv[j];
+            if jj == NONE {
+                continue;
+            }
+            let xj = self.x[j];
+            for p in (self.l_ptr[jj] + 1)..self.l_ptr[jj + 1] {
+                let i = self.l_idx[p];
+                self.x[i] -= self.l_val[p] * xj;
+            }
+        }
+        top
+    }
+
+    /// Performs a non-recursive depth-first search starting at node j (LU only)
+    ///
+    /// The reached nodes are pushed onto xi[..top] in topological order; returns the new top.
+    fn dfs(&mut self, j: usize, k: usize, mut top: usize) -> usize {
+        // the recursion stack is kept in xi[0..=head] (it never overlaps xi[top..n])
+        let mut head = 0;
+        self.xi[0] = j;
+        loop {
+            let j = self.xi[head];
+            let jj = self.pinv[j];
+            if self.mark[j] != k {
+                self.mark[j] = k;
+                self.pos[head] = if jj == NONE { 0 } else { self.l_ptr[jj] };
+            }
+            let end = if jj == NONE { 0 } else { self.l_ptr[jj + 1] };
+            let mut done = true;
+            let mut p = self.pos[head];
+            while p < end {
+                let i = self.l_idx[p];
+                if self.mark[i] != k {
+                    self.pos[head] = p;
+                    head += 1;
+                    self.xi[head] = i;
+                    done = false;
+                    break;
+                }
+                p += 1;
+            }
+            if done {
+                top -= 1;
+                self.xi[top] = j;
+                if head == 0 {
+                    return top;
+                }
+                head -= 1;
+            }
+        }
+    }
+
+    /// Solves L·x = b in place (x holds b on input); `general_diagonal` divides by L(j,j)
+    fn lower_solve(&mut self, general_diagonal: bool) {
+        for j in 0..self.ndim {
+            if general_diagonal {
+                self.x[j] /= self.l_val[self.l_ptr[j]];
+            }
+            let xj = self.x[j];
+            for p in (self.l_ptr[j] + 1)..self.l_ptr[j + 1] {
+                let i = self.l_idx[p];
+                self.x[i] -= self.l_val[p] * xj;
+            }
+        }
+    }
+
+    /// Solves Lᵀ·x = b (or Lᴴ·x = b if `conjugate`) in place (x holds b on input)
+    fn lower_transpose_solve(&mut self, conjugate: bool) {
+        let value = |v: T| if conjugate { v.conjugate() } else { v };
+        for j in (0..self.ndim).rev() {
+            let mut xj = self.x[j];
+            for p in (self.l_ptr[j] + 1)..self.l_ptr[j + 1] {
+                xj -= value(self.l_val[p]) * self.x[self.l_idx[p]];
+            }
+            self.x[j] = xj / value(self.l_val[self.l_ptr[j]]);
+        }
+    }
+
+    /// Solves U·x = b in place (x holds b on input)
+    fn upper_solve(&mut self) {
+        for j in (0..self.ndim).rev() {
+            let last = self.u_ptr[j + 1] - 1;
+            self.x[j] /= self.u_val[last];
+            let xj = self.x[j];
+            for p in self.u_ptr[j]..last {
+                let i = self.u_idx[p];
+                self.x[i] -= self.u_val[p] * xj;
+            }
+        }
+    }
+
+    /// Solves Uᵀ·x = b (or Uᴴ·x = b if `conjugate`) in place (x holds b on input)
+    fn upper_transpose_solve(&mut self, conjugate: bool) {
+        let value = |v: T| if conjugate { v.conjugate() } else { v };
+        for j in 0..self.ndim {
+            let last = self.u_ptr[j + 1] - 1;
+            let mut xj = self.x[j];
+            for p in self.u_ptr[j]..last {
+                xj -= value(self.u_val[p]) * self.x[self.u_idx[p]];
+            }
+            self.x[j] = xj / value(self.u_val[last]);
+        }
+    }
+}
+
+/// Returns true if the permutation has an odd number of transpositions
+fn permutation_is_odd(perm: &[usize]) -> bool {
+    let mut visited = vec![false; perm.len()];
+    let mut odd = false;
+    for start in 0..perm.len() {
+        if visited[start] {
+            continue;
+        }
+        let mut len = 0;
+        let mut i = start;
+        while !visited[i] {
+            visited[i] = true;
+            i = perm[i];
+            len += 1;
+        }
+        if len % 2 == 0 {
+            odd = !odd;
+        }
+    }
+    odd
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{permutation_is_odd, FactorKind, SparseFactor};
+    use crate::Ordering;
+    use crate::{ComplexCooMatrix, ComplexCscMatrix, CooMatrix, CscMatrix, Samples, Sym};
+    use russell_lab::{approx_eq, complex_approx_eq, complex_vec_approx_eq, complex_vec_mat_mul, cpx};
+    use russell_lab::{mat_vec_mul, vec_approx_eq, vec_mat_mul};
+    use russell_lab::{Complex64, ComplexVector, Vector};
+
+    fn check_solution(csc: &CscMatrix, kind: FactorKind, ordering: Ordering, det: f64) {
+        let n = csc.nrow;
+        let mut factor = SparseFactor::analyze(csc, kind, ordering).unwrap();
+        factor.factorize(csc, 0.1).unwrap();
+        let x_correct = Vector::initialized(n, |i| (i + 1) as f64);
+        let mut rhs = Vector::new(n);
+        mat_vec_mul(&mut rhs, 1.0, &csc.as_dense(), &x_correct).unwrap();
+        let mut x = Vector::new(n);
+        factor.solve(x.as_mut_data(), rhs.as_data()).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-10);
+        vec_mat_mul(&mut rhs, 1.0, &x_correct, &csc.as_dense()).unwrap(); // rhs = Aᵀ·x
+        factor.solve_transpose(x.as_mut_data(), rhs.as_data(), false).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-10);
+        let (mantissa, exponent) = factor.determinant();
+        approx_eq(
+            mantissa * f64::powf(10.0, exponent),
+            det,
+            1e-10 * f64::max(1.0, f64::abs(det)),
+        );
+
+        // refactorization with slightly different values (same pattern and symmetry)
+        let mut csc_new = csc.clone();
+        for j in 0..n {
+            for p in (csc.col_pointers[j] as usize)..(csc.col_pointers[j + 1] as usize) {
+                let i = csc.row_indices[p] as usize;
+                csc_new.values[p] *= 1.0 + 0.01 * ((i + j) as f64);
+            }
+        }
+        factor.refactorize(&csc_new).unwrap();
+        mat_vec_mul(&mut rhs, 1.0, &csc_new.as_dense(), &x_correct).unwrap();
+        factor.solve(x.as_mut_data(), rhs.as_data()).unwrap();
+        vec_approx_eq(&x, &x_correct, 1e-10);
+    }
+
+    #[test]
+    fn permutation_is_odd_works() {
+        assert!(!permutation_is_odd(&[0, 1, 2]));
+        assert!(permutation_is_odd(&[1, 0, 2]));
+        assert!(!permutation_is_odd(&[1, 2, 0]));
+        assert!(permutation_is_odd(&[3, 2, 1, 0, 5, 4]));
+    }
+
+    #[test]
+    fn analyze_captures_errors() {
+        let (_, csc, _, _) = Samples::rectangular_3x4();
+        assert_eq!(
+            SparseFactor::analyze(&csc, FactorKind::Lu, Ordering::Amd).err(),
+            Some("the matrix must be square")
+        );
+    }
+
+    #[test]
+    fn lu_works() {
+        for ordering in [Ordering::No, Ordering::Amd, Ordering::Metis] {
+            let (_, csc, _, det) = Samples::umfpack_unsymmetric_5x5();
+            check_solution(&csc, FactorKind::Lu, ordering, det);
+            let (_, csc, _, det) = Samples::mkl_unsymmetric_5x5();
+            check_solution(&csc, FactorKind::Lu, ordering, det);
+            let (_, csc, _, det) = Samples::mkl_symmetric_5x5_lower(false, false);
+            check_solution(&csc, FactorKind::Lu, ordering, det);
+            let (_, csc, _, det) = Samples::mkl_symmetric_5x5_upper(false, false);
+            check_solution(&csc, FactorKind::Lu, ordering, det);
+            let (_, csc, _, det) = Samples::mkl_symmetric_5x5_full();
+            check_solution(&csc, FactorKind::Lu, ordering, det);
+        }
+    }
+
+    #[test]
+    fn lu_pivoting_works() {
+        // zero diagonal requires row exchanges
+        let mut coo = CooMatrix::new(3, 3, 4, Sym::No).unwrap();
+        coo.put(0, 1, 2.0).unwrap();
+        coo.put(1, 0, 3.0).unwrap();
+        coo.put(1, 2, 1.0).unwrap();
+        coo.put(2, 2, 4.0).unwrap();
+        let csc = CscMatrix::from_coo(&coo).unwrap();
+        check_solution(&csc, FactorKind::Lu, Ordering::No, -24.0);
+        check_solution(&csc, FactorKind::Lu, Ordering::Amd, -24.0);
+    }
+
+    #[test]
+    fn determinant_handles_non_finite_values() {
+        let mut coo = CooMatrix::new(2, 2, 2, Sym::No).unwrap();
+        coo.put(0, 0, f64::MAX).unwrap();
+        coo.put(1, 1, f64::INFINITY).unwrap();
+        let csc = CscMatrix::from_coo(&coo).unwrap();
+        let mut factor = SparseFactor::analyze(&csc, FactorKind::Lu, Ordering::No).unwrap();
+        factor.factorize(&csc, 1.0).unwrap();
+        let (mantissa, _) = factor.determinant();
+        assert!(!mantissa.is_finite());
+    }
+
+    #[test]
+    fn cholesky_works() {
+        for ordering in [Ordering::No, Ordering::Amd, Ordering::Metis] {
+            let (_, csc, _, det) = Samples::positive_definite_3x3_lower();
+            check_solution(&csc, FactorKind::Cholesky, ordering, det);
+            let (_, csc, _, det) = Samples::positive_definite_3x3_upper();
+            check_solution(&csc, FactorKind::Cholesky, ordering, det);
+            let (_, csc, _, det) = Samples::positive_definite_3x3_full();
+            check_solution(&csc, FactorKind::Cholesky, ordering, det);
+            let (_, csc, _, det) = Samples::mkl_positive_definite_5x5_lower();
+            check_solution(&csc, FactorKind::Cholesky, ordering, det);
+        }
+    }
+
+    #[test]
+    fn refactorize_captures_errors() {
+        let (_, csc, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let mut factor = SparseFactor::analyze(&csc, FactorKind::Lu, Ordering::Amd).unwrap();
+        assert_eq!(
+            factor.refactorize(&csc).err(),
+            Some("the numeric factorization must be computed before the refactorization")
+        );
+        factor.factorize(&csc, 0.1).unwrap();
+        let (_, csc_wrong, _, _) = Samples::mkl_unsymmetric_5x5();
+        assert_eq!(
+            factor.refactorize(&csc_wrong).err(),
+            Some("the matrix structure differs from the analyzed one")
+        );
+        let mut csc_zero = csc.clone();
+        csc_zero.get_values_mut().fill(0.0);
+        assert_eq!(
+            factor.refactorize(&csc_zero).err(),
+            Some("the matrix is singular (LU refactorization failed)")
+        );
+    }
+
+    #[test]
+    fn factorize_captures_errors() {
+        // singular
+        let mut coo = CooMatrix::new(2, 2, 4, Sym::No).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(0, 1, 2.0).unwrap();
+        coo.put(1, 0, 2.0).unwrap();
+        coo.put(1, 1, 4.0).unwrap();
+        let csc = CscMatrix::from_coo(&coo).unwrap();
+        let mut factor = SparseFactor::analyze(&csc, FactorKind::Lu, Ordering::Amd).unwrap();
+        assert_eq!(
+            factor.factorize(&csc, 0.1).err(),
+            Some("the matrix is singular (LU factorization failed)")
+        );
+        let mut x = Vector::new(2);
+        let rhs = Vector::new(2);
+        assert_eq!(
+            factor.solve(x.as_mut_data(), rhs.as_data()).err(),
+            Some("the numeric factorization must be computed first")
+        );
+
+        // indefinite
+        let mut coo = CooMatrix::new(2, 2, 3, Sym::YesLower).unwrap();
+        coo.put(0, 0, 1.0).unwrap();
+        coo.put(1, 0, 2.0).unwrap();
+        coo.put(1, 1, 1.0).unwrap();
+        let csc = CscMatrix::from_coo(&coo).unwrap();
+        let mut factor = SparseFactor::analyze(&csc, FactorKind::Cholesky, Ordering::Amd).unwrap();
+        assert_eq!(
+            factor.factorize(&csc, 0.1).err(),
+            Some("the matrix is not positive-definite (Cholesky factorization failed)")
+        );
+
+        // different structure
+        let (_, other, _, _) = Samples::positive_definite_3x3_lower();
+        assert_eq!(
+            factor.factorize(&other, 0.1).err(),
+            Some("the matrix structure differs from the analyzed one")
+        );
+    }
+
+    #[test]
+    fn null_pivot_detection_works() {
+        // 1D Laplacian with free ends (the null space is spanned by constant vectors)
+        let n = 4;
+        let mut coo = CooMatrix::new(n, n, 3 * n - 2, Sym::No).unwrap();
+        for i in 0..n {
+            let d = if i == 0 || i == n - 1 { 1.0 } else { 2.0 };
+            coo.put(i, i, d).unwrap();
+            if i > 0 {
+                coo.put(i, i - 1, -1.0).unwrap();
+                coo.put(i - 1, i, -1.0).unwrap();
+            }
+        }
+        let csc = CscMatrix::from_coo(&coo).unwrap();
+        let a = csc.as_dense();
+        for kind in [FactorKind::Lu, FactorKind::Cholesky] {
+            for ordering in [Ordering::No, Ordering::Amd] {
+                let mut factor = SparseFactor::analyze(&csc, kind, ordering).unwrap();
+                assert!(factor.factorize(&csc, 0.1).is_err());
+                factor.set_null_pivot_tolerance(Some(1e-10));
+                factor.factorize(&csc, 0.1).unwrap();
+                let null_pivots = factor.null_pivots();
+                assert_eq!(null_pivots.len(), 1);
+                assert_eq!(factor.determinant(), (0.0, 0.0));
+
+                // z = A'⁻¹·e_r with A' = A + e_r·e_cᵀ is a null vector of A (scaled such that z_c = 1)
+                let (r, c) = null_pivots[0];
+                let mut e = Vector::new(n);
+                e[r] = 1.0;
+                let mut z = Vector::new(n);
+                factor.solve(z.as_mut_data(), e.as_data()).unwrap();
+                approx_eq(z[c], 1.0, 1e-14);
+                vec_approx_eq(&z, &[1.0, 1.0, 1.0, 1.0], 1e-14);
+                let mut az = Vector::new(n);
+                mat_vec_mul(&mut az, 1.0, &a, &z).unwrap();
+                vec_approx_eq(&az, &[0.0, 0.0, 0.0, 0.0], 1e-14);
+
+                // the refactorization searches the null pivots again
+                factor.refactorize(&csc).unwrap();
+                assert_eq!(factor.null_pivots().len(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn complex_lu_works() {
+        let (_, csc, _, det) = Samples::complex_symmetric_3x3_lower();
+        let mut factor = SparseFactor::analyze(&csc, FactorKind::Lu, Ordering::Amd).unwrap();
+        factor.factorize(&csc, 0.1).unwrap();
+        let (mantissa, exponent) = factor.determinant();
+        complex_approx_eq(mantissa * f64::powf(10.0, exponent), det, 1e-12);
+        let x_correct = ComplexVector::from(&[cpx!(1.0, 1.0), cpx!(2.0, -1.0), cpx!(0.0, 3.0)]);
+        let mut rhs = ComplexVector::new(3);
+        csc.mat_vec_mul(&mut rhs, cpx!(1.0, 0.0), &x_correct).unwrap();
+        let mut x = ComplexVector::new(3);
+        factor.solve(x.as_mut_data(), rhs.as_data()).unwrap();
+        complex_vec_approx_eq(&x, x_correct.as_data(), 1e-14);
+    }
+
+    #[test]
+    fn complex_solve_transpose_works() {
+        let mut coo = ComplexCooMatrix::new(3, 3, 7, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(2.0, 1.0)).unwrap();
+        coo.put(0, 2, cpx!(1.0, -1.0)).unwrap();
+        coo.put(1, 0, cpx!(-1.0, 2.0)).unwrap();
+        coo.put(1, 1, cpx!(3.0, 0.0)).unwrap();
+        coo.put(2, 0, cpx!(0.5, 0.0)).unwrap();
+        coo.put(2, 1, cpx!(0.0, -2.0)).unwrap();
+        coo.put(2, 2, cpx!(4.0, 1.0)).unwrap();
+        let csc = ComplexCscMatrix::from_coo(&coo).unwrap();
+        let a = csc.as_dense();
+        let x_correct = ComplexVector::from(&[cpx!(1.0, 1.0), cpx!(2.0, -1.0), cpx!(0.0, 3.0)]);
+        for kind in [FactorKind::Lu, FactorKind::Cholesky] {
+            let (csc, a) = if kind == FactorKind::Lu {
+                (csc.clone(), a.clone())
+            } else {
+                let (_, csc_sym, _, _) = Samples::complex_symmetric_3x3_lower();
+                let a_sym = csc_sym.as_dense();
+                (csc_sym, a_sym)
+            };
+            let mut factor = SparseFactor::analyze(&csc, kind, Ordering::Amd).unwrap();
+            factor.factorize(&csc, 0.1).unwrap();
+            let mut x = ComplexVector::new(3);
+
+            // Aᵀ·x = rhs
+            let mut rhs = ComplexVector::new(3);
+            complex_vec_mat_mul(&mut rhs, cpx!(1.0, 0.0), &x_correct, &a).unwrap();
+            factor.solve_transpose(x.as_mut_data(), rhs.as_data(), false).unwrap();
+            complex_vec_approx_eq(&x, x_correct.as_data(), 1e-13);
+
+            // Aᴴ·x = rhs
+            let rhs = ComplexVector::initialized(3, |j| {
+                (0..3).fold(cpx!(0.0, 0.0), |acc, i| acc + a.get(i, j).conj() * x_correct[i])
+            });
+            factor.solve_transpose(x.as_mut_data(), rhs.as_data(), true).unwrap();
+            complex_vec_approx_eq(&x, x_correct.as_data(), 1e-13);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use super::{NumCooMatrix, NumCscMatrix, NumCsrMatrix, Sym};
 use crate::StrError;
 use num_traits::{Num, NumCast};
-use russell_lab::{NumMatrix, NumVector};
+use russell_lab::{LinearOperator, NumMatrix, NumVector, Vector};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::ops::{AddAssign, MulAssign};
@@ -247,6 +247,66 @@ where
         }
     }
 
+    /// Performs the matrix-vector multiplication using multiple threads (CSR only)
+    ///
+    /// ```text
+    ///  v  :=  α ⋅  a   ⋅  u
+    /// (m)        (m,n)   (n)
+    /// ```
+    ///
+    /// # Input
+    ///
+    /// * `u` -- Vector with dimension equal to the number of columns of the matrix
+    /// * `num_threads` -- number of threads; use 0 to select the available parallelism of the machine
+    ///
+    /// # Output
+    ///
+    /// * `v` -- Vector with dimension equal to the number of rows of the matrix
+    ///
+    /// **Note:** If the CSR matrix is not available, the serial version ([NumSparseMatrix::mat_vec_mul]) is called.
+    /// See [NumCsrMatrix::mat_vec_mul_parallel].
+    pub fn mat_vec_mul_parallel(
+        &self,
+        v: &mut NumVector<T>,
+        alpha: T,
+        u: &NumVector<T>,
+        num_threads: usize,
+    ) -> Result<(), StrError>
+    where
+        T: Send + Sync,
+    {
+        match &self.csr {
+            Some(csr) => csr.mat_vec_mul_parallel(v, alpha, u, num_threads),
+            None => self.mat_vec_mul(v, alpha, u),
+        }
+    }
+
+    /// Performs the transposed matrix-vector multiplication
+    ///
+    /// ```text
+    ///  v  :=  α ⋅  aᵀ  ⋅  u
+    /// (n)        (n,m)   (m)
+    /// ```
+    ///
+    /// # Input
+    ///
+    /// * `u` -- Vector with dimension equal to the number of rows of the matrix
+    ///
+    /// # Output
+    ///
+    /// * `v` -- Vector with dimension equal to the number of columns of the matrix
+    ///
+    /// **Priority**: CSC -> CSR -> COO
+    pub fn mat_t_vec_mul(&self, v: &mut NumVector<T>, alpha: T, u: &NumVector<T>) -> Result<(), StrError> {
+        match &self.csc {
+            Some(csc) => csc.mat_t_vec_mul(v, alpha, u),
+            None => match &self.csr {
+                Some(csr) => csr.mat_t_vec_mul(v, alpha, u),
+                None => self.coo.as_ref().unwrap().mat_t_vec_mul(v, alpha, u), // unwrap OK because at least one mat must be available
+            },
+        }
+    }
+
     /// Converts the sparse matrix to dense format
     ///
     /// **Priority**: CSC -> CSR -> COO
@@ -437,13 +497,28 @@ where
     }
 }
 
+impl LinearOperator for NumSparseMatrix<f64> {
+    fn dims(&self) -> (usize, usize) {
+        let (nrow, ncol, _, _) = self.get_info();
+        (nrow, ncol)
+    }
+
+    fn apply(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.mat_vec_mul(v, 1.0, u)
+    }
+
+    fn apply_transpose(&self, v: &mut Vector, u: &Vector) -> Result<(), StrError> {
+        self.mat_t_vec_mul(v, 1.0, u)
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
     use super::NumSparseMatrix;
     use crate::{Samples, Sym};
-    use russell_lab::{vec_approx_eq, Matrix, Vector};
+    use russell_lab::{vec_approx_eq, LinearOperator, Matrix, Vector};
 
     #[test]
     fn new_functions_work() {
@@ -497,9 +572,21 @@ mod tests {
         assert_eq!(csr_mat.get_values(), &[10.0, 20.0]);
         // COO, CSC, CSR
         let mut ax = Vector::new(1);
+        let u = Vector::from(&[1.0]);
+        let mut atu = Vector::new(2);
         for mat in [&coo_mat, &csc_mat, &csr_mat] {
             mat.mat_vec_mul(&mut ax, 2.0, &x).unwrap();
             vec_approx_eq(&ax, &[80.0], 1e-15);
+            mat.mat_vec_mul_parallel(&mut ax, 2.0, &x, 2).unwrap();
+            vec_approx_eq(&ax, &[80.0], 1e-15);
+            mat.mat_t_vec_mul(&mut atu, 2.0, &u).unwrap();
+            vec_approx_eq(&atu, &[20.0, 40.0], 1e-15);
+            let op: &dyn LinearOperator = mat;
+            assert_eq!(op.dims(), (1, 2));
+            op.apply(&mut ax, &x).unwrap();
+            vec_approx_eq(&ax, &[40.0], 1e-15);
+            op.apply_transpose(&mut atu, &u).unwrap();
+            vec_approx_eq(&atu, &[10.0, 20.0], 1e-15);
             assert_eq!(
                 mat.mat_vec_mul(&mut wrong, 1.0, &x).err(),
                 Some("v vector is incompatible")
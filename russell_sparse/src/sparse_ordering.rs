@@ -0,0 +1,872 @@
+use super::{to_i32, NumCooMatrix, NumCsrMatrix, Sym};
+use crate::StrError;
+use num_traits::{Num, NumCast};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::{AddAssign, MulAssign};
+
+/// Indicates an unset position in the workspace arrays
+const NONE: usize = usize::MAX;
+
+/// Defines the maximum number of nodes of a subgraph that is not dissected any further
+const DISSECTION_LEAF_SIZE: usize = 64;
+
+/// Returns the adjacency lists of the graph of `A + Aᵀ` (without self-loops)
+///
+/// The input arrays are the compressed arrays (row or column) of a square matrix.
+/// Since the graph of `A + Aᵀ` is symmetric, the result is the same for the CSR and CSC arrays
+/// and also for the triangular representations of symmetric matrices.
+pub(crate) fn graph_adjacency(n: usize, pointers: &[i32], indices: &[i32]) -> Vec<Vec<usize>> {
+    let mut adj = vec![Vec::new(); n];
+    for i in 0..n {
+        for &j in &indices[(pointers[i] as usize)..(pointers[i + 1] as usize)] {
+            let j = j as usize;
+            if i != j {
+                adj[i].push(j);
+                adj[j].push(i);
+            }
+        }
+    }
+    for list in adj.iter_mut() {
+        list.sort_unstable();
+        list.dedup();
+    }
+    adj
+}
+
+/// Computes the approximate minimum degree ordering of a symmetric graph
+///
+/// The elimination is simulated on the quotient graph, i.e., the fill-in is never formed
+/// explicitly. Instead, each eliminated node becomes an element holding the list of its
+/// (uneliminated) neighbors. The degree of a node is approximated by the upper bound of
+/// Amestoy, Davis, and Duff (1996), and elements whose lists are contained in the
+/// newest element are absorbed (aggressive absorption). Ties are broken by the smallest index.
+///
+/// # Input
+///
+/// * `adj` -- the adjacency lists (without self-loops) of the graph of A + Aᵀ
+///
+/// # Output
+///
+/// Returns the permutation `perm` such that `perm[k]` is the k-th eliminated node.
+pub(crate) fn approximate_minimum_degree(adj: &[Vec<usize>]) -> Vec<usize> {
+    let n = adj.len();
+    let mut vars: Vec<Vec<usize>> = adj.to_vec(); // adjacent variables of each variable
+    let mut elems: Vec<Vec<usize>> = vec![Vec::new(); n]; // adjacent elements of each variable
+    let mut lists: Vec<Vec<usize>> = vec![Vec::new(); n]; // variables of each element
+    let mut eliminated = vec![false; n];
+    let mut absorbed = vec![false; n];
+    let mut degree: Vec<usize> = adj.iter().map(|list| list.len()).collect();
+    let mut mark = vec![NONE; n];
+    let mut w = vec![0; n]; // |Le \ Lp|
+    let mut w_mark = vec![NONE; n];
+    let mut heap: BinaryHeap<Reverse<(usize, usize)>> = (0..n).map(|v| Reverse((degree[v], v))).collect();
+    let mut perm = Vec::with_capacity(n);
+    while let Some(Reverse((d, p))) = heap.pop() {
+        if eliminated[p] || d != degree[p] {
+            continue; // outdated entry
+        }
+        eliminated[p] = true;
+        perm.push(p);
+        let remaining = n - perm.len();
+
+        // the new element p holds Lp = (Ap ∪ Le for all e in Ep) \ {p}
+        let mut lp = Vec::new();
+        for &i in &vars[p] {
+            if !eliminated[i] && mark[i] != p {
+                mark[i] = p;
+                lp.push(i);
+            }
+        }
+        for &e in &elems[p] {
+            if absorbed[e] {
+                continue;
+            }
+            for &i in &lists[e] {
+                if !eliminated[i] && mark[i] != p {
+                    mark[i] = p;
+                    lp.push(i);
+                }
+            }
+            absorbed[e] = true;
+            lists[e] = Vec::new();
+        }
+        vars[p] = Vec::new();
+        elems[p] = Vec::new();
+
+        // update the variables in Lp (the entries of Ai covered by the new element are pruned)
+        for &i in &lp {
+            vars[i].retain(|&j| !eliminated[j] && mark[j] != p);
+            elems[i].retain(|&e| !absorbed[e]);
+            elems[i].push(p);
+        }
+
+        // compute |Le \ Lp| for all elements adjacent to Lp
+        for &i in &lp {
+            for &e in &elems[i] {
+                if e == p {
+                    continue;
+                }
+                if w_mark[e] != p {
+                    w_mark[e] = p;
+                    w[e] = lists[e].len();
+                }
+                w[e] -= 1;
+            }
+        }
+
+        // approximate degrees
+        let lp_len = lp.len();
+        for &i in &lp {
+            let mut bound = vars[i].len() + lp_len - 1;
+            for &e in &elems[i] {
+                if e == p {
+                    continue;
+                }
+                if w[e] == 0 {
+                    absorbed[e] = true; // Le ⊆ Lp
+                    lists[e] = Vec::new();
+                } else {
+                    bound += w[e];
+                }
+            }
+            elems[i].retain(|&e| !absorbed[e]);
+            let d = usize::min(bound, usize::min(remaining - 1, degree[i] + lp_len - 1));
+            degree[i] = d;
+            heap.push(Reverse((d, i)));
+        }
+        lists[p] = lp;
+    }
+    perm
+}
+
+/// Computes the level structure of the connected subgraph containing the root node
+///
+/// Only the nodes with `region[node] == id` are visited. The `level` array is used as workspace
+/// (it must be filled with NONE for the nodes of the region) and is restored on output.
+fn level_structure(
+    adj: &[Vec<usize>],
+    root: usize,
+    region: &[usize],
+    id: usize,
+    level: &mut [usize],
+) -> Vec<Vec<usize>> {
+    let mut levels = vec![vec![root]];
+    level[root] = 0;
+    loop {
+        let k = levels.len();
+        let mut next = Vec::new();
+        for &v in &levels[k - 1] {
+            for &u in &adj[v] {
+                if region[u] == id && level[u] == NONE {
+                    level[u] = k;
+                    next.push(u);
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        levels.push(next);
+    }
+    for list in &levels {
+        for &v in list {
+            level[v] = NONE;
+        }
+    }
+    levels
+}
+
+/// Finds a pseudo-peripheral node by the algorithm of George and Liu (1979)
+///
+/// Returns the node and its level structure.
+fn pseudo_peripheral_node(
+    adj: &[Vec<usize>],
+    start: usize,
+    region: &[usize],
+    id: usize,
+    level: &mut [usize],
+) -> (usize, Vec<Vec<usize>>) {
+    let mut root = start;
+    let mut levels = level_structure(adj, root, region, id, level);
+    loop {
+        let last = levels.last().unwrap();
+        let mut candidate = last[0];
+        for &v in last {
+            if adj[v].len() < adj[candidate].len() {
+                candidate = v;
+            }
+        }
+        let candidate_levels = level_structure(adj, candidate, region, id, level);
+        if candidate_levels.len() <= levels.len() {
+            return (root, levels);
+        }
+        root = candidate;
+        levels = candidate_levels;
+    }
+}
+
+/// Computes the reverse Cuthill-McKee ordering of a symmetric graph
+///
+/// Each connected component is traversed in breadth-first order starting from a pseudo-peripheral
+/// node, with the neighbors visited by increasing degree. The final sequence is then reversed.
+///
+/// # Output
+///
+/// Returns the permutation `perm` such that `perm[k]` is the k-th node of the new numbering.
+pub(crate) fn reverse_cuthill_mckee(adj: &[Vec<usize>]) -> Vec<usize> {
+    let n = adj.len();
+    let region = vec![0; n];
+    let mut level = vec![NONE; n];
+    let mut visited = vec![false; n];
+    let mut by_degree: Vec<usize> = (0..n).collect();
+    by_degree.sort_by_key(|&v| (adj[v].len(), v));
+    let mut perm = Vec::with_capacity(n);
+    let mut neighbors: Vec<usize> = Vec::new();
+    for &start in &by_degree {
+        if visited[start] {
+            continue;
+        }
+        let (root, _) = pseudo_peripheral_node(adj, start, &region, 0, &mut level);
+        let mut head = perm.len();
+        visited[root] = true;
+        perm.push(root);
+        while head < perm.len() {
+            let v = perm[head];
+            head += 1;
+            neighbors.clear();
+            neighbors.extend(adj[v].iter().filter(|&&u| !visited[u]));
+            neighbors.sort_by_key(|&u| (adj[u].len(), u));
+            for &u in &neighbors {
+                visited[u] = true;
+                perm.push(u);
+            }
+        }
+    }
+    perm.reverse();
+    perm
+}
+
+/// Holds a pending task of the nested dissection algorithm
+enum Dissection {
+    /// Orders the subgraph with the given nodes
+    Split(Vec<usize>),
+
+    /// Appends the (already ordered) nodes to the permutation
+    Append(Vec<usize>),
+}
+
+/// Computes the nested dissection ordering of a symmetric graph
+///
+/// The graph is recursively split by vertex separators found from the level structure rooted at a
+/// pseudo-peripheral node: the separator consists of the nodes of the median level adjacent to the
+/// next level. The two parts are ordered first and the separator last. Subgraphs with no more than
+/// 64 nodes are ordered by the approximate minimum degree method.
+///
+/// # Output
+///
+/// Returns the permutation `perm` such that `perm[k]` is the k-th eliminated node.
+pub(crate) fn nested_dissection(adj: &[Vec<usize>]) -> Vec<usize> {
+    let n = adj.len();
+    let mut region = vec![0; n];
+    let mut next_id = 0;
+    let mut level = vec![NONE; n];
+    let mut local = vec![NONE; n];
+    let mut perm = Vec::with_capacity(n);
+    let mut stack = vec![Dissection::Split((0..n).collect())];
+    while let Some(task) = stack.pop() {
+        let nodes = match task {
+            Dissection::Append(nodes) => {
+                perm.extend(nodes);
+                continue;
+            }
+            Dissection::Split(nodes) => nodes,
+        };
+        next_id += 1;
+        let id = next_id;
+        for &v in &nodes {
+            region[v] = id;
+        }
+
+        // small subgraphs are ordered by the minimum degree method
+        if nodes.len() <= DISSECTION_LEAF_SIZE {
+            for (k, &v) in nodes.iter().enumerate() {
+                local[v] = k;
+            }
+            let sub: Vec<Vec<usize>> = nodes
+                .iter()
+                .map(|&v| adj[v].iter().filter(|&&u| region[u] == id).map(|&u| local[u]).collect())
+                .collect();
+            perm.extend(approximate_minimum_degree(&sub).iter().map(|&k| nodes[k]));
+            continue;
+        }
+
+        // disconnected subgraphs are split into their components
+        let (root, levels) = pseudo_peripheral_node(adj, nodes[0], &region, id, &mut level);
+        let count: usize = levels.iter().map(|list| list.len()).sum();
+        if count < nodes.len() {
+            let component: Vec<usize> = levels.concat();
+            next_id += 1;
+            for &v in &component {
+                region[v] = next_id;
+            }
+            let rest = nodes.into_iter().filter(|&v| region[v] == id).collect();
+            stack.push(Dissection::Split(rest));
+            stack.push(Dissection::Split(component));
+            continue;
+        }
+
+        // subgraphs with a small diameter cannot be dissected
+        let nlevel = levels.len();
+        if nlevel < 3 {
+            stack.push(Dissection::Append(vec![root]));
+            let rest = nodes.into_iter().filter(|&v| v != root).collect();
+            stack.push(Dissection::Split(rest));
+            continue;
+        }
+
+        // median level (both parts must be non-empty)
+        let mut m = 0;
+        let mut cumulative = 0;
+        for (k, list) in levels.iter().enumerate() {
+            cumulative += list.len();
+            if 2 * cumulative >= count {
+                m = k;
+                break;
+            }
+        }
+        let m = usize::max(1, usize::min(m, nlevel - 2));
+
+        // separator and parts
+        next_id += 1;
+        let upper_id = next_id;
+        for list in &levels[(m + 1)..] {
+            for &v in list {
+                region[v] = upper_id;
+            }
+        }
+        let mut separator = Vec::new();
+        let mut first: Vec<usize> = levels[..m].concat();
+        for &v in &levels[m] {
+            if adj[v].iter().any(|&u| region[u] == upper_id) {
+                separator.push(v);
+            } else {
+                first.push(v);
+            }
+        }
+        let second = levels[(m + 1)..].concat();
+        stack.push(Dissection::Append(separator));
+        stack.push(Dissection::Split(second));
+        stack.push(Dissection::Split(first));
+    }
+    perm
+}
+
+/// Checks whether the matrix is square and returns the graph of A + Aᵀ
+fn csr_graph<T>(a: &NumCsrMatrix<T>) -> Result<Vec<Vec<usize>>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.nrow != a.ncol {
+        return Err("the matrix must be square");
+    }
+    Ok(graph_adjacency(a.nrow, &a.row_pointers, &a.col_indices))
+}
+
+/// Computes the reverse Cuthill-McKee (bandwidth-reducing) ordering of a CSR matrix
+///
+/// The ordering is computed from the sparsity pattern of `A + Aᵀ`; thus, the matrix may be
+/// unsymmetric or symmetric with any representation.
+///
+/// # Output
+///
+/// Returns the permutation `perm` such that `perm[k]` is the original index of the k-th
+/// row/column of the permuted matrix `B = P·A·Pᵀ`, i.e., `B[k][l] = A[perm[k]][perm[l]]`.
+///
+/// Reference: George A, Liu JWH (1981) Computer Solution of Large Sparse Positive Definite
+/// Systems, Prentice-Hall
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     // arrow matrix
+///     // ┌         ┐
+///     // │ 1 1 1 1 │
+///     // │ 1 1 . . │
+///     // │ 1 . 1 . │
+///     // │ 1 . . 1 │
+///     // └         ┘
+///     let mut coo = CooMatrix::new(4, 4, 10, Sym::No)?;
+///     for i in 0..4 {
+///         coo.put(i, i, 1.0)?;
+///         if i > 0 {
+///             coo.put(0, i, 1.0)?;
+///             coo.put(i, 0, 1.0)?;
+///         }
+///     }
+///     let a = CsrMatrix::from_coo(&coo)?;
+///     assert_eq!(csr_bandwidth(&a), (3, 3));
+///
+///     let perm = ordering_rcm(&a)?;
+///     let b = csr_permute_symmetric(&a, &perm)?;
+///     assert_eq!(csr_bandwidth(&b), (2, 2));
+///     Ok(())
+/// }
+/// ```
+pub fn ordering_rcm<T>(a: &NumCsrMatrix<T>) -> Result<Vec<usize>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    Ok(reverse_cuthill_mckee(&csr_graph(a)?))
+}
+
+/// Computes the approximate minimum degree (fill-reducing) ordering of a CSR matrix
+///
+/// The ordering is computed from the sparsity pattern of `A + Aᵀ`; thus, the matrix may be
+/// unsymmetric or symmetric with any representation.
+///
+/// # Output
+///
+/// Returns the permutation `perm` such that `perm[k]` is the original index of the k-th
+/// row/column of the permuted matrix `B = P·A·Pᵀ`, i.e., `B[k][l] = A[perm[k]][perm[l]]`.
+///
+/// Reference: Amestoy PR, Davis TA, Duff IS (1996) An approximate minimum degree ordering
+/// algorithm, SIAM Journal on Matrix Analysis and Applications, 17(4):886-905
+pub fn ordering_amd<T>(a: &NumCsrMatrix<T>) -> Result<Vec<usize>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    Ok(approximate_minimum_degree(&csr_graph(a)?))
+}
+
+/// Computes the nested dissection (fill-reducing) ordering of a CSR matrix
+///
+/// The ordering is computed from the sparsity pattern of `A + Aᵀ`; thus, the matrix may be
+/// unsymmetric or symmetric with any representation. The separators are found from level
+/// structures of the graph (no external partitioning library is required).
+///
+/// # Output
+///
+/// Returns the permutation `perm` such that `perm[k]` is the original index of the k-th
+/// row/column of the permuted matrix `B = P·A·Pᵀ`, i.e., `B[k][l] = A[perm[k]][perm[l]]`.
+///
+/// Reference: George A (1973) Nested dissection of a regular finite element mesh,
+/// SIAM Journal on Numerical Analysis, 10(2):345-363
+pub fn ordering_nested_dissection<T>(a: &NumCsrMatrix<T>) -> Result<Vec<usize>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    Ok(nested_dissection(&csr_graph(a)?))
+}
+
+/// Returns the inverse of a permutation
+///
+/// The inverse permutation `iperm` is such that `iperm[perm[k]] = k`. Thus, if `perm` is given by
+/// one of the ordering functions, `iperm[i]` is the new index of the original row/column `i`.
+///
+/// # Examples
+///
+/// ```
+/// use russell_sparse::prelude::*;
+/// use russell_sparse::StrError;
+///
+/// fn main() -> Result<(), StrError> {
+///     let iperm = permutation_inverse(&[2, 0, 1])?;
+///     assert_eq!(iperm, &[1, 2, 0]);
+///     Ok(())
+/// }
+/// ```
+pub fn permutation_inverse(perm: &[usize]) -> Result<Vec<usize>, StrError> {
+    let n = perm.len();
+    let mut iperm = vec![NONE; n];
+    for (k, &i) in perm.iter().enumerate() {
+        if i >= n {
+            return Err("the permutation has an out-of-range index");
+        }
+        if iperm[i] != NONE {
+            return Err("the permutation has a repeated index");
+        }
+        iperm[i] = k;
+    }
+    Ok(iperm)
+}
+
+/// Returns the new (i, j) indices of an entry, keeping it in the stored triangle (if any)
+fn permuted_indices(sym: Sym, iperm: &[usize], i: usize, j: usize) -> (usize, usize) {
+    let (i, j) = (iperm[i], iperm[j]);
+    match sym {
+        Sym::YesLower if j > i => (j, i),
+        Sym::YesUpper if j < i => (j, i),
+        _ => (i, j),
+    }
+}
+
+/// Symmetrically permutes a COO matrix
+///
+/// ```text
+/// B := P⋅A⋅Pᵀ  with  B[k][l] = A[perm[k]][perm[l]]
+/// ```
+///
+/// **Note:** If A is symmetric with a triangular representation, the entries of B are moved
+/// to the same triangle. The duplicate entries, if any, are preserved.
+pub fn coo_permute_symmetric<T>(a: &NumCooMatrix<T>, perm: &[usize]) -> Result<NumCooMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.nrow != a.ncol {
+        return Err("the matrix must be square");
+    }
+    if perm.len() != a.nrow {
+        return Err("the permutation length must equal the matrix dimension");
+    }
+    let iperm = permutation_inverse(perm)?;
+    let mut b = NumCooMatrix::new(a.nrow, a.ncol, a.max_nnz, a.symmetric)?;
+    for p in 0..a.nnz {
+        let (i, j) = permuted_indices(a.symmetric, &iperm, a.indices_i[p] as usize, a.indices_j[p] as usize);
+        b.put(i, j, a.values[p])?;
+    }
+    Ok(b)
+}
+
+/// Symmetrically permutes a CSR matrix
+///
+/// ```text
+/// B := P⋅A⋅Pᵀ  with  B[k][l] = A[perm[k]][perm[l]]
+/// ```
+///
+/// **Note:** If A is symmetric with a triangular representation, the entries of B are moved
+/// to the same triangle. The column indices of B are sorted within each row.
+pub fn csr_permute_symmetric<T>(a: &NumCsrMatrix<T>, perm: &[usize]) -> Result<NumCsrMatrix<T>, StrError>
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    if a.nrow != a.ncol {
+        return Err("the matrix must be square");
+    }
+    if perm.len() != a.nrow {
+        return Err("the permutation length must equal the matrix dimension");
+    }
+    let n = a.nrow;
+    let iperm = permutation_inverse(perm)?;
+    let nnz = a.row_pointers[n] as usize;
+    let mut entries = Vec::with_capacity(nnz);
+    for i in 0..n {
+        for p in (a.row_pointers[i] as usize)..(a.row_pointers[i + 1] as usize) {
+            let (k, l) = permuted_indices(a.symmetric, &iperm, i, a.col_indices[p] as usize);
+            entries.push((k, l, a.values[p]));
+        }
+    }
+    entries.sort_by_key(|&(k, l, _)| (k, l));
+    let mut row_pointers = vec![0; n + 1];
+    for &(k, _, _) in &entries {
+        row_pointers[k + 1] += 1;
+    }
+    for k in 0..n {
+        row_pointers[k + 1] += row_pointers[k];
+    }
+    let col_indices = entries.iter().map(|&(_, l, _)| to_i32(l)).collect();
+    let values = entries.iter().map(|&(_, _, v)| v).collect();
+    NumCsrMatrix::new(n, n, row_pointers, col_indices, values, a.symmetric)
+}
+
+/// Returns the lower and upper bandwidths of a CSR matrix
+///
+/// The lower (upper) bandwidth `ml` (`mu`) is the largest distance of a non-zero entry below
+/// (above) the diagonal. If A is symmetric with a triangular representation, `ml = mu`.
+///
+/// **Note:** The output may be used to allocate the band storage of the dense
+/// `mat_convert_to_blas_band` function of russell_lab.
+///
+/// # Output
+///
+/// Returns `(ml, mu)`
+pub fn csr_bandwidth<T>(a: &NumCsrMatrix<T>) -> (usize, usize)
+where
+    T: AddAssign + MulAssign + Num + NumCast + Copy + DeserializeOwned + Serialize,
+{
+    let (mut ml, mut mu) = (0, 0);
+    for i in 0..a.nrow {
+        for p in (a.row_pointers[i] as usize)..(a.row_pointers[i + 1] as usize) {
+            let j = a.col_indices[p] as usize;
+            if j < i {
+                ml = usize::max(ml, i - j);
+            } else {
+                mu = usize::max(mu, j - i);
+            }
+        }
+    }
+    if a.symmetric.triangular() {
+        let m = usize::max(ml, mu);
+        (m, m)
+    } else {
+        (ml, mu)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CooMatrix, CsrMatrix, Samples};
+    use russell_lab::mat_approx_eq;
+
+    /// Returns the 5-point Laplacian of a nx × ny grid
+    fn grid_laplacian(nx: usize, ny: usize) -> CsrMatrix {
+        let n = nx * ny;
+        let mut coo = CooMatrix::new(n, n, 5 * n, Sym::No).unwrap();
+        for r in 0..ny {
+            for c in 0..nx {
+                let i = r * nx + c;
+                coo.put(i, i, 4.0).unwrap();
+                if c > 0 {
+                    coo.put(i, i - 1, -1.0).unwrap();
+                }
+                if c + 1 < nx {
+                    coo.put(i, i + 1, -1.0).unwrap();
+                }
+                if r > 0 {
+                    coo.put(i, i - nx, -1.0).unwrap();
+                }
+                if r + 1 < ny {
+                    coo.put(i, i + nx, -1.0).unwrap();
+                }
+            }
+        }
+        CsrMatrix::from_coo(&coo).unwrap()
+    }
+
+    /// Returns the number of non-zeros of the Cholesky factor L (including the diagonal)
+    fn cholesky_fill(adj: &[Vec<usize>], perm: &[usize]) -> usize {
+        let n = adj.len();
+        let iperm = permutation_inverse(perm).unwrap();
+        let mut sets: Vec<Vec<usize>> = (0..n)
+            .map(|k| adj[perm[k]].iter().map(|&u| iperm[u]).filter(|&l| l > k).collect())
+            .collect();
+        let mut total = n;
+        for k in 0..n {
+            let mut set = std::mem::take(&mut sets[k]);
+            set.sort_unstable();
+            set.dedup();
+            total += set.len();
+            if let Some(&parent) = set.first() {
+                sets[parent].extend(set[1..].iter().copied());
+            }
+        }
+        total
+    }
+
+    fn check_permutation(perm: &[usize], n: usize) {
+        assert_eq!(perm.len(), n);
+        assert!(permutation_inverse(perm).is_ok());
+    }
+
+    #[test]
+    fn graph_adjacency_works() {
+        let (_, _, csr, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        let adj = graph_adjacency(5, &csr.row_pointers, &csr.col_indices);
+        assert_eq!(adj, &[vec![1, 2, 3, 4], vec![0], vec![0], vec![0], vec![0]]);
+        let (_, _, csr, _) = Samples::mkl_symmetric_5x5_full();
+        assert_eq!(graph_adjacency(5, &csr.row_pointers, &csr.col_indices), adj);
+    }
+
+    #[test]
+    fn approximate_minimum_degree_works() {
+        // star graph: the leaves are eliminated before the center
+        // (the last two nodes have the same degree; thus, the smallest index wins)
+        let mut adj = vec![Vec::new(); 5];
+        for i in 1..5 {
+            adj[0].push(i);
+            adj[i].push(0);
+        }
+        assert_eq!(approximate_minimum_degree(&adj), &[1, 2, 3, 0, 4]);
+
+        // path graph 0-1-2-3: ends first
+        let adj = vec![vec![1], vec![0, 2], vec![1, 3], vec![2]];
+        assert_eq!(approximate_minimum_degree(&adj), &[0, 1, 2, 3]);
+
+        // no edges
+        let adj = vec![Vec::new(); 3];
+        assert_eq!(approximate_minimum_degree(&adj), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn reverse_cuthill_mckee_works() {
+        // path graph numbered at random: the path is recovered
+        let adj = vec![vec![2, 3], vec![3], vec![0], vec![0, 1]];
+        let perm = reverse_cuthill_mckee(&adj);
+        assert_eq!(perm, &[2, 0, 3, 1]);
+
+        // two components
+        let adj = vec![vec![2], vec![3], vec![0], vec![1], vec![]];
+        let perm = reverse_cuthill_mckee(&adj);
+        check_permutation(&perm, 5);
+    }
+
+    #[test]
+    fn orderings_capture_errors() {
+        let (_, _, csr, _) = Samples::rectangular_3x4();
+        assert_eq!(ordering_rcm(&csr).err(), Some("the matrix must be square"));
+        assert_eq!(ordering_amd(&csr).err(), Some("the matrix must be square"));
+        assert_eq!(
+            ordering_nested_dissection(&csr).err(),
+            Some("the matrix must be square")
+        );
+    }
+
+    #[test]
+    fn orderings_work() {
+        for (_, _, csr, _) in [
+            Samples::mkl_unsymmetric_5x5(),
+            Samples::mkl_symmetric_5x5_lower(false, false),
+            Samples::mkl_symmetric_5x5_upper(false, false),
+            Samples::mkl_symmetric_5x5_full(),
+        ] {
+            check_permutation(&ordering_rcm(&csr).unwrap(), 5);
+            check_permutation(&ordering_amd(&csr).unwrap(), 5);
+            check_permutation(&ordering_nested_dissection(&csr).unwrap(), 5);
+        }
+    }
+
+    #[test]
+    fn orderings_reduce_bandwidth_and_fill() {
+        // a grid with a scrambled numbering
+        let (nx, ny) = (40, 30);
+        let n = nx * ny;
+        let a = grid_laplacian(nx, ny);
+        let scramble: Vec<usize> = (0..n).map(|k| (k * 7) % n).collect();
+        let a = csr_permute_symmetric(&a, &scramble).unwrap();
+        let adj = graph_adjacency(n, &a.row_pointers, &a.col_indices);
+        let identity: Vec<usize> = (0..n).collect();
+        let fill_original = cholesky_fill(&adj, &identity);
+
+        // bandwidth (the natural ordering has bandwidth nx)
+        let perm = ordering_rcm(&a).unwrap();
+        check_permutation(&perm, n);
+        let b = csr_permute_symmetric(&a, &perm).unwrap();
+        let (ml, mu) = csr_bandwidth(&b);
+        assert!(ml <= ny + 2 && mu <= ny + 2);
+
+        // fill-in
+        let perm = ordering_amd(&a).unwrap();
+        check_permutation(&perm, n);
+        let fill_amd = cholesky_fill(&adj, &perm);
+        assert!(fill_amd < fill_original / 2);
+
+        let perm = ordering_nested_dissection(&a).unwrap();
+        check_permutation(&perm, n);
+        let fill_nd = cholesky_fill(&adj, &perm);
+        assert!(fill_nd < fill_original / 2);
+    }
+
+    #[test]
+    fn nested_dissection_handles_components_and_small_graphs() {
+        // block diagonal: two grids and isolated nodes
+        let n = 2 * 100 + 50;
+        let mut adj = vec![Vec::new(); n];
+        for offset in [0, 100] {
+            let g = grid_laplacian(10, 10);
+            let sub = graph_adjacency(100, &g.row_pointers, &g.col_indices);
+            for (i, list) in sub.iter().enumerate() {
+                adj[offset + i] = list.iter().map(|&j| offset + j).collect();
+            }
+        }
+        check_permutation(&nested_dissection(&adj), n);
+
+        // complete graph (no separator)
+        let n = 80;
+        let adj: Vec<Vec<usize>> = (0..n).map(|i| (0..n).filter(|&j| j != i).collect()).collect();
+        check_permutation(&nested_dissection(&adj), n);
+    }
+
+    #[test]
+    fn permutation_inverse_captures_errors() {
+        assert_eq!(
+            permutation_inverse(&[0, 3, 1]).err(),
+            Some("the permutation has an out-of-range index")
+        );
+        assert_eq!(
+            permutation_inverse(&[0, 1, 1]).err(),
+            Some("the permutation has a repeated index")
+        );
+    }
+
+    #[test]
+    fn permute_symmetric_captures_errors() {
+        let (coo, _, csr, _) = Samples::rectangular_3x4();
+        assert_eq!(
+            coo_permute_symmetric(&coo, &[0, 1, 2]).err(),
+            Some("the matrix must be square")
+        );
+        assert_eq!(
+            csr_permute_symmetric(&csr, &[0, 1, 2]).err(),
+            Some("the matrix must be square")
+        );
+        let (coo, _, csr, _) = Samples::mkl_unsymmetric_5x5();
+        assert_eq!(
+            coo_permute_symmetric(&coo, &[0, 1, 2]).err(),
+            Some("the permutation length must equal the matrix dimension")
+        );
+        assert_eq!(
+            csr_permute_symmetric(&csr, &[0, 1, 2, 3, 3]).err(),
+            Some("the permutation has a repeated index")
+        );
+    }
+
+    #[test]
+    fn permute_symmetric_works() {
+        let perm = [3, 0, 4, 1, 2];
+        for (coo, _, csr, _) in [
+            Samples::mkl_unsymmetric_5x5(),
+            Samples::mkl_symmetric_5x5_lower(false, false),
+            Samples::mkl_symmetric_5x5_upper(false, false),
+            Samples::mkl_symmetric_5x5_full(),
+        ] {
+            let a = coo.as_dense();
+            let b_coo = coo_permute_symmetric(&coo, &perm).unwrap();
+            let b_csr = csr_permute_symmetric(&csr, &perm).unwrap();
+            assert_eq!(b_coo.symmetric, coo.symmetric);
+            assert_eq!(b_csr.symmetric, csr.symmetric);
+            let b = b_coo.as_dense();
+            for k in 0..5 {
+                for l in 0..5 {
+                    assert_eq!(b.get(k, l), a.get(perm[k], perm[l]));
+                }
+            }
+            mat_approx_eq(&b_csr.as_dense(), &b, 1e-15);
+            // the entries remain in the stored triangle
+            let c = CsrMatrix::from_coo(&b_coo).unwrap();
+            assert_eq!(c.row_pointers, b_csr.row_pointers);
+            assert_eq!(c.col_indices, b_csr.col_indices);
+        }
+    }
+
+    #[test]
+    fn csr_bandwidth_works() {
+        let (_, _, csr, _) = Samples::mkl_unsymmetric_5x5();
+        let a = csr.as_dense();
+        let (mut ml, mut mu) = (0, 0);
+        for i in 0..5 {
+            for j in 0..5 {
+                if a.get(i, j) != 0.0 {
+                    if j < i {
+                        ml = usize::max(ml, i - j);
+                    } else {
+                        mu = usize::max(mu, j - i);
+                    }
+                }
+            }
+        }
+        assert_eq!(csr_bandwidth(&csr), (ml, mu));
+        let (_, _, csr, _) = Samples::mkl_symmetric_5x5_lower(false, false);
+        assert_eq!(csr_bandwidth(&csr), (4, 4));
+        let (_, _, csr, _) = Samples::mkl_symmetric_5x5_upper(false, false);
+        assert_eq!(csr_bandwidth(&csr), (4, 4));
+    }
+}
@@ -1,4 +1,4 @@
-use super::{StatsLinSolMUMPS, VerifyLinSys};
+use super::{MatrixAnalysis, StatsLinSolMUMPS, VerifyLinSys};
 use crate::StrError;
 use russell_lab::{format_nanoseconds, get_num_threads, using_intel_mkl};
 use serde::{Deserialize, Serialize};
@@ -43,9 +43,14 @@ pub struct StatsLinSolOutput {
     pub openmp_num_threads: usize,
     pub umfpack_strategy: String,
     pub umfpack_rcond_estimate: f64, // reciprocal condition number estimate
+    #[serde(default)]
+    pub cholmod_method: String, // supernodal or simplicial
 }
 
 /// Holds the determinant of the coefficient matrix (if requested)
+///
+/// **Note:** All solvers report the determinant with `base = 10` and `1 ≤ |mantissa| < 10`
+/// (or `mantissa = 0`); see [StatsLinSolDeterminant::set].
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct StatsLinSolDeterminant {
     // det = mantissa * pow(base, exponent)
@@ -55,6 +60,46 @@ pub struct StatsLinSolDeterminant {
     pub exponent: f64,
 }
 
+/// Holds information about the rank of the coefficient matrix (if the null pivot detection is requested)
+///
+/// **Note:** The rank equals `ndim - null_space_dimension`. With MUMPS, the dimension of the null space
+/// is estimated by the number of null pivots.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct StatsLinSolRank {
+    pub null_pivot_detection: bool,
+    pub number_of_null_pivots: usize,
+    pub null_space_dimension: usize,
+    pub rank: usize,
+}
+
+/// Holds information about the convergence of iterative (Krylov) solvers
+///
+/// **Note:** The history holds the residual norms `‖b - A·x‖`, starting with the initial residual.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct StatsLinSolIterative {
+    pub converged: bool,
+    pub iterations: usize,
+    pub mat_vec_mul_count: usize,
+    pub tolerance: f64,     // max(rtol·‖b‖, atol)
+    pub rhs_norm: f64,      // ‖b‖
+    pub residual_norm: f64, // final ‖b - A·x‖
+    pub history: Vec<f64>,
+}
+
+/// Holds information about the iterative refinement of the solution (if requested)
+///
+/// **Note:** The history holds the backward errors `‖b - A·x‖∞ / (‖A‖∞·‖x‖∞ + ‖b‖∞)`, starting with the
+/// backward error of the solution before the refinement. Only accepted updates are recorded.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct StatsLinSolRefinement {
+    pub mixed_precision: bool, // the factorization has been computed in single precision
+    pub converged: bool,
+    pub iterations: usize,
+    pub tolerance: f64,
+    pub backward_error: f64, // final backward error
+    pub history: Vec<f64>,
+}
+
 /// Holds the computer times in human readable format (post-processed)
 ///
 /// **Note:** These are automatically converted from TimeNanoseconds when calling [StatsLinSol::get_json]
@@ -63,17 +108,25 @@ pub struct StatsLinSolTimeHuman {
     pub read_matrix: String,
     pub initialize: String,
     pub factorize: String,
+    #[serde(default)]
+    pub refactorize: String,
     pub solve: String,
     pub total_ifs: String, // initialize + factorize + solve
     pub verify: String,
 }
 
 /// Holds the computer times in nanoseconds
+///
+/// **Note:** `initialize` holds the time spent on the symbolic analysis (e.g., `analyze` or MUMPS JOB=1),
+/// `factorize` holds the time spent on the numeric factorization (e.g., `factorize_numeric` or MUMPS JOB=2),
+/// and `refactorize` holds the time spent on the last call to `refactorize`.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct StatsLinSolTimeNanoseconds {
     pub read_matrix: u128,
     pub initialize: u128,
     pub factorize: u128,
+    #[serde(default)]
+    pub refactorize: u128,
     pub solve: u128,
     pub total_ifs: u128, // initialize + factorize + solve
     pub verify: u128,
@@ -88,9 +141,52 @@ pub struct StatsLinSol {
     pub output: StatsLinSolOutput,
     pub determinant: StatsLinSolDeterminant,
     pub verify: VerifyLinSys,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_solution_error: Option<f64>, // max abs difference between x and the known solution (if given)
+    #[serde(default)]
+    pub iterative: StatsLinSolIterative,
+    #[serde(default)]
+    pub refinement: StatsLinSolRefinement,
+    #[serde(default)]
+    pub rank: StatsLinSolRank,
     pub time_human: StatsLinSolTimeHuman,
     pub time_nanoseconds: StatsLinSolTimeNanoseconds,
     pub mumps_stats: StatsLinSolMUMPS,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analysis: Option<MatrixAnalysis>, // structural and numerical properties of the matrix (if requested)
+}
+
+impl StatsLinSolDeterminant {
+    /// Sets the determinant `det = mantissa · base^exponent` using the normalized representation
+    ///
+    /// The determinant is saved with `base = 10` and `1 ≤ |mantissa| < 10` (or `mantissa = 0`),
+    /// where `|mantissa|` is the modulus of the (possibly complex) mantissa. Thus, the results
+    /// of solvers using different bases (e.g., MUMPS uses base 2) can be compared directly.
+    pub fn set(&mut self, mantissa_real: f64, mantissa_imag: f64, base: f64, exponent: f64) {
+        self.base = 10.0;
+        let modulus = f64::hypot(mantissa_real, mantissa_imag);
+        if modulus == 0.0 || !modulus.is_finite() || base <= 0.0 {
+            self.mantissa_real = if modulus.is_finite() { 0.0 } else { mantissa_real };
+            self.mantissa_imag = if modulus.is_finite() { 0.0 } else { mantissa_imag };
+            self.exponent = 0.0;
+            return;
+        }
+        let log10 = f64::log10(modulus) + exponent * f64::log10(base);
+        let mut e = f64::floor(log10);
+        let mut scale = f64::powf(10.0, log10 - e) / modulus;
+        if modulus * scale >= 10.0 {
+            scale /= 10.0;
+            e += 1.0;
+        }
+        self.mantissa_real = mantissa_real * scale;
+        self.mantissa_imag = mantissa_imag * scale;
+        self.exponent = e;
+    }
+
+    /// Returns the real part of the determinant (it may overflow or underflow)
+    pub fn value(&self) -> f64 {
+        self.mantissa_real * f64::powf(self.base, self.exponent)
+    }
 }
 
 impl StatsLinSol {
@@ -127,6 +223,7 @@ impl StatsLinSol {
                 openmp_num_threads: 0,
                 umfpack_strategy: unknown.clone(),
                 umfpack_rcond_estimate: 0.0,
+                cholmod_method: unknown.clone(),
             },
             determinant: StatsLinSolDeterminant {
                 mantissa_real: 0.0,
@@ -140,10 +237,15 @@ impl StatsLinSol {
                 max_abs_diff: 0.0,
                 relative_error: 0.0,
             },
+            known_solution_error: None,
+            iterative: StatsLinSolIterative::default(),
+            refinement: StatsLinSolRefinement::default(),
+            rank: StatsLinSolRank::default(),
             time_human: StatsLinSolTimeHuman {
                 read_matrix: String::new(),
                 initialize: String::new(),
                 factorize: String::new(),
+                refactorize: String::new(),
                 solve: String::new(),
                 total_ifs: String::new(),
                 verify: String::new(),
@@ -152,6 +254,7 @@ impl StatsLinSol {
                 read_matrix: 0,
                 initialize: 0,
                 factorize: 0,
+                refactorize: 0,
                 solve: 0,
                 total_ifs: 0,
                 verify: 0,
@@ -166,6 +269,7 @@ impl StatsLinSol {
                 condition_number1: 0.0,
                 condition_number2: 0.0,
             },
+            analysis: None,
         }
     }
 
@@ -229,6 +333,7 @@ impl StatsLinSol {
         self.time_human.read_matrix = format_nanoseconds(self.time_nanoseconds.read_matrix);
         self.time_human.initialize = format_nanoseconds(self.time_nanoseconds.initialize);
         self.time_human.factorize = format_nanoseconds(self.time_nanoseconds.factorize);
+        self.time_human.refactorize = format_nanoseconds(self.time_nanoseconds.refactorize);
         self.time_human.solve = format_nanoseconds(self.time_nanoseconds.solve);
         self.time_human.total_ifs = format_nanoseconds(self.time_nanoseconds.total_ifs);
         self.time_human.verify = format_nanoseconds(self.time_nanoseconds.verify);
@@ -239,7 +344,8 @@ impl StatsLinSol {
 
 #[cfg(test)]
 mod tests {
-    use super::StatsLinSol;
+    use super::{StatsLinSol, StatsLinSolDeterminant};
+    use russell_lab::approx_eq;
 
     #[test]
     fn derive_works() {
@@ -254,6 +360,32 @@ mod tests {
         assert_eq!(json_in.main.platform, stats.main.platform);
     }
 
+    #[test]
+    fn determinant_set_works() {
+        let mut det = StatsLinSol::new().determinant;
+        det.set(0.0, 0.0, 2.0, 5.0);
+        assert_eq!(
+            (det.mantissa_real, det.mantissa_imag, det.base, det.exponent),
+            (0.0, 0.0, 10.0, 0.0)
+        );
+        det.set(0.75, 0.0, 2.0, 10.0); // 768
+        approx_eq(det.mantissa_real, 7.68, 1e-14);
+        assert_eq!((det.base, det.exponent), (10.0, 2.0));
+        approx_eq(det.value(), 768.0, 1e-11);
+        det.set(-0.5, 0.0, 10.0, 0.0);
+        approx_eq(det.mantissa_real, -5.0, 1e-15);
+        assert_eq!(det.exponent, -1.0);
+        det.set(3.0, 4.0, 10.0, 100.0); // modulus = 5e100
+        approx_eq(det.mantissa_real, 3.0, 1e-14);
+        approx_eq(det.mantissa_imag, 4.0, 1e-14);
+        assert_eq!(det.exponent, 100.0);
+        det.set(1.0, 0.0, 2.0, -2000.0); // underflows if computed directly
+        approx_eq(det.exponent, f64::floor(-2000.0 * f64::log10(2.0)), 1e-15);
+        assert!(det.mantissa_real >= 1.0 && det.mantissa_real < 10.0);
+        let clone: StatsLinSolDeterminant = det.clone();
+        assert_eq!(clone.exponent, det.exponent);
+    }
+
     #[test]
     fn set_matrix_name_from_path_works() {
         let mut stats = StatsLinSol::new();
@@ -280,6 +412,7 @@ mod tests {
         stats.time_nanoseconds.read_matrix = ONE_SECOND;
         stats.time_nanoseconds.initialize = ONE_SECOND;
         stats.time_nanoseconds.factorize = ONE_SECOND * 2;
+        stats.time_nanoseconds.refactorize = ONE_SECOND * 5;
         stats.time_nanoseconds.solve = ONE_SECOND * 3;
         stats.time_nanoseconds.verify = ONE_SECOND * 4;
         let json = stats.get_json();
@@ -288,6 +421,7 @@ mod tests {
         assert_eq!(stats.time_human.read_matrix, "1s");
         assert_eq!(stats.time_human.initialize, "1s");
         assert_eq!(stats.time_human.factorize, "2s");
+        assert_eq!(stats.time_human.refactorize, "5s");
         assert_eq!(stats.time_human.solve, "3s");
         assert_eq!(stats.time_human.total_ifs, "6s");
         assert_eq!(stats.time_human.verify, "4s");
@@ -301,6 +435,10 @@ mod tests {
         assert_eq!(stats.matrix.name, "pre2");
         assert_eq!(stats.matrix.complex, false);
         assert_eq!(stats.matrix.symmetric, "No");
+        assert_eq!(stats.iterative.iterations, 0);
+        assert_eq!(stats.iterative.history.len(), 0);
+        assert_eq!(stats.refinement.iterations, 0);
+        assert_eq!(stats.refinement.history.len(), 0);
     }
 
     #[test]
@@ -0,0 +1,99 @@
+use russell_lab::format_nanoseconds;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write};
+
+/// Holds information about the convergence of the nonlinear system solver
+///
+/// **Note:** The history holds the residual norms `‖F(x)‖`, starting with the initial residual.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct StatsNonlin {
+    pub converged: bool,
+    pub iterations: usize,
+    pub n_function: usize,       // number of calls to F(x), including the finite differences
+    pub n_jacobian: usize,       // number of (analytical or numerical) Jacobian evaluations
+    pub n_lin_sol: usize,        // number of linear solves
+    pub n_lin_iterations: usize, // total number of Krylov iterations (Newton-Krylov)
+    pub n_backtracks: usize,     // total number of step reductions (line search)
+    pub n_rejected: usize,       // number of rejected steps (trust region)
+    pub tolerance: f64,          // max(rtol·‖F(x₀)‖, atol)
+    pub residual_norm: f64,      // final ‖F(x)‖
+    pub history: Vec<f64>,
+    pub nanos_total: u128,
+}
+
+impl StatsNonlin {
+    /// Returns a pretty formatted string with the stats
+    pub fn summary(&self) -> String {
+        let mut buffer = String::new();
+        write!(
+            &mut buffer,
+            "Converged                        = {}\n\
+             Number of iterations             = {}\n\
+             Number of function evaluations   = {}\n\
+             Number of Jacobian evaluations   = {}\n\
+             Number of linear solves          = {}\n\
+             Number of Krylov iterations      = {}\n\
+             Number of line search backtracks = {}\n\
+             Number of rejected steps         = {}\n\
+             Final residual norm              = {:.2e}",
+            self.converged,
+            self.iterations,
+            self.n_function,
+            self.n_jacobian,
+            self.n_lin_sol,
+            self.n_lin_iterations,
+            self.n_backtracks,
+            self.n_rejected,
+            self.residual_norm,
+        )
+        .unwrap();
+        buffer
+    }
+}
+
+impl fmt::Display for StatsNonlin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\n\
+             Total computation time           = {}",
+            self.summary(),
+            format_nanoseconds(self.nanos_total),
+        )
+        .unwrap();
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::StatsNonlin;
+
+    #[test]
+    fn derive_and_display_work() {
+        let mut stats = StatsNonlin::default();
+        stats.iterations = 3;
+        stats.residual_norm = 1e-12;
+        let clone = stats.clone();
+        assert!(format!("{:?}", stats).len() > 0);
+        assert_eq!(clone.iterations, 3);
+        let json = serde_json::to_string(&stats).unwrap();
+        let from_json: StatsNonlin = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_json.iterations, 3);
+        assert_eq!(
+            format!("{}", stats),
+            "Converged                        = false\n\
+             Number of iterations             = 3\n\
+             Number of function evaluations   = 0\n\
+             Number of Jacobian evaluations   = 0\n\
+             Number of linear solves          = 0\n\
+             Number of Krylov iterations      = 0\n\
+             Number of line search backtracks = 0\n\
+             Number of rejected steps         = 0\n\
+             Final residual norm              = 1.00e-12\n\
+             Total computation time           = 0ns"
+        );
+    }
+}
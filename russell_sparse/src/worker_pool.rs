@@ -0,0 +1,123 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::JoinHandle;
+
+/// Defines the job type sent to the worker threads
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Holds a set of persistent threads that run (scoped) jobs, one job per thread
+///
+/// The threads are spawned once by [WorkerPool::new] and joined by `drop`. Thus, repeated
+/// parallel operations (e.g., the matrix-vector products of an iterative solver) do not
+/// spawn threads on every call.
+pub(crate) struct WorkerPool {
+    /// Holds the channels to send the jobs (one per thread)
+    senders: Vec<Sender<Job>>,
+
+    /// Receives the completion signals (true if the job did not panic)
+    done: Receiver<bool>,
+
+    /// Holds the thread handles
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    /// Spawns the worker threads
+    pub(crate) fn new(num_threads: usize) -> Self {
+        let (done_sender, done) = channel();
+        let mut senders = Vec::with_capacity(num_threads);
+        let mut handles = Vec::with_capacity(num_threads);
+        for _ in 0..num_threads {
+            let (sender, receiver) = channel::<Job>();
+            let done_sender = done_sender.clone();
+            handles.push(std::thread::spawn(move || {
+                // the loop ends when the sender is dropped
+                for job in receiver {
+                    let success = panic::catch_unwind(AssertUnwindSafe(job)).is_ok();
+                    if done_sender.send(success).is_err() {
+                        break;
+                    }
+                }
+            }));
+            senders.push(sender);
+        }
+        WorkerPool { senders, done, handles }
+    }
+
+    /// Returns the number of threads
+    pub(crate) fn num_threads(&self) -> usize {
+        self.senders.len()
+    }
+
+    /// Runs the jobs (at most one per thread) and waits for all of them to finish
+    ///
+    /// # Panics
+    ///
+    /// 1. If there are more jobs than threads
+    /// 2. If any job panics (after all jobs have finished)
+    pub(crate) fn run<'a>(&self, jobs: Vec<Box<dyn FnOnce() + Send + 'a>>) {
+        assert!(jobs.len() <= self.senders.len(), "there are more jobs than threads");
+        let mut sent = 0;
+        for (job, sender) in jobs.into_iter().zip(&self.senders) {
+            // SAFETY: the data borrowed by the job outlives it because this function
+            // does not return (nor unwind) before all the sent jobs have finished
+            let job = unsafe { std::mem::transmute::<Box<dyn FnOnce() + Send + 'a>, Job>(job) };
+            if sender.send(job).is_err() {
+                break;
+            }
+            sent += 1;
+        }
+        let mut success = true;
+        for _ in 0..sent {
+            success &= self.done.recv().unwrap_or(false);
+        }
+        assert!(success, "a worker thread has panicked");
+    }
+}
+
+impl Drop for WorkerPool {
+    /// Stops and joins the worker threads
+    fn drop(&mut self) {
+        self.senders.clear();
+        for handle in self.handles.drain(..) {
+            handle.join().unwrap_or(());
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::WorkerPool;
+
+    #[test]
+    fn run_works() {
+        let pool = WorkerPool::new(3);
+        assert_eq!(pool.num_threads(), 3);
+        let mut data = vec![0; 6];
+        for round in 1..4 {
+            let jobs: Vec<Box<dyn FnOnce() + Send + '_>> = data
+                .chunks_mut(2)
+                .enumerate()
+                .map(|(t, chunk)| {
+                    Box::new(move || {
+                        for x in chunk.iter_mut() {
+                            *x += round * (t + 1);
+                        }
+                    }) as Box<dyn FnOnce() + Send + '_>
+                })
+                .collect();
+            pool.run(jobs);
+        }
+        assert_eq!(data, &[6, 6, 12, 12, 18, 18]);
+    }
+
+    #[test]
+    #[should_panic(expected = "a worker thread has panicked")]
+    fn run_captures_panics() {
+        let pool = WorkerPool::new(2);
+        let jobs: Vec<Box<dyn FnOnce() + Send>> = vec![Box::new(|| {}), Box::new(|| panic!("stop"))];
+        pool.run(jobs);
+    }
+}
@@ -0,0 +1,363 @@
+use super::{ComplexCscMatrix, CscMatrix, Sym};
+use crate::StrError;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs::{self, File};
+use std::io::Write as IoWrite;
+use std::path::Path;
+
+/// Number of real values per line (each with width 26)
+const RB_VALUES_PER_LINE: usize = 3;
+
+/// Computes the lower-triangle (if symmetric) column-compressed structure to be written
+///
+/// Returns `(col_pointers, row_indices, positions)` (zero-based) where `positions` holds the
+/// positions of the written values in the original arrays.
+fn rb_structure(
+    symmetric: Sym,
+    ncol: usize,
+    col_pointers: &[i32],
+    row_indices: &[i32],
+) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+    // collect the triplets (target column, row, position) in the order of the source columns
+    let mut triplets = Vec::new();
+    for j in 0..ncol {
+        for p in col_pointers[j]..col_pointers[j + 1] {
+            let i = row_indices[p as usize] as usize;
+            match symmetric {
+                Sym::No | Sym::YesLower => triplets.push((j, i, p as usize)),
+                Sym::YesUpper => triplets.push((i, j, p as usize)), // transpose to the lower triangle
+                Sym::YesFull => {
+                    if i >= j {
+                        triplets.push((j, i, p as usize));
+                    }
+                }
+            }
+        }
+    }
+
+    // counting sort by target column (stable; thus, the rows remain sorted)
+    let mut pointers = vec![0; ncol + 1];
+    for (j, _, _) in &triplets {
+        pointers[*j + 1] += 1;
+    }
+    for j in 0..ncol {
+        pointers[j + 1] += pointers[j];
+    }
+    let nnz = triplets.len();
+    let mut rows = vec![0; nnz];
+    let mut positions = vec![0; nnz];
+    let mut next = pointers.clone();
+    for (j, i, p) in triplets {
+        rows[next[j]] = i;
+        positions[next[j]] = p;
+        next[j] += 1;
+    }
+    (pointers, rows, positions)
+}
+
+/// Formats a real number as a Fortran `1PE26.16` field
+fn fortran_real(value: f64) -> String {
+    let s = format!("{:.16E}", value);
+    let (mantissa, exponent) = s.split_at(s.find('E').unwrap()); // unwrap OK because of the E format
+    let e: i32 = exponent[1..].parse().unwrap(); // unwrap OK because of the E format
+    let sign = if e < 0 { '-' } else { '+' };
+    format!("{:>26}", format!("{}E{}{:02}", mantissa, sign, e.abs()))
+}
+
+/// Writes integers (one-based) in a fixed-width format; returns the number of lines
+fn write_ints(buffer: &mut String, data: &[usize], per_line: usize, width: usize) -> usize {
+    let mut lines = 0;
+    for chunk in data.chunks(per_line) {
+        for v in chunk {
+            write!(buffer, "{:>w$}", v + 1, w = width).unwrap();
+        }
+        buffer.push('\n');
+        lines += 1;
+    }
+    lines
+}
+
+/// Writes a Rutherford-Boeing file given the structure and the (flattened) values
+fn write_rb_file(
+    full_path: &Path,
+    title: &str,
+    key: &str,
+    mxtype: &str,
+    nrow: usize,
+    ncol: usize,
+    pointers: &[usize],
+    rows: &[usize],
+    values: Option<&[f64]>,
+) -> Result<(), StrError> {
+    // integer formats
+    let nnz = rows.len();
+    let width = format!("{}", usize::max(nnz + 1, nrow)).len() + 1;
+    let per_line = usize::max(80 / width, 1);
+    let int_fmt = format!("({}I{})", per_line, width);
+
+    // data
+    let mut data = String::new();
+    let ptrcrd = write_ints(&mut data, pointers, per_line, width);
+    let indcrd = write_ints(&mut data, rows, per_line, width);
+    let mut valcrd = 0;
+    if let Some(vals) = values {
+        for chunk in vals.chunks(RB_VALUES_PER_LINE) {
+            for v in chunk {
+                data.push_str(&fortran_real(*v));
+            }
+            data.push('\n');
+            valcrd += 1;
+        }
+    }
+    let val_fmt = if values.is_some() {
+        format!("(1P,{}E26.16)", RB_VALUES_PER_LINE)
+    } else {
+        String::new()
+    };
+
+    // header
+    let title: String = title.chars().take(72).collect();
+    let key: String = key.chars().take(8).collect();
+    let mut buffer = String::new();
+    write!(&mut buffer, "{:<72}{:<8}\n", title, key).unwrap();
+    write!(
+        &mut buffer,
+        "{:>14}{:>14}{:>14}{:>14}\n",
+        ptrcrd + indcrd + valcrd,
+        ptrcrd,
+        indcrd,
+        valcrd
+    )
+    .unwrap();
+    write!(
+        &mut buffer,
+        "{}{:11}{:>14}{:>14}{:>14}{:>14}\n",
+        mxtype, "", nrow, ncol, nnz, 0
+    )
+    .unwrap();
+    write!(&mut buffer, "{:<16}{:<16}{:<20}\n", int_fmt, int_fmt, val_fmt).unwrap();
+    buffer.push_str(&data);
+
+    // create directory
+    if let Some(p) = full_path.parent() {
+        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    }
+
+    // write file
+    let mut file = File::create(full_path).map_err(|_| "cannot create file")?;
+    file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+    // force sync
+    file.sync_all().map_err(|_| "cannot sync file")?;
+    Ok(())
+}
+
+/// Returns the second character of the matrix type
+fn rb_structure_type(symmetric: Sym, nrow: usize, ncol: usize) -> char {
+    if symmetric != Sym::No {
+        'S'
+    } else if nrow != ncol {
+        'R'
+    } else {
+        'U'
+    }
+}
+
+impl CscMatrix {
+    /// Writes a Rutherford-Boeing file
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    /// * `title` -- the title (up to 72 characters)
+    /// * `key` -- the key (up to 8 characters)
+    /// * `pattern` -- writes the pattern only (type `P`) without the values
+    ///
+    /// # Notes
+    ///
+    /// 1. If the matrix is symmetric, only the lower triangle + diagonal will be written (type `RSA` or `PSA`)
+    /// 2. The values are written with 17 significant digits; thus, the round trip is exact
+    /// 3. The file can also be read as a Harwell-Boeing file (the number of right-hand side lines is blank)
+    ///
+    /// See also [crate::read_rutherford_boeing()]
+    pub fn write_rutherford_boeing<P>(
+        &self,
+        full_path: &P,
+        title: &str,
+        key: &str,
+        pattern: bool,
+    ) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let (pointers, rows, positions) =
+            rb_structure(self.symmetric, self.ncol, &self.col_pointers, &self.row_indices);
+        let values: Vec<f64> = positions.iter().map(|p| self.values[*p]).collect();
+        let mxtype = format!(
+            "{}{}A",
+            if pattern { 'P' } else { 'R' },
+            rb_structure_type(self.symmetric, self.nrow, self.ncol)
+        );
+        let vals = if pattern { None } else { Some(values.as_slice()) };
+        write_rb_file(
+            Path::new(full_path),
+            title,
+            key,
+            &mxtype,
+            self.nrow,
+            self.ncol,
+            &pointers,
+            &rows,
+            vals,
+        )
+    }
+}
+
+impl ComplexCscMatrix {
+    /// Writes a Rutherford-Boeing file
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    /// * `title` -- the title (up to 72 characters)
+    /// * `key` -- the key (up to 8 characters)
+    /// * `pattern` -- writes the pattern only (type `P`) without the values
+    ///
+    /// # Notes
+    ///
+    /// 1. If the matrix is symmetric, only the lower triangle + diagonal will be written (type `CSA` or `PSA`)
+    /// 2. The values are written with 17 significant digits; thus, the round trip is exact
+    /// 3. The file can also be read as a Harwell-Boeing file (the number of right-hand side lines is blank)
+    ///
+    /// See also [crate::read_rutherford_boeing()]
+    pub fn write_rutherford_boeing<P>(
+        &self,
+        full_path: &P,
+        title: &str,
+        key: &str,
+        pattern: bool,
+    ) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let (pointers, rows, positions) =
+            rb_structure(self.symmetric, self.ncol, &self.col_pointers, &self.row_indices);
+        let mut values = Vec::with_capacity(2 * positions.len());
+        for p in &positions {
+            values.push(self.values[*p].re);
+            values.push(self.values[*p].im);
+        }
+        let mxtype = format!(
+            "{}{}A",
+            if pattern { 'P' } else { 'C' },
+            rb_structure_type(self.symmetric, self.nrow, self.ncol)
+        );
+        let vals = if pattern { None } else { Some(values.as_slice()) };
+        write_rb_file(
+            Path::new(full_path),
+            title,
+            key,
+            &mxtype,
+            self.nrow,
+            self.ncol,
+            &pointers,
+            &rows,
+            vals,
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_rutherford_boeing, ComplexCooMatrix, CooMatrix, MMsym, Samples};
+    use russell_lab::{cpx, Complex64};
+
+    #[test]
+    fn fortran_real_works() {
+        assert_eq!(fortran_real(2.0), "    2.0000000000000000E+00");
+        assert_eq!(fortran_real(-1.5e-123), "  -1.4999999999999999E-123");
+    }
+
+    #[test]
+    fn csc_write_rutherford_boeing_works() {
+        //  2  3  .  .  .
+        //  3  .  4  .  6
+        //  . -1 -3  2  .
+        //  .  .  1  .  .
+        //  .  4  2  .  1
+        let (_, csc, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let full_path = "/tmp/russell_sparse/test_write_rutherford_boeing_csc.rua";
+        csc.write_rutherford_boeing(full_path, "Sample matrix", "SAMPLE", true)
+            .unwrap();
+        let contents = fs::read_to_string(full_path).map_err(|_| "cannot open file").unwrap();
+        let lines: Vec<_> = contents.lines().collect();
+        assert_eq!(lines[0].trim_end(), format!("{:<72}SAMPLE", "Sample matrix"));
+        assert_eq!(lines[1], "             2             1             1             0");
+        assert_eq!(
+            lines[2],
+            "PUA                        5             5            12             0"
+        );
+        assert_eq!(lines[3].trim_end(), "(26I3)          (26I3)");
+        assert_eq!(lines[4], "  1  3  6 10 11 13");
+        assert_eq!(lines[5], "  1  2  1  3  5  2  3  4  5  3  2  5");
+
+        // round trip
+        csc.write_rutherford_boeing(full_path, "Sample matrix", "SAMPLE", false)
+            .unwrap();
+        let (coo, _) = read_rutherford_boeing(full_path, MMsym::LeaveAsLower).unwrap();
+        assert_eq!(coo.unwrap().as_dense().as_data(), csc.as_dense().as_data());
+    }
+
+    #[test]
+    fn csc_write_rutherford_boeing_sym_works() {
+        // 2  1  .
+        // 1  2  1
+        // .  1  2
+        for sym in [Sym::YesFull, Sym::YesLower, Sym::YesUpper] {
+            let mut coo = CooMatrix::new(3, 3, 7, sym).unwrap();
+            for i in 0..3 {
+                coo.put(i, i, 2.0).unwrap();
+            }
+            for (i, j) in [(1, 0), (2, 1)] {
+                match sym {
+                    Sym::YesFull => {
+                        coo.put(i, j, 1.0).unwrap();
+                        coo.put(j, i, 1.0).unwrap();
+                    }
+                    Sym::YesLower => coo.put(i, j, 1.0).unwrap(),
+                    _ => coo.put(j, i, 1.0).unwrap(),
+                }
+            }
+            let csc = CscMatrix::from_coo(&coo).unwrap();
+            let full_path = "/tmp/russell_sparse/test_write_rutherford_boeing_csc_sym.rsa";
+            csc.write_rutherford_boeing(full_path, "", "", false).unwrap();
+            let contents = fs::read_to_string(full_path).map_err(|_| "cannot open file").unwrap();
+            assert!(contents.lines().nth(2).unwrap().starts_with("RSA"));
+            let (res, _) = read_rutherford_boeing(full_path, MMsym::LeaveAsLower).unwrap();
+            let res = res.unwrap();
+            assert_eq!(res.indices_i, &[0, 1, 1, 2, 2]);
+            assert_eq!(res.indices_j, &[0, 0, 1, 1, 2]);
+            assert_eq!(res.values, &[2.0, 1.0, 2.0, 1.0, 2.0]);
+        }
+    }
+
+    #[test]
+    fn complex_csc_write_rutherford_boeing_works() {
+        let mut coo = ComplexCooMatrix::new(2, 3, 3, Sym::No).unwrap();
+        coo.put(0, 0, cpx!(1.0, 0.1)).unwrap();
+        coo.put(1, 1, cpx!(2.0, -0.2)).unwrap();
+        coo.put(0, 2, cpx!(3.0, 1.0 / 3.0)).unwrap();
+        let csc = ComplexCscMatrix::from_coo(&coo).unwrap();
+        let full_path = "/tmp/russell_sparse/test_write_rutherford_boeing_complex_csc.cra";
+        csc.write_rutherford_boeing(full_path, "Complex", "CPX", false).unwrap();
+        let contents = fs::read_to_string(full_path).map_err(|_| "cannot open file").unwrap();
+        assert!(contents.lines().nth(2).unwrap().starts_with("CRA"));
+        let (_, res) = read_rutherford_boeing(full_path, MMsym::LeaveAsLower).unwrap();
+        let res = res.unwrap();
+        assert_eq!(res.as_dense().as_data(), csc.as_dense().as_data());
+    }
+}
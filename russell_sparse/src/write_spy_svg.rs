@@ -0,0 +1,766 @@
+use super::{ComplexCscMatrix, CscMatrix, Sym};
+use crate::StrError;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs::{self, File};
+use std::io::Write as IoWrite;
+use std::path::Path;
+
+/// Holds the parameters of the sparsity pattern plots (spy plots) written by `write_spy_svg`
+///
+/// See [CscMatrix::write_spy_svg()] and [ComplexCscMatrix::write_spy_svg()]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpyParams {
+    /// Maximum number of cells along the largest dimension of the matrix
+    ///
+    /// If the matrix is larger, the entries are aggregated (downsampled) into cells
+    /// of `f × f` entries, with `f = ceil(max(nrow, ncol) / max_cells)`, and each
+    /// cell displays the largest magnitude among its entries.
+    pub max_cells: usize,
+
+    /// Size (in pixels) of the largest dimension of the drawing area
+    pub size: f64,
+
+    /// Colors the entries by the magnitude (log10 scale); otherwise, all entries are black
+    pub color_by_magnitude: bool,
+
+    /// Shows the fill-in of the factorization
+    ///
+    /// The fill-in is computed by the symbolic Cholesky factorization (without pivoting) of the
+    /// pattern of `A + Aᵀ`, which corresponds to the LU factorization of structurally symmetric matrices.
+    /// The fill-in entries are drawn in red and counted in the caption. Thus, the effect of an ordering
+    /// can be inspected by comparing the plots of A and of the permuted matrix (see [SpyParams::permutation]).
+    ///
+    /// **Note:** This option requires a square matrix.
+    pub fill_in: bool,
+
+    /// Holds the symmetric permutation applied to rows and columns before plotting
+    ///
+    /// The entry `a[perm[i]][perm[j]]` is drawn at the position `(i, j)`, as in [crate::csr_permute_symmetric()];
+    /// e.g., `perm` may be computed by [crate::ordering_amd()] or [crate::ordering_rcm()].
+    ///
+    /// **Note:** This option requires a square matrix.
+    pub permutation: Option<Vec<usize>>,
+
+    /// Holds the sizes of the row blocks to draw the block boundaries (empty => no boundaries)
+    ///
+    /// The sum of the sizes must equal the number of rows. The boundaries refer to the
+    /// positions in the plot (i.e., after the permutation, if any).
+    pub row_blocks: Vec<usize>,
+
+    /// Holds the sizes of the column blocks to draw the block boundaries (empty => no boundaries)
+    ///
+    /// The sum of the sizes must equal the number of columns.
+    pub col_blocks: Vec<usize>,
+
+    /// Holds the title; the dimensions, the number of non-zeros, and the fill-in are appended
+    pub title: String,
+}
+
+impl SpyParams {
+    /// Allocates a new instance with default values
+    pub fn new() -> Self {
+        SpyParams {
+            max_cells: 512,
+            size: 600.0,
+            color_by_magnitude: true,
+            fill_in: false,
+            permutation: None,
+            row_blocks: Vec::new(),
+            col_blocks: Vec::new(),
+            title: String::new(),
+        }
+    }
+}
+
+impl CscMatrix {
+    /// Writes an SVG file with the sparsity pattern (spy plot) of the matrix
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    /// * `params` -- the parameters of the plot; None => use default
+    ///
+    /// # Notes
+    ///
+    /// 1. If the matrix is symmetric with one triangle stored, the full matrix is drawn.
+    /// 2. The entries are colored by the magnitude in a log10 scale (see [SpyParams::color_by_magnitude])
+    ///    and huge matrices are downsampled (see [SpyParams::max_cells]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use russell_sparse::prelude::*;
+    /// use russell_sparse::StrError;
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // arrow matrix: the natural ordering yields a full factor
+    ///     let n = 6;
+    ///     let mut coo = CooMatrix::new(n, n, 3 * n - 2, Sym::No)?;
+    ///     for i in 0..n {
+    ///         coo.put(i, i, 4.0)?;
+    ///         if i > 0 {
+    ///             coo.put(0, i, 1.0)?;
+    ///             coo.put(i, 0, 1.0)?;
+    ///         }
+    ///     }
+    ///     let csc = CscMatrix::from_coo(&coo)?;
+    ///
+    ///     // the fill-in is shown in red
+    ///     let mut params = SpyParams::new();
+    ///     params.fill_in = true;
+    ///     csc.write_spy_svg("/tmp/russell_sparse/doc_spy_arrow.svg", Some(&params))?;
+    ///
+    ///     // reversing the ordering eliminates the fill-in
+    ///     params.permutation = Some((0..n).rev().collect());
+    ///     csc.write_spy_svg("/tmp/russell_sparse/doc_spy_arrow_reversed.svg", Some(&params))?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_spy_svg<P>(&self, full_path: &P, params: Option<&SpyParams>) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let default = SpyParams::new();
+        let par = params.unwrap_or(&default);
+        let buffer = spy_svg(
+            self.nrow,
+            self.ncol,
+            self.symmetric,
+            &self.col_pointers,
+            &self.row_indices,
+            |p| f64::abs(self.values[p]),
+            par,
+        )?;
+        write_file(full_path, &buffer)
+    }
+}
+
+impl ComplexCscMatrix {
+    /// Writes an SVG file with the sparsity pattern (spy plot) of the matrix
+    ///
+    /// The entries are colored by the modulus of the complex numbers.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    /// * `params` -- the parameters of the plot; None => use default
+    ///
+    /// See also [CscMatrix::write_spy_svg()]
+    pub fn write_spy_svg<P>(&self, full_path: &P, params: Option<&SpyParams>) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let default = SpyParams::new();
+        let par = params.unwrap_or(&default);
+        let buffer = spy_svg(
+            self.nrow,
+            self.ncol,
+            self.symmetric,
+            &self.col_pointers,
+            &self.row_indices,
+            |p| self.values[p].norm(),
+            par,
+        )?;
+        write_file(full_path, &buffer)
+    }
+}
+
+/// Marks an empty cell
+const EMPTY: f64 = -1.0;
+
+/// Holds the margin around the drawing area (pixels)
+const MARGIN: f64 = 10.0;
+
+/// Holds the height of the caption area (pixels)
+const CAPTION: f64 = 30.0;
+
+/// Holds the width of the colorbar area (pixels)
+const COLORBAR: f64 = 70.0;
+
+/// Holds the colors of the colormap (from the smallest to the largest magnitude)
+const COLORMAP: [(f64, f64, f64); 5] = [
+    (68.0, 1.0, 84.0),
+    (59.0, 82.0, 139.0),
+    (33.0, 145.0, 140.0),
+    (94.0, 201.0, 98.0),
+    (253.0, 231.0, 37.0),
+];
+
+/// Holds the color of the fill-in entries
+const FILL_IN_COLOR: &str = "#d62728";
+
+/// Holds the color of the block boundaries
+const BLOCK_COLOR: &str = "#1f77b4";
+
+/// Generates the SVG code of the spy plot
+fn spy_svg<F>(
+    nrow: usize,
+    ncol: usize,
+    symmetric: Sym,
+    col_pointers: &[i32],
+    row_indices: &[i32],
+    magnitude: F,
+    par: &SpyParams,
+) -> Result<String, StrError>
+where
+    F: Fn(usize) -> f64,
+{
+    // check
+    if par.max_cells < 1 {
+        return Err("max_cells must be ≥ 1");
+    }
+    if par.size <= 0.0 {
+        return Err("the size of the drawing area must be > 0");
+    }
+    if (par.fill_in || par.permutation.is_some()) && nrow != ncol {
+        return Err("the fill-in and the permutation require a square matrix");
+    }
+    if !par.row_blocks.is_empty() && par.row_blocks.iter().sum::<usize>() != nrow {
+        return Err("the sum of the row block sizes must equal the number of rows");
+    }
+    if !par.col_blocks.is_empty() && par.col_blocks.iter().sum::<usize>() != ncol {
+        return Err("the sum of the column block sizes must equal the number of columns");
+    }
+
+    // inverse permutation: new position of the old index
+    let position = match &par.permutation {
+        Some(perm) => {
+            if perm.len() != nrow {
+                return Err("the permutation must have length equal to the dimension of the matrix");
+            }
+            let mut inv = vec![usize::MAX; nrow];
+            for (new, &old) in perm.iter().enumerate() {
+                if old >= nrow || inv[old] != usize::MAX {
+                    return Err("the permutation is invalid");
+                }
+                inv[old] = new;
+            }
+            inv
+        }
+        None => (0..nrow).collect(),
+    };
+    let pos_col = |j: usize| if par.permutation.is_some() { position[j] } else { j };
+
+    // collect the entries at their positions (mirroring the triangular storage)
+    let mirror = symmetric.triangular();
+    let mut entries = Vec::new();
+    let mut nnz_pattern = 0;
+    for j in 0..ncol {
+        for p in (col_pointers[j] as usize)..(col_pointers[j + 1] as usize) {
+            let i = row_indices[p] as usize;
+            let m = magnitude(p);
+            entries.push((position[i], pos_col(j), m));
+            nnz_pattern += 1;
+            if mirror && i != j {
+                entries.push((position[j], pos_col(i), m));
+                nnz_pattern += 1;
+            }
+        }
+    }
+
+    // downsampling
+    let dim = usize::max(nrow, ncol);
+    let factor = usize::max(1, dim.div_ceil(par.max_cells));
+    let (nr, nc) = (nrow.div_ceil(factor), ncol.div_ceil(factor));
+    let mut cells = vec![EMPTY; nr * nc];
+    let (mut min_mag, mut max_mag) = (f64::MAX, 0.0);
+    for &(i, j, m) in &entries {
+        let k = (i / factor) * nc + j / factor;
+        cells[k] = f64::max(cells[k], m);
+        if m > 0.0 {
+            min_mag = f64::min(min_mag, m);
+            max_mag = f64::max(max_mag, m);
+        }
+    }
+
+    // fill-in
+    let mut fill_cells = vec![false; if par.fill_in { nr * nc } else { 0 }];
+    let mut nnz_fill = 0;
+    if par.fill_in {
+        for (i, j) in symbolic_fill_in(nrow, &entries) {
+            nnz_fill += 2; // L and U (or Lᵀ)
+            for (r, c) in [(i, j), (j, i)] {
+                let k = (r / factor) * nc + c / factor;
+                if cells[k] == EMPTY {
+                    fill_cells[k] = true;
+                }
+            }
+        }
+    }
+
+    // geometry
+    let cell = par.size / usize::max(nr, nc) as f64;
+    let (width, height) = (nc as f64 * cell, nr as f64 * cell);
+    let extra = if par.color_by_magnitude && max_mag > 0.0 {
+        COLORBAR
+    } else {
+        0.0
+    };
+    let total_width = width + 2.0 * MARGIN + extra;
+    let total_height = height + 2.0 * MARGIN + CAPTION;
+    let (x0, y0) = (MARGIN, MARGIN + CAPTION);
+
+    // header
+    let mut buffer = String::new();
+    writeln!(
+        &mut buffer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+        total_width, total_height, total_width, total_height
+    )
+    .unwrap();
+    writeln!(&mut buffer, "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>").unwrap();
+
+    // caption
+    let mut caption = par.title.clone();
+    if !caption.is_empty() {
+        caption.push_str(": ");
+    }
+    write!(&mut caption, "{} × {}, nnz = {}", nrow, ncol, nnz_pattern).unwrap();
+    if par.fill_in {
+        write!(&mut caption, ", fill-in = {}", nnz_fill).unwrap();
+    }
+    if factor > 1 {
+        write!(&mut caption, " ({}×{} entries per cell)", factor, factor).unwrap();
+    }
+    let caption = caption.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    writeln!(
+        &mut buffer,
+        "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"14\">{}</text>",
+        MARGIN,
+        MARGIN + 14.0,
+        caption
+    )
+    .unwrap();
+
+    // cells
+    let (log_min, log_max) = if max_mag > 0.0 {
+        (f64::log10(min_mag), f64::log10(max_mag))
+    } else {
+        (0.0, 0.0)
+    };
+    writeln!(&mut buffer, "<g shape-rendering=\"crispEdges\">").unwrap();
+    for r in 0..nr {
+        for c in 0..nc {
+            let k = r * nc + c;
+            let color = if cells[k] != EMPTY {
+                if par.color_by_magnitude && max_mag > 0.0 {
+                    let t = if cells[k] > 0.0 && log_max > log_min {
+                        (f64::log10(cells[k]) - log_min) / (log_max - log_min)
+                    } else if cells[k] > 0.0 {
+                        1.0
+                    } else {
+                        0.0
+                    };
+                    colormap(t)
+                } else {
+                    "#000000".to_string()
+                }
+            } else if par.fill_in && fill_cells[k] {
+                FILL_IN_COLOR.to_string()
+            } else {
+                continue;
+            };
+            writeln!(
+                &mut buffer,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                x0 + c as f64 * cell,
+                y0 + r as f64 * cell,
+                cell,
+                cell,
+                color
+            )
+            .unwrap();
+        }
+    }
+    writeln!(&mut buffer, "</g>").unwrap();
+
+    // block boundaries
+    let mut start = 0;
+    for size in par.row_blocks.iter().take(usize::max(1, par.row_blocks.len()) - 1) {
+        start += size;
+        let y = y0 + start as f64 / factor as f64 * cell;
+        writeln!(
+            &mut buffer,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-dasharray=\"4 2\"/>",
+            x0,
+            y,
+            x0 + width,
+            y,
+            BLOCK_COLOR
+        )
+        .unwrap();
+    }
+    let mut start = 0;
+    for size in par.col_blocks.iter().take(usize::max(1, par.col_blocks.len()) - 1) {
+        start += size;
+        let x = x0 + start as f64 / factor as f64 * cell;
+        writeln!(
+            &mut buffer,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-dasharray=\"4 2\"/>",
+            x,
+            y0,
+            x,
+            y0 + height,
+            BLOCK_COLOR
+        )
+        .unwrap();
+    }
+
+    // frame
+    writeln!(
+        &mut buffer,
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"black\"/>",
+        x0, y0, width, height
+    )
+    .unwrap();
+
+    // colorbar
+    if extra > 0.0 {
+        let (bx, bw) = (x0 + width + 10.0, 15.0);
+        writeln!(
+            &mut buffer,
+            "<defs><linearGradient id=\"colormap\" x1=\"0\" y1=\"1\" x2=\"0\" y2=\"0\">"
+        )
+        .unwrap();
+        for (s, _) in COLORMAP.iter().enumerate() {
+            let t = s as f64 / (COLORMAP.len() - 1) as f64;
+            writeln!(&mut buffer, "<stop offset=\"{}\" stop-color=\"{}\"/>", t, colormap(t)).unwrap();
+        }
+        writeln!(&mut buffer, "</linearGradient></defs>").unwrap();
+        writeln!(
+            &mut buffer,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"url(#colormap)\" stroke=\"black\"/>",
+            bx, y0, bw, height
+        )
+        .unwrap();
+        for (label, y) in [(max_mag, y0 + 10.0), (min_mag, y0 + height)] {
+            writeln!(
+                &mut buffer,
+                "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"10\">{:.1e}</text>",
+                bx + bw + 3.0,
+                y,
+                label
+            )
+            .unwrap();
+        }
+    }
+    writeln!(&mut buffer, "</svg>").unwrap();
+    Ok(buffer)
+}
+
+/// Returns the color corresponding to t ∈ [0, 1]
+fn colormap(t: f64) -> String {
+    let t = t.clamp(0.0, 1.0);
+    let n = COLORMAP.len() - 1;
+    let s = usize::min(n - 1, (t * n as f64) as usize);
+    let w = t * n as f64 - s as f64;
+    let (a, b) = (COLORMAP[s], COLORMAP[s + 1]);
+    let r = a.0 + w * (b.0 - a.0);
+    let g = a.1 + w * (b.1 - a.1);
+    let b = a.2 + w * (b.2 - a.2);
+    format!("#{:02x}{:02x}{:02x}", r.round() as u8, g.round() as u8, b.round() as u8)
+}
+
+/// Computes the fill-in entries `(i, j)` with `i > j` of the symbolic Cholesky factorization of the pattern of A + Aᵀ
+///
+/// The algorithm computes the elimination tree and then, for each row k, the reach of the
+/// pattern of the k-th row in the elimination tree (i.e., the pattern of the k-th row of L).
+fn symbolic_fill_in(n: usize, entries: &[(usize, usize, f64)]) -> Vec<(usize, usize)> {
+    // pattern of the strictly lower triangle of A + Aᵀ by rows
+    let mut lower: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for &(i, j, _) in entries {
+        if i > j {
+            lower[i].push(j);
+        } else if j > i {
+            lower[j].push(i);
+        }
+    }
+    for row in lower.iter_mut() {
+        row.sort_unstable();
+        row.dedup();
+    }
+
+    // elimination tree (with path compression)
+    const NONE: usize = usize::MAX;
+    let mut parent = vec![NONE; n];
+    let mut ancestor = vec![NONE; n];
+    for (k, row) in lower.iter().enumerate() {
+        for &j in row {
+            let mut i = j;
+            while i != NONE && i < k {
+                let next = ancestor[i];
+                ancestor[i] = k;
+                if next == NONE {
+                    parent[i] = k;
+                }
+                i = next;
+            }
+        }
+    }
+
+    // row patterns of L
+    let mut fill = Vec::new();
+    let mut mark = vec![NONE; n];
+    for (k, row) in lower.iter().enumerate() {
+        mark[k] = k;
+        for &j in row {
+            mark[j] = k; // original entry
+        }
+        for &j in row {
+            let mut i = parent[j];
+            while i != NONE && i < k && mark[i] != k {
+                fill.push((k, i));
+                mark[i] = k;
+                i = parent[i];
+            }
+        }
+    }
+    fill
+}
+
+/// Writes the buffer to a file, creating the directory if needed
+fn write_file<P>(full_path: &P, buffer: &str) -> Result<(), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    // create directory
+    let path = Path::new(full_path);
+    if let Some(p) = path.parent() {
+        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    }
+
+    // write file
+    let mut file = File::create(path).map_err(|_| "cannot create file")?;
+    file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+    // force sync
+    file.sync_all().map_err(|_| "cannot sync file")?;
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CooMatrix, Samples};
+
+    /// Returns the (n,n) arrow matrix with the dense first row and column
+    fn arrow(n: usize) -> CscMatrix {
+        let mut coo = CooMatrix::new(n, n, 3 * n - 2, Sym::No).unwrap();
+        for i in 0..n {
+            coo.put(i, i, 4.0).unwrap();
+            if i > 0 {
+                coo.put(0, i, 1.0).unwrap();
+                coo.put(i, 0, 1.0).unwrap();
+            }
+        }
+        CscMatrix::from_coo(&coo).unwrap()
+    }
+
+    #[test]
+    fn spy_params_new_works() {
+        let params = SpyParams::new();
+        assert_eq!(params.max_cells, 512);
+        assert_eq!(params.size, 600.0);
+        assert!(params.color_by_magnitude);
+        assert!(!params.fill_in);
+        assert_eq!(params.permutation, None);
+        assert!(params.row_blocks.is_empty() && params.col_blocks.is_empty());
+        assert_eq!(params.title, "");
+    }
+
+    #[test]
+    fn write_spy_svg_captures_errors() {
+        let (_, csc, _, _) = Samples::rectangular_3x4();
+        let path = "/tmp/russell_sparse/test_write_spy_svg_errors.svg";
+        let mut params = SpyParams::new();
+        params.max_cells = 0;
+        assert_eq!(
+            csc.write_spy_svg(path, Some(&params)).err(),
+            Some("max_cells must be ≥ 1")
+        );
+        params.max_cells = 10;
+        params.size = 0.0;
+        assert_eq!(
+            csc.write_spy_svg(path, Some(&params)).err(),
+            Some("the size of the drawing area must be > 0")
+        );
+        params.size = 100.0;
+        params.fill_in = true;
+        assert_eq!(
+            csc.write_spy_svg(path, Some(&params)).err(),
+            Some("the fill-in and the permutation require a square matrix")
+        );
+        params.fill_in = false;
+        params.row_blocks = vec![1, 1];
+        assert_eq!(
+            csc.write_spy_svg(path, Some(&params)).err(),
+            Some("the sum of the row block sizes must equal the number of rows")
+        );
+        params.row_blocks = vec![1, 2];
+        params.col_blocks = vec![5];
+        assert_eq!(
+            csc.write_spy_svg(path, Some(&params)).err(),
+            Some("the sum of the column block sizes must equal the number of columns")
+        );
+        let csc = arrow(3);
+        let mut params = SpyParams::new();
+        params.permutation = Some(vec![0, 1]);
+        assert_eq!(
+            csc.write_spy_svg(path, Some(&params)).err(),
+            Some("the permutation must have length equal to the dimension of the matrix")
+        );
+        params.permutation = Some(vec![0, 1, 1]);
+        assert_eq!(
+            csc.write_spy_svg(path, Some(&params)).err(),
+            Some("the permutation is invalid")
+        );
+    }
+
+    #[test]
+    fn write_spy_svg_works() {
+        //  2  3  .  .  .
+        //  3  .  4  .  6
+        //  . -1 -3  2  .
+        //  .  .  1  .  .
+        //  .  4  2  .  1
+        let (_, csc, _, _) = Samples::umfpack_unsymmetric_5x5();
+        let full_path = "/tmp/russell_sparse/test_write_spy_svg.svg";
+        let mut params = SpyParams::new();
+        params.size = 50.0;
+        params.title = "A".to_string();
+        params.row_blocks = vec![2, 3];
+        params.col_blocks = vec![2, 3];
+        csc.write_spy_svg(full_path, Some(&params)).unwrap();
+        let contents = fs::read_to_string(full_path).unwrap();
+        assert!(contents.starts_with("<svg"));
+        assert!(contents.ends_with("</svg>\n"));
+        assert!(contents.contains(">A: 5 × 5, nnz = 12</text>"));
+        assert_eq!(contents.matches("width=\"10\" height=\"10\"").count(), 12);
+        assert_eq!(contents.matches("<line x1").count(), 2);
+        assert!(contents.contains("<line x1=\"10\" y1=\"60\" x2=\"60\" y2=\"60\""));
+        assert!(contents.contains("linearGradient"));
+        // the smallest magnitude (1) has the first color, the largest (6) has the last color
+        assert!(contents.contains("<rect x=\"50\" y=\"50\" width=\"10\" height=\"10\" fill=\"#fde725\"/>"));
+        assert!(contents.contains("<rect x=\"30\" y=\"70\" width=\"10\" height=\"10\" fill=\"#440154\"/>"));
+
+        // black and white
+        params.color_by_magnitude = false;
+        csc.write_spy_svg(full_path, Some(&params)).unwrap();
+        let contents = fs::read_to_string(full_path).unwrap();
+        assert_eq!(contents.matches("fill=\"#000000\"").count(), 12);
+        assert!(!contents.contains("linearGradient"));
+    }
+
+    #[test]
+    fn write_spy_svg_works_symmetric_and_complex() {
+        //  2  -1
+        // -1   2  -1
+        //     -1   2
+        let (_, csc, _, _) = Samples::positive_definite_3x3_lower();
+        let full_path = "/tmp/russell_sparse/test_write_spy_svg_sym.svg";
+        csc.write_spy_svg(full_path, None).unwrap();
+        let contents = fs::read_to_string(full_path).unwrap();
+        assert!(contents.contains("3 × 3, nnz = 7</text>"));
+        assert_eq!(contents.matches("width=\"200\" height=\"200\"").count(), 7);
+
+        // 4+4i    .     2+2i
+        //  .      1     3+3i
+        //  .     5+5i   1+1i
+        //  1      .      .
+        let (_, csc, _, _) = Samples::complex_rectangular_4x3();
+        let full_path = "/tmp/russell_sparse/test_write_spy_svg_complex.svg";
+        csc.write_spy_svg(full_path, None).unwrap();
+        let contents = fs::read_to_string(full_path).unwrap();
+        assert!(contents.contains("4 × 3, nnz = 7</text>"));
+        assert_eq!(contents.matches("width=\"150\" height=\"150\"").count(), 7);
+    }
+
+    #[test]
+    fn write_spy_svg_shows_fill_in_and_permutation() {
+        let n = 5;
+        let csc = arrow(n);
+        let full_path = "/tmp/russell_sparse/test_write_spy_svg_arrow.svg";
+        let mut params = SpyParams::new();
+        params.fill_in = true;
+        params.color_by_magnitude = false;
+        csc.write_spy_svg(full_path, Some(&params)).unwrap();
+        let contents = fs::read_to_string(full_path).unwrap();
+        // the natural ordering fills the whole matrix
+        assert!(contents.contains("5 × 5, nnz = 13, fill-in = 12</text>"));
+        assert_eq!(contents.matches(FILL_IN_COLOR).count(), 12);
+
+        // the reversed ordering yields no fill-in
+        params.permutation = Some((0..n).rev().collect());
+        csc.write_spy_svg(full_path, Some(&params)).unwrap();
+        let contents = fs::read_to_string(full_path).unwrap();
+        assert!(contents.contains("5 × 5, nnz = 13, fill-in = 0</text>"));
+        assert!(!contents.contains(FILL_IN_COLOR));
+        // the dense row is now the last one
+        assert!(contents.contains("<rect x=\"10\" y=\"520\" width=\"120\" height=\"120\" fill=\"#000000\"/>"));
+    }
+
+    #[test]
+    fn write_spy_svg_downsamples() {
+        let csc = arrow(100);
+        let full_path = "/tmp/russell_sparse/test_write_spy_svg_downsampled.svg";
+        let mut params = SpyParams::new();
+        params.max_cells = 10;
+        params.size = 100.0;
+        csc.write_spy_svg(full_path, Some(&params)).unwrap();
+        let contents = fs::read_to_string(full_path).unwrap();
+        assert!(contents.contains("100 × 100, nnz = 298 (10×10 entries per cell)</text>"));
+        // first row, first column, and diagonal cells
+        assert_eq!(contents.matches("width=\"10\" height=\"10\"").count(), 10 + 9 + 9);
+    }
+
+    #[test]
+    fn symbolic_fill_in_works() {
+        // 5-point Laplacian on a 3×3 grid (natural ordering): the fill-in is inside the band
+        let n = 9;
+        let mut entries = Vec::new();
+        for k in 0..n {
+            entries.push((k, k, 4.0));
+            if k % 3 != 2 {
+                entries.push((k + 1, k, 1.0));
+            }
+            if k + 3 < n {
+                entries.push((k + 3, k, 1.0));
+            }
+        }
+        let mut fill = symbolic_fill_in(n, &entries);
+        fill.sort();
+
+        // brute-force elimination of the dense pattern
+        let mut dense = vec![vec![false; n]; n];
+        for &(i, j, _) in &entries {
+            dense[i][j] = true;
+            dense[j][i] = true;
+        }
+        let original = dense.clone();
+        for k in 0..n {
+            for i in (k + 1)..n {
+                for j in (k + 1)..n {
+                    if dense[i][k] && dense[k][j] {
+                        dense[i][j] = true;
+                    }
+                }
+            }
+        }
+        let mut correct = Vec::new();
+        for i in 0..n {
+            for j in 0..i {
+                if dense[i][j] && !original[i][j] {
+                    correct.push((i, j));
+                }
+            }
+        }
+        assert_eq!(fill, correct);
+        assert_eq!(fill.len(), 8);
+        assert_eq!(colormap(0.0), "#440154");
+        assert_eq!(colormap(1.0), "#fde725");
+        assert_eq!(colormap(0.5), "#21918c");
+    }
+}
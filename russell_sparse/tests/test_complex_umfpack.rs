@@ -1,3 +1,5 @@
+#![cfg(feature = "with_suitesparse")]
+
 use russell_lab::*;
 use russell_sparse::prelude::*;
 
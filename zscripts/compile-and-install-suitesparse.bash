@@ -71,6 +71,7 @@ action CHOLMOD
 action UMFPACK
 action BTF
 action KLU
+action SPQR
 
 # copy include files
 sudo mkdir -p $INCDIR/